@@ -60,6 +60,7 @@ impl App {
         if self.file_line_count_cache.is_empty() {
             self.populate_file_line_count_cache();
         }
+        self.populate_blame_cache();
 
         self.line_annotations.clear();
 
@@ -121,7 +122,8 @@ impl App {
                     let Some(_muted) = visibility.render_decision(thread) else {
                         continue;
                     };
-                    let n = thread_display_lines(thread);
+                    let n =
+                        thread_display_lines(thread, self.session.thread_replies_for(&thread.id));
                     for _ in 0..n {
                         self.line_annotations
                             .push(AnnotatedLine::RemoteThreadLine { thread_idx });
@@ -308,7 +310,9 @@ impl App {
                                 &line_comments,
                                 path,
                                 &self.forge_review_threads,
+                                &self.session.thread_replies,
                                 &remote_index,
+                                &self.diagnostics,
                                 self.diff_state.viewport_width,
                                 commit_set.as_ref(),
                             );
@@ -322,7 +326,9 @@ impl App {
                                 &line_comments,
                                 path,
                                 &self.forge_review_threads,
+                                &self.session.thread_replies,
                                 &remote_index,
+                                &self.diagnostics,
                                 self.diff_state.viewport_width,
                                 commit_set.as_ref(),
                             );
@@ -469,6 +475,7 @@ impl App {
     fn push_remote_threads(
         annotations: &mut Vec<AnnotatedLine>,
         threads: &[crate::forge::remote_comments::RemoteReviewThread],
+        thread_replies: &std::collections::HashMap<String, Vec<crate::model::Comment>>,
         index: &RemoteThreadIndex,
         path: &std::path::Path,
         line: u32,
@@ -482,7 +489,11 @@ impl App {
         };
         for thread_idx in thread_indices {
             if let Some(thread) = threads.get(*thread_idx) {
-                let n = crate::forge::remote_comments::thread_display_lines(thread);
+                let replies = thread_replies
+                    .get(&thread.id)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                let n = crate::forge::remote_comments::thread_display_lines(thread, replies);
                 for _ in 0..n {
                     annotations.push(AnnotatedLine::RemoteThreadLine {
                         thread_idx: *thread_idx,
@@ -492,6 +503,21 @@ impl App {
         }
     }
 
+    fn push_diagnostics(
+        annotations: &mut Vec<AnnotatedLine>,
+        file_idx: usize,
+        diagnostics: &[crate::diagnostics::Diagnostic],
+        path: &std::path::Path,
+        line: u32,
+    ) {
+        for diagnostic_idx in crate::diagnostics::diagnostics_at(diagnostics, path, line) {
+            annotations.push(AnnotatedLine::Diagnostic {
+                file_idx,
+                diagnostic_idx,
+            });
+        }
+    }
+
     /// Build annotations for unified diff mode (one annotation per diff line)
     #[allow(clippy::too_many_arguments)]
     fn build_unified_diff_annotations(
@@ -502,7 +528,9 @@ impl App {
         line_comments: &std::collections::HashMap<u32, Vec<crate::model::Comment>>,
         path: &std::path::Path,
         remote_threads: &[crate::forge::remote_comments::RemoteReviewThread],
+        thread_replies: &std::collections::HashMap<String, Vec<crate::model::Comment>>,
         remote_index: &RemoteThreadIndex,
+        diagnostics: &[crate::diagnostics::Diagnostic],
         viewport_width: usize,
         commit_set: Option<&std::collections::HashSet<String>>,
     ) {
@@ -529,6 +557,7 @@ impl App {
                 Self::push_remote_threads(
                     annotations,
                     remote_threads,
+                    thread_replies,
                     remote_index,
                     path,
                     old_ln,
@@ -550,11 +579,13 @@ impl App {
                 Self::push_remote_threads(
                     annotations,
                     remote_threads,
+                    thread_replies,
                     remote_index,
                     path,
                     new_ln,
                     LineSide::New,
                 );
+                Self::push_diagnostics(annotations, file_idx, diagnostics, path, new_ln);
             }
         }
     }
@@ -569,7 +600,9 @@ impl App {
         line_comments: &std::collections::HashMap<u32, Vec<crate::model::Comment>>,
         path: &std::path::Path,
         remote_threads: &[crate::forge::remote_comments::RemoteReviewThread],
+        thread_replies: &std::collections::HashMap<String, Vec<crate::model::Comment>>,
         remote_index: &RemoteThreadIndex,
+        diagnostics: &[crate::diagnostics::Diagnostic],
         viewport_width: usize,
         commit_set: Option<&std::collections::HashSet<String>>,
     ) {
@@ -601,11 +634,13 @@ impl App {
                         Self::push_remote_threads(
                             annotations,
                             remote_threads,
+                            thread_replies,
                             remote_index,
                             path,
                             new_ln,
                             LineSide::New,
                         );
+                        Self::push_diagnostics(annotations, file_idx, diagnostics, path, new_ln);
                     }
 
                     i += 1
@@ -667,6 +702,7 @@ impl App {
                             Self::push_remote_threads(
                                 annotations,
                                 remote_threads,
+                                thread_replies,
                                 remote_index,
                                 path,
                                 old_ln,
@@ -686,11 +722,19 @@ impl App {
                             Self::push_remote_threads(
                                 annotations,
                                 remote_threads,
+                                thread_replies,
                                 remote_index,
                                 path,
                                 new_ln,
                                 LineSide::New,
                             );
+                            Self::push_diagnostics(
+                                annotations,
+                                file_idx,
+                                diagnostics,
+                                path,
+                                new_ln,
+                            );
                         }
                     }
 
@@ -719,11 +763,13 @@ impl App {
                         Self::push_remote_threads(
                             annotations,
                             remote_threads,
+                            thread_replies,
                             remote_index,
                             path,
                             new_ln,
                             LineSide::New,
                         );
+                        Self::push_diagnostics(annotations, file_idx, diagnostics, path, new_ln);
                     }
 
                     i += 1;
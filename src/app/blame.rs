@@ -0,0 +1,147 @@
+use crate::vcs::BlameLine;
+
+use super::*;
+
+impl App {
+    /// Turn the blame gutter on or off. Blame is read from the local VCS, so
+    /// it is not offered for pull request reviews.
+    pub fn set_show_blame(&mut self, enabled: bool) {
+        if enabled && matches!(self.diff_source, DiffSource::PullRequest(_)) {
+            self.set_warning("Blame is not available for pull request reviews");
+            return;
+        }
+        self.show_blame = enabled;
+        if !enabled {
+            self.blame_cache = None;
+            return;
+        }
+        self.populate_blame_cache();
+        if let Some(warning) = self.blame_cache.as_mut().and_then(|c| c.error.take()) {
+            self.set_warning(warning);
+        }
+    }
+
+    pub fn toggle_blame(&mut self) {
+        self.set_show_blame(!self.show_blame);
+    }
+
+    /// Blame every file in the diff that is not cached yet. The cache is
+    /// dropped whenever the snapshot being reviewed changes.
+    pub(in crate::app) fn populate_blame_cache(&mut self) {
+        if !self.show_blame {
+            return;
+        }
+        let ref_commit = self.ref_commit().map(str::to_string);
+        if self
+            .blame_cache
+            .as_ref()
+            .is_none_or(|cache| cache.ref_commit != ref_commit)
+        {
+            self.blame_cache = Some(BlameCache {
+                ref_commit: ref_commit.clone(),
+                ..BlameCache::default()
+            });
+        }
+        let Some(cache) = self.blame_cache.as_mut() else {
+            return;
+        };
+        for file in &self.diff_files {
+            if file.is_commit_message || file.status == FileStatus::Deleted || file.hunks.is_empty()
+            {
+                continue;
+            }
+            let path = file.display_path().clone();
+            if cache.files.contains_key(&path) {
+                continue;
+            }
+            let lines = match self.vcs.blame(&path, ref_commit.as_deref()) {
+                Ok(lines) => lines,
+                Err(e) => {
+                    cache
+                        .error
+                        .get_or_insert_with(|| format!("Blame failed for {}: {e}", path.display()));
+                    Vec::new()
+                }
+            };
+            cache.files.insert(path, lines);
+        }
+    }
+
+    /// Blame for line `new_lineno` of the file at `file_idx`, when the gutter
+    /// is on and the line was blamed.
+    pub fn blame_line(&self, file_idx: usize, new_lineno: u32) -> Option<&BlameLine> {
+        if !self.show_blame {
+            return None;
+        }
+        let path = self.diff_files.get(file_idx)?.display_path();
+        let lines = self.blame_cache.as_ref()?.files.get(path)?;
+        lines.get(new_lineno.checked_sub(1)? as usize)
+    }
+
+    /// Show the commit that last touched the cursor line, with the option of
+    /// opening it as a review of its own.
+    pub fn show_blame_commit_at_cursor(&mut self) {
+        if !self.show_blame {
+            self.set_warning("Blame is off · :set blame turns it on");
+            return;
+        }
+        let Some((lineno, LineSide::New)) = self.get_line_at_cursor() else {
+            self.set_warning("No blame for this line");
+            return;
+        };
+        let file_idx = self.diff_state.current_file_idx;
+        let Some(commit_id) = self
+            .blame_line(file_idx, lineno)
+            .map(|line| line.commit_id.clone())
+        else {
+            self.set_warning("No blame for this line");
+            return;
+        };
+        let Some(commit_id) = commit_id else {
+            self.set_message("Line is not committed yet");
+            return;
+        };
+        match self.vcs.get_commits_info(std::slice::from_ref(&commit_id)) {
+            Ok(mut commits) if !commits.is_empty() => {
+                self.enter_confirm_mode(ConfirmAction::OpenBlameCommit(Box::new(
+                    commits.remove(0),
+                )));
+            }
+            Ok(_) => self.set_warning(format!("Commit {commit_id} not found")),
+            Err(e) => self.set_error(format!("Failed to load commit {commit_id}: {e}")),
+        }
+    }
+
+    /// Open the commit behind the blame popup as a new review target, the
+    /// same way picking it in the commit selector would.
+    pub fn confirm_pending_blame_commit(&mut self) {
+        let pending = self.pending_confirm.take();
+        self.exit_confirm_mode();
+        let Some(ConfirmAction::OpenBlameCommit(commit)) = pending else {
+            return;
+        };
+        let short_id = commit.short_id.clone();
+        if self.diff_source == DiffSource::CommitRange(vec![commit.id.clone()]) {
+            self.set_message(format!("Already reviewing commit {short_id}"));
+            return;
+        }
+        let previous_list = std::mem::replace(&mut self.commit_list, vec![*commit]);
+        let previous_range = self.commit_selection_range.replace((0, 0));
+        let previous_cursor = std::mem::replace(&mut self.commit_list_cursor, 0);
+        let previous_source = self.diff_source.clone();
+
+        let result = self.confirm_commit_selection();
+        if self.diff_source == previous_source {
+            self.commit_list = previous_list;
+            self.commit_selection_range = previous_range;
+            self.commit_list_cursor = previous_cursor;
+        }
+        match result {
+            Ok(()) if self.diff_source != previous_source => {
+                self.set_message(format!("Reviewing commit {short_id}"));
+            }
+            Ok(()) => {}
+            Err(e) => self.set_error(format!("Failed to load commit {short_id}: {e}")),
+        }
+    }
+}
@@ -0,0 +1,386 @@
+//! Carrying local review state onto a new PR head.
+//!
+//! PR sessions are keyed by head SHA, so a force-push or new commits open a
+//! fresh session. The previous head's drafts and reviewed markers are moved
+//! into it here: files whose PR diff is unchanged keep everything verbatim,
+//! line drafts on changed files are re-anchored through the diff between the
+//! two heads, and drafts that no longer map are kept and flagged outdated.
+
+use super::*;
+use crate::forge::pr_open::OpenedPullRequest;
+use crate::forge::traits::PullRequestDetails;
+
+impl App {
+    /// Seed `opened` with the drafts of the most recent older-head session
+    /// for the same PR, if one is on disk.
+    pub(in crate::app) fn pr_session_from_previous_head(
+        opened: &OpenedPullRequest,
+        backend: &dyn ForgeBackend,
+        highlighter: &SyntaxHighlighter,
+    ) -> Result<Option<ReviewSession>> {
+        let Some((_path, previous)) =
+            crate::persistence::load_previous_head_pr_session(&opened.key)?
+        else {
+            return Ok(None);
+        };
+        let interdiff = Self::head_interdiff(&previous, opened, backend, highlighter);
+        Ok(Some(Self::reviewed_state_carried_forward(
+            &previous,
+            opened.session.clone(),
+            &opened.diff_files,
+            interdiff.as_deref(),
+        )))
+    }
+
+    /// Diff between the previous session's head and the opened head, used to
+    /// re-anchor line drafts. Only fetched when some changed file has state
+    /// worth moving. Skipped when the old head is no longer part of the PR
+    /// (force-push): forges compare from the merge base, whose line numbers
+    /// would not match the old head's.
+    pub(in crate::app) fn head_interdiff(
+        previous: &ReviewSession,
+        opened: &OpenedPullRequest,
+        backend: &dyn ForgeBackend,
+        highlighter: &SyntaxHighlighter,
+    ) -> Option<Vec<DiffFile>> {
+        let old_head = previous.pr_session_key.as_ref()?.head_sha.as_str();
+        if old_head == opened.details.head_sha
+            || !Self::has_state_on_changed_files(previous, &opened.diff_files)
+        {
+            return None;
+        }
+        if !opened.commits.is_empty() && !opened.commits.iter().any(|c| c.oid == old_head) {
+            return None;
+        }
+        Self::fetch_head_interdiff(backend, &opened.details, old_head, highlighter)
+    }
+
+    fn fetch_head_interdiff(
+        backend: &dyn ForgeBackend,
+        details: &PullRequestDetails,
+        old_head: &str,
+        highlighter: &SyntaxHighlighter,
+    ) -> Option<Vec<DiffFile>> {
+        let patches = backend
+            .get_pull_request_commit_range_diff(details, old_head, &details.head_sha)
+            .ok()?;
+        match crate::vcs::diff_parser::parse_file_patches(patches, highlighter) {
+            Ok(files) => Some(files),
+            // Nothing changed between the heads: every line maps to itself.
+            Err(TuicrError::NoChanges) => Some(Vec::new()),
+            Err(_) => None,
+        }
+    }
+
+    fn has_state_on_changed_files(previous: &ReviewSession, diff_files: &[DiffFile]) -> bool {
+        previous.files.iter().any(|(path, review)| {
+            let has_state = review.reviewed
+                || review
+                    .line_comments
+                    .values()
+                    .flatten()
+                    .any(|comment| !comment.is_locked());
+            let unchanged = diff_files.iter().any(|file| {
+                file.display_path() == path && review.content_hash == Some(file.content_hash)
+            });
+            has_state && !unchanged
+        })
+    }
+
+    /// Move `previous`'s local state into `next`. `interdiff` is the diff
+    /// between the two heads, or `None` when it is unavailable; without it
+    /// only files whose PR diff is unchanged keep their line anchors.
+    pub(in crate::app) fn reviewed_state_carried_forward(
+        previous: &ReviewSession,
+        next: ReviewSession,
+        diff_files: &[DiffFile],
+        interdiff: Option<&[DiffFile]>,
+    ) -> ReviewSession {
+        let file_by_path: HashMap<_, _> = diff_files
+            .iter()
+            .map(|file| (file.display_path().clone(), file))
+            .collect();
+        let files: HashMap<_, _> = next
+            .files
+            .into_iter()
+            .map(|(path, review)| {
+                Self::file_review_carried_forward(path, review, previous, &file_by_path, interdiff)
+            })
+            .collect();
+        let mut review_comments: Vec<_> = previous
+            .review_comments
+            .iter()
+            .filter(|comment| !comment.is_locked())
+            .cloned()
+            .collect();
+
+        // Drafts on files that left the PR have nothing to anchor to; keep
+        // them as outdated review-level comments instead of dropping them.
+        let mut removed: Vec<_> = previous
+            .files
+            .iter()
+            .filter(|(path, _)| !files.contains_key(*path))
+            .collect();
+        removed.sort_by_key(|(path, _)| *path);
+        for (path, previous_review) in removed {
+            review_comments.extend(Self::removed_file_drafts(path, previous_review));
+        }
+
+        // Remote threads outlive head changes, so queued replies stay valid.
+        let thread_replies = previous
+            .thread_replies
+            .iter()
+            .map(|(id, replies)| {
+                let drafts: Vec<_> = replies.iter().filter(|c| !c.is_locked()).cloned().collect();
+                (id.clone(), drafts)
+            })
+            .filter(|(_, drafts)| !drafts.is_empty())
+            .collect();
+
+        ReviewSession {
+            files,
+            review_comments,
+            thread_replies,
+            ..next
+        }
+    }
+
+    fn file_review_carried_forward(
+        path: PathBuf,
+        review: FileReview,
+        previous: &ReviewSession,
+        file_by_path: &HashMap<PathBuf, &DiffFile>,
+        interdiff: Option<&[DiffFile]>,
+    ) -> (PathBuf, FileReview) {
+        let Some(file) = file_by_path.get(&path) else {
+            return (path, review);
+        };
+        let Some(previous_review) = previous.files.get(&path) else {
+            return (path, review);
+        };
+
+        let unchanged_file = previous_review.content_hash == Some(file.content_hash);
+        let interdiff_file = interdiff.map(|files| interdiff_file_for(files, &path));
+        // A file the new commits never touched still reads the same, even if
+        // its PR diff moved because the base did.
+        let untouched_between_heads = matches!(interdiff_file, Some(None));
+        let valid_hunks: HashSet<_> = file.hunk_review_keys().into_iter().collect();
+        let reviewed_hunks = previous_review
+            .reviewed_hunks
+            .iter()
+            .filter(|key| valid_hunks.contains(*key))
+            .cloned()
+            .collect();
+        let mut file_comments: Vec<_> = previous_review
+            .file_comments
+            .iter()
+            .filter(|comment| !comment.is_locked())
+            .cloned()
+            .collect();
+        let line_comments = if unchanged_file {
+            Self::line_draft_comments_carried_forward(previous_review)
+        } else {
+            let (anchored, outdated) =
+                Self::line_draft_comments_reanchored(previous_review, file, interdiff_file);
+            file_comments.extend(outdated);
+            anchored
+        };
+
+        (
+            path,
+            FileReview {
+                reviewed: previous_review.reviewed && (unchanged_file || untouched_between_heads),
+                reviewed_hunks,
+                file_comments,
+                line_comments,
+                ..review
+            },
+        )
+    }
+
+    fn line_draft_comments_carried_forward(
+        previous_review: &FileReview,
+    ) -> HashMap<u32, Vec<Comment>> {
+        previous_review
+            .line_comments
+            .iter()
+            .filter_map(|(line, comments)| {
+                let drafts: Vec<_> = comments
+                    .iter()
+                    .filter(|comment| !comment.is_locked())
+                    .cloned()
+                    .collect();
+                (!drafts.is_empty()).then_some((*line, drafts))
+            })
+            .collect()
+    }
+
+    /// Split a changed file's line drafts into those re-anchored on the new
+    /// head (keyed by their new line) and those that no longer map, which
+    /// come back flagged outdated for use as file-level comments.
+    fn line_draft_comments_reanchored(
+        previous_review: &FileReview,
+        file: &DiffFile,
+        interdiff_file: Option<Option<&DiffFile>>,
+    ) -> (HashMap<u32, Vec<Comment>>, Vec<Comment>) {
+        let mut lines: Vec<_> = previous_review.line_comments.iter().collect();
+        lines.sort_by_key(|(line, _)| **line);
+
+        let mut anchored: HashMap<u32, Vec<Comment>> = HashMap::new();
+        let mut outdated = Vec::new();
+        for (&line, comments) in lines {
+            for comment in comments.iter().filter(|comment| !comment.is_locked()) {
+                match reanchored_comment(comment, line, file, interdiff_file) {
+                    Some((new_line, comment)) => {
+                        anchored.entry(new_line).or_default().push(comment)
+                    }
+                    None => outdated.push(outdated_comment(comment.clone(), line)),
+                }
+            }
+        }
+        (anchored, outdated)
+    }
+
+    fn removed_file_drafts(path: &Path, previous_review: &FileReview) -> Vec<Comment> {
+        let file_comments = previous_review
+            .file_comments
+            .iter()
+            .filter(|comment| !comment.is_locked())
+            .map(|comment| (comment, None));
+        let mut lines: Vec<_> = previous_review.line_comments.iter().collect();
+        lines.sort_by_key(|(line, _)| **line);
+        let line_comments = lines.into_iter().flat_map(|(line, comments)| {
+            comments
+                .iter()
+                .filter(|comment| !comment.is_locked())
+                .map(move |comment| (comment, Some(*line)))
+        });
+
+        file_comments
+            .chain(line_comments)
+            .map(|(comment, line)| {
+                let location = match line {
+                    Some(line) => format!("{}:{line}", path.display()),
+                    None => path.display().to_string(),
+                };
+                let mut comment = comment.clone();
+                comment.content = format!("`{location}`: {}", comment.content);
+                comment.line_context = None;
+                comment.side = None;
+                comment.line_range = None;
+                comment.outdated = true;
+                comment
+            })
+            .collect()
+    }
+}
+
+/// The interdiff entry for `path`, matching either side of a rename.
+fn interdiff_file_for<'a>(files: &'a [DiffFile], path: &Path) -> Option<&'a DiffFile> {
+    files.iter().find(|file| {
+        file.old_path.as_deref() == Some(path) || file.new_path.as_deref() == Some(path)
+    })
+}
+
+fn reanchored_comment(
+    comment: &Comment,
+    line: u32,
+    file: &DiffFile,
+    interdiff_file: Option<Option<&DiffFile>>,
+) -> Option<(u32, Comment)> {
+    let side = comment.side.unwrap_or_default();
+    let map = |line: u32| reanchored_line(line, side, file, interdiff_file);
+    let mut comment = comment.clone();
+    let new_line = match comment.line_range {
+        Some(range) => {
+            let (start, end) = (map(range.start)?, map(range.end)?);
+            if end - start != range.end - range.start {
+                // Lines were inserted or removed inside the selection.
+                return None;
+            }
+            comment.line_range = Some(LineRange::new(start, end));
+            end
+        }
+        None => map(line)?,
+    };
+    if let Some(context) = comment.line_context.as_mut() {
+        let current = line_on_side(file, new_line, side)?;
+        if current.content != context.content {
+            return None;
+        }
+        match side {
+            LineSide::New => context.new_line = Some(new_line),
+            LineSide::Old => context.old_line = Some(new_line),
+        }
+    }
+    Some((new_line, comment))
+}
+
+/// Where an old-head line lives on the new head, or `None` when it can no
+/// longer be located. Old-side lines belong to the base, so they only carry
+/// over when the new PR diff still deletes that line.
+fn reanchored_line(
+    line: u32,
+    side: LineSide,
+    file: &DiffFile,
+    interdiff_file: Option<Option<&DiffFile>>,
+) -> Option<u32> {
+    match side {
+        LineSide::Old => line_on_side(file, line, LineSide::Old).map(|_| line),
+        LineSide::New => match interdiff_file? {
+            None => Some(line),
+            Some(interdiff) => map_line_through_diff(interdiff, line),
+        },
+    }
+}
+
+fn line_on_side(file: &DiffFile, line: u32, side: LineSide) -> Option<&DiffLine> {
+    file.hunks
+        .iter()
+        .flat_map(|hunk| &hunk.lines)
+        .find(|diff_line| match side {
+            LineSide::Old => {
+                diff_line.origin == LineOrigin::Deletion && diff_line.old_lineno == Some(line)
+            }
+            LineSide::New => {
+                diff_line.origin != LineOrigin::Deletion && diff_line.new_lineno == Some(line)
+            }
+        })
+}
+
+/// Map a line of the diff's old side to its new-side line number. Lines
+/// outside every hunk shift by the hunks before them; lines the diff deletes
+/// have no counterpart.
+pub(in crate::app) fn map_line_through_diff(diff: &DiffFile, line: u32) -> Option<u32> {
+    let mut delta = 0i64;
+    for hunk in &diff.hunks {
+        // A pure insertion (`-N,0`) lands after old line N.
+        let before_hunk = if hunk.old_count == 0 {
+            line <= hunk.old_start
+        } else {
+            line < hunk.old_start
+        };
+        if before_hunk {
+            break;
+        }
+        if let Some(diff_line) = hunk.lines.iter().find(|diff_line| {
+            diff_line.origin != LineOrigin::Addition && diff_line.old_lineno == Some(line)
+        }) {
+            return match diff_line.origin {
+                LineOrigin::Context => diff_line.new_lineno,
+                _ => None,
+            };
+        }
+        delta += i64::from(hunk.new_count) - i64::from(hunk.old_count);
+    }
+    u32::try_from(i64::from(line) + delta).ok()
+}
+
+fn outdated_comment(mut comment: Comment, line: u32) -> Comment {
+    // Keep the old position visible so the reviewer can find the spot again.
+    if comment.line_range.is_none() {
+        comment.line_range = Some(LineRange::single(line));
+    }
+    comment.outdated = true;
+    comment
+}
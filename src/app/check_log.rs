@@ -0,0 +1,224 @@
+use super::*;
+
+use crate::forge::check_log::{clean_log, file_references, is_failing};
+
+impl App {
+    /// `:check-log`: fetch the log of the failing check under the cursor in
+    /// the PR info panel, or of the first failing check otherwise.
+    pub fn open_check_log(&mut self) {
+        let DiffSource::PullRequest(pr) = &self.diff_source else {
+            self.set_warning(":check-log only applies in PR mode");
+            return;
+        };
+        let Some(info) = self.pr_info.as_ref() else {
+            self.set_warning("PR info has not loaded yet");
+            return;
+        };
+        if info.checks.is_empty() {
+            self.set_warning("No CI checks loaded (enable show_pr_checks)");
+            return;
+        }
+        let check = match crate::ui::pr_info_panel::check_at_cursor(self) {
+            Some(check) if is_failing(check) => check.clone(),
+            Some(check) => {
+                self.set_warning(format!("{} has not failed", check.name));
+                return;
+            }
+            None => match info.checks.iter().find(|check| is_failing(check)) {
+                Some(check) => check.clone(),
+                None => {
+                    self.set_message("No failing checks");
+                    return;
+                }
+            },
+        };
+        if self.check_log_rx.is_some() {
+            self.set_message("A CI log is already loading");
+            return;
+        }
+
+        let details = pr.details();
+        let local_checkout = self
+            .forge_backend
+            .as_deref()
+            .and_then(|backend| backend.local_checkout_path());
+        let show_pr_checks = self.show_pr_checks;
+        let show_pr_comments = self.show_pr_comments;
+        let forge_config = self.forge_config.clone();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.check_log_rx = Some(rx);
+        self.set_message(format!("Fetching the log for {}...", check.name));
+        std::thread::spawn(move || {
+            let backend = create_forge_backend(
+                &details.repository,
+                local_checkout,
+                show_pr_checks,
+                show_pr_comments,
+                &forge_config,
+            );
+            let result = backend
+                .fetch_check_log(&details, &check)
+                .map_err(|e| e.to_string());
+            let _ = tx.send(CheckLogEvent::Done {
+                repository: details.repository,
+                pr_number: details.number,
+                check_name: check.name,
+                result,
+            });
+        });
+    }
+
+    /// Pump a finished `:check-log` fetch. The view only opens from Normal
+    /// mode, so a slow fetch can't pull the user out of a comment.
+    pub fn poll_check_log_events(&mut self) {
+        let Some(rx) = self.check_log_rx.as_ref() else {
+            return;
+        };
+        let event = match rx.try_recv() {
+            Ok(e) => e,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                self.check_log_rx = None;
+                return;
+            }
+        };
+        self.check_log_rx = None;
+
+        let CheckLogEvent::Done {
+            repository,
+            pr_number,
+            check_name,
+            result,
+        } = event;
+        let still_relevant = matches!(
+            &self.diff_source,
+            DiffSource::PullRequest(pr)
+                if pr.key.repository == repository && pr.key.number == pr_number
+        );
+        if !still_relevant {
+            return;
+        }
+        match result {
+            Ok(raw) if self.input_mode == InputMode::Normal => {
+                self.message = None;
+                self.show_check_log(check_name, &raw);
+            }
+            Ok(_) => {
+                self.set_message(format!(
+                    "Log for {check_name} arrived while busy; run :check-log again"
+                ));
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to fetch the log for {check_name}: {e}"));
+            }
+        }
+    }
+
+    /// Open the check-log view on `raw`, starting at the bottom where the
+    /// failure usually is.
+    pub(crate) fn show_check_log(&mut self, check_name: String, raw: &str) {
+        let mut lines = clean_log(raw);
+        if lines.is_empty() {
+            lines.push("(the log is empty)".to_string());
+        }
+        let targets = lines
+            .iter()
+            .map(|line| self.check_log_target(line))
+            .collect();
+        let selected = lines.len() - 1;
+        self.check_log = Some(CheckLogState {
+            check_name,
+            lines,
+            targets,
+            selected,
+            scroll_offset: selected,
+            ..CheckLogState::default()
+        });
+        self.overlay_return_mode = self.input_mode;
+        self.input_mode = InputMode::CheckLog;
+    }
+
+    /// First reference in `line` to a file of the diff. Logs usually print
+    /// paths from the CI checkout root, so a reference matches a diff path
+    /// it equals or ends with.
+    fn check_log_target(&self, line: &str) -> Option<(PathBuf, u32)> {
+        file_references(line).into_iter().find_map(|reference| {
+            self.diff_files
+                .iter()
+                .map(|file| file.display_path())
+                .filter(|path| {
+                    let path = path.to_string_lossy();
+                    reference.path == path
+                        || reference
+                            .path
+                            .strip_suffix(path.as_ref())
+                            .is_some_and(|prefix| prefix.ends_with('/'))
+                })
+                .max_by_key(|path| path.as_os_str().len())
+                .map(|path| (path.clone(), reference.line))
+        })
+    }
+
+    pub fn close_check_log(&mut self) {
+        self.input_mode = self.overlay_return_mode;
+    }
+
+    pub fn check_log_select_down(&mut self, lines: usize) {
+        if let Some(log) = self.check_log.as_mut() {
+            log.selected = log
+                .selected
+                .saturating_add(lines)
+                .min(log.lines.len().saturating_sub(1));
+            log.keep_selection_visible();
+        }
+    }
+
+    pub fn check_log_select_up(&mut self, lines: usize) {
+        if let Some(log) = self.check_log.as_mut() {
+            log.selected = log.selected.saturating_sub(lines);
+            log.keep_selection_visible();
+        }
+    }
+
+    pub fn check_log_page(&self) -> usize {
+        self.check_log
+            .as_ref()
+            .map_or(1, |log| log.viewport_height.max(1))
+    }
+
+    /// Enter: close the view and jump to the diff line the selected log
+    /// line refers to.
+    pub fn jump_to_check_log_target(&mut self) {
+        let Some(log) = self.check_log.as_ref() else {
+            return;
+        };
+        let Some((path, line)) = log.targets.get(log.selected).cloned().flatten() else {
+            self.set_message("No file from the diff on this line");
+            return;
+        };
+        let Some(file_idx) = self
+            .diff_files
+            .iter()
+            .position(|file| file.display_path() == &path)
+        else {
+            self.set_warning("That file is no longer in the diff");
+            return;
+        };
+        self.close_check_log();
+        self.focused_panel = FocusedPanel::Diff;
+        self.jump_to_file(file_idx);
+        self.go_to_source_line(line, LineSide::New);
+    }
+}
+
+impl CheckLogState {
+    pub(crate) fn keep_selection_visible(&mut self) {
+        let height = self.viewport_height.max(1);
+        if self.selected < self.scroll_offset {
+            self.scroll_offset = self.selected;
+        } else if self.selected >= self.scroll_offset + height {
+            self.scroll_offset = self.selected + 1 - height;
+        }
+    }
+}
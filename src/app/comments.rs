@@ -519,10 +519,9 @@ impl App {
         self.set_message(format!("Comment {}/{}", target_idx + 1, items.len()));
     }
 
-    /// True when the cursor sits on a local comment whose lifecycle state
-    /// has been pushed/submitted to the forge. Such comments are locked from
-    /// edit/delete in tuicr to prevent the local state from drifting from
-    /// what GitHub now stores.
+    /// True when the cursor sits on a local comment tuicr can no longer
+    /// change (see [`Comment::is_read_only`]). Editing it locally would only
+    /// drift from what the forge now stores.
     pub fn cursor_on_locked_comment(&self) -> bool {
         let Some(location) = self.find_comment_at_cursor() else {
             return false;
@@ -532,13 +531,13 @@ impl App {
                 .session
                 .review_comments
                 .get(index)
-                .is_some_and(|c| c.is_locked()),
+                .is_some_and(|c| c.is_read_only()),
             CommentLocation::File { path, index } => self
                 .session
                 .files
                 .get(&path)
                 .and_then(|review| review.file_comments.get(index))
-                .is_some_and(|c| c.is_locked()),
+                .is_some_and(|c| c.is_read_only()),
             CommentLocation::Line {
                 path,
                 line,
@@ -561,7 +560,7 @@ impl App {
                     }
                     None
                 })
-                .is_some_and(|c| c.is_locked()),
+                .is_some_and(|c| c.is_read_only()),
         }
     }
 
@@ -686,7 +685,8 @@ impl App {
             }
             Some(CommentLocation::File { path, index }) => {
                 if let Some(review) = self.session.get_file_mut(&path) {
-                    review.file_comments.remove(index);
+                    let removed = review.file_comments.remove(index);
+                    self.push_pending_comment_delete(&removed);
                     self.dirty = true;
                     self.set_message("Comment deleted");
                     self.rebuild_annotations();
@@ -708,10 +708,11 @@ impl App {
                     if index < comments.len() {
                         let comment_side = comments[index].side.unwrap_or(LineSide::New);
                         if comment_side == side {
-                            comments.remove(index);
+                            let removed = comments.remove(index);
                             if comments.is_empty() {
                                 review.line_comments.remove(&line);
                             }
+                            self.push_pending_comment_delete(&removed);
                             self.dirty = true;
                             self.set_message(format!("Comment on line {line} deleted"));
                             self.rebuild_annotations();
@@ -954,6 +955,7 @@ impl App {
         self.comment_vim_pending = CommentVimPending::None;
         self.comment_is_review_level = false;
         self.editing_comment_id = None;
+        self.comment_reply_thread_id = None;
         self.comment_line_range = None;
     }
 
@@ -964,9 +966,14 @@ impl App {
         }
 
         let content = self.comment_buffer.trim().to_string();
+        if let Some(thread_id) = self.comment_reply_thread_id.clone() {
+            self.save_thread_reply(thread_id, content);
+            return;
+        }
 
         let mut message = "Error: Could not save comment".to_string();
         let mut autosave_error = None;
+        let mut added = None;
 
         // Check if we're editing an existing comment
         if let Some(editing_id) = &self.editing_comment_id {
@@ -978,6 +985,7 @@ impl App {
             {
                 comment.content = content.clone();
                 comment.comment_type = self.comment_type.clone();
+                comment.sent_to.clear();
                 message = "Review comment updated".to_string();
             } else if let Some(path) = self.current_file_path().cloned()
                 && let Some(review) = self.session.get_file_mut(&path)
@@ -989,6 +997,7 @@ impl App {
                 {
                     comment.content = content.clone();
                     comment.comment_type = self.comment_type.clone();
+                    comment.sent_to.clear();
                     message = "Comment updated".to_string();
                 } else {
                     // If not found in file comments, search in line comments
@@ -1003,6 +1012,7 @@ impl App {
                     if let Some(comment) = found_comment {
                         comment.content = content.clone();
                         comment.comment_type = self.comment_type.clone();
+                        comment.sent_to.clear();
                         message = if let Some((line, _)) = self.comment_line {
                             format!("Comment on line {line} updated")
                         } else {
@@ -1022,7 +1032,10 @@ impl App {
                 commit_id: None,
             };
             message = match add_comment_to_session(&mut self.session, request) {
-                Ok(_) => "Review comment added".to_string(),
+                Ok(comment) => {
+                    added = Some((CommentTarget::Review, comment));
+                    "Review comment added".to_string()
+                }
                 Err(e) => format!("Error: Could not save comment: {e}"),
             };
         } else if let Some(path) = self.current_file_path().cloned() {
@@ -1051,24 +1064,33 @@ impl App {
             };
 
             let request = AddCommentRequest {
-                target,
+                target: target.clone(),
                 content,
                 comment_type: self.comment_type.clone(),
                 author: self.username.clone(),
                 commit_id: self.commit_id_for_new_comment(),
             };
             message = match add_comment_to_session(&mut self.session, request) {
-                Ok(_) => success_message,
+                Ok(comment) => {
+                    added = Some((target, comment));
+                    success_message
+                }
                 Err(e) => format!("Error: Could not save comment: {e}"),
             };
         }
 
         if !message.starts_with("Error:") {
             self.dirty = true;
+            if let Some(editing_id) = self.editing_comment_id.clone() {
+                self.push_pending_comment_edit(&editing_id);
+            }
             if let Err(e) = self.save_current_session_merging_external() {
                 autosave_error = Some(format!("{message}; autosave failed: {e}"));
             }
         }
+        if let Some((target, comment)) = added {
+            self.publish_comment_added(&target, &comment);
+        }
         if let Some(error) = autosave_error {
             self.set_error(error);
         } else {
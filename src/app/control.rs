@@ -0,0 +1,209 @@
+use super::*;
+
+use serde_json::{Value, json};
+
+use crate::control::{
+    ControlEvent, ControlHub, ControlRequest, INVALID_PARAMS, METHOD_NOT_FOUND, REQUEST_FAILED,
+};
+use crate::review_cli::{CommentOutput, add_request_from_json, resolve_cli_author};
+
+/// Requests answered per tick, so a flood of them cannot starve the keyboard.
+const CONTROL_REQUESTS_PER_TICK: usize = 32;
+
+type ControlResult = std::result::Result<Value, (i64, String)>;
+
+impl App {
+    /// Listen on this process's control socket and record it in the
+    /// active-session entry.
+    pub fn start_control_socket(&mut self) -> Result<PathBuf> {
+        let path = crate::control::default_socket_path()?;
+        #[cfg(unix)]
+        {
+            self.control = Some(ControlHub::bind(&path)?);
+            Ok(path)
+        }
+        #[cfg(not(unix))]
+        {
+            Err(TuicrError::UnsupportedOperation(format!(
+                "control sockets need Unix domain sockets ({})",
+                path.display()
+            )))
+        }
+    }
+
+    /// Answer queued control requests. Returns `true` when one ran, so the
+    /// main loop redraws.
+    pub fn poll_control_requests(&mut self) -> bool {
+        let mut handled = false;
+        for _ in 0..CONTROL_REQUESTS_PER_TICK {
+            let Some(request) = self.control.as_ref().and_then(ControlHub::try_recv) else {
+                break;
+            };
+            let result = self.run_control_request(&request);
+            request.respond(result);
+            handled = true;
+        }
+        handled
+    }
+
+    /// Tell subscribed control clients about `event`.
+    pub(crate) fn publish_control_event(&self, event: ControlEvent) {
+        if let Some(control) = &self.control {
+            control.publish(&event);
+        }
+    }
+
+    fn run_control_request(&mut self, request: &ControlRequest) -> ControlResult {
+        let params = &request.params;
+        match request.method.as_str() {
+            "state" => Ok(self.control_state()),
+            "message" => self.control_message(params),
+            "goto" => {
+                self.require_normal_mode()?;
+                self.control_goto(params)
+            }
+            "open" => {
+                self.require_normal_mode()?;
+                self.control_open(params)
+            }
+            "comment" => {
+                self.require_normal_mode()?;
+                self.control_comment(params)
+            }
+            method => Err((METHOD_NOT_FOUND, format!("unknown method {method}"))),
+        }
+    }
+
+    /// Requests that move the cursor or change the session wait for the
+    /// user to leave whatever they are typing or reading.
+    fn require_normal_mode(&self) -> std::result::Result<(), (i64, String)> {
+        if self.input_mode == InputMode::Normal {
+            return Ok(());
+        }
+        Err((
+            REQUEST_FAILED,
+            format!("tuicr is busy in {:?} mode", self.input_mode),
+        ))
+    }
+
+    fn control_state(&self) -> Value {
+        let (line, side) = match self.get_line_at_cursor() {
+            Some((line, side)) => (Some(line), Some(side)),
+            None => (None, None),
+        };
+        json!({
+            "slug": self.session_slug().map(|slug| slug.to_string()),
+            "mode": format!("{:?}", self.input_mode),
+            "file": self.current_file_path().map(|path| path.display().to_string()),
+            "line": line,
+            "side": side.map(|side| match side {
+                LineSide::Old => "old",
+                LineSide::New => "new",
+            }),
+            "single_file_view": self.is_single_file_view,
+            "files": self.diff_files.len(),
+            "reviewed": self.reviewed_count(),
+        })
+    }
+
+    /// `message {"text", "level"}`: flash `text` in the status bar.
+    fn control_message(&mut self, params: &Value) -> ControlResult {
+        let text = required_str(params, "text")?.to_string();
+        match params["level"].as_str().unwrap_or("info") {
+            "info" => self.set_message(text),
+            "warning" => self.set_warning(text),
+            "error" => self.set_error(text),
+            other => {
+                return Err((
+                    INVALID_PARAMS,
+                    format!("unknown level {other}; expected info, warning or error"),
+                ));
+            }
+        }
+        Ok(json!({}))
+    }
+
+    /// `goto {"file", "line", "side"}`: move the cursor to a diff line, or
+    /// to the file header without `line`.
+    fn control_goto(&mut self, params: &Value) -> ControlResult {
+        let file_idx = self.control_file_idx(params)?;
+        let side = match params["side"].as_str().unwrap_or("new") {
+            "new" => LineSide::New,
+            "old" => LineSide::Old,
+            other => {
+                return Err((
+                    INVALID_PARAMS,
+                    format!("unknown side {other}; expected old or new"),
+                ));
+            }
+        };
+        self.focused_panel = FocusedPanel::Diff;
+        self.jump_to_file(file_idx);
+        if let Some(line) = params["line"].as_u64() {
+            let line = u32::try_from(line)
+                .ok()
+                .filter(|line| *line > 0)
+                .ok_or((INVALID_PARAMS, "line must be a positive line number".into()))?;
+            self.go_to_source_line(line, side);
+        }
+        Ok(self.control_state())
+    }
+
+    /// `open {"file"}`: show one file in single-file view.
+    fn control_open(&mut self, params: &Value) -> ControlResult {
+        let file_idx = self.control_file_idx(params)?;
+        self.focused_panel = FocusedPanel::Diff;
+        self.diff_state.current_file_idx = file_idx;
+        if !self.is_single_file_view {
+            self.toggle_single_file_view();
+        }
+        self.jump_to_file(file_idx);
+        Ok(self.control_state())
+    }
+
+    /// `comment {...}`: add a local draft comment, shown at once. Takes the
+    /// same payload as `tuicr review add --input`.
+    fn control_comment(&mut self, params: &Value) -> ControlResult {
+        let parts = add_request_from_json(&params.to_string())
+            .map_err(|e| (INVALID_PARAMS, e.to_string()))?;
+        let request = AddCommentRequest {
+            target: parts.target.clone(),
+            content: parts.content,
+            comment_type: CommentType::from_id(&parts.comment_type),
+            author: resolve_cli_author(parts.username),
+            commit_id: None,
+        };
+        let comment = add_comment_to_session(&mut self.session, request)
+            .map_err(|e| (INVALID_PARAMS, e.to_string()))?;
+        self.dirty = true;
+        if let Err(e) = self.save_current_session_merging_external() {
+            self.set_warning(format!("Comment added; autosave failed: {e}"));
+        }
+        self.rebuild_annotations();
+        self.publish_comment_added(&parts.target, &comment);
+        Ok(comment_json(&parts.target, &comment))
+    }
+
+    /// Publish `comment_added` with the `tuicr review` comment shape.
+    pub(crate) fn publish_comment_added(&self, target: &CommentTarget, comment: &Comment) {
+        self.publish_control_event(ControlEvent::CommentAdded(comment_json(target, comment)));
+    }
+
+    fn control_file_idx(&self, params: &Value) -> std::result::Result<usize, (i64, String)> {
+        let file = required_str(params, "file")?;
+        self.diff_files
+            .iter()
+            .position(|diff_file| diff_file.display_path() == Path::new(file))
+            .ok_or_else(|| (REQUEST_FAILED, format!("{file} is not in the diff")))
+    }
+}
+
+fn comment_json(target: &CommentTarget, comment: &Comment) -> Value {
+    serde_json::to_value(CommentOutput::from_target(target, comment)).unwrap_or(Value::Null)
+}
+
+fn required_str<'a>(params: &'a Value, name: &str) -> std::result::Result<&'a str, (i64, String)> {
+    params[name]
+        .as_str()
+        .ok_or_else(|| (INVALID_PARAMS, format!("{name} is required")))
+}
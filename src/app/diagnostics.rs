@@ -0,0 +1,202 @@
+use super::*;
+
+use crate::diagnostics::{Diagnostic, diagnostics_at, parse_diagnostics};
+
+impl App {
+    /// `:diagnostics <path>` and `--diagnostics`: load saved tool output.
+    pub fn load_diagnostics_file(&mut self, path: &Path) {
+        match std::fs::read_to_string(path) {
+            Ok(output) => {
+                let diagnostics = parse_diagnostics(&output, &self.vcs_info.root_path);
+                self.show_diagnostics(diagnostics, &path.display().to_string());
+            }
+            Err(e) => self.set_error(format!("Failed to read {}: {e}", path.display())),
+        }
+    }
+
+    /// Bare `:diagnostics`: run `diagnostics_command` on a background thread.
+    /// A non-zero exit is expected from linters that found something, so it
+    /// is only an error when the output held no diagnostics at all.
+    pub fn run_diagnostics_command(&mut self) {
+        let Some(command) = self.diagnostics_command.clone() else {
+            self.set_warning("No diagnostics_command configured; use :diagnostics <file>");
+            return;
+        };
+        if self.diagnostics_rx.is_some() {
+            self.set_message("Diagnostics are already running");
+            return;
+        }
+        let root = self.vcs_info.root_path.clone();
+        // PR mode swaps in a synthetic root with no checkout behind it.
+        let cwd = Some(root.clone()).filter(|root| root.is_dir());
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.diagnostics_rx = Some(rx);
+        self.set_message(format!("Running {command}..."));
+        std::thread::spawn(move || {
+            let (shell, flag) = if cfg!(windows) {
+                ("cmd", "/C")
+            } else {
+                ("sh", "-c")
+            };
+            let mut child = std::process::Command::new(shell);
+            child
+                .args([flag, command.as_str()])
+                .stdin(std::process::Stdio::null());
+            if let Some(cwd) = &cwd {
+                child.current_dir(cwd);
+            }
+            let result = match child.output() {
+                Ok(output) => {
+                    let stdout = String::from_utf8_lossy(&output.stdout);
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    let diagnostics = parse_diagnostics(&format!("{stdout}\n{stderr}"), &root);
+                    match output.status.code() {
+                        Some(code) if diagnostics.is_empty() && code != 0 => {
+                            match stderr.lines().find(|line| !line.trim().is_empty()) {
+                                Some(line) => {
+                                    Err(format!("exited with status {code}: {}", line.trim()))
+                                }
+                                None => Err(format!("exited with status {code}")),
+                            }
+                        }
+                        _ => Ok(diagnostics),
+                    }
+                }
+                Err(e) => Err(format!("could not run `{command}`: {e}")),
+            };
+            let _ = tx.send(DiagnosticsEvent::Done { command, result });
+        });
+    }
+
+    pub fn poll_diagnostics_events(&mut self) {
+        let Some(rx) = self.diagnostics_rx.as_ref() else {
+            return;
+        };
+        let event = match rx.try_recv() {
+            Ok(e) => e,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                self.diagnostics_rx = None;
+                return;
+            }
+        };
+        self.diagnostics_rx = None;
+
+        let DiagnosticsEvent::Done { command, result } = event;
+        match result {
+            Ok(diagnostics) => self.show_diagnostics(diagnostics, &command),
+            Err(e) => self.set_error(format!("Diagnostics failed: {e}")),
+        }
+    }
+
+    /// Replace the overlay, keeping only diagnostics on lines the diff shows
+    /// on the new side.
+    pub fn set_diagnostics(&mut self, diagnostics: Vec<Diagnostic>) {
+        let changed_lines: HashSet<(&Path, u32)> = self
+            .diff_files
+            .iter()
+            .flat_map(|file| {
+                let path = file.display_path().as_path();
+                file.hunks
+                    .iter()
+                    .flat_map(|hunk| &hunk.lines)
+                    .filter_map(move |line| Some((path, line.new_lineno?)))
+            })
+            .collect();
+        let mut kept: Vec<_> = diagnostics
+            .into_iter()
+            .filter(|d| changed_lines.contains(&(d.path.as_path(), d.line)))
+            .collect();
+        kept.sort_by(|a, b| (&a.path, a.line).cmp(&(&b.path, b.line)));
+        self.diagnostics = kept;
+        self.rebuild_annotations();
+    }
+
+    pub fn clear_diagnostics(&mut self) {
+        self.diagnostics.clear();
+        self.rebuild_annotations();
+        self.set_message("Diagnostics cleared");
+    }
+
+    fn show_diagnostics(&mut self, diagnostics: Vec<Diagnostic>, source: &str) {
+        let total = diagnostics.len();
+        self.set_diagnostics(diagnostics);
+        let shown = self.diagnostics.len();
+        let noun = if shown == 1 {
+            "diagnostic"
+        } else {
+            "diagnostics"
+        };
+        let message = if total == 0 {
+            format!("No diagnostics from {source}")
+        } else if shown == total {
+            format!("{shown} {noun} on changed lines from {source}")
+        } else {
+            let hidden = total - shown;
+            format!("{shown} {noun} on changed lines from {source} ({hidden} elsewhere hidden)")
+        };
+        self.set_message(message);
+    }
+
+    /// Index into `diagnostics` for the cursor row: the diagnostic itself, or
+    /// the first one on the diff line under the cursor.
+    pub fn diagnostic_at_cursor(&self) -> Option<usize> {
+        let (file_idx, line) = match self.line_annotations.get(self.diff_state.cursor_line)? {
+            AnnotatedLine::Diagnostic { diagnostic_idx, .. } => return Some(*diagnostic_idx),
+            AnnotatedLine::DiffLine {
+                file_idx,
+                new_lineno: Some(line),
+                ..
+            }
+            | AnnotatedLine::SideBySideLine {
+                file_idx,
+                new_lineno: Some(line),
+                ..
+            } => (*file_idx, *line),
+            _ => return None,
+        };
+        let path = self.diff_files.get(file_idx)?.display_path();
+        let range = diagnostics_at(&self.diagnostics, path, line);
+        (!range.is_empty()).then_some(range.start)
+    }
+
+    /// `p`: turn the diagnostic at the cursor into a line comment and drop it
+    /// from the overlay.
+    pub fn promote_diagnostic_at_cursor(&mut self) {
+        let Some(idx) = self.diagnostic_at_cursor() else {
+            self.set_message("No diagnostic at cursor");
+            return;
+        };
+        let diagnostic = &self.diagnostics[idx];
+        let target = CommentTarget::Line {
+            path: diagnostic.path.clone(),
+            line: diagnostic.line,
+            side: LineSide::New,
+        };
+        let line = diagnostic.line;
+        let request = AddCommentRequest {
+            target: target.clone(),
+            content: format!("{}: {}", diagnostic.title(), diagnostic.message),
+            comment_type: self.default_comment_type(),
+            author: self.username.clone(),
+            commit_id: self.commit_id_for_new_comment(),
+        };
+        let comment = match add_comment_to_session(&mut self.session, request) {
+            Ok(comment) => comment,
+            Err(e) => {
+                self.set_error(format!("Error: Could not save comment: {e}"));
+                return;
+            }
+        };
+        self.diagnostics.remove(idx);
+        self.dirty = true;
+        let message = format!("Diagnostic added as a comment on line {line}");
+        match self.save_current_session_merging_external() {
+            Ok(_) => self.set_message(message),
+            Err(e) => self.set_error(format!("{message}; autosave failed: {e}")),
+        }
+        self.publish_comment_added(&target, &comment);
+        self.rebuild_annotations();
+    }
+}
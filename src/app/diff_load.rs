@@ -77,6 +77,7 @@ impl App {
             old_count: 0,
             new_start: 1,
             new_count: line_count,
+            raw_body: None,
         }];
         let content_hash = DiffFile::compute_content_hash(&hunks);
         let commit_msg_file = DiffFile {
@@ -91,6 +92,7 @@ impl App {
             is_too_large: false,
             is_commit_message: true,
             content_hash,
+            review_key: None,
         };
         self.diff_files.insert(0, commit_msg_file);
         self.session.add_diff_file(&self.diff_files[0]);
@@ -662,7 +664,7 @@ impl App {
     /// `root_path` as parameters instead of reading `self.vcs`/`self.vcs_info`
     /// so the diff-watch worker thread (`diff_watch_fetch`) can call it
     /// against a backend it opened itself, without borrowing `App`.
-    pub(in crate::app) fn fetch_diff_files_for_source(
+    pub(crate) fn fetch_diff_files_for_source(
         vcs: &dyn VcsBackend,
         root_path: &Path,
         diff_source: &DiffSource,
@@ -983,12 +985,13 @@ impl App {
         };
         // `PullRequest` has its own reload path (`reload_pull_request`).
         // `is_pristine_mode` (`--all-files`) and `VcsType::File` (`--file`)
-        // both back onto `FileBackend`, which the worker cannot reopen: it
-        // resolves a backend via `detect_vcs`, which only ever discovers a
-        // real git/jj/hg repository at the process cwd.
+        // both back onto `FileBackend`, and `VcsType::Patch` onto
+        // `PatchBackend`, none of which the worker can reopen: it resolves a
+        // backend via `detect_vcs`, which only ever discovers a real
+        // git/jj/hg repository at the process cwd.
         if matches!(self.diff_source, DiffSource::PullRequest(_))
             || self.is_pristine_mode
-            || self.vcs_info.vcs_type == VcsType::File
+            || matches!(self.vcs_info.vcs_type, VcsType::File | VcsType::Patch)
         {
             return DiffWatchTick::Idle;
         }
@@ -1589,6 +1592,7 @@ mod tests {
             is_too_large,
             is_commit_message: false,
             content_hash,
+            review_key: None,
         }
     }
 
@@ -371,6 +371,7 @@ impl App {
         self.expanded_top.clear();
         self.expanded_bottom.clear();
         self.file_line_count_cache.clear();
+        self.blame_cache = None;
     }
 
     pub(in crate::app) fn eof_gap_enabled(&self) -> bool {
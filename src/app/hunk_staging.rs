@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+
+use crate::vcs::{HunkChange, HunkChangeBase, HunkOperation, HunkSelection};
+
+use super::*;
+
+impl App {
+    /// Which side of the index the current diff shows, if hunk-level changes
+    /// make sense for it.
+    pub fn hunk_change_base(&self) -> Option<HunkChangeBase> {
+        match self.diff_source {
+            DiffSource::Unstaged => Some(HunkChangeBase::Unstaged),
+            DiffSource::Staged => Some(HunkChangeBase::Staged),
+            DiffSource::StagedAndUnstaged => Some(HunkChangeBase::StagedAndUnstaged),
+            _ => None,
+        }
+    }
+
+    /// Stage, unstage or discard the visual selection, or the hunk under the
+    /// cursor outside visual mode. Discarding asks for confirmation first.
+    pub fn change_hunks_at_cursor(&mut self, operation: HunkOperation) {
+        let Some(base) = self.hunk_change_base() else {
+            self.set_warning("Hunk staging only available when viewing staged or unstaged diffs");
+            self.leave_visual_mode_if_active();
+            return;
+        };
+        if !base.supports(operation) {
+            self.set_warning(match operation {
+                HunkOperation::Stage => "Nothing to stage: these changes are already staged",
+                HunkOperation::Unstage => "Nothing to unstage: these changes are not staged",
+                HunkOperation::Discard => "Unstage changes before discarding them",
+            });
+            self.leave_visual_mode_if_active();
+            return;
+        }
+        let target = self.hunk_change_target();
+        self.leave_visual_mode_if_active();
+        let target = match target {
+            Ok(target) => target,
+            Err(message) => {
+                self.set_warning(message);
+                return;
+            }
+        };
+        if operation == HunkOperation::Discard {
+            self.enter_confirm_mode(ConfirmAction::DiscardHunks(target));
+        } else {
+            self.apply_hunk_change(operation, target);
+        }
+    }
+
+    /// Runs the discard queued behind the confirmation prompt.
+    pub fn confirm_pending_discard(&mut self) {
+        let pending = self.pending_confirm.take();
+        self.exit_confirm_mode();
+        if let Some(ConfirmAction::DiscardHunks(target)) = pending {
+            self.apply_hunk_change(HunkOperation::Discard, target);
+        }
+    }
+
+    fn apply_hunk_change(&mut self, operation: HunkOperation, target: HunkChangeTarget) {
+        let (Some(base), Some(file)) = (
+            self.hunk_change_base(),
+            self.diff_files.get(target.file_idx),
+        ) else {
+            self.set_warning("Diff changed, reload with :e and try again");
+            return;
+        };
+        let change = HunkChange {
+            file,
+            base,
+            operation,
+            selections: &target.selections,
+        };
+        if let Err(e) = self.vcs.apply_hunk_change(&change) {
+            self.set_error(format!("Failed to {}: {e}", operation_verb(operation)));
+            return;
+        }
+        let verb = match operation {
+            HunkOperation::Stage => "Staged",
+            HunkOperation::Unstage => "Unstaged",
+            HunkOperation::Discard => "Discarded",
+        };
+        self.set_message(format!("{verb} {}", target.describe()));
+        self.reload_after_staging();
+    }
+
+    fn hunk_change_target(&self) -> std::result::Result<HunkChangeTarget, &'static str> {
+        if let Some(sel) = self.get_visual_selection() {
+            return self.visual_hunk_change_target(sel);
+        }
+        let (file_idx, hunk_idx) = self
+            .hunk_at_cursor()
+            .ok_or("Move cursor to a hunk to change it")?;
+        Ok(HunkChangeTarget {
+            file_idx,
+            selections: vec![HunkSelection {
+                hunk_idx,
+                lines: None,
+            }],
+        })
+    }
+
+    /// Collapses the selected rows into one line range per hunk. Hunk
+    /// headers and comment rows inside the selection are skipped.
+    fn visual_hunk_change_target(
+        &self,
+        sel: &VisualSelection,
+    ) -> std::result::Result<HunkChangeTarget, &'static str> {
+        let (start, end) = sel.ordered();
+        let mut file = None;
+        let mut ranges: BTreeMap<usize, (usize, usize)> = BTreeMap::new();
+        for idx in start.annotation_idx..=end.annotation_idx {
+            let (file_idx, hunk_idx, line_idxs) = match self.line_annotations.get(idx) {
+                Some(AnnotatedLine::DiffLine {
+                    file_idx,
+                    hunk_idx,
+                    line_idx,
+                    ..
+                }) => (*file_idx, *hunk_idx, [Some(*line_idx), None]),
+                Some(AnnotatedLine::SideBySideLine {
+                    file_idx,
+                    hunk_idx,
+                    del_line_idx,
+                    add_line_idx,
+                    ..
+                }) => (*file_idx, *hunk_idx, [*del_line_idx, *add_line_idx]),
+                _ => continue,
+            };
+            if *file.get_or_insert(file_idx) != file_idx {
+                return Err("Select lines from a single file to change them");
+            }
+            for line_idx in line_idxs.into_iter().flatten() {
+                let range = ranges.entry(hunk_idx).or_insert((line_idx, line_idx));
+                range.0 = range.0.min(line_idx);
+                range.1 = range.1.max(line_idx);
+            }
+        }
+        let file_idx = file.ok_or("Select diff lines to change them")?;
+        Ok(HunkChangeTarget {
+            file_idx,
+            selections: ranges
+                .into_iter()
+                .map(|(hunk_idx, (lo, hi))| HunkSelection {
+                    hunk_idx,
+                    lines: Some(lo..=hi),
+                })
+                .collect(),
+        })
+    }
+
+    fn leave_visual_mode_if_active(&mut self) {
+        if self.input_mode == InputMode::VisualSelect {
+            self.exit_visual_mode();
+        }
+    }
+}
+
+impl HunkChangeTarget {
+    fn describe(&self) -> String {
+        if self.selections.iter().any(|s| s.lines.is_some()) {
+            return "selected lines".to_string();
+        }
+        match self.selections.len() {
+            1 => "hunk".to_string(),
+            n => format!("{n} hunks"),
+        }
+    }
+}
+
+fn operation_verb(operation: HunkOperation) -> &'static str {
+    match operation {
+        HunkOperation::Stage => "stage",
+        HunkOperation::Unstage => "unstage",
+        HunkOperation::Discard => "discard",
+    }
+}
@@ -28,6 +28,7 @@ impl App {
                     show_checks: options.show_pr_checks,
                     show_comments: options.show_pr_comments,
                 },
+                options.forge_config.clone(),
             );
         }
 
@@ -64,6 +65,86 @@ impl App {
             return Ok(app);
         }
 
+        // --patch mode: review a raw diff that was never applied. Context
+        // comes from the enclosing checkout when one matches the patch, and
+        // the session is keyed by the patch text rather than any HEAD.
+        if let Some(patch) = options.patch {
+            let (label, text) = crate::vcs::patch::read_patch(patch)?;
+            let root =
+                match detect_vcs(options.git_backend_preference, options.diff_whitespace_mode) {
+                    Ok(vcs) => vcs.info().root_path.clone(),
+                    Err(_) => std::env::current_dir()?,
+                };
+            let vcs = Box::new(PatchBackend::new(label, &text, root)?);
+            let vcs_info = vcs.info().clone();
+            let highlighter = theme.syntax_highlighter();
+
+            // A mail series reviews like a commit range: one selector entry
+            // per message, keyed by the commits the messages were made from.
+            if let Some(series) = vcs.series().cloned() {
+                let commit_ids: Vec<String> = series.patches.iter().map(|p| p.id.clone()).collect();
+                let diff_files = Self::get_commit_range_diff_with_ignore(
+                    vcs.as_ref(),
+                    &vcs_info.root_path,
+                    &ResolvedRevisionRange::from_commit_ids(
+                        &commit_ids,
+                        RevisionDiffTarget::CommitList,
+                    ),
+                    highlighter,
+                    None,
+                )?;
+                let session = Self::load_or_create_commit_range_session(&vcs_info, &commit_ids);
+                let review_commits: Vec<CommitInfo> = vcs
+                    .get_commits_info(&commit_ids)?
+                    .into_iter()
+                    .rev()
+                    .collect();
+
+                let mut app = Self::build(
+                    vcs,
+                    vcs_info,
+                    theme,
+                    comment_type_configs,
+                    output_to_stdout,
+                    diff_files,
+                    session,
+                    DiffSource::CommitRange(commit_ids),
+                    InputMode::Normal,
+                    Vec::new(),
+                    None, // no path_filter
+                    options.repo_url_override.clone(),
+                )?;
+                app.patch_series = Some(series);
+                app.start_commit_range_review(review_commits, options.commit_selection)?;
+                app.focused_panel = FocusedPanel::Diff;
+                return Ok(app);
+            }
+
+            let diff_files = vcs.get_working_tree_diff(highlighter)?;
+            let session = Self::load_or_create_session(&vcs_info, SessionDiffSource::Patch);
+
+            let mut app = Self::build(
+                vcs,
+                vcs_info,
+                theme,
+                comment_type_configs,
+                output_to_stdout,
+                diff_files,
+                session,
+                DiffSource::WorkingTree,
+                InputMode::Normal,
+                Vec::new(),
+                None, // no path_filter
+                options.repo_url_override.clone(),
+            )?;
+            if app.diff_files.len() == 1 {
+                app.show_file_list = false;
+            }
+            app.focused_panel = FocusedPanel::Diff;
+
+            return Ok(app);
+        }
+
         // --all-files mode: enumerate every tracked file via `git ls-files`
         // and render in context-only mode for whole-repo annotation. Git-only
         // for MVP; non-git invocation surfaces as `NotARepository`.
@@ -270,35 +351,7 @@ impl App {
             )?
             .with_vcs_open_options(options.vcs_open_options());
 
-            // Set up inline commit selector for multi-commit reviews
-            if review_commits.len() > 1 {
-                app.range_diff_files = Some(app.diff_files.clone());
-                app.commit_list = review_commits.clone();
-                let range =
-                    Self::initial_commit_range(options.commit_selection, review_commits.len());
-                app.commit_selection_range = range;
-                app.commit_list_cursor = range.map(|(start, _)| start).unwrap_or(0);
-                app.commit_list_scroll_offset = 0;
-                app.visible_commit_count = review_commits.len();
-                app.has_more_commit = false;
-                app.show_commit_selector = true;
-                app.commit_diff_cache.clear();
-            }
-            app.review_commits = review_commits;
-            // `initial_commit_selection = oldest` opens the review scoped to a single
-            // commit; narrow the loaded diff to it. Otherwise finalize the
-            // full-range diff already loaded above.
-            if Self::is_strict_commit_selection(
-                app.commit_selection_range,
-                app.review_commits.len(),
-            ) {
-                app.reload_inline_selection()?;
-            } else {
-                app.insert_commit_message_if_single();
-                app.sort_files_by_directory(true);
-                app.expand_all_dirs();
-                app.rebuild_annotations();
-            }
+            app.start_commit_range_review(review_commits, options.commit_selection)?;
 
             Ok(app)
         } else if options.working_tree {
@@ -405,6 +458,42 @@ impl App {
         }
     }
 
+    /// Load `review_commits` (newest first) into the inline commit selector
+    /// and finish the initial diff for the selection it starts on.
+    pub(in crate::app) fn start_commit_range_review(
+        &mut self,
+        review_commits: Vec<CommitInfo>,
+        commit_selection: CommitSelectionStart,
+    ) -> Result<()> {
+        // Set up inline commit selector for multi-commit reviews
+        if review_commits.len() > 1 {
+            self.range_diff_files = Some(self.diff_files.clone());
+            self.commit_list = review_commits.clone();
+            let range = Self::initial_commit_range(commit_selection, review_commits.len());
+            self.commit_selection_range = range;
+            self.commit_list_cursor = range.map(|(start, _)| start).unwrap_or(0);
+            self.commit_list_scroll_offset = 0;
+            self.visible_commit_count = review_commits.len();
+            self.has_more_commit = false;
+            self.show_commit_selector = true;
+            self.commit_diff_cache.clear();
+        }
+        self.review_commits = review_commits;
+        // `initial_commit_selection = oldest` opens the review scoped to a single
+        // commit; narrow the loaded diff to it. Otherwise finalize the
+        // full-range diff the caller already loaded.
+        if Self::is_strict_commit_selection(self.commit_selection_range, self.review_commits.len())
+        {
+            self.reload_inline_selection()?;
+        } else {
+            self.insert_commit_message_if_single();
+            self.sort_files_by_directory(true);
+            self.expand_all_dirs();
+            self.rebuild_annotations();
+        }
+        Ok(())
+    }
+
     /// Records how `detect_vcs` opened the backend, so the diff-watch worker
     /// can open its own the same way.
     fn with_vcs_open_options(mut self, vcs_open_options: VcsOpenOptions) -> Self {
@@ -487,6 +576,9 @@ impl App {
             focused_panel: FocusedPanel::Diff,
             diff_view_mode: DiffViewMode::Unified,
             relative_line_numbers: false,
+            show_blame: false,
+            blame_cache: None,
+            patch_series: None,
             file_list_state: FileListState::default(),
             comment_navigator_state: CommentNavigatorState::default(),
             diff_state: DiffState::default(),
@@ -518,6 +610,7 @@ impl App {
             comment_is_file_level: true,
             comment_line: None,
             editing_comment_id: None,
+            comment_reply_thread_id: None,
             visual_selection: None,
             mouse_drag_active: false,
             comment_line_range: None,
@@ -547,6 +640,16 @@ impl App {
             forge_review_summaries: Vec::new(),
             forge_review_threads_loading: false,
             pr_threads_rx: None,
+            pr_thread_resolve_rx: None,
+            pr_viewed_sync: None,
+            pending_review: None,
+            pr_pending_review_sync: None,
+            pr_action_rx: None,
+            check_log_rx: None,
+            check_log: None,
+            export_send_rx: None,
+            diagnostics_rx: None,
+            control: None,
             forge_config: crate::config::ForgeConfig::default(),
             username: crate::model::comment::DEFAULT_AUTHOR.to_string(),
             submit_state: None,
@@ -574,6 +677,7 @@ impl App {
             up_released_since_arm: false,
             cursor_line_highlight: true,
             leader_key: crate::config::DEFAULT_LEADER_KEY,
+            key_bindings: crate::input::KeyBindings::default(),
             scroll_offset: 0,
             file_list_area: None,
             comment_navigator_area: None,
@@ -607,6 +711,8 @@ impl App {
             saved_inline_selection: None,
             path_filter: path_filter.map(|s| s.to_string()),
             export: ExportConfig::default(),
+            diagnostics: Vec::new(),
+            diagnostics_command: None,
         };
         // Auto-hide file list when path filter matches exactly one file
         if app.path_filter.is_some() && app.diff_files.len() == 1 {
@@ -657,7 +763,7 @@ impl App {
     /// defaults to untyped comments with no `[TYPE]` prefix. Configuring types
     /// overrides that default (the first configured type becomes the default),
     /// but `None` stays available: it is appended so it can still be cycled to.
-    fn resolve_comment_types(
+    pub(crate) fn resolve_comment_types(
         comment_type_configs: Option<Vec<CommentTypeConfig>>,
     ) -> Vec<CommentTypeDefinition> {
         let Some(configs) = comment_type_configs else {
@@ -813,9 +919,11 @@ impl App {
                 show_checks: false,
                 show_comments: true,
             },
+            crate::config::ForgeConfig::default(),
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn new_from_pr_target_with_pr_display_options(
         theme: Theme,
         comment_type_configs: Option<Vec<CommentTypeConfig>>,
@@ -824,23 +932,32 @@ impl App {
         repo_url_override: Option<ForgeRepository>,
         commit_selection: CommitSelectionStart,
         display_options: PrDisplayOptions,
+        forge_config: crate::config::ForgeConfig,
     ) -> Result<Self> {
         use crate::forge::azure::az::parse_pull_request_target_azure;
         use crate::forge::bitbucket::bkt::parse_pull_request_target_bitbucket;
+        use crate::forge::bitbucket_dc::api::parse_pull_request_target_bitbucket_dc;
+        use crate::forge::gerrit::api::parse_pull_request_target_gerrit;
+        use crate::forge::gitea::api::parse_pull_request_target_gitea;
         use crate::forge::github::gh::parse_pull_request_target;
         use crate::forge::gitlab::glab::parse_pull_request_target_gitlab;
         use crate::forge::pr_open::open_pull_request;
         use crate::forge::traits::ForgeKind;
 
-        // Bitbucket first: its URL shape (`/pull-requests/<n>`) is distinct,
-        // and the GitHub parser would otherwise claim the host. GitHub then
+        // Bitbucket first: its URL shapes (`/pull-requests/<n>`, and Data
+        // Center's `/projects/<key>/repos/<repo>/pull-requests/<n>`) are
+        // distinct, and the GitHub parser would otherwise claim the host. GitHub then
         // handles numeric / `owner/repo#N` / GitHub URLs, GitLab handles
-        // `/-/merge_requests/<n>`, and an Azure DevOps PR URL falls through to
-        // the Azure parser last.
+        // `/-/merge_requests/<n>`, and Azure DevOps (`/pullrequest/<n>`),
+        // Gitea (`/pulls/<n>`), and Gerrit (`/c/<project>/+/<n>`) URLs fall
+        // through to their parsers last.
         let parsed = parse_pull_request_target_bitbucket(target)
+            .or_else(|_| parse_pull_request_target_bitbucket_dc(target))
             .or_else(|_| parse_pull_request_target(target))
             .or_else(|_| parse_pull_request_target_gitlab(target))
-            .or_else(|_| parse_pull_request_target_azure(target))?;
+            .or_else(|_| parse_pull_request_target_azure(target))
+            .or_else(|_| parse_pull_request_target_gitea(target))
+            .or_else(|_| parse_pull_request_target_gerrit(target))?;
 
         // Resolution order when the target lacks an explicit repo
         // (`tuicr pr 125`):
@@ -860,11 +977,10 @@ impl App {
         let canonical_repo = detected_repo.as_ref().and_then(|origin| {
             if origin.kind == ForgeKind::GitHub {
                 use crate::forge::canonical::resolve_canonical_repository;
-                use crate::forge::github::gh::SystemGhRunner;
                 Some(resolve_canonical_repository(
                     origin,
                     repo_url_override.as_ref(),
-                    &SystemGhRunner,
+                    gh_runner(&forge_config).as_ref(),
                 ))
             } else {
                 None
@@ -896,6 +1012,7 @@ impl App {
             local_checkout_for_target.clone(),
             display_options.show_checks,
             display_options.show_comments,
+            &forge_config,
         );
         let highlighter = theme.syntax_highlighter();
         let opened = open_pull_request(
@@ -904,7 +1021,7 @@ impl App {
             local_checkout_for_target.as_deref(),
             highlighter,
         )?;
-        let opened = Self::opened_pr_with_persisted_session(opened)?;
+        let opened = Self::opened_pr_with_persisted_session(opened, backend.as_ref(), highlighter)?;
 
         let pr_source = PullRequestDiffSource::from_details(&opened.details);
         let diff_source = DiffSource::PullRequest(Box::new(pr_source));
@@ -939,6 +1056,7 @@ impl App {
         )?;
         app.show_pr_checks = display_options.show_checks;
         app.show_pr_comments = display_options.show_comments;
+        app.forge_config = forge_config;
 
         // `build` sees the PR's synthetic root, so record the real launch
         // directory here — PRs opened later from the PR tab resolve their
@@ -23,10 +23,12 @@ use crate::syntax::SyntaxHighlighter;
 use crate::theme::Theme;
 use crate::update::UpdateInfo;
 use crate::vcs::git::calculate_gap;
+use crate::vcs::mbox::PatchSeries;
 use crate::vcs::traits::VcsType;
 use crate::vcs::{
-    ChangeKind, CommitInfo, DiffWhitespaceMode, FileBackend, GitBackendPreference, PrNoopVcs,
-    ResolvedRevisionRange, RevisionDiffTarget, VcsBackend, VcsChangeStatus, VcsInfo, detect_vcs,
+    ChangeKind, CommitInfo, DiffWhitespaceMode, FileBackend, GitBackendPreference, PatchBackend,
+    PrNoopVcs, ResolvedRevisionRange, RevisionDiffTarget, VcsBackend, VcsChangeStatus, VcsInfo,
+    detect_vcs,
 };
 
 const VISIBLE_COMMIT_COUNT: usize = 10;
@@ -38,33 +40,77 @@ pub const GAP_EXPAND_BATCH: usize = 20;
 
 /// Create a forge backend for the given repository.
 /// Routes to the GitHub backend (via `gh`), the GitLab backend (via `glab`),
-/// the Bitbucket Cloud backend (via `bkt`), or the Azure DevOps backend (via
-/// `az`) based on `repo.kind`.
-fn create_forge_backend(
+/// the Bitbucket Cloud backend (via `bkt`), the Azure DevOps backend (via
+/// `az`), or the Bitbucket Data Center, Gitea / Forgejo, and Gerrit backends
+/// (direct REST) based on `repo.kind`. With `[forge] transport = "http"` the
+/// first three swap their CLI runner for the HTTP one.
+pub(crate) fn create_forge_backend(
     repo: &ForgeRepository,
     local_checkout: Option<PathBuf>,
     show_pr_checks: bool,
     show_pr_comments: bool,
+    forge: &crate::config::ForgeConfig,
 ) -> Box<dyn ForgeBackend> {
+    use crate::config::ForgeTransport;
     use crate::forge::traits::ForgeKind;
+    let http = forge.transport == ForgeTransport::Http;
     match repo.kind {
         ForgeKind::GitHub => {
             use crate::forge::github::gh::GitHubGhBackend;
-            Box::new(
-                GitHubGhBackend::new(Some(repo.clone()))
-                    .with_local_checkout(local_checkout)
-                    .with_pr_checks(show_pr_checks)
-                    .with_pr_comments(show_pr_comments),
-            )
+            use crate::forge::github::http::GhHttpRunner;
+            if http {
+                Box::new(
+                    GitHubGhBackend::with_runner(Some(repo.clone()), GhHttpRunner::new(forge))
+                        .with_local_checkout(local_checkout)
+                        .with_pr_checks(show_pr_checks)
+                        .with_pr_comments(show_pr_comments),
+                )
+            } else {
+                Box::new(
+                    GitHubGhBackend::new(Some(repo.clone()))
+                        .with_local_checkout(local_checkout)
+                        .with_pr_checks(show_pr_checks)
+                        .with_pr_comments(show_pr_comments),
+                )
+            }
         }
         ForgeKind::GitLab => {
             use crate::forge::gitlab::GitLabGlabBackend;
-            Box::new(GitLabGlabBackend::new(Some(repo.clone())).with_local_checkout(local_checkout))
+            use crate::forge::gitlab::http::GlabHttpRunner;
+            if http {
+                Box::new(
+                    GitLabGlabBackend::with_runner(Some(repo.clone()), GlabHttpRunner::new(forge))
+                        .with_local_checkout(local_checkout)
+                        .with_pr_checks(show_pr_checks),
+                )
+            } else {
+                Box::new(
+                    GitLabGlabBackend::new(Some(repo.clone()))
+                        .with_local_checkout(local_checkout)
+                        .with_pr_checks(show_pr_checks),
+                )
+            }
         }
         ForgeKind::Bitbucket => {
             use crate::forge::bitbucket::BitbucketBktBackend;
+            use crate::forge::bitbucket::http::BktHttpRunner;
+            if http {
+                Box::new(
+                    BitbucketBktBackend::with_runner(Some(repo.clone()), BktHttpRunner::new(forge))
+                        .with_local_checkout(local_checkout),
+                )
+            } else {
+                Box::new(
+                    BitbucketBktBackend::new(Some(repo.clone()))
+                        .with_local_checkout(local_checkout),
+                )
+            }
+        }
+        ForgeKind::BitbucketDataCenter => {
+            use crate::forge::bitbucket_dc::BitbucketDcBackend;
             Box::new(
-                BitbucketBktBackend::new(Some(repo.clone())).with_local_checkout(local_checkout),
+                BitbucketDcBackend::new(Some(repo.clone()), forge)
+                    .with_local_checkout(local_checkout),
             )
         }
         ForgeKind::AzureDevOps => {
@@ -73,6 +119,32 @@ fn create_forge_backend(
                 AzureDevOpsBackend::new(Some(repo.clone())).with_local_checkout(local_checkout),
             )
         }
+        ForgeKind::Gitea => {
+            use crate::forge::gitea::GiteaBackend;
+            Box::new(
+                GiteaBackend::new(Some(repo.clone()), forge).with_local_checkout(local_checkout),
+            )
+        }
+        ForgeKind::Gerrit => {
+            use crate::forge::gerrit::GerritBackend;
+            Box::new(
+                GerritBackend::new(Some(repo.clone()), forge).with_local_checkout(local_checkout),
+            )
+        }
+    }
+}
+
+/// The runner for GitHub calls made outside a backend (the fork-parent
+/// lookup), honoring `[forge] transport`.
+fn gh_runner(
+    forge: &crate::config::ForgeConfig,
+) -> Box<dyn crate::forge::github::gh::GhCommandRunner + Send> {
+    use crate::config::ForgeTransport;
+    use crate::forge::github::gh::SystemGhRunner;
+    use crate::forge::github::http::GhHttpRunner;
+    match forge.transport {
+        ForgeTransport::Cli => Box::new(SystemGhRunner),
+        ForgeTransport::Http => Box::new(GhHttpRunner::new(forge)),
     }
 }
 
@@ -169,6 +241,10 @@ pub enum ExpandDirection {
 /// Minimum line-number column width (covers files up to 9 999 lines).
 const MIN_LINENO_WIDTH: usize = 4;
 
+/// Extra line-number column width taken by the blame gutter: short id (7),
+/// author (10) and date (10), each followed by a space.
+pub const BLAME_GUTTER_WIDTH: usize = 30;
+
 /// Number of characters needed to display `n` in decimal, minimum `MIN_LINENO_WIDTH`.
 pub fn lineno_width(max_lineno: u32) -> usize {
     if max_lineno == 0 {
@@ -332,6 +408,11 @@ pub enum AnnotatedLine {
     /// edit or reply to these in v1; the annotation is informational so
     /// hit-testing and scroll math stay correct.
     RemoteThreadLine { thread_idx: usize },
+    /// A linter or compiler diagnostic on a new-side line, one row each.
+    Diagnostic {
+        file_idx: usize,
+        diagnostic_idx: usize,
+    },
     /// Binary or empty file indicator
     BinaryOrEmpty { file_idx: usize },
     /// Spacing between files
@@ -466,6 +547,7 @@ pub fn annotation_file_idx(annotation: &AnnotatedLine) -> Option<usize> {
         | AnnotatedLine::DiffLine { file_idx, .. }
         | AnnotatedLine::SideBySideLine { file_idx, .. }
         | AnnotatedLine::LineComment { file_idx, .. }
+        | AnnotatedLine::Diagnostic { file_idx, .. }
         | AnnotatedLine::BinaryOrEmpty { file_idx } => Some(*file_idx),
         AnnotatedLine::PrInfoLine { .. }
         | AnnotatedLine::IssueCommentsHeader
@@ -594,6 +676,8 @@ pub enum InputMode {
     /// no `SubmitConfirm` follows (resolver still runs if any comment is
     /// unmappable).
     SubmitActionPicker,
+    /// Log of a failing CI check, opened by `:check-log`.
+    CheckLog,
 }
 
 /// CommandCompletionState keeps one Tab-completion run anchored to the text
@@ -679,6 +763,30 @@ impl PullRequestDiffSource {
         }
     }
 
+    /// Rebuild the details this PR was opened from, as far as the diff source
+    /// remembers them. Enough for backend calls keyed by repository, number
+    /// and SHAs; the body, author and timestamps are not kept.
+    pub fn details(&self) -> crate::forge::traits::PullRequestDetails {
+        crate::forge::traits::PullRequestDetails {
+            repository: self.key.repository.clone(),
+            number: self.key.number,
+            title: self.title.clone(),
+            url: self.url.clone(),
+            state: self.state.clone(),
+            is_draft: false,
+            author: None,
+            head_ref_name: self.head_ref_name.clone(),
+            base_ref_name: self.base_ref_name.clone(),
+            head_sha: self.key.head_sha.clone(),
+            base_sha: self.base_sha.clone(),
+            body: String::new(),
+            updated_at: None,
+            closed: self.closed,
+            merged_at: None,
+            diff_start_sha: None,
+        }
+    }
+
     pub fn read_only_reason(&self) -> Option<&'static str> {
         if self.merged {
             Some("merged")
@@ -694,9 +802,82 @@ impl PullRequestDiffSource {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ConfirmAction {
     CopyAndQuit,
+    /// Discard the selected worktree changes.
+    DiscardHunks(HunkChangeTarget),
+    /// Open the commit shown in the blame popup as a new review target.
+    OpenBlameCommit(Box<CommitInfo>),
+    /// Delete the viewer's pending forge review.
+    DiscardPendingReview,
+    /// Run a PR-level action on the forge.
+    PrAction(PrAction),
+}
+
+impl ConfirmAction {
+    pub fn prompt(&self) -> &'static str {
+        match self {
+            ConfirmAction::CopyAndQuit => "Copy review to clipboard?",
+            ConfirmAction::DiscardHunks(_) => "Discard selected changes? This cannot be undone.",
+            ConfirmAction::OpenBlameCommit(_) => "Review this commit?",
+            ConfirmAction::DiscardPendingReview => "Discard your pending review and its comments?",
+            ConfirmAction::PrAction(_) => "Apply this change to the PR?",
+        }
+    }
+}
+
+/// A change to the PR itself, rather than to its review.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrAction {
+    Merge(crate::forge::traits::MergeMethod),
+    Close,
+    RequestReviewer(String),
+    AddLabel(String),
+}
+
+impl PrAction {
+    /// Question shown in the confirmation modal.
+    pub fn prompt(&self) -> String {
+        use crate::forge::traits::MergeMethod;
+        match self {
+            PrAction::Merge(MergeMethod::Merge) => "Merge this PR?".to_string(),
+            PrAction::Merge(MergeMethod::Squash) => "Squash and merge this PR?".to_string(),
+            PrAction::Merge(MergeMethod::Rebase) => "Rebase and merge this PR?".to_string(),
+            PrAction::Close => "Close this PR without merging?".to_string(),
+            PrAction::RequestReviewer(user) => format!("Request a review from {user}?"),
+            PrAction::AddLabel(label) => format!("Add the label \"{label}\" to this PR?"),
+        }
+    }
+
+    /// Verb used in warnings and errors, e.g. "Cannot merge: PR is closed".
+    pub fn verb(&self) -> &'static str {
+        match self {
+            PrAction::Merge(_) => "merge",
+            PrAction::Close => "close",
+            PrAction::RequestReviewer(_) => "request a reviewer",
+            PrAction::AddLabel(_) => "add a label",
+        }
+    }
+}
+
+/// Blame for the files of the current diff, all taken from one snapshot.
+#[derive(Debug, Default)]
+pub struct BlameCache {
+    /// Snapshot the blame was taken at; `None` is the working copy.
+    pub ref_commit: Option<String>,
+    /// Per-file blame, indexed by new-side line number minus one. Files that
+    /// failed to blame map to an empty list so they are not retried.
+    pub files: HashMap<PathBuf, Vec<crate::vcs::BlameLine>>,
+    /// First blame failure, reported once when the gutter is turned on.
+    pub error: Option<String>,
+}
+
+/// Lines of one file picked for a hunk-level stage, unstage or discard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HunkChangeTarget {
+    pub file_idx: usize,
+    pub selections: Vec<crate::vcs::HunkSelection>,
 }
 
 /// Push a `MappedComment` onto the appropriate bucket. Free function so the
@@ -733,6 +914,10 @@ pub struct SubmitState {
     /// has chosen for each (defaults to `MoveToSummary` per spec).
     pub unmappable: Vec<crate::forge::submit::UnmappableItem>,
     pub resolver_choices: Vec<crate::forge::submit::ResolverAction>,
+    /// Queued replies to existing remote threads. Always empty for
+    /// `:submit draft` — forges post thread replies immediately, so they
+    /// cannot join a pending review.
+    pub replies: Vec<crate::forge::submit::ThreadReply>,
     /// Cursor row inside the resolver modal.
     pub resolver_cursor: usize,
     /// Originally-reviewed head SHA — used as `commit_id` in the payload.
@@ -756,6 +941,10 @@ pub const SUBMIT_PICKER_EVENTS: &[(&str, crate::forge::submit::SubmitEvent)] = &
     ("Draft", crate::forge::submit::SubmitEvent::Draft),
 ];
 
+/// Extra action-picker row, after the events, shown while the viewer has a
+/// pending review on the forge.
+pub const SUBMIT_PICKER_DISCARD_LABEL: &str = "Discard pending review";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FocusedPanel {
     FileList,
@@ -941,6 +1130,8 @@ pub struct SubmitInFlightState {
     /// Source `Comment.id`s of review-level comments that were rendered into
     /// the review body.
     pub review_comment_ids: Vec<String>,
+    /// Source `Comment.id`s of the thread replies posted with this submit.
+    pub reply_comment_ids: Vec<String>,
     /// Display count of moved-to-summary items, used only by the success
     /// message (kept separate from `summary_comment_ids` so message wording
     /// doesn't accidentally drift if the id list is empty).
@@ -952,6 +1143,9 @@ pub struct SubmitInFlightState {
     /// result still applies to the same PR session.
     pub repository: crate::forge::traits::ForgeRepository,
     pub pr_number: u64,
+    /// The submit went into the viewer's existing pending review instead of
+    /// creating a new one.
+    pub extends_pending_review: bool,
     pub started_at: Instant,
 }
 
@@ -962,7 +1156,13 @@ pub enum PrSubmitEvent {
         repository: crate::forge::traits::ForgeRepository,
         pr_number: u64,
         head_sha: String,
-        result: std::result::Result<crate::forge::traits::GhCreateReviewResponse, String>,
+        /// `Ok(None)` when the submit only carried thread replies, so no
+        /// review was created.
+        result: std::result::Result<Option<crate::forge::traits::GhCreateReviewResponse>, String>,
+        /// Source `Comment.id`s of the thread replies that were posted.
+        /// Replies go out before the review, so this is accurate even when
+        /// `result` is an error.
+        sent_reply_ids: Vec<String>,
     },
 }
 
@@ -981,6 +1181,140 @@ pub enum PrThreadsEvent {
             std::result::Result<Vec<crate::forge::remote_comments::RemoteReviewThread>, String>,
         summaries:
             std::result::Result<Vec<crate::forge::remote_comments::RemoteReviewSummary>, String>,
+        /// Files the viewer has marked "Viewed" on the forge. Best-effort:
+        /// empty when syncing is off, unsupported, or the call failed.
+        viewed_files: Vec<PathBuf>,
+        /// The viewer's pending review. `Ok(None)` when there is none or
+        /// the forge has no pending reviews.
+        pending_review:
+            std::result::Result<Option<crate::forge::remote_comments::PendingReview>, String>,
+    },
+}
+
+/// Result delivered from a background "Viewed" checkbox update.
+#[derive(Debug)]
+pub enum PrViewedSyncEvent {
+    Done {
+        repository: crate::forge::traits::ForgeRepository,
+        pr_number: u64,
+        path: PathBuf,
+        viewed: bool,
+        result: std::result::Result<(), String>,
+    },
+}
+
+/// One "Viewed" checkbox update waiting for the sync worker.
+pub struct PrViewedJob {
+    pub details: crate::forge::traits::PullRequestDetails,
+    pub path: PathBuf,
+    pub viewed: bool,
+}
+
+/// The worker that sends "Viewed" updates, one at a time and in toggle
+/// order, so marking and quickly unmarking a file cannot land on the forge
+/// the other way round. Unlike the one-shot receivers it stays open until
+/// every queued update has reported back.
+pub struct PrViewedSync {
+    pub jobs: std::sync::mpsc::Sender<PrViewedJob>,
+    pub tx: std::sync::mpsc::Sender<PrViewedSyncEvent>,
+    pub rx: std::sync::mpsc::Receiver<PrViewedSyncEvent>,
+    pub in_flight: usize,
+}
+
+/// A change to the viewer's pending review made outside `:submit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PendingReviewChange {
+    UpdateComment,
+    DeleteComment,
+    Discard,
+}
+
+/// Result delivered from a background pending-review change.
+#[derive(Debug)]
+pub enum PrPendingReviewEvent {
+    Done {
+        repository: crate::forge::traits::ForgeRepository,
+        pr_number: u64,
+        change: PendingReviewChange,
+        result: std::result::Result<(), String>,
+    },
+}
+
+/// The forge call behind one [`PendingReviewChange`].
+pub type PendingReviewCall = Box<
+    dyn FnOnce(&dyn ForgeBackend, &crate::forge::traits::PullRequestDetails) -> Result<()> + Send,
+>;
+
+/// One pending-review change waiting for the sync worker.
+pub struct PrPendingReviewJob {
+    pub details: crate::forge::traits::PullRequestDetails,
+    pub change: PendingReviewChange,
+    pub call: PendingReviewCall,
+}
+
+/// The worker that sends pending-review changes one at a time and in the
+/// order they were made, so editing and then deleting a comment cannot
+/// reach the forge the other way round. Stays open until every queued
+/// change has reported back (see [`PrViewedSync`]).
+pub struct PrPendingReviewSync {
+    pub jobs: std::sync::mpsc::Sender<PrPendingReviewJob>,
+    pub tx: std::sync::mpsc::Sender<PrPendingReviewEvent>,
+    pub rx: std::sync::mpsc::Receiver<PrPendingReviewEvent>,
+    pub in_flight: usize,
+}
+
+/// Result delivered from a PR action's background thread. On success it
+/// carries the refreshed description-panel info, when that fetch worked.
+#[derive(Debug)]
+pub enum PrActionEvent {
+    Done {
+        repository: crate::forge::traits::ForgeRepository,
+        pr_number: u64,
+        action: PrAction,
+        result: std::result::Result<Option<crate::forge::traits::PullRequestInfo>, String>,
+    },
+}
+
+/// Result delivered from a `:send` command.
+#[derive(Debug)]
+pub enum ExportSendEvent {
+    Done {
+        target: String,
+        /// Comments to mark sent on success; empty unless the target is
+        /// `only_new`.
+        sent_ids: Vec<String>,
+        result: std::result::Result<(), String>,
+    },
+}
+
+/// Result delivered from a `:diagnostics` command run.
+#[derive(Debug)]
+pub enum DiagnosticsEvent {
+    Done {
+        command: String,
+        result: std::result::Result<Vec<crate::diagnostics::Diagnostic>, String>,
+    },
+}
+
+/// Result delivered from a CI log fetch.
+#[derive(Debug)]
+pub enum CheckLogEvent {
+    Done {
+        repository: crate::forge::traits::ForgeRepository,
+        pr_number: u64,
+        check_name: String,
+        result: std::result::Result<String, String>,
+    },
+}
+
+/// Result delivered from the resolve/unresolve background thread.
+#[derive(Debug)]
+pub enum PrThreadResolveEvent {
+    Done {
+        repository: crate::forge::traits::ForgeRepository,
+        pr_number: u64,
+        resolved: bool,
+        result: std::result::Result<(), String>,
     },
 }
 
@@ -1122,6 +1456,11 @@ pub struct App {
     pub focused_panel: FocusedPanel,
     pub diff_view_mode: DiffViewMode,
     pub relative_line_numbers: bool,
+    /// Show the blame gutter (`:set blame`).
+    pub show_blame: bool,
+    pub blame_cache: Option<BlameCache>,
+    /// The mail series under review with `--patch`, kept for `:export email`.
+    pub patch_series: Option<PatchSeries>,
 
     pub file_list_state: FileListState,
     pub comment_navigator_state: CommentNavigatorState,
@@ -1165,6 +1504,9 @@ pub struct App {
     pub comment_is_file_level: bool,
     pub comment_line: Option<(u32, LineSide)>,
     pub editing_comment_id: Option<String>,
+    /// Remote thread the comment input is replying to. While set, the input
+    /// renders as a reply popup and saves into `session.thread_replies`.
+    pub comment_reply_thread_id: Option<String>,
 
     pub visual_selection: Option<VisualSelection>,
     /// True once the active mouse drag has actually moved off the press cell.
@@ -1243,6 +1585,29 @@ pub struct App {
     /// Background-thread channel that delivers remote-thread fetch results.
     /// `Receiver` is only present while a fetch is in flight.
     pub pr_threads_rx: Option<std::sync::mpsc::Receiver<PrThreadsEvent>>,
+    /// Background-thread channel for a `:resolve` / `:unresolve` call.
+    pub pr_thread_resolve_rx: Option<std::sync::mpsc::Receiver<PrThreadResolveEvent>>,
+    /// In-flight pushes of the reviewed mark to the forge's "Viewed"
+    /// checkbox. `None` when nothing is pending.
+    pub pr_viewed_sync: Option<PrViewedSync>,
+    /// The viewer's pending review on the open PR, as of the last thread
+    /// fetch. `:submit` extends it instead of creating a second review.
+    pub pending_review: Option<crate::forge::remote_comments::PendingReview>,
+    /// In-flight edits, deletions and discards of the pending review.
+    pub pr_pending_review_sync: Option<PrPendingReviewSync>,
+    /// Background-thread channel for a `:merge`, `:close`,
+    /// `:request-reviewer` or `:label` call.
+    pub pr_action_rx: Option<std::sync::mpsc::Receiver<PrActionEvent>>,
+    /// In-flight `:check-log` fetch.
+    pub check_log_rx: Option<std::sync::mpsc::Receiver<CheckLogEvent>>,
+    /// The CI log open in `InputMode::CheckLog`.
+    pub check_log: Option<CheckLogState>,
+    /// In-flight `:send` command.
+    pub export_send_rx: Option<std::sync::mpsc::Receiver<ExportSendEvent>>,
+    /// In-flight `diagnostics_command` run.
+    pub diagnostics_rx: Option<std::sync::mpsc::Receiver<DiagnosticsEvent>>,
+    /// This process's control socket, when `control_socket` is on.
+    pub control: Option<crate::control::ControlHub>,
 
     /// `[forge]` section settings resolved at startup. Drives the body/footer
     /// formatting on submit. Defaults to `ForgeConfig::default()` when the
@@ -1326,6 +1691,8 @@ pub struct App {
     pub up_released_since_arm: bool,
     pub cursor_line_highlight: bool,
     pub leader_key: char,
+    /// `[keys]` bindings layered over the built-in key maps.
+    pub key_bindings: crate::input::KeyBindings,
     pub scroll_offset: usize,
     pub file_list_area: Option<ratatui::layout::Rect>,
     pub comment_navigator_area: Option<ratatui::layout::Rect>,
@@ -1402,6 +1769,11 @@ pub struct App {
     pub path_filter: Option<String>,
     /// Resolved `[export]` settings shaping the generated review markdown.
     pub export: ExportConfig,
+    /// Linter and compiler diagnostics on changed lines, sorted by
+    /// `(path, line)` so renderers can look them up per diff line.
+    pub diagnostics: Vec<crate::diagnostics::Diagnostic>,
+    /// Shell command a bare `:diagnostics` runs, from the config.
+    pub diagnostics_command: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -1649,6 +2021,20 @@ pub struct HelpState {
     pub(crate) current_match_line: Option<usize>,
 }
 
+/// A CI check's log, cleaned for display.
+#[derive(Debug, Default)]
+pub struct CheckLogState {
+    pub check_name: String,
+    pub lines: Vec<String>,
+    /// Per line, the first `path:line` it mentions that names a file in the
+    /// diff. Enter jumps there.
+    pub targets: Vec<Option<(PathBuf, u32)>>,
+    pub selected: usize,
+    pub scroll_offset: usize,
+    pub viewport_height: usize, // Set during render
+    pub(crate) last_search_pattern: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SummaryCommentTarget {
     Review {
@@ -1708,8 +2094,8 @@ struct VcsOpenOptions {
 }
 
 impl Default for VcsOpenOptions {
-    /// What every non-Git start uses: `--file`, `--all-files`, and PR reviews
-    /// never reopen a backend, so their options are never read.
+    /// What every non-Git start uses: `--file`, `--all-files`, `--patch` and
+    /// PR reviews never reopen a backend, so their options are never read.
     fn default() -> Self {
         Self {
             git_backend_preference: GitBackendPreference::Libgit2,
@@ -1726,6 +2112,8 @@ pub struct AppStartupOptions<'a> {
     /// Whole-repo annotation mode (`--all-files`). Mutually exclusive with
     /// the other selectors; the binary validates that before reaching here.
     pub all_files: bool,
+    /// Raw patch to review (`--patch`), as a path or `-` for stdin.
+    pub patch: Option<&'a str>,
     /// Whether pull-request CI checks are fetched and rendered.
     pub show_pr_checks: bool,
     /// Whether pull-request conversation comments are fetched and rendered.
@@ -1741,6 +2129,9 @@ pub struct AppStartupOptions<'a> {
     /// `ForgeRepository`. When `Some`, the canonical resolver short-circuits
     /// the `gh api` parent lookup and uses this value directly.
     pub repo_url_override: Option<ForgeRepository>,
+    /// `[forge]` settings. Only `tuicr pr <target>` reads them here, since it
+    /// opens its backend before the binary can set `App::forge_config`.
+    pub forge_config: crate::config::ForgeConfig,
 }
 
 impl AppStartupOptions<'_> {
@@ -1755,21 +2146,32 @@ impl AppStartupOptions<'_> {
 }
 
 mod annotations;
+mod blame;
+mod carry_forward;
+mod check_log;
 mod comment_vim;
 mod comments;
 mod commits;
+mod control;
+mod diagnostics;
 mod diff_load;
 mod file_filter;
 mod gaps;
+mod hunk_staging;
 mod init;
 mod modes;
 mod navigation;
+mod pending_review;
 mod pr;
+mod pr_actions;
 mod reviewed;
 mod search;
+mod send;
 mod session;
 mod submit;
+mod thread_actions;
 mod tree;
+mod viewed_sync;
 mod visual;
 
 #[cfg(test)]
@@ -81,6 +81,10 @@ impl App {
         self.input_mode == InputMode::Search && self.search_return_mode == InputMode::Help
     }
 
+    pub fn searching_check_log(&self) -> bool {
+        self.input_mode == InputMode::Search && self.search_return_mode == InputMode::CheckLog
+    }
+
     pub fn open_message_details(&mut self) {
         if self
             .message
@@ -620,7 +620,14 @@ impl App {
         } else {
             0
         };
-        lineno_width(hunk_max.max(cache_max).max(relative_max))
+        let digits = lineno_width(hunk_max.max(cache_max).max(relative_max));
+        // The blame gutter rides in the line-number column so every gutter
+        // and wrap calculation picks up its width for free.
+        if self.show_blame {
+            digits + BLAME_GUTTER_WIDTH
+        } else {
+            digits
+        }
     }
 
     pub fn pane_geometry(&self, inner: ratatui::layout::Rect, side: LineSide) -> PaneGeom {
@@ -984,7 +991,8 @@ impl App {
                     if visibility.render_decision(thread).is_none() {
                         continue;
                     }
-                    height += thread_display_lines(thread);
+                    height +=
+                        thread_display_lines(thread, self.session.thread_replies_for(&thread.id));
                 }
             }
         }
@@ -1032,11 +1040,12 @@ impl App {
         let mut content_lines = 0;
         let mut comment_lines = 0;
 
-        // Pre-aggregate remote-thread rows by (line, side) for this file. Must
-        // mirror the filter/anchor logic in build_remote_thread_index — the
-        // rebuild_annotations renderer uses the same filter, and the two must
-        // emit identical row counts or scroll math goes out of sync.
-        let remote_thread_rows: HashMap<(u32, LineSide), usize> = {
+        // Pre-aggregate remote-thread and diagnostic rows by (line, side) for
+        // this file. Must mirror the filter/anchor logic in
+        // build_remote_thread_index — the rebuild_annotations renderer uses
+        // the same filter, and the two must emit identical row counts or
+        // scroll math goes out of sync.
+        let anchored_rows: HashMap<(u32, LineSide), usize> = {
             use crate::forge::remote_comments::{RemoteCommentSide, thread_display_lines};
             let mut map: HashMap<(u32, LineSide), usize> = HashMap::new();
             let path_str = path.to_string_lossy();
@@ -1053,7 +1062,12 @@ impl App {
                     RemoteCommentSide::Right => LineSide::New,
                     RemoteCommentSide::Left => LineSide::Old,
                 };
-                *map.entry((line, side)).or_default() += thread_display_lines(thread);
+                *map.entry((line, side)).or_default() +=
+                    thread_display_lines(thread, self.session.thread_replies_for(&thread.id));
+            }
+            // Diagnostics are one row each, always on the new side.
+            for diagnostic in self.diagnostics.iter().filter(|d| d.path == *path) {
+                *map.entry((diagnostic.line, LineSide::New)).or_default() += 1;
             }
             map
         };
@@ -1151,13 +1165,13 @@ impl App {
                             }
 
                             if let Some(old_ln) = diff_line.old_lineno {
-                                comment_lines += remote_thread_rows
+                                comment_lines += anchored_rows
                                     .get(&(old_ln, LineSide::Old))
                                     .copied()
                                     .unwrap_or(0);
                             }
                             if let Some(new_ln) = diff_line.new_lineno {
-                                comment_lines += remote_thread_rows
+                                comment_lines += anchored_rows
                                     .get(&(new_ln, LineSide::New))
                                     .copied()
                                     .unwrap_or(0);
@@ -1196,7 +1210,7 @@ impl App {
                                         }
                                     }
                                     if let Some(new_ln) = diff_line.new_lineno {
-                                        comment_lines += remote_thread_rows
+                                        comment_lines += anchored_rows
                                             .get(&(new_ln, LineSide::New))
                                             .copied()
                                             .unwrap_or(0);
@@ -1274,7 +1288,7 @@ impl App {
 
                                     for line in &lines[del_start..del_end] {
                                         if let Some(old_ln) = line.old_lineno {
-                                            comment_lines += remote_thread_rows
+                                            comment_lines += anchored_rows
                                                 .get(&(old_ln, LineSide::Old))
                                                 .copied()
                                                 .unwrap_or(0);
@@ -1282,7 +1296,7 @@ impl App {
                                     }
                                     for line in &lines[add_start..add_end] {
                                         if let Some(new_ln) = line.new_lineno {
-                                            comment_lines += remote_thread_rows
+                                            comment_lines += anchored_rows
                                                 .get(&(new_ln, LineSide::New))
                                                 .copied()
                                                 .unwrap_or(0);
@@ -1314,7 +1328,7 @@ impl App {
                                         }
                                     }
                                     if let Some(new_ln) = diff_line.new_lineno {
-                                        comment_lines += remote_thread_rows
+                                        comment_lines += anchored_rows
                                             .get(&(new_ln, LineSide::New))
                                             .copied()
                                             .unwrap_or(0);
@@ -0,0 +1,265 @@
+use super::*;
+
+use crate::forge::remote_comments::{PendingReview, RemoteCommentSide, without_pending_threads};
+use crate::forge::traits::PullRequestDetails;
+use crate::model::comment::CommentLifecycleState;
+
+impl PrPendingReviewSync {
+    /// Start a worker that runs queued changes in order on a backend from
+    /// `backend_for`. It exits once the sync is dropped.
+    pub fn start<F>(backend_for: F) -> Self
+    where
+        F: Fn(&PullRequestDetails) -> Box<dyn ForgeBackend> + Send + 'static,
+    {
+        let (jobs, job_rx) = std::sync::mpsc::channel::<PrPendingReviewJob>();
+        let (tx, rx) = std::sync::mpsc::channel();
+        let worker_tx = tx.clone();
+        std::thread::spawn(move || {
+            for PrPendingReviewJob {
+                details,
+                change,
+                call,
+            } in job_rx
+            {
+                let result =
+                    call(backend_for(&details).as_ref(), &details).map_err(|e| e.to_string());
+                let _ = worker_tx.send(PrPendingReviewEvent::Done {
+                    repository: details.repository,
+                    pr_number: details.number,
+                    change,
+                    result,
+                });
+            }
+        });
+        Self {
+            jobs,
+            tx,
+            rx,
+            in_flight: 0,
+        }
+    }
+}
+
+impl App {
+    /// Bring the viewer's pending review into the session. Its comments
+    /// become `PushedDraft` comments carrying their remote ids, so they can
+    /// be edited and deleted like local drafts, and the threads GitHub
+    /// renders for them are dropped. Expects `prune_locked_comments` to
+    /// have cleared the previous copies.
+    pub(in crate::app) fn import_pending_review(&mut self, review: Option<PendingReview>) {
+        self.pending_review = None;
+        let Some(review) = review else {
+            return;
+        };
+        let type_ids: Vec<&str> = self.comment_types.iter().map(|t| t.id.as_str()).collect();
+        let review_id = review.database_id.to_string();
+        for pending in &review.comments {
+            let parsed = crate::forge::submit::parse_inline_body(
+                &pending.body,
+                &type_ids,
+                &self.forge_config,
+            );
+            let side = match pending.side {
+                RemoteCommentSide::Left => LineSide::Old,
+                RemoteCommentSide::Right => LineSide::New,
+            };
+            let line = pending.line.filter(|_| !parsed.file_level);
+            let mut comment = match (line, pending.start_line) {
+                (Some(end), Some(start)) if start < end => Comment::new_with_range(
+                    parsed.content,
+                    parsed.comment_type,
+                    Some(side),
+                    LineRange::new(start, end),
+                ),
+                (Some(_), _) => Comment::new(parsed.content, parsed.comment_type, Some(side)),
+                (None, _) => Comment::new(parsed.content, parsed.comment_type, None),
+            }
+            .with_author(self.username.clone());
+            comment.lifecycle_state = CommentLifecycleState::PushedDraft;
+            comment.remote_review_id = Some(review_id.clone());
+            comment.remote_comment_id = Some(pending.id.clone());
+
+            let Some(file) = self.session.get_file_mut(&PathBuf::from(&pending.path)) else {
+                continue;
+            };
+            match line {
+                Some(line) => file.add_line_comment(line, comment),
+                None => file.add_file_comment(comment),
+            }
+        }
+        self.forge_review_threads =
+            without_pending_threads(std::mem::take(&mut self.forge_review_threads), &review);
+        self.pending_review = Some(review);
+    }
+
+    /// Forward an edit of a pending-review comment to the forge. No-op for
+    /// comments that only exist locally.
+    pub(in crate::app) fn push_pending_comment_edit(&mut self, comment_id: &str) {
+        let Some((remote_id, body)) = self.session.files.values().find_map(|review| {
+            let file_level = review.file_comments.iter().find(|c| c.id == comment_id);
+            let line = review
+                .line_comments
+                .values()
+                .flatten()
+                .find(|c| c.id == comment_id);
+            let (comment, file_level) = match (file_level, line) {
+                (Some(comment), _) => (comment, true),
+                (None, Some(comment)) => (comment, false),
+                (None, None) => return None,
+            };
+            if comment.lifecycle_state != CommentLifecycleState::PushedDraft {
+                return None;
+            }
+            let body =
+                crate::forge::submit::build_inline_body(comment, file_level, &self.forge_config);
+            comment.remote_comment_id.clone().map(|id| (id, body))
+        }) else {
+            return;
+        };
+        self.spawn_pending_review_change(
+            PendingReviewChange::UpdateComment,
+            move |backend, details| {
+                backend.update_pending_review_comment(details, &remote_id, &body)
+            },
+        );
+    }
+
+    /// Forward the deletion of a pending-review comment to the forge. No-op
+    /// for comments that only exist locally.
+    pub(in crate::app) fn push_pending_comment_delete(&mut self, comment: &Comment) {
+        if comment.lifecycle_state != CommentLifecycleState::PushedDraft {
+            return;
+        }
+        let Some(remote_id) = comment.remote_comment_id.clone() else {
+            return;
+        };
+        self.spawn_pending_review_change(
+            PendingReviewChange::DeleteComment,
+            move |backend, details| backend.delete_pending_review_comment(details, &remote_id),
+        );
+    }
+
+    /// Delete the viewer's pending review and every comment in it.
+    pub fn discard_pending_review(&mut self) {
+        let Some(review) = self.pending_review.clone() else {
+            self.set_warning("No pending review to discard");
+            return;
+        };
+        self.spawn_pending_review_change(PendingReviewChange::Discard, move |backend, details| {
+            backend.discard_pending_review(details, &review)
+        });
+    }
+
+    /// Queue one pending-review change for the sync worker. Results land
+    /// in `poll_pr_pending_review_events`.
+    fn spawn_pending_review_change<F>(&mut self, change: PendingReviewChange, call: F)
+    where
+        F: FnOnce(&dyn ForgeBackend, &PullRequestDetails) -> Result<()> + Send + 'static,
+    {
+        let DiffSource::PullRequest(pr) = &self.diff_source else {
+            return;
+        };
+        let details = pr.details();
+        let local_checkout = self
+            .forge_backend
+            .as_deref()
+            .and_then(|backend| backend.local_checkout_path());
+        let show_pr_checks = self.show_pr_checks;
+        let show_pr_comments = self.show_pr_comments;
+        let forge_config = self.forge_config.clone();
+
+        let sync = self.pr_pending_review_sync.get_or_insert_with(|| {
+            PrPendingReviewSync::start(move |details| {
+                create_forge_backend(
+                    &details.repository,
+                    local_checkout.clone(),
+                    show_pr_checks,
+                    show_pr_comments,
+                    &forge_config,
+                )
+            })
+        });
+        let job = PrPendingReviewJob {
+            details,
+            change,
+            call: Box::new(call),
+        };
+        if sync.jobs.send(job).is_ok() {
+            sync.in_flight += 1;
+        }
+    }
+
+    /// Drain finished pending-review changes. Failed comment edits and
+    /// deletions warn; the next thread fetch restores the forge's copy.
+    pub fn poll_pr_pending_review_events(&mut self) {
+        let Some(sync) = self.pr_pending_review_sync.as_mut() else {
+            return;
+        };
+        let mut finished = Vec::new();
+        while let Ok(event) = sync.rx.try_recv() {
+            sync.in_flight = sync.in_flight.saturating_sub(1);
+            let PrPendingReviewEvent::Done {
+                repository,
+                pr_number,
+                change,
+                result,
+            } = event;
+            finished.push((repository, pr_number, change, result));
+        }
+        if sync.in_flight == 0 {
+            self.pr_pending_review_sync = None;
+        }
+
+        let forge = self.forge_display_name();
+        for (repository, pr_number, change, result) in finished {
+            let still_relevant = matches!(
+                &self.diff_source,
+                DiffSource::PullRequest(pr)
+                    if pr.key.repository == repository && pr.key.number == pr_number
+            );
+            if !still_relevant {
+                continue;
+            }
+            match (change, result) {
+                (PendingReviewChange::Discard, Ok(())) => {
+                    self.pending_review = None;
+                    self.prune_locked_comments();
+                    let _ = self.save_current_session_merging_external();
+                    self.set_message(format!("Discarded pending {forge} review"));
+                    self.refetch_pr_threads();
+                }
+                (PendingReviewChange::Discard, Err(e)) => {
+                    self.set_error(format!("Failed to discard pending {forge} review: {e}"));
+                }
+                (PendingReviewChange::UpdateComment, Err(e)) => {
+                    self.set_warning(format!("Failed to update pending comment on {forge}: {e}"));
+                }
+                (PendingReviewChange::DeleteComment, Err(e)) => {
+                    self.set_warning(format!("Failed to delete pending comment on {forge}: {e}"));
+                }
+                (_, Ok(())) => {}
+            }
+        }
+    }
+
+    /// After a publish through the pending review: everything that was in
+    /// it is now part of a submitted review.
+    pub(in crate::app) fn settle_published_pending_review(&mut self) {
+        self.pending_review = None;
+        for comment in
+            self.session
+                .review_comments
+                .iter_mut()
+                .chain(self.session.files.values_mut().flat_map(|review| {
+                    review
+                        .file_comments
+                        .iter_mut()
+                        .chain(review.line_comments.values_mut().flatten())
+                }))
+        {
+            if comment.lifecycle_state == CommentLifecycleState::PushedDraft {
+                comment.lifecycle_state = CommentLifecycleState::Submitted;
+            }
+        }
+    }
+}
@@ -45,6 +45,7 @@ impl App {
         self.forge_review_summaries = Vec::new();
         self.forge_review_threads_loading = false;
         self.pr_threads_rx = None;
+        self.pending_review = None;
         // Latest known remote head — equal to the session head at open time;
         // refreshed by future `gh pr view` calls in PR 6.
         self.current_pr_head = Some(details.head_sha.clone());
@@ -327,12 +328,14 @@ impl App {
         let base_sha = current.base_sha.clone();
         let show_pr_checks = self.show_pr_checks;
         let show_pr_comments = self.show_pr_comments;
+        let forge_config = self.forge_config.clone();
         std::thread::spawn(move || {
             let backend = create_forge_backend(
                 &repository,
                 local_checkout,
                 show_pr_checks,
                 show_pr_comments,
+                &forge_config,
             );
             let details = crate::forge::traits::PullRequestDetails {
                 repository: repository.clone(),
@@ -490,12 +493,14 @@ impl App {
         let pr_number = current.key.number;
         let show_pr_checks = self.show_pr_checks;
         let show_pr_comments = self.show_pr_comments;
+        let forge_config = self.forge_config.clone();
         std::thread::spawn(move || {
             let backend = create_forge_backend(
                 &repository,
                 local_checkout,
                 show_pr_checks,
                 show_pr_comments,
+                &forge_config,
             );
             let target =
                 PullRequestTarget::with_repository(repository, pr_number, pr_number.to_string());
@@ -576,13 +581,14 @@ impl App {
         let head_changed = opened.details.head_sha != request.head_sha;
         if head_changed {
             let details_for_threads = opened.details.clone();
-            let opened = self.opened_pr_with_new_head_session(opened)?;
             let backend = create_forge_backend(
                 &request.repository,
                 local_checkout.clone(),
                 self.show_pr_checks,
                 self.show_pr_comments,
+                &self.forge_config,
             );
+            let opened = self.opened_pr_with_new_head_session(opened, backend.as_ref())?;
             let previous_message = self.message.clone();
             self.enter_pr_diff_mode(backend, opened)?;
             self.spawn_pr_threads_fetch(&details_for_threads, local_checkout);
@@ -642,6 +648,7 @@ impl App {
             local_checkout.clone(),
             self.show_pr_checks,
             self.show_pr_comments,
+            &self.forge_config,
         );
         self.reload_pull_request_with_backend(backend, local_checkout)
     }
@@ -679,7 +686,7 @@ impl App {
         if head_changed {
             // Save the old-head session before switching so drafts persist.
             let details_for_threads = opened.details.clone();
-            let opened = self.opened_pr_with_new_head_session(opened)?;
+            let opened = self.opened_pr_with_new_head_session(opened, backend.as_ref())?;
             self.enter_pr_diff_mode(backend, opened)?;
             // Fetch threads against the new head; old-head threads stay
             // tied to the old session and are dropped here.
@@ -732,17 +739,23 @@ impl App {
 
         let show_pr_checks = self.show_pr_checks;
         let show_pr_comments = self.show_pr_comments;
+        let forge_config = self.forge_config.clone();
         std::thread::spawn(move || {
             // Canonical resolution (fork parent lookup) is GitHub-only.
             let canonical = if skip_resolution || origin.kind != ForgeKind::GitHub {
                 override_repo.unwrap_or(origin)
             } else {
                 use crate::forge::canonical::resolve_canonical_repository;
-                use crate::forge::github::gh::SystemGhRunner;
-                let runner = SystemGhRunner;
-                resolve_canonical_repository(&origin, override_repo.as_ref(), &runner)
+                let runner = gh_runner(&forge_config);
+                resolve_canonical_repository(&origin, override_repo.as_ref(), runner.as_ref())
             };
-            let backend = create_forge_backend(&canonical, None, show_pr_checks, show_pr_comments);
+            let backend = create_forge_backend(
+                &canonical,
+                None,
+                show_pr_checks,
+                show_pr_comments,
+                &forge_config,
+            );
             let query =
                 PullRequestListQuery::first_page_with_scope(canonical.clone(), PR_PAGE_SIZE, scope);
             let result = backend
@@ -768,8 +781,15 @@ impl App {
 
         let show_pr_checks = self.show_pr_checks;
         let show_pr_comments = self.show_pr_comments;
+        let forge_config = self.forge_config.clone();
         std::thread::spawn(move || {
-            let backend = create_forge_backend(&repository, None, show_pr_checks, show_pr_comments);
+            let backend = create_forge_backend(
+                &repository,
+                None,
+                show_pr_checks,
+                show_pr_comments,
+                &forge_config,
+            );
             let query = PullRequestListQuery {
                 repository,
                 already_loaded,
@@ -919,12 +939,14 @@ impl App {
         let local_checkout = self.local_checkout_for(&summary.repository);
         let show_pr_checks = self.show_pr_checks;
         let show_pr_comments = self.show_pr_comments;
+        let forge_config = self.forge_config.clone();
         std::thread::spawn(move || {
             let backend = create_forge_backend(
                 &summary_repo,
                 local_checkout,
                 show_pr_checks,
                 show_pr_comments,
+                &forge_config,
             );
             let target =
                 PullRequestTarget::with_repository(summary_repo, pr_number, pr_number.to_string());
@@ -1013,13 +1035,14 @@ impl App {
             local_checkout.as_deref(),
             highlighter,
         )?;
-        let opened = Self::opened_pr_with_persisted_session(opened)?;
         let backend = create_forge_backend(
             &request.repository,
             local_checkout.clone(),
             self.show_pr_checks,
             self.show_pr_comments,
+            &self.forge_config,
         );
+        let opened = Self::opened_pr_with_persisted_session(opened, backend.as_ref(), highlighter)?;
         let previous_message = self.message.clone();
         self.enter_pr_diff_mode(backend, opened)?;
         // Kick the remote-thread fetch off on a fresh background thread.
@@ -1055,6 +1078,8 @@ impl App {
         let head_sha = details.head_sha.clone();
         let show_pr_checks = self.show_pr_checks;
         let show_pr_comments = self.show_pr_comments;
+        let forge_config = self.forge_config.clone();
+        let sync_viewed = forge_config.sync_viewed;
 
         std::thread::spawn(move || {
             let backend = create_forge_backend(
@@ -1062,6 +1087,7 @@ impl App {
                 local_checkout,
                 show_pr_checks,
                 show_pr_comments,
+                &forge_config,
             );
             let threads = backend
                 .list_review_threads(&details_clone)
@@ -1069,12 +1095,28 @@ impl App {
             let summaries = backend
                 .list_review_summaries(&details_clone)
                 .map_err(|e| e.to_string());
+            let viewed_files = if sync_viewed && backend.supports_viewed_files() {
+                backend
+                    .list_viewed_files(&details_clone)
+                    .unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+            let pending_review = if backend.supports_pending_reviews() {
+                backend
+                    .get_pending_review(&details_clone)
+                    .map_err(|e| e.to_string())
+            } else {
+                Ok(None)
+            };
             let _ = tx.send(PrThreadsEvent::Done {
                 repository,
                 pr_number,
                 head_sha,
                 threads,
                 summaries,
+                viewed_files,
+                pending_review,
             });
         });
     }
@@ -1100,6 +1142,8 @@ impl App {
                 head_sha,
                 threads,
                 summaries,
+                viewed_files,
+                pending_review,
             } => {
                 // Validate against the currently open PR. If the user has
                 // opened a different PR (or left PR mode) while the fetch
@@ -1119,6 +1163,7 @@ impl App {
                 if !still_relevant {
                     return;
                 }
+                self.seed_reviewed_from_viewed_files(&viewed_files);
                 let mut had_error = false;
                 let mut threads_loaded = false;
                 match threads {
@@ -1148,8 +1193,21 @@ impl App {
                         }
                     }
                 }
+                let pending_review = match pending_review {
+                    Ok(review) => review,
+                    Err(e) => {
+                        // Without the pending review the pushed drafts
+                        // cannot be re-imported, so keep them as they are.
+                        if !had_error {
+                            self.set_warning(format!("Failed to load pending review: {e}"));
+                        }
+                        had_error = true;
+                        None
+                    }
+                };
                 if !had_error {
                     self.prune_locked_comments();
+                    self.import_pending_review(pending_review);
                     let _ = self.save_current_session_merging_external();
                 }
                 self.rebuild_annotations();
@@ -1246,7 +1304,7 @@ impl App {
             local_checkout.as_deref(),
             highlighter,
         )?;
-        let opened = Self::opened_pr_with_persisted_session(opened)?;
+        let opened = Self::opened_pr_with_persisted_session(opened, backend.as_ref(), highlighter)?;
         // Sync thread + summary fetch — tests assert on
         // `app.forge_review_threads`/`forge_review_summaries` immediately
         // after this returns.
@@ -1256,10 +1314,14 @@ impl App {
         let summaries = backend
             .list_review_summaries(&opened.details)
             .unwrap_or_default();
+        let pending_review = backend
+            .get_pending_review(&opened.details)
+            .unwrap_or_default();
         self.enter_pr_diff_mode(backend, opened)?;
         self.forge_review_threads = crate::forge::remote_comments::dedupe_threads(threads);
         self.forge_review_summaries = summaries;
         self.prune_locked_comments();
+        self.import_pending_review(pending_review);
         self.rebuild_annotations();
         Ok(())
     }
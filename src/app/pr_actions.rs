@@ -0,0 +1,176 @@
+use super::*;
+
+use crate::forge::traits::PullRequestTarget;
+
+impl App {
+    /// Queue a PR-level action behind the confirmation modal. Refuses
+    /// outside PR mode, on a merged or closed PR, and while another action
+    /// is still running.
+    pub fn request_pr_action(&mut self, action: PrAction) {
+        let DiffSource::PullRequest(pr) = &self.diff_source else {
+            self.set_warning(format!(
+                ":{} only applies in PR mode",
+                command_name(&action)
+            ));
+            return;
+        };
+        let reason = self
+            .pr_info
+            .as_ref()
+            .and_then(|info| info.details.read_only_reason())
+            .or_else(|| pr.read_only_reason());
+        if let Some(reason) = reason {
+            self.set_warning(format!("Cannot {}: PR is {reason}", action.verb()));
+            return;
+        }
+        if self.pr_action_rx.is_some() {
+            self.set_message("A PR action is already in progress");
+            return;
+        }
+        self.enter_confirm_mode(ConfirmAction::PrAction(action));
+    }
+
+    /// Run the action queued behind the confirmation prompt.
+    pub fn confirm_pr_action(&mut self) {
+        let pending = self.pending_confirm.take();
+        self.exit_confirm_mode();
+        if let Some(ConfirmAction::PrAction(action)) = pending {
+            self.spawn_pr_action(action);
+        }
+    }
+
+    /// Send the action to the forge on a background thread. The thread
+    /// re-reads the PR first so a merge or close that happened elsewhere
+    /// stops it, then refreshes the description panel afterwards.
+    fn spawn_pr_action(&mut self, action: PrAction) {
+        let DiffSource::PullRequest(pr) = &self.diff_source else {
+            return;
+        };
+        let repository = pr.key.repository.clone();
+        let pr_number = pr.key.number;
+        let reviewed_head = pr.key.head_sha.clone();
+        let local_checkout = self
+            .forge_backend
+            .as_deref()
+            .and_then(|backend| backend.local_checkout_path());
+        let show_pr_checks = self.show_pr_checks;
+        let show_pr_comments = self.show_pr_comments;
+        let forge_config = self.forge_config.clone();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.pr_action_rx = Some(rx);
+        self.set_message(format!("Running :{}...", command_name(&action)));
+        std::thread::spawn(move || {
+            let backend = create_forge_backend(
+                &repository,
+                local_checkout,
+                show_pr_checks,
+                show_pr_comments,
+                &forge_config,
+            );
+            let target = PullRequestTarget::with_repository(
+                repository.clone(),
+                pr_number,
+                pr_number.to_string(),
+            );
+            let result = backend
+                .get_pull_request(target.clone())
+                .and_then(|mut details| {
+                    if let Some(reason) = details.read_only_reason() {
+                        return Err(TuicrError::Forge(format!("PR is {reason}")));
+                    }
+                    // Merge what was reviewed: the forge refuses if the
+                    // head has moved on since.
+                    details.head_sha = reviewed_head;
+                    match &action {
+                        PrAction::Merge(method) => backend.merge_pull_request(&details, *method),
+                        PrAction::Close => backend.close_pull_request(&details),
+                        PrAction::RequestReviewer(user) => backend.request_reviewer(&details, user),
+                        PrAction::AddLabel(label) => backend.add_label(&details, label),
+                    }
+                })
+                .map(|()| backend.get_pull_request_info(target).ok())
+                .map_err(|e| e.to_string());
+            let _ = tx.send(PrActionEvent::Done {
+                repository,
+                pr_number,
+                action,
+                result,
+            });
+        });
+    }
+
+    /// Pump a finished PR action. Success swaps in the refreshed PR info,
+    /// which also flips the session read-only after a merge or close.
+    pub fn poll_pr_action_events(&mut self) {
+        let Some(rx) = self.pr_action_rx.as_ref() else {
+            return;
+        };
+        let event = match rx.try_recv() {
+            Ok(e) => e,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                self.pr_action_rx = None;
+                return;
+            }
+        };
+        self.pr_action_rx = None;
+
+        let PrActionEvent::Done {
+            repository,
+            pr_number,
+            action,
+            result,
+        } = event;
+        let still_relevant = matches!(
+            &self.diff_source,
+            DiffSource::PullRequest(pr)
+                if pr.key.repository == repository && pr.key.number == pr_number
+        );
+        if !still_relevant {
+            return;
+        }
+        let forge = self.forge_display_name();
+        match result {
+            Ok(info) => {
+                if let Some(info) = info {
+                    self.apply_refreshed_pr_info(info);
+                }
+                self.set_message(done_message(&action, forge));
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to {} on {forge}: {e}", action.verb()));
+            }
+        }
+    }
+
+    /// Replace the description panel's info and carry the PR's state over
+    /// to the diff source, so read-only checks see a merge or close.
+    fn apply_refreshed_pr_info(&mut self, info: crate::forge::traits::PullRequestInfo) {
+        if let DiffSource::PullRequest(pr) = &mut self.diff_source {
+            pr.state = info.details.state.clone();
+            pr.closed = info.details.closed;
+            pr.merged = info.details.merged_at.is_some();
+        }
+        self.pr_info = Some(info);
+        self.rebuild_annotations();
+    }
+}
+
+fn command_name(action: &PrAction) -> &'static str {
+    match action {
+        PrAction::Merge(_) => "merge",
+        PrAction::Close => "close",
+        PrAction::RequestReviewer(_) => "request-reviewer",
+        PrAction::AddLabel(_) => "label",
+    }
+}
+
+fn done_message(action: &PrAction, forge: &str) -> String {
+    match action {
+        PrAction::Merge(method) => format!("Merged PR on {forge} ({})", method.label()),
+        PrAction::Close => format!("Closed PR on {forge}"),
+        PrAction::RequestReviewer(user) => format!("Requested a review from {user} on {forge}"),
+        PrAction::AddLabel(label) => format!("Added label \"{label}\" on {forge}"),
+    }
+}
@@ -35,6 +35,12 @@ impl App {
             staged += 1;
         }
         self.set_message(format!("Staged {} reviewed file(s)", staged));
+        self.reload_after_staging();
+    }
+
+    /// Reloads the diff after the index or worktree changed underneath it,
+    /// leaving an empty view rather than an error once nothing is left.
+    pub(in crate::app) fn reload_after_staging(&mut self) {
         if let Err(TuicrError::NoChanges) = self.reload_diff_files() {
             self.diff_files.clear();
             self.diff_state = DiffState::default();
@@ -194,7 +200,13 @@ impl App {
         self.revealed_reviewed_file = None;
         if let Some(review) = self.session.get_file_mut(&path) {
             review.reviewed = !review.reviewed;
+            let reviewed = review.reviewed;
             self.dirty = true;
+            self.publish_control_event(crate::control::ControlEvent::FileReviewed {
+                path: path.clone(),
+                reviewed,
+            });
+            self.push_viewed_state(path, reviewed);
 
             // Update current_file_idx before rebuilding annotations:
             // single-file view filters annotations against it.
@@ -248,7 +260,7 @@ impl App {
         }
     }
 
-    fn hunk_at_cursor(&self) -> Option<(usize, usize)> {
+    pub(in crate::app) fn hunk_at_cursor(&self) -> Option<(usize, usize)> {
         match self.line_annotations.get(self.diff_state.cursor_line)? {
             AnnotatedLine::HunkHeader { file_idx, hunk_idx }
             | AnnotatedLine::DiffLine {
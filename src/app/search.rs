@@ -106,6 +106,61 @@ impl App {
         }
     }
 
+    /// `/` in the check-log view: select the next line matching the
+    /// pattern, starting at the selected one.
+    pub fn search_in_check_log_from_selection(&mut self) -> bool {
+        let pattern = self.search_buffer.clone();
+        if pattern.trim().is_empty() {
+            self.set_message("Search pattern is empty");
+            return false;
+        }
+        if let Some(log) = self.check_log.as_mut() {
+            log.last_search_pattern = Some(pattern.clone());
+        }
+        self.search_check_log(&pattern, true, true)
+    }
+
+    pub fn search_next_in_check_log(&mut self) -> bool {
+        self.repeat_check_log_search(true)
+    }
+
+    pub fn search_prev_in_check_log(&mut self) -> bool {
+        self.repeat_check_log_search(false)
+    }
+
+    fn repeat_check_log_search(&mut self, forward: bool) -> bool {
+        let Some(pattern) = self
+            .check_log
+            .as_ref()
+            .and_then(|log| log.last_search_pattern.clone())
+        else {
+            self.set_message("No previous log search");
+            return false;
+        };
+        self.search_check_log(&pattern, forward, false)
+    }
+
+    fn search_check_log(&mut self, pattern: &str, forward: bool, include_current: bool) -> bool {
+        let Some(log) = self.check_log.as_mut() else {
+            return false;
+        };
+        let found = find_search_match(
+            log.lines.len(),
+            log.selected,
+            forward,
+            include_current,
+            pattern,
+            |line_idx| log.lines.get(line_idx).cloned(),
+        );
+        let Some(line) = found else {
+            self.set_message(format!("No log matches for \"{pattern}\""));
+            return false;
+        };
+        log.selected = line;
+        log.scroll_offset = line.saturating_sub(log.viewport_height / 2);
+        true
+    }
+
     pub fn search_in_diff_from_cursor(&mut self) -> bool {
         let pattern = self.search_buffer.clone();
         if pattern.trim().is_empty() {
@@ -353,6 +408,14 @@ impl App {
                 let comment = comments.get(*comment_idx)?;
                 Some(Cow::Borrowed(comment.content.as_str()))
             }
+            AnnotatedLine::Diagnostic { diagnostic_idx, .. } => {
+                let diagnostic = self.diagnostics.get(*diagnostic_idx)?;
+                Some(Cow::Owned(format!(
+                    "{}: {}",
+                    diagnostic.title(),
+                    diagnostic.message
+                )))
+            }
             AnnotatedLine::Expander { gap_id, direction } => {
                 let arrow = match direction {
                     ExpandDirection::Down => "↓",
@@ -0,0 +1,224 @@
+use super::*;
+
+use crate::config::ExportTarget;
+use crate::output::generate_export_content;
+use crate::process::{CommandOutputErrorKind, run_command_output_with_stdin_timeout};
+
+impl App {
+    /// `:send [<target>]`: pipe the review export into an `[export.targets]`
+    /// command on a background thread. Without a name, the only configured
+    /// target is used.
+    pub fn send_to_export_target(&mut self, name: Option<&str>) {
+        let (name, target) = match self.resolve_export_target(name) {
+            Ok(found) => found,
+            Err(message) => {
+                self.set_warning(message);
+                return;
+            }
+        };
+        if self.export_send_rx.is_some() {
+            self.set_message("A :send is already running");
+            return;
+        }
+
+        let (session, remote_threads) = if target.only_new {
+            (unsent_comments(&self.session, &name), Vec::new())
+        } else {
+            (self.session.clone(), self.forge_review_threads.clone())
+        };
+        if target.only_new && !session.has_comments() {
+            self.set_message(format!("No new comments to send to {name}"));
+            return;
+        }
+        let export = ExportConfig {
+            format: Some(target.format.unwrap_or(self.export.format())),
+            ..self.export.clone()
+        };
+        let slug = self.session_slug();
+        let content = match generate_export_content(
+            &session,
+            &self.diff_files,
+            &self.diff_source,
+            &self.comment_types,
+            &export,
+            &remote_threads,
+            slug.as_deref(),
+        ) {
+            Ok(content) => content,
+            Err(e) => {
+                self.set_warning(format!("{e}"));
+                return;
+            }
+        };
+        let sent_ids = if target.only_new {
+            comment_ids(&session)
+        } else {
+            Vec::new()
+        };
+        // PR mode swaps in a synthetic root with no checkout behind it.
+        let cwd = Some(self.vcs_info.root_path.clone()).filter(|root| root.is_dir());
+        let timeout = target.timeout();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.export_send_rx = Some(rx);
+        self.set_message(format!("Sending review to {name}..."));
+        std::thread::spawn(move || {
+            let (shell, flag) = if cfg!(windows) {
+                ("cmd", "/C")
+            } else {
+                ("sh", "-c")
+            };
+            let result = run_command_output_with_stdin_timeout(
+                shell,
+                cwd.as_deref(),
+                [flag, target.command.as_str()],
+                &content,
+                timeout,
+            )
+            .map(|_| ())
+            .map_err(|e| match e.kind {
+                CommandOutputErrorKind::Unsuccessful => {
+                    let status = e
+                        .status
+                        .map_or("a signal".to_string(), |code| format!("status {code}"));
+                    match e.stderr.lines().find(|line| !line.trim().is_empty()) {
+                        Some(line) => format!("exited with {status}: {}", line.trim()),
+                        None => format!("exited with {status}"),
+                    }
+                }
+                CommandOutputErrorKind::TimedOut => e.stderr,
+                _ => format!("could not run `{}`: {}", target.command, e.stderr),
+            });
+            let _ = tx.send(ExportSendEvent::Done {
+                target: name,
+                sent_ids,
+                result,
+            });
+        });
+    }
+
+    fn resolve_export_target(
+        &self,
+        name: Option<&str>,
+    ) -> std::result::Result<(String, ExportTarget), String> {
+        let targets = &self.export.targets;
+        if targets.is_empty() {
+            return Err("No export targets configured; add [export.targets] to the config".into());
+        }
+        let name = match name {
+            Some(name) => name,
+            None if targets.len() == 1 => targets.keys().next().map(String::as_str).unwrap_or(""),
+            None => {
+                let names: Vec<_> = targets.keys().map(String::as_str).collect();
+                return Err(format!("Usage: :send <target> ({})", names.join(", ")));
+            }
+        };
+        match targets.get(name) {
+            Some(target) => Ok((name.to_string(), target.clone())),
+            None => {
+                let names: Vec<_> = targets.keys().map(String::as_str).collect();
+                Err(format!(
+                    "Unknown export target {name}; configured: {}",
+                    names.join(", ")
+                ))
+            }
+        }
+    }
+
+    /// Pump a finished `:send`. On success the comments an `only_new` target
+    /// received are stamped with its name so its next send skips them.
+    pub fn poll_export_send_events(&mut self) {
+        let Some(rx) = self.export_send_rx.as_ref() else {
+            return;
+        };
+        let event = match rx.try_recv() {
+            Ok(e) => e,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                self.export_send_rx = None;
+                return;
+            }
+        };
+        self.export_send_rx = None;
+
+        let ExportSendEvent::Done {
+            target,
+            sent_ids,
+            result,
+        } = event;
+        if let Err(e) = result {
+            self.set_error(format!("Send to {target} failed: {e}"));
+            return;
+        }
+        if sent_ids.is_empty() {
+            self.set_message(format!("Sent review to {target} (exit 0)"));
+            return;
+        }
+        let now = Utc::now();
+        let mut marked = 0;
+        for id in &sent_ids {
+            if let Some(comment) = find_comment_mut(&mut self.session, id) {
+                comment.sent_to.insert(target.clone(), now);
+                marked += 1;
+            }
+        }
+        self.dirty = true;
+        let noun = if marked == 1 { "comment" } else { "comments" };
+        let message = format!("Sent {marked} new {noun} to {target} (exit 0)");
+        match self.save_current_session_merging_external() {
+            Ok(_) => self.set_message(message),
+            Err(e) => self.set_warning(format!("{message}; autosave failed: {e}")),
+        }
+    }
+}
+
+/// The session with only the comments `target` has not received yet.
+fn unsent_comments(session: &ReviewSession, target: &str) -> ReviewSession {
+    let mut unsent = session.clone();
+    let is_unsent = |comment: &Comment| !comment.sent_to.contains_key(target);
+    unsent.review_comments.retain(is_unsent);
+    for review in unsent.files.values_mut() {
+        review.file_comments.retain(is_unsent);
+        for comments in review.line_comments.values_mut() {
+            comments.retain(is_unsent);
+        }
+        review
+            .line_comments
+            .retain(|_, comments| !comments.is_empty());
+    }
+    for replies in unsent.thread_replies.values_mut() {
+        replies.retain(is_unsent);
+    }
+    unsent
+        .thread_replies
+        .retain(|_, replies| !replies.is_empty());
+    unsent
+}
+
+fn comment_ids(session: &ReviewSession) -> Vec<String> {
+    let files = session.files.values().flat_map(|review| {
+        review
+            .file_comments
+            .iter()
+            .chain(review.line_comments.values().flatten())
+    });
+    session
+        .review_comments
+        .iter()
+        .chain(files)
+        .chain(session.thread_replies.values().flatten())
+        .map(|comment| comment.id.clone())
+        .collect()
+}
+
+/// Like [`ReviewSession::comment_mut`], but also searches thread replies.
+fn find_comment_mut<'a>(session: &'a mut ReviewSession, id: &str) -> Option<&'a mut Comment> {
+    if session.comment_mut(id).is_some() {
+        return session.comment_mut(id);
+    }
+    session
+        .thread_replies
+        .values_mut()
+        .flatten()
+        .find(|comment| comment.id == id)
+}
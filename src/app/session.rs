@@ -130,7 +130,10 @@ impl App {
     }
 
     fn mark_current_session_active_at(&mut self, path: &Path) {
-        if let Err(e) = crate::persistence::storage::mark_session_active(&self.session, path) {
+        let socket = self.control.as_ref().and_then(|hub| hub.socket_path());
+        if let Err(e) =
+            crate::persistence::storage::mark_session_active(&self.session, path, socket)
+        {
             self.set_warning(format!("Failed to mark active review session: {e}"));
         }
     }
@@ -165,8 +168,9 @@ impl App {
     }
 
     /// True while any forge background fetch (PR list/open/reload/threads/
-    /// submit) is in flight. Used by the main loop to keep redrawing so
-    /// spinners animate and results land without waiting for input.
+    /// submit/thread resolve) is in flight. Used by the main loop to keep
+    /// redrawing so spinners animate and results land without waiting for
+    /// input.
     pub fn has_pending_pr_work(&self) -> bool {
         self.pr_load_rx.is_some()
             || self.pr_open_rx.is_some()
@@ -174,6 +178,13 @@ impl App {
             || self.pr_range_reload_rx.is_some()
             || self.pr_threads_rx.is_some()
             || self.pr_submit_rx.is_some()
+            || self.pr_thread_resolve_rx.is_some()
+            || self.pr_viewed_sync.is_some()
+            || self.pr_pending_review_sync.is_some()
+            || self.pr_action_rx.is_some()
+            || self.check_log_rx.is_some()
+            || self.export_send_rx.is_some()
+            || self.diagnostics_rx.is_some()
     }
 
     pub fn reload_persisted_session_if_changed(&mut self, force: bool) -> Result<usize> {
@@ -554,10 +565,19 @@ impl App {
         }))
     }
 
+    /// Attach persisted local state to a freshly opened PR: the session for
+    /// the same head when one exists, otherwise drafts carried forward from
+    /// the most recent session for an older head of the same PR.
     pub(in crate::app) fn opened_pr_with_persisted_session(
         opened: crate::forge::pr_open::OpenedPullRequest,
+        backend: &dyn ForgeBackend,
+        highlighter: &SyntaxHighlighter,
     ) -> Result<crate::forge::pr_open::OpenedPullRequest> {
-        match Self::load_pr_session_for_opened(&opened)? {
+        let session = match Self::load_pr_session_for_opened(&opened)? {
+            Some(session) => Some(session),
+            None => Self::pr_session_from_previous_head(&opened, backend, highlighter)?,
+        };
+        match session {
             Some(session) => Ok(crate::forge::pr_open::OpenedPullRequest { session, ..opened }),
             None => Ok(opened),
         }
@@ -566,111 +586,27 @@ impl App {
     pub(in crate::app) fn opened_pr_with_new_head_session(
         &mut self,
         opened: crate::forge::pr_open::OpenedPullRequest,
+        backend: &dyn ForgeBackend,
     ) -> Result<crate::forge::pr_open::OpenedPullRequest> {
         self.save_current_session_merging_external()?;
         let previous_session = self.session.clone();
         let session = match Self::load_pr_session_for_opened(&opened)? {
             Some(session) => session,
-            None => Self::reviewed_state_carried_forward(
-                &previous_session,
-                opened.session.clone(),
-                &opened.diff_files,
-            ),
+            None => {
+                let interdiff = Self::head_interdiff(
+                    &previous_session,
+                    &opened,
+                    backend,
+                    self.theme.syntax_highlighter(),
+                );
+                Self::reviewed_state_carried_forward(
+                    &previous_session,
+                    opened.session.clone(),
+                    &opened.diff_files,
+                    interdiff.as_deref(),
+                )
+            }
         };
         Ok(crate::forge::pr_open::OpenedPullRequest { session, ..opened })
     }
-
-    pub(in crate::app) fn reviewed_state_carried_forward(
-        previous: &ReviewSession,
-        next: ReviewSession,
-        diff_files: &[DiffFile],
-    ) -> ReviewSession {
-        let file_by_path: HashMap<_, _> = diff_files
-            .iter()
-            .map(|file| (file.display_path().clone(), file))
-            .collect();
-        let files = next
-            .files
-            .into_iter()
-            .map(|(path, review)| {
-                Self::file_review_carried_forward(path, review, previous, &file_by_path)
-            })
-            .collect();
-        let review_comments = previous
-            .review_comments
-            .iter()
-            .filter(|comment| !comment.is_locked())
-            .cloned()
-            .collect();
-
-        ReviewSession {
-            files,
-            review_comments,
-            ..next
-        }
-    }
-
-    fn file_review_carried_forward(
-        path: PathBuf,
-        review: FileReview,
-        previous: &ReviewSession,
-        file_by_path: &HashMap<PathBuf, &DiffFile>,
-    ) -> (PathBuf, FileReview) {
-        let Some(file) = file_by_path.get(&path) else {
-            return (path, review);
-        };
-        let Some(previous_review) = previous.files.get(&path) else {
-            return (path, review);
-        };
-
-        let unchanged_file = previous_review.content_hash == Some(file.content_hash);
-        let valid_hunks: HashSet<_> = file.hunk_review_keys().into_iter().collect();
-        let reviewed_hunks = previous_review
-            .reviewed_hunks
-            .iter()
-            .filter(|key| valid_hunks.contains(*key))
-            .cloned()
-            .collect();
-        let (file_comments, line_comments) = if unchanged_file {
-            (
-                previous_review
-                    .file_comments
-                    .iter()
-                    .filter(|comment| !comment.is_locked())
-                    .cloned()
-                    .collect(),
-                Self::line_draft_comments_carried_forward(previous_review),
-            )
-        } else {
-            (review.file_comments, review.line_comments)
-        };
-
-        (
-            path,
-            FileReview {
-                reviewed: unchanged_file && previous_review.reviewed,
-                reviewed_hunks,
-                file_comments,
-                line_comments,
-                ..review
-            },
-        )
-    }
-
-    fn line_draft_comments_carried_forward(
-        previous_review: &FileReview,
-    ) -> HashMap<u32, Vec<Comment>> {
-        previous_review
-            .line_comments
-            .iter()
-            .filter_map(|(line, comments)| {
-                let drafts: Vec<_> = comments
-                    .iter()
-                    .filter(|comment| !comment.is_locked())
-                    .cloned()
-                    .collect();
-                (!drafts.is_empty()).then_some((*line, drafts))
-            })
-            .collect()
-    }
 }
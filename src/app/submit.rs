@@ -23,7 +23,8 @@ impl App {
         skip_confirm: bool,
     ) {
         use crate::forge::submit::{
-            CommentAnchor, InlineComment, ResolverAction, UnmappableItem, map_comment,
+            CommentAnchor, InlineComment, ResolverAction, SubmitEvent, UnmappableItem,
+            collect_thread_replies, map_comment,
         };
 
         let DiffSource::PullRequest(pr) = &self.diff_source else {
@@ -56,6 +57,7 @@ impl App {
         // Source of truth for the diff: when the inline commit selector is
         // showing a strict subset, `range_diff_files` carries the merged
         // subset diff; otherwise `diff_files` is canonical.
+        let forge = pr.key.repository.kind;
         let files: Vec<&DiffFile> = match self.range_diff_files.as_ref() {
             Some(range) => range.iter().collect(),
             None => self.diff_files.iter().collect(),
@@ -78,7 +80,13 @@ impl App {
                 }
                 total_local_drafts += 1;
                 bucket_mapping(
-                    map_comment(comment, CommentAnchor::FileLevel, file, &self.forge_config),
+                    map_comment(
+                        comment,
+                        CommentAnchor::FileLevel,
+                        file,
+                        &self.forge_config,
+                        forge,
+                    ),
                     &mut mappable,
                     &mut unmappable,
                 );
@@ -100,7 +108,7 @@ impl App {
                         }
                     };
                     bucket_mapping(
-                        map_comment(comment, anchor, file, &self.forge_config),
+                        map_comment(comment, anchor, file, &self.forge_config, forge),
                         &mut mappable,
                         &mut unmappable,
                     );
@@ -108,12 +116,28 @@ impl App {
             }
         }
 
+        let replies = if event == SubmitEvent::Draft {
+            Vec::new()
+        } else {
+            collect_thread_replies(
+                &self.session.thread_replies,
+                &self.forge_review_threads,
+                &self.forge_config,
+            )
+        };
+
         // Approve is the one event that's meaningful with no comments — a
-        // bare "LGTM" approval. Every other event needs at least one local
-        // draft comment or a review-level comment, otherwise there's
-        // nothing to submit.
-        let bare_allowed = matches!(event, crate::forge::submit::SubmitEvent::Approve);
-        if !bare_allowed && total_local_drafts == 0 && self.session.review_comments.is_empty() {
+        // bare "LGTM" approval. Publishing a pending review is the other:
+        // its comments are already on the forge. Every other event needs at
+        // least one local draft comment, a review-level comment, or a
+        // thread reply, otherwise there's nothing to submit.
+        let bare_allowed = matches!(event, SubmitEvent::Approve)
+            || (self.pending_review.is_some() && event != SubmitEvent::Draft);
+        if !bare_allowed
+            && total_local_drafts == 0
+            && self.session.review_comments.is_empty()
+            && replies.is_empty()
+        {
             self.set_warning("Nothing to submit — no local-draft comments");
             return;
         }
@@ -125,6 +149,7 @@ impl App {
             mappable,
             unmappable,
             resolver_choices,
+            replies,
             resolver_cursor: 0,
             commit_id,
             skip_confirm,
@@ -153,9 +178,15 @@ impl App {
         self.input_mode = InputMode::SubmitActionPicker;
     }
 
+    /// Rows in the action picker: every event, plus the discard row while
+    /// a pending review exists.
+    pub fn submit_picker_rows(&self) -> usize {
+        SUBMIT_PICKER_EVENTS.len() + usize::from(self.pending_review.is_some())
+    }
+
     /// Move the action-picker cursor down by one row, wrapping at the end.
     pub fn submit_picker_cursor_down(&mut self) {
-        let total = SUBMIT_PICKER_EVENTS.len();
+        let total = self.submit_picker_rows();
         if total > 0 {
             self.submit_picker_cursor = (self.submit_picker_cursor + 1) % total;
         }
@@ -163,15 +194,23 @@ impl App {
 
     /// Move the action-picker cursor up by one row, wrapping at the start.
     pub fn submit_picker_cursor_up(&mut self) {
-        let total = SUBMIT_PICKER_EVENTS.len();
+        let total = self.submit_picker_rows();
         if total > 0 {
             self.submit_picker_cursor = (self.submit_picker_cursor + total - 1) % total;
         }
     }
 
     /// Confirm the action picker selection: dispatch into preflight with the
-    /// chosen event and `skip_confirm = true`.
+    /// chosen event and `skip_confirm = true`. The discard row still asks
+    /// for confirmation, since it deletes comments on the forge.
     pub fn submit_picker_confirm(&mut self) {
+        if self.pending_review.is_some() && self.submit_picker_cursor == SUBMIT_PICKER_EVENTS.len()
+        {
+            self.submit_picker_cursor = 0;
+            self.input_mode = InputMode::Normal;
+            self.request_discard_pending_review();
+            return;
+        }
         let Some(event) = SUBMIT_PICKER_EVENTS
             .get(self.submit_picker_cursor)
             .map(|(_, ev)| *ev)
@@ -183,6 +222,28 @@ impl App {
         self.start_submit_with(event, true);
     }
 
+    /// `:submit discard`: ask before deleting the pending review.
+    pub fn request_discard_pending_review(&mut self) {
+        if !matches!(self.diff_source, DiffSource::PullRequest(_)) {
+            self.set_warning(":submit only applies in PR mode");
+            return;
+        }
+        if self.pending_review.is_none() {
+            self.set_warning("No pending review to discard");
+            return;
+        }
+        self.enter_confirm_mode(ConfirmAction::DiscardPendingReview);
+    }
+
+    /// Runs the discard queued behind the confirmation prompt.
+    pub fn confirm_discard_pending_review(&mut self) {
+        let pending = self.pending_confirm.take();
+        self.exit_confirm_mode();
+        if pending == Some(ConfirmAction::DiscardPendingReview) {
+            self.discard_pending_review();
+        }
+    }
+
     /// Cancel the action picker without entering preflight.
     pub fn cancel_submit_action_picker(&mut self) {
         self.input_mode = InputMode::Normal;
@@ -326,10 +387,12 @@ impl App {
             mappable: state.mappable.clone(),
             summary_comment_ids,
             review_comment_ids,
+            reply_comment_ids: state.replies.iter().map(|r| r.comment_id.clone()).collect(),
             moved_to_summary_count: summary_items.len(),
             head_sha_snapshot: state.commit_id.clone(),
             repository: pr.key.repository.clone(),
             pr_number: pr.key.number,
+            extends_pending_review: self.pending_review.is_some(),
             started_at: Instant::now(),
         };
         self.pr_submit_state = Some(in_flight.clone());
@@ -348,9 +411,20 @@ impl App {
         let head_sha = in_flight.head_sha_snapshot.clone();
         let event = in_flight.event;
         let mappable = in_flight.mappable.clone();
+        let replies = state.replies;
         let commit_id = state.commit_id.clone();
+        // A Comment submit whose only content is thread replies has no
+        // review to create; every other event still records one, as does
+        // publishing a pending review.
+        let pending_review = self.pending_review.clone();
+        let creates_review = !mappable.is_empty()
+            || !body.is_empty()
+            || replies.is_empty()
+            || pending_review.is_some()
+            || event != crate::forge::submit::SubmitEvent::Comment;
         let show_pr_checks = self.show_pr_checks;
         let show_pr_comments = self.show_pr_comments;
+        let forge_config = self.forge_config.clone();
 
         std::thread::spawn(move || {
             let backend = create_forge_backend(
@@ -358,6 +432,7 @@ impl App {
                 local_checkout,
                 show_pr_checks,
                 show_pr_comments,
+                &forge_config,
             );
             // Need PR details for repo/owner routing; refetch lightly via
             // the same target the user opened with.
@@ -366,25 +441,41 @@ impl App {
                 pr_number,
                 pr_number.to_string(),
             );
-            let result = match backend.get_pull_request(target) {
-                Ok(details) => backend
-                    .create_review(
-                        &details,
-                        CreateReviewRequest {
-                            event,
-                            commit_id: &commit_id,
-                            body: &body,
-                            comments: &mappable,
-                        },
-                    )
-                    .map_err(|e| e.to_string()),
-                Err(e) => Err(e.to_string()),
-            };
+            let mut sent_reply_ids = Vec::new();
+            let result = backend
+                .get_pull_request(target)
+                .and_then(|details| {
+                    // Replies first: a failure stops before the review is
+                    // created, so a retry never duplicates the review.
+                    for reply in &replies {
+                        backend.reply_to_review_thread(&details, &reply.thread, &reply.body)?;
+                        sent_reply_ids.push(reply.comment_id.clone());
+                    }
+                    if !creates_review {
+                        return Ok(None);
+                    }
+                    let request = CreateReviewRequest {
+                        event,
+                        commit_id: &commit_id,
+                        body: &body,
+                        comments: &mappable,
+                    };
+                    // GitHub allows one pending review per viewer, so an
+                    // existing one absorbs the new comments and is what
+                    // gets published.
+                    match &pending_review {
+                        Some(pending) => backend.extend_pending_review(&details, pending, request),
+                        None => backend.create_review(&details, request),
+                    }
+                    .map(Some)
+                })
+                .map_err(|e| e.to_string());
             let _ = tx.send(PrSubmitEvent::Done {
                 repository,
                 pr_number,
                 head_sha,
                 result,
+                sent_reply_ids,
             });
         });
         Ok(())
@@ -407,6 +498,7 @@ impl App {
             pr_number,
             head_sha,
             result,
+            sent_reply_ids,
         } = event;
 
         // Stale-result discard: if the user reloaded the PR mid-submit, the
@@ -423,7 +515,44 @@ impl App {
             return;
         }
 
-        self.finish_pr_submit(in_flight, result);
+        self.apply_sent_thread_replies(&sent_reply_ids);
+        match result {
+            Ok(Some(response)) => self.finish_pr_submit(in_flight, Ok(response)),
+            Ok(None) => {
+                let _ = self.save_current_session_merging_external();
+                let count = sent_reply_ids.len();
+                let noun = if count == 1 { "reply" } else { "replies" };
+                self.set_message(format!(
+                    "Posted {count} thread {noun} to {}",
+                    self.forge_display_name()
+                ));
+                self.refetch_pr_threads();
+            }
+            Err(e) => {
+                if !sent_reply_ids.is_empty() {
+                    let _ = self.save_current_session_merging_external();
+                }
+                self.finish_pr_submit(in_flight, Err(e));
+            }
+        }
+    }
+
+    /// Lock the queued thread replies that reached the forge. Like sent
+    /// review comments, they stay visible until the next thread fetch.
+    pub fn apply_sent_thread_replies(&mut self, comment_ids: &[String]) {
+        use crate::model::comment::CommentLifecycleState;
+
+        if comment_ids.is_empty() {
+            return;
+        }
+        for replies in self.session.thread_replies.values_mut() {
+            for reply in replies.iter_mut() {
+                if comment_ids.contains(&reply.id) {
+                    reply.lifecycle_state = CommentLifecycleState::Submitted;
+                }
+            }
+        }
+        self.rebuild_annotations();
     }
 
     /// Human-readable name of the forge backing the current PR/MR review.
@@ -465,6 +594,11 @@ impl App {
         };
 
         self.apply_submit_success(&in_flight, &response);
+        let published_pending =
+            in_flight.extends_pending_review && in_flight.event != SubmitEvent::Draft;
+        if published_pending {
+            self.settle_published_pending_review();
+        }
 
         // Post-submit save — captures the lifecycle transitions.
         let _ = self.save_current_session_merging_external();
@@ -490,15 +624,38 @@ impl App {
                     )
                 }
             }
-            _ => format!(
-                "Submitted {forge_name} review #{}: {} inline, {} moved to summary",
-                response.id, inline_count, summary_count,
-            ),
+            _ => {
+                let reply_count = in_flight.reply_comment_ids.len();
+                let replies = match reply_count {
+                    0 => String::new(),
+                    1 => ", 1 thread reply".to_string(),
+                    n => format!(", {n} thread replies"),
+                };
+                let verb = if published_pending {
+                    "Published pending"
+                } else {
+                    "Submitted"
+                };
+                format!(
+                    "{verb} {forge_name} review #{}: {} inline, {} moved to summary{replies}",
+                    response.id, inline_count, summary_count,
+                )
+            }
         };
         if in_flight.event != SubmitEvent::Draft {
             self.mark_pr_commits_reviewed_through(&in_flight.head_sha_snapshot);
         }
         self.set_message(message);
+        self.publish_control_event(crate::control::ControlEvent::SubmitCompleted {
+            event: match in_flight.event {
+                SubmitEvent::Comment => "comment",
+                SubmitEvent::Approve => "approve",
+                SubmitEvent::RequestChanges => "request_changes",
+                SubmitEvent::Draft => "draft",
+            }
+            .to_string(),
+            url: response.html_url.clone(),
+        });
 
         // Refetch remote threads so the just-submitted comments appear immediately.
         self.refetch_pr_threads();
@@ -572,5 +729,9 @@ impl App {
             }
             review.line_comments.retain(|_, v| !v.is_empty());
         }
+        for replies in self.session.thread_replies.values_mut() {
+            replies.retain(|c| !c.is_locked());
+        }
+        self.session.thread_replies.retain(|_, v| !v.is_empty());
     }
 }
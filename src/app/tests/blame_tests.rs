@@ -0,0 +1,321 @@
+use crate::app::*;
+use crate::handler::{handle_confirm_action, handle_diff_action};
+use crate::input::keybindings::Action;
+use crate::vcs::BlameLine;
+use chrono::TimeZone;
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+const BLAMED_COMMIT: &str = "c0ffee1234567890c0ffee1234567890c0ffee12";
+
+/// Fake `VcsBackend` that blames line 1 of every file to `BLAMED_COMMIT` and
+/// the rest to the working copy, and records the snapshot each blame was
+/// taken at.
+struct BlameVcs {
+    info: VcsInfo,
+    files: Vec<DiffFile>,
+    blamed_at: Arc<Mutex<Vec<Option<String>>>>,
+    commit_diffs: Arc<AtomicUsize>,
+}
+
+impl VcsBackend for BlameVcs {
+    fn info(&self) -> &VcsInfo {
+        &self.info
+    }
+
+    fn get_working_tree_diff(&self, _highlighter: &SyntaxHighlighter) -> Result<Vec<DiffFile>> {
+        Ok(self.files.clone())
+    }
+
+    fn get_commit_range_diff(
+        &self,
+        _revision_range: &ResolvedRevisionRange<'_>,
+        _highlighter: &SyntaxHighlighter,
+    ) -> Result<Vec<DiffFile>> {
+        self.commit_diffs.fetch_add(1, Ordering::SeqCst);
+        Ok(self.files.clone())
+    }
+
+    fn get_commits_info(&self, ids: &[String]) -> Result<Vec<CommitInfo>> {
+        Ok(ids
+            .iter()
+            .map(|id| CommitInfo {
+                id: id.clone(),
+                short_id: id[..7].to_string(),
+                branch_name: None,
+                summary: "Teach the parser about tabs".to_string(),
+                body: Some("Longer explanation.".to_string()),
+                author: "Alice".to_string(),
+                time: chrono::Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap(),
+            })
+            .collect())
+    }
+
+    fn blame(&self, _file_path: &Path, ref_commit: Option<&str>) -> Result<Vec<BlameLine>> {
+        self.blamed_at
+            .lock()
+            .expect("blamed_at poisoned")
+            .push(ref_commit.map(str::to_string));
+        Ok(vec![
+            BlameLine {
+                commit_id: Some(BLAMED_COMMIT.to_string()),
+                author: "Alice".to_string(),
+                time: chrono::Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).single(),
+            },
+            BlameLine::uncommitted(),
+            BlameLine::uncommitted(),
+        ])
+    }
+
+    fn fetch_context_lines(
+        &self,
+        _file_path: &Path,
+        _file_status: FileStatus,
+        _ref_commit: Option<&str>,
+        _start_line: u32,
+        _end_line: u32,
+    ) -> Result<Vec<DiffLine>> {
+        Ok(Vec::new())
+    }
+
+    fn file_line_count(
+        &self,
+        _file_path: &Path,
+        _file_status: FileStatus,
+        _ref_commit: Option<&str>,
+    ) -> Result<u32> {
+        Ok(0)
+    }
+}
+
+fn diff_line(origin: LineOrigin, content: &str, old: Option<u32>, new: Option<u32>) -> DiffLine {
+    DiffLine {
+        origin,
+        content: content.to_string(),
+        old_lineno: old,
+        new_lineno: new,
+        highlighted_spans: None,
+    }
+}
+
+fn test_file() -> DiffFile {
+    let hunks = vec![DiffHunk {
+        header: "@@ -1,2 +1,3 @@".to_string(),
+        lines: vec![
+            diff_line(LineOrigin::Context, "keep", Some(1), Some(1)),
+            diff_line(LineOrigin::Deletion, "old", Some(2), None),
+            diff_line(LineOrigin::Addition, "new one", None, Some(2)),
+            diff_line(LineOrigin::Addition, "new two", None, Some(3)),
+        ],
+        old_start: 1,
+        old_count: 2,
+        new_start: 1,
+        new_count: 3,
+        raw_body: None,
+    }];
+    let content_hash = DiffFile::compute_content_hash(&hunks);
+    DiffFile {
+        old_path: None,
+        new_path: Some(PathBuf::from("src/lib.rs")),
+        status: FileStatus::Modified,
+        hunks,
+        is_binary: false,
+        is_too_large: false,
+        is_commit_message: false,
+        content_hash,
+        review_key: None,
+    }
+}
+
+struct Harness {
+    app: App,
+    blamed_at: Arc<Mutex<Vec<Option<String>>>>,
+    commit_diffs: Arc<AtomicUsize>,
+}
+
+impl Harness {
+    fn new() -> Self {
+        let info = VcsInfo {
+            root_path: PathBuf::from("/tmp"),
+            head_commit: "abc123".to_string(),
+            branch_name: Some("main".to_string()),
+            vcs_type: VcsType::Git,
+        };
+        let files = vec![test_file()];
+        let blamed_at = Arc::new(Mutex::new(Vec::new()));
+        let commit_diffs = Arc::new(AtomicUsize::new(0));
+        let vcs = BlameVcs {
+            info: info.clone(),
+            files: files.clone(),
+            blamed_at: Arc::clone(&blamed_at),
+            commit_diffs: Arc::clone(&commit_diffs),
+        };
+        let session = ReviewSession::new(
+            info.root_path.clone(),
+            info.head_commit.clone(),
+            info.branch_name.clone(),
+            SessionDiffSource::WorkingTree,
+        );
+        let app = App::build(
+            Box::new(vcs),
+            info,
+            Theme::dark(),
+            None,
+            false,
+            files,
+            session,
+            DiffSource::WorkingTree,
+            InputMode::Normal,
+            Vec::new(),
+            None,
+            None,
+        )
+        .expect("failed to build test app");
+        Self {
+            app,
+            blamed_at,
+            commit_diffs,
+        }
+    }
+
+    fn blamed_at(&self) -> Vec<Option<String>> {
+        self.blamed_at.lock().expect("blamed_at poisoned").clone()
+    }
+
+    fn move_to_line(&mut self, line_idx: usize) {
+        let idx = self
+            .app
+            .line_annotations
+            .iter()
+            .position(|line| {
+                matches!(
+                    line,
+                    AnnotatedLine::DiffLine { line_idx: l, .. } if *l == line_idx
+                )
+            })
+            .expect("diff line should be annotated");
+        self.app.diff_state.cursor_line = idx;
+    }
+
+    fn message(&self) -> &str {
+        self.app
+            .message
+            .as_ref()
+            .map(|m| m.content.as_str())
+            .unwrap_or_default()
+    }
+
+    fn screen(&mut self) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+        terminal
+            .draw(|frame| crate::ui::render(frame, &mut self.app))
+            .expect("draw frame");
+        let buffer = terminal.backend().buffer().clone();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol().to_string())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[test]
+fn should_load_blame_only_once_the_gutter_is_on() {
+    let mut h = Harness::new();
+    let plain_width = h.app.lineno_width();
+    assert!(h.blamed_at().is_empty());
+
+    h.app.set_show_blame(true);
+
+    assert_eq!(h.blamed_at(), vec![None]);
+    assert_eq!(h.app.lineno_width(), plain_width + BLAME_GUTTER_WIDTH);
+    assert_eq!(
+        h.app.blame_line(0, 1).and_then(|l| l.commit_id.as_deref()),
+        Some(BLAMED_COMMIT)
+    );
+
+    // Rebuilding the view reuses the cache instead of blaming again.
+    h.app.rebuild_annotations();
+    assert_eq!(h.blamed_at().len(), 1);
+
+    h.app.set_show_blame(false);
+    assert!(h.app.blame_line(0, 1).is_none());
+    assert_eq!(h.app.lineno_width(), plain_width);
+}
+
+#[test]
+fn should_draw_blame_on_new_side_lines_in_both_views() {
+    let mut h = Harness::new();
+    h.app.set_show_blame(true);
+
+    let unified = h.screen();
+    assert!(unified.contains("c0ffee1 Alice      2024-05-01    1"));
+    assert!(unified.contains("not committed yet"));
+
+    h.app.toggle_diff_view_mode();
+    let side_by_side = h.screen();
+    assert!(side_by_side.contains("c0ffee1 Alice      2024-05-01    1"));
+}
+
+#[test]
+fn should_open_the_blamed_commit_as_a_new_review() {
+    let mut h = Harness::new();
+    h.app.set_show_blame(true);
+    h.move_to_line(0);
+
+    handle_diff_action(&mut h.app, Action::ShowBlameCommit);
+    assert_eq!(h.app.input_mode, InputMode::Confirm);
+    assert!(matches!(
+        &h.app.pending_confirm,
+        Some(ConfirmAction::OpenBlameCommit(commit)) if commit.id == BLAMED_COMMIT
+    ));
+    assert!(h.screen().contains("Teach the parser about tabs"));
+
+    handle_confirm_action(&mut h.app, Action::ConfirmYes);
+
+    assert_eq!(h.commit_diffs.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        h.app.diff_source,
+        DiffSource::CommitRange(vec![BLAMED_COMMIT.to_string()])
+    );
+    assert_eq!(h.app.input_mode, InputMode::Normal);
+    assert_eq!(h.message(), "Reviewing commit c0ffee1");
+    // The new review is blamed at its own snapshot.
+    assert_eq!(h.blamed_at().last(), Some(&Some(BLAMED_COMMIT.to_string())));
+}
+
+#[test]
+fn should_not_open_a_popup_for_uncommitted_or_deleted_lines() {
+    let mut h = Harness::new();
+    h.app.set_show_blame(true);
+
+    h.move_to_line(2);
+    handle_diff_action(&mut h.app, Action::ShowBlameCommit);
+    assert_eq!(h.app.input_mode, InputMode::Normal);
+    assert_eq!(h.message(), "Line is not committed yet");
+
+    h.move_to_line(1);
+    handle_diff_action(&mut h.app, Action::ShowBlameCommit);
+    assert_eq!(h.app.input_mode, InputMode::Normal);
+    assert_eq!(h.message(), "No blame for this line");
+}
+
+#[test]
+fn should_close_the_popup_without_changing_the_review() {
+    let mut h = Harness::new();
+    h.app.set_show_blame(true);
+    h.move_to_line(0);
+    handle_diff_action(&mut h.app, Action::ShowBlameCommit);
+
+    handle_confirm_action(&mut h.app, Action::ConfirmNo);
+
+    assert_eq!(h.app.input_mode, InputMode::Normal);
+    assert!(h.app.pending_confirm.is_none());
+    assert_eq!(h.app.diff_source, DiffSource::WorkingTree);
+    assert_eq!(h.commit_diffs.load(Ordering::SeqCst), 0);
+}
@@ -72,6 +72,7 @@ fn diff_file(path: &str) -> DiffFile {
         is_too_large: false,
         is_commit_message: false,
         content_hash: 0,
+        review_key: None,
     }
 }
 
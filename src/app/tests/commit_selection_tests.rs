@@ -281,6 +281,7 @@ fn commit_only_file(path: &Path, hunks: Vec<DiffHunk>) -> DiffFile {
         is_too_large: false,
         is_commit_message: false,
         content_hash: 7,
+        review_key: None,
     }
 }
 
@@ -298,6 +299,7 @@ fn one_line_hunk() -> DiffHunk {
         old_count: 1,
         new_start: 1,
         new_count: 1,
+        raw_body: None,
     }
 }
 
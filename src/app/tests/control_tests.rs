@@ -0,0 +1,264 @@
+use std::sync::mpsc::{Receiver, Sender};
+use std::time::{Duration, Instant};
+
+use serde_json::{Value, json};
+
+use crate::app::*;
+use crate::control::ControlHub;
+use crate::model::{DiffFile, DiffHunk, DiffLine, FileStatus, LineOrigin};
+use crate::vcs::traits::{VcsBackend, VcsInfo, VcsType};
+
+struct StubVcs(VcsInfo);
+impl VcsBackend for StubVcs {
+    fn info(&self) -> &VcsInfo {
+        &self.0
+    }
+    fn get_working_tree_diff(
+        &self,
+        _hl: &crate::syntax::SyntaxHighlighter,
+    ) -> crate::error::Result<Vec<DiffFile>> {
+        Ok(Vec::new())
+    }
+    fn fetch_context_lines(
+        &self,
+        _path: &std::path::Path,
+        _status: FileStatus,
+        _ref_commit: Option<&str>,
+        _start: u32,
+        _end: u32,
+    ) -> crate::error::Result<Vec<DiffLine>> {
+        Ok(Vec::new())
+    }
+    fn file_line_count(
+        &self,
+        _path: &std::path::Path,
+        _status: FileStatus,
+        _ref_commit: Option<&str>,
+    ) -> crate::error::Result<u32> {
+        Ok(0)
+    }
+}
+
+fn file(path: &str, count: u32) -> DiffFile {
+    let lines = (1..=count)
+        .map(|lineno| DiffLine {
+            origin: LineOrigin::Context,
+            content: format!("line {lineno}"),
+            old_lineno: Some(lineno),
+            new_lineno: Some(lineno),
+            highlighted_spans: None,
+        })
+        .collect();
+    let hunks = vec![DiffHunk {
+        header: format!("@@ -1,{count} +1,{count} @@"),
+        lines,
+        old_start: 1,
+        old_count: count,
+        new_start: 1,
+        new_count: count,
+        raw_body: None,
+    }];
+    let content_hash = DiffFile::compute_content_hash(&hunks);
+    DiffFile {
+        old_path: None,
+        new_path: Some(PathBuf::from(path)),
+        status: FileStatus::Modified,
+        hunks,
+        is_binary: false,
+        is_too_large: false,
+        is_commit_message: false,
+        content_hash,
+        review_key: None,
+    }
+}
+
+/// An app over `src/a.rs` and `src/b.rs` with a control hub attached, and
+/// a client connection to it.
+fn connected_app() -> (App, Sender<String>, Receiver<Value>) {
+    let vcs_info = VcsInfo {
+        root_path: PathBuf::from("/tmp"),
+        head_commit: "head".into(),
+        branch_name: Some("main".into()),
+        vcs_type: VcsType::Git,
+    };
+    let session = ReviewSession::new(
+        vcs_info.root_path.clone(),
+        vcs_info.head_commit.clone(),
+        vcs_info.branch_name.clone(),
+        SessionDiffSource::WorkingTree,
+    );
+    let mut app = App::build(
+        Box::new(StubVcs(vcs_info.clone())),
+        vcs_info,
+        crate::theme::Theme::dark(),
+        None,
+        false,
+        vec![file("src/a.rs", 5), file("src/b.rs", 20)],
+        session,
+        DiffSource::WorkingTree,
+        InputMode::Normal,
+        Vec::new(),
+        None,
+        None,
+    )
+    .expect("build app");
+    let hub = ControlHub::new();
+    let (tx, rx) = hub.connect();
+    app.control = Some(hub);
+    (app, tx, rx)
+}
+
+/// Send one request and pump the app until its reply arrives.
+fn call(
+    app: &mut App,
+    tx: &Sender<String>,
+    rx: &Receiver<Value>,
+    method: &str,
+    params: Value,
+) -> Value {
+    let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+    tx.send(request.to_string()).unwrap();
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !app.poll_control_requests() {
+        assert!(Instant::now() < deadline, "request was never queued");
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    loop {
+        let message = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        if message.get("id").is_some() {
+            return message;
+        }
+    }
+}
+
+#[test]
+fn should_jump_the_cursor_to_a_file_line() {
+    // given
+    let (mut app, tx, rx) = connected_app();
+
+    // when
+    let reply = call(
+        &mut app,
+        &tx,
+        &rx,
+        "goto",
+        json!({ "file": "src/b.rs", "line": 12 }),
+    );
+
+    // then
+    assert_eq!(reply["result"]["file"], "src/b.rs");
+    assert_eq!(reply["result"]["line"], 12);
+    assert_eq!(app.get_line_at_cursor(), Some((12, LineSide::New)));
+}
+
+#[test]
+fn should_open_a_file_in_single_file_view() {
+    // given
+    let (mut app, tx, rx) = connected_app();
+
+    // when
+    let reply = call(&mut app, &tx, &rx, "open", json!({ "file": "src/b.rs" }));
+
+    // then
+    assert_eq!(reply["result"]["single_file_view"], true);
+    assert!(app.is_single_file_view);
+    assert_eq!(app.current_file_path(), Some(&PathBuf::from("src/b.rs")));
+}
+
+#[test]
+fn should_reject_a_file_outside_the_diff() {
+    // given
+    let (mut app, tx, rx) = connected_app();
+
+    // when
+    let reply = call(&mut app, &tx, &rx, "goto", json!({ "file": "src/c.rs" }));
+
+    // then
+    assert_eq!(reply["error"]["code"], crate::control::REQUEST_FAILED);
+    assert_eq!(app.get_line_at_cursor(), None);
+}
+
+#[test]
+fn should_add_a_comment_and_announce_it_to_subscribers() {
+    // given
+    let (mut app, tx, rx) = connected_app();
+    let (watch_tx, watch_rx) = app.control.as_ref().unwrap().connect();
+    watch_tx
+        .send(r#"{"jsonrpc":"2.0","id":1,"method":"subscribe"}"#.into())
+        .unwrap();
+    watch_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+    // when
+    let reply = call(
+        &mut app,
+        &tx,
+        &rx,
+        "comment",
+        json!({ "file": "src/a.rs", "line": 3, "content": "Handle this", "author": "bot" }),
+    );
+
+    // then
+    assert_eq!(reply["result"]["content"], "Handle this");
+    let review = &app.session.files[&PathBuf::from("src/a.rs")];
+    assert_eq!(review.line_comments[&3][0].author, "bot");
+    let event = watch_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(event["params"]["event"], "comment_added");
+    assert_eq!(event["params"]["data"]["comment"]["content"], "Handle this");
+}
+
+#[test]
+fn should_refuse_to_move_the_cursor_while_the_user_is_typing() {
+    // given
+    let (mut app, tx, rx) = connected_app();
+    app.input_mode = InputMode::Comment;
+
+    // when
+    let reply = call(&mut app, &tx, &rx, "goto", json!({ "file": "src/b.rs" }));
+
+    // then
+    assert_eq!(reply["error"]["code"], crate::control::REQUEST_FAILED);
+    assert_eq!(app.input_mode, InputMode::Comment);
+}
+
+#[test]
+fn should_flash_a_status_message() {
+    // given
+    let (mut app, tx, rx) = connected_app();
+
+    // when
+    call(
+        &mut app,
+        &tx,
+        &rx,
+        "message",
+        json!({ "text": "Tests passed", "level": "warning" }),
+    );
+
+    // then
+    let message = app.message.as_ref().unwrap();
+    assert_eq!(message.content, "Tests passed");
+    assert_eq!(message.message_type, MessageType::Warning);
+}
+
+#[test]
+fn should_publish_file_reviewed_when_a_file_is_marked() {
+    // given
+    let (mut app, _tx, _rx) = connected_app();
+    let (watch_tx, watch_rx) = app.control.as_ref().unwrap().connect();
+    watch_tx
+        .send(
+            r#"{"jsonrpc":"2.0","id":1,"method":"subscribe","params":{"events":["file_reviewed"]}}"#
+                .into(),
+        )
+        .unwrap();
+    watch_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+    // when
+    app.toggle_reviewed_for_file_idx(0, false);
+
+    // then
+    let event = watch_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(event["params"]["event"], "file_reviewed");
+    assert_eq!(event["params"]["data"]["path"], "src/a.rs");
+    assert_eq!(event["params"]["data"]["reviewed"], true);
+}
@@ -0,0 +1,257 @@
+use std::time::{Duration, Instant};
+
+use crate::app::*;
+use crate::diagnostics::{Diagnostic, DiagnosticSeverity};
+use crate::model::{DiffFile, DiffHunk, DiffLine, FileStatus, LineOrigin};
+use crate::vcs::traits::{VcsBackend, VcsInfo, VcsType};
+
+struct StubVcs(VcsInfo);
+impl VcsBackend for StubVcs {
+    fn info(&self) -> &VcsInfo {
+        &self.0
+    }
+    fn get_working_tree_diff(
+        &self,
+        _hl: &crate::syntax::SyntaxHighlighter,
+    ) -> crate::error::Result<Vec<DiffFile>> {
+        Ok(Vec::new())
+    }
+    fn fetch_context_lines(
+        &self,
+        _path: &std::path::Path,
+        _status: FileStatus,
+        _ref_commit: Option<&str>,
+        _start: u32,
+        _end: u32,
+    ) -> crate::error::Result<Vec<DiffLine>> {
+        Ok(Vec::new())
+    }
+    fn file_line_count(
+        &self,
+        _path: &std::path::Path,
+        _status: FileStatus,
+        _ref_commit: Option<&str>,
+    ) -> crate::error::Result<u32> {
+        Ok(0)
+    }
+}
+
+fn diff_line(origin: LineOrigin, old: Option<u32>, new: Option<u32>) -> DiffLine {
+    DiffLine {
+        origin,
+        content: "let x = 1;".to_string(),
+        old_lineno: old,
+        new_lineno: new,
+        highlighted_spans: None,
+    }
+}
+
+/// `src/main.rs` with context line 1, a deleted old line 2 and an added
+/// new line 2.
+fn file() -> DiffFile {
+    let hunks = vec![DiffHunk {
+        header: "@@ -1,2 +1,2 @@".to_string(),
+        lines: vec![
+            diff_line(LineOrigin::Context, Some(1), Some(1)),
+            diff_line(LineOrigin::Deletion, Some(2), None),
+            diff_line(LineOrigin::Addition, None, Some(2)),
+        ],
+        old_start: 1,
+        old_count: 2,
+        new_start: 1,
+        new_count: 2,
+        raw_body: None,
+    }];
+    let content_hash = DiffFile::compute_content_hash(&hunks);
+    DiffFile {
+        old_path: None,
+        new_path: Some(PathBuf::from("src/main.rs")),
+        status: FileStatus::Modified,
+        hunks,
+        is_binary: false,
+        is_too_large: false,
+        is_commit_message: false,
+        content_hash,
+        review_key: None,
+    }
+}
+
+fn app_at(root: &std::path::Path) -> App {
+    let vcs_info = VcsInfo {
+        root_path: root.to_path_buf(),
+        head_commit: "head".into(),
+        branch_name: Some("main".into()),
+        vcs_type: VcsType::Git,
+    };
+    let session = ReviewSession::new(
+        vcs_info.root_path.clone(),
+        vcs_info.head_commit.clone(),
+        vcs_info.branch_name.clone(),
+        SessionDiffSource::WorkingTree,
+    );
+    App::build(
+        Box::new(StubVcs(vcs_info.clone())),
+        vcs_info,
+        crate::theme::Theme::dark(),
+        None,
+        false,
+        vec![file()],
+        session,
+        DiffSource::WorkingTree,
+        InputMode::Normal,
+        Vec::new(),
+        None,
+        None,
+    )
+    .expect("build app")
+}
+
+fn warning(line: u32, message: &str) -> Diagnostic {
+    Diagnostic {
+        path: PathBuf::from("src/main.rs"),
+        line,
+        column: Some(9),
+        severity: DiagnosticSeverity::Warning,
+        code: Some("unused_variables".to_string()),
+        message: message.to_string(),
+    }
+}
+
+fn diagnostic_rows(app: &App) -> Vec<usize> {
+    app.line_annotations
+        .iter()
+        .enumerate()
+        .filter(|(_, annotation)| matches!(annotation, AnnotatedLine::Diagnostic { .. }))
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+fn message(app: &App) -> &str {
+    app.message.as_ref().map_or("", |m| m.content.as_str())
+}
+
+#[test]
+fn should_keep_only_diagnostics_on_changed_lines() {
+    // given
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("lint.txt");
+    std::fs::write(
+        &output,
+        "src/main.rs:2:9: warning: unused variable `x`\n\
+         src/main.rs:40:1: error: mismatched types\n\
+         src/other.rs:2:1: warning: dead code\n",
+    )
+    .unwrap();
+    let mut app = app_at(dir.path());
+
+    // when
+    app.load_diagnostics_file(&output);
+
+    // then
+    assert_eq!(app.diagnostics.len(), 1);
+    assert_eq!(app.diagnostics[0].line, 2);
+    assert_eq!(
+        message(&app),
+        format!(
+            "1 diagnostic on changed lines from {} (2 elsewhere hidden)",
+            output.display()
+        )
+    );
+}
+
+#[test]
+fn should_render_diagnostics_after_their_new_side_line_in_both_views() {
+    for mode in [DiffViewMode::Unified, DiffViewMode::SideBySide] {
+        // given
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = app_at(dir.path());
+        app.diff_view_mode = mode;
+
+        // when
+        app.set_diagnostics(vec![warning(2, "unused variable `x`")]);
+
+        // then
+        let rows = diagnostic_rows(&app);
+        assert_eq!(rows.len(), 1, "mode {mode:?}");
+        let anchor = &app.line_annotations[rows[0] - 1];
+        assert!(
+            matches!(
+                anchor,
+                AnnotatedLine::DiffLine {
+                    new_lineno: Some(2),
+                    ..
+                } | AnnotatedLine::SideBySideLine {
+                    new_lineno: Some(2),
+                    ..
+                }
+            ),
+            "mode {mode:?}: diagnostic anchored after {anchor:?}"
+        );
+        assert_eq!(app.total_lines(), app.line_annotations.len());
+    }
+}
+
+#[test]
+fn should_promote_the_diagnostic_at_cursor_into_a_line_comment() {
+    // given
+    let dir = tempfile::tempdir().unwrap();
+    let mut app = app_at(dir.path());
+    app.set_diagnostics(vec![warning(2, "unused variable `x`")]);
+    app.diff_state.cursor_line = diagnostic_rows(&app)[0];
+
+    // when
+    app.promote_diagnostic_at_cursor();
+
+    // then
+    let review = &app.session.files[&PathBuf::from("src/main.rs")];
+    let comments = &review.line_comments[&2];
+    assert_eq!(comments.len(), 1);
+    assert_eq!(
+        comments[0].content,
+        "warning[unused_variables]: unused variable `x`"
+    );
+    assert_eq!(comments[0].side, Some(LineSide::New));
+    assert!(app.diagnostics.is_empty());
+    assert!(diagnostic_rows(&app).is_empty());
+    assert_eq!(message(&app), "Diagnostic added as a comment on line 2");
+}
+
+#[test]
+fn should_report_when_no_diagnostic_is_at_cursor() {
+    // given
+    let dir = tempfile::tempdir().unwrap();
+    let mut app = app_at(dir.path());
+    app.set_diagnostics(vec![warning(2, "unused variable `x`")]);
+    app.diff_state.cursor_line = 0;
+
+    // when
+    app.promote_diagnostic_at_cursor();
+
+    // then
+    assert_eq!(message(&app), "No diagnostic at cursor");
+    assert_eq!(app.diagnostics.len(), 1);
+}
+
+#[cfg(unix)]
+#[test]
+fn should_run_the_configured_command_and_parse_its_output() {
+    // given
+    let dir = tempfile::tempdir().unwrap();
+    let mut app = app_at(dir.path());
+    app.diagnostics_command =
+        Some("echo 'src/main.rs:2:9: warning: unused variable' >&2; exit 101".to_string());
+
+    // when
+    app.run_diagnostics_command();
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while app.diagnostics_rx.is_some() {
+        assert!(Instant::now() < deadline, ":diagnostics never finished");
+        std::thread::sleep(Duration::from_millis(10));
+        app.poll_diagnostics_events();
+    }
+
+    // then
+    assert_eq!(app.diagnostics.len(), 1);
+    assert_eq!(app.diagnostics[0].message, "unused variable");
+    assert!(message(&app).starts_with("1 diagnostic on changed lines from echo"));
+}
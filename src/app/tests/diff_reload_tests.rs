@@ -175,6 +175,7 @@ fn make_diff_file(path: &str, status: FileStatus, content_hash: u64) -> DiffFile
         is_too_large: false,
         is_commit_message: false,
         content_hash,
+        review_key: None,
     }
 }
 
@@ -57,6 +57,7 @@ fn file(path: &str, contents: &[&str]) -> DiffFile {
         old_count: 0,
         new_start: 1,
         new_count: contents.len() as u32,
+        raw_body: None,
     }];
     let content_hash = DiffFile::compute_content_hash(&hunks);
     DiffFile {
@@ -68,6 +69,7 @@ fn file(path: &str, contents: &[&str]) -> DiffFile {
         is_too_large: false,
         is_commit_message: false,
         content_hash,
+        review_key: None,
     }
 }
 
@@ -113,6 +113,7 @@ fn make_diff_file(path: &str, content_hash: u64) -> DiffFile {
         is_too_large: false,
         is_commit_message: false,
         content_hash,
+        review_key: None,
     }
 }
 
@@ -134,6 +135,7 @@ fn make_hunk(new_start: u32, new_count: u32) -> DiffHunk {
         old_count: new_count,
         new_start,
         new_count,
+        raw_body: None,
     }
 }
 
@@ -148,6 +150,7 @@ fn make_file_with_hunks(path: &str, hunks: Vec<DiffHunk>) -> DiffFile {
         is_too_large: false,
         is_commit_message: false,
         content_hash,
+        review_key: None,
     }
 }
 
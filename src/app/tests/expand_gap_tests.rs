@@ -66,6 +66,7 @@ fn make_hunk(new_start: u32, new_count: u32) -> DiffHunk {
         old_count: new_count,
         new_start,
         new_count,
+        raw_body: None,
     }
 }
 
@@ -114,6 +115,7 @@ fn make_file_with_hunks(path: &str, hunks: Vec<DiffHunk>) -> DiffFile {
         is_too_large: false,
         is_commit_message: false,
         content_hash,
+        review_key: None,
     }
 }
 
@@ -1438,6 +1440,7 @@ fn should_not_show_eof_gap_for_deleted_files() {
         is_too_large: false,
         is_commit_message: false,
         content_hash,
+        review_key: None,
     };
     let app = build_app_with_files(vec![file], 100);
     let eof_gap_id = GapId {
@@ -58,6 +58,7 @@ fn hunk() -> DiffHunk {
         old_count: 1,
         new_start: 1,
         new_count: 2,
+        raw_body: None,
     }
 }
 
@@ -73,6 +74,7 @@ fn file(path: &str) -> DiffFile {
         is_too_large: false,
         is_commit_message: false,
         content_hash,
+        review_key: None,
     }
 }
 
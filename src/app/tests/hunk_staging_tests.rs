@@ -0,0 +1,338 @@
+use crate::app::*;
+use crate::handler::{handle_confirm_action, handle_diff_action, handle_visual_action};
+use crate::input::keybindings::Action;
+use crate::vcs::{HunkChange, HunkChangeBase, HunkOperation, HunkSelection};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+type AppliedChange = (HunkChangeBase, HunkOperation, Vec<HunkSelection>);
+
+/// Fake `VcsBackend` that records every hunk change it is asked to apply and
+/// serves the same diff back on reload, so a test can check both the
+/// request and that the view was refreshed afterwards.
+struct RecordingVcs {
+    info: VcsInfo,
+    files: Vec<DiffFile>,
+    applied: Arc<Mutex<Vec<AppliedChange>>>,
+    reloads: Arc<AtomicUsize>,
+}
+
+impl VcsBackend for RecordingVcs {
+    fn info(&self) -> &VcsInfo {
+        &self.info
+    }
+
+    fn get_working_tree_diff(&self, _highlighter: &SyntaxHighlighter) -> Result<Vec<DiffFile>> {
+        self.reloads.fetch_add(1, Ordering::SeqCst);
+        Ok(self.files.clone())
+    }
+
+    fn get_staged_diff(&self, _highlighter: &SyntaxHighlighter) -> Result<Vec<DiffFile>> {
+        self.reloads.fetch_add(1, Ordering::SeqCst);
+        Ok(self.files.clone())
+    }
+
+    fn get_unstaged_diff(&self, _highlighter: &SyntaxHighlighter) -> Result<Vec<DiffFile>> {
+        self.reloads.fetch_add(1, Ordering::SeqCst);
+        Ok(self.files.clone())
+    }
+
+    fn apply_hunk_change(&self, change: &HunkChange<'_>) -> Result<()> {
+        self.applied.lock().expect("applied poisoned").push((
+            change.base,
+            change.operation,
+            change.selections.to_vec(),
+        ));
+        Ok(())
+    }
+
+    fn fetch_context_lines(
+        &self,
+        _file_path: &Path,
+        _file_status: FileStatus,
+        _ref_commit: Option<&str>,
+        _start_line: u32,
+        _end_line: u32,
+    ) -> Result<Vec<DiffLine>> {
+        Ok(Vec::new())
+    }
+
+    fn file_line_count(
+        &self,
+        _file_path: &Path,
+        _file_status: FileStatus,
+        _ref_commit: Option<&str>,
+    ) -> Result<u32> {
+        Ok(0)
+    }
+}
+
+fn diff_line(origin: LineOrigin, content: &str, old: Option<u32>, new: Option<u32>) -> DiffLine {
+    DiffLine {
+        origin,
+        content: content.to_string(),
+        old_lineno: old,
+        new_lineno: new,
+        highlighted_spans: None,
+    }
+}
+
+/// One modified file with two hunks of a context, a deletion and two
+/// additions each.
+fn staged_test_file() -> DiffFile {
+    let hunk = |start: u32| DiffHunk {
+        header: format!("@@ -{start},2 +{start},3 @@"),
+        lines: vec![
+            diff_line(LineOrigin::Context, "keep", Some(start), Some(start)),
+            diff_line(LineOrigin::Deletion, "old", Some(start + 1), None),
+            diff_line(LineOrigin::Addition, "new one", None, Some(start + 1)),
+            diff_line(LineOrigin::Addition, "new two", None, Some(start + 2)),
+        ],
+        old_start: start,
+        old_count: 2,
+        new_start: start,
+        new_count: 3,
+        raw_body: None,
+    };
+    let hunks = vec![hunk(1), hunk(40)];
+    let content_hash = DiffFile::compute_content_hash(&hunks);
+    DiffFile {
+        old_path: None,
+        new_path: Some(PathBuf::from("src/lib.rs")),
+        status: FileStatus::Modified,
+        hunks,
+        is_binary: false,
+        is_too_large: false,
+        is_commit_message: false,
+        content_hash,
+        review_key: None,
+    }
+}
+
+struct Harness {
+    app: App,
+    applied: Arc<Mutex<Vec<AppliedChange>>>,
+    reloads: Arc<AtomicUsize>,
+}
+
+impl Harness {
+    fn new(diff_source: DiffSource) -> Self {
+        let info = VcsInfo {
+            root_path: PathBuf::from("/tmp"),
+            head_commit: "abc123".to_string(),
+            branch_name: Some("main".to_string()),
+            vcs_type: VcsType::Git,
+        };
+        let files = vec![staged_test_file()];
+        let applied = Arc::new(Mutex::new(Vec::new()));
+        let reloads = Arc::new(AtomicUsize::new(0));
+        let vcs = RecordingVcs {
+            info: info.clone(),
+            files: files.clone(),
+            applied: Arc::clone(&applied),
+            reloads: Arc::clone(&reloads),
+        };
+        let session = ReviewSession::new(
+            info.root_path.clone(),
+            info.head_commit.clone(),
+            info.branch_name.clone(),
+            SessionDiffSource::WorkingTree,
+        );
+        let app = App::build(
+            Box::new(vcs),
+            info,
+            Theme::dark(),
+            None,
+            false,
+            files,
+            session,
+            diff_source,
+            InputMode::Normal,
+            Vec::new(),
+            None,
+            None,
+        )
+        .expect("failed to build test app");
+        Self {
+            app,
+            applied,
+            reloads,
+        }
+    }
+
+    fn applied(&self) -> Vec<AppliedChange> {
+        self.applied.lock().expect("applied poisoned").clone()
+    }
+
+    /// Moves the cursor onto the unified diff row for `hunk_idx`/`line_idx`.
+    fn move_to_line(&mut self, hunk_idx: usize, line_idx: usize) {
+        let idx = self
+            .app
+            .line_annotations
+            .iter()
+            .position(|line| {
+                matches!(
+                    line,
+                    AnnotatedLine::DiffLine { hunk_idx: h, line_idx: l, .. }
+                        if *h == hunk_idx && *l == line_idx
+                )
+            })
+            .expect("diff line should be annotated");
+        self.app.diff_state.cursor_line = idx;
+    }
+
+    fn message(&self) -> &str {
+        self.app
+            .message
+            .as_ref()
+            .map(|m| m.content.as_str())
+            .unwrap_or_default()
+    }
+}
+
+#[test]
+fn should_stage_the_whole_hunk_at_cursor_and_reload() {
+    let mut h = Harness::new(DiffSource::Unstaged);
+    h.move_to_line(1, 2);
+
+    handle_diff_action(&mut h.app, Action::StageHunk);
+
+    assert_eq!(
+        h.applied(),
+        vec![(
+            HunkChangeBase::Unstaged,
+            HunkOperation::Stage,
+            vec![HunkSelection {
+                hunk_idx: 1,
+                lines: None,
+            }],
+        )]
+    );
+    assert_eq!(h.reloads.load(Ordering::SeqCst), 1);
+    assert_eq!(h.message(), "Staged hunk");
+}
+
+#[test]
+fn should_unstage_only_the_visually_selected_lines() {
+    let mut h = Harness::new(DiffSource::StagedAndUnstaged);
+    h.move_to_line(0, 1);
+    h.app.enter_visual_mode_at_cursor();
+    h.move_to_line(0, 2);
+    h.app.extend_visual_to_cursor();
+
+    handle_visual_action(&mut h.app, Action::UnstageHunk);
+
+    assert_eq!(
+        h.applied(),
+        vec![(
+            HunkChangeBase::StagedAndUnstaged,
+            HunkOperation::Unstage,
+            vec![HunkSelection {
+                hunk_idx: 0,
+                lines: Some(1..=2),
+            }],
+        )]
+    );
+    assert_eq!(h.app.input_mode, InputMode::Normal);
+    assert!(h.app.visual_selection.is_none());
+    assert_eq!(h.message(), "Unstaged selected lines");
+}
+
+#[test]
+fn should_split_a_selection_spanning_hunks_into_one_range_per_hunk() {
+    let mut h = Harness::new(DiffSource::Unstaged);
+    h.move_to_line(0, 3);
+    h.app.enter_visual_mode_at_cursor();
+    h.move_to_line(1, 1);
+    h.app.extend_visual_to_cursor();
+
+    handle_visual_action(&mut h.app, Action::StageHunk);
+
+    let (_, _, selections) = h.applied().pop().expect("change should be applied");
+    assert_eq!(
+        selections,
+        vec![
+            HunkSelection {
+                hunk_idx: 0,
+                lines: Some(3..=3),
+            },
+            HunkSelection {
+                hunk_idx: 1,
+                lines: Some(0..=1),
+            },
+        ]
+    );
+}
+
+#[test]
+fn should_discard_only_after_confirmation() {
+    let mut h = Harness::new(DiffSource::Unstaged);
+    h.move_to_line(0, 1);
+
+    handle_diff_action(&mut h.app, Action::DiscardHunk);
+
+    assert_eq!(h.app.input_mode, InputMode::Confirm);
+    assert_eq!(
+        h.app.pending_confirm.as_ref().map(ConfirmAction::prompt),
+        Some("Discard selected changes? This cannot be undone.")
+    );
+    assert!(h.applied().is_empty());
+
+    handle_confirm_action(&mut h.app, Action::ConfirmYes);
+
+    assert_eq!(h.app.input_mode, InputMode::Normal);
+    assert!(h.app.pending_confirm.is_none());
+    assert!(!h.app.should_quit);
+    assert_eq!(
+        h.applied(),
+        vec![(
+            HunkChangeBase::Unstaged,
+            HunkOperation::Discard,
+            vec![HunkSelection {
+                hunk_idx: 0,
+                lines: None,
+            }],
+        )]
+    );
+}
+
+#[test]
+fn should_keep_changes_when_discard_is_declined() {
+    let mut h = Harness::new(DiffSource::Unstaged);
+    h.move_to_line(0, 1);
+
+    handle_diff_action(&mut h.app, Action::DiscardHunk);
+    handle_confirm_action(&mut h.app, Action::ConfirmNo);
+
+    assert!(h.applied().is_empty());
+    assert_eq!(h.app.input_mode, InputMode::Normal);
+    assert!(!h.app.should_quit);
+    assert_eq!(h.message(), "Discard cancelled");
+}
+
+#[test]
+fn should_refuse_operations_that_do_not_apply_to_the_view() {
+    let mut h = Harness::new(DiffSource::Unstaged);
+    h.move_to_line(0, 1);
+    handle_diff_action(&mut h.app, Action::UnstageHunk);
+    assert_eq!(
+        h.message(),
+        "Nothing to unstage: these changes are not staged"
+    );
+
+    let mut h = Harness::new(DiffSource::Staged);
+    h.move_to_line(0, 1);
+    handle_diff_action(&mut h.app, Action::DiscardHunk);
+    assert_eq!(h.app.input_mode, InputMode::Normal);
+    assert_eq!(h.message(), "Unstage changes before discarding them");
+
+    let mut h = Harness::new(DiffSource::WorkingTree);
+    h.move_to_line(0, 1);
+    handle_diff_action(&mut h.app, Action::StageHunk);
+    assert_eq!(
+        h.message(),
+        "Hunk staging only available when viewing staged or unstaged diffs"
+    );
+
+    assert!(h.applied().is_empty());
+}
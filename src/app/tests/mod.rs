@@ -1,7 +1,10 @@
+mod blame_tests;
 mod change_status_tests;
 mod commit_scoped_comment_tests;
 mod commit_selection_tests;
+mod control_tests;
 mod decoration_skip_tests;
+mod diagnostics_tests;
 mod diff_reload_tests;
 mod diff_search_tests;
 mod diff_source_tests;
@@ -9,11 +12,14 @@ mod diff_watch_tests;
 mod expand_gap_tests;
 mod file_filter_tests;
 mod find_source_line_tests;
+mod hunk_staging_tests;
+mod patch_review_tests;
 mod persistence_merge_tests;
 mod pr_info_tests;
 mod render_perf_tests;
 mod scroll_behavior_tests;
 mod scroll_tests;
+mod send_tests;
 mod single_file_view_tests;
 mod submit_flow_tests;
 mod target_selector_tests;
@@ -0,0 +1,309 @@
+use crate::app::*;
+use crate::input::keybindings::Action;
+use crate::model::{Comment, CommentType};
+use crate::vcs::PatchBackend;
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use std::fs;
+
+const PATCH: &str = "\
+diff --git a/notes.txt b/notes.txt
+--- a/notes.txt
++++ b/notes.txt
+@@ -1,2 +1,2 @@
+ one
+-two
++TWO
+@@ -6,2 +6,3 @@
+ six
+ seven
++eight
+";
+
+fn build_app(root: &Path) -> App {
+    let vcs = PatchBackend::new("fix.patch", PATCH, root.to_path_buf()).expect("patch backend");
+    let info = vcs.info().clone();
+    let files = vcs
+        .get_working_tree_diff(&SyntaxHighlighter::default())
+        .expect("patch should parse");
+    let session = ReviewSession::new(
+        info.root_path.clone(),
+        info.head_commit.clone(),
+        info.branch_name.clone(),
+        SessionDiffSource::Patch,
+    );
+    App::build(
+        Box::new(vcs),
+        info,
+        Theme::dark(),
+        None,
+        false,
+        files,
+        session,
+        DiffSource::WorkingTree,
+        InputMode::Normal,
+        Vec::new(),
+        None,
+        None,
+    )
+    .expect("failed to build test app")
+}
+
+fn between_hunks() -> GapId {
+    GapId {
+        file_idx: 0,
+        hunk_idx: 1,
+    }
+}
+
+fn expanded_contents(app: &App) -> Vec<String> {
+    app.expanded_top
+        .get(&between_hunks())
+        .map(|lines| lines.iter().map(|l| l.content.clone()).collect())
+        .unwrap_or_default()
+}
+
+#[test]
+fn should_expand_context_from_a_checkout_the_patch_applies_to() {
+    let temp = tempfile::tempdir().unwrap();
+    fs::write(
+        temp.path().join("notes.txt"),
+        "one\ntwo\nthree\nfour\nfive\nsix\nseven\n",
+    )
+    .unwrap();
+    let mut app = build_app(temp.path());
+
+    app.expand_gap(between_hunks(), ExpandDirection::Both, None)
+        .expect("context should expand");
+
+    assert_eq!(expanded_contents(&app), vec!["three", "four", "five"]);
+}
+
+#[test]
+fn should_refuse_context_when_no_checkout_matches() {
+    let temp = tempfile::tempdir().unwrap();
+    let mut app = build_app(temp.path());
+
+    let err = app
+        .expand_gap(between_hunks(), ExpandDirection::Both, None)
+        .expect_err("there is nothing to read context from");
+
+    assert!(err.to_string().contains("does not match this patch"));
+    assert!(expanded_contents(&app).is_empty());
+}
+
+#[test]
+fn should_name_the_patch_instead_of_a_commit_in_the_header() {
+    let temp = tempfile::tempdir().unwrap();
+    let mut app = build_app(temp.path());
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 10)).unwrap();
+    terminal
+        .draw(|frame| crate::ui::render(frame, &mut app))
+        .expect("draw frame");
+    let buffer = terminal.backend().buffer();
+    let header: String = (0..buffer.area.width)
+        .map(|x| buffer[(x, 0)].symbol().to_string())
+        .collect();
+
+    assert!(header.contains("patch:fix.patch"), "header: {header}");
+    assert!(!header.contains("commit"), "header: {header}");
+}
+
+const SERIES: &str = "\
+From 1111111111111111111111111111111111111111 Mon Sep 17 00:00:00 2001
+From: Alice <alice@example.com>
+Date: Tue, 7 May 2024 10:00:00 +0200
+Subject: [PATCH 1/2] notes: shout two
+Message-Id: <one@example.com>
+
+Two deserves emphasis.
+---
+diff --git a/notes.txt b/notes.txt
+--- a/notes.txt
++++ b/notes.txt
+@@ -1,2 +1,2 @@
+ one
+-two
++TWO
+--
+2.43.0
+
+From 2222222222222222222222222222222222222222 Mon Sep 17 00:00:00 2001
+From: Alice <alice@example.com>
+Date: Tue, 7 May 2024 10:00:00 +0200
+Subject: [PATCH 2/2] notes: shout one
+Message-Id: <two@example.com>
+In-Reply-To: <one@example.com>
+References: <one@example.com>
+
+---
+diff --git a/notes.txt b/notes.txt
+--- a/notes.txt
++++ b/notes.txt
+@@ -1,2 +1,2 @@
+-one
++ONE
+ TWO
+";
+
+fn build_series_app(root: &Path) -> App {
+    let vcs = PatchBackend::new("series.mbox", SERIES, root.to_path_buf()).expect("series");
+    let info = vcs.info().clone();
+    let series = vcs.series().cloned().expect("mail series");
+    let ids: Vec<String> = series.patches.iter().map(|p| p.id.clone()).collect();
+    let files = vcs
+        .get_commit_range_diff(
+            &ResolvedRevisionRange::from_commit_ids(&ids, RevisionDiffTarget::CommitList),
+            &SyntaxHighlighter::default(),
+        )
+        .expect("series should parse");
+    let commits: Vec<CommitInfo> = vcs
+        .get_commits_info(&ids)
+        .unwrap()
+        .into_iter()
+        .rev()
+        .collect();
+    let session = ReviewSession::new(
+        info.root_path.clone(),
+        ids[1].clone(),
+        info.branch_name.clone(),
+        SessionDiffSource::CommitRange,
+    );
+    let mut app = App::build(
+        Box::new(vcs),
+        info,
+        Theme::dark(),
+        None,
+        false,
+        files,
+        session,
+        DiffSource::CommitRange(ids),
+        InputMode::Normal,
+        Vec::new(),
+        None,
+        None,
+    )
+    .expect("failed to build test app");
+    app.patch_series = Some(series);
+    app.start_commit_range_review(commits, CommitSelectionStart::All)
+        .expect("selector should start");
+    app
+}
+
+fn file_names(app: &App) -> Vec<String> {
+    app.diff_files
+        .iter()
+        .map(|f| f.display_path().display().to_string())
+        .collect()
+}
+
+#[test]
+fn should_offer_each_series_message_as_a_commit() {
+    let temp = tempfile::tempdir().unwrap();
+    let mut app = build_series_app(temp.path());
+
+    assert!(app.show_commit_selector);
+    let summaries: Vec<&str> = app
+        .review_commits
+        .iter()
+        .map(|c| c.summary.as_str())
+        .collect();
+    assert_eq!(summaries, vec!["notes: shout one", "notes: shout two"]);
+    assert_eq!(file_names(&app), vec!["notes.txt", "notes.txt (2222222)"]);
+
+    app.commit_selection_range = Some((1, 1));
+    app.reload_inline_selection().unwrap();
+
+    assert_eq!(
+        file_names(&app),
+        vec!["Commit Message (1111111)", "notes.txt"]
+    );
+    let message = &app.diff_files[0];
+    assert!(message.is_commit_message);
+    assert_eq!(message.hunks[0].lines[2].content, "Two deserves emphasis.");
+}
+
+#[test]
+fn should_write_email_replies_for_the_series() {
+    let temp = tempfile::tempdir().unwrap();
+    let mut app = build_series_app(temp.path());
+    app.session
+        .get_file_mut(&PathBuf::from("notes.txt (2222222)"))
+        .expect("second patch's file is registered")
+        .add_line_comment(
+            1,
+            Comment::new(
+                "Why shout?".to_string(),
+                CommentType::None,
+                Some(LineSide::New),
+            ),
+        );
+    let out = temp.path().join("reply.mbox");
+
+    app.enter_command_mode();
+    app.command_buffer = format!("export email {}", out.display());
+    crate::handler::handle_command_action(&mut app, Action::SubmitInput);
+
+    let mbox = fs::read_to_string(&out).expect("replies written");
+    assert!(mbox.contains("Subject: Re: [PATCH 2/2] notes: shout one\n"));
+    assert!(!mbox.contains("[PATCH 1/2]"));
+    assert!(mbox.contains("References: <one@example.com> <two@example.com>\n"));
+    assert!(mbox.contains("> +++ b/notes.txt\n> @@ -1,2 +1,2 @@\n> -one\n> +ONE\n\nWhy shout?\n"));
+    assert_eq!(
+        app.message.as_ref().map(|m| m.content.as_str()),
+        Some(format!("Wrote 1 reply to {}", out.display()).as_str())
+    );
+}
+
+#[test]
+fn should_refuse_email_export_outside_a_series() {
+    let temp = tempfile::tempdir().unwrap();
+    let mut app = build_app(temp.path());
+
+    app.enter_command_mode();
+    app.command_buffer = "export email".to_string();
+    crate::handler::handle_command_action(&mut app, Action::SubmitInput);
+
+    assert!(
+        app.message
+            .as_ref()
+            .is_some_and(|m| m.content.contains("need a mail series"))
+    );
+}
+
+#[test]
+fn should_resume_the_same_session_from_stdin_and_from_a_file() {
+    // given comments left on a patch and a series piped in on stdin
+    let reviews = tempfile::tempdir().unwrap();
+    crate::persistence::storage::set_test_reviews_dir(Some(reviews.path().to_path_buf()));
+    let root = tempfile::tempdir().unwrap();
+    let open = |label: &str, text: &str| {
+        let vcs = PatchBackend::new(label, text, root.path().to_path_buf()).unwrap();
+        match vcs.series() {
+            Some(series) => {
+                let ids: Vec<String> = series.patches.iter().map(|p| p.id.clone()).collect();
+                App::load_or_create_commit_range_session(vcs.info(), &ids)
+            }
+            None => App::load_or_create_session(vcs.info(), SessionDiffSource::Patch),
+        }
+    };
+    for text in [PATCH, SERIES] {
+        let mut session = open("stdin", text);
+        session.review_comments.push(Comment::new(
+            "Looks good".to_string(),
+            CommentType::None,
+            None,
+        ));
+        crate::persistence::save_session(&session).unwrap();
+    }
+
+    // when the same texts are opened from files
+    let resumed = [open("fix.patch", PATCH), open("series.mbox", SERIES)];
+    crate::persistence::storage::set_test_reviews_dir(None);
+
+    // then both resume the piped-in sessions
+    for session in resumed {
+        assert_eq!(session.review_comments.len(), 1);
+    }
+}
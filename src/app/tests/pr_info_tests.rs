@@ -1,9 +1,12 @@
 use std::path::PathBuf;
 
-use crate::app::{App, DiffSource, InputMode, PullRequestDiffSource};
+use crate::app::{
+    App, CheckLogEvent, ConfirmAction, DiffSource, InputMode, PrAction, PrActionEvent,
+    PullRequestDiffSource,
+};
 use crate::forge::traits::{
-    ForgeRepository, PrSessionKey, PullRequestCheckStatus, PullRequestDetails, PullRequestInfo,
-    PullRequestIssueComment, PullRequestReviewStatus,
+    ForgeRepository, MergeMethod, PrSessionKey, PullRequestCheckStatus, PullRequestDetails,
+    PullRequestInfo, PullRequestIssueComment, PullRequestReviewStatus,
 };
 use crate::model::{DiffFile, FileStatus, ReviewSession, SessionDiffSource};
 use crate::theme::Theme;
@@ -98,6 +101,7 @@ fn build_pr_app() -> App {
             is_too_large: false,
             is_commit_message: false,
             content_hash: 0,
+            review_key: None,
         }],
         session,
         DiffSource::PullRequest(Box::new(pr)),
@@ -220,3 +224,148 @@ fn should_keep_pr_info_annotations_in_sync_with_rendered_lines_at_wrap_boundary(
         "PrInfoLine annotation count must equal the rendered PR-info line count"
     );
 }
+
+#[test]
+fn should_confirm_pr_actions_before_running_them() {
+    // given
+    let mut app = build_pr_app();
+
+    // when
+    app.request_pr_action(PrAction::Merge(MergeMethod::Squash));
+
+    // then
+    assert_eq!(app.input_mode, InputMode::Confirm);
+    assert_eq!(
+        app.pending_confirm,
+        Some(ConfirmAction::PrAction(PrAction::Merge(
+            MergeMethod::Squash
+        )))
+    );
+}
+
+#[test]
+fn should_refuse_pr_actions_on_a_merged_pr() {
+    // given the refreshed info reports the PR as merged
+    let mut app = build_pr_app();
+    let mut info = sample_pr_info();
+    info.details.merged_at = Some(chrono::Utc::now());
+    app.pr_info = Some(info);
+
+    // when
+    app.request_pr_action(PrAction::Close);
+
+    // then
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert!(app.pending_confirm.is_none());
+}
+
+#[test]
+fn should_refresh_pr_info_after_a_merge() {
+    // given a merge that finished on the forge
+    let mut app = build_pr_app();
+    let mut info = sample_pr_info();
+    info.details.state = "MERGED".to_string();
+    info.details.merged_at = Some(chrono::Utc::now());
+    let (tx, rx) = std::sync::mpsc::channel();
+    app.pr_action_rx = Some(rx);
+    tx.send(PrActionEvent::Done {
+        repository: ForgeRepository::github("github.com", "owner", "repo"),
+        pr_number: 42,
+        action: PrAction::Merge(MergeMethod::Merge),
+        result: Ok(Some(info)),
+    })
+    .unwrap();
+
+    // when
+    app.poll_pr_action_events();
+
+    // then
+    assert!(app.pr_action_rx.is_none());
+    let DiffSource::PullRequest(pr) = &app.diff_source else {
+        panic!("expected PR diff source");
+    };
+    assert_eq!(pr.read_only_reason(), Some("merged"));
+    assert_eq!(app.pr_info.as_ref().unwrap().details.state, "MERGED");
+}
+
+#[test]
+fn should_refuse_the_log_of_a_passing_check_under_the_cursor() {
+    // given the cursor on the panel's only check, which passed
+    let mut app = build_pr_app();
+    app.diff_state.cursor_line = crate::ui::pr_info_panel::pr_info_render_height(&app) - 1;
+
+    // when
+    app.open_check_log();
+
+    // then
+    assert!(app.check_log_rx.is_none());
+    assert_eq!(
+        app.message.as_ref().map(|m| m.content.as_str()),
+        Some("build has not failed")
+    );
+}
+
+#[test]
+fn should_link_log_lines_to_files_in_the_diff() {
+    // given
+    let mut app = build_pr_app();
+    let raw = "test\tRun tests\t2026-01-01T00:00:00.0000000Z running 3 tests\n\
+               test\tRun tests\t2026-01-01T00:00:01.0000000Z panicked at /home/runner/work/repo/repo/src/lib.rs:12:5\n\
+               test\tRun tests\t2026-01-01T00:00:02.0000000Z error: 1 test failed\n";
+
+    // when
+    app.show_check_log("test".to_string(), raw);
+
+    // then
+    assert_eq!(app.input_mode, InputMode::CheckLog);
+    let log = app.check_log.as_ref().unwrap();
+    assert_eq!(log.lines[0], "running 3 tests");
+    assert_eq!(
+        log.targets,
+        vec![None, Some((PathBuf::from("src/lib.rs"), 12)), None]
+    );
+    assert_eq!(log.selected, 2);
+}
+
+#[test]
+fn should_search_the_check_log_from_the_selection() {
+    // given
+    let mut app = build_pr_app();
+    app.show_check_log(
+        "test".to_string(),
+        "warning: a\nerror: b\nwarning: c\nerror: d\n",
+    );
+    app.check_log.as_mut().unwrap().selected = 0;
+    app.search_buffer = "ERROR".to_string();
+
+    // when / then
+    assert!(app.search_in_check_log_from_selection());
+    assert_eq!(app.check_log.as_ref().unwrap().selected, 1);
+    assert!(app.search_next_in_check_log());
+    assert_eq!(app.check_log.as_ref().unwrap().selected, 3);
+    assert!(app.search_prev_in_check_log());
+    assert_eq!(app.check_log.as_ref().unwrap().selected, 1);
+}
+
+#[test]
+fn should_open_the_check_log_when_the_fetch_finishes() {
+    // given
+    let mut app = build_pr_app();
+    let (tx, rx) = std::sync::mpsc::channel();
+    app.check_log_rx = Some(rx);
+    tx.send(CheckLogEvent::Done {
+        repository: ForgeRepository::github("github.com", "owner", "repo"),
+        pr_number: 42,
+        check_name: "build".to_string(),
+        result: Ok("error: boom\n".to_string()),
+    })
+    .unwrap();
+
+    // when
+    app.poll_check_log_events();
+
+    // then
+    assert!(app.check_log_rx.is_none());
+    assert_eq!(app.input_mode, InputMode::CheckLog);
+    assert_eq!(app.check_log.as_ref().unwrap().lines, vec!["error: boom"]);
+}
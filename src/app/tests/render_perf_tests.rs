@@ -78,6 +78,7 @@ fn file(path: &str, lines_per_file: usize) -> DiffFile {
         old_count: lines_per_file as u32,
         new_start: 1,
         new_count: lines_per_file as u32,
+        raw_body: None,
     }];
     let content_hash = DiffFile::compute_content_hash(&hunks);
     DiffFile {
@@ -89,6 +90,7 @@ fn file(path: &str, lines_per_file: usize) -> DiffFile {
         is_too_large: false,
         is_commit_message: false,
         content_hash,
+        review_key: None,
     }
 }
 
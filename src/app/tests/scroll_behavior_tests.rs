@@ -1,4 +1,6 @@
 use crate::app::*;
+use crate::input::keymap::{KeyBinding, KeyBindings, KeyMode};
+use crate::input::{Action, ChordPrefix};
 use crate::model::FileStatus;
 use crate::ui::row_height::annotation_row_height;
 use crate::vcs::traits::VcsType;
@@ -59,6 +61,7 @@ fn build_scroll_app(n: usize, viewport: usize, scroll_offset_config: usize) -> A
         old_count: n as u32,
         new_start: 1,
         new_count: n as u32,
+        raw_body: None,
     };
 
     let file = DiffFile {
@@ -70,6 +73,7 @@ fn build_scroll_app(n: usize, viewport: usize, scroll_offset_config: usize) -> A
         is_too_large: false,
         is_commit_message: false,
         content_hash: 0,
+        review_key: None,
     };
 
     let vcs_info = VcsInfo {
@@ -749,3 +753,43 @@ fn cursor_down_with_stale_cursor_past_end_does_not_underflow() {
         "cursor should be clamped to the current max line"
     );
 }
+
+fn press(app: &mut App, pending_chord: &mut Option<ChordPrefix>, c: char) -> Option<Action> {
+    let key = crossterm::event::KeyEvent::new(
+        crossterm::event::KeyCode::Char(c),
+        crossterm::event::KeyModifiers::NONE,
+    );
+    crate::handler::resolve_key_action(app, pending_chord, key)
+}
+
+#[test]
+fn chord_bound_to_none_swallows_its_second_key() {
+    let mut app = build_scroll_app(40, 20, 5);
+    app.focused_panel = FocusedPanel::Diff;
+    app.key_bindings = KeyBindings::new(vec![KeyBinding {
+        mode: KeyMode::Normal,
+        chord: "dd".parse().expect("valid chord"),
+        action_name: "none".to_string(),
+        action: Action::None,
+    }]);
+    let mut pending_chord = None;
+
+    // `d` waits for the second key, which the `none` binding then eats.
+    assert_eq!(press(&mut app, &mut pending_chord, 'd'), None);
+    assert_eq!(pending_chord, Some(ChordPrefix::D));
+    assert_eq!(press(&mut app, &mut pending_chord, 'd'), None);
+    assert_eq!(pending_chord, None, "second `d` must not start a new chord");
+
+    // Other chords still resolve, and an unbound second key maps on its own.
+    assert_eq!(press(&mut app, &mut pending_chord, 'z'), None);
+    assert_eq!(
+        press(&mut app, &mut pending_chord, 'z'),
+        Some(Action::CenterCursor)
+    );
+    assert_eq!(press(&mut app, &mut pending_chord, 'd'), None);
+    assert_eq!(
+        press(&mut app, &mut pending_chord, 'j'),
+        Some(Action::CursorDown(1))
+    );
+    assert_eq!(pending_chord, None);
+}
@@ -0,0 +1,297 @@
+use std::time::{Duration, Instant};
+
+use crate::app::*;
+use crate::config::ExportTarget;
+use crate::model::{DiffFile, DiffHunk, DiffLine, FileStatus, LineOrigin};
+use crate::vcs::traits::{VcsBackend, VcsInfo, VcsType};
+
+struct StubVcs(VcsInfo);
+impl VcsBackend for StubVcs {
+    fn info(&self) -> &VcsInfo {
+        &self.0
+    }
+    fn get_working_tree_diff(
+        &self,
+        _hl: &crate::syntax::SyntaxHighlighter,
+    ) -> crate::error::Result<Vec<DiffFile>> {
+        Ok(Vec::new())
+    }
+    fn fetch_context_lines(
+        &self,
+        _path: &std::path::Path,
+        _status: FileStatus,
+        _ref_commit: Option<&str>,
+        _start: u32,
+        _end: u32,
+    ) -> crate::error::Result<Vec<DiffLine>> {
+        Ok(Vec::new())
+    }
+    fn file_line_count(
+        &self,
+        _path: &std::path::Path,
+        _status: FileStatus,
+        _ref_commit: Option<&str>,
+    ) -> crate::error::Result<u32> {
+        Ok(0)
+    }
+}
+
+fn file(path: &str) -> DiffFile {
+    let hunks = vec![DiffHunk {
+        header: "@@ -1,1 +1,1 @@".to_string(),
+        lines: vec![DiffLine {
+            origin: LineOrigin::Context,
+            content: "line 1".to_string(),
+            old_lineno: Some(1),
+            new_lineno: Some(1),
+            highlighted_spans: None,
+        }],
+        old_start: 1,
+        old_count: 1,
+        new_start: 1,
+        new_count: 1,
+        raw_body: None,
+    }];
+    let content_hash = DiffFile::compute_content_hash(&hunks);
+    DiffFile {
+        old_path: None,
+        new_path: Some(PathBuf::from(path)),
+        status: FileStatus::Modified,
+        hunks,
+        is_binary: false,
+        is_too_large: false,
+        is_commit_message: false,
+        content_hash,
+        review_key: None,
+    }
+}
+
+/// An app rooted at `root` with one `[export.targets]` entry named `agent`.
+fn app_with_target(root: &std::path::Path, target: ExportTarget) -> App {
+    let vcs_info = VcsInfo {
+        root_path: root.to_path_buf(),
+        head_commit: "head".into(),
+        branch_name: Some("main".into()),
+        vcs_type: VcsType::Git,
+    };
+    let session = ReviewSession::new(
+        vcs_info.root_path.clone(),
+        vcs_info.head_commit.clone(),
+        vcs_info.branch_name.clone(),
+        SessionDiffSource::WorkingTree,
+    );
+    let mut app = App::build(
+        Box::new(StubVcs(vcs_info.clone())),
+        vcs_info,
+        crate::theme::Theme::dark(),
+        None,
+        false,
+        vec![file("src/main.rs")],
+        session,
+        DiffSource::WorkingTree,
+        InputMode::Normal,
+        Vec::new(),
+        None,
+        None,
+    )
+    .expect("build app");
+    app.export.targets.insert("agent".to_string(), target);
+    app
+}
+
+fn add_review_comment(app: &mut App, content: &str) {
+    add_comment_to_session(
+        &mut app.session,
+        AddCommentRequest {
+            target: CommentTarget::Review,
+            content: content.to_string(),
+            comment_type: CommentType::None,
+            author: "user".to_string(),
+            commit_id: None,
+        },
+    )
+    .expect("add comment");
+}
+
+fn wait_for_send(app: &mut App) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while app.export_send_rx.is_some() {
+        assert!(Instant::now() < deadline, ":send never finished");
+        std::thread::sleep(Duration::from_millis(10));
+        app.poll_export_send_events();
+    }
+}
+
+fn message(app: &App) -> &str {
+    app.message.as_ref().map_or("", |m| m.content.as_str())
+}
+
+#[cfg(unix)]
+#[test]
+fn should_pipe_the_export_into_the_target_command() {
+    // given
+    let dir = tempfile::tempdir().unwrap();
+    let mut app = app_with_target(
+        dir.path(),
+        ExportTarget {
+            command: "cat > review.md".to_string(),
+            ..ExportTarget::default()
+        },
+    );
+    add_review_comment(&mut app, "Split this function");
+
+    // when
+    app.send_to_export_target(None);
+    wait_for_send(&mut app);
+
+    // then
+    let sent = std::fs::read_to_string(dir.path().join("review.md")).unwrap();
+    assert!(sent.contains("Split this function"));
+    assert_eq!(message(&app), "Sent review to agent (exit 0)");
+}
+
+#[cfg(unix)]
+#[test]
+fn should_send_only_new_comments_to_an_only_new_target() {
+    // given
+    let dir = tempfile::tempdir().unwrap();
+    let mut app = app_with_target(
+        dir.path(),
+        ExportTarget {
+            command: "cat > review.md".to_string(),
+            only_new: true,
+            ..ExportTarget::default()
+        },
+    );
+    add_review_comment(&mut app, "First round");
+    app.send_to_export_target(Some("agent"));
+    wait_for_send(&mut app);
+
+    // when
+    app.send_to_export_target(Some("agent"));
+    let message_without_new = message(&app).to_string();
+    add_review_comment(&mut app, "Second round");
+    app.send_to_export_target(Some("agent"));
+    wait_for_send(&mut app);
+
+    // then
+    assert_eq!(message_without_new, "No new comments to send to agent");
+    let sent = std::fs::read_to_string(dir.path().join("review.md")).unwrap();
+    assert!(sent.contains("Second round"));
+    assert!(!sent.contains("First round"));
+    assert_eq!(message(&app), "Sent 1 new comment to agent (exit 0)");
+    assert!(
+        app.session
+            .review_comments
+            .iter()
+            .all(|c| c.sent_to.contains_key("agent"))
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn should_track_sent_comments_per_only_new_target() {
+    // given two only_new targets and a comment already sent to one of them
+    let dir = tempfile::tempdir().unwrap();
+    let mut app = app_with_target(
+        dir.path(),
+        ExportTarget {
+            command: "cat > agent.md".to_string(),
+            only_new: true,
+            ..ExportTarget::default()
+        },
+    );
+    app.export.targets.insert(
+        "codex".to_string(),
+        ExportTarget {
+            command: "cat > codex.md".to_string(),
+            only_new: true,
+            ..ExportTarget::default()
+        },
+    );
+    add_review_comment(&mut app, "First round");
+    app.send_to_export_target(Some("agent"));
+    wait_for_send(&mut app);
+
+    // when
+    app.send_to_export_target(Some("codex"));
+    wait_for_send(&mut app);
+
+    // then the other target still received it
+    let sent = std::fs::read_to_string(dir.path().join("codex.md")).unwrap();
+    assert!(sent.contains("First round"));
+    assert_eq!(message(&app), "Sent 1 new comment to codex (exit 0)");
+    let sent_to = &app.session.review_comments[0].sent_to;
+    assert!(sent_to.contains_key("agent") && sent_to.contains_key("codex"));
+}
+
+#[cfg(unix)]
+#[test]
+fn should_give_up_on_a_target_that_runs_past_its_timeout() {
+    // given a target that never exits
+    let dir = tempfile::tempdir().unwrap();
+    let mut app = app_with_target(
+        dir.path(),
+        ExportTarget {
+            command: "sleep 30".to_string(),
+            timeout_secs: Some(0),
+            ..ExportTarget::default()
+        },
+    );
+    add_review_comment(&mut app, "Please fix");
+
+    // when
+    app.send_to_export_target(None);
+    wait_for_send(&mut app);
+
+    // then the send is over and a new one can start
+    assert_eq!(message(&app), "Send to agent failed: timed out after 0s");
+    assert!(app.export_send_rx.is_none());
+}
+
+#[cfg(unix)]
+#[test]
+fn should_report_the_exit_status_of_a_failing_target() {
+    // given
+    let dir = tempfile::tempdir().unwrap();
+    let mut app = app_with_target(
+        dir.path(),
+        ExportTarget {
+            command: "echo 'agent is offline' >&2; exit 3".to_string(),
+            only_new: true,
+            ..ExportTarget::default()
+        },
+    );
+    add_review_comment(&mut app, "Please fix");
+
+    // when
+    app.send_to_export_target(None);
+    wait_for_send(&mut app);
+
+    // then
+    let message = app.message.as_ref().unwrap();
+    assert_eq!(message.message_type, MessageType::Error);
+    assert_eq!(
+        message.content,
+        "Send to agent failed: exited with status 3: agent is offline"
+    );
+    assert!(app.session.review_comments[0].sent_to.is_empty());
+}
+
+#[test]
+fn should_ask_for_a_target_name_when_several_are_configured() {
+    // given
+    let dir = tempfile::tempdir().unwrap();
+    let mut app = app_with_target(dir.path(), ExportTarget::default());
+    app.export
+        .targets
+        .insert("codex".to_string(), ExportTarget::default());
+    add_review_comment(&mut app, "Please fix");
+
+    // when
+    app.send_to_export_target(None);
+
+    // then
+    assert_eq!(message(&app), "Usage: :send <target> (agent, codex)");
+    assert!(app.export_send_rx.is_none());
+}
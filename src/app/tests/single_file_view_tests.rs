@@ -52,6 +52,7 @@ fn hunk(start: u32, count: u32) -> DiffHunk {
         old_count: count,
         new_start: start,
         new_count: count,
+        raw_body: None,
     }
 }
 
@@ -66,6 +67,7 @@ fn file(path: &str, hunks: Vec<DiffHunk>) -> DiffFile {
         is_too_large: false,
         is_commit_message: false,
         content_hash,
+        review_key: None,
     }
 }
 
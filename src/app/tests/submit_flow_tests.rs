@@ -79,11 +79,13 @@ fn make_pr_app_with_single_modified_file(file_path: &str) -> App {
                     highlighted_spans: None,
                 },
             ],
+            raw_body: None,
         }],
         is_binary: false,
         is_too_large: false,
         is_commit_message: false,
         content_hash: 0,
+        review_key: None,
     };
     let pr_source = PullRequestDiffSource {
         key: PrSessionKey::new(
@@ -159,6 +161,8 @@ fn deliver_matching_pr_threads_event(
         head_sha: pr_key.head_sha,
         threads,
         summaries,
+        viewed_files: Vec::new(),
+        pending_review: Ok(None),
     })
     .unwrap();
 }
@@ -633,10 +637,12 @@ fn make_in_flight(
         mappable,
         summary_comment_ids: Vec::new(),
         review_comment_ids: Vec::new(),
+        reply_comment_ids: Vec::new(),
         moved_to_summary_count,
         head_sha_snapshot: head_sha.to_string(),
         repository: ForgeRepository::github("github.com", "agavra", "tuicr"),
         pr_number: 125,
+        extends_pending_review: false,
         started_at: Instant::now(),
     }
 }
@@ -949,6 +955,96 @@ fn should_emit_draft_message_with_pr_url_for_draft_submit() {
     );
 }
 
+/// Point an app built by `make_pr_app_with_single_modified_file` at a
+/// Bitbucket Cloud pull request instead of a GitHub one.
+fn make_bitbucket_pr_app(file_path: &str) -> App {
+    let mut app = make_pr_app_with_single_modified_file(file_path);
+    if let DiffSource::PullRequest(pr) = &mut app.diff_source {
+        pr.key = PrSessionKey::new(
+            ForgeRepository::bitbucket("bitbucket.org", "example-workspace", "repo"),
+            830,
+            "abcdef0123".to_string(),
+        );
+        pr.url = "https://bitbucket.org/example-workspace/repo/pull-requests/830".to_string();
+    }
+    app
+}
+
+#[test]
+fn should_preflight_every_picker_event_on_bitbucket() {
+    // Bitbucket Cloud supports all four events, so none is rejected
+    // before the backend runs.
+    for (_, event) in SUBMIT_PICKER_EVENTS {
+        let mut app = make_bitbucket_pr_app("src/lib.rs");
+        add_line_comment(
+            &mut app,
+            "src/lib.rs",
+            11,
+            line_comment(LineSide::New, Some(11), None),
+        );
+        app.start_submit(*event);
+        assert_eq!(app.input_mode, InputMode::SubmitConfirm, "{event:?}");
+        let state = app.submit_state.as_ref().expect("submit state");
+        assert_eq!(state.event, *event);
+        assert_eq!(state.mappable.len(), 1);
+    }
+}
+
+#[test]
+fn should_lock_bitbucket_draft_comments_as_pushed_draft() {
+    // given
+    let mut app = make_bitbucket_pr_app("src/lib.rs");
+    let comment = line_comment(LineSide::New, Some(11), None);
+    let comment_id = comment.id.clone();
+    add_line_comment(&mut app, "src/lib.rs", 11, comment);
+    let in_flight = make_in_flight(SubmitEvent::Draft, &[comment_id.as_str()], "abcdef0123", 0);
+    let response = make_response(
+        31,
+        "https://bitbucket.org/example-workspace/repo/pull-requests/830",
+        "PENDING",
+    );
+    // when
+    app.finish_pr_submit(in_flight, Ok(response));
+    // then — the comment is locked and the message says where to finish
+    let review = app.session.files.get(&PathBuf::from("src/lib.rs")).unwrap();
+    let saved = &review.line_comments.get(&11).unwrap()[0];
+    assert_eq!(saved.lifecycle_state, CommentLifecycleState::PushedDraft);
+    assert_eq!(saved.remote_review_id.as_deref(), Some("31"));
+    let msg = app.message.as_ref().expect("info message");
+    assert!(msg.content.contains("Pushed pending Bitbucket review #31"));
+    assert!(
+        msg.content.contains(
+            "Finish it in Bitbucket: https://bitbucket.org/example-workspace/repo/pull-requests/830"
+        ),
+        "got: {}",
+        msg.content
+    );
+}
+
+#[test]
+fn should_lock_bitbucket_request_changes_comments_as_submitted() {
+    // given
+    let mut app = make_bitbucket_pr_app("src/lib.rs");
+    let comment = line_comment(LineSide::New, Some(11), None);
+    let comment_id = comment.id.clone();
+    add_line_comment(&mut app, "src/lib.rs", 11, comment);
+    let in_flight = make_in_flight(
+        SubmitEvent::RequestChanges,
+        &[comment_id.as_str()],
+        "abcdef0123",
+        0,
+    );
+    let response = make_response(5, "u", "CHANGES_REQUESTED");
+    // when
+    app.finish_pr_submit(in_flight, Ok(response));
+    // then
+    let review = app.session.files.get(&PathBuf::from("src/lib.rs")).unwrap();
+    let saved = &review.line_comments.get(&11).unwrap()[0];
+    assert_eq!(saved.lifecycle_state, CommentLifecycleState::Submitted);
+    let msg = app.message.as_ref().expect("info message");
+    assert!(msg.content.contains("Submitted Bitbucket review #5"));
+}
+
 #[test]
 fn should_keep_comments_as_local_draft_on_submit_failure() {
     // given a local-draft comment in the session
@@ -997,7 +1093,8 @@ fn should_discard_stale_submit_result_when_head_sha_changed() {
         repository: ForgeRepository::github("github.com", "agavra", "tuicr"),
         pr_number: 125,
         head_sha: "DIFFERENT_HEAD".to_string(),
-        result: Ok(make_response(42, "u", "COMMENTED")),
+        result: Ok(Some(make_response(42, "u", "COMMENTED"))),
+        sent_reply_ids: Vec::new(),
     })
     .unwrap();
     drop(tx);
@@ -1035,7 +1132,8 @@ fn should_apply_result_via_poll_when_head_sha_matches() {
         repository: ForgeRepository::github("github.com", "agavra", "tuicr"),
         pr_number: 125,
         head_sha: "abcdef0123".to_string(),
-        result: Ok(make_response(123, "u", "COMMENTED")),
+        result: Ok(Some(make_response(123, "u", "COMMENTED"))),
+        sent_reply_ids: Vec::new(),
     })
     .unwrap();
     drop(tx);
@@ -1191,3 +1289,486 @@ fn should_yank_nothing_when_cursor_is_not_on_a_comment() {
     app.diff_state.cursor_line = idx;
     assert_eq!(app.comment_content_at_cursor(), None);
 }
+
+fn remote_thread(id: &str, line: u32) -> crate::forge::remote_comments::RemoteReviewThread {
+    use crate::forge::remote_comments::{RemoteCommentSide, RemoteReviewComment};
+
+    crate::forge::remote_comments::RemoteReviewThread {
+        id: id.to_string(),
+        path: "src/lib.rs".to_string(),
+        line: Some(line),
+        side: RemoteCommentSide::Right,
+        is_resolved: false,
+        is_outdated: false,
+        comments: vec![RemoteReviewComment {
+            id: format!("{id}-c1"),
+            author: Some("alice".to_string()),
+            body: "please rename".to_string(),
+            created_at: None,
+            in_reply_to: None,
+            url: String::new(),
+        }],
+    }
+}
+
+fn move_cursor_to_remote_thread(app: &mut App) {
+    app.rebuild_annotations();
+    app.diff_state.cursor_line = app
+        .line_annotations
+        .iter()
+        .position(|a| matches!(a, AnnotatedLine::RemoteThreadLine { .. }))
+        .expect("expected a remote thread annotation");
+}
+
+#[test]
+fn should_queue_thread_reply_and_reopen_it_for_editing() {
+    // given the cursor on a remote thread
+    let mut app = make_pr_app_with_single_modified_file("src/lib.rs");
+    app.forge_review_threads = vec![remote_thread("T1", 11)];
+    move_cursor_to_remote_thread(&mut app);
+
+    // when a reply is written and saved
+    assert!(app.enter_thread_reply_mode());
+    app.comment_buffer = "done".to_string();
+    app.save_comment();
+
+    // then it is queued on the thread, not as a line comment
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert_eq!(app.session.thread_replies_for("T1").len(), 1);
+    assert_eq!(app.session.thread_replies_for("T1")[0].content, "done");
+    assert!(
+        app.session
+            .files
+            .values()
+            .all(|review| review.line_comments.is_empty())
+    );
+
+    // when the reply is reopened
+    move_cursor_to_remote_thread(&mut app);
+    assert!(app.enter_thread_reply_mode());
+
+    // then the draft is edited in place rather than duplicated
+    assert_eq!(app.comment_buffer, "done");
+    app.comment_buffer = "done, renamed".to_string();
+    app.save_comment();
+    let replies = app.session.thread_replies_for("T1");
+    assert_eq!(replies.len(), 1);
+    assert_eq!(replies[0].content, "done, renamed");
+}
+
+#[test]
+fn should_delete_reply_draft_from_thread_row() {
+    // given a queued reply
+    let mut app = make_pr_app_with_single_modified_file("src/lib.rs");
+    app.forge_review_threads = vec![remote_thread("T1", 11)];
+    app.session.add_thread_reply(
+        "T1".to_string(),
+        Comment::new("done".to_string(), CommentType::None, None),
+    );
+    move_cursor_to_remote_thread(&mut app);
+
+    // when
+    let deleted = app.delete_thread_reply_at_cursor();
+
+    // then
+    assert!(deleted);
+    assert_eq!(app.session.thread_reply_count(), 0);
+    assert!(!app.delete_thread_reply_at_cursor());
+}
+
+#[test]
+fn should_submit_thread_replies_without_other_drafts() {
+    // given only a queued reply
+    let mut app = make_pr_app_with_single_modified_file("src/lib.rs");
+    app.forge_review_threads = vec![remote_thread("T1", 11)];
+    app.session.add_thread_reply(
+        "T1".to_string(),
+        Comment::new("done".to_string(), CommentType::None, None),
+    );
+
+    // when
+    app.start_submit(SubmitEvent::Comment);
+
+    // then the confirm modal opens with the reply counted
+    assert_eq!(app.input_mode, InputMode::SubmitConfirm);
+    let state = app.submit_state.as_ref().expect("submit state");
+    assert!(state.mappable.is_empty());
+    assert_eq!(state.replies.len(), 1);
+    assert_eq!(state.replies[0].thread.id, "T1");
+}
+
+#[test]
+fn should_lock_sent_thread_replies() {
+    // given a queued reply that went out with the submit
+    let mut app = make_pr_app_with_single_modified_file("src/lib.rs");
+    let reply = Comment::new("done".to_string(), CommentType::None, None);
+    let reply_id = reply.id.clone();
+    app.session.add_thread_reply("T1".to_string(), reply);
+
+    // when
+    app.apply_sent_thread_replies(std::slice::from_ref(&reply_id));
+
+    // then
+    assert!(app.session.thread_replies_for("T1")[0].is_locked());
+}
+
+#[test]
+fn should_not_reply_or_resolve_on_read_only_pr() {
+    // given a merged PR with the cursor on a thread
+    let mut app = make_pr_app_with_single_modified_file("src/lib.rs");
+    if let DiffSource::PullRequest(pr) = &mut app.diff_source {
+        pr.merged = true;
+    }
+    app.forge_review_threads = vec![remote_thread("T1", 11)];
+    move_cursor_to_remote_thread(&mut app);
+
+    // when
+    app.enter_thread_reply_mode();
+    app.set_thread_resolved_at_cursor(true);
+
+    // then
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert!(app.pr_thread_resolve_rx.is_none());
+}
+
+#[test]
+fn should_refetch_threads_after_successful_resolve() {
+    // given a resolve result for the open PR
+    let mut app = make_pr_app_with_single_modified_file("src/lib.rs");
+    let (tx, rx) = std::sync::mpsc::channel();
+    app.pr_thread_resolve_rx = Some(rx);
+    tx.send(PrThreadResolveEvent::Done {
+        repository: ForgeRepository::github("github.com", "agavra", "tuicr"),
+        pr_number: 125,
+        resolved: true,
+        result: Ok(()),
+    })
+    .unwrap();
+
+    // when
+    app.poll_pr_thread_resolve_events();
+
+    // then
+    assert!(app.pr_thread_resolve_rx.is_none());
+    assert!(app.forge_review_threads_loading);
+    assert_eq!(
+        app.message.as_ref().map(|m| m.content.as_str()),
+        Some("Resolved thread on GitHub")
+    );
+}
+
+fn move_cursor_to_new_line(app: &mut App, line: u32) {
+    app.rebuild_annotations();
+    app.diff_state.cursor_line = app
+        .line_annotations
+        .iter()
+        .position(|a| {
+            matches!(
+                a,
+                AnnotatedLine::DiffLine { new_lineno: Some(n), .. }
+                    | AnnotatedLine::SideBySideLine { new_lineno: Some(n), .. }
+                    if *n == line
+            )
+        })
+        .expect("expected the line in the diff");
+}
+
+#[test]
+fn should_prefill_suggestion_from_visual_selection() {
+    // given lines 10-11 selected
+    let mut app = make_pr_app_with_single_modified_file("src/lib.rs");
+    move_cursor_to_new_line(&mut app, 10);
+    app.enter_visual_mode_at_cursor();
+    move_cursor_to_new_line(&mut app, 11);
+    app.extend_visual_to_cursor();
+
+    // when
+    app.enter_suggestion_from_visual();
+
+    // then
+    assert_eq!(app.input_mode, InputMode::Comment);
+    assert_eq!(app.comment_buffer, "```suggestion\na\nb\n```");
+    assert_eq!(
+        app.comment_line_range,
+        Some((LineRange::new(10, 11), LineSide::New))
+    );
+    assert_eq!(app.comment_cursor, "```suggestion\na\nb".len());
+    assert!(app.visual_selection.is_none());
+}
+
+#[test]
+fn should_prefill_suggestion_with_the_file_tabs() {
+    // given line 11 indented with a tab, shown expanded to spaces
+    let mut app = make_pr_app_with_single_modified_file("src/lib.rs");
+    let hunk = &mut app.diff_files[0].hunks[0];
+    hunk.lines[1].content = "    b".to_string();
+    hunk.raw_body = Some(" a\n+\tb\n".to_string());
+    move_cursor_to_new_line(&mut app, 11);
+
+    // when
+    app.enter_suggestion_at_cursor();
+
+    // then
+    assert_eq!(app.comment_buffer, "```suggestion\n\tb\n```");
+}
+
+#[test]
+fn should_submit_suggestion_with_fence_on_its_own_line() {
+    // given a saved suggestion on line 11
+    let mut app = make_pr_app_with_single_modified_file("src/lib.rs");
+    move_cursor_to_new_line(&mut app, 11);
+    app.enter_suggestion_at_cursor();
+    app.comment_buffer = "```suggestion\nbb\n```".to_string();
+    app.save_comment();
+
+    // when
+    app.start_submit(SubmitEvent::Comment);
+
+    // then
+    let state = app.submit_state.as_ref().expect("submit state");
+    assert_eq!(state.mappable.len(), 1);
+    let body = &state.mappable[0].body;
+    assert!(
+        body.lines().any(|line| line == "```suggestion"),
+        "fence must start its own line: {body:?}"
+    );
+    assert!(body.ends_with("```suggestion\nbb\n```"));
+}
+
+#[test]
+fn should_refuse_suggestion_on_deleted_side() {
+    // given a cursor on a line with no new-side counterpart
+    let mut app = make_pr_app_with_single_modified_file("src/lib.rs");
+    app.diff_files[0].hunks[0].lines.push(DiffLine {
+        origin: LineOrigin::Deletion,
+        content: "c".to_string(),
+        old_lineno: Some(11),
+        new_lineno: None,
+        highlighted_spans: None,
+    });
+    app.rebuild_annotations();
+    app.diff_state.cursor_line = app
+        .line_annotations
+        .iter()
+        .position(|a| {
+            matches!(
+                a,
+                AnnotatedLine::DiffLine {
+                    new_lineno: None,
+                    old_lineno: Some(11),
+                    ..
+                }
+            )
+        })
+        .expect("deleted line");
+
+    // when
+    app.enter_suggestion_at_cursor();
+
+    // then
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert!(app.comment_buffer.is_empty());
+}
+
+fn pending_review(comment_id: &str, line: u32) -> crate::forge::remote_comments::PendingReview {
+    use crate::forge::remote_comments::{PendingReview, PendingReviewComment, RemoteCommentSide};
+
+    PendingReview {
+        id: "PRR_pending".to_string(),
+        database_id: 77,
+        body: String::new(),
+        comments: vec![PendingReviewComment {
+            id: comment_id.to_string(),
+            path: "src/lib.rs".to_string(),
+            line: Some(line),
+            start_line: None,
+            side: RemoteCommentSide::Right,
+            body: "Add a test".to_string(),
+        }],
+    }
+}
+
+#[test]
+fn should_import_pending_review_comments_as_editable_drafts() {
+    // given
+    let mut app = make_pr_app_with_single_modified_file("src/lib.rs");
+    app.forge_review_threads = vec![remote_thread("t1", 11)];
+
+    // when
+    app.import_pending_review(Some(pending_review("t1-c1", 11)));
+
+    // then
+    let review = app.session.files.get(Path::new("src/lib.rs")).unwrap();
+    let comment = &review.line_comments[&11][0];
+    assert_eq!(comment.content, "Add a test");
+    assert_eq!(comment.lifecycle_state, CommentLifecycleState::PushedDraft);
+    assert_eq!(comment.remote_comment_id.as_deref(), Some("t1-c1"));
+    assert_eq!(comment.remote_review_id.as_deref(), Some("77"));
+    assert!(!comment.is_read_only());
+    assert!(app.forge_review_threads.is_empty());
+    assert!(app.pending_review.is_some());
+}
+
+#[test]
+fn should_allow_publishing_a_pending_review_without_new_comments() {
+    // given
+    let mut app = make_pr_app_with_single_modified_file("src/lib.rs");
+    app.import_pending_review(Some(pending_review("c1", 11)));
+
+    // when
+    app.start_submit(SubmitEvent::Comment);
+
+    // then
+    assert_eq!(app.input_mode, InputMode::SubmitConfirm);
+    assert!(app.submit_state.is_some());
+}
+
+#[test]
+fn should_settle_pending_comments_after_publishing_the_review() {
+    // given
+    let mut app = make_pr_app_with_single_modified_file("src/lib.rs");
+    app.import_pending_review(Some(pending_review("c1", 11)));
+    let mut in_flight = make_in_flight(SubmitEvent::Approve, &[], "abcdef0123", 0);
+    in_flight.extends_pending_review = true;
+
+    // when
+    app.finish_pr_submit(in_flight, Ok(make_response(77, "", "APPROVED")));
+
+    // then
+    assert!(app.pending_review.is_none());
+    let review = app.session.files.get(Path::new("src/lib.rs")).unwrap();
+    assert_eq!(
+        review.line_comments[&11][0].lifecycle_state,
+        CommentLifecycleState::Submitted
+    );
+    assert!(
+        app.message
+            .as_ref()
+            .is_some_and(|m| m.content.starts_with("Published pending GitHub review #77"))
+    );
+}
+
+#[test]
+fn should_offer_discard_in_the_picker_only_with_a_pending_review() {
+    // given
+    let mut app = make_pr_app_with_single_modified_file("src/lib.rs");
+    assert_eq!(app.submit_picker_rows(), SUBMIT_PICKER_EVENTS.len());
+    app.import_pending_review(Some(pending_review("c1", 11)));
+    app.start_submit_action_picker();
+
+    // when
+    app.submit_picker_cursor_up();
+    app.submit_picker_confirm();
+
+    // then
+    assert_eq!(app.input_mode, InputMode::Confirm);
+    assert_eq!(
+        app.pending_confirm,
+        Some(ConfirmAction::DiscardPendingReview)
+    );
+}
+
+/// Records each pending-review call; updates are slow, so a racing delete
+/// would overtake them.
+struct PendingReviewLogBackend(std::sync::Arc<std::sync::Mutex<Vec<String>>>);
+
+impl crate::forge::traits::ForgeBackend for PendingReviewLogBackend {
+    fn list_pull_requests(
+        &self,
+        _q: crate::forge::traits::PullRequestListQuery,
+    ) -> crate::error::Result<crate::forge::traits::PagedPullRequests> {
+        unimplemented!()
+    }
+    fn get_pull_request(
+        &self,
+        _t: crate::forge::traits::PullRequestTarget,
+    ) -> crate::error::Result<crate::forge::traits::PullRequestDetails> {
+        unimplemented!()
+    }
+    fn get_pull_request_diff(
+        &self,
+        _p: &crate::forge::traits::PullRequestDetails,
+    ) -> crate::error::Result<Vec<crate::model::FilePatch>> {
+        unimplemented!()
+    }
+    fn fetch_file_lines(
+        &self,
+        _r: crate::forge::traits::ForgeFileLinesRequest,
+    ) -> crate::error::Result<Vec<DiffLine>> {
+        unimplemented!()
+    }
+    fn list_review_threads(
+        &self,
+        _pr: &crate::forge::traits::PullRequestDetails,
+    ) -> crate::error::Result<Vec<crate::forge::remote_comments::RemoteReviewThread>> {
+        unimplemented!()
+    }
+    fn list_pull_request_commits(
+        &self,
+        _pr: &crate::forge::traits::PullRequestDetails,
+    ) -> crate::error::Result<Vec<crate::forge::traits::PullRequestCommit>> {
+        unimplemented!()
+    }
+    fn get_pull_request_commit_range_diff(
+        &self,
+        _pr: &crate::forge::traits::PullRequestDetails,
+        _start_sha: &str,
+        _end_sha: &str,
+    ) -> crate::error::Result<Vec<crate::model::FilePatch>> {
+        unimplemented!()
+    }
+    fn create_review(
+        &self,
+        _pr: &crate::forge::traits::PullRequestDetails,
+        _request: crate::forge::traits::CreateReviewRequest<'_>,
+    ) -> crate::error::Result<crate::forge::traits::GhCreateReviewResponse> {
+        unimplemented!()
+    }
+    fn update_pending_review_comment(
+        &self,
+        _pr: &crate::forge::traits::PullRequestDetails,
+        comment_id: &str,
+        _body: &str,
+    ) -> crate::error::Result<()> {
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        self.0.lock().unwrap().push(format!("update {comment_id}"));
+        Ok(())
+    }
+    fn delete_pending_review_comment(
+        &self,
+        _pr: &crate::forge::traits::PullRequestDetails,
+        comment_id: &str,
+    ) -> crate::error::Result<()> {
+        self.0.lock().unwrap().push(format!("delete {comment_id}"));
+        Ok(())
+    }
+}
+
+#[test]
+fn should_push_pending_review_changes_in_the_order_they_were_made() {
+    // given a pending comment and a forge that answers updates slowly
+    let mut app = make_pr_app_with_single_modified_file("src/lib.rs");
+    app.import_pending_review(Some(pending_review("c1", 11)));
+    let log = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let worker_log = log.clone();
+    app.pr_pending_review_sync = Some(PrPendingReviewSync::start(move |_| {
+        Box::new(PendingReviewLogBackend(worker_log.clone()))
+    }));
+    let comment = app.session.files[Path::new("src/lib.rs")].line_comments[&11][0].clone();
+
+    // when the comment is edited and then deleted right away
+    app.push_pending_comment_edit(&comment.id);
+    app.push_pending_comment_delete(&comment);
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while app.pr_pending_review_sync.is_some() {
+        assert!(
+            std::time::Instant::now() < deadline,
+            "pending-review sync never finished"
+        );
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        app.poll_pr_pending_review_events();
+    }
+
+    // then the forge saw the delete last
+    assert_eq!(*log.lock().unwrap(), vec!["update c1", "delete c1"]);
+}
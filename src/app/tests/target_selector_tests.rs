@@ -793,6 +793,16 @@ fn two_hunk_pr_patch(second_replacement: &str) -> String {
     .to_string()
 }
 
+fn shift_patch(name: &str) -> String {
+    match name {
+        "head a" => include_str!("../../../tests/fixtures/pr_refresh/shift_head_a.patch"),
+        "head b" => include_str!("../../../tests/fixtures/pr_refresh/shift_head_b.patch"),
+        "interdiff" => include_str!("../../../tests/fixtures/pr_refresh/shift_interdiff.patch"),
+        _ => panic!("unexpected shift patch: {name}"),
+    }
+    .to_string()
+}
+
 fn write_session_file_without_manifest(session: &ReviewSession) {
     let path = crate::persistence::storage::session_path(session).unwrap();
     std::fs::create_dir_all(path.parent().unwrap()).unwrap();
@@ -1579,8 +1589,9 @@ fn should_leave_next_session_unchanged_when_carry_forward_has_no_matching_file()
 
     // when carry-forward has no matching previous file or diff file
     let carried_without_previous =
-        App::reviewed_state_carried_forward(&previous, next.clone(), &app.diff_files);
-    let carried_without_diff = App::reviewed_state_carried_forward(&previous, next.clone(), &[]);
+        App::reviewed_state_carried_forward(&previous, next.clone(), &app.diff_files, None);
+    let carried_without_diff =
+        App::reviewed_state_carried_forward(&previous, next.clone(), &[], None);
 
     // then it keeps the fresh next-session state untouched.
     assert!(carried_without_previous.is_file_reviewed(&stable_path));
@@ -1600,7 +1611,7 @@ fn should_leave_next_session_unchanged_when_carry_forward_has_no_matching_file()
     let mut fresh_next = next;
     fresh_next.get_file_mut(&stable_path).unwrap().reviewed = false;
     let carried_without_diff =
-        App::reviewed_state_carried_forward(&previous_with_reviewed_file, fresh_next, &[]);
+        App::reviewed_state_carried_forward(&previous_with_reviewed_file, fresh_next, &[], None);
     assert!(!carried_without_diff.is_file_reviewed(&stable_path));
 }
 
@@ -1721,7 +1732,7 @@ fn should_carry_reviewed_marks_for_unchanged_files_when_pr_head_advances() {
         .unwrap();
 
     // then unchanged files stay reviewed, changed files reopen, and
-    // draft comments on unchanged files move to the new head.
+    // file-level drafts move to the new head either way.
     assert!(head_changed);
     assert!(app.session.is_file_reviewed(&stable_path));
     assert!(!app.session.is_file_reviewed(&changed_path));
@@ -1729,7 +1740,202 @@ fn should_carry_reviewed_marks_for_unchanged_files_when_pr_head_advances() {
     assert_eq!(stable_review.file_comments.len(), 1);
     assert_eq!(stable_review.file_comments[0].content, "old-head draft");
     let changed_review = app.session.files.get(&changed_path).unwrap();
-    assert!(changed_review.file_comments.is_empty());
+    assert_eq!(changed_review.file_comments.len(), 1);
+    assert_eq!(
+        changed_review.file_comments[0].content,
+        "changed-file draft"
+    );
+    assert!(!changed_review.file_comments[0].outdated);
+}
+
+#[test]
+fn should_carry_drafts_from_previous_head_session_when_reopening_pr() {
+    // given a persisted head-A session with line drafts on a file that the
+    // next commit changes
+    let _reviews = TestReviewsDir::new();
+    let mut app = build_app();
+    let summary = sample_pr(424260, "shift");
+    let mut details_a = test_pr_details(424260, "shift");
+    details_a.head_sha = "aaaaaaaaaaaaaaaa".to_string();
+    let backend_a = Box::new(FakeForgeBackend::open_pr_details(
+        details_a.clone(),
+        shift_patch("head a"),
+    ));
+    app.open_pr_with_backend(&summary, backend_a, None).unwrap();
+    let path = PathBuf::from("src/shift.rs");
+    let review = app.session.get_file_mut(&path).unwrap();
+    review.reviewed = true;
+    review.add_line_comment(
+        4,
+        Comment::new(
+            "four draft".to_string(),
+            CommentType::from_id("issue"),
+            Some(LineSide::New),
+        ),
+    );
+    review.add_line_comment(
+        2,
+        Comment::new(
+            "two draft".to_string(),
+            CommentType::from_id("note"),
+            Some(LineSide::New),
+        ),
+    );
+    app.save_current_session_merging_external().unwrap();
+
+    // when the PR is opened fresh at head B, which inserts a line above
+    // and rewrites line 2
+    let mut reopened = build_app();
+    let mut details_b = details_a.clone();
+    details_b.head_sha = "bbbbbbbbbbbbbbbb".to_string();
+    let mut backend_b = FakeForgeBackend::open_pr_details(details_b, shift_patch("head b"));
+    backend_b.range_patch = Some(shift_patch("interdiff"));
+    reopened
+        .open_pr_with_backend(&summary, Box::new(backend_b), None)
+        .unwrap();
+
+    // then the shifted draft follows its line and the rewritten one is kept
+    // as an outdated file-level comment.
+    let review = reopened.session.files.get(&path).unwrap();
+    assert!(!review.reviewed);
+    assert_eq!(review.line_comments.len(), 1);
+    assert_eq!(review.line_comments[&5][0].content, "four draft");
+    assert_eq!(review.file_comments.len(), 1);
+    let outdated = &review.file_comments[0];
+    assert_eq!(outdated.content, "two draft");
+    assert!(outdated.outdated);
+    assert_eq!(outdated.line_range, Some(LineRange::single(2)));
+}
+
+#[test]
+fn should_mark_line_drafts_outdated_when_interdiff_is_unavailable() {
+    // given a head-A session with a line draft on a file whose diff changes
+    let mut app = build_app();
+    let summary = sample_pr(424261, "shift");
+    let mut details_a = test_pr_details(424261, "shift");
+    details_a.head_sha = "aaaaaaaaaaaaaaaa".to_string();
+    let backend_a = Box::new(FakeForgeBackend::open_pr_details(
+        details_a.clone(),
+        shift_patch("head a"),
+    ));
+    app.open_pr_with_backend(&summary, backend_a, None).unwrap();
+    let path = PathBuf::from("src/shift.rs");
+    app.session.get_file_mut(&path).unwrap().add_line_comment(
+        4,
+        Comment::new(
+            "four draft".to_string(),
+            CommentType::from_id("issue"),
+            None,
+        ),
+    );
+    let previous = app.session.clone();
+
+    // when the new head is built without a diff between the two heads
+    let mut details_b = details_a;
+    details_b.head_sha = "bbbbbbbbbbbbbbbb".to_string();
+    let pr_info_b = crate::forge::traits::PullRequestInfo::from_details(details_b.clone());
+    let opened = crate::forge::pr_open::prepare_open_pr(
+        details_b,
+        structured_patch(&shift_patch("head b")),
+        Vec::new(),
+        PullRequestReviewMetadata::default(),
+        pr_info_b,
+        None,
+        app.theme.syntax_highlighter(),
+    )
+    .unwrap();
+    let next = App::reviewed_state_carried_forward(
+        &previous,
+        opened.session.clone(),
+        &opened.diff_files,
+        None,
+    );
+
+    // then the draft survives, flagged outdated rather than guessed at.
+    let review = next.files.get(&path).unwrap();
+    assert!(review.line_comments.is_empty());
+    assert_eq!(review.file_comments.len(), 1);
+    assert!(review.file_comments[0].outdated);
+}
+
+#[test]
+fn should_keep_drafts_on_files_dropped_from_pr_as_outdated_review_comments() {
+    // given a previous session with drafts on a file the new head no
+    // longer touches
+    let mut app = build_app();
+    let summary = sample_pr(424262, "dropped");
+    let backend = Box::new(FakeForgeBackend::open_pr_details(
+        test_pr_details(424262, "dropped"),
+        two_file_patch("new changed"),
+    ));
+    app.open_pr_with_backend(&summary, backend, None).unwrap();
+    let gone_path = PathBuf::from("src/gone.rs");
+    let mut previous = app.session.clone();
+    previous.files.insert(
+        gone_path.clone(),
+        crate::model::review::FileReview::new(gone_path.clone(), FileStatus::Modified, 1),
+    );
+    let gone = previous.get_file_mut(&gone_path).unwrap();
+    gone.add_file_comment(Comment::new(
+        "file draft".to_string(),
+        CommentType::from_id("note"),
+        None,
+    ));
+    gone.add_line_comment(
+        3,
+        Comment::new(
+            "line draft".to_string(),
+            CommentType::from_id("issue"),
+            Some(LineSide::New),
+        ),
+    );
+
+    // when carrying forward onto a session without that file
+    let next =
+        App::reviewed_state_carried_forward(&previous, app.session.clone(), &app.diff_files, None);
+
+    // then both drafts become outdated review-level comments naming where
+    // they used to live.
+    assert!(!next.files.contains_key(&gone_path));
+    let contents: Vec<_> = next
+        .review_comments
+        .iter()
+        .map(|comment| (comment.content.as_str(), comment.outdated))
+        .collect();
+    assert_eq!(
+        contents,
+        vec![
+            ("`src/gone.rs`: file draft", true),
+            ("`src/gone.rs:3`: line draft", true),
+        ]
+    );
+}
+
+#[test]
+fn should_map_lines_through_interdiff_hunks() {
+    // given the diff between two heads of src/shift.rs
+    let theme = Theme::default();
+    let files = crate::vcs::diff_parser::parse_file_patches(
+        structured_patch(&shift_patch("interdiff")),
+        theme.syntax_highlighter(),
+    )
+    .unwrap();
+    let diff = &files[0];
+
+    // when / then context lines and lines after the hunk move, while
+    // rewritten lines have no counterpart.
+    assert_eq!(
+        crate::app::carry_forward::map_line_through_diff(diff, 1),
+        Some(2)
+    );
+    assert_eq!(
+        crate::app::carry_forward::map_line_through_diff(diff, 2),
+        None
+    );
+    assert_eq!(
+        crate::app::carry_forward::map_line_through_diff(diff, 4),
+        Some(5)
+    );
 }
 
 #[test]
@@ -1785,8 +1991,12 @@ fn should_build_new_head_session_by_carrying_only_unchanged_reviewed_state() {
         highlighter,
     )
     .unwrap();
-    let next =
-        App::reviewed_state_carried_forward(&previous, opened.session.clone(), &opened.diff_files);
+    let next = App::reviewed_state_carried_forward(
+        &previous,
+        opened.session.clone(),
+        &opened.diff_files,
+        None,
+    );
 
     // then unchanged files carry reviewed state and changed files reopen.
     assert!(next.is_file_reviewed(&stable_path));
@@ -1844,8 +2054,12 @@ fn should_carry_unchanged_hunk_marks_inside_changed_file_when_pr_head_advances()
     .unwrap();
     let new_first_key = opened.diff_files[0].hunk_review_key(0).unwrap();
     let new_second_key = opened.diff_files[0].hunk_review_key(1).unwrap();
-    let next =
-        App::reviewed_state_carried_forward(&previous, opened.session.clone(), &opened.diff_files);
+    let next = App::reviewed_state_carried_forward(
+        &previous,
+        opened.session.clone(),
+        &opened.diff_files,
+        None,
+    );
 
     // then only the unchanged hunk stays reviewed; the file and changed
     // hunk reopen.
@@ -2511,11 +2725,16 @@ use crate::forge::remote_comments::{
     PrCommentsVisibility, RemoteCommentSide, RemoteReviewComment, RemoteReviewThread,
 };
 
+/// `(path, viewed)` pairs in the order the forge received them.
+type ViewedLog = std::sync::Arc<std::sync::Mutex<Vec<(std::path::PathBuf, bool)>>>;
+
 struct ThreadAwareForgeBackend {
     details: crate::forge::traits::PullRequestDetails,
     patch: String,
     threads: Vec<RemoteReviewThread>,
     calls: std::cell::Cell<u32>,
+    /// When set, the backend has a "Viewed" checkbox and logs every update.
+    viewed_log: Option<ViewedLog>,
 }
 
 impl ThreadAwareForgeBackend {
@@ -2529,8 +2748,14 @@ impl ThreadAwareForgeBackend {
             patch,
             threads,
             calls: std::cell::Cell::new(0),
+            viewed_log: None,
         }
     }
+
+    fn with_viewed_log(mut self, log: ViewedLog) -> Self {
+        self.viewed_log = Some(log);
+        self
+    }
 }
 
 impl crate::forge::traits::ForgeBackend for ThreadAwareForgeBackend {
@@ -2586,6 +2811,24 @@ impl crate::forge::traits::ForgeBackend for ThreadAwareForgeBackend {
     ) -> Result<crate::forge::traits::GhCreateReviewResponse> {
         unimplemented!()
     }
+    fn supports_viewed_files(&self) -> bool {
+        self.viewed_log.is_some()
+    }
+    fn set_file_viewed(
+        &self,
+        _pr: &crate::forge::traits::PullRequestDetails,
+        path: &std::path::Path,
+        viewed: bool,
+    ) -> Result<()> {
+        // Marking is the slow call, so a racing unmark would overtake it.
+        if viewed {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+        if let Some(log) = &self.viewed_log {
+            log.lock().unwrap().push((path.to_path_buf(), viewed));
+        }
+        Ok(())
+    }
 }
 
 fn sample_thread(line: u32, body: &str, resolved: bool, outdated: bool) -> RemoteReviewThread {
@@ -2737,6 +2980,8 @@ fn should_apply_remote_threads_event_when_relevant() {
             sample_thread(2, "duplicate", false, false),
         ]),
         summaries: Ok(Vec::new()),
+        viewed_files: Vec::new(),
+        pending_review: Ok(None),
     })
     .unwrap();
     // when
@@ -2768,6 +3013,8 @@ fn should_discard_stale_remote_threads_event_after_switching_pr() {
         head_sha: "definitely-not-this".into(), // wrong head
         threads: Ok(vec![sample_thread(2, "stale", false, false)]),
         summaries: Ok(Vec::new()),
+        viewed_files: Vec::new(),
+        pending_review: Ok(None),
     })
     .unwrap();
     // when
@@ -2775,3 +3022,149 @@ fn should_discard_stale_remote_threads_event_after_switching_pr() {
     // then — stale result was dropped
     assert!(app.forge_review_threads.is_empty());
 }
+
+#[test]
+fn should_seed_reviewed_files_from_the_forge_viewed_state() {
+    // given a PR open with nothing reviewed locally
+    let mut app = build_app();
+    let summary = sample_pr(42, "answer");
+    let backend = Box::new(ThreadAwareForgeBackend::new(
+        test_pr_details(42, "answer"),
+        crate::forge::github::gh::tests_fixture::SIMPLE_PATCH.to_string(),
+        Vec::new(),
+    ));
+    app.open_pr_with_backend(&summary, backend, None).unwrap();
+    let pr_key = match &app.diff_source {
+        DiffSource::PullRequest(pr) => pr.key.clone(),
+        _ => panic!("expected PR mode"),
+    };
+    let (tx, rx) = std::sync::mpsc::channel();
+    app.pr_threads_rx = Some(rx);
+    tx.send(crate::app::PrThreadsEvent::Done {
+        repository: pr_key.repository.clone(),
+        pr_number: pr_key.number,
+        head_sha: pr_key.head_sha.clone(),
+        threads: Ok(Vec::new()),
+        summaries: Ok(Vec::new()),
+        viewed_files: vec![
+            std::path::PathBuf::from("src/lib.rs"),
+            std::path::PathBuf::from("not/in/this/diff.rs"),
+        ],
+        pending_review: Ok(None),
+    })
+    .unwrap();
+    // when
+    app.poll_pr_threads_events();
+    // then
+    assert!(
+        app.session
+            .is_file_reviewed(&std::path::PathBuf::from("src/lib.rs"))
+    );
+}
+
+#[test]
+fn should_not_push_viewed_state_when_the_forge_has_no_viewed_checkbox() {
+    // given a PR open on a backend without viewed-file support
+    let mut app = build_app();
+    let summary = sample_pr(42, "answer");
+    let backend = Box::new(ThreadAwareForgeBackend::new(
+        test_pr_details(42, "answer"),
+        crate::forge::github::gh::tests_fixture::SIMPLE_PATCH.to_string(),
+        Vec::new(),
+    ));
+    app.open_pr_with_backend(&summary, backend, None).unwrap();
+    // when
+    app.toggle_reviewed_for_file_idx(0, false);
+    // then — marked locally, nothing queued for the forge
+    assert!(
+        app.session
+            .is_file_reviewed(&std::path::PathBuf::from("src/lib.rs"))
+    );
+    assert!(app.pr_viewed_sync.is_none());
+}
+
+#[test]
+fn should_push_viewed_updates_in_toggle_order() {
+    // given a PR open on a backend with a "Viewed" checkbox
+    let mut app = build_app();
+    let summary = sample_pr(42, "answer");
+    let log = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let backend = |log| {
+        ThreadAwareForgeBackend::new(
+            test_pr_details(42, "answer"),
+            crate::forge::github::gh::tests_fixture::SIMPLE_PATCH.to_string(),
+            Vec::new(),
+        )
+        .with_viewed_log(log)
+    };
+    app.open_pr_with_backend(&summary, Box::new(backend(log.clone())), None)
+        .unwrap();
+    let worker_log = log.clone();
+    app.pr_viewed_sync = Some(crate::app::PrViewedSync::start(move |_| {
+        Box::new(backend(worker_log.clone()))
+    }));
+    // when the file is marked and immediately unmarked
+    app.toggle_reviewed_for_file_idx(0, false);
+    app.toggle_reviewed_for_file_idx(0, false);
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while app.pr_viewed_sync.is_some() {
+        assert!(
+            std::time::Instant::now() < deadline,
+            "viewed sync never finished"
+        );
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        app.poll_pr_viewed_sync_events();
+    }
+    // then the forge saw the unmark last, matching the local state
+    let path = std::path::PathBuf::from("src/lib.rs");
+    assert!(!app.session.is_file_reviewed(&path));
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![(path.clone(), true), (path, false)]
+    );
+}
+
+#[test]
+fn should_warn_when_a_viewed_sync_fails_for_the_open_pr() {
+    // given a PR open and one failed "Viewed" update in flight
+    let mut app = build_app();
+    let summary = sample_pr(42, "answer");
+    let backend = Box::new(ThreadAwareForgeBackend::new(
+        test_pr_details(42, "answer"),
+        crate::forge::github::gh::tests_fixture::SIMPLE_PATCH.to_string(),
+        Vec::new(),
+    ));
+    app.open_pr_with_backend(&summary, backend, None).unwrap();
+    let pr_key = match &app.diff_source {
+        DiffSource::PullRequest(pr) => pr.key.clone(),
+        _ => panic!("expected PR mode"),
+    };
+    let (tx, rx) = std::sync::mpsc::channel();
+    let (jobs, _) = std::sync::mpsc::channel();
+    app.pr_viewed_sync = Some(crate::app::PrViewedSync {
+        jobs,
+        tx: tx.clone(),
+        rx,
+        in_flight: 1,
+    });
+    tx.send(crate::app::PrViewedSyncEvent::Done {
+        repository: pr_key.repository.clone(),
+        pr_number: pr_key.number,
+        path: std::path::PathBuf::from("src/lib.rs"),
+        viewed: true,
+        result: Err("HTTP 403: Resource not accessible".to_string()),
+    })
+    .unwrap();
+    // when
+    app.poll_pr_viewed_sync_events();
+    // then
+    assert!(app.pr_viewed_sync.is_none());
+    let msg = app.message.as_ref().expect("expected a warning");
+    assert!(matches!(msg.message_type, MessageType::Warning));
+    assert!(
+        msg.content
+            .contains("Failed to mark src/lib.rs as viewed on GitHub"),
+        "got message: {}",
+        msg.content
+    );
+}
@@ -12,6 +12,7 @@ fn make_file(path: &str) -> DiffFile {
         is_too_large: false,
         is_commit_message: false,
         content_hash: 0,
+        review_key: None,
     }
 }
 
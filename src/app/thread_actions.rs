@@ -0,0 +1,228 @@
+use super::*;
+
+use crate::forge::remote_comments::RemoteReviewThread;
+
+impl App {
+    /// The remote review thread whose box the cursor is on, if any.
+    pub fn remote_thread_at_cursor(&self) -> Option<&RemoteReviewThread> {
+        match self.line_annotations.get(self.diff_state.cursor_line) {
+            Some(AnnotatedLine::RemoteThreadLine { thread_idx }) => {
+                self.forge_review_threads.get(*thread_idx)
+            }
+            _ => None,
+        }
+    }
+
+    /// Shared guard for thread replies and resolution: the open PR must
+    /// accept writes. Emits the warning and returns false otherwise.
+    fn ensure_pr_accepts_thread_actions(&mut self, action: &str) -> bool {
+        let DiffSource::PullRequest(pr) = &self.diff_source else {
+            self.set_warning(format!("{action} only applies in PR mode"));
+            return false;
+        };
+        if let Some(reason) = pr.read_only_reason() {
+            self.set_warning(format!("Cannot {action}: PR is {reason}"));
+            return false;
+        }
+        true
+    }
+
+    /// Open the reply input for the thread under the cursor. Re-opens the
+    /// thread's unsent reply draft when one exists so a thread never queues
+    /// two replies by accident. Returns false when the cursor is not on a
+    /// thread.
+    pub fn enter_thread_reply_mode(&mut self) -> bool {
+        let Some(thread) = self.remote_thread_at_cursor() else {
+            return false;
+        };
+        let thread_id = thread.id.clone();
+        if !self.ensure_pr_accepts_thread_actions("reply") {
+            return true;
+        }
+        let draft = self
+            .session
+            .thread_replies_for(&thread_id)
+            .iter()
+            .rfind(|c| !c.is_locked())
+            .map(|c| (c.id.clone(), c.content.clone(), c.comment_type.clone()));
+
+        self.input_mode = InputMode::Comment;
+        self.comment_cursor = 0;
+        self.comment_buffer.clear();
+        self.editing_comment_id = None;
+        // Replies are conversational, so they start untyped rather than
+        // with the configured default.
+        self.comment_type = CommentType::None;
+        if let Some((id, content, comment_type)) = draft {
+            self.comment_cursor = content.len();
+            self.comment_buffer = content;
+            self.comment_type = comment_type;
+            self.editing_comment_id = Some(id);
+        }
+        self.comment_is_review_level = false;
+        self.comment_is_file_level = false;
+        self.comment_line = None;
+        self.comment_line_range = None;
+        self.comment_reply_thread_id = Some(thread_id);
+        true
+    }
+
+    /// Store the reply input as a draft on its thread. Called from
+    /// `save_comment` when `comment_reply_thread_id` is set.
+    pub(super) fn save_thread_reply(&mut self, thread_id: String, content: String) {
+        let editing = self.editing_comment_id.clone();
+        let existing = editing.and_then(|id| {
+            self.session
+                .thread_replies
+                .get_mut(&thread_id)?
+                .iter_mut()
+                .find(|c| c.id == id && !c.is_locked())
+        });
+        let message = if let Some(comment) = existing {
+            comment.content = content;
+            comment.comment_type = self.comment_type.clone();
+            "Reply updated — sent with :submit"
+        } else {
+            let comment = Comment::new(content, self.comment_type.clone(), None)
+                .with_author(self.username.clone());
+            self.session.add_thread_reply(thread_id, comment);
+            "Reply queued — sent with :submit"
+        };
+
+        self.dirty = true;
+        if let Err(e) = self.save_current_session_merging_external() {
+            self.set_error(format!("{message}; autosave failed: {e}"));
+        } else {
+            self.set_message(message);
+        }
+        self.rebuild_annotations();
+        self.exit_comment_mode();
+    }
+
+    /// Drop the newest unsent reply draft on the thread under the cursor.
+    /// Returns false when the cursor is not on a thread with a draft.
+    pub fn delete_thread_reply_at_cursor(&mut self) -> bool {
+        let Some(thread_id) = self.remote_thread_at_cursor().map(|t| t.id.clone()) else {
+            return false;
+        };
+        let Some(replies) = self.session.thread_replies.get_mut(&thread_id) else {
+            return false;
+        };
+        let Some(index) = replies.iter().rposition(|c| !c.is_locked()) else {
+            return false;
+        };
+        replies.remove(index);
+        if replies.is_empty() {
+            self.session.thread_replies.remove(&thread_id);
+        }
+        self.dirty = true;
+        self.set_message("Reply draft deleted");
+        self.rebuild_annotations();
+        true
+    }
+
+    /// Resolve (or reopen) the thread under the cursor on the forge. Runs
+    /// in the background; `poll_pr_thread_resolve_events` applies the result.
+    pub fn set_thread_resolved_at_cursor(&mut self, resolved: bool) {
+        let verb = if resolved { "resolve" } else { "unresolve" };
+        let Some(thread) = self.remote_thread_at_cursor().cloned() else {
+            self.set_message(format!("Move cursor to a review thread to {verb} it"));
+            return;
+        };
+        if !self.ensure_pr_accepts_thread_actions(verb) {
+            return;
+        }
+        if thread.is_resolved == resolved {
+            let state = if resolved { "resolved" } else { "unresolved" };
+            self.set_message(format!("Thread is already {state}"));
+            return;
+        }
+        if self.pr_thread_resolve_rx.is_some() {
+            self.set_message("A thread update is already in progress");
+            return;
+        }
+        let DiffSource::PullRequest(pr) = &self.diff_source else {
+            return;
+        };
+        let repository = pr.key.repository.clone();
+        let pr_number = pr.key.number;
+        let local_checkout = self
+            .forge_backend
+            .as_deref()
+            .and_then(|backend| backend.local_checkout_path());
+        let show_pr_checks = self.show_pr_checks;
+        let show_pr_comments = self.show_pr_comments;
+        let forge_config = self.forge_config.clone();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.pr_thread_resolve_rx = Some(rx);
+        std::thread::spawn(move || {
+            let backend = create_forge_backend(
+                &repository,
+                local_checkout,
+                show_pr_checks,
+                show_pr_comments,
+                &forge_config,
+            );
+            let target = crate::forge::traits::PullRequestTarget::with_repository(
+                repository.clone(),
+                pr_number,
+                pr_number.to_string(),
+            );
+            let result = backend
+                .get_pull_request(target)
+                .and_then(|details| backend.set_review_thread_resolved(&details, &thread, resolved))
+                .map_err(|e| e.to_string());
+            let _ = tx.send(PrThreadResolveEvent::Done {
+                repository,
+                pr_number,
+                resolved,
+                result,
+            });
+        });
+    }
+
+    /// Pump a pending resolve/unresolve result. Success refetches the
+    /// threads so the box picks up the forge's new state.
+    pub fn poll_pr_thread_resolve_events(&mut self) {
+        let Some(rx) = self.pr_thread_resolve_rx.as_ref() else {
+            return;
+        };
+        let event = match rx.try_recv() {
+            Ok(e) => e,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                self.pr_thread_resolve_rx = None;
+                return;
+            }
+        };
+        self.pr_thread_resolve_rx = None;
+
+        let PrThreadResolveEvent::Done {
+            repository,
+            pr_number,
+            resolved,
+            result,
+        } = event;
+        let still_relevant = matches!(
+            &self.diff_source,
+            DiffSource::PullRequest(pr)
+                if pr.key.repository == repository && pr.key.number == pr_number
+        );
+        if !still_relevant {
+            return;
+        }
+        let forge = self.forge_display_name();
+        match result {
+            Ok(()) => {
+                let verb = if resolved { "Resolved" } else { "Reopened" };
+                self.set_message(format!("{verb} thread on {forge}"));
+                self.refetch_pr_threads();
+            }
+            Err(e) => {
+                let verb = if resolved { "resolve" } else { "unresolve" };
+                self.set_error(format!("Failed to {verb} thread on {forge}: {e}"));
+            }
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use super::*;
+
+use crate::forge::traits::PullRequestDetails;
+
+impl PrViewedSync {
+    /// Start a worker that runs queued updates in order on a backend from
+    /// `backend_for`. It exits once the sync is dropped.
+    pub fn start<F>(backend_for: F) -> Self
+    where
+        F: Fn(&PullRequestDetails) -> Box<dyn ForgeBackend> + Send + 'static,
+    {
+        let (jobs, job_rx) = std::sync::mpsc::channel::<PrViewedJob>();
+        let (tx, rx) = std::sync::mpsc::channel();
+        let worker_tx = tx.clone();
+        std::thread::spawn(move || {
+            for PrViewedJob {
+                details,
+                path,
+                viewed,
+            } in job_rx
+            {
+                let result = backend_for(&details)
+                    .set_file_viewed(&details, &path, viewed)
+                    .map_err(|e| e.to_string());
+                let _ = worker_tx.send(PrViewedSyncEvent::Done {
+                    repository: details.repository,
+                    pr_number: details.number,
+                    path,
+                    viewed,
+                    result,
+                });
+            }
+        });
+        Self {
+            jobs,
+            tx,
+            rx,
+            in_flight: 0,
+        }
+    }
+}
+
+impl App {
+    /// Whether reviewed marks round-trip through the forge's "Viewed"
+    /// checkbox for the open PR.
+    pub(in crate::app) fn syncs_viewed_files(&self) -> bool {
+        self.forge_config.sync_viewed
+            && matches!(self.diff_source, DiffSource::PullRequest(_))
+            && self
+                .forge_backend
+                .as_deref()
+                .is_some_and(|backend| backend.supports_viewed_files())
+    }
+
+    /// Mark files the viewer already checked off on the forge as reviewed.
+    /// Only adds marks: a file reviewed locally but not on the forge keeps
+    /// its local state. Returns how many files changed.
+    pub(in crate::app) fn seed_reviewed_from_viewed_files(&mut self, viewed: &[PathBuf]) -> usize {
+        let mut seeded = 0;
+        for path in viewed {
+            if let Some(review) = self.session.get_file_mut(path)
+                && !review.reviewed
+            {
+                review.reviewed = true;
+                seeded += 1;
+            }
+        }
+        if seeded > 0 {
+            self.dirty = true;
+        }
+        seeded
+    }
+
+    /// Push a reviewed toggle to the forge's "Viewed" checkbox in the
+    /// background. Failures surface as a warning; the local mark stands.
+    pub(in crate::app) fn push_viewed_state(&mut self, path: PathBuf, viewed: bool) {
+        if !self.syncs_viewed_files() {
+            return;
+        }
+        let DiffSource::PullRequest(pr) = &self.diff_source else {
+            return;
+        };
+        let details = pr.details();
+        let local_checkout = self
+            .forge_backend
+            .as_deref()
+            .and_then(|backend| backend.local_checkout_path());
+        let show_pr_checks = self.show_pr_checks;
+        let show_pr_comments = self.show_pr_comments;
+        let forge_config = self.forge_config.clone();
+
+        let sync = self.pr_viewed_sync.get_or_insert_with(|| {
+            PrViewedSync::start(move |details| {
+                create_forge_backend(
+                    &details.repository,
+                    local_checkout.clone(),
+                    show_pr_checks,
+                    show_pr_comments,
+                    &forge_config,
+                )
+            })
+        });
+        let job = PrViewedJob {
+            details,
+            path,
+            viewed,
+        };
+        if sync.jobs.send(job).is_ok() {
+            sync.in_flight += 1;
+        }
+    }
+
+    /// Drain finished "Viewed" updates, warning about any that failed for
+    /// the PR still open.
+    pub fn poll_pr_viewed_sync_events(&mut self) {
+        let Some(sync) = self.pr_viewed_sync.as_mut() else {
+            return;
+        };
+        let mut failures = Vec::new();
+        while let Ok(event) = sync.rx.try_recv() {
+            sync.in_flight = sync.in_flight.saturating_sub(1);
+            let PrViewedSyncEvent::Done {
+                repository,
+                pr_number,
+                path,
+                viewed,
+                result,
+            } = event;
+            if let Err(e) = result {
+                failures.push((repository, pr_number, path, viewed, e));
+            }
+        }
+        if sync.in_flight == 0 {
+            self.pr_viewed_sync = None;
+        }
+
+        let forge = self.forge_display_name();
+        for (repository, pr_number, path, viewed, e) in failures {
+            let still_relevant = matches!(
+                &self.diff_source,
+                DiffSource::PullRequest(pr)
+                    if pr.key.repository == repository && pr.key.number == pr_number
+            );
+            if !still_relevant {
+                continue;
+            }
+            let verb = if viewed { "mark" } else { "unmark" };
+            self.set_warning(format!(
+                "Failed to {verb} {} as viewed on {forge}: {e}",
+                path.display()
+            ));
+        }
+    }
+}
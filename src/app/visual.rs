@@ -147,4 +147,55 @@ impl App {
             self.exit_visual_mode();
         }
     }
+
+    /// Open the comment editor prefilled with a suggested-change block over
+    /// the visual selection.
+    pub fn enter_suggestion_from_visual(&mut self) {
+        let Some((range, side)) = self.visual_selection_line_range() else {
+            self.set_warning("Invalid visual selection");
+            self.exit_visual_mode();
+            return;
+        };
+        self.visual_selection = None;
+        self.input_mode = InputMode::Normal;
+        self.enter_suggestion_mode(range, side);
+    }
+
+    /// Open the comment editor prefilled with a suggested-change block over
+    /// the line under the cursor.
+    pub fn enter_suggestion_at_cursor(&mut self) {
+        match self.get_line_at_cursor() {
+            Some((line, side)) => self.enter_suggestion_mode(LineRange::single(line), side),
+            None => self.set_message("Move cursor to a diff line to suggest a change"),
+        }
+    }
+
+    fn enter_suggestion_mode(&mut self, range: LineRange, side: LineSide) {
+        if side != LineSide::New {
+            self.set_warning("Suggestions replace new-side lines — select added or context lines");
+            return;
+        }
+        let Some(lines) = self
+            .current_file()
+            .and_then(|file| file.new_side_lines(range))
+        else {
+            self.set_warning("Suggestions need every selected line visible in the diff");
+            return;
+        };
+        let block = crate::model::comment::suggestion_block(&lines);
+
+        self.enter_comment_mode(false, Some((range.end, side)));
+        self.comment_line_range = Some((range, side));
+        if self
+            .comment_types
+            .iter()
+            .any(|comment_type| comment_type.id == "suggestion")
+        {
+            self.comment_type = CommentType::from_id("suggestion");
+        }
+        // Park the cursor at the end of the last suggested line, just above
+        // the closing fence, where the edit usually starts.
+        self.comment_cursor = block.len() - "\n```".len();
+        self.comment_buffer = block;
+    }
 }
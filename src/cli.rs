@@ -7,6 +7,7 @@ use std::path::PathBuf;
 
 use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
 
+use crate::output::ExportFormat;
 use crate::theme::{AppearanceArg, ThemeArg};
 
 /// CLI arguments consumed by the rest of the binary.
@@ -16,6 +17,8 @@ pub struct CliArgs {
     pub appearance: Option<AppearanceArg>,
     /// Output to stdout instead of clipboard when exporting.
     pub output_to_stdout: bool,
+    /// Export format override for `y`, `:clip` and `--stdout`.
+    pub export_format: Option<ExportFormat>,
     /// Skip checking for updates on startup.
     pub no_update_check: bool,
     /// Commit/revision range to review.
@@ -28,6 +31,10 @@ pub struct CliArgs {
     pub file_path: Option<String>,
     /// Whole-repo annotation mode.
     pub all_files: bool,
+    /// Review a raw patch file, or stdin for `-`.
+    pub patch: Option<String>,
+    /// Linter or compiler output to show on changed lines at startup.
+    pub diagnostics: Option<String>,
     /// Direct PR target from `tuicr pr <target>`.
     pub pr_target: Option<String>,
     /// Override the GitHub repo used for PR operations.
@@ -36,6 +43,8 @@ pub struct CliArgs {
     pub review_command: Option<ReviewCommand>,
     /// Update the installed tuicr binary and exit.
     pub update_command: bool,
+    /// Serve persisted review sessions over MCP on stdio.
+    pub mcp_command: bool,
     /// Exact version requested by `tuicr update`, if any.
     pub update_version: Option<semver::Version>,
 }
@@ -102,7 +111,7 @@ struct TuiOptions {
         long = "file",
         value_name = "PATH",
         value_parser = non_empty_path,
-        conflicts_with_all = ["path_filter", "revisions", "working_tree", "all_files"],
+        conflicts_with_all = ["path_filter", "revisions", "working_tree", "all_files", "patch"],
     )]
     file_path: Option<String>,
 
@@ -111,14 +120,35 @@ struct TuiOptions {
         short = 'A',
         long = "all-files",
         action = ArgAction::SetTrue,
-        conflicts_with_all = ["path_filter", "revisions", "working_tree", "file_path"],
+        conflicts_with_all = ["path_filter", "revisions", "working_tree", "file_path", "patch"],
     )]
     all_files: bool,
 
+    /// Review a unified or git-format patch without applying it. Pass `-` to
+    /// read the patch from stdin.
+    #[arg(
+        long = "patch",
+        value_name = "FILE",
+        value_parser = non_empty_path,
+        allow_hyphen_values = true,
+        conflicts_with_all = ["path_filter", "revisions", "working_tree"],
+    )]
+    patch: Option<String>,
+
+    /// Show linter or compiler diagnostics from FILE on changed lines.
+    /// Accepts `cargo --message-format=json`, SARIF, or `file:line:col: message`.
+    #[arg(long = "diagnostics", value_name = "FILE", value_parser = non_empty_path)]
+    diagnostics: Option<String>,
+
     /// Output to stdout instead of clipboard when exporting.
     #[arg(long = "stdout", action = ArgAction::SetTrue)]
     stdout: bool,
 
+    /// Export format for the clipboard and --stdout. Overrides
+    /// `[export] format` in the config.
+    #[arg(long = "format", value_name = "FORMAT", value_enum)]
+    format: Option<ExportFormat>,
+
     /// Skip checking for updates on startup.
     #[arg(long = "no-update-check", action = ArgAction::SetTrue)]
     no_update_check: bool,
@@ -145,6 +175,9 @@ enum Subcmd {
         #[command(subcommand)]
         command: ReviewCommand,
     },
+    /// Serve review sessions to coding agents over the Model Context
+    /// Protocol on stdio.
+    Mcp,
     /// Update the installed tuicr binary.
     Update {
         /// Install a specific SemVer release, including an older known-good version.
@@ -263,6 +296,121 @@ pub enum ReviewCommand {
         #[arg(long, value_name = "PATH|OWNER/REPO", default_value = ".")]
         repo: PathBuf,
     },
+
+    /// Replace the text of a local draft comment.
+    Edit {
+        /// Session slug from `tuicr review list` (local or PR), or path to a
+        /// session JSON file.
+        #[arg(long, value_name = "SESSION")]
+        session: String,
+
+        /// Repo selector used to resolve a local session slug (path or
+        /// `owner/repo`). PR slugs and JSON paths resolve without it.
+        #[arg(long, value_name = "PATH|OWNER/REPO", default_value = ".")]
+        repo: PathBuf,
+
+        /// New comment classification. Keeps the current type when omitted.
+        #[arg(long = "type", value_name = "TYPE", value_parser = non_empty_comment_type)]
+        comment_type: Option<String>,
+
+        /// Comment id from `tuicr review comments`.
+        #[arg(value_name = "ID")]
+        id: String,
+
+        /// New comment text.
+        #[arg(
+            value_name = "COMMENT",
+            value_parser = non_empty_comment_text,
+            allow_hyphen_values = true
+        )]
+        content: String,
+    },
+
+    /// Delete local draft comments.
+    Delete {
+        /// Session slug from `tuicr review list` (local or PR), or path to a
+        /// session JSON file.
+        #[arg(long, value_name = "SESSION")]
+        session: String,
+
+        /// Repo selector used to resolve a local session slug (path or
+        /// `owner/repo`). PR slugs and JSON paths resolve without it.
+        #[arg(long, value_name = "PATH|OWNER/REPO", default_value = ".")]
+        repo: PathBuf,
+
+        /// Comment ids from `tuicr review comments`.
+        #[arg(value_name = "ID", required = true)]
+        ids: Vec<String>,
+    },
+
+    /// Mark comments as addressed. The TUI shows them muted.
+    Resolve {
+        /// Session slug from `tuicr review list` (local or PR), or path to a
+        /// session JSON file.
+        #[arg(long, value_name = "SESSION")]
+        session: String,
+
+        /// Repo selector used to resolve a local session slug (path or
+        /// `owner/repo`). PR slugs and JSON paths resolve without it.
+        #[arg(long, value_name = "PATH|OWNER/REPO", default_value = ".")]
+        repo: PathBuf,
+
+        /// Comment ids from `tuicr review comments`.
+        #[arg(value_name = "ID", required = true)]
+        ids: Vec<String>,
+    },
+
+    /// Clear the addressed mark set by `resolve`.
+    Reopen {
+        /// Session slug from `tuicr review list` (local or PR), or path to a
+        /// session JSON file.
+        #[arg(long, value_name = "SESSION")]
+        session: String,
+
+        /// Repo selector used to resolve a local session slug (path or
+        /// `owner/repo`). PR slugs and JSON paths resolve without it.
+        #[arg(long, value_name = "PATH|OWNER/REPO", default_value = ".")]
+        repo: PathBuf,
+
+        /// Comment ids from `tuicr review comments`.
+        #[arg(value_name = "ID", required = true)]
+        ids: Vec<String>,
+    },
+
+    /// Print a persisted session as a JSON, SARIF or Markdown export.
+    Export {
+        /// Session slug from `tuicr review list` (local or PR), or path to a
+        /// session JSON file.
+        #[arg(long, value_name = "SESSION")]
+        session: String,
+
+        /// Repo selector used to resolve a local session slug (path or
+        /// `owner/repo`). PR slugs and JSON paths resolve without it.
+        #[arg(long, value_name = "PATH|OWNER/REPO", default_value = ".")]
+        repo: PathBuf,
+
+        /// Export format.
+        #[arg(long, value_name = "FORMAT", value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+    },
+
+    /// Stream changes to a persisted session as newline-delimited JSON.
+    /// Exits with status 3 when the TUI showing the session closes.
+    Watch {
+        /// Session slug from `tuicr review list` (local or PR), or path to a
+        /// session JSON file.
+        #[arg(long, value_name = "SESSION")]
+        session: String,
+
+        /// Repo selector used to resolve a local session slug (path or
+        /// `owner/repo`). PR slugs and JSON paths resolve without it.
+        #[arg(long, value_name = "PATH|OWNER/REPO", default_value = ".")]
+        repo: PathBuf,
+
+        /// How often to check the session file, in milliseconds.
+        #[arg(long = "interval-ms", value_name = "MS", default_value_t = 500, value_parser = clap::value_parser!(u64).range(1..))]
+        interval_ms: u64,
+    },
 }
 
 /// Diff side accepted by `tuicr review add --side`.
@@ -275,6 +423,7 @@ pub enum LineSideArg {
 
 impl From<Cli> for CliArgs {
     fn from(cli: Cli) -> Self {
+        let mcp_command = matches!(cli.command, Some(Subcmd::Mcp));
         let (options, pr_target, review_command, update_version, update_command) = match cli.command
         {
             Some(Subcmd::Tui(command)) => match command.command {
@@ -304,23 +453,29 @@ impl From<Cli> for CliArgs {
                 (TuiOptions::default(), None, Some(command), None, false)
             }
             Some(Subcmd::Update { version }) => (TuiOptions::default(), None, None, version, true),
+            Some(Subcmd::Mcp) => (TuiOptions::default(), None, None, None, false),
             None => (cli.tui_options, None, None, None, false),
         };
+
         Self {
             theme: options.theme,
             appearance: options.appearance,
             output_to_stdout: options.stdout,
+            export_format: options.format,
             no_update_check: options.no_update_check,
             revisions: options.revisions,
             working_tree: options.working_tree,
             path_filter: options.path_filter,
             file_path: options.file_path,
             all_files: options.all_files,
+            patch: options.patch,
+            diagnostics: options.diagnostics,
             pr_target,
             repo_url: options.repo_url,
             review_command,
             update_command,
             update_version,
+            mcp_command,
         }
     }
 }
@@ -330,12 +485,15 @@ impl TuiOptions {
         self.theme.is_some()
             || self.appearance.is_some()
             || self.stdout
+            || self.format.is_some()
             || self.no_update_check
             || self.revisions.is_some()
             || self.working_tree
             || self.path_filter.is_some()
             || self.file_path.is_some()
             || self.all_files
+            || self.patch.is_some()
+            || self.diagnostics.is_some()
             || self.repo_url.is_some()
     }
 
@@ -344,12 +502,15 @@ impl TuiOptions {
             theme: later.theme.or(self.theme),
             appearance: later.appearance.or(self.appearance),
             stdout: self.stdout || later.stdout,
+            format: later.format.or(self.format),
             no_update_check: self.no_update_check || later.no_update_check,
             revisions: later.revisions.or(self.revisions),
             working_tree: self.working_tree || later.working_tree,
             path_filter: later.path_filter.or(self.path_filter),
             file_path: later.file_path.or(self.file_path),
             all_files: self.all_files || later.all_files,
+            patch: later.patch.or(self.patch),
+            diagnostics: later.diagnostics.or(self.diagnostics),
             repo_url: later.repo_url.or(self.repo_url),
         }
     }
@@ -375,6 +536,7 @@ impl Cli {
         match self.command {
             Some(Subcmd::Review { .. }) => Some("review"),
             Some(Subcmd::Update { .. }) => Some("update"),
+            Some(Subcmd::Mcp) => Some("mcp"),
             _ => None,
         }
     }
@@ -397,17 +559,20 @@ fn non_empty_theme_name(s: &str) -> Result<String, String> {
 }
 
 /// Reject `--repo-url` values that don't parse as a supported forge remote URL
-/// (GitHub, GitLab, Bitbucket, or Azure DevOps) so the failure is surfaced at
-/// startup rather than when the PR tab is opened.
+/// (GitHub, GitLab, Bitbucket Cloud or Data Center, Azure DevOps, Gitea /
+/// Forgejo, or Gerrit) so the failure is surfaced at startup rather than when
+/// the PR tab is opened.
 fn parse_repo_url(s: &str) -> Result<String, String> {
     if crate::forge::parse_any_remote_url(s).is_some() {
         Ok(s.to_string())
     } else {
         Err(format!(
-            "--repo-url value '{s}' is not a recognized GitHub, GitLab, Bitbucket, or Azure \
-             DevOps URL. Expected forms like: https://github.com/owner/repo, \
-             git@gitlab.com:owner/repo, https://bitbucket.org/workspace/repo, or \
-             https://dev.azure.com/org/project/_git/repo"
+            "--repo-url value '{s}' is not a recognized GitHub, GitLab, Bitbucket, Azure \
+             DevOps, Gitea, or Gerrit URL. Expected forms like: https://github.com/owner/repo, \
+             git@gitlab.com:owner/repo, https://bitbucket.org/workspace/repo, \
+             https://bitbucket.example.com/scm/project/repo.git, \
+             https://dev.azure.com/org/project/_git/repo, https://codeberg.org/owner/repo, \
+             or ssh://gerrit.example.com:29418/project"
         ))
     }
 }
@@ -677,12 +842,53 @@ mod tests {
         assert!(parsed.all_files);
     }
 
+    #[test]
+    fn should_parse_patch_flag_including_stdin() {
+        let parsed =
+            parse_for_test(&["tuicr", "--patch", "fix.patch"]).expect("parse should succeed");
+        assert_eq!(parsed.patch.as_deref(), Some("fix.patch"));
+
+        let parsed = parse_for_test(&["tuicr", "--patch", "-"]).expect("parse should succeed");
+        assert_eq!(parsed.patch.as_deref(), Some("-"));
+    }
+
+    #[test]
+    fn should_parse_diagnostics_flag_alongside_a_target() {
+        let parsed = parse_for_test(&["tuicr", "-w", "--diagnostics", "clippy.json"])
+            .expect("parse should succeed");
+        assert!(parsed.working_tree);
+        assert_eq!(parsed.diagnostics.as_deref(), Some("clippy.json"));
+    }
+
+    #[test]
+    fn should_reject_patch_combined_with_other_targets() {
+        for args in [
+            &["tuicr", "--patch", "-", "-r", "HEAD~1.."][..],
+            &["tuicr", "--patch", "-", "-w"][..],
+            &["tuicr", "--patch", "-", "--file", "f.md"][..],
+            &["tuicr", "--patch", "-", "-A"][..],
+        ] {
+            let err = parse_for_test(args).expect_err("parse should fail");
+            assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+        }
+    }
+
     #[test]
     fn should_parse_stdout_flag() {
         let parsed = parse_for_test(&["tuicr", "--stdout"]).expect("parse should succeed");
         assert!(parsed.output_to_stdout);
     }
 
+    #[test]
+    fn should_parse_export_format_flag() {
+        let parsed = parse_for_test(&["tuicr", "--stdout", "--format", "sarif"])
+            .expect("parse should succeed");
+        assert_eq!(parsed.export_format, Some(ExportFormat::Sarif));
+
+        let parsed = parse_for_test(&["tuicr"]).expect("parse should succeed");
+        assert_eq!(parsed.export_format, None);
+    }
+
     #[test]
     fn should_parse_no_update_check_flag() {
         let parsed = parse_for_test(&["tuicr", "--no-update-check"]).expect("parse should succeed");
@@ -720,6 +926,17 @@ mod tests {
         assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
     }
 
+    #[test]
+    fn should_parse_mcp_command() {
+        let parsed = parse_for_test(&["tuicr", "mcp"]).expect("parse should succeed");
+        assert!(parsed.mcp_command);
+        assert!(!parsed.update_command);
+        assert_eq!(parsed.review_command, None);
+
+        let err = parse_for_test(&["tuicr", "--stdout", "mcp"]).expect_err("parse should fail");
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+    }
+
     #[test]
     fn should_parse_pr_target_as_bare_number() {
         let parsed = parse_for_test(&["tuicr", "pr", "125"]).expect("parse should succeed");
@@ -855,8 +1072,9 @@ mod tests {
             parse_for_test(&["tuicr", "--repo-url", "not-a-url"]).expect_err("parse should fail");
         assert_eq!(err.kind(), ErrorKind::ValueValidation);
         assert!(
-            err.to_string()
-                .contains("not a recognized GitHub, GitLab, Bitbucket, or Azure"),
+            err.to_string().contains(
+                "not a recognized GitHub, GitLab, Bitbucket, Azure DevOps, Gitea, or Gerrit"
+            ),
             "unexpected error: {err}"
         );
     }
@@ -871,6 +1089,9 @@ mod tests {
             "https://bitbucket.org/example-workspace/repo.git",
             "git@bitbucket.org:example-workspace/repo.git",
             "https://dev.azure.com/org/project/_git/repo",
+            "https://codeberg.org/forgejo/forgejo.git",
+            "ssh://review.example.com:29418/platform/build",
+            "https://git.corp.example/scm/plat/build.git",
         ] {
             let parsed = parse_for_test(&["tuicr", "--repo-url", url])
                 .unwrap_or_else(|err| panic!("{url} should parse: {err}"));
@@ -1031,6 +1252,115 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_parse_review_export_command_defaulting_to_json() {
+        let parsed = parse_for_test(&["tuicr", "review", "export", "--session", "session.json"])
+            .expect("parse should succeed");
+        assert_eq!(
+            parsed.review_command,
+            Some(ReviewCommand::Export {
+                session: "session.json".to_string(),
+                repo: PathBuf::from("."),
+                format: ExportFormat::Json,
+            })
+        );
+
+        let parsed = parse_for_test(&[
+            "tuicr",
+            "review",
+            "export",
+            "--session",
+            "session.json",
+            "--format",
+            "markdown",
+        ])
+        .expect("parse should succeed");
+        assert!(matches!(
+            parsed.review_command,
+            Some(ReviewCommand::Export {
+                format: ExportFormat::Markdown,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn should_parse_review_edit_command() {
+        let parsed = parse_for_test(&[
+            "tuicr",
+            "review",
+            "edit",
+            "--session",
+            "session.json",
+            "--type",
+            "issue",
+            "abc-123",
+            "- reworded",
+        ])
+        .expect("parse should succeed");
+        assert_eq!(
+            parsed.review_command,
+            Some(ReviewCommand::Edit {
+                session: "session.json".to_string(),
+                repo: PathBuf::from("."),
+                comment_type: Some("issue".to_string()),
+                id: "abc-123".to_string(),
+                content: "- reworded".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn should_parse_review_resolve_with_several_ids() {
+        let parsed = parse_for_test(&[
+            "tuicr",
+            "review",
+            "resolve",
+            "--session",
+            "session.json",
+            "one",
+            "two",
+        ])
+        .expect("parse should succeed");
+        assert_eq!(
+            parsed.review_command,
+            Some(ReviewCommand::Resolve {
+                session: "session.json".to_string(),
+                repo: PathBuf::from("."),
+                ids: vec!["one".to_string(), "two".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn should_parse_review_watch_command() {
+        let parsed = parse_for_test(&[
+            "tuicr",
+            "review",
+            "watch",
+            "--session",
+            "agavra/tuicr@main/worktree",
+            "--interval-ms",
+            "250",
+        ])
+        .expect("parse should succeed");
+        assert_eq!(
+            parsed.review_command,
+            Some(ReviewCommand::Watch {
+                session: "agavra/tuicr@main/worktree".to_string(),
+                repo: PathBuf::from("."),
+                interval_ms: 250,
+            })
+        );
+    }
+
+    #[test]
+    fn should_require_an_id_for_review_delete() {
+        let err = parse_for_test(&["tuicr", "review", "delete", "--session", "session.json"])
+            .expect_err("parse should fail");
+        assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+    }
+
     #[test]
     fn should_require_file_for_review_add_line() {
         let err = parse_for_test(&[
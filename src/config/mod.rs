@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
@@ -6,6 +7,9 @@ use anyhow::{Result, anyhow};
 use serde::{Deserialize, Serialize};
 use toml::Value;
 
+use crate::input::keymap::{KeyBinding, KeyBindings, KeyChord, KeyMode, action_from_name};
+use crate::output::ExportFormat;
+
 pub const DEFAULT_LEADER_KEY: char = ';';
 
 #[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
@@ -24,16 +28,48 @@ pub struct ForgeConfig {
     /// reader can see the comment classification at a glance. Defaults to
     /// `true`; set to `false` to send the raw comment body.
     pub comment_type_prefix: bool,
+    /// How the GitHub, GitLab and Bitbucket Cloud backends reach the forge:
+    /// through `gh` / `glab` / `bkt`, or directly over HTTPS.
+    pub transport: ForgeTransport,
+    /// Token for the HTTP transport, used when the forge's token environment
+    /// variable is unset. It names no host, so it is only sent with `api_url`.
+    pub token: Option<String>,
+    /// `[forge.tokens]`: HTTP transport tokens keyed by the forge host they
+    /// may be sent to (e.g. `"github.com"`).
+    pub tokens: BTreeMap<String, String>,
+    /// API base URL for the HTTP transport, replacing the one derived from
+    /// the repository host (e.g. a proxy, or a local stub in tests).
+    pub api_url: Option<String>,
+    /// Mirror the local reviewed mark onto the forge's per-file "Viewed"
+    /// checkbox in PR mode: seed it on open and push each toggle. Defaults
+    /// to `true`.
+    pub sync_viewed: bool,
 }
 
 impl Default for ForgeConfig {
     fn default() -> Self {
         Self {
             comment_type_prefix: true,
+            transport: ForgeTransport::Cli,
+            token: None,
+            tokens: BTreeMap::new(),
+            api_url: None,
+            sync_viewed: true,
         }
     }
 }
 
+/// `[forge] transport` values.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ForgeTransport {
+    /// Shell out to `gh`, `glab` or `bkt`.
+    #[default]
+    Cli,
+    /// Call the REST/GraphQL APIs directly with a token.
+    Http,
+}
+
 const DEFAULT_EXPORT_INTRO: &str =
     "I reviewed your code and have the following comments. Please address them.";
 const DEFAULT_EXPORT_COMMENTS_HEADER: &str = "## Local tuicr Comments";
@@ -62,6 +98,38 @@ pub struct ExportConfig {
     pub remote_comments_header: Option<String>,
     /// Whether to emit the `Comment types:` legend.
     pub legend: Option<bool>,
+    /// Document format. The Markdown-only keys above are ignored for JSON
+    /// and SARIF.
+    pub format: Option<ExportFormat>,
+    /// `[export.targets]`: named shell commands `:send <name>` pipes the
+    /// export into.
+    pub targets: BTreeMap<String, ExportTarget>,
+}
+
+/// One `[export.targets]` entry. Written either as a bare command string or
+/// as a table with the options below.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ExportTarget {
+    /// Run with `sh -c` from the repository root, with the export on stdin.
+    pub command: String,
+    /// Overrides `export.format` for this target.
+    pub format: Option<ExportFormat>,
+    /// Send only comments this target has not received yet, and mark them
+    /// sent to it once the command succeeds.
+    pub only_new: bool,
+    /// Seconds before a still-running command is killed. Defaults to
+    /// [`DEFAULT_SEND_TIMEOUT_SECS`].
+    pub timeout_secs: Option<u64>,
+}
+
+/// How long `:send` waits for a target command that sets no `timeout_secs`.
+pub const DEFAULT_SEND_TIMEOUT_SECS: u64 = 600;
+
+impl ExportTarget {
+    pub fn timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.timeout_secs.unwrap_or(DEFAULT_SEND_TIMEOUT_SECS))
+    }
 }
 
 impl ExportConfig {
@@ -92,6 +160,10 @@ impl ExportConfig {
     pub fn legend(&self) -> bool {
         self.legend.unwrap_or(true)
     }
+
+    pub fn format(&self) -> ExportFormat {
+        self.format.unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
@@ -146,6 +218,12 @@ pub struct AppConfig {
     /// Disabled by default, and `0` disables it too. Ignored for
     /// pull-request reviews and `--all-files` mode.
     pub diff_watch_interval_ms: Option<usize>,
+    /// Listen on a per-session control socket that external tools use to
+    /// drive the TUI. Defaults to true on Unix and false elsewhere.
+    pub control_socket: Option<bool>,
+    /// Shell command whose output bare `:diagnostics` loads, e.g.
+    /// `cargo clippy --message-format=json`. Run from the repository root.
+    pub diagnostics_command: Option<String>,
     pub no_update_check: Option<bool>,
     /// Render single-file and pristine views in full-width mode by default.
     /// Pristine `--all-files` mode already defaults to true regardless of
@@ -161,6 +239,10 @@ pub struct AppConfig {
     /// `[export]` section settings. `None` means "no override"; downstream
     /// code should treat it as `ExportConfig::default()`.
     pub export: Option<ExportConfig>,
+    /// `[keys]` bindings. `None` when the section is absent or binds nothing
+    /// valid.
+    #[serde(skip)]
+    pub keys: Option<KeyBindings>,
 }
 
 impl AppConfig {
@@ -209,14 +291,24 @@ const KNOWN_KEYS: &[&str] = &[
     "scroll_offset",
     "review_watch_interval_ms",
     "diff_watch_interval_ms",
+    "control_socket",
+    "diagnostics_command",
     "no_update_check",
     "single_file_view",
     "username",
     "forge",
     "export",
+    "keys",
 ];
 
-const FORGE_KNOWN_KEYS: &[&str] = &["comment_type_prefix"];
+const FORGE_KNOWN_KEYS: &[&str] = &[
+    "comment_type_prefix",
+    "transport",
+    "token",
+    "tokens",
+    "api_url",
+    "sync_viewed",
+];
 
 const EXPORT_KNOWN_KEYS: &[&str] = &[
     "intro",
@@ -225,8 +317,12 @@ const EXPORT_KNOWN_KEYS: &[&str] = &[
     "comments_header",
     "remote_comments_header",
     "legend",
+    "format",
+    "targets",
 ];
 
+const EXPORT_TARGET_KNOWN_KEYS: &[&str] = &["command", "format", "only_new", "timeout_secs"];
+
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct ConfigLoadOutcome {
     pub config: Option<AppConfig>,
@@ -449,6 +545,8 @@ fn load_config_from_path(path: &Path) -> Result<ConfigLoadOutcome> {
         scroll_offset: read_usize(table, "scroll_offset", &mut warnings),
         review_watch_interval_ms: read_usize(table, "review_watch_interval_ms", &mut warnings),
         diff_watch_interval_ms: read_usize(table, "diff_watch_interval_ms", &mut warnings),
+        control_socket: read_bool(table, "control_socket", &mut warnings),
+        diagnostics_command: read_string(table, "diagnostics_command", &mut warnings),
         no_update_check: read_bool(table, "no_update_check", &mut warnings),
         single_file_view: read_bool(table, "single_file_view", &mut warnings),
         username: read_string(table, "username", &mut warnings),
@@ -458,6 +556,7 @@ fn load_config_from_path(path: &Path) -> Result<ConfigLoadOutcome> {
         export: table
             .get("export")
             .and_then(|v| parse_export(v, &mut warnings)),
+        keys: table.get("keys").and_then(|v| parse_keys(v, &mut warnings)),
     };
 
     for key in table.keys() {
@@ -498,9 +597,128 @@ fn parse_forge(value: &Value, warnings: &mut Vec<String>) -> Option<ForgeConfig>
         any_override = true;
     }
 
+    if let Some(v) = read_section_bool(table, "forge", "sync_viewed", warnings) {
+        cfg.sync_viewed = v;
+        any_override = true;
+    }
+
+    if let Some(v) = read_section_string(table, "forge", "transport", warnings) {
+        match v.as_str() {
+            "cli" => cfg.transport = ForgeTransport::Cli,
+            "http" => cfg.transport = ForgeTransport::Http,
+            other => warnings.push(format!(
+                "Warning: Config key 'forge.transport' must be \"cli\" or \"http\", got \"{other}\"; ignoring value"
+            )),
+        }
+        any_override |= cfg.transport != defaults.transport;
+    }
+
+    for (key, slot) in [("token", &mut cfg.token), ("api_url", &mut cfg.api_url)] {
+        if let Some(v) = read_section_string(table, "forge", key, warnings)
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+        {
+            *slot = Some(v);
+            any_override = true;
+        }
+    }
+
+    if let Some(value) = table.get("tokens") {
+        if let Some(tokens) = value.as_table() {
+            for (host, token) in tokens {
+                match token.as_str().map(str::trim).filter(|t| !t.is_empty()) {
+                    Some(token) => {
+                        cfg.tokens
+                            .insert(host.trim().to_ascii_lowercase(), token.to_string());
+                        any_override = true;
+                    }
+                    None => warnings.push(format!(
+                        "Warning: Config key 'forge.tokens.{host}' must be a non-empty string; ignoring value"
+                    )),
+                }
+            }
+        } else {
+            warnings.push(
+                "Warning: Config key 'forge.tokens' must be a table; ignoring value".to_string(),
+            );
+        }
+    }
+
+    if cfg.token.is_some() && cfg.api_url.is_none() {
+        warnings.push(
+            "Warning: Config key 'forge.token' is only sent to 'forge.api_url'; use [forge.tokens] to scope it to a host"
+                .to_string(),
+        );
+    }
+
     if any_override { Some(cfg) } else { None }
 }
 
+/// Parse the `[keys]` section: one table per mode mapping chords to action
+/// names. Invalid entries are skipped with a warning so the rest still apply.
+fn parse_keys(value: &Value, warnings: &mut Vec<String>) -> Option<KeyBindings> {
+    let Some(table) = value.as_table() else {
+        warnings.push("Warning: Config key 'keys' must be a table; ignoring value".to_string());
+        return None;
+    };
+
+    let mut bindings = Vec::new();
+    for (mode_name, mode_value) in table {
+        let Some(mode) = KeyMode::from_name(mode_name) else {
+            warnings.push(format!(
+                "Warning: Unknown config key 'keys.{mode_name}', ignoring"
+            ));
+            continue;
+        };
+        let Some(mode_table) = mode_value.as_table() else {
+            warnings.push(format!(
+                "Warning: Config key 'keys.{mode_name}' must be a table; ignoring value"
+            ));
+            continue;
+        };
+        for (raw_chord, action_value) in mode_table {
+            let key = format!("keys.{mode_name}.{raw_chord}");
+            let chord: KeyChord = match raw_chord.parse() {
+                Ok(chord) => chord,
+                Err(err) => {
+                    warnings.push(format!("Warning: Config key '{key}': {err}; ignoring"));
+                    continue;
+                }
+            };
+            if chord.prefix.is_some() && mode != KeyMode::Normal {
+                warnings.push(format!(
+                    "Warning: Config key '{key}': chords are only supported in normal mode; ignoring"
+                ));
+                continue;
+            }
+            let Some(action_name) = action_value.as_str() else {
+                warnings.push(format!(
+                    "Warning: Config key '{key}' must be a string; ignoring value"
+                ));
+                continue;
+            };
+            let Some(action) = action_from_name(action_name) else {
+                warnings.push(format!(
+                    "Warning: Config key '{key}' names unknown action \"{action_name}\", ignoring"
+                ));
+                continue;
+            };
+            bindings.push(KeyBinding {
+                mode,
+                chord,
+                action_name: action_name.to_string(),
+                action,
+            });
+        }
+    }
+
+    if bindings.is_empty() {
+        None
+    } else {
+        Some(KeyBindings::new(bindings))
+    }
+}
+
 /// Parse the `[export]` section. Returns `Some` only when at least one
 /// recognized key is set, so an absent or empty section leaves every default —
 /// and the older top-level `export_legend` — untouched.
@@ -530,6 +748,11 @@ fn parse_export(value: &Value, warnings: &mut Vec<String>) -> Option<ExportConfi
             warnings,
         ),
         legend: read_section_bool(table, "export", "legend", warnings),
+        format: read_export_format(table, "export", warnings),
+        targets: table
+            .get("targets")
+            .map(|value| parse_export_targets(value, warnings))
+            .unwrap_or_default(),
     };
 
     if cfg == ExportConfig::default() {
@@ -539,6 +762,77 @@ fn parse_export(value: &Value, warnings: &mut Vec<String>) -> Option<ExportConfi
     }
 }
 
+fn read_export_format(
+    table: &toml::Table,
+    section: &str,
+    warnings: &mut Vec<String>,
+) -> Option<ExportFormat> {
+    let raw = read_section_string(table, section, "format", warnings)?;
+    let format = raw.parse().ok();
+    if format.is_none() {
+        warnings.push(format!(
+            "Warning: Config key '{section}.format' must be \"markdown\" or \"json\" or \"sarif\"; got \"{raw}\", ignoring"
+        ));
+    }
+    format
+}
+
+/// Parse `[export.targets]`. Entries without a usable command are dropped
+/// with a warning.
+fn parse_export_targets(
+    value: &Value,
+    warnings: &mut Vec<String>,
+) -> BTreeMap<String, ExportTarget> {
+    let Some(table) = value.as_table() else {
+        warnings.push(
+            "Warning: Config key 'export.targets' must be a table; ignoring value".to_string(),
+        );
+        return BTreeMap::new();
+    };
+
+    let mut targets = BTreeMap::new();
+    for (name, entry) in table {
+        let section = format!("export.targets.{name}");
+        let target = match entry {
+            Value::String(command) => ExportTarget {
+                command: command.clone(),
+                ..ExportTarget::default()
+            },
+            Value::Table(entry) => {
+                for key in entry.keys() {
+                    if !EXPORT_TARGET_KNOWN_KEYS.contains(&key.as_str()) {
+                        warnings.push(format!(
+                            "Warning: Unknown config key '{section}.{key}', ignoring"
+                        ));
+                    }
+                }
+                ExportTarget {
+                    command: read_section_string(entry, &section, "command", warnings)
+                        .unwrap_or_default(),
+                    format: read_export_format(entry, &section, warnings),
+                    only_new: read_section_bool(entry, &section, "only_new", warnings)
+                        .unwrap_or(false),
+                    timeout_secs: read_section_u64(entry, &section, "timeout_secs", warnings),
+                }
+            }
+            _ => {
+                warnings.push(format!(
+                    "Warning: Config key '{section}' must be a command string or a table; ignoring value"
+                ));
+                continue;
+            }
+        };
+        if target.command.trim().is_empty() {
+            warnings.push(format!(
+                "Warning: Config key '{section}' needs a non-empty command; ignoring target"
+            ));
+            continue;
+        }
+        targets.insert(name.clone(), target);
+    }
+    targets
+}
+
 /// Like `read_bool`, but emits a `<section>.<key>` qualified warning so the
 /// user can locate the misconfigured field.
 fn read_section_bool(
@@ -558,6 +852,25 @@ fn read_section_bool(
     }
 }
 
+/// Like `read_usize`, but emits a `<section>.<key>` qualified warning.
+fn read_section_u64(
+    table: &toml::Table,
+    section: &str,
+    key: &str,
+    warnings: &mut Vec<String>,
+) -> Option<u64> {
+    let val = table.get(key)?;
+    match val.as_integer().map(u64::try_from) {
+        Some(Ok(n)) => Some(n),
+        _ => {
+            warnings.push(format!(
+                "Warning: Config key '{section}.{key}' must be a non-negative integer; ignoring value"
+            ));
+            None
+        }
+    }
+}
+
 /// Like `read_string`, but emits a `<section>.<key>` qualified warning.
 fn read_section_string(
     table: &toml::Table,
@@ -1385,6 +1698,34 @@ mod tests {
         );
     }
 
+    // control_socket
+
+    #[test]
+    fn should_parse_control_socket_false() {
+        let outcome = parse_config("control_socket = false\n");
+        assert_eq!(
+            outcome.config.as_ref().and_then(|cfg| cfg.control_socket),
+            Some(false)
+        );
+        assert!(outcome.warnings.is_empty());
+    }
+
+    // diagnostics_command
+
+    #[test]
+    fn should_parse_diagnostics_command() {
+        let outcome =
+            parse_config("diagnostics_command = \"cargo clippy --message-format=json\"\n");
+        assert_eq!(
+            outcome
+                .config
+                .as_ref()
+                .and_then(|cfg| cfg.diagnostics_command.as_deref()),
+            Some("cargo clippy --message-format=json")
+        );
+        assert!(outcome.warnings.is_empty());
+    }
+
     // no_update_check
 
     #[test]
@@ -1624,10 +1965,112 @@ comment_type_prefix = "yes"
         );
     }
 
+    #[test]
+    fn should_parse_forge_http_transport_settings() {
+        let outcome = parse_config(
+            r#"[forge]
+transport = "http"
+token = "  secret  "
+api_url = "http://127.0.0.1:8080"
+"#,
+        );
+        let forge = outcome
+            .config
+            .as_ref()
+            .and_then(|cfg| cfg.forge.clone())
+            .expect("forge section should parse");
+        assert_eq!(forge.transport, ForgeTransport::Http);
+        assert_eq!(forge.token.as_deref(), Some("secret"));
+        assert_eq!(forge.api_url.as_deref(), Some("http://127.0.0.1:8080"));
+        assert!(forge.comment_type_prefix);
+        assert!(outcome.warnings.is_empty());
+    }
+
+    #[test]
+    fn should_parse_forge_tokens_keyed_by_host() {
+        let outcome = parse_config(
+            r#"[forge]
+transport = "http"
+
+[forge.tokens]
+"GitHub.com" = " gh-secret "
+"gitlab.example.com" = "gl-secret"
+"bitbucket.org" = 42
+"#,
+        );
+        let forge = outcome
+            .config
+            .as_ref()
+            .and_then(|cfg| cfg.forge.clone())
+            .expect("forge section should parse");
+        assert_eq!(
+            forge.tokens,
+            BTreeMap::from([
+                ("github.com".to_string(), "gh-secret".to_string()),
+                ("gitlab.example.com".to_string(), "gl-secret".to_string()),
+            ])
+        );
+        assert_eq!(
+            outcome.warnings,
+            vec![
+                "Warning: Config key 'forge.tokens.bitbucket.org' must be a non-empty string; ignoring value"
+                    .to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn should_warn_that_a_bare_forge_token_needs_an_api_url() {
+        let outcome = parse_config(
+            r#"[forge]
+token = "secret"
+"#,
+        );
+        assert_eq!(outcome.warnings.len(), 1);
+        assert!(outcome.warnings[0].contains("forge.token"));
+        assert!(outcome.warnings[0].contains("[forge.tokens]"));
+    }
+
+    #[test]
+    fn should_parse_forge_sync_viewed_opt_out() {
+        let outcome = parse_config(
+            r#"[forge]
+sync_viewed = false
+"#,
+        );
+        let forge = outcome
+            .config
+            .as_ref()
+            .and_then(|cfg| cfg.forge.clone())
+            .expect("forge section should parse");
+        assert!(!forge.sync_viewed);
+        assert!(ForgeConfig::default().sync_viewed);
+        assert!(outcome.warnings.is_empty());
+    }
+
+    #[test]
+    fn should_warn_and_ignore_unknown_forge_transport() {
+        let outcome = parse_config(
+            r#"[forge]
+transport = "carrier-pigeon"
+"#,
+        );
+        assert!(
+            outcome
+                .config
+                .as_ref()
+                .and_then(|cfg| cfg.forge.clone())
+                .is_none()
+        );
+        assert_eq!(outcome.warnings.len(), 1);
+        assert!(outcome.warnings[0].contains("forge.transport"));
+    }
+
     #[test]
     fn forge_defaults_enable_comment_type_prefix() {
         let cfg = ForgeConfig::default();
         assert!(cfg.comment_type_prefix);
+        assert_eq!(cfg.transport, ForgeTransport::Cli);
     }
 
     #[test]
@@ -1789,6 +2232,168 @@ intro = 42
         );
     }
 
+    #[test]
+    fn should_parse_export_format() {
+        let outcome = parse_config("[export]\nformat = \"sarif\"\n");
+        let export = outcome
+            .config
+            .as_ref()
+            .and_then(|cfg| cfg.export.clone())
+            .expect("export section should parse");
+        assert_eq!(export.format(), ExportFormat::Sarif);
+        assert_eq!(ExportConfig::default().format(), ExportFormat::Markdown);
+        assert!(outcome.warnings.is_empty());
+    }
+
+    #[test]
+    fn should_parse_export_targets_from_strings_and_tables() {
+        let outcome = parse_config(
+            r#"[export.targets]
+claude = "claude -p"
+codex = { command = "codex exec", format = "json", only_new = true, timeout_secs = 90 }
+"#,
+        );
+        let export = outcome
+            .config
+            .as_ref()
+            .and_then(|cfg| cfg.export.clone())
+            .expect("export section should parse");
+        assert_eq!(
+            export.targets.get("claude"),
+            Some(&ExportTarget {
+                command: "claude -p".to_string(),
+                format: None,
+                only_new: false,
+                timeout_secs: None,
+            })
+        );
+        assert_eq!(
+            export.targets.get("codex"),
+            Some(&ExportTarget {
+                command: "codex exec".to_string(),
+                format: Some(ExportFormat::Json),
+                only_new: true,
+                timeout_secs: Some(90),
+            })
+        );
+        assert!(outcome.warnings.is_empty());
+    }
+
+    #[test]
+    fn should_warn_and_drop_export_targets_without_a_command() {
+        let outcome = parse_config(
+            r#"[export.targets]
+empty = ""
+broken = { only_new = true }
+agent = "agent-cli"
+"#,
+        );
+        let export = outcome
+            .config
+            .as_ref()
+            .and_then(|cfg| cfg.export.clone())
+            .expect("export section should parse");
+        assert_eq!(export.targets.keys().collect::<Vec<_>>(), vec!["agent"]);
+        assert_eq!(
+            outcome.warnings,
+            vec![
+                "Warning: Config key 'export.targets.broken' needs a non-empty command; ignoring target".to_string(),
+                "Warning: Config key 'export.targets.empty' needs a non-empty command; ignoring target".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_warn_and_ignore_unknown_export_format() {
+        let outcome = parse_config("[export]\nformat = \"xml\"\n");
+        assert_eq!(
+            outcome.config.as_ref().and_then(|cfg| cfg.export.clone()),
+            None
+        );
+        assert_eq!(
+            outcome.warnings,
+            vec![
+                "Warning: Config key 'export.format' must be \"markdown\" or \"json\" or \"sarif\"; got \"xml\", ignoring"
+                    .to_string()
+            ]
+        );
+    }
+
+    // [keys]
+
+    #[test]
+    fn should_parse_key_bindings_per_mode() {
+        let outcome = parse_config(
+            r#"[keys.normal]
+"<C-n>" = "next_file"
+"<leader>t" = "toggle_file_list"
+
+[keys.visual]
+x = "add_range_comment"
+"#,
+        );
+        assert!(outcome.warnings.is_empty(), "{:?}", outcome.warnings);
+        let keys = outcome
+            .config
+            .and_then(|cfg| cfg.keys)
+            .expect("keys parsed");
+        let parsed: Vec<_> = keys
+            .bindings()
+            .iter()
+            .map(|b| {
+                (
+                    b.mode,
+                    b.chord.label(DEFAULT_LEADER_KEY),
+                    b.action_name.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            parsed,
+            vec![
+                (KeyMode::Normal, "<C-n>".to_string(), "next_file"),
+                (KeyMode::Normal, ";t".to_string(), "toggle_file_list"),
+                (KeyMode::Visual, "x".to_string(), "add_range_comment"),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_warn_and_skip_invalid_key_bindings() {
+        let outcome = parse_config(
+            r#"[keys]
+insert = {}
+
+[keys.normal]
+xy = "next_file"
+j = "fly"
+k = 3
+n = "search_next"
+
+[keys.visual]
+gt = "next_hunk"
+"#,
+        );
+        let keys = outcome
+            .config
+            .and_then(|cfg| cfg.keys)
+            .expect("valid binding kept");
+        assert_eq!(keys.bindings().len(), 1);
+        assert_eq!(keys.bindings()[0].action_name, "search_next");
+        assert_eq!(
+            outcome.warnings,
+            vec![
+                "Warning: Unknown config key 'keys.insert', ignoring".to_string(),
+                "Warning: Config key 'keys.normal.j' names unknown action \"fly\", ignoring"
+                    .to_string(),
+                "Warning: Config key 'keys.normal.k' must be a string; ignoring value".to_string(),
+                "Warning: Config key 'keys.normal.xy': 'xy' is not a key; ignoring".to_string(),
+                "Warning: Config key 'keys.visual.gt': chords are only supported in normal mode; ignoring"
+                    .to_string(),
+            ]
+        );
+    }
+
     #[test]
     fn should_warn_and_ignore_export_bool_with_invalid_type() {
         let outcome = parse_config(
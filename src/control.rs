@@ -0,0 +1,466 @@
+//! Live control socket for a running TUI.
+//!
+//! Each TUI listens on a Unix domain socket, recorded as `socket` in its
+//! `active_sessions.json` entry and in `tuicr review list`. Clients send
+//! newline-delimited JSON-RPC 2.0 requests:
+//!
+//! ```json
+//! {"jsonrpc":"2.0","id":1,"method":"goto","params":{"file":"src/lib.rs","line":42}}
+//! ```
+//!
+//! Connection threads only parse and frame; every request that touches
+//! review state is handed to the event loop through [`ControlHub::try_recv`]
+//! and answered from there, so the socket never races the keyboard.
+//! `subscribe` is the exception: it registers the connection for event
+//! notifications without waiting on the loop.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+use serde_json::{Value, json};
+
+// JSON-RPC error codes.
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+/// The request was valid but the TUI could not carry it out now.
+pub const REQUEST_FAILED: i64 = -32000;
+
+/// Events a client can subscribe to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ControlEvent {
+    /// A comment was added in the TUI or through the socket. Carries the
+    /// same fields as `tuicr review comments`.
+    CommentAdded(Value),
+    FileReviewed {
+        path: PathBuf,
+        reviewed: bool,
+    },
+    /// A review was submitted to the forge.
+    SubmitCompleted {
+        event: String,
+        url: String,
+    },
+}
+
+impl ControlEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ControlEvent::CommentAdded(_) => "comment_added",
+            ControlEvent::FileReviewed { .. } => "file_reviewed",
+            ControlEvent::SubmitCompleted { .. } => "submit_completed",
+        }
+    }
+
+    fn params(&self) -> Value {
+        let data = match self {
+            ControlEvent::CommentAdded(comment) => json!({ "comment": comment }),
+            ControlEvent::FileReviewed { path, reviewed } => {
+                json!({ "path": path.display().to_string(), "reviewed": reviewed })
+            }
+            ControlEvent::SubmitCompleted { event, url } => json!({ "event": event, "url": url }),
+        };
+        json!({ "event": self.name(), "data": data })
+    }
+}
+
+const EVENT_NAMES: &[&str] = &["comment_added", "file_reviewed", "submit_completed"];
+
+/// A request waiting for the event loop.
+#[derive(Debug)]
+pub struct ControlRequest {
+    pub method: String,
+    pub params: Value,
+    /// `None` for notifications, which get no reply.
+    id: Option<Value>,
+    reply: Sender<Value>,
+}
+
+impl ControlRequest {
+    pub fn respond(self, result: std::result::Result<Value, (i64, String)>) {
+        let Some(id) = self.id else {
+            return;
+        };
+        let reply = match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error_reply(id, code, message),
+        };
+        let _ = self.reply.send(reply);
+    }
+}
+
+struct Subscriber {
+    /// Event names this connection asked for.
+    events: Vec<String>,
+    out: Sender<Value>,
+}
+
+/// The event loop's end of the control socket: pending requests in, event
+/// notifications out.
+pub struct ControlHub {
+    requests_tx: Sender<ControlRequest>,
+    requests_rx: Receiver<ControlRequest>,
+    subscribers: Arc<Mutex<Vec<Subscriber>>>,
+    socket_path: Option<PathBuf>,
+}
+
+impl Default for ControlHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ControlHub {
+    /// A hub with no socket. Connections are attached with
+    /// [`ControlHub::connect`].
+    pub fn new() -> Self {
+        let (requests_tx, requests_rx) = mpsc::channel();
+        Self {
+            requests_tx,
+            requests_rx,
+            subscribers: Arc::new(Mutex::new(Vec::new())),
+            socket_path: None,
+        }
+    }
+
+    /// Listen on `path`, replacing a socket left behind by a crashed run.
+    #[cfg(unix)]
+    pub fn bind(path: &Path) -> std::io::Result<Self> {
+        use std::os::unix::fs::DirBuilderExt;
+        use std::os::unix::net::UnixListener;
+
+        if let Some(dir) = path.parent() {
+            // Only the owner may connect: the socket can add comments.
+            std::fs::DirBuilder::new()
+                .recursive(true)
+                .mode(0o700)
+                .create(dir)?;
+        }
+        match std::fs::remove_file(path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        let listener = UnixListener::bind(path)?;
+        let mut hub = Self::new();
+        hub.socket_path = Some(path.to_path_buf());
+        let requests_tx = hub.requests_tx.clone();
+        let subscribers = Arc::clone(&hub.subscribers);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                let Ok(writer) = stream.try_clone() else {
+                    continue;
+                };
+                let (out_tx, out_rx) = mpsc::channel();
+                spawn_writer(writer, out_rx);
+                let requests_tx = requests_tx.clone();
+                let subscribers = Arc::clone(&subscribers);
+                std::thread::spawn(move || {
+                    serve_connection(
+                        std::io::BufReader::new(stream),
+                        &out_tx,
+                        &requests_tx,
+                        &subscribers,
+                    );
+                });
+            }
+        });
+        Ok(hub)
+    }
+
+    pub fn socket_path(&self) -> Option<&Path> {
+        self.socket_path.as_deref()
+    }
+
+    /// The next request for the event loop, if one is queued.
+    pub fn try_recv(&self) -> Option<ControlRequest> {
+        self.requests_rx.try_recv().ok()
+    }
+
+    /// Notify every subscriber of `event`. Closed connections are dropped.
+    pub fn publish(&self, event: &ControlEvent) {
+        let Ok(mut subscribers) = self.subscribers.lock() else {
+            return;
+        };
+        let notification = json!({
+            "jsonrpc": "2.0",
+            "method": "event",
+            "params": event.params(),
+        });
+        subscribers.retain(|subscriber| {
+            if !subscriber.events.iter().any(|name| name == event.name()) {
+                return true;
+            }
+            subscriber.out.send(notification.clone()).is_ok()
+        });
+    }
+
+    /// Attach an in-process connection: lines sent on the returned sender
+    /// are served like socket lines, and replies and events arrive on the
+    /// receiver.
+    pub fn connect(&self) -> (Sender<String>, Receiver<Value>) {
+        let (line_tx, line_rx) = mpsc::channel::<String>();
+        let (out_tx, out_rx) = mpsc::channel();
+        let requests_tx = self.requests_tx.clone();
+        let subscribers = Arc::clone(&self.subscribers);
+        std::thread::spawn(move || {
+            for line in line_rx {
+                handle_line(&line, &out_tx, &requests_tx, &subscribers);
+            }
+        });
+        (line_tx, out_rx)
+    }
+}
+
+impl Drop for ControlHub {
+    fn drop(&mut self) {
+        if let Some(path) = &self.socket_path {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Where this process's socket lives: `sockets/<pid>.sock` beside the
+/// session store, which keeps it private to the user.
+pub fn default_socket_path() -> crate::error::Result<PathBuf> {
+    let reviews_dir = crate::persistence::storage::get_reviews_dir()?;
+    Ok(reviews_dir
+        .join("sockets")
+        .join(format!("{}.sock", std::process::id())))
+}
+
+#[cfg(unix)]
+fn spawn_writer(mut stream: std::os::unix::net::UnixStream, out: Receiver<Value>) {
+    use std::io::Write;
+
+    std::thread::spawn(move || {
+        for message in out {
+            let mut line = message.to_string();
+            line.push('\n');
+            if stream.write_all(line.as_bytes()).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+#[cfg(unix)]
+fn serve_connection(
+    reader: impl std::io::BufRead,
+    out: &Sender<Value>,
+    requests: &Sender<ControlRequest>,
+    subscribers: &Arc<Mutex<Vec<Subscriber>>>,
+) {
+    for line in reader.lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        handle_line(&line, out, requests, subscribers);
+    }
+}
+
+fn handle_line(
+    line: &str,
+    out: &Sender<Value>,
+    requests: &Sender<ControlRequest>,
+    subscribers: &Arc<Mutex<Vec<Subscriber>>>,
+) {
+    if line.trim().is_empty() {
+        return;
+    }
+    let message: Value = match serde_json::from_str(line) {
+        Ok(message) => message,
+        Err(e) => {
+            let _ = out.send(error_reply(
+                Value::Null,
+                PARSE_ERROR,
+                format!("invalid JSON: {e}"),
+            ));
+            return;
+        }
+    };
+    let id = message.get("id").cloned();
+    let Some(method) = message.get("method").and_then(Value::as_str) else {
+        if let Some(id) = id {
+            let _ = out.send(error_reply(
+                id,
+                INVALID_REQUEST,
+                "expected a JSON-RPC request".to_string(),
+            ));
+        }
+        return;
+    };
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+    let request = ControlRequest {
+        method: method.to_string(),
+        params,
+        id,
+        reply: out.clone(),
+    };
+    if request.method == "subscribe" {
+        subscribe(request, subscribers);
+        return;
+    }
+    let _ = requests.send(request);
+}
+
+/// `subscribe {"events": [...]}`; every event when `events` is omitted.
+fn subscribe(request: ControlRequest, subscribers: &Arc<Mutex<Vec<Subscriber>>>) {
+    let events: Vec<String> = match request.params.get("events") {
+        None | Some(Value::Null) => EVENT_NAMES.iter().map(|name| name.to_string()).collect(),
+        Some(Value::Array(names)) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        Some(_) => {
+            request.respond(Err((INVALID_PARAMS, "events must be an array".to_string())));
+            return;
+        }
+    };
+    if let Some(unknown) = events
+        .iter()
+        .find(|name| !EVENT_NAMES.contains(&name.as_str()))
+    {
+        let message = format!(
+            "unknown event {unknown}; expected one of {}",
+            EVENT_NAMES.join(", ")
+        );
+        request.respond(Err((INVALID_PARAMS, message)));
+        return;
+    }
+    if let Ok(mut subscribers) = subscribers.lock() {
+        subscribers.push(Subscriber {
+            events: events.clone(),
+            out: request.reply.clone(),
+        });
+    }
+    request.respond(Ok(json!({ "events": events })));
+}
+
+fn error_reply(id: Value, code: i64, message: String) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn recv(rx: &Receiver<Value>) -> Value {
+        rx.recv_timeout(Duration::from_secs(5)).unwrap()
+    }
+
+    #[test]
+    fn should_queue_requests_for_the_event_loop() {
+        // given
+        let hub = ControlHub::new();
+        let (tx, rx) = hub.connect();
+
+        // when
+        tx.send(r#"{"jsonrpc":"2.0","id":7,"method":"message","params":{"text":"hi"}}"#.into())
+            .unwrap();
+        let request = loop {
+            if let Some(request) = hub.try_recv() {
+                break request;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        };
+        assert_eq!(request.method, "message");
+        assert_eq!(request.params["text"], "hi");
+        request.respond(Ok(json!({ "ok": true })));
+
+        // then
+        let reply = recv(&rx);
+        assert_eq!(reply["id"], 7);
+        assert_eq!(reply["result"]["ok"], true);
+    }
+
+    #[test]
+    fn should_deliver_only_subscribed_events() {
+        // given
+        let hub = ControlHub::new();
+        let (tx, rx) = hub.connect();
+        tx.send(
+            r#"{"jsonrpc":"2.0","id":1,"method":"subscribe","params":{"events":["file_reviewed"]}}"#
+                .into(),
+        )
+        .unwrap();
+        assert_eq!(recv(&rx)["result"]["events"], json!(["file_reviewed"]));
+
+        // when
+        hub.publish(&ControlEvent::SubmitCompleted {
+            event: "approve".to_string(),
+            url: String::new(),
+        });
+        hub.publish(&ControlEvent::FileReviewed {
+            path: PathBuf::from("src/lib.rs"),
+            reviewed: true,
+        });
+
+        // then
+        let event = recv(&rx);
+        assert_eq!(event["method"], "event");
+        assert_eq!(event["params"]["event"], "file_reviewed");
+        assert_eq!(event["params"]["data"]["path"], "src/lib.rs");
+        assert!(rx.recv_timeout(Duration::from_millis(50)).is_err());
+    }
+
+    #[test]
+    fn should_reject_bad_json_and_unknown_events() {
+        let hub = ControlHub::new();
+        let (tx, rx) = hub.connect();
+
+        tx.send("{nope".into()).unwrap();
+        assert_eq!(recv(&rx)["error"]["code"], PARSE_ERROR);
+
+        tx.send(
+            r#"{"jsonrpc":"2.0","id":2,"method":"subscribe","params":{"events":["keypress"]}}"#
+                .into(),
+        )
+        .unwrap();
+        assert_eq!(recv(&rx)["error"]["code"], INVALID_PARAMS);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn should_serve_requests_over_the_socket_and_remove_it_on_drop() {
+        use std::io::{BufRead, BufReader, Write};
+        use std::os::unix::net::UnixStream;
+
+        // given
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("sockets").join("1.sock");
+        let hub = ControlHub::bind(&path).unwrap();
+        let mut stream = UnixStream::connect(&path).unwrap();
+
+        // when
+        stream
+            .write_all(b"{\"jsonrpc\":\"2.0\",\"id\":3,\"method\":\"state\"}\n")
+            .unwrap();
+        let request = loop {
+            if let Some(request) = hub.try_recv() {
+                break request;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        };
+        request.respond(Ok(json!({ "mode": "normal" })));
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line).unwrap();
+
+        // then
+        let reply: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(reply["result"]["mode"], "normal");
+        drop(hub);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,435 @@
+//! Linter and compiler diagnostics shown inline on changed lines.
+//!
+//! [`parse_diagnostics`] accepts three shapes and tells them apart by
+//! sniffing the input: cargo's `--message-format=json` stream (or rustc's
+//! `--error-format=json`), a SARIF log, and plain `file:line:col: message`
+//! lines as printed by gcc, `eslint -f unix`, `tsc --pretty false` and most
+//! other tools.
+
+use std::collections::HashSet;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+    /// Notes, help and informational results.
+    Note,
+}
+
+impl DiagnosticSeverity {
+    pub fn label(self) -> &'static str {
+        match self {
+            DiagnosticSeverity::Error => "error",
+            DiagnosticSeverity::Warning => "warning",
+            DiagnosticSeverity::Note => "note",
+        }
+    }
+
+    fn from_label(label: &str) -> Option<Self> {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "error" | "fatal error" | "fatal" | "error: internal compiler error" => {
+                Some(DiagnosticSeverity::Error)
+            }
+            "warning" | "warn" => Some(DiagnosticSeverity::Warning),
+            "note" | "help" | "info" | "information" | "hint" | "none" | "failure-note" => {
+                Some(DiagnosticSeverity::Note)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// One diagnostic, anchored to a line of the new side of a file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    /// Path relative to the repository root, as the diff shows it.
+    pub path: PathBuf,
+    pub line: u32,
+    pub column: Option<u32>,
+    pub severity: DiagnosticSeverity,
+    /// Lint or rule name, e.g. `clippy::needless_return` or `semi`.
+    pub code: Option<String>,
+    /// First line of the tool's message.
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// `warning[clippy::needless_return]`, or just `warning` without a code.
+    pub fn title(&self) -> String {
+        match &self.code {
+            Some(code) => format!("{}[{code}]", self.severity.label()),
+            None => self.severity.label().to_string(),
+        }
+    }
+}
+
+/// Parse tool output into diagnostics with repo-relative paths, in input
+/// order and without duplicates. Lines that are not diagnostics (build
+/// progress, summaries) are skipped.
+pub fn parse_diagnostics(input: &str, root: &Path) -> Vec<Diagnostic> {
+    let parsed = match serde_json::from_str::<Value>(input.trim()) {
+        Ok(value) if value.get("runs").is_some() => parse_sarif(&value),
+        _ => input.lines().filter_map(parse_line).collect(),
+    };
+
+    let mut seen = HashSet::new();
+    parsed
+        .into_iter()
+        .filter_map(|(raw_path, mut diagnostic)| {
+            diagnostic.path = relative_path(&raw_path, root)?;
+            Some(diagnostic)
+        })
+        .filter(|diagnostic| seen.insert(diagnostic.clone()))
+        .collect()
+}
+
+/// Index range of the diagnostics on `path:line` in a slice sorted by
+/// `(path, line)`.
+pub fn diagnostics_at(diagnostics: &[Diagnostic], path: &Path, line: u32) -> Range<usize> {
+    let start = diagnostics.partition_point(|d| (d.path.as_path(), d.line) < (path, line));
+    let end = diagnostics.partition_point(|d| (d.path.as_path(), d.line) <= (path, line));
+    start..end
+}
+
+type RawDiagnostic = (String, Diagnostic);
+
+fn parse_line(line: &str) -> Option<RawDiagnostic> {
+    let trimmed = line.trim();
+    if trimmed.starts_with('{') {
+        let value: Value = serde_json::from_str(trimmed).ok()?;
+        return parse_rustc_message(&value);
+    }
+    parse_plain_line(trimmed)
+}
+
+/// A cargo `compiler-message` record, or a bare rustc JSON diagnostic.
+fn parse_rustc_message(value: &Value) -> Option<RawDiagnostic> {
+    let message = match value.get("reason").and_then(Value::as_str) {
+        Some("compiler-message") => value.get("message")?,
+        Some(_) => return None,
+        None => value,
+    };
+    let span = message
+        .get("spans")?
+        .as_array()?
+        .iter()
+        .find(|span| span.get("is_primary").and_then(Value::as_bool) == Some(true))?;
+    let severity = DiagnosticSeverity::from_label(message.get("level")?.as_str()?)?;
+    Some((
+        span.get("file_name")?.as_str()?.to_string(),
+        Diagnostic {
+            path: PathBuf::new(),
+            line: u32::try_from(span.get("line_start")?.as_u64()?).ok()?,
+            column: span
+                .get("column_start")
+                .and_then(Value::as_u64)
+                .and_then(|c| u32::try_from(c).ok()),
+            severity,
+            code: message
+                .get("code")
+                .and_then(|code| code.get("code"))
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            message: first_line(message.get("message")?.as_str()?),
+        },
+    ))
+}
+
+fn parse_sarif(log: &Value) -> Vec<RawDiagnostic> {
+    let results = log
+        .get("runs")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|run| run.get("results").and_then(Value::as_array))
+        .flatten();
+    results.filter_map(parse_sarif_result).collect()
+}
+
+fn parse_sarif_result(result: &Value) -> Option<RawDiagnostic> {
+    let location = result
+        .get("locations")?
+        .as_array()?
+        .first()?
+        .get("physicalLocation")?;
+    let region = location.get("region")?;
+    let message = result.get("message")?;
+    let text = message
+        .get("text")
+        .or_else(|| message.get("markdown"))?
+        .as_str()?;
+    // SARIF's default level is `warning`.
+    let severity = match result.get("level").and_then(Value::as_str) {
+        Some(level) => DiagnosticSeverity::from_label(level)?,
+        None => DiagnosticSeverity::Warning,
+    };
+    Some((
+        location
+            .get("artifactLocation")?
+            .get("uri")?
+            .as_str()?
+            .to_string(),
+        Diagnostic {
+            path: PathBuf::new(),
+            line: u32::try_from(region.get("startLine")?.as_u64()?).ok()?,
+            column: region
+                .get("startColumn")
+                .and_then(Value::as_u64)
+                .and_then(|c| u32::try_from(c).ok()),
+            severity,
+            code: result
+                .get("ruleId")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            message: first_line(text),
+        },
+    ))
+}
+
+/// `path:line[:col]: [severity:] message`, with eslint's unix-format
+/// `[Severity/rule]` suffix picked apart when present.
+fn parse_plain_line(line: &str) -> Option<RawDiagnostic> {
+    let (path, rest) = split_location(line)?;
+    let (line_no, rest) = split_number(rest)?;
+    let (column, rest) = match split_number(rest) {
+        Some((column, rest)) => (Some(column), rest),
+        None => (None, rest),
+    };
+    let mut message = rest.trim();
+    let mut severity = None;
+    let mut code = None;
+    if let Some((label, tail)) = message.split_once(':')
+        && let Some(parsed) = DiagnosticSeverity::from_label(label)
+    {
+        severity = Some(parsed);
+        message = tail.trim();
+    }
+    if let Some(body) = message.strip_suffix(']')
+        && let Some((text, tag)) = body.rsplit_once(" [")
+        && let Some((label, rule)) = tag.split_once('/')
+        && let Some(parsed) = DiagnosticSeverity::from_label(label)
+    {
+        severity = severity.or(Some(parsed));
+        code = Some(rule.to_string());
+        message = text.trim();
+    }
+    if message.is_empty() {
+        return None;
+    }
+    Some((
+        path.to_string(),
+        Diagnostic {
+            path: PathBuf::new(),
+            line: line_no,
+            column,
+            severity: severity.unwrap_or(DiagnosticSeverity::Warning),
+            code,
+            message: message.to_string(),
+        },
+    ))
+}
+
+/// Split `path:<digits>:...` at the first colon followed by a line number.
+/// Paths may not contain whitespace, which keeps prose such as
+/// `error: could not compile foo:1:` from reading as a location.
+fn split_location(line: &str) -> Option<(&str, &str)> {
+    line.match_indices(':')
+        .map(|(idx, _)| (&line[..idx], &line[idx + 1..]))
+        .take_while(|(path, _)| !path.contains(char::is_whitespace))
+        .find(|(path, rest)| !path.is_empty() && split_number(rest).is_some())
+}
+
+fn split_number(text: &str) -> Option<(u32, &str)> {
+    let (digits, rest) = text.split_once(':')?;
+    let number = digits.parse::<u32>().ok().filter(|n| *n > 0)?;
+    Some((number, rest))
+}
+
+/// Map a tool's path onto the diff's repo-relative paths. Absolute paths
+/// outside the repository yield `None`.
+fn relative_path(raw: &str, root: &Path) -> Option<PathBuf> {
+    let raw = raw.strip_prefix("file://").unwrap_or(raw);
+    let path = Path::new(raw);
+    if path.is_absolute() {
+        let canonical_root = root.canonicalize().ok();
+        return [Some(root), canonical_root.as_deref()]
+            .into_iter()
+            .flatten()
+            .find_map(|root| path.strip_prefix(root).ok())
+            .map(Path::to_path_buf);
+    }
+    Some(path.strip_prefix("./").unwrap_or(path).to_path_buf())
+}
+
+fn first_line(text: &str) -> String {
+    text.lines().next().unwrap_or_default().trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root() -> PathBuf {
+        PathBuf::from("/work/repo")
+    }
+
+    #[test]
+    fn should_parse_cargo_json_messages_and_skip_other_records() {
+        // given
+        let input = concat!(
+            r#"{"reason":"compiler-artifact","package_id":"x"}"#,
+            "\n",
+            r#"{"reason":"compiler-message","message":{"message":"unneeded `return` statement","code":{"code":"clippy::needless_return"},"level":"warning","spans":[{"file_name":"src/lib.rs","line_start":12,"column_start":5,"is_primary":true}]}}"#,
+            "\n",
+            r#"{"reason":"compiler-message","message":{"message":"aborting due to 1 previous error","code":null,"level":"error","spans":[]}}"#,
+            "\n",
+            r#"{"reason":"build-finished","success":false}"#,
+        );
+
+        // when
+        let diagnostics = parse_diagnostics(input, &root());
+
+        // then
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic {
+                path: PathBuf::from("src/lib.rs"),
+                line: 12,
+                column: Some(5),
+                severity: DiagnosticSeverity::Warning,
+                code: Some("clippy::needless_return".to_string()),
+                message: "unneeded `return` statement".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn should_parse_sarif_results_relative_to_the_repo_root() {
+        // given
+        let input = r#"{
+          "version": "2.1.0",
+          "runs": [{
+            "tool": {"driver": {"name": "semgrep"}},
+            "results": [{
+              "ruleId": "no-eval",
+              "level": "error",
+              "message": {"text": "Avoid eval\nIt runs arbitrary code."},
+              "locations": [{"physicalLocation": {
+                "artifactLocation": {"uri": "file:///work/repo/web/app.js"},
+                "region": {"startLine": 3, "startColumn": 1}
+              }}]
+            }, {
+              "message": {"text": "Outside the repo"},
+              "locations": [{"physicalLocation": {
+                "artifactLocation": {"uri": "/usr/lib/node/x.js"},
+                "region": {"startLine": 1}
+              }}]
+            }]
+          }]
+        }"#;
+
+        // when
+        let diagnostics = parse_diagnostics(input, &root());
+
+        // then
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic {
+                path: PathBuf::from("web/app.js"),
+                line: 3,
+                column: Some(1),
+                severity: DiagnosticSeverity::Error,
+                code: Some("no-eval".to_string()),
+                message: "Avoid eval".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn should_parse_plain_file_line_col_output() {
+        // given
+        let input = "\
+./src/main.c:7:3: error: expected ';' before '}' token
+src/util.ts:20: unused import
+/work/repo/web/app.js:4:10: Missing semicolon. [Error/semi]
+error: could not compile `demo`:1: nope
+Compiling demo v0.1.0
+";
+
+        // when
+        let diagnostics = parse_diagnostics(input, &root());
+
+        // then
+        let summary: Vec<_> = diagnostics
+            .iter()
+            .map(|d| {
+                (
+                    d.path.to_string_lossy().into_owned(),
+                    d.line,
+                    d.column,
+                    d.title(),
+                    d.message.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "src/main.c".to_string(),
+                    7,
+                    Some(3),
+                    "error".to_string(),
+                    "expected ';' before '}' token"
+                ),
+                (
+                    "src/util.ts".to_string(),
+                    20,
+                    None,
+                    "warning".to_string(),
+                    "unused import"
+                ),
+                (
+                    "web/app.js".to_string(),
+                    4,
+                    Some(10),
+                    "error[semi]".to_string(),
+                    "Missing semicolon."
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_drop_duplicate_diagnostics() {
+        // given
+        let input = "src/a.rs:1:1: warning: dup\nsrc/a.rs:1:1: warning: dup\n";
+
+        // when
+        let diagnostics = parse_diagnostics(input, &root());
+
+        // then
+        assert_eq!(diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn should_find_the_diagnostics_on_a_line() {
+        // given
+        let mut diagnostics = parse_diagnostics(
+            "b.rs:2:1: one\na.rs:2:1: two\na.rs:2:5: three\na.rs:9:1: four\n",
+            &root(),
+        );
+        diagnostics.sort_by(|a, b| (&a.path, a.line).cmp(&(&b.path, b.line)));
+
+        // when
+        let range = diagnostics_at(&diagnostics, Path::new("a.rs"), 2);
+
+        // then
+        let messages: Vec<_> = diagnostics[range].iter().map(|d| &d.message).collect();
+        assert_eq!(messages, ["two", "three"]);
+    }
+}
@@ -37,6 +37,10 @@ pub enum TuicrError {
 
     #[error("Invalid input: {0}")]
     InvalidInput(String),
+
+    /// `tuicr review watch` saw the TUI showing the session exit.
+    #[error("Review session closed: {0}")]
+    SessionClosed(String),
 }
 
 pub type Result<T> = std::result::Result<T, TuicrError>;
@@ -27,7 +27,7 @@ use crate::forge::remote_comments::RemoteReviewThread;
 use crate::forge::submit::{GhSide, SubmitEvent};
 use crate::forge::traits::{
     CreateReviewRequest, ForgeBackend, ForgeFileLinesRequest, ForgeRepository,
-    GhCreateReviewResponse, PagedPullRequests, PullRequestCommit, PullRequestDetails,
+    GhCreateReviewResponse, MergeMethod, PagedPullRequests, PullRequestCommit, PullRequestDetails,
     PullRequestListQuery, PullRequestListScope, PullRequestTarget,
 };
 use crate::model::{DiffLine, FilePatch};
@@ -36,7 +36,7 @@ use crate::vcs::git::raw::run_git_diff;
 use crate::vcs::slice_context_lines;
 
 use super::models::{
-    AzConnectionData, AzGitCommitRef, AzList, AzPullRequest, AzThread, AzThreadResponse,
+    AzConnectionData, AzGitCommitRef, AzIdentity, AzList, AzPullRequest, AzThread, AzThreadResponse,
 };
 
 /// REST api-version pinned across all calls.
@@ -111,6 +111,12 @@ impl AzHttp for PatHttp {
                 .header("Authorization", self.auth_header.as_str())
                 .header("Content-Type", "application/json")
                 .send(body.unwrap_or("")),
+            "PATCH" => self
+                .agent
+                .patch(url)
+                .header("Authorization", self.auth_header.as_str())
+                .header("Content-Type", "application/json")
+                .send(body.unwrap_or("")),
             other => {
                 return Err(AzHttpError::Failed {
                     status: None,
@@ -192,7 +198,9 @@ impl From<CommandOutputError> for AzHttpError {
                  Azure CLI (`az`)."
                     .to_string(),
             ),
-            CommandOutputErrorKind::SpawnFailed | CommandOutputErrorKind::Unsuccessful => {
+            CommandOutputErrorKind::SpawnFailed
+            | CommandOutputErrorKind::Unsuccessful
+            | CommandOutputErrorKind::TimedOut => {
                 if looks_like_az_auth_failure(&error.stderr) {
                     Self::Auth(error.stderr)
                 } else {
@@ -434,6 +442,30 @@ impl AzureDevOpsBackend {
             .filter(|id| !id.is_empty()))
     }
 
+    /// Resolve a user name, email or display name to the identity id the
+    /// reviewer endpoints are keyed by.
+    fn identity_id(&self, repo: &ForgeRepository, user: &str) -> Result<String> {
+        let (org, _project) = azure_coords(repo)?;
+        // Identities live on the organization's vssps host in the cloud and
+        // on the server itself everywhere else.
+        let base = if repo.host == DEFAULT_AZURE_HOST {
+            format!("https://vssps.{}/{org}", repo.host)
+        } else {
+            format!("https://{}/{org}", repo.host)
+        };
+        let url = format!(
+            "{base}/_apis/identities?searchFilter=General&filterValue={}",
+            encode_query_value(user)
+        );
+        let identities: AzList<AzIdentity> = self.get_json(repo, url)?;
+        identities
+            .value
+            .into_iter()
+            .map(|identity| identity.id)
+            .find(|id| !id.is_empty())
+            .ok_or_else(|| TuicrError::Forge(format!("Azure DevOps user `{user}` was not found")))
+    }
+
     fn fetch_file_via_api(&self, request: &ForgeFileLinesRequest) -> Result<String> {
         let base = git_api_base(&request.repository);
         let mut path = request.path.to_string_lossy().replace('\\', "/");
@@ -678,6 +710,117 @@ impl ForgeBackend for AzureDevOpsBackend {
             state: state.to_string(),
         })
     }
+
+    fn reply_to_review_thread(
+        &self,
+        pr: &PullRequestDetails,
+        thread: &RemoteReviewThread,
+        body: &str,
+    ) -> Result<()> {
+        let base = git_api_base(&pr.repository);
+        let url = format!(
+            "{base}/pullRequests/{}/threads/{}/comments",
+            pr.number, thread.id
+        );
+        // Replies hang off the thread's root comment, matching the web UI.
+        let parent = thread
+            .comments
+            .first()
+            .and_then(|c| c.id.parse::<u64>().ok())
+            .unwrap_or(1);
+        let payload = json!({ "parentCommentId": parent, "content": body, "commentType": "text" });
+        self.send(
+            &pr.repository,
+            "POST",
+            url,
+            &serde_json::to_string(&payload)?,
+        )?;
+        Ok(())
+    }
+
+    fn set_review_thread_resolved(
+        &self,
+        pr: &PullRequestDetails,
+        thread: &RemoteReviewThread,
+        resolved: bool,
+    ) -> Result<()> {
+        let base = git_api_base(&pr.repository);
+        let url = format!("{base}/pullRequests/{}/threads/{}", pr.number, thread.id);
+        let status = if resolved { "fixed" } else { "active" };
+        let payload = json!({ "status": status });
+        self.send(
+            &pr.repository,
+            "PATCH",
+            url,
+            &serde_json::to_string(&payload)?,
+        )?;
+        Ok(())
+    }
+
+    fn merge_pull_request(&self, pr: &PullRequestDetails, method: MergeMethod) -> Result<()> {
+        let base = git_api_base(&pr.repository);
+        let url = format!("{base}/pullRequests/{}", pr.number);
+        let strategy = match method {
+            MergeMethod::Merge => "noFastForward",
+            MergeMethod::Squash => "squash",
+            MergeMethod::Rebase => "rebase",
+        };
+        // Completing requires the source commit; a moved head is rejected
+        // instead of merging commits nobody reviewed.
+        let payload = json!({
+            "status": "completed",
+            "lastMergeSourceCommit": { "commitId": pr.head_sha },
+            "completionOptions": { "mergeStrategy": strategy },
+        });
+        self.send(
+            &pr.repository,
+            "PATCH",
+            url,
+            &serde_json::to_string(&payload)?,
+        )?;
+        Ok(())
+    }
+
+    fn close_pull_request(&self, pr: &PullRequestDetails) -> Result<()> {
+        let base = git_api_base(&pr.repository);
+        let url = format!("{base}/pullRequests/{}", pr.number);
+        let payload = json!({ "status": "abandoned" });
+        self.send(
+            &pr.repository,
+            "PATCH",
+            url,
+            &serde_json::to_string(&payload)?,
+        )?;
+        Ok(())
+    }
+
+    fn request_reviewer(&self, pr: &PullRequestDetails, username: &str) -> Result<()> {
+        let reviewer_id = self.identity_id(&pr.repository, username)?;
+        let base = git_api_base(&pr.repository);
+        let url = format!("{base}/pullRequests/{}/reviewers/{reviewer_id}", pr.number);
+        // Vote 0 adds the reviewer without casting a vote for them.
+        let payload = json!({ "vote": 0 });
+        self.send(
+            &pr.repository,
+            "PUT",
+            url,
+            &serde_json::to_string(&payload)?,
+        )?;
+        Ok(())
+    }
+
+    fn add_label(&self, pr: &PullRequestDetails, label: &str) -> Result<()> {
+        let base = git_api_base(&pr.repository);
+        let url = format!("{base}/pullRequests/{}/labels", pr.number);
+        let payload = json!({ "name": label });
+        self.send(
+            &pr.repository,
+            "POST",
+            url,
+            &serde_json::to_string(&payload)?,
+        )?;
+        Ok(())
+    }
 }
 
 fn capture_thread_id(output: &str, first: &mut u64) {
@@ -1197,6 +1340,129 @@ mod tests {
         assert_eq!(body_json(put)["vote"], 10);
     }
 
+    fn thread_with_root(id: &str, root_comment_id: &str) -> RemoteReviewThread {
+        RemoteReviewThread {
+            id: id.to_string(),
+            path: "src/lib.rs".to_string(),
+            line: Some(10),
+            side: crate::forge::remote_comments::RemoteCommentSide::Right,
+            is_resolved: false,
+            is_outdated: false,
+            comments: vec![crate::forge::remote_comments::RemoteReviewComment {
+                id: root_comment_id.to_string(),
+                author: Some("alice".to_string()),
+                body: "why?".to_string(),
+                created_at: None,
+                in_reply_to: None,
+                url: String::new(),
+            }],
+        }
+    }
+
+    #[test]
+    fn reply_posts_comment_parented_to_thread_root() {
+        let shared = SharedHttp::new(vec![r#"{"id": 2}"#.to_string()]);
+        let backend =
+            AzureDevOpsBackend::with_transport(Some(azure_repo()), Box::new(shared.clone()));
+        backend
+            .reply_to_review_thread(&pr_details(), &thread_with_root("77", "3"), "done")
+            .unwrap();
+
+        let calls = shared.0.calls.lock().unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].0, "POST");
+        assert!(
+            calls[0]
+                .1
+                .contains("/pullRequests/42/threads/77/comments?api-version=")
+        );
+        let body = body_json(&calls[0]);
+        assert_eq!(body["parentCommentId"], 3);
+        assert_eq!(body["content"], "done");
+    }
+
+    #[test]
+    fn merge_completes_the_pull_request_with_the_chosen_strategy() {
+        let shared = SharedHttp::new(vec!["{}".to_string()]);
+        let backend =
+            AzureDevOpsBackend::with_transport(Some(azure_repo()), Box::new(shared.clone()));
+        backend
+            .merge_pull_request(&pr_details(), MergeMethod::Squash)
+            .unwrap();
+
+        let calls = shared.0.calls.lock().unwrap();
+        assert_eq!(calls[0].0, "PATCH");
+        assert!(calls[0].1.contains("/pullRequests/42?api-version="));
+        let body = body_json(&calls[0]);
+        assert_eq!(body["status"], "completed");
+        assert_eq!(body["lastMergeSourceCommit"]["commitId"], "head111");
+        assert_eq!(body["completionOptions"]["mergeStrategy"], "squash");
+    }
+
+    #[test]
+    fn request_reviewer_resolves_the_identity_first() {
+        let shared = SharedHttp::new(vec![
+            r#"{"count":1,"value":[{"id":"id-carol","displayName":"Carol"}]}"#.to_string(),
+            "{}".to_string(),
+        ]);
+        let backend =
+            AzureDevOpsBackend::with_transport(Some(azure_repo()), Box::new(shared.clone()));
+        backend
+            .request_reviewer(&pr_details(), "carol@example.com")
+            .unwrap();
+
+        let calls = shared.0.calls.lock().unwrap();
+        assert!(calls[0].1.starts_with(
+            "https://vssps.dev.azure.com/myorg/_apis/identities?searchFilter=General&filterValue=carol@example.com"
+        ));
+        assert_eq!(calls[1].0, "PUT");
+        assert!(
+            calls[1]
+                .1
+                .contains("/pullRequests/42/reviewers/id-carol?api-version=")
+        );
+        assert_eq!(body_json(&calls[1])["vote"], 0);
+    }
+
+    #[test]
+    fn close_abandons_and_label_posts_by_name() {
+        let shared = SharedHttp::new(vec!["{}".to_string(), "{}".to_string()]);
+        let backend =
+            AzureDevOpsBackend::with_transport(Some(azure_repo()), Box::new(shared.clone()));
+        backend.close_pull_request(&pr_details()).unwrap();
+        backend.add_label(&pr_details(), "infra").unwrap();
+
+        let calls = shared.0.calls.lock().unwrap();
+        assert_eq!(body_json(&calls[0])["status"], "abandoned");
+        assert_eq!(calls[1].0, "POST");
+        assert!(calls[1].1.contains("/pullRequests/42/labels?api-version="));
+        assert_eq!(body_json(&calls[1])["name"], "infra");
+    }
+
+    #[test]
+    fn resolve_and_reopen_patch_thread_status() {
+        let shared = SharedHttp::new(vec!["{}".to_string(), "{}".to_string()]);
+        let backend =
+            AzureDevOpsBackend::with_transport(Some(azure_repo()), Box::new(shared.clone()));
+        let thread = thread_with_root("77", "1");
+        backend
+            .set_review_thread_resolved(&pr_details(), &thread, true)
+            .unwrap();
+        backend
+            .set_review_thread_resolved(&pr_details(), &thread, false)
+            .unwrap();
+
+        let calls = shared.0.calls.lock().unwrap();
+        assert!(calls.iter().all(|c| c.0 == "PATCH"));
+        assert!(
+            calls[0]
+                .1
+                .contains("/pullRequests/42/threads/77?api-version=")
+        );
+        assert_eq!(body_json(&calls[0])["status"], "fixed");
+        assert_eq!(body_json(&calls[1])["status"], "active");
+    }
+
     #[test]
     fn backend_is_send_and_sync() {
         fn assert_send_sync<T: Send + Sync>() {}
@@ -36,7 +36,7 @@ use super::models::{
 };
 
 /// The only Bitbucket host this backend claims. Data Center instances live on
-/// arbitrary hostnames and speak REST 1.0, so they are deliberately excluded.
+/// arbitrary hostnames and speak REST 1.0; `bitbucket_dc` handles them.
 const BITBUCKET_CLOUD_HOST: &str = "bitbucket.org";
 
 /// Cloud's maximum page size for most collections.
@@ -76,12 +76,12 @@ impl From<CommandOutputError> for BktCommandError {
     fn from(error: CommandOutputError) -> Self {
         match error.kind {
             CommandOutputErrorKind::NotFound => Self::MissingBkt,
-            CommandOutputErrorKind::SpawnFailed | CommandOutputErrorKind::Unsuccessful => {
-                Self::Failed {
-                    status: error.status,
-                    stderr: error.stderr,
-                }
-            }
+            CommandOutputErrorKind::SpawnFailed
+            | CommandOutputErrorKind::Unsuccessful
+            | CommandOutputErrorKind::TimedOut => Self::Failed {
+                status: error.status,
+                stderr: error.stderr,
+            },
         }
     }
 }
@@ -139,11 +139,6 @@ impl BitbucketBktBackend<SystemBktRunner> {
             local_checkout: None,
         }
     }
-
-    pub fn with_local_checkout(mut self, checkout: Option<PathBuf>) -> Self {
-        self.local_checkout = checkout;
-        self
-    }
 }
 
 impl<R> BitbucketBktBackend<R>
@@ -158,6 +153,11 @@ where
         }
     }
 
+    pub fn with_local_checkout(mut self, checkout: Option<PathBuf>) -> Self {
+        self.local_checkout = checkout;
+        self
+    }
+
     fn resolve_repository(&self, target: &PullRequestTarget) -> Result<ForgeRepository> {
         target
             .repository
@@ -325,6 +325,42 @@ where
             .map(|comment| comment.id))
     }
 
+    /// Publish the viewer's pending (draft) comments on a pull request.
+    ///
+    /// Cloud only returns pending comments to their author, so every
+    /// `pending` comment in the list belongs to the viewer and no identity
+    /// lookup is needed. Each one is published by updating it with
+    /// `pending: false`; Cloud has no batch publish endpoint.
+    fn publish_pending_comments(&self, pr: &PullRequestDetails) -> Result<()> {
+        let pending: Vec<BbComment> = self
+            .list_comments(pr)?
+            .into_iter()
+            .filter(|comment| comment.pending && !comment.deleted)
+            .collect();
+        for comment in &pending {
+            let path = format!(
+                "{}/pullrequests/{}/comments/{}",
+                Self::repo_path(&pr.repository),
+                pr.number,
+                comment.id
+            );
+            let body = serde_json::json!({
+                "content": { "raw": comment.content.raw },
+                "pending": false,
+            });
+            let args = vec![
+                "api".to_string(),
+                path,
+                "--method".to_string(),
+                "PUT".to_string(),
+                "--input".to_string(),
+                serde_json::to_string(&body)?,
+            ];
+            self.runner.run(&args).map_err(map_create_comment_error)?;
+        }
+        Ok(())
+    }
+
     /// Build the `inline` anchor for a review comment.
     ///
     /// `to` is the head-side line, `from` the base-side line. Multi-line
@@ -550,61 +586,82 @@ where
         pr: &PullRequestDetails,
         request: CreateReviewRequest<'_>,
     ) -> Result<GhCreateReviewResponse> {
-        // Bitbucket has no pending-review primitive reachable through `bkt`,
-        // and Cloud's request-changes endpoint is not yet wired up here.
-        // Reject up front rather than silently downgrading to a plain comment.
-        match request.event {
-            SubmitEvent::Comment | SubmitEvent::Approve => {}
-            SubmitEvent::RequestChanges => {
-                return Err(TuicrError::UnsupportedOperation(
-                    "Requesting changes is not supported for Bitbucket yet. \
-                     Use `:submit` to post comments, then request changes in Bitbucket."
-                        .to_string(),
-                ));
-            }
-            SubmitEvent::Draft => {
-                return Err(TuicrError::UnsupportedOperation(
-                    "Draft (pending) reviews are not supported for Bitbucket yet. \
-                     Use `:submit` to publish comments directly."
-                        .to_string(),
-                ));
-            }
-        }
-
+        // Bitbucket has no review object. A draft is a set of pending
+        // comments only the author can see; every other event posts live
+        // comments and then sets the viewer's participant state.
+        let pending = request.event == SubmitEvent::Draft;
         let mut first_comment_id: Option<u64> = None;
 
         // The review body becomes a general (non-inline) comment.
         if !request.body.is_empty() {
-            let body = serde_json::json!({ "content": { "raw": request.body } });
+            let mut body = serde_json::json!({ "content": { "raw": request.body } });
+            if pending {
+                body["pending"] = serde_json::Value::Bool(true);
+            }
             let id = self.post_comment(pr, &body)?;
             first_comment_id = first_comment_id.or(id);
         }
 
         for comment in request.comments {
-            let body = serde_json::json!({
+            let mut body = serde_json::json!({
                 "content": { "raw": comment.body },
                 "inline": Self::inline_anchor(comment),
             });
+            if pending {
+                body["pending"] = serde_json::Value::Bool(true);
+            }
             let id = self.post_comment(pr, &body)?;
             first_comment_id = first_comment_id.or(id);
         }
 
-        let approved = request.event == SubmitEvent::Approve;
-        if approved {
-            let mut args = vec![
-                "pr".to_string(),
-                "approve".to_string(),
-                pr.number.to_string(),
-            ];
-            args.extend(Self::repo_args(&pr.repository));
-            self.runner.run(&args).map_err(map_approve_error)?;
+        if !pending {
+            // Publish anything an earlier `:submit draft` left pending, so
+            // the vote below covers it — the equivalent of finishing a
+            // GitHub pending review.
+            self.publish_pending_comments(pr)?;
         }
 
+        let state = match request.event {
+            SubmitEvent::Approve => {
+                let mut args = vec![
+                    "pr".to_string(),
+                    "approve".to_string(),
+                    pr.number.to_string(),
+                ];
+                args.extend(Self::repo_args(&pr.repository));
+                self.runner
+                    .run(&args)
+                    .map_err(|e| map_review_state_error(e, "the approval"))?;
+                "APPROVED"
+            }
+            SubmitEvent::RequestChanges => {
+                // `bkt pr` has no request-changes subcommand; the REST
+                // endpoint takes no body.
+                let path = format!(
+                    "{}/pullrequests/{}/request-changes",
+                    Self::repo_path(&pr.repository),
+                    pr.number
+                );
+                let args = vec![
+                    "api".to_string(),
+                    path,
+                    "--method".to_string(),
+                    "POST".to_string(),
+                ];
+                self.runner
+                    .run(&args)
+                    .map_err(|e| map_review_state_error(e, "the change request"))?;
+                "CHANGES_REQUESTED"
+            }
+            SubmitEvent::Draft => "PENDING",
+            SubmitEvent::Comment => "COMMENTED",
+        };
+
         // Synthesized: Bitbucket returns no review object to report.
         Ok(GhCreateReviewResponse {
             id: first_comment_id.unwrap_or(0),
             html_url: pr.url.clone(),
-            state: if approved { "APPROVED" } else { "COMMENTED" }.to_string(),
+            state: state.to_string(),
         })
     }
 }
@@ -636,7 +693,7 @@ pub fn parse_pull_request_target_bitbucket(input: &str) -> Result<PullRequestTar
 ///
 /// Returns `None` for anything that is not `bitbucket.org`, including
 /// self-hosted Data Center instances: they expose an unrelated REST 1.0 API
-/// this backend cannot drive, so they must not be claimed here.
+/// this backend cannot drive, so they are left to `bitbucket_dc`.
 pub fn parse_bitbucket_remote_url(remote_url: &str) -> Option<ForgeRepository> {
     let trimmed = trim_url_suffix(remote_url.trim());
     if trimmed.is_empty() {
@@ -844,14 +901,15 @@ fn map_create_comment_error(error: BktCommandError) -> TuicrError {
     }
 }
 
-fn map_approve_error(error: BktCommandError) -> TuicrError {
+/// Map a failed approve / request-changes call. Comments are always posted
+/// first, so the message must make clear they survived.
+fn map_review_state_error(error: BktCommandError, action: &str) -> TuicrError {
     match &error {
         BktCommandError::Failed { stderr, .. } if looks_like_permission_failure(stderr) => {
-            TuicrError::Forge(
-                "Comments were posted, but Bitbucket rejected the approval: \
+            TuicrError::Forge(format!(
+                "Comments were posted, but Bitbucket rejected {action}: \
                  you may not be a reviewer on this pull request."
-                    .to_string(),
-            )
+            ))
         }
         _ => map_bkt_error(error),
     }
@@ -998,6 +1056,9 @@ mod tests {
         }
     }
 
+    /// An empty comment page: no pending comments to publish.
+    const NO_COMMENTS: &str = r#"{"values":[]}"#;
+
     // ---- command construction -------------------------------------------
 
     #[test]
@@ -1356,7 +1417,7 @@ mod tests {
     #[test]
     fn should_post_body_then_inline_comments_on_submit() {
         // given
-        let backend = backend(vec![r#"{"id":111}"#, r#"{"id":222}"#]);
+        let backend = backend(vec![r#"{"id":111}"#, r#"{"id":222}"#, NO_COMMENTS]);
         let comments = vec![inline_comment(42, GhSide::Right, None)];
         let request = CreateReviewRequest {
             event: SubmitEvent::Comment,
@@ -1366,9 +1427,10 @@ mod tests {
         };
         // when
         let response = backend.create_review(&details(), request).unwrap();
-        // then — general comment first, then the inline one
+        // then — general comment first, then the inline one, then the
+        // lookup for comments left pending by an earlier draft
         let calls = backend.runner.calls.borrow();
-        assert_eq!(calls.len(), 2);
+        assert_eq!(calls.len(), 3);
         assert_eq!(calls[0][2..4], ["--method".to_string(), "POST".to_string()]);
         let general: serde_json::Value = serde_json::from_str(&calls[0][5]).unwrap();
         assert_eq!(general["content"]["raw"], "overall looks fine");
@@ -1384,7 +1446,7 @@ mod tests {
     #[test]
     fn should_skip_the_general_comment_when_body_is_empty() {
         // given
-        let backend = backend(vec![r#"{"id":222}"#]);
+        let backend = backend(vec![r#"{"id":222}"#, NO_COMMENTS]);
         let comments = vec![inline_comment(1, GhSide::Right, None)];
         let request = CreateReviewRequest {
             event: SubmitEvent::Comment,
@@ -1395,13 +1457,15 @@ mod tests {
         // when
         backend.create_review(&details(), request).unwrap();
         // then — only the inline comment is posted
-        assert_eq!(backend.runner.calls.borrow().len(), 1);
+        let calls = backend.runner.calls.borrow();
+        assert_eq!(calls.len(), 2);
+        assert!(calls[1].iter().all(|arg| arg != "POST"));
     }
 
     #[test]
     fn should_anchor_left_side_comments_with_from() {
         // given — a comment on a deleted line
-        let backend = backend(vec![r#"{"id":1}"#]);
+        let backend = backend(vec![r#"{"id":1}"#, NO_COMMENTS]);
         let comments = vec![inline_comment(17, GhSide::Left, None)];
         let request = CreateReviewRequest {
             event: SubmitEvent::Comment,
@@ -1421,7 +1485,7 @@ mod tests {
     #[test]
     fn should_send_a_range_anchor_for_multi_line_comments() {
         // given — a selection spanning lines 10..14 on the head side
-        let backend = backend(vec![r#"{"id":1}"#]);
+        let backend = backend(vec![r#"{"id":1}"#, NO_COMMENTS]);
         let comments = vec![inline_comment(14, GhSide::Right, Some(10))];
         let request = CreateReviewRequest {
             event: SubmitEvent::Comment,
@@ -1441,7 +1505,7 @@ mod tests {
     #[test]
     fn should_omit_range_anchor_when_start_equals_end() {
         // given — a single-line selection reported with an explicit start
-        let backend = backend(vec![r#"{"id":1}"#]);
+        let backend = backend(vec![r#"{"id":1}"#, NO_COMMENTS]);
         let comments = vec![inline_comment(9, GhSide::Right, Some(9))];
         let request = CreateReviewRequest {
             event: SubmitEvent::Comment,
@@ -1460,7 +1524,7 @@ mod tests {
     #[test]
     fn should_approve_after_posting_comments() {
         // given
-        let backend = backend(vec![r#"{"id":1}"#, "{}"]);
+        let backend = backend(vec![r#"{"id":1}"#, NO_COMMENTS, "{}"]);
         let comments = vec![inline_comment(3, GhSide::Right, None)];
         let request = CreateReviewRequest {
             event: SubmitEvent::Approve,
@@ -1475,7 +1539,7 @@ mod tests {
         let calls = backend.runner.calls.borrow();
         assert_eq!(calls[0][0], "api");
         assert_eq!(
-            calls[1],
+            calls[2],
             vec![
                 "pr",
                 "approve",
@@ -1492,7 +1556,7 @@ mod tests {
     #[test]
     fn should_synthesize_a_response_when_the_comment_id_is_unparseable() {
         // given — Bitbucket has no review object to report
-        let backend = backend(vec!["not json"]);
+        let backend = backend(vec!["not json", NO_COMMENTS]);
         let request = CreateReviewRequest {
             event: SubmitEvent::Comment,
             commit_id: &"a".repeat(40),
@@ -1510,25 +1574,91 @@ mod tests {
     }
 
     #[test]
-    fn should_reject_request_changes_and_draft_before_posting_anything() {
-        for event in [SubmitEvent::RequestChanges, SubmitEvent::Draft] {
-            // given
-            let backend = backend(vec![]);
-            let request = CreateReviewRequest {
-                event,
-                commit_id: &"a".repeat(40),
-                body: "text",
-                comments: &[],
-            };
-            // when
-            let err = backend.create_review(&details(), request).unwrap_err();
-            // then — nothing was sent, so the user can retry with `:submit`
-            assert!(
-                err.to_string().contains("not supported for Bitbucket yet"),
-                "unexpected error for {event:?}: {err}"
-            );
-            assert!(backend.runner.calls.borrow().is_empty());
+    fn should_request_changes_after_posting_comments() {
+        // given
+        let backend = backend(vec![r#"{"id":5}"#, NO_COMMENTS, "{}"]);
+        let comments = vec![inline_comment(3, GhSide::Right, None)];
+        let request = CreateReviewRequest {
+            event: SubmitEvent::RequestChanges,
+            commit_id: &"a".repeat(40),
+            body: "",
+            comments: &comments,
+        };
+        // when
+        let response = backend.create_review(&details(), request).unwrap();
+        // then — `bkt pr` has no request-changes command, so it goes
+        // through the REST endpoint after the comments
+        let calls = backend.runner.calls.borrow();
+        assert_eq!(calls.len(), 3);
+        assert_eq!(
+            calls[2],
+            vec![
+                "api",
+                "/2.0/repositories/example-workspace/repo/pullrequests/830/request-changes",
+                "--method",
+                "POST"
+            ]
+        );
+        assert_eq!(response.id, 5);
+        assert_eq!(response.state, "CHANGES_REQUESTED");
+    }
+
+    #[test]
+    fn should_post_pending_comments_for_a_draft() {
+        // given
+        let backend = backend(vec![r#"{"id":31}"#, r#"{"id":32}"#]);
+        let comments = vec![inline_comment(8, GhSide::Right, None)];
+        let request = CreateReviewRequest {
+            event: SubmitEvent::Draft,
+            commit_id: &"a".repeat(40),
+            body: "first pass",
+            comments: &comments,
+        };
+        // when
+        let response = backend.create_review(&details(), request).unwrap();
+        // then — both comments stay pending, and nothing is published or
+        // voted on
+        let calls = backend.runner.calls.borrow();
+        assert_eq!(calls.len(), 2);
+        for call in calls.iter() {
+            let posted: serde_json::Value = serde_json::from_str(&call[5]).unwrap();
+            assert_eq!(posted["pending"], true);
         }
+        let inline: serde_json::Value = serde_json::from_str(&calls[1][5]).unwrap();
+        assert_eq!(inline["inline"]["to"], 8);
+        assert_eq!(response.id, 31);
+        assert_eq!(response.state, "PENDING");
+    }
+
+    #[test]
+    fn should_publish_pending_comments_before_voting() {
+        // given — an earlier draft left one pending comment
+        let existing = r#"{"values":[
+          {"id": 70, "content": {"raw": "published already"}, "pending": false},
+          {"id": 71, "content": {"raw": "left pending"}, "pending": true,
+           "inline": {"path": "src/lib.rs", "to": 4}}
+        ]}"#;
+        let backend = backend(vec![existing, r#"{"id":71}"#, "{}"]);
+        let request = CreateReviewRequest {
+            event: SubmitEvent::Approve,
+            commit_id: &"a".repeat(40),
+            body: "",
+            comments: &[],
+        };
+        // when
+        backend.create_review(&details(), request).unwrap();
+        // then — only the pending comment is updated, then the approval
+        let calls = backend.runner.calls.borrow();
+        assert_eq!(calls.len(), 3);
+        assert_eq!(
+            calls[1][1],
+            "/2.0/repositories/example-workspace/repo/pullrequests/830/comments/71"
+        );
+        assert_eq!(calls[1][2..4], ["--method".to_string(), "PUT".to_string()]);
+        let published: serde_json::Value = serde_json::from_str(&calls[1][5]).unwrap();
+        assert_eq!(published["pending"], false);
+        assert_eq!(published["content"]["raw"], "left pending");
+        assert_eq!(calls[2][..2], ["pr".to_string(), "approve".to_string()]);
     }
 
     // ---- error mapping ---------------------------------------------------
@@ -1606,6 +1736,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_say_comments_survived_when_only_the_change_request_fails() {
+        // given
+        struct RequestChangesFails;
+        impl BktCommandRunner for RequestChangesFails {
+            fn run(&self, args: &[String]) -> BktCommandResult<String> {
+                if args[1].ends_with("/request-changes") {
+                    return Err(BktCommandError::Failed {
+                        status: Some(1),
+                        stderr: "403 Forbidden".to_string(),
+                    });
+                }
+                Ok(r#"{"id":1}"#.to_string())
+            }
+        }
+        let backend = BitbucketBktBackend::with_runner(Some(repo()), RequestChangesFails);
+        let request = CreateReviewRequest {
+            event: SubmitEvent::RequestChanges,
+            commit_id: &"a".repeat(40),
+            body: "text",
+            comments: &[],
+        };
+        // when
+        let err = backend.create_review(&details(), request).unwrap_err();
+        // then
+        assert!(
+            err.to_string()
+                .contains("Comments were posted, but Bitbucket rejected the change request"),
+            "unexpected error: {err}"
+        );
+    }
+
     #[test]
     fn should_require_a_repository_for_a_bare_numeric_target() {
         // given — no default repository configured
@@ -0,0 +1,253 @@
+//! `BktCommandRunner` that talks to the Bitbucket Cloud API directly instead
+//! of `bkt`.
+//!
+//! The backend already speaks REST 2.0 through `bkt api`, so those calls map
+//! one-to-one. The two `bkt pr` subcommands it uses, `diff` and `approve`,
+//! are thin wrappers over a single endpoint each.
+//!
+//! Auth is `BITBUCKET_TOKEN` (an access or API token, sent as a bearer
+//! token), or `BITBUCKET_USERNAME` + `BITBUCKET_APP_PASSWORD` sent as HTTP
+//! Basic auth. The `[forge.tokens]` entry for `bitbucket.org` is the
+//! bearer-token fallback.
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64;
+
+use crate::config::ForgeConfig;
+use crate::forge::http::{
+    ApiArgs, ConfiguredTokens, HttpClient, HttpRequest, failure_message, join_url, resolve_token,
+};
+
+use super::bkt::{BktCommandError, BktCommandResult, BktCommandRunner};
+
+const DEFAULT_API_URL: &str = "https://api.bitbucket.org";
+const HOST: &str = "bitbucket.org";
+const TOKEN_HINT: &str =
+    "set BITBUCKET_TOKEN, BITBUCKET_USERNAME and BITBUCKET_APP_PASSWORD, or `[forge.tokens]`";
+
+pub struct BktHttpRunner {
+    client: HttpClient,
+    configured_token: Option<String>,
+    api_url: String,
+}
+
+impl BktHttpRunner {
+    pub fn new(config: &ForgeConfig) -> Self {
+        Self {
+            client: HttpClient::new(),
+            configured_token: ConfiguredTokens::new(config)
+                .for_host(HOST)
+                .map(str::to_string),
+            api_url: config
+                .api_url
+                .clone()
+                .unwrap_or_else(|| DEFAULT_API_URL.to_string()),
+        }
+    }
+
+    fn authorization(&self) -> Option<String> {
+        if let Some(token) = resolve_token(&["BITBUCKET_TOKEN"], None) {
+            return Some(format!("Bearer {token}"));
+        }
+        let username = resolve_token(&["BITBUCKET_USERNAME"], None);
+        let password = resolve_token(&["BITBUCKET_APP_PASSWORD"], None);
+        if let (Some(username), Some(password)) = (username, password) {
+            return Some(format!(
+                "Basic {}",
+                BASE64.encode(format!("{username}:{password}"))
+            ));
+        }
+        self.configured_token
+            .as_ref()
+            .map(|token| format!("Bearer {token}"))
+    }
+
+    fn send(&self, mut request: HttpRequest) -> BktCommandResult<String> {
+        if let Some(auth) = self.authorization() {
+            request = request.header("Authorization", auth);
+        }
+        let (status, body) = self.client.send(&request).map_err(failed)?;
+        if (200..300).contains(&status) {
+            Ok(body)
+        } else {
+            Err(failed(failure_message(status, &body, HOST, TOKEN_HINT)))
+        }
+    }
+
+    fn run_api(&self, api: ApiArgs) -> BktCommandResult<String> {
+        let body = api.body("");
+        let url = api.url_with_params(join_url(&self.api_url, &api.endpoint));
+        let mut request = HttpRequest::new(&api.effective_method(body.is_some()), url);
+        if let Some(body) = body {
+            request = request.body(body);
+        }
+        self.send(request)
+    }
+
+    fn pr_endpoint(&self, args: &[String], suffix: &str) -> BktCommandResult<String> {
+        let mut number = None;
+        let mut workspace = None;
+        let mut repo = None;
+        let mut iter = args.iter().skip(2);
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--workspace" => workspace = iter.next(),
+                "--repo" => repo = iter.next(),
+                positional if !positional.starts_with('-') => number = Some(positional),
+                _ => {}
+            }
+        }
+        match (workspace, repo, number) {
+            (Some(workspace), Some(repo), Some(number)) => Ok(join_url(
+                &self.api_url,
+                &format!("/2.0/repositories/{workspace}/{repo}/pullrequests/{number}/{suffix}"),
+            )),
+            _ => Err(failed(
+                "`bkt pr` needs a pull request id, --workspace and --repo".to_string(),
+            )),
+        }
+    }
+}
+
+impl BktCommandRunner for BktHttpRunner {
+    fn run(&self, args: &[String]) -> BktCommandResult<String> {
+        if let Some(api) = ApiArgs::parse(args) {
+            return self.run_api(api);
+        }
+        match (
+            args.first().map(String::as_str),
+            args.get(1).map(String::as_str),
+        ) {
+            (Some("pr"), Some("diff")) => {
+                self.send(HttpRequest::new("GET", self.pr_endpoint(args, "diff")?))
+            }
+            (Some("pr"), Some("approve")) => {
+                self.send(HttpRequest::new("POST", self.pr_endpoint(args, "approve")?))
+            }
+            _ => Err(failed(format!(
+                "`bkt {}` is not available over the HTTP transport",
+                args.join(" ")
+            ))),
+        }
+    }
+}
+
+fn failed(stderr: String) -> BktCommandError {
+    BktCommandError::Failed {
+        status: None,
+        stderr,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forge::http::stub::StubServer;
+
+    fn runner(server: &StubServer) -> BktHttpRunner {
+        BktHttpRunner::new(&ForgeConfig {
+            api_url: Some(server.base_url.clone()),
+            token: Some("cfg-token".to_string()),
+            ..ForgeConfig::default()
+        })
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn should_send_api_params_as_a_query_string() {
+        // given
+        let server = StubServer::start(vec![(
+            "GET",
+            "/2.0/repositories/ws/repo/pullrequests",
+            200,
+            r#"{"values":[]}"#,
+        )]);
+        // when
+        let output = runner(&server)
+            .run(&args(&[
+                "api",
+                "/2.0/repositories/ws/repo/pullrequests",
+                "-P",
+                "state=OPEN",
+                "-P",
+                "q=reviewers.uuid=\"{1}\"",
+            ]))
+            .unwrap();
+        // then
+        assert_eq!(output, r#"{"values":[]}"#);
+        assert_eq!(
+            server.requests()[0].path,
+            "/2.0/repositories/ws/repo/pullrequests?state=OPEN&q=reviewers.uuid%3D%22%7B1%7D%22"
+        );
+    }
+
+    #[test]
+    fn should_translate_pr_subcommands_to_their_endpoints() {
+        // given
+        let server = StubServer::start(vec![
+            (
+                "GET",
+                "/2.0/repositories/ws/repo/pullrequests/5/diff",
+                200,
+                "diff --git",
+            ),
+            (
+                "POST",
+                "/2.0/repositories/ws/repo/pullrequests/5/approve",
+                200,
+                "{}",
+            ),
+        ]);
+        let runner = runner(&server);
+        // when
+        let diff = runner
+            .run(&args(&[
+                "pr",
+                "diff",
+                "5",
+                "--workspace",
+                "ws",
+                "--repo",
+                "repo",
+            ]))
+            .unwrap();
+        runner
+            .run(&args(&[
+                "pr",
+                "approve",
+                "5",
+                "--workspace",
+                "ws",
+                "--repo",
+                "repo",
+            ]))
+            .unwrap();
+        // then
+        assert_eq!(diff, "diff --git");
+        let requests = server.requests();
+        assert_eq!(requests[1].method, "POST");
+        assert!(requests[1].header("Authorization").is_some());
+    }
+
+    #[test]
+    fn should_pass_literal_input_as_the_body() {
+        // given
+        let server = StubServer::start(vec![("POST", "/2.0/", 201, r#"{"id":1}"#)]);
+        // when
+        runner(&server)
+            .run(&args(&[
+                "api",
+                "/2.0/repositories/ws/repo/pullrequests/5/comments",
+                "--method",
+                "POST",
+                "--input",
+                r#"{"content":{"raw":"hi"}}"#,
+            ]))
+            .unwrap();
+        // then
+        assert_eq!(server.requests()[0].body, r#"{"content":{"raw":"hi"}}"#);
+    }
+}
@@ -1,10 +1,12 @@
-//! Bitbucket Cloud integration, driven by the `bkt` CLI.
+//! Bitbucket Cloud integration, driven by the `bkt` CLI (or, with
+//! `[forge] transport = "http"`, by direct REST calls; see `http`).
 //!
-//! Data Center is deliberately out of scope: it exposes an unrelated REST 1.0
-//! API, so `parse_bitbucket_remote_url` accepts `bitbucket.org` only and any
-//! self-hosted remote falls through to the other forge parsers.
+//! Data Center is not handled here: it exposes an unrelated REST 1.0 API, so
+//! `parse_bitbucket_remote_url` accepts `bitbucket.org` only and self-hosted
+//! remotes fall through to `bitbucket_dc`.
 
 pub mod bkt;
+pub mod http;
 pub mod models;
 
 pub use bkt::BitbucketBktBackend;
@@ -1,8 +1,7 @@
 //! Deserialization models for the Bitbucket Cloud REST 2.0 API.
 //!
-//! Only Bitbucket Cloud is supported. Data Center speaks an unrelated REST
-//! 1.0 API (project keys, `/rest/api/1.0`, comment versioning) and is
-//! rejected during remote-URL parsing rather than modelled here.
+//! Cloud only. Data Center speaks an unrelated REST 1.0 API (project keys,
+//! `/rest/api/1.0`, comment versioning), modelled in `bitbucket_dc`.
 //!
 //! Two Cloud quirks shape these types:
 //! - Pull request payloads carry *abbreviated* (12-char) commit hashes while
@@ -0,0 +1,1301 @@
+//! Bitbucket Data Center backend: `ForgeBackend` over the REST 1.0 API.
+//!
+//! Transport is pluggable ([`BitbucketDcHttp`]) so tests can stand in a
+//! fixture server; production uses [`TokenHttp`], which calls the API
+//! directly over HTTP(S). Auth is an HTTP access token, sent as a bearer
+//! token: the `[forge.tokens]` entry for the host, or `BITBUCKET_DC_TOKEN` for
+//! a host listed in `BITBUCKET_DC_HOSTS`. The token only ever goes to that
+//! host; anything else is requested anonymously, which only works on
+//! instances that allow public access.
+//!
+//! `bkt` is not used here even though it speaks Data Center: the Cloud
+//! backend's `bkt api` calls are 2.0 paths, and REST 1.0 is simple enough to
+//! call directly, the way the Gitea backend does.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde_json::json;
+
+use crate::config::ForgeConfig;
+use crate::error::{Result, TuicrError};
+use crate::forge::remote_comments::{RemoteReviewSummary, RemoteReviewThread};
+use crate::forge::self_hosted::{
+    HostAuth, configured_token, env_token_for_listed_host, host_listed, local_range_diff,
+    parse_scp_like_remote, process_env, read_blob_with_repo, split_url, strip_git_suffix,
+    trim_url_suffix,
+};
+use crate::forge::submit::{GhSide, InlineComment, SubmitEvent};
+use crate::forge::traits::{
+    CreateReviewRequest, ForgeBackend, ForgeFileLinesRequest, ForgeRepository,
+    GhCreateReviewResponse, PagedPullRequests, PullRequestCommit, PullRequestDetails,
+    PullRequestListQuery, PullRequestListScope, PullRequestReviewMetadata, PullRequestTarget,
+};
+use crate::model::{DiffLine, FilePatch};
+use crate::vcs::slice_context_lines;
+
+use super::models::{
+    DcActivity, DcComment, DcCommit, DcDiff, DcDiffResponse, DcPaged, DcPullRequest,
+    group_into_review_threads, review_summaries,
+};
+
+/// Data Center's default page limit cap is 1000, but large pages are slow;
+/// 100 keeps each call quick.
+const PAGE_LIMIT: usize = 100;
+/// Upper bound on pages walked by any paginated fetch.
+const MAX_PAGES: usize = 20;
+/// Default SSH port of a Data Center instance. A remote on it is Data Center
+/// whatever the hostname.
+const DC_SSH_PORT: &str = "7999";
+/// Cloud hostnames, which belong to the `bkt`-driven Cloud backend.
+const CLOUD_HOSTS: &[&str] = &["bitbucket.org", "altssh.bitbucket.org", "api.bitbucket.org"];
+/// Comma-separated extra hosts to treat as Data Center (custom domains).
+const HOSTS_ENV_VAR: &str = "BITBUCKET_DC_HOSTS";
+const TOKEN_ENV_VAR: &str = "BITBUCKET_DC_TOKEN";
+/// Context lines requested from the diff endpoints.
+const DIFF_CONTEXT: u32 = 3;
+
+// ---------- Transport ----------
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BitbucketDcHttpError {
+    /// 401/403 — missing, expired, or under-permissioned token.
+    Auth(String),
+    /// Any other non-2xx status or transport error.
+    Failed { status: Option<u16>, body: String },
+}
+
+pub type BitbucketDcHttpResult<T> = std::result::Result<T, BitbucketDcHttpError>;
+
+/// HTTP transport for the Data Center REST API. `url` is absolute. Returns
+/// the raw 2xx response body.
+pub trait BitbucketDcHttp: Send + Sync {
+    fn request(&self, method: &str, url: &str, body: Option<&str>)
+    -> BitbucketDcHttpResult<String>;
+}
+
+/// Direct REST transport. Sends `Authorization: Bearer <TOKEN>` to the host
+/// the token was configured for, and nowhere else.
+pub struct TokenHttp {
+    auth: Option<HostAuth>,
+    agent: ureq::Agent,
+}
+
+impl TokenHttp {
+    pub fn new(host: &str, token: Option<String>) -> Self {
+        let config = ureq::Agent::config_builder()
+            .timeout_global(Some(Duration::from_secs(30)))
+            .http_status_as_error(false)
+            .build();
+        Self {
+            auth: token.map(|token| HostAuth::new(host, format!("Bearer {token}"))),
+            agent: config.into(),
+        }
+    }
+}
+
+impl BitbucketDcHttp for TokenHttp {
+    fn request(
+        &self,
+        method: &str,
+        url: &str,
+        body: Option<&str>,
+    ) -> BitbucketDcHttpResult<String> {
+        let auth = self.auth.as_ref().and_then(|auth| auth.header_for(url));
+        let result = match method.to_ascii_uppercase().as_str() {
+            "GET" => {
+                let mut request = self.agent.get(url).header("Accept", "application/json");
+                if let Some(auth) = auth {
+                    request = request.header("Authorization", auth);
+                }
+                request.call()
+            }
+            verb @ ("POST" | "PUT") => {
+                let mut request = if verb == "POST" {
+                    self.agent.post(url)
+                } else {
+                    self.agent.put(url)
+                }
+                .header("Content-Type", "application/json")
+                // Data Center's XSRF check rejects writes without this.
+                .header("X-Atlassian-Token", "no-check");
+                if let Some(auth) = auth {
+                    request = request.header("Authorization", auth);
+                }
+                request.send(body.unwrap_or(""))
+            }
+            other => {
+                return Err(BitbucketDcHttpError::Failed {
+                    status: None,
+                    body: format!("unsupported HTTP method {other}"),
+                });
+            }
+        };
+
+        let response = result.map_err(|err| BitbucketDcHttpError::Failed {
+            status: None,
+            body: err.to_string(),
+        })?;
+        let status = response.status().as_u16();
+        let text =
+            response
+                .into_body()
+                .read_to_string()
+                .map_err(|err| BitbucketDcHttpError::Failed {
+                    status: Some(status),
+                    body: err.to_string(),
+                })?;
+
+        if (200..300).contains(&status) {
+            Ok(text)
+        } else if status == 401 || status == 403 {
+            Err(BitbucketDcHttpError::Auth(text))
+        } else {
+            Err(BitbucketDcHttpError::Failed {
+                status: Some(status),
+                body: text,
+            })
+        }
+    }
+}
+
+/// The access token configured for `host`: its `[forge.tokens]` entry, else
+/// `BITBUCKET_DC_TOKEN` when `BITBUCKET_DC_HOSTS` lists the host.
+fn token_for_host(
+    forge: &ForgeConfig,
+    host: &str,
+    env: &dyn Fn(&str) -> Option<String>,
+) -> Option<String> {
+    configured_token(forge, host)
+        .map(str::to_string)
+        .or_else(|| env_token_for_listed_host(env, &[TOKEN_ENV_VAR], HOSTS_ENV_VAR, host))
+}
+
+// ---------- Coordinate helpers ----------
+
+/// `<web root>/rest/api/1.0/projects/{key}/repos/{slug}`. Personal repos
+/// use the `~user` key, which the same path accepts.
+fn repo_api_base(repo: &ForgeRepository) -> String {
+    format!(
+        "{}/rest/api/1.0/projects/{}/repos/{}",
+        repo.web_base(),
+        encode_path_segment(&repo.owner),
+        encode_path_segment(&repo.name)
+    )
+}
+
+fn pr_api_base(pr: &PullRequestDetails) -> String {
+    format!(
+        "{}/pull-requests/{}",
+        repo_api_base(&pr.repository),
+        pr.number
+    )
+}
+
+/// Percent-encode one URL path segment.
+fn encode_path_segment(segment: &str) -> String {
+    let mut out = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                out.push(byte as char)
+            }
+            other => out.push_str(&format!("%{other:02X}")),
+        }
+    }
+    out
+}
+
+/// Append the paging parameters to `url`.
+fn with_start(url: &str, start: usize) -> String {
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{url}{separator}start={start}&limit={PAGE_LIMIT}")
+}
+
+fn diff_patches(response: DcDiffResponse) -> Vec<FilePatch> {
+    response
+        .diffs
+        .into_iter()
+        .map(DcDiff::into_file_patch)
+        .collect()
+}
+
+/// The `anchor` of an inline comment. Data Center wants the line's kind as
+/// well as its side: a line with a counterpart on the other side is
+/// unchanged context. Ranges land on their last line.
+fn comment_anchor(comment: &InlineComment) -> serde_json::Value {
+    let (line_type, file_type) = match (comment.side, comment.counterpart_line) {
+        (_, Some(_)) => (
+            "CONTEXT",
+            if comment.side == GhSide::Left {
+                "FROM"
+            } else {
+                "TO"
+            },
+        ),
+        (GhSide::Right, None) => ("ADDED", "TO"),
+        (GhSide::Left, None) => ("REMOVED", "FROM"),
+    };
+    let mut anchor = json!({
+        "path": comment.path.to_string_lossy().replace('\\', "/"),
+        "line": comment.line,
+        "lineType": line_type,
+        "fileType": file_type,
+        "diffType": "EFFECTIVE",
+    });
+    if let Some(old_path) = comment.old_path.as_ref() {
+        anchor["srcPath"] = json!(old_path.to_string_lossy().replace('\\', "/"));
+    }
+    anchor
+}
+
+// ---------- Backend ----------
+
+pub struct BitbucketDcBackend {
+    default_repository: Option<ForgeRepository>,
+    http: Box<dyn BitbucketDcHttp>,
+    local_checkout: Option<PathBuf>,
+}
+
+impl BitbucketDcBackend {
+    /// Build a backend with a token transport for the repository's host.
+    pub fn new(default_repository: Option<ForgeRepository>, forge: &ForgeConfig) -> Self {
+        let host = default_repository
+            .as_ref()
+            .map(|repo| repo.host.clone())
+            .unwrap_or_default();
+        let token = token_for_host(forge, &host, &process_env);
+        Self::with_transport(default_repository, Box::new(TokenHttp::new(&host, token)))
+    }
+
+    /// Build a backend with an explicit transport (used in tests).
+    pub fn with_transport(
+        default_repository: Option<ForgeRepository>,
+        http: Box<dyn BitbucketDcHttp>,
+    ) -> Self {
+        Self {
+            default_repository,
+            http,
+            local_checkout: None,
+        }
+    }
+
+    pub fn with_local_checkout(mut self, checkout: Option<PathBuf>) -> Self {
+        self.local_checkout = checkout;
+        self
+    }
+
+    fn resolve_repository(&self, target: &PullRequestTarget) -> Result<ForgeRepository> {
+        target
+            .repository
+            .clone()
+            .or_else(|| self.default_repository.clone())
+            .ok_or_else(|| {
+                TuicrError::Forge(format!(
+                    "Bitbucket Data Center pull request target `{}` does not include a repository",
+                    target.original
+                ))
+            })
+    }
+
+    fn get(&self, repo: &ForgeRepository, url: &str) -> Result<String> {
+        self.http
+            .request("GET", url, None)
+            .map_err(|err| map_http_error(err, &repo.host))
+    }
+
+    fn get_json<T: serde::de::DeserializeOwned>(
+        &self,
+        repo: &ForgeRepository,
+        url: &str,
+    ) -> Result<T> {
+        let output = self.get(repo, url)?;
+        Ok(serde_json::from_str(&output)?)
+    }
+
+    fn send(
+        &self,
+        repo: &ForgeRepository,
+        method: &str,
+        url: &str,
+        body: &serde_json::Value,
+    ) -> Result<String> {
+        let body = serde_json::to_string(body)?;
+        self.http
+            .request(method, url, Some(&body))
+            .map_err(|err| map_http_error(err, &repo.host))
+    }
+
+    /// Walk an offset-paged collection until the last page, the page
+    /// ceiling, or `stop` says enough rows are in.
+    fn collect_pages<T: serde::de::DeserializeOwned>(
+        &self,
+        repo: &ForgeRepository,
+        url: &str,
+        mut stop: impl FnMut(&[T]) -> bool,
+    ) -> Result<Vec<T>> {
+        let mut rows: Vec<T> = Vec::new();
+        let mut start = 0;
+        for _ in 0..MAX_PAGES {
+            let page: DcPaged<T> = self.get_json(repo, &with_start(url, start))?;
+            rows.extend(page.values);
+            match page.next_page_start {
+                Some(next) if !page.is_last_page && !stop(&rows) => start = next,
+                _ => break,
+            }
+        }
+        Ok(rows)
+    }
+
+    /// The authenticated user's login, from the whoami servlet (REST 1.0 has
+    /// no "current user" resource). Empty when anonymous.
+    fn current_user_login(&self, repo: &ForgeRepository) -> Result<Option<String>> {
+        let url = format!("{}/plugins/servlet/applinks/whoami", repo.web_base());
+        let login = self.get(repo, &url)?;
+        Ok(Some(login.trim().to_string()).filter(|login| !login.is_empty()))
+    }
+
+    fn get_pull_request_raw(&self, repo: &ForgeRepository, number: u64) -> Result<DcPullRequest> {
+        let url = format!("{}/pull-requests/{number}", repo_api_base(repo));
+        self.get_json(repo, &url)
+    }
+
+    fn list_activities(&self, pr: &PullRequestDetails) -> Result<Vec<DcActivity>> {
+        let url = format!("{}/activities", pr_api_base(pr));
+        self.collect_pages(&pr.repository, &url, |_| false)
+    }
+
+    fn fetch_file_via_api(&self, request: &ForgeFileLinesRequest) -> Result<String> {
+        let path = request
+            .path
+            .to_string_lossy()
+            .replace('\\', "/")
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(encode_path_segment)
+            .collect::<Vec<_>>()
+            .join("/");
+        let url = format!(
+            "{}/raw/{path}?at={}",
+            repo_api_base(&request.repository),
+            request.sha()
+        );
+        self.get(&request.repository, &url)
+    }
+
+    /// File content at the request's revision: local blob first, REST fallback.
+    fn file_content(&self, request: &ForgeFileLinesRequest) -> Result<String> {
+        let local = self
+            .local_checkout
+            .as_deref()
+            .and_then(|root| read_blob_with_repo(root, request.sha(), request.path.as_path()));
+        match local {
+            Some(content) => Ok(content),
+            None => self.fetch_file_via_api(request),
+        }
+    }
+
+    /// POST a comment and return its id. A missing id is not fatal: the
+    /// comment landed, it just can't be recorded.
+    fn post_comment(&self, pr: &PullRequestDetails, body: &serde_json::Value) -> Result<u64> {
+        let url = format!("{}/comments", pr_api_base(pr));
+        let output = self
+            .send(&pr.repository, "POST", &url, body)
+            .map_err(|err| {
+                TuicrError::Forge(format!("Bitbucket Data Center rejected a comment: {err}"))
+            })?;
+        Ok(serde_json::from_str::<DcComment>(&output)
+            .map(|comment| comment.id)
+            .unwrap_or(0))
+    }
+
+    /// Set the viewer's reviewer status (`APPROVED` or `NEEDS_WORK`). The
+    /// participants resource is keyed by user slug, which the PR's reviewer
+    /// list provides; an unlisted viewer falls back to the lowercased login,
+    /// which is the slug Data Center derives for ordinary usernames.
+    fn set_participant_status(&self, pr: &PullRequestDetails, status: &str) -> Result<()> {
+        let login = self.current_user_login(&pr.repository)?.ok_or_else(|| {
+            TuicrError::Forge(format!(
+                "Could not determine the authenticated Bitbucket Data Center user. Set \
+                 {TOKEN_ENV_VAR} to an HTTP access token."
+            ))
+        })?;
+        let slug = self
+            .get_pull_request_raw(&pr.repository, pr.number)?
+            .participant_slug(&login)
+            .unwrap_or_else(|| login.to_ascii_lowercase());
+        let url = format!(
+            "{}/participants/{}",
+            pr_api_base(pr),
+            encode_path_segment(&slug)
+        );
+        let body = json!({ "user": { "name": login }, "status": status });
+        self.send(&pr.repository, "PUT", &url, &body)?;
+        Ok(())
+    }
+}
+
+impl ForgeBackend for BitbucketDcBackend {
+    fn list_pull_requests(&self, query: PullRequestListQuery) -> Result<PagedPullRequests> {
+        let page_size = query.page_size.max(1);
+        let wanted = query.already_loaded + page_size + 1;
+        let url = format!(
+            "{}/pull-requests?state=OPEN&order=NEWEST",
+            repo_api_base(&query.repository)
+        );
+        // The repository PR list has no reviewer filter, so "requested" scope
+        // filters client-side on `reviewers`. Best-effort: without a
+        // resolvable user, fall back to all open PRs.
+        let reviewer = match query.scope {
+            PullRequestListScope::ReviewRequested => {
+                self.current_user_login(&query.repository).ok().flatten()
+            }
+            PullRequestListScope::Open => None,
+        };
+        let matches = |pr: &DcPullRequest| {
+            reviewer
+                .as_deref()
+                .is_none_or(|login| pr.requests_review_from(login))
+        };
+        let rows: Vec<DcPullRequest> = self.collect_pages(&query.repository, &url, |rows| {
+            rows.iter().filter(|pr| matches(pr)).count() >= wanted
+        })?;
+        let rows: Vec<DcPullRequest> = rows.into_iter().filter(|pr| matches(pr)).collect();
+        let has_more = rows.len() > query.already_loaded + page_size;
+        let pull_requests = rows
+            .into_iter()
+            .skip(query.already_loaded)
+            .take(page_size)
+            .map(|pr| pr.into_summary(&query.repository))
+            .collect::<Vec<_>>();
+        let total_loaded = query.already_loaded + pull_requests.len();
+        Ok(PagedPullRequests {
+            pull_requests,
+            has_more,
+            total_loaded,
+        })
+    }
+
+    fn get_pull_request(&self, target: PullRequestTarget) -> Result<PullRequestDetails> {
+        let repository = self.resolve_repository(&target)?;
+        let pr = self.get_pull_request_raw(&repository, target.number)?;
+        Ok(pr.into_details(&repository))
+    }
+
+    fn get_pull_request_diff(&self, pr: &PullRequestDetails) -> Result<Vec<FilePatch>> {
+        let url = format!(
+            "{}/diff?contextLines={DIFF_CONTEXT}&withComments=false",
+            pr_api_base(pr)
+        );
+        let response: DcDiffResponse = self.get_json(&pr.repository, &url)?;
+        Ok(diff_patches(response))
+    }
+
+    fn get_pull_request_commit_range_diff(
+        &self,
+        pr: &PullRequestDetails,
+        start_sha: &str,
+        end_sha: &str,
+    ) -> Result<Vec<FilePatch>> {
+        if let Some(patches) = self
+            .local_checkout
+            .as_deref()
+            .and_then(|root| local_range_diff(root, start_sha, end_sha))
+        {
+            return Ok(patches);
+        }
+        // `compare/diff` shows what `from` has that `to` lacks, so a base
+        // that is the target tip rather than the merge base still works.
+        let url = format!(
+            "{}/compare/diff?from={end_sha}&to={start_sha}&contextLines={DIFF_CONTEXT}",
+            repo_api_base(&pr.repository)
+        );
+        let response: DcDiffResponse = self.get_json(&pr.repository, &url)?;
+        Ok(diff_patches(response))
+    }
+
+    fn local_checkout_path(&self) -> Option<PathBuf> {
+        self.local_checkout.clone()
+    }
+
+    fn fetch_file_lines(&self, request: ForgeFileLinesRequest) -> Result<Vec<DiffLine>> {
+        if request.start_line == 0 || request.start_line > request.end_line {
+            return Ok(Vec::new());
+        }
+        let content = self.file_content(&request)?;
+        Ok(slice_context_lines(
+            &content,
+            request.start_line,
+            request.end_line,
+        ))
+    }
+
+    fn file_line_count(&self, request: ForgeFileLinesRequest) -> Result<u32> {
+        let content = self.file_content(&request)?;
+        Ok(content.lines().count() as u32)
+    }
+
+    fn list_review_threads(&self, pr: &PullRequestDetails) -> Result<Vec<RemoteReviewThread>> {
+        Ok(group_into_review_threads(
+            self.list_activities(pr)?,
+            &pr.url,
+        ))
+    }
+
+    fn list_review_summaries(&self, pr: &PullRequestDetails) -> Result<Vec<RemoteReviewSummary>> {
+        Ok(review_summaries(&self.list_activities(pr)?, &pr.url))
+    }
+
+    fn list_pull_request_commits(&self, pr: &PullRequestDetails) -> Result<Vec<PullRequestCommit>> {
+        let url = format!("{}/commits", pr_api_base(pr));
+        let rows: Vec<DcCommit> = self.collect_pages(&pr.repository, &url, |_| false)?;
+        // Data Center lists PR commits newest first.
+        Ok(rows
+            .into_iter()
+            .rev()
+            .map(DcCommit::into_pull_request_commit)
+            .collect())
+    }
+
+    fn list_pull_request_review_metadata(
+        &self,
+        pr: &PullRequestDetails,
+    ) -> Result<PullRequestReviewMetadata> {
+        let raw = self.get_pull_request_raw(&pr.repository, pr.number)?;
+        Ok(PullRequestReviewMetadata {
+            viewer_login: self.current_user_login(&pr.repository).unwrap_or_default(),
+            reviews: raw.review_records(),
+        })
+    }
+
+    fn create_review(
+        &self,
+        pr: &PullRequestDetails,
+        request: CreateReviewRequest<'_>,
+    ) -> Result<GhCreateReviewResponse> {
+        let status = match request.event {
+            SubmitEvent::Comment => None,
+            SubmitEvent::Approve => Some("APPROVED"),
+            SubmitEvent::RequestChanges => Some("NEEDS_WORK"),
+            SubmitEvent::Draft => {
+                return Err(TuicrError::UnsupportedOperation(
+                    "Draft (pending) reviews are not supported for Bitbucket Data Center. \
+                     Use `:submit` to publish comments directly."
+                        .to_string(),
+                ));
+            }
+        };
+
+        let mut first_comment_id: Option<u64> = None;
+
+        // The review body becomes a general (non-inline) comment.
+        if !request.body.is_empty() {
+            let id = self.post_comment(pr, &json!({ "text": request.body }))?;
+            first_comment_id = first_comment_id.or(Some(id));
+        }
+
+        for comment in request.comments {
+            let body = json!({
+                "text": comment.body,
+                "anchor": comment_anchor(comment),
+            });
+            let id = self.post_comment(pr, &body)?;
+            first_comment_id = first_comment_id.or(Some(id));
+        }
+
+        // Comments go first so a rejected vote doesn't lose them.
+        if let Some(status) = status {
+            self.set_participant_status(pr, status).map_err(|err| {
+                if request.body.is_empty() && request.comments.is_empty() {
+                    err
+                } else {
+                    TuicrError::Forge(format!(
+                        "Comments were posted, but Bitbucket Data Center rejected the review \
+                         status: {err}"
+                    ))
+                }
+            })?;
+        }
+
+        let state = match request.event {
+            SubmitEvent::Approve => "APPROVED",
+            SubmitEvent::RequestChanges => "CHANGES_REQUESTED",
+            SubmitEvent::Comment | SubmitEvent::Draft => "COMMENTED",
+        };
+        // Synthesized: Data Center has no review object to report.
+        Ok(GhCreateReviewResponse {
+            id: first_comment_id.unwrap_or(0),
+            html_url: pr.url.clone(),
+            state: state.to_string(),
+        })
+    }
+
+    fn reply_to_review_thread(
+        &self,
+        pr: &PullRequestDetails,
+        thread: &RemoteReviewThread,
+        body: &str,
+    ) -> Result<()> {
+        let parent = thread
+            .comments
+            .last()
+            .map(|comment| comment.id.as_str())
+            .unwrap_or(thread.id.as_str());
+        let parent: u64 = parent.parse().map_err(|_| {
+            TuicrError::Forge(format!(
+                "Invalid Bitbucket Data Center comment id `{parent}`"
+            ))
+        })?;
+        self.post_comment(pr, &json!({ "text": body, "parent": { "id": parent } }))?;
+        Ok(())
+    }
+
+    fn set_review_thread_resolved(
+        &self,
+        pr: &PullRequestDetails,
+        thread: &RemoteReviewThread,
+        resolved: bool,
+    ) -> Result<()> {
+        // Edits must echo the comment's current version, so read it first.
+        let url = format!("{}/comments/{}", pr_api_base(pr), thread.id);
+        let root: DcComment = self.get_json(&pr.repository, &url)?;
+        let body = json!({ "version": root.version, "threadResolved": resolved });
+        self.send(&pr.repository, "PUT", &url, &body)?;
+        Ok(())
+    }
+}
+
+fn map_http_error(error: BitbucketDcHttpError, host: &str) -> TuicrError {
+    match error {
+        BitbucketDcHttpError::Auth(detail) => TuicrError::Forge(format!(
+            "{host} rejected the request. Add an HTTP access token with repository write \
+             permission under [forge.tokens] as `\"{host}\" = \"<token>\"`, or list {host} in \
+             {HOSTS_ENV_VAR} and set {TOKEN_ENV_VAR}.\n{}",
+            trim_detail(&detail)
+        )),
+        BitbucketDcHttpError::Failed { status, body } => {
+            let status = status.map(|s| format!(" (HTTP {s})")).unwrap_or_default();
+            TuicrError::Forge(format!(
+                "Bitbucket Data Center request to {host} failed{status}: {}",
+                trim_detail(&body)
+            ))
+        }
+    }
+}
+
+/// Keep error detail readable: collapse whitespace and cap the length.
+fn trim_detail(detail: &str) -> String {
+    let collapsed = detail.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.len() > 400 {
+        let cut = (0..=400)
+            .rev()
+            .find(|&i| collapsed.is_char_boundary(i))
+            .unwrap_or(0);
+        format!("{}…", &collapsed[..cut])
+    } else {
+        collapsed
+    }
+}
+
+// ---------- URL & target parsing ----------
+
+/// True when `host` names a Data Center instance: a hostname that says
+/// Bitbucket but isn't Cloud (`bitbucket.example.com`), or a host listed in
+/// `BITBUCKET_DC_HOSTS`.
+pub(crate) fn is_bitbucket_dc_host(host: &str) -> bool {
+    let host = host.to_ascii_lowercase();
+    if CLOUD_HOSTS.contains(&host.as_str()) {
+        return false;
+    }
+    if host.contains("bitbucket") {
+        return true;
+    }
+    host_listed(HOSTS_ENV_VAR, &host)
+}
+
+/// Parse a Data Center remote (git) URL into a `ForgeRepository`.
+///
+/// Accepts `https://host[:port][/<context>]/scm/<project>/<repo>.git`,
+/// `ssh://git@host:7999/<project>/<repo>.git` and `git@host:<project>/<repo>.git`.
+/// The `/scm/` clone path and SSH port 7999 are Data Center's own, so those
+/// are claimed on any host; anything else needs [`is_bitbucket_dc_host`].
+/// An HTTP(S) remote's scheme, port and context path are kept as the
+/// repository's web root.
+pub fn parse_bitbucket_dc_remote_url(remote_url: &str) -> Option<ForgeRepository> {
+    let trimmed = trim_url_suffix(remote_url.trim());
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    if let Some((host, path)) = parse_scp_like_remote(trimmed) {
+        if !is_bitbucket_dc_host(host) {
+            return None;
+        }
+        return dc_from_path(host, path);
+    }
+
+    let parts = split_url(trimmed)?;
+    let host = parts.host();
+    if CLOUD_HOSTS.contains(&host.to_ascii_lowercase().as_str()) {
+        return None;
+    }
+    let scm = if let Some(rest) = parts.path.strip_prefix("scm/") {
+        Some(("", rest))
+    } else {
+        parts.path.split_once("/scm/")
+    };
+    if let Some((context, rest)) = scm {
+        return Some(dc_from_path(host, rest)?.with_web_root(parts.web_root(context)));
+    }
+    let on_dc_port = parts.port() == Some(DC_SSH_PORT);
+    if !on_dc_port && !is_bitbucket_dc_host(host) {
+        return None;
+    }
+    Some(dc_from_path(host, parts.path)?.with_web_root(parts.web_root("")))
+}
+
+fn dc_from_path(host: &str, path: &str) -> Option<ForgeRepository> {
+    let mut parts = path.split('/').filter(|part| !part.is_empty());
+    let project = parts.next()?;
+    let repo = strip_git_suffix(parts.next()?);
+    if parts.next().is_some() || repo.is_empty() {
+        return None;
+    }
+    Some(ForgeRepository::bitbucket_data_center(
+        host.to_ascii_lowercase(),
+        normalize_project_key(project),
+        repo.to_ascii_lowercase(),
+    ))
+}
+
+/// Project keys are case-insensitive but shown uppercase; clone URLs spell
+/// them lowercase. Personal `~user` keys are lowercase. Normalizing keeps
+/// remotes and PR URLs comparing equal.
+fn normalize_project_key(key: &str) -> String {
+    if key.starts_with('~') {
+        key.to_ascii_lowercase()
+    } else {
+        key.to_ascii_uppercase()
+    }
+}
+
+/// Parse a pull request target: a bare number or a PR web URL,
+/// `https://host[:port][/<context>]/projects/<KEY>/repos/<repo>/pull-requests/<id>[/...]`
+/// (or `/users/<user>/repos/...` for personal repos). That path shape is Data
+/// Center's alone, so PR URLs are accepted on any host; the token is only
+/// sent to a configured one.
+pub fn parse_pull_request_target_bitbucket_dc(input: &str) -> Result<PullRequestTarget> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return malformed_target(input);
+    }
+    if let Some(target) = parse_numeric_target(trimmed) {
+        return Ok(target);
+    }
+    if let Some(target) = parse_dc_url_target(trimmed) {
+        return Ok(target);
+    }
+    malformed_target(input)
+}
+
+fn parse_numeric_target(target: &str) -> Option<PullRequestTarget> {
+    if !target.chars().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    let number = target.parse::<u64>().ok()?;
+    if number == 0 {
+        return None;
+    }
+    Some(PullRequestTarget::number(number, target))
+}
+
+fn parse_dc_url_target(target: &str) -> Option<PullRequestTarget> {
+    let url = split_url(target).filter(|url| url.scheme != "ssh")?;
+    let segments: Vec<&str> = url.path.split('/').filter(|p| !p.is_empty()).collect();
+    // [context.., "projects"|"users", key, "repos", repo, "pull-requests", id, ..]
+    let start = segments.windows(6).position(|window| {
+        matches!(window[0], "projects" | "users")
+            && window[2] == "repos"
+            && window[4] == "pull-requests"
+    })?;
+    let parts = &segments[start..];
+    let project = match parts[0] {
+        "projects" => parts[1].to_string(),
+        _ => format!("~{}", parts[1]),
+    };
+    let number = parts[5].parse::<u64>().ok()?;
+    if number == 0 {
+        return None;
+    }
+    let repository = ForgeRepository::bitbucket_data_center(
+        url.host().to_ascii_lowercase(),
+        normalize_project_key(&project),
+        parts[3].to_ascii_lowercase(),
+    )
+    .with_web_root(url.web_root(&segments[..start].join("/")));
+    Some(PullRequestTarget::with_repository(
+        repository, number, target,
+    ))
+}
+
+fn malformed_target<T>(input: &str) -> Result<T> {
+    Err(TuicrError::Forge(format!(
+        "Malformed Bitbucket Data Center pull request target: `{input}`"
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forge::remote_comments::{RemoteCommentSide, RemoteReviewComment};
+    use crate::forge::self_hosted::fixture::{Call, FixtureServer};
+
+    fn dc_repo() -> ForgeRepository {
+        ForgeRepository::bitbucket_data_center("bitbucket.example.com", "PLAT", "build")
+    }
+
+    // ---- URL parsing ----
+
+    #[test]
+    fn parses_scm_https_remote_on_any_host() {
+        let repo =
+            parse_bitbucket_dc_remote_url("https://alice@git.corp.example/scm/plat/build.git")
+                .unwrap();
+        assert_eq!(
+            repo,
+            ForgeRepository::bitbucket_data_center("git.corp.example", "PLAT", "build")
+        );
+        assert_eq!(repo.display_name(), "git.corp.example/PLAT/build");
+    }
+
+    #[test]
+    fn parses_ssh_remote_on_data_center_port() {
+        let repo = parse_bitbucket_dc_remote_url("ssh://git@git.corp.example:7999/plat/build.git")
+            .unwrap();
+        assert_eq!(repo.owner, "PLAT");
+        assert_eq!(repo.name, "build");
+    }
+
+    #[test]
+    fn parses_scp_remote_on_named_host_and_personal_repo() {
+        let repo =
+            parse_bitbucket_dc_remote_url("git@bitbucket.example.com:~Alice/dotfiles.git").unwrap();
+        assert_eq!(
+            repo,
+            ForgeRepository::bitbucket_data_center("bitbucket.example.com", "~alice", "dotfiles")
+        );
+    }
+
+    #[test]
+    fn leaves_cloud_and_foreign_remotes_alone() {
+        assert!(parse_bitbucket_dc_remote_url("git@bitbucket.org:team/repo.git").is_none());
+        assert!(parse_bitbucket_dc_remote_url("https://bitbucket.org/scm/team/repo.git").is_none());
+        assert!(parse_bitbucket_dc_remote_url("https://github.com/agavra/tuicr").is_none());
+    }
+
+    #[test]
+    fn parses_pull_request_url_with_trailing_segments() {
+        let target = parse_pull_request_target_bitbucket_dc(
+            "https://bitbucket.example.com/projects/PLAT/repos/build/pull-requests/42/overview",
+        )
+        .unwrap();
+        assert_eq!(target.number, 42);
+        assert_eq!(target.repository.unwrap(), dc_repo());
+
+        let personal = parse_pull_request_target_bitbucket_dc(
+            "https://bitbucket.example.com/users/alice/repos/dotfiles/pull-requests/3",
+        )
+        .unwrap();
+        assert_eq!(personal.repository.unwrap().owner, "~alice");
+    }
+
+    #[test]
+    fn keeps_scheme_port_and_context_path_of_urls() {
+        let target = parse_pull_request_target_bitbucket_dc(
+            "http://git.corp.example:7990/bitbucket/projects/PLAT/repos/build/pull-requests/42",
+        )
+        .unwrap();
+        let repository = target.repository.unwrap();
+        assert_eq!(
+            repository,
+            ForgeRepository::bitbucket_data_center("git.corp.example", "PLAT", "build")
+        );
+        assert_eq!(
+            repo_api_base(&repository),
+            "http://git.corp.example:7990/bitbucket/rest/api/1.0/projects/PLAT/repos/build"
+        );
+
+        let remote = parse_bitbucket_dc_remote_url(
+            "https://git.corp.example:8443/bitbucket/scm/plat/build.git",
+        )
+        .unwrap();
+        assert_eq!(remote.web_base(), "https://git.corp.example:8443/bitbucket");
+    }
+
+    #[test]
+    fn reads_the_token_only_for_a_configured_host() {
+        let forge = ForgeConfig {
+            tokens: [("bitbucket.example.com".to_string(), "cfg".to_string())].into(),
+            ..ForgeConfig::default()
+        };
+        let env = |name: &str| match name {
+            "BITBUCKET_DC_HOSTS" => Some("git.corp.example".to_string()),
+            "BITBUCKET_DC_TOKEN" => Some("env".to_string()),
+            _ => None,
+        };
+        assert_eq!(
+            token_for_host(&forge, "bitbucket.example.com", &env).as_deref(),
+            Some("cfg")
+        );
+        assert_eq!(
+            token_for_host(&forge, "git.corp.example", &env).as_deref(),
+            Some("env")
+        );
+        assert_eq!(token_for_host(&forge, "evil.example", &env), None);
+
+        let http = TokenHttp::new("git.corp.example", Some("env".to_string()));
+        let auth = http.auth.as_ref().unwrap();
+        assert!(
+            auth.header_for("https://git.corp.example:8443/rest/api/1.0/projects")
+                .is_some()
+        );
+        assert!(
+            auth.header_for("https://evil.example/rest/api/1.0/projects")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn rejects_foreign_pull_request_urls() {
+        assert!(
+            parse_pull_request_target_bitbucket_dc(
+                "https://bitbucket.org/team/repo/pull-requests/3"
+            )
+            .is_err()
+        );
+        assert!(parse_pull_request_target_bitbucket_dc("owner/repo#3").is_err());
+    }
+
+    // ---- Backend calls (via a fixture server transport) ----
+
+    impl BitbucketDcHttp for FixtureServer {
+        fn request(
+            &self,
+            method: &str,
+            url: &str,
+            body: Option<&str>,
+        ) -> BitbucketDcHttpResult<String> {
+            self.respond(method, url, body)
+                .map_err(|path| BitbucketDcHttpError::Failed {
+                    status: Some(404),
+                    body: format!("Not found: {path}"),
+                })
+        }
+    }
+
+    const REPO: &str = "/rest/api/1.0/projects/PLAT/repos/build";
+    const PR: &str = "/rest/api/1.0/projects/PLAT/repos/build/pull-requests/42";
+    const WHOAMI: &str = "/plugins/servlet/applinks/whoami";
+
+    const PR_JSON: &str = r#"{
+      "id": 42, "title": "Cache build outputs", "state": "OPEN",
+      "reviewers": [ { "user": { "name": "Carol.Smith", "slug": "carol.smith" }, "status": "UNAPPROVED" } ],
+      "fromRef": { "displayId": "cache", "latestCommit": "head222" },
+      "toRef": { "displayId": "main", "latestCommit": "base000" },
+      "links": { "self": [ { "href": "https://bitbucket.example.com/projects/PLAT/repos/build/pull-requests/42" } ] }
+    }"#;
+
+    fn backend(server: &FixtureServer) -> BitbucketDcBackend {
+        BitbucketDcBackend::with_transport(Some(dc_repo()), Box::new(server.clone()))
+    }
+
+    fn pr_details() -> PullRequestDetails {
+        let pr: DcPullRequest = serde_json::from_str(PR_JSON).unwrap();
+        pr.into_details(&dc_repo())
+    }
+
+    fn body_json(call: &Call) -> serde_json::Value {
+        serde_json::from_str(call.2.as_deref().unwrap()).unwrap()
+    }
+
+    fn inline(line: u32, side: GhSide, counterpart_line: Option<u32>) -> InlineComment {
+        InlineComment {
+            path: PathBuf::from("src/cache.rs"),
+            line,
+            side,
+            counterpart_line,
+            start_line: None,
+            start_side: None,
+            range_anchors: None,
+            old_path: None,
+            body: "please fix".to_string(),
+            comment_id: "c1".to_string(),
+        }
+    }
+
+    #[test]
+    fn get_pull_request_reads_the_project_repo_path() {
+        let server = FixtureServer::new(&[("GET", PR, PR_JSON)]);
+        let details = backend(&server)
+            .get_pull_request(PullRequestTarget::number(42, "42"))
+            .unwrap();
+        assert_eq!(details.head_sha, "head222");
+        assert_eq!(
+            server.calls()[0].1,
+            format!("https://bitbucket.example.com{PR}")
+        );
+    }
+
+    #[test]
+    fn list_follows_next_page_start() {
+        let server = FixtureServer::new(&[
+            (
+                "GET",
+                &format!("{REPO}/pull-requests?state=OPEN&order=NEWEST&start=0"),
+                r#"{ "isLastPage": false, "nextPageStart": 100,
+                     "values": [ { "id": 2, "title": "two", "state": "OPEN" } ] }"#,
+            ),
+            (
+                "GET",
+                &format!("{REPO}/pull-requests?state=OPEN&order=NEWEST&start=100"),
+                r#"{ "isLastPage": true, "values": [ { "id": 1, "title": "one", "state": "OPEN" } ] }"#,
+            ),
+        ]);
+        let page = backend(&server)
+            .list_pull_requests(PullRequestListQuery::first_page(dc_repo(), 30))
+            .unwrap();
+        let numbers: Vec<u64> = page.pull_requests.iter().map(|pr| pr.number).collect();
+        assert_eq!(numbers, vec![2, 1]);
+        assert!(!page.has_more);
+        assert_eq!(server.calls().len(), 2);
+    }
+
+    #[test]
+    fn list_requested_scope_filters_on_reviewers() {
+        let server = FixtureServer::new(&[
+            ("GET", WHOAMI, "carol.smith\n"),
+            (
+                "GET",
+                &format!("{REPO}/pull-requests"),
+                &format!(
+                    r#"{{ "isLastPage": true, "values": [ {PR_JSON},
+                       {{ "id": 7, "title": "other", "state": "OPEN" }} ] }}"#
+                ),
+            ),
+        ]);
+        let query = PullRequestListQuery::first_page_with_scope(
+            dc_repo(),
+            30,
+            PullRequestListScope::ReviewRequested,
+        );
+        let page = backend(&server).list_pull_requests(query).unwrap();
+        assert_eq!(page.pull_requests.len(), 1);
+        assert_eq!(page.pull_requests[0].number, 42);
+    }
+
+    #[test]
+    fn commits_are_returned_oldest_first() {
+        let server = FixtureServer::new(&[(
+            "GET",
+            &format!("{PR}/commits"),
+            r#"{ "isLastPage": true, "values": [
+                 { "id": "head222", "message": "second" }, { "id": "aaa111", "message": "first" } ] }"#,
+        )]);
+        let commits = backend(&server)
+            .list_pull_request_commits(&pr_details())
+            .unwrap();
+        let oids: Vec<&str> = commits.iter().map(|c| c.oid.as_str()).collect();
+        assert_eq!(oids, vec!["aaa111", "head222"]);
+    }
+
+    #[test]
+    fn range_diff_uses_compare_from_end_to_start() {
+        let server = FixtureServer::new(&[(
+            "GET",
+            &format!("{REPO}/compare/diff"),
+            r#"{ "diffs": [ { "source": { "toString": "a.rs" }, "destination": { "toString": "a.rs" },
+                 "hunks": [ { "sourceLine": 1, "sourceSpan": 1, "destinationLine": 1, "destinationSpan": 1,
+                   "segments": [ { "type": "REMOVED", "lines": [ { "line": "x" } ] },
+                                 { "type": "ADDED", "lines": [ { "line": "y" } ] } ] } ] } ] }"#,
+        )]);
+        let patches = backend(&server)
+            .get_pull_request_commit_range_diff(&pr_details(), "aaa111", "head222")
+            .unwrap();
+        assert_eq!(patches[0].patch, "@@ -1,1 +1,1 @@\n-x\n+y\n");
+        assert!(server.calls()[0].1.contains("from=head222&to=aaa111"));
+    }
+
+    #[test]
+    fn request_changes_posts_comments_then_needs_work() {
+        let server = FixtureServer::new(&[
+            (
+                "POST",
+                &format!("{PR}/comments"),
+                r#"{ "id": 501, "version": 0 }"#,
+            ),
+            ("GET", WHOAMI, "Carol.Smith"),
+            ("GET", PR, PR_JSON),
+            ("PUT", &format!("{PR}/participants/carol.smith"), "{}"),
+        ]);
+        let request = CreateReviewRequest {
+            event: SubmitEvent::RequestChanges,
+            commit_id: "head222",
+            body: "Needs tests",
+            comments: &[
+                inline(10, GhSide::Right, None),
+                inline(4, GhSide::Left, None),
+                inline(6, GhSide::Right, Some(5)),
+            ],
+        };
+        let response = backend(&server)
+            .create_review(&pr_details(), request)
+            .unwrap();
+        assert_eq!(response.state, "CHANGES_REQUESTED");
+        assert_eq!(response.id, 501);
+
+        let calls = server.calls();
+        assert_eq!(body_json(&calls[0]), json!({ "text": "Needs tests" }));
+        let anchors: Vec<(String, String)> = calls[1..4]
+            .iter()
+            .map(|call| {
+                let anchor = &body_json(call)["anchor"];
+                (
+                    anchor["lineType"].as_str().unwrap().to_string(),
+                    anchor["fileType"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            anchors,
+            vec![
+                ("ADDED".to_string(), "TO".to_string()),
+                ("REMOVED".to_string(), "FROM".to_string()),
+                ("CONTEXT".to_string(), "TO".to_string()),
+            ]
+        );
+        let vote = calls.last().unwrap();
+        assert_eq!(vote.0, "PUT");
+        assert_eq!(body_json(vote)["status"], "NEEDS_WORK");
+    }
+
+    #[test]
+    fn comment_event_sets_no_status() {
+        let server = FixtureServer::new(&[("POST", &format!("{PR}/comments"), r#"{ "id": 9 }"#)]);
+        let request = CreateReviewRequest {
+            event: SubmitEvent::Comment,
+            commit_id: "head222",
+            body: "",
+            comments: &[inline(10, GhSide::Right, None)],
+        };
+        let response = backend(&server)
+            .create_review(&pr_details(), request)
+            .unwrap();
+        assert_eq!(response.state, "COMMENTED");
+        assert_eq!(server.calls().len(), 1);
+    }
+
+    #[test]
+    fn draft_is_rejected_before_posting_anything() {
+        let server = FixtureServer::new(&[]);
+        let request = CreateReviewRequest {
+            event: SubmitEvent::Draft,
+            commit_id: "head222",
+            body: "x",
+            comments: &[],
+        };
+        let err = backend(&server)
+            .create_review(&pr_details(), request)
+            .unwrap_err();
+        assert!(matches!(err, TuicrError::UnsupportedOperation(_)));
+        assert!(server.calls().is_empty());
+    }
+
+    #[test]
+    fn approval_failure_after_comments_says_comments_survived() {
+        let server = FixtureServer::new(&[
+            ("POST", &format!("{PR}/comments"), r#"{ "id": 1 }"#),
+            ("GET", WHOAMI, "carol.smith"),
+            ("GET", PR, PR_JSON),
+        ]);
+        let request = CreateReviewRequest {
+            event: SubmitEvent::Approve,
+            commit_id: "head222",
+            body: "LGTM",
+            comments: &[],
+        };
+        let err = backend(&server)
+            .create_review(&pr_details(), request)
+            .unwrap_err();
+        assert!(err.to_string().contains("Comments were posted"));
+    }
+
+    fn thread() -> RemoteReviewThread {
+        RemoteReviewThread {
+            id: "10".to_string(),
+            path: "src/cache.rs".to_string(),
+            line: Some(7),
+            side: RemoteCommentSide::Right,
+            is_resolved: false,
+            is_outdated: false,
+            comments: vec![
+                RemoteReviewComment {
+                    id: "10".to_string(),
+                    author: None,
+                    body: "why?".to_string(),
+                    created_at: None,
+                    in_reply_to: None,
+                    url: String::new(),
+                },
+                RemoteReviewComment {
+                    id: "11".to_string(),
+                    author: None,
+                    body: "because".to_string(),
+                    created_at: None,
+                    in_reply_to: Some("10".to_string()),
+                    url: String::new(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn reply_nests_under_the_latest_comment() {
+        let server = FixtureServer::new(&[("POST", &format!("{PR}/comments"), r#"{ "id": 12 }"#)]);
+        backend(&server)
+            .reply_to_review_thread(&pr_details(), &thread(), "ok")
+            .unwrap();
+        assert_eq!(
+            body_json(&server.calls()[0]),
+            json!({ "text": "ok", "parent": { "id": 11 } })
+        );
+    }
+
+    #[test]
+    fn resolve_echoes_the_comment_version() {
+        let server = FixtureServer::new(&[
+            (
+                "GET",
+                &format!("{PR}/comments/10"),
+                r#"{ "id": 10, "version": 3 }"#,
+            ),
+            (
+                "PUT",
+                &format!("{PR}/comments/10"),
+                r#"{ "id": 10, "version": 4 }"#,
+            ),
+        ]);
+        backend(&server)
+            .set_review_thread_resolved(&pr_details(), &thread(), true)
+            .unwrap();
+        assert_eq!(
+            body_json(&server.calls()[1]),
+            json!({ "version": 3, "threadResolved": true })
+        );
+    }
+
+    #[test]
+    fn backend_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<BitbucketDcBackend>();
+    }
+}
@@ -0,0 +1,10 @@
+//! Bitbucket Data Center (formerly Server) integration.
+//!
+//! Separate from `bitbucket`, which drives Cloud through `bkt`: Data Center
+//! speaks the unrelated REST 1.0 API, which this backend calls directly over
+//! HTTPS.
+
+pub mod api;
+pub mod models;
+
+pub use api::BitbucketDcBackend;
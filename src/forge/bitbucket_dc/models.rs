@@ -0,0 +1,764 @@
+//! Deserialization models for the Bitbucket Data Center REST 1.0 API.
+//!
+//! Data Center shares almost nothing with Cloud's 2.0 API, hence the separate
+//! module. The differences that shape these types:
+//! - Repositories live under a project *key* (`PROJ`, or `~user` for
+//!   personal repos) rather than a workspace.
+//! - Timestamps are epoch milliseconds, not RFC 3339 strings.
+//! - Comments are read from the pull request's activity stream, where each
+//!   root comment carries its replies nested under `comments`.
+//! - Diffs come back as JSON segments rather than unified text;
+//!   [`DcDiff::into_file_patch`] rebuilds the hunks.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::forge::remote_comments::{
+    RemoteCommentSide, RemoteReviewComment, RemoteReviewState, RemoteReviewSummary,
+    RemoteReviewThread,
+};
+use crate::forge::traits::{
+    ForgeRepository, PullRequestCommit, PullRequestDetails, PullRequestReviewRecord,
+    PullRequestSummary,
+};
+use crate::model::{FilePatch, FileStatus};
+
+/// Envelope Data Center wraps every paginated collection in. Paging is by
+/// offset: `nextPageStart` is the `start` of the next request.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DcPaged<T> {
+    #[serde(default = "Vec::new")]
+    pub values: Vec<T>,
+    #[serde(default = "default_true")]
+    pub is_last_page: bool,
+    #[serde(default)]
+    pub next_page_start: Option<usize>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn millis(value: Option<i64>) -> Option<DateTime<Utc>> {
+    value.and_then(DateTime::from_timestamp_millis)
+}
+
+#[derive(Debug, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DcUser {
+    /// Login name — the handle shown in the UI and used for identity.
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub display_name: String,
+    /// URL-safe form of `name`, used in `participants/{slug}` paths.
+    #[serde(default)]
+    pub slug: String,
+}
+
+impl DcUser {
+    pub fn label(&self) -> Option<String> {
+        [&self.name, &self.display_name]
+            .into_iter()
+            .find(|candidate| !candidate.is_empty())
+            .cloned()
+    }
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct DcLink {
+    #[serde(default)]
+    pub href: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct DcLinks {
+    #[serde(default, rename = "self")]
+    pub self_links: Vec<DcLink>,
+}
+
+impl DcLinks {
+    pub fn self_href(&self) -> String {
+        self.self_links
+            .first()
+            .map(|link| link.href.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// `fromRef` / `toRef`: a branch plus its tip at the time of the request.
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DcRef {
+    /// Fully qualified, e.g. `refs/heads/feature`.
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub display_id: String,
+    #[serde(default)]
+    pub latest_commit: String,
+}
+
+/// A reviewer or participant. `status` is `UNAPPROVED`, `NEEDS_WORK`, or
+/// `APPROVED`.
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DcParticipant {
+    #[serde(default)]
+    pub user: DcUser,
+    #[serde(default)]
+    pub status: String,
+    /// Head commit the participant last reviewed, when the server tracks it.
+    #[serde(default)]
+    pub last_reviewed_commit: Option<String>,
+}
+
+/// `GET .../pull-requests[/{id}]` — the same shape serves both the list and
+/// the detail endpoints.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DcPullRequest {
+    pub id: u64,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub state: String,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub author: Option<DcParticipant>,
+    #[serde(default)]
+    pub reviewers: Vec<DcParticipant>,
+    #[serde(default)]
+    pub participants: Vec<DcParticipant>,
+    #[serde(default)]
+    pub from_ref: DcRef,
+    #[serde(default)]
+    pub to_ref: DcRef,
+    #[serde(default)]
+    pub updated_date: Option<i64>,
+    #[serde(default)]
+    pub closed_date: Option<i64>,
+    #[serde(default)]
+    pub links: DcLinks,
+}
+
+impl DcPullRequest {
+    fn author_label(&self) -> Option<String> {
+        self.author.as_ref().and_then(|author| author.user.label())
+    }
+
+    /// True when `login` is listed as a reviewer who hasn't approved yet.
+    pub fn requests_review_from(&self, login: &str) -> bool {
+        self.reviewers.iter().any(|reviewer| {
+            reviewer.user.name.eq_ignore_ascii_case(login) && reviewer.status != "APPROVED"
+        })
+    }
+
+    /// The URL slug of `login` as a reviewer or participant, if present.
+    pub fn participant_slug(&self, login: &str) -> Option<String> {
+        self.reviewers
+            .iter()
+            .chain(&self.participants)
+            .find(|participant| participant.user.name.eq_ignore_ascii_case(login))
+            .map(|participant| participant.user.slug.clone())
+            .filter(|slug| !slug.is_empty())
+    }
+
+    pub fn into_summary(self, repo: &ForgeRepository) -> PullRequestSummary {
+        PullRequestSummary {
+            repository: repo.clone(),
+            number: self.id,
+            title: self.title.clone(),
+            author: self.author_label(),
+            head_ref_name: self.from_ref.display_id.clone(),
+            base_ref_name: self.to_ref.display_id.clone(),
+            updated_at: millis(self.updated_date),
+            url: self.links.self_href(),
+            state: normalize_state(&self.state),
+            is_draft: self.draft,
+        }
+    }
+
+    /// Reviewers who have voted, for "commits since my last review"
+    /// inference. Data Center records the head each reviewer last saw.
+    pub fn review_records(&self) -> Vec<PullRequestReviewRecord> {
+        self.reviewers
+            .iter()
+            .filter(|reviewer| {
+                reviewer.status != "UNAPPROVED" || reviewer.last_reviewed_commit.is_some()
+            })
+            .map(|reviewer| PullRequestReviewRecord {
+                author: reviewer.user.label(),
+                submitted_at: None,
+                commit_oid: reviewer.last_reviewed_commit.clone(),
+            })
+            .collect()
+    }
+
+    /// `base_sha` is the target branch tip, not the merge base; the server's
+    /// own diff and compare endpoints are merge-base relative, so it only
+    /// anchors the commit selector.
+    pub fn into_details(self, repo: &ForgeRepository) -> PullRequestDetails {
+        let state = normalize_state(&self.state);
+        let merged = state == "MERGED";
+        PullRequestDetails {
+            repository: repo.clone(),
+            number: self.id,
+            title: self.title.clone(),
+            url: self.links.self_href(),
+            is_draft: self.draft,
+            author: self.author_label(),
+            head_ref_name: self.from_ref.display_id,
+            base_ref_name: self.to_ref.display_id,
+            head_sha: self.from_ref.latest_commit,
+            base_sha: self.to_ref.latest_commit,
+            body: self.description,
+            updated_at: millis(self.updated_date),
+            closed: state != "OPEN",
+            merged_at: if merged {
+                millis(self.closed_date.or(self.updated_date))
+            } else {
+                None
+            },
+            state,
+            diff_start_sha: None,
+        }
+    }
+}
+
+/// `GET .../pull-requests/{id}/commits`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DcCommit {
+    pub id: String,
+    #[serde(default)]
+    pub display_id: String,
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub author: Option<DcCommitAuthor>,
+    #[serde(default)]
+    pub author_timestamp: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DcCommitAuthor {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub display_name: String,
+}
+
+impl DcCommit {
+    pub fn into_pull_request_commit(self) -> PullRequestCommit {
+        let short_oid = if self.display_id.is_empty() {
+            self.id.chars().take(7).collect()
+        } else {
+            self.display_id.chars().take(7).collect()
+        };
+        let summary = self
+            .message
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        let author = self
+            .author
+            .map(|author| {
+                if author.display_name.is_empty() {
+                    author.name
+                } else {
+                    author.display_name
+                }
+            })
+            .unwrap_or_default();
+        PullRequestCommit {
+            oid: self.id,
+            short_oid,
+            summary,
+            author,
+            timestamp: millis(self.author_timestamp),
+        }
+    }
+}
+
+/// Where an inline comment is anchored. `fileType` is `FROM` (base side) or
+/// `TO` (head side); `orphaned` means later pushes moved the line away.
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DcCommentAnchor {
+    #[serde(default)]
+    pub path: String,
+    #[serde(default)]
+    pub line: Option<u32>,
+    #[serde(default)]
+    pub file_type: Option<String>,
+    #[serde(default)]
+    pub orphaned: bool,
+}
+
+impl DcCommentAnchor {
+    fn side(&self) -> RemoteCommentSide {
+        match self.file_type.as_deref() {
+            Some("FROM") => RemoteCommentSide::Left,
+            _ => RemoteCommentSide::Right,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DcComment {
+    pub id: u64,
+    /// Optimistic-locking counter; every edit must echo the current value.
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default)]
+    pub text: String,
+    #[serde(default)]
+    pub author: DcUser,
+    #[serde(default)]
+    pub created_date: Option<i64>,
+    /// `OPEN` or `RESOLVED` (task-style comments).
+    #[serde(default)]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub thread_resolved: bool,
+    /// Replies, each possibly with replies of its own.
+    #[serde(default)]
+    pub comments: Vec<DcComment>,
+}
+
+impl DcComment {
+    fn is_resolved(&self) -> bool {
+        self.thread_resolved || self.state.as_deref() == Some("RESOLVED")
+    }
+
+    /// Depth-first flatten of this comment and its replies, each tagged with
+    /// its parent id.
+    fn flatten_into(self, parent: Option<u64>, url: &str, out: &mut Vec<RemoteReviewComment>) {
+        let id = self.id;
+        if !self.text.is_empty() {
+            out.push(RemoteReviewComment {
+                id: id.to_string(),
+                author: self.author.label(),
+                body: self.text,
+                created_at: millis(self.created_date),
+                in_reply_to: parent.map(|parent| parent.to_string()),
+                url: comment_url(url, id),
+            });
+        }
+        for reply in self.comments {
+            reply.flatten_into(Some(id), url, out);
+        }
+    }
+}
+
+/// One entry of `GET .../pull-requests/{id}/activities`. Only `COMMENTED`
+/// entries with `commentAction: ADDED` introduce a root comment; replies
+/// produce their own `REPLIED` entries but also arrive nested under the root.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DcActivity {
+    #[serde(default)]
+    pub action: String,
+    #[serde(default)]
+    pub comment_action: Option<String>,
+    #[serde(default)]
+    pub comment: Option<DcComment>,
+    #[serde(default)]
+    pub comment_anchor: Option<DcCommentAnchor>,
+}
+
+impl DcActivity {
+    fn is_root_comment(&self) -> bool {
+        self.action == "COMMENTED" && self.comment_action.as_deref() == Some("ADDED")
+    }
+}
+
+fn comment_url(pr_url: &str, id: u64) -> String {
+    if pr_url.is_empty() {
+        return String::new();
+    }
+    format!("{}/overview?commentId={id}", pr_url.trim_end_matches('/'))
+}
+
+/// Line-anchored threads from the activity stream.
+///
+/// File-level comments (anchored to a path but no line) and general comments
+/// are excluded; general ones surface through [`review_summaries`].
+pub fn group_into_review_threads(
+    activities: Vec<DcActivity>,
+    pr_url: &str,
+) -> Vec<RemoteReviewThread> {
+    let mut seen = std::collections::HashSet::new();
+    let mut threads = Vec::new();
+    for activity in activities {
+        if !activity.is_root_comment() {
+            continue;
+        }
+        let (Some(comment), Some(anchor)) = (activity.comment, activity.comment_anchor) else {
+            continue;
+        };
+        let Some(line) = anchor.line.filter(|_| !anchor.path.is_empty()) else {
+            continue;
+        };
+        if !seen.insert(comment.id) {
+            continue;
+        }
+        let id = comment.id.to_string();
+        let is_resolved = comment.is_resolved();
+        let mut comments = Vec::new();
+        comment.flatten_into(None, pr_url, &mut comments);
+        if comments.is_empty() {
+            continue;
+        }
+        threads.push(RemoteReviewThread {
+            id,
+            side: anchor.side(),
+            path: anchor.path,
+            line: Some(line),
+            is_resolved,
+            is_outdated: anchor.orphaned,
+            comments,
+        });
+    }
+    threads
+}
+
+/// General (unanchored) root comments, as review-level summaries.
+///
+/// Approvals and "needs work" votes carry no text, so — like Cloud — they
+/// surface through reviewers (see [`DcPullRequest::review_records`]) rather
+/// than here.
+pub fn review_summaries(activities: &[DcActivity], pr_url: &str) -> Vec<RemoteReviewSummary> {
+    activities
+        .iter()
+        .filter(|activity| activity.is_root_comment() && activity.comment_anchor.is_none())
+        .filter_map(|activity| activity.comment.as_ref())
+        .filter(|comment| !comment.text.is_empty())
+        .map(|comment| RemoteReviewSummary {
+            id: comment.id.to_string(),
+            author: comment.author.label(),
+            body: comment.text.clone(),
+            state: RemoteReviewState::Commented,
+            created_at: millis(comment.created_date),
+            url: comment_url(pr_url, comment.id),
+        })
+        .collect()
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DcPath {
+    #[serde(default, rename = "toString")]
+    pub to_string: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DcDiffLine {
+    #[serde(default)]
+    pub line: String,
+}
+
+/// A run of lines of one kind: `CONTEXT`, `ADDED`, or `REMOVED`.
+#[derive(Debug, Deserialize)]
+pub struct DcSegment {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub lines: Vec<DcDiffLine>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DcHunk {
+    #[serde(default)]
+    pub source_line: u32,
+    #[serde(default)]
+    pub source_span: u32,
+    #[serde(default)]
+    pub destination_line: u32,
+    #[serde(default)]
+    pub destination_span: u32,
+    #[serde(default)]
+    pub segments: Vec<DcSegment>,
+}
+
+/// One file of a diff response. `source` is absent for added files and
+/// `destination` for deleted ones.
+#[derive(Debug, Deserialize)]
+pub struct DcDiff {
+    #[serde(default)]
+    pub source: Option<DcPath>,
+    #[serde(default)]
+    pub destination: Option<DcPath>,
+    #[serde(default)]
+    pub binary: bool,
+    #[serde(default)]
+    pub hunks: Vec<DcHunk>,
+}
+
+/// `{ "diffs": [...] }` — the body of every diff endpoint.
+#[derive(Debug, Deserialize)]
+pub struct DcDiffResponse {
+    #[serde(default)]
+    pub diffs: Vec<DcDiff>,
+}
+
+impl DcDiff {
+    pub fn into_file_patch(self) -> FilePatch {
+        let old = self
+            .source
+            .map(|path| path.to_string)
+            .filter(|path| !path.is_empty())
+            .map(std::path::PathBuf::from);
+        let new = self
+            .destination
+            .map(|path| path.to_string)
+            .filter(|path| !path.is_empty())
+            .map(std::path::PathBuf::from);
+        let status = match (&old, &new) {
+            (None, Some(_)) => FileStatus::Added,
+            (Some(_), None) => FileStatus::Deleted,
+            (Some(old), Some(new)) if old != new => FileStatus::Renamed,
+            _ => FileStatus::Modified,
+        };
+        let mut text = String::new();
+        for hunk in &self.hunks {
+            text.push_str(&format!(
+                "@@ -{},{} +{},{} @@\n",
+                hunk.source_line, hunk.source_span, hunk.destination_line, hunk.destination_span
+            ));
+            for segment in &hunk.segments {
+                let prefix = match segment.kind.as_str() {
+                    "ADDED" => '+',
+                    "REMOVED" => '-',
+                    _ => ' ',
+                };
+                for line in &segment.lines {
+                    text.push(prefix);
+                    text.push_str(&line.line);
+                    text.push('\n');
+                }
+            }
+        }
+        let mut patch = FilePatch::new(old, new, status, text);
+        patch.is_binary = self.binary;
+        patch
+    }
+}
+
+fn normalize_state(state: &str) -> String {
+    match state.to_ascii_uppercase().as_str() {
+        // Data Center declines rather than closes; report CLOSED so the
+        // shared read-only handling treats it like the other forges.
+        "DECLINED" => "CLOSED".to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Trimmed from a `GET .../pull-requests/42` response.
+    const PR_JSON: &str = r#"{
+      "id": 42,
+      "title": "Cache build outputs",
+      "description": "Speeds up CI.",
+      "state": "OPEN",
+      "draft": false,
+      "updatedDate": 1767225600000,
+      "author": { "user": { "name": "alice", "displayName": "Alice", "slug": "alice" }, "status": "UNAPPROVED" },
+      "reviewers": [
+        { "user": { "name": "bob", "displayName": "Bob", "slug": "bob" }, "status": "NEEDS_WORK",
+          "lastReviewedCommit": "aaa111" },
+        { "user": { "name": "Carol.Smith", "displayName": "Carol", "slug": "carol.smith" }, "status": "UNAPPROVED" }
+      ],
+      "fromRef": { "id": "refs/heads/cache", "displayId": "cache", "latestCommit": "head222" },
+      "toRef": { "id": "refs/heads/main", "displayId": "main", "latestCommit": "base000" },
+      "links": { "self": [ { "href": "https://bitbucket.example.com/projects/PLAT/repos/build/pull-requests/42" } ] }
+    }"#;
+
+    fn repo() -> ForgeRepository {
+        ForgeRepository::bitbucket_data_center("bitbucket.example.com", "PLAT", "build")
+    }
+
+    fn pr() -> DcPullRequest {
+        serde_json::from_str(PR_JSON).unwrap()
+    }
+
+    #[test]
+    fn should_build_details_from_pull_request_payload() {
+        let details = pr().into_details(&repo());
+        assert_eq!(details.number, 42);
+        assert_eq!(details.head_sha, "head222");
+        assert_eq!(details.base_sha, "base000");
+        assert_eq!(details.head_ref_name, "cache");
+        assert_eq!(details.author.as_deref(), Some("alice"));
+        assert_eq!(
+            details.url,
+            "https://bitbucket.example.com/projects/PLAT/repos/build/pull-requests/42"
+        );
+        assert_eq!(
+            details.updated_at.unwrap().to_rfc3339(),
+            "2026-01-01T00:00:00+00:00"
+        );
+        assert!(!details.closed);
+    }
+
+    #[test]
+    fn should_normalize_declined_state_to_closed() {
+        let json = PR_JSON.replace("\"OPEN\"", "\"DECLINED\"");
+        let pr: DcPullRequest = serde_json::from_str(&json).unwrap();
+        let details = pr.into_details(&repo());
+        assert_eq!(details.state, "CLOSED");
+        assert!(details.closed);
+        assert!(details.merged_at.is_none());
+    }
+
+    #[test]
+    fn should_match_reviewers_case_insensitively() {
+        let pr = pr();
+        assert!(pr.requests_review_from("carol.smith"));
+        assert!(pr.requests_review_from("bob"));
+        assert!(!pr.requests_review_from("alice"));
+        assert_eq!(
+            pr.participant_slug("CAROL.SMITH").as_deref(),
+            Some("carol.smith")
+        );
+    }
+
+    #[test]
+    fn should_record_last_reviewed_commit_for_voting_reviewers() {
+        let records = pr().review_records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].author.as_deref(), Some("bob"));
+        assert_eq!(records[0].commit_oid.as_deref(), Some("aaa111"));
+    }
+
+    #[test]
+    fn should_convert_commit_with_millisecond_timestamp() {
+        let commit: DcCommit = serde_json::from_str(
+            r#"{ "id": "0123456789abcdef", "displayId": "0123456789a",
+                 "message": "Cache outputs\n\nDetails.",
+                 "author": { "name": "alice", "emailAddress": "a@example.com", "displayName": "Alice" },
+                 "authorTimestamp": 1767225600000 }"#,
+        )
+        .unwrap();
+        let commit = commit.into_pull_request_commit();
+        assert_eq!(commit.short_oid, "0123456");
+        assert_eq!(commit.summary, "Cache outputs");
+        assert_eq!(commit.author, "Alice");
+        assert!(commit.timestamp.is_some());
+    }
+
+    /// Trimmed from a `GET .../pull-requests/42/activities` response.
+    const ACTIVITIES_JSON: &str = r#"{
+      "isLastPage": true,
+      "values": [
+        { "action": "COMMENTED", "commentAction": "REPLIED",
+          "comment": { "id": 11, "text": "fixed", "author": { "name": "alice" } } },
+        { "action": "COMMENTED", "commentAction": "ADDED",
+          "comment": { "id": 10, "version": 2, "text": "Why clone here?", "author": { "name": "bob" },
+                       "createdDate": 1767225600000, "threadResolved": true,
+                       "comments": [ { "id": 11, "text": "fixed", "author": { "name": "alice" },
+                                       "comments": [ { "id": 12, "text": "thanks", "author": { "name": "bob" } } ] } ] },
+          "commentAnchor": { "path": "src/cache.rs", "line": 7, "lineType": "REMOVED", "fileType": "FROM" } },
+        { "action": "COMMENTED", "commentAction": "ADDED",
+          "comment": { "id": 20, "text": "Old spot", "author": { "name": "bob" } },
+          "commentAnchor": { "path": "src/cache.rs", "line": 3, "lineType": "ADDED", "fileType": "TO", "orphaned": true } },
+        { "action": "COMMENTED", "commentAction": "ADDED",
+          "comment": { "id": 30, "text": "Whole file looks odd", "author": { "name": "bob" } },
+          "commentAnchor": { "path": "src/cache.rs", "fileType": "TO" } },
+        { "action": "COMMENTED", "commentAction": "ADDED",
+          "comment": { "id": 40, "text": "Overall LGTM once fixed", "author": { "name": "carol" } } },
+        { "action": "APPROVED", "user": { "name": "carol" } }
+      ]
+    }"#;
+
+    fn activities() -> Vec<DcActivity> {
+        serde_json::from_str::<DcPaged<DcActivity>>(ACTIVITIES_JSON)
+            .unwrap()
+            .values
+    }
+
+    const PR_URL: &str = "https://bitbucket.example.com/projects/PLAT/repos/build/pull-requests/42";
+
+    #[test]
+    fn should_group_nested_replies_into_line_threads() {
+        let threads = group_into_review_threads(activities(), PR_URL);
+        assert_eq!(threads.len(), 2);
+
+        let first = &threads[0];
+        assert_eq!(first.id, "10");
+        assert_eq!(first.path, "src/cache.rs");
+        assert_eq!(first.line, Some(7));
+        assert_eq!(first.side, RemoteCommentSide::Left);
+        assert!(first.is_resolved);
+        assert!(!first.is_outdated);
+        let bodies: Vec<&str> = first.comments.iter().map(|c| c.body.as_str()).collect();
+        assert_eq!(bodies, vec!["Why clone here?", "fixed", "thanks"]);
+        assert_eq!(first.comments[2].in_reply_to.as_deref(), Some("11"));
+        assert_eq!(
+            first.comments[0].url,
+            format!("{PR_URL}/overview?commentId=10")
+        );
+
+        assert!(threads[1].is_outdated);
+        assert_eq!(threads[1].side, RemoteCommentSide::Right);
+    }
+
+    #[test]
+    fn should_surface_general_comments_as_summaries() {
+        let summaries = review_summaries(&activities(), PR_URL);
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].body, "Overall LGTM once fixed");
+        assert_eq!(summaries[0].author.as_deref(), Some("carol"));
+        assert_eq!(summaries[0].state, RemoteReviewState::Commented);
+    }
+
+    #[test]
+    fn should_rebuild_unified_hunks_from_diff_segments() {
+        let response: DcDiffResponse = serde_json::from_str(
+            r##"{ "diffs": [
+              { "source": { "toString": "src/cache.rs" }, "destination": { "toString": "src/cache.rs" },
+                "hunks": [ { "sourceLine": 5, "sourceSpan": 3, "destinationLine": 5, "destinationSpan": 3,
+                  "segments": [
+                    { "type": "CONTEXT", "lines": [ { "source": 5, "destination": 5, "line": "fn get() {" } ] },
+                    { "type": "REMOVED", "lines": [ { "source": 6, "destination": 6, "line": "    a.clone()" } ] },
+                    { "type": "ADDED", "lines": [ { "source": 7, "destination": 6, "line": "    a" } ] },
+                    { "type": "CONTEXT", "lines": [ { "source": 7, "destination": 7, "line": "}" } ] } ] } ] },
+              { "source": null, "destination": { "toString": "docs/CACHE.md" },
+                "hunks": [ { "sourceLine": 0, "sourceSpan": 0, "destinationLine": 1, "destinationSpan": 1,
+                  "segments": [ { "type": "ADDED", "lines": [ { "line": "# Cache" } ] } ] } ] },
+              { "source": { "toString": "old.rs" }, "destination": { "toString": "new.rs" }, "hunks": [] }
+            ] }"##,
+        )
+        .unwrap();
+        let patches: Vec<FilePatch> = response
+            .diffs
+            .into_iter()
+            .map(DcDiff::into_file_patch)
+            .collect();
+
+        assert_eq!(patches[0].status, FileStatus::Modified);
+        assert_eq!(
+            patches[0].patch,
+            "@@ -5,3 +5,3 @@\n fn get() {\n-    a.clone()\n+    a\n }\n"
+        );
+        assert_eq!(patches[1].status, FileStatus::Added);
+        assert_eq!(patches[1].old_path, None);
+        assert_eq!(patches[1].new_path, Some(PathBuf::from("docs/CACHE.md")));
+        assert_eq!(patches[1].patch, "@@ -0,0 +1,1 @@\n+# Cache\n");
+        assert_eq!(patches[2].status, FileStatus::Renamed);
+    }
+}
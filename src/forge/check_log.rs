@@ -0,0 +1,175 @@
+//! CI job logs shown by the check-log view.
+//!
+//! Backends hand logs back as the forge serves them. `gh run view
+//! --log-failed` prefixes every line with the job name, step name and a
+//! timestamp (`build\tRun tests\t2026-01-01T00:00:00.0000000Z ...`), and the
+//! raw Actions job log keeps the timestamp alone. GitLab traces carry ANSI
+//! colors and `section_start:`/`section_end:` markers joined to the text with
+//! `\r`. [`clean_log`] reduces both to what the job printed.
+
+use crate::forge::traits::PullRequestCheckStatus;
+
+/// A `path:line` mention in a log line, e.g. from a compiler error or a
+/// failing test's backtrace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileReference {
+    pub path: String,
+    pub line: u32,
+}
+
+/// Whether `check` finished unsuccessfully and so has a log worth reading.
+pub fn is_failing(check: &PullRequestCheckStatus) -> bool {
+    matches!(
+        check.conclusion.as_deref(),
+        Some("FAILURE" | "ERROR" | "TIMED_OUT" | "ACTION_REQUIRED" | "CANCELLED")
+    )
+}
+
+/// Split `raw` into display lines with forge decoration removed.
+pub fn clean_log(raw: &str) -> Vec<String> {
+    raw.trim_start_matches('\u{feff}')
+        .lines()
+        .filter_map(|line| {
+            // A `\r` inside a line overwrites what came before it on a
+            // terminal; keep what would be left on screen.
+            let visible = line.rsplit('\r').next().unwrap_or(line);
+            let text = strip_ansi(strip_prefix_columns(visible));
+            if text.is_empty() && line.starts_with("section_") {
+                return None;
+            }
+            Some(text)
+        })
+        .collect()
+}
+
+/// Every `path:line` (or `path:line:column`) token in `line`. Only tokens
+/// whose path looks like a file are kept; callers still have to match the
+/// path against the diff.
+pub fn file_references(line: &str) -> Vec<FileReference> {
+    line.split(|c: char| {
+        c.is_whitespace() || matches!(c, '(' | ')' | '[' | ']' | '"' | '\'' | ',' | '<' | '>')
+    })
+    .filter_map(parse_reference)
+    .collect()
+}
+
+fn parse_reference(token: &str) -> Option<FileReference> {
+    let (path, rest) = token.split_once(':')?;
+    let digits = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or(rest, |end| &rest[..end]);
+    let line = digits.parse::<u32>().ok().filter(|line| *line > 0)?;
+    let path = path.trim_start_matches("./");
+    let looks_like_file = path.contains('/') || path.contains('.');
+    (looks_like_file && !path.is_empty()).then(|| FileReference {
+        path: path.to_string(),
+        line,
+    })
+}
+
+/// Drop the `job\tstep\t` columns of `gh run view --log-failed` and the
+/// timestamp Actions puts at the start of every line.
+fn strip_prefix_columns(line: &str) -> &str {
+    if let Some(stripped) = strip_timestamp(line) {
+        return stripped;
+    }
+    let mut columns = line.splitn(3, '\t');
+    match (columns.next(), columns.next(), columns.next()) {
+        (Some(_), Some(_), Some(rest)) => strip_timestamp(rest).unwrap_or(line),
+        _ => line,
+    }
+}
+
+fn strip_timestamp(line: &str) -> Option<&str> {
+    let (stamp, rest) = line.split_once(' ').unwrap_or((line, ""));
+    let bytes = stamp.as_bytes();
+    let is_timestamp = bytes.len() >= 20
+        && bytes[..4].iter().all(u8::is_ascii_digit)
+        && bytes[4] == b'-'
+        && bytes[7] == b'-'
+        && bytes[10] == b'T'
+        && stamp.ends_with('Z');
+    is_timestamp.then_some(rest)
+}
+
+fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            out.push(c);
+            continue;
+        }
+        // CSI sequences run to a final byte in `@`..=`~`; anything else is a
+        // two-character escape.
+        if chars.next() == Some('[') {
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_strip_run_view_columns_and_timestamps() {
+        // given
+        let raw = "\u{feff}build\tRun tests\t2026-01-01T10:00:00.1234567Z \u{1b}[31merror\u{1b}[0m: boom\n\
+                   2026-01-01T10:00:01.0000000Z plain job log line\n\
+                   no prefix at all";
+
+        // when
+        let lines = clean_log(raw);
+
+        // then
+        assert_eq!(
+            lines,
+            vec!["error: boom", "plain job log line", "no prefix at all"]
+        );
+    }
+
+    #[test]
+    fn should_drop_gitlab_section_markers() {
+        // given
+        let raw = "section_start:1700000000:step_script\r\u{1b}[0K\u{1b}[36;1mExecuting step\u{1b}[0;m\n\
+                   $ cargo test\n\
+                   section_end:1700000001:step_script\r\u{1b}[0K\n";
+
+        // when
+        let lines = clean_log(raw);
+
+        // then
+        assert_eq!(lines, vec!["Executing step", "$ cargo test"]);
+    }
+
+    #[test]
+    fn should_find_path_and_line_references() {
+        // given
+        let line = "error[E0308]: mismatched types --> src/app/mod.rs:42:7 (see ./README.md:3)";
+
+        // when
+        let refs = file_references(line);
+
+        // then
+        assert_eq!(
+            refs,
+            vec![
+                FileReference {
+                    path: "src/app/mod.rs".to_string(),
+                    line: 42,
+                },
+                FileReference {
+                    path: "README.md".to_string(),
+                    line: 3,
+                },
+            ]
+        );
+        assert!(file_references("error: 42 tests failed at 10:30").is_empty());
+    }
+}
@@ -0,0 +1,1378 @@
+//! Gerrit backend: `ForgeBackend` over the Gerrit REST API.
+//!
+//! Transport is pluggable ([`GerritHttp`]) so tests can stand in a fixture
+//! server; production uses [`PasswordHttp`], which calls the API directly over
+//! HTTPS. Auth is the HTTP password from the account's settings page, sent as
+//! HTTP Basic auth on the authenticated `/a/` endpoints: a `[forge.tokens]`
+//! entry (`"<host>" = "<username>:<password>"`), or `GERRIT_USERNAME` +
+//! `GERRIT_HTTP_PASSWORD` for a host listed in `GERRIT_HOSTS`. Credentials
+//! only ever go to the host they are configured for; anything else is
+//! requested anonymously, which is enough to read public changes.
+//!
+//! A change is reviewed like a PR with one commit per patch set: the full
+//! diff is the current patch set against its parent (`/patch`), and selecting
+//! a patch-set subrange shows the diff between patch sets, rebuilt from
+//! Gerrit's per-file diff JSON. Every JSON response carries the `)]}'` XSSI
+//! guard line, which [`strip_xssi_prefix`] removes before parsing.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde_json::json;
+
+use crate::config::ForgeConfig;
+use crate::error::{Result, TuicrError};
+use crate::forge::remote_comments::{RemoteReviewSummary, RemoteReviewThread};
+use crate::forge::self_hosted::{
+    HostAuth, configured_token, env_token_for_listed_host, host_listed, local_range_diff,
+    parse_scp_like_remote, process_env, read_blob_with_repo, split_url, strip_git_suffix,
+    trim_url_suffix,
+};
+use crate::forge::submit::{GhSide, SubmitEvent};
+use crate::forge::traits::{
+    CreateReviewRequest, ForgeBackend, ForgeFileLinesRequest, ForgeRepository,
+    GhCreateReviewResponse, PagedPullRequests, PullRequestCommit, PullRequestDetails,
+    PullRequestListQuery, PullRequestListScope, PullRequestTarget,
+};
+use crate::model::{DiffLine, FilePatch};
+use crate::vcs::patch::split_patch;
+use crate::vcs::slice_context_lines;
+
+use super::models::{
+    CODE_REVIEW_LABEL, GrChange, GrChangeMessage, GrComment, GrDiffInfo, GrFileInfo, change_url,
+    flatten_comments, group_into_review_threads, is_magic_path, patch_set_from_ref,
+};
+
+/// Public instances recognized without any configuration.
+const KNOWN_GERRIT_HOSTS: &[&str] = &[
+    "review.opendev.org",
+    "review.gerrithub.io",
+    "gerrit-review.googlesource.com",
+];
+/// Gerrit's SSH port. A remote on it is Gerrit whatever the hostname.
+const GERRIT_SSH_PORT: &str = "29418";
+/// Comma-separated extra hosts to treat as Gerrit (custom domains).
+const HOSTS_ENV_VAR: &str = "GERRIT_HOSTS";
+const USERNAME_ENV_VAR: &str = "GERRIT_USERNAME";
+/// Env vars checked (in order) for the account's HTTP password.
+const PASSWORD_ENV_VARS: &[&str] = &["GERRIT_HTTP_PASSWORD", "GERRIT_PASSWORD"];
+/// Anti-XSSI line Gerrit prepends to every JSON response.
+const XSSI_PREFIX: &str = ")]}'";
+/// Options for a change lookup that needs only the current patch set.
+const CURRENT_CHANGE_OPTIONS: &str = "o=CURRENT_REVISION&o=CURRENT_COMMIT&o=DETAILED_ACCOUNTS";
+/// Options for a change lookup that walks every patch set.
+const ALL_PATCH_SETS_OPTIONS: &str = "o=ALL_REVISIONS&o=ALL_COMMITS&o=DETAILED_ACCOUNTS";
+/// Context lines requested for rebuilt patch-set diffs.
+const DIFF_CONTEXT: u32 = 3;
+
+// ---------- Transport ----------
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GerritHttpError {
+    /// 401/403 — missing or wrong HTTP password, or no permission.
+    Auth(String),
+    /// Any other non-2xx status or transport error.
+    Failed { status: Option<u16>, body: String },
+}
+
+pub type GerritHttpResult<T> = std::result::Result<T, GerritHttpError>;
+
+/// HTTP transport for the Gerrit REST API. `url` is absolute. Returns the raw
+/// 2xx response body, XSSI guard included.
+pub trait GerritHttp: Send + Sync {
+    fn request(&self, method: &str, url: &str, body: Option<&str>) -> GerritHttpResult<String>;
+}
+
+/// An account's username and HTTP password.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GerritCredentials {
+    pub username: String,
+    pub password: String,
+}
+
+/// Direct REST transport. Sends HTTP Basic auth to the host the credentials
+/// were configured for, and nowhere else.
+pub struct PasswordHttp {
+    auth: Option<HostAuth>,
+    agent: ureq::Agent,
+}
+
+impl PasswordHttp {
+    pub fn new(host: &str, credentials: Option<GerritCredentials>) -> Self {
+        let config = ureq::Agent::config_builder()
+            .timeout_global(Some(Duration::from_secs(30)))
+            .http_status_as_error(false)
+            .build();
+        Self {
+            auth: credentials.map(|c| {
+                let token = BASE64.encode(format!("{}:{}", c.username, c.password));
+                HostAuth::new(host, format!("Basic {token}"))
+            }),
+            agent: config.into(),
+        }
+    }
+}
+
+impl GerritHttp for PasswordHttp {
+    fn request(&self, method: &str, url: &str, body: Option<&str>) -> GerritHttpResult<String> {
+        let auth = self.auth.as_ref().and_then(|auth| auth.header_for(url));
+        let result = match method.to_ascii_uppercase().as_str() {
+            "GET" => {
+                let mut request = self.agent.get(url);
+                if let Some(auth) = auth {
+                    request = request.header("Authorization", auth);
+                }
+                request.call()
+            }
+            verb @ ("POST" | "PUT") => {
+                let mut request = if verb == "POST" {
+                    self.agent.post(url)
+                } else {
+                    self.agent.put(url)
+                }
+                .header("Content-Type", "application/json");
+                if let Some(auth) = auth {
+                    request = request.header("Authorization", auth);
+                }
+                request.send(body.unwrap_or(""))
+            }
+            other => {
+                return Err(GerritHttpError::Failed {
+                    status: None,
+                    body: format!("unsupported HTTP method {other}"),
+                });
+            }
+        };
+
+        let response = result.map_err(|err| GerritHttpError::Failed {
+            status: None,
+            body: err.to_string(),
+        })?;
+        let status = response.status().as_u16();
+        let text =
+            response
+                .into_body()
+                .read_to_string()
+                .map_err(|err| GerritHttpError::Failed {
+                    status: Some(status),
+                    body: err.to_string(),
+                })?;
+
+        if (200..300).contains(&status) {
+            Ok(text)
+        } else if status == 401 || status == 403 {
+            Err(GerritHttpError::Auth(text))
+        } else {
+            Err(GerritHttpError::Failed {
+                status: Some(status),
+                body: text,
+            })
+        }
+    }
+}
+
+/// The HTTP credentials configured for `host`: its `[forge.tokens]` entry
+/// (`username:password`), else the environment's when `GERRIT_HOSTS` lists
+/// the host.
+fn credentials_for_host(
+    forge: &ForgeConfig,
+    host: &str,
+    env: &dyn Fn(&str) -> Option<String>,
+) -> Option<GerritCredentials> {
+    if let Some((username, password)) = configured_token(forge, host)
+        .and_then(|token| token.split_once(':'))
+        .filter(|(username, password)| !username.is_empty() && !password.is_empty())
+    {
+        return Some(GerritCredentials {
+            username: username.to_string(),
+            password: password.to_string(),
+        });
+    }
+    let username = env_token_for_listed_host(env, &[USERNAME_ENV_VAR], HOSTS_ENV_VAR, host)?;
+    let password = env_token_for_listed_host(env, PASSWORD_ENV_VARS, HOSTS_ENV_VAR, host)?;
+    Some(GerritCredentials { username, password })
+}
+
+/// Drop the `)]}'` guard line from a JSON response body.
+pub fn strip_xssi_prefix(body: &str) -> &str {
+    body.trim_start()
+        .strip_prefix(XSSI_PREFIX)
+        .unwrap_or(body)
+        .trim_start()
+}
+
+// ---------- Coordinate helpers ----------
+
+/// Percent-encode one URL path segment or query value. Gerrit needs `/`
+/// inside project names and file paths encoded as `%2F`.
+fn encode_component(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                out.push(byte as char)
+            }
+            other => out.push_str(&format!("%{other:02X}")),
+        }
+    }
+    out
+}
+
+/// Decode a base64 body (file content, patches), tolerating line breaks.
+fn decode_base64_body(body: &str) -> Result<String> {
+    let compact: String = body.split_whitespace().collect();
+    let bytes = BASE64
+        .decode(compact)
+        .map_err(|err| TuicrError::Forge(format!("Gerrit returned invalid base64: {err}")))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+// ---------- Backend ----------
+
+pub struct GerritBackend {
+    default_repository: Option<ForgeRepository>,
+    http: Box<dyn GerritHttp>,
+    /// Route calls for the default repository's host through the `/a/`
+    /// endpoints that require a login. Other hosts get no credentials.
+    authenticated: bool,
+    local_checkout: Option<PathBuf>,
+}
+
+impl GerritBackend {
+    /// Build a backend with a password transport for the repository's host.
+    pub fn new(default_repository: Option<ForgeRepository>, forge: &ForgeConfig) -> Self {
+        let host = default_repository
+            .as_ref()
+            .map(|repo| repo.host.clone())
+            .unwrap_or_default();
+        let credentials = credentials_for_host(forge, &host, &process_env);
+        Self {
+            default_repository,
+            authenticated: credentials.is_some(),
+            http: Box::new(PasswordHttp::new(&host, credentials)),
+            local_checkout: None,
+        }
+    }
+
+    /// Build a backend with an explicit transport (used in tests).
+    pub fn with_transport(
+        default_repository: Option<ForgeRepository>,
+        http: Box<dyn GerritHttp>,
+        authenticated: bool,
+    ) -> Self {
+        Self {
+            default_repository,
+            http,
+            authenticated,
+            local_checkout: None,
+        }
+    }
+
+    pub fn with_local_checkout(mut self, checkout: Option<PathBuf>) -> Self {
+        self.local_checkout = checkout;
+        self
+    }
+
+    fn resolve_repository(&self, target: &PullRequestTarget) -> Result<ForgeRepository> {
+        target
+            .repository
+            .clone()
+            .or_else(|| self.default_repository.clone())
+            .ok_or_else(|| {
+                TuicrError::Forge(format!(
+                    "Gerrit change target `{}` does not include a project",
+                    target.original
+                ))
+            })
+    }
+
+    fn api_base(&self, repo: &ForgeRepository) -> String {
+        let authenticated = self.authenticated
+            && self
+                .default_repository
+                .as_ref()
+                .is_some_and(|default| default.host == repo.host);
+        let prefix = if authenticated { "/a" } else { "" };
+        format!("{}{prefix}", repo.web_base())
+    }
+
+    /// `…/changes/<project>~<number>`: the project-qualified id is
+    /// unambiguous across projects, unlike the bare number.
+    fn change_base(&self, repo: &ForgeRepository, number: u64) -> String {
+        format!(
+            "{}/changes/{}~{number}",
+            self.api_base(repo),
+            encode_component(&repo.slug())
+        )
+    }
+
+    fn get(&self, repo: &ForgeRepository, url: &str) -> Result<String> {
+        self.http
+            .request("GET", url, None)
+            .map_err(|err| map_http_error(err, &repo.host))
+    }
+
+    fn get_json<T: serde::de::DeserializeOwned>(
+        &self,
+        repo: &ForgeRepository,
+        url: &str,
+    ) -> Result<T> {
+        let output = self.get(repo, url)?;
+        Ok(serde_json::from_str(strip_xssi_prefix(&output))?)
+    }
+
+    fn send(
+        &self,
+        repo: &ForgeRepository,
+        method: &str,
+        url: &str,
+        body: &serde_json::Value,
+    ) -> Result<String> {
+        let body = serde_json::to_string(body)?;
+        self.http
+            .request(method, url, Some(&body))
+            .map_err(|err| map_http_error(err, &repo.host))
+    }
+
+    fn get_change(&self, repo: &ForgeRepository, number: u64, options: &str) -> Result<GrChange> {
+        let url = format!("{}?{options}", self.change_base(repo, number));
+        self.get_json(repo, &url)
+    }
+
+    fn list_comments(&self, pr: &PullRequestDetails) -> Result<Vec<GrComment>> {
+        let url = format!("{}/comments", self.change_base(&pr.repository, pr.number));
+        let by_path: HashMap<String, Vec<GrComment>> = self.get_json(&pr.repository, &url)?;
+        Ok(flatten_comments(by_path))
+    }
+
+    fn fetch_file_via_api(&self, request: &ForgeFileLinesRequest) -> Result<String> {
+        let path = request.path.to_string_lossy().replace('\\', "/");
+        let url = format!(
+            "{}/projects/{}/commits/{}/files/{}/content",
+            self.api_base(&request.repository),
+            encode_component(&request.repository.slug()),
+            request.sha(),
+            encode_component(&path),
+        );
+        decode_base64_body(&self.get(&request.repository, &url)?)
+    }
+
+    /// File content at the request's revision: local blob first, REST fallback.
+    fn file_content(&self, request: &ForgeFileLinesRequest) -> Result<String> {
+        let local = self
+            .local_checkout
+            .as_deref()
+            .and_then(|root| read_blob_with_repo(root, request.sha(), request.path.as_path()));
+        match local {
+            Some(content) => Ok(content),
+            None => self.fetch_file_via_api(request),
+        }
+    }
+
+    /// A revision's diff against its parent, from the `format-patch` output
+    /// Gerrit serves base64-encoded.
+    fn revision_patch(&self, pr: &PullRequestDetails, revision: &str) -> Result<Vec<FilePatch>> {
+        let url = format!(
+            "{}/revisions/{revision}/patch",
+            self.change_base(&pr.repository, pr.number)
+        );
+        let patch = decode_base64_body(&self.get(&pr.repository, &url)?)?;
+        Ok(split_patch(&patch))
+    }
+
+    /// The diff between two patch sets, rebuilt file by file from Gerrit's
+    /// diff JSON (there is no unified interdiff endpoint).
+    fn patch_set_diff(
+        &self,
+        pr: &PullRequestDetails,
+        base_patch_set: u32,
+        revision: &str,
+    ) -> Result<Vec<FilePatch>> {
+        let revision_base = format!(
+            "{}/revisions/{revision}",
+            self.change_base(&pr.repository, pr.number)
+        );
+        let files_url = format!("{revision_base}/files?base={base_patch_set}");
+        let files: HashMap<String, GrFileInfo> = self.get_json(&pr.repository, &files_url)?;
+        let mut paths: Vec<(String, GrFileInfo)> = files
+            .into_iter()
+            .filter(|(path, _)| !is_magic_path(path))
+            .collect();
+        paths.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut patches = Vec::with_capacity(paths.len());
+        for (path, info) in paths {
+            if info.binary {
+                let (old, new, status) = info.paths(&path);
+                let mut patch = FilePatch::new(old, new, status, String::new());
+                patch.is_binary = true;
+                patches.push(patch);
+                continue;
+            }
+            let diff_url = format!(
+                "{revision_base}/files/{}/diff?base={base_patch_set}&context={DIFF_CONTEXT}&intraline=false",
+                encode_component(&path)
+            );
+            let diff: GrDiffInfo = self.get_json(&pr.repository, &diff_url)?;
+            patches.push(diff.into_file_patch(&path, &info));
+        }
+        Ok(patches)
+    }
+
+    /// POST a review (`ReviewInput`) to `revision`.
+    fn post_review(
+        &self,
+        pr: &PullRequestDetails,
+        revision: &str,
+        review: &serde_json::Value,
+    ) -> Result<String> {
+        let url = format!(
+            "{}/revisions/{revision}/review",
+            self.change_base(&pr.repository, pr.number)
+        );
+        self.send(&pr.repository, "POST", &url, review)
+    }
+
+    /// Reply on a thread with `message`, setting the thread's resolved state.
+    /// The reply is posted on the patch set of the comment it answers, with
+    /// the same anchor, so Gerrit keeps it in that thread.
+    fn reply_in_thread(
+        &self,
+        pr: &PullRequestDetails,
+        thread: &RemoteReviewThread,
+        message: &str,
+        unresolved: bool,
+    ) -> Result<()> {
+        let last_id = thread
+            .comments
+            .last()
+            .map(|c| c.id.clone())
+            .unwrap_or_else(|| thread.id.clone());
+        let parent = self
+            .list_comments(pr)?
+            .into_iter()
+            .find(|c| c.id == last_id)
+            .ok_or_else(|| {
+                TuicrError::Forge(format!(
+                    "Gerrit comment {last_id} no longer exists on change {}",
+                    pr.number
+                ))
+            })?;
+
+        let mut reply = json!({
+            "in_reply_to": parent.id,
+            "message": message,
+            "unresolved": unresolved,
+        });
+        if let Some(line) = parent.line {
+            reply["line"] = json!(line);
+        }
+        if let Some(range) = parent.range {
+            reply["range"] = json!({
+                "start_line": range.start_line,
+                "start_character": range.start_character,
+                "end_line": range.end_line,
+                "end_character": range.end_character,
+            });
+        }
+        if let Some(side) = parent.side.as_deref() {
+            reply["side"] = json!(side);
+        }
+        let revision = parent
+            .commit_id
+            .clone()
+            .or_else(|| parent.patch_set.map(|ps| ps.to_string()))
+            .unwrap_or_else(|| pr.head_sha.clone());
+        let mut comments = serde_json::Map::new();
+        comments.insert(thread.path.clone(), json!([reply]));
+        self.post_review(pr, &revision, &json!({ "comments": comments }))?;
+        Ok(())
+    }
+}
+
+/// `CommentInput` for an inline comment. Gerrit ranges need character
+/// offsets that tuicr doesn't track, so a range comment lands on its last
+/// line.
+fn comment_input(comment: &crate::forge::submit::InlineComment) -> serde_json::Value {
+    let mut input = json!({
+        "line": comment.line,
+        "message": comment.body,
+        "unresolved": true,
+    });
+    if comment.side == GhSide::Left {
+        input["side"] = json!("PARENT");
+    }
+    input
+}
+
+fn comment_path(comment: &crate::forge::submit::InlineComment) -> String {
+    comment.path.to_string_lossy().replace('\\', "/")
+}
+
+impl ForgeBackend for GerritBackend {
+    fn list_pull_requests(&self, query: PullRequestListQuery) -> Result<PagedPullRequests> {
+        let page_size = query.page_size.max(1);
+        let mut terms = vec![
+            "status:open".to_string(),
+            format!("project:{}", query.repository.slug()),
+        ];
+        // `self` only resolves for a logged-in caller; anonymously the
+        // requested scope falls back to every open change.
+        if query.scope == PullRequestListScope::ReviewRequested && self.authenticated {
+            terms.push("reviewer:self".to_string());
+            terms.push("-owner:self".to_string());
+        }
+        let q = terms
+            .iter()
+            .map(|term| encode_component(term))
+            .collect::<Vec<_>>()
+            .join("+");
+        // Fetch one extra to detect a further page.
+        let url = format!(
+            "{}/changes/?q={q}&n={}&S={}&o=CURRENT_REVISION&o=DETAILED_ACCOUNTS",
+            self.api_base(&query.repository),
+            page_size + 1,
+            query.already_loaded,
+        );
+        let changes: Vec<GrChange> = self.get_json(&query.repository, &url)?;
+        let has_more = changes.len() > page_size;
+        let pull_requests = changes
+            .into_iter()
+            .take(page_size)
+            .map(|change| change.into_summary(&query.repository))
+            .collect::<Vec<_>>();
+        let total_loaded = query.already_loaded + pull_requests.len();
+        Ok(PagedPullRequests {
+            pull_requests,
+            has_more,
+            total_loaded,
+        })
+    }
+
+    fn get_pull_request(&self, target: PullRequestTarget) -> Result<PullRequestDetails> {
+        let repository = self.resolve_repository(&target)?;
+        let change = self.get_change(&repository, target.number, CURRENT_CHANGE_OPTIONS)?;
+        Ok(change.into_details(&repository))
+    }
+
+    fn get_pull_request_diff(&self, pr: &PullRequestDetails) -> Result<Vec<FilePatch>> {
+        self.revision_patch(pr, &pr.head_sha)
+    }
+
+    fn get_pull_request_commit_range_diff(
+        &self,
+        pr: &PullRequestDetails,
+        start_sha: &str,
+        end_sha: &str,
+    ) -> Result<Vec<FilePatch>> {
+        if let Some(patches) = self
+            .local_checkout
+            .as_deref()
+            .and_then(|root| local_range_diff(root, start_sha, end_sha))
+        {
+            return Ok(patches);
+        }
+        // `start_sha` is an earlier patch set, or the change's parent when
+        // the selection reaches back to patch set 1.
+        let change = self.get_change(&pr.repository, pr.number, ALL_PATCH_SETS_OPTIONS)?;
+        match change.patch_set_of(start_sha) {
+            Some(base_patch_set) => self.patch_set_diff(pr, base_patch_set, end_sha),
+            None => self.revision_patch(pr, end_sha),
+        }
+    }
+
+    fn local_checkout_path(&self) -> Option<PathBuf> {
+        self.local_checkout.clone()
+    }
+
+    fn fetch_file_lines(&self, request: ForgeFileLinesRequest) -> Result<Vec<DiffLine>> {
+        if request.start_line == 0 || request.start_line > request.end_line {
+            return Ok(Vec::new());
+        }
+        let content = self.file_content(&request)?;
+        Ok(slice_context_lines(
+            &content,
+            request.start_line,
+            request.end_line,
+        ))
+    }
+
+    fn file_line_count(&self, request: ForgeFileLinesRequest) -> Result<u32> {
+        let content = self.file_content(&request)?;
+        Ok(content.lines().count() as u32)
+    }
+
+    fn list_review_threads(&self, pr: &PullRequestDetails) -> Result<Vec<RemoteReviewThread>> {
+        let comments = self.list_comments(pr)?;
+        Ok(group_into_review_threads(
+            comments,
+            patch_set_from_ref(&pr.head_ref_name),
+            &change_url(&pr.repository, pr.number),
+        ))
+    }
+
+    fn list_review_summaries(&self, pr: &PullRequestDetails) -> Result<Vec<RemoteReviewSummary>> {
+        let url = format!("{}/messages", self.change_base(&pr.repository, pr.number));
+        let messages: Vec<GrChangeMessage> = self.get_json(&pr.repository, &url)?;
+        let link = change_url(&pr.repository, pr.number);
+        Ok(messages
+            .into_iter()
+            .filter_map(|message| message.into_review_summary(&link))
+            .collect())
+    }
+
+    fn list_pull_request_commits(&self, pr: &PullRequestDetails) -> Result<Vec<PullRequestCommit>> {
+        let change = self.get_change(&pr.repository, pr.number, ALL_PATCH_SETS_OPTIONS)?;
+        Ok(change.into_patch_sets())
+    }
+
+    fn create_review(
+        &self,
+        pr: &PullRequestDetails,
+        request: CreateReviewRequest<'_>,
+    ) -> Result<GhCreateReviewResponse> {
+        let revision = if request.commit_id.is_empty() {
+            pr.head_sha.as_str()
+        } else {
+            request.commit_id
+        };
+
+        // Gerrit's drafts are per comment: stage each one (the body as a
+        // patch-set-level comment) for the author to publish from the web UI.
+        if request.event == SubmitEvent::Draft {
+            let url = format!(
+                "{}/revisions/{revision}/drafts",
+                self.change_base(&pr.repository, pr.number)
+            );
+            if !request.body.is_empty() {
+                let draft = json!({
+                    "path": "/PATCHSET_LEVEL",
+                    "message": request.body,
+                    "unresolved": false,
+                });
+                self.send(&pr.repository, "PUT", &url, &draft)?;
+            }
+            for comment in request.comments {
+                let mut draft = comment_input(comment);
+                draft["path"] = json!(comment_path(comment));
+                self.send(&pr.repository, "PUT", &url, &draft)?;
+            }
+            return Ok(GhCreateReviewResponse {
+                id: 0,
+                html_url: pr.url.clone(),
+                state: "PENDING".to_string(),
+            });
+        }
+
+        // Approve casts the strongest Code-Review vote the reviewer holds
+        // (+2 for maintainers, +1 otherwise); request changes is a -1.
+        let vote = match request.event {
+            SubmitEvent::Approve => {
+                let change = self.get_change(&pr.repository, pr.number, "o=DETAILED_LABELS")?;
+                let max = change.max_permitted_vote(CODE_REVIEW_LABEL).unwrap_or(1);
+                if max <= 0 {
+                    return Err(TuicrError::Forge(format!(
+                        "You may not vote {CODE_REVIEW_LABEL}+1 on change {}",
+                        pr.number
+                    )));
+                }
+                Some(max)
+            }
+            SubmitEvent::RequestChanges => Some(-1),
+            SubmitEvent::Comment | SubmitEvent::Draft => None,
+        };
+
+        let mut comments: serde_json::Map<String, serde_json::Value> = serde_json::Map::new();
+        for comment in request.comments {
+            let entry = comments
+                .entry(comment_path(comment))
+                .or_insert_with(|| json!([]));
+            if let Some(list) = entry.as_array_mut() {
+                list.push(comment_input(comment));
+            }
+        }
+        let mut review = json!({ "comments": comments });
+        if !request.body.is_empty() {
+            review["message"] = json!(request.body);
+        }
+        if let Some(vote) = vote {
+            review["labels"] = json!({ CODE_REVIEW_LABEL: vote });
+        }
+        self.post_review(pr, revision, &review)?;
+
+        let state = match request.event {
+            SubmitEvent::Approve => "APPROVED",
+            SubmitEvent::RequestChanges => "CHANGES_REQUESTED",
+            SubmitEvent::Comment | SubmitEvent::Draft => "COMMENTED",
+        };
+        Ok(GhCreateReviewResponse {
+            id: 0,
+            html_url: pr.url.clone(),
+            state: state.to_string(),
+        })
+    }
+
+    fn reply_to_review_thread(
+        &self,
+        pr: &PullRequestDetails,
+        thread: &RemoteReviewThread,
+        body: &str,
+    ) -> Result<()> {
+        // A reply keeps the thread's state; resolving is a separate action.
+        self.reply_in_thread(pr, thread, body, !thread.is_resolved)
+    }
+
+    fn set_review_thread_resolved(
+        &self,
+        pr: &PullRequestDetails,
+        thread: &RemoteReviewThread,
+        resolved: bool,
+    ) -> Result<()> {
+        // Gerrit has no resolve toggle: a thread takes the state of its
+        // latest comment, so resolving means replying, as the web UI's
+        // "Done" button does.
+        let message = if resolved { "Done" } else { "Reopened" };
+        self.reply_in_thread(pr, thread, message, !resolved)
+    }
+}
+
+fn map_http_error(error: GerritHttpError, host: &str) -> TuicrError {
+    match error {
+        GerritHttpError::Auth(detail) => TuicrError::Forge(format!(
+            "{host} rejected the request. Add `\"{host}\" = \"<username>:<password>\"` under \
+             [forge.tokens], or list {host} in GERRIT_HOSTS and set GERRIT_USERNAME and \
+             GERRIT_HTTP_PASSWORD, using the HTTP credentials from your Gerrit settings page.\n{}",
+            trim_detail(&detail)
+        )),
+        GerritHttpError::Failed { status, body } => {
+            let status = status.map(|s| format!(" (HTTP {s})")).unwrap_or_default();
+            TuicrError::Forge(format!(
+                "Gerrit request to {host} failed{status}: {}",
+                trim_detail(&body)
+            ))
+        }
+    }
+}
+
+/// Keep error detail readable: collapse whitespace and cap the length.
+fn trim_detail(detail: &str) -> String {
+    let collapsed = detail.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.len() > 400 {
+        let cut = (0..=400)
+            .rev()
+            .find(|&i| collapsed.is_char_boundary(i))
+            .unwrap_or(0);
+        format!("{}…", &collapsed[..cut])
+    } else {
+        collapsed
+    }
+}
+
+// ---------- URL & target parsing ----------
+
+/// True when `host` names a Gerrit instance: a well-known public host, a
+/// `*-review.googlesource.com` host, a hostname that says so
+/// (`gerrit.example.com`), or a host listed in `GERRIT_HOSTS`.
+pub(crate) fn is_gerrit_host(host: &str) -> bool {
+    let host = host.to_ascii_lowercase();
+    if KNOWN_GERRIT_HOSTS.contains(&host.as_str())
+        || host.ends_with("-review.googlesource.com")
+        || host.contains("gerrit")
+    {
+        return true;
+    }
+    host_listed(HOSTS_ENV_VAR, &host)
+}
+
+/// Parse a Gerrit remote (git) URL into a `ForgeRepository`.
+///
+/// Accepts `ssh://user@host:29418/project`, `https://host[:port]/[a/]project[.git]`
+/// and `user@host:project.git`. Returns `None` unless the remote uses
+/// Gerrit's SSH port or [`is_gerrit_host`] recognizes the host, so it never
+/// claims GitHub or GitLab remotes. An HTTP(S) remote's scheme and port are
+/// kept as the repository's web root.
+pub fn parse_gerrit_remote_url(remote_url: &str) -> Option<ForgeRepository> {
+    let trimmed = trim_url_suffix(remote_url.trim());
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    if let Some((host, path)) = parse_scp_like_remote(trimmed) {
+        if !is_gerrit_host(host) {
+            return None;
+        }
+        return gerrit_from_path(host, path);
+    }
+
+    let parts = split_url(trimmed)?;
+    let on_gerrit_port = parts.port() == Some(GERRIT_SSH_PORT);
+    if !on_gerrit_port && !is_gerrit_host(parts.host()) {
+        return None;
+    }
+    Some(gerrit_from_path(parts.host(), parts.path)?.with_web_root(parts.web_root("")))
+}
+
+/// The project from the path after the host: a change URL's
+/// `c/<project>/+/…`, the authenticated `a/<project>`, or the bare project.
+fn gerrit_from_path(host: &str, path: &str) -> Option<ForgeRepository> {
+    let path = path.trim_matches('/');
+    let project = match path.strip_prefix("c/") {
+        Some(rest) if rest.contains("/+/") => rest.split("/+/").next()?,
+        _ => path.strip_prefix("a/").unwrap_or(path),
+    };
+    let project = strip_git_suffix(project);
+    if project.is_empty() {
+        return None;
+    }
+    Some(ForgeRepository::gerrit(host.to_ascii_lowercase(), project))
+}
+
+/// Parse a change target: a bare number or a change web URL
+/// (`https://host[:port][/<prefix>]/c/<project>/+/<n>[/<patch set>]`). The
+/// `/c/…/+/` shape is Gerrit's alone, so change URLs are accepted on any
+/// host; credentials are only sent to a configured one.
+pub fn parse_pull_request_target_gerrit(input: &str) -> Result<PullRequestTarget> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return malformed_target(input);
+    }
+
+    if let Some(target) = parse_numeric_target(trimmed) {
+        return Ok(target);
+    }
+    if let Some(target) = parse_gerrit_url_target(trimmed) {
+        return Ok(target);
+    }
+    malformed_target(input)
+}
+
+fn parse_numeric_target(target: &str) -> Option<PullRequestTarget> {
+    if !target.chars().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    let number = target.parse::<u64>().ok()?;
+    if number == 0 {
+        return None;
+    }
+    Some(PullRequestTarget::number(number, target))
+}
+
+fn parse_gerrit_url_target(target: &str) -> Option<PullRequestTarget> {
+    let parts = split_url(target).filter(|parts| parts.scheme != "ssh")?;
+    let (route, change) = parts.path.split_once("/+/")?;
+    // Gerrit served under a context path (`/r/`, `/gerrit/`) puts it before
+    // the `c/` route segment.
+    let (prefix, project) = if let Some(project) = route.strip_prefix("c/") {
+        ("", project)
+    } else {
+        let (prefix, project) = route.split_once("/c/")?;
+        (prefix, project)
+    };
+    let number = change.split('/').next()?.parse::<u64>().ok()?;
+    if number == 0 || project.is_empty() {
+        return None;
+    }
+    let repository = ForgeRepository::gerrit(parts.host().to_ascii_lowercase(), project)
+        .with_web_root(parts.web_root(prefix));
+    Some(PullRequestTarget::with_repository(
+        repository, number, target,
+    ))
+}
+
+fn malformed_target<T>(input: &str) -> Result<T> {
+    Err(TuicrError::Forge(format!(
+        "Malformed Gerrit change target: `{input}`"
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forge::remote_comments::{RemoteCommentSide, RemoteReviewComment};
+    use crate::forge::self_hosted::fixture::{Call, FixtureServer};
+    use crate::forge::submit::InlineComment;
+
+    fn gerrit_repo() -> ForgeRepository {
+        ForgeRepository::gerrit("review.example.com", "platform/build")
+    }
+
+    // ---- URL parsing ----
+
+    #[test]
+    fn parses_ssh_remote_on_gerrit_port_for_any_host() {
+        let repo =
+            parse_gerrit_remote_url("ssh://alice@review.example.com:29418/platform/build").unwrap();
+        assert_eq!(repo, gerrit_repo());
+    }
+
+    #[test]
+    fn parses_https_remote_on_named_host_with_auth_prefix() {
+        let repo =
+            parse_gerrit_remote_url("https://gerrit.corp.example/a/platform/build.git").unwrap();
+        assert_eq!(
+            repo,
+            ForgeRepository::gerrit("gerrit.corp.example", "platform/build")
+        );
+        assert_eq!(repo.slug(), "platform/build");
+    }
+
+    #[test]
+    fn parses_top_level_project_on_known_host() {
+        let repo = parse_gerrit_remote_url("https://review.opendev.org/nova").unwrap();
+        assert_eq!(repo.slug(), "nova");
+        assert_eq!(repo.display_name(), "review.opendev.org/nova");
+    }
+
+    #[test]
+    fn rejects_unrecognized_hosts() {
+        assert!(parse_gerrit_remote_url("https://github.com/agavra/tuicr").is_none());
+        assert!(parse_gerrit_remote_url("git@gitlab.com:owner/repo.git").is_none());
+    }
+
+    #[test]
+    fn parses_change_url_target_on_any_host() {
+        let target = parse_pull_request_target_gerrit(
+            "https://review.example.com/c/platform/build/+/4711/3",
+        )
+        .unwrap();
+        assert_eq!(target.number, 4711);
+        assert_eq!(target.repository.unwrap(), gerrit_repo());
+    }
+
+    #[test]
+    fn keeps_port_and_path_prefix_of_change_url_target() {
+        let target = parse_pull_request_target_gerrit(
+            "https://review.example.com:8443/r/c/platform/build/+/4711",
+        )
+        .unwrap();
+        let repository = target.repository.unwrap();
+        assert_eq!(repository, gerrit_repo());
+        assert_eq!(repository.web_base(), "https://review.example.com:8443/r");
+        assert_eq!(
+            crate::forge::gerrit::models::change_url(&repository, 4711),
+            "https://review.example.com:8443/r/c/platform/build/+/4711"
+        );
+    }
+
+    #[test]
+    fn keeps_port_of_https_remote() {
+        let repo = parse_gerrit_remote_url("https://gerrit.corp.example:8443/a/platform/build.git")
+            .unwrap();
+        assert_eq!(repo.web_base(), "https://gerrit.corp.example:8443");
+        let ssh =
+            parse_gerrit_remote_url("ssh://alice@review.example.com:29418/platform/build").unwrap();
+        assert_eq!(ssh.web_base(), "https://review.example.com");
+    }
+
+    #[test]
+    fn reads_credentials_only_for_a_configured_host() {
+        let forge = ForgeConfig {
+            tokens: [("review.example.com".to_string(), "alice:s3cret".to_string())].into(),
+            ..ForgeConfig::default()
+        };
+        let no_env = |_: &str| None;
+        assert_eq!(
+            credentials_for_host(&forge, "review.example.com", &no_env),
+            Some(GerritCredentials {
+                username: "alice".to_string(),
+                password: "s3cret".to_string(),
+            })
+        );
+        assert_eq!(credentials_for_host(&forge, "evil.example", &no_env), None);
+
+        let env = |name: &str| match name {
+            "GERRIT_HOSTS" => Some("gerrit.corp.example".to_string()),
+            "GERRIT_USERNAME" => Some("bob".to_string()),
+            "GERRIT_HTTP_PASSWORD" => Some("pw".to_string()),
+            _ => None,
+        };
+        let none = ForgeConfig::default();
+        assert!(credentials_for_host(&none, "gerrit.corp.example", &env).is_some());
+        assert_eq!(credentials_for_host(&none, "evil.example", &env), None);
+    }
+
+    #[test]
+    fn rejects_non_change_url_target() {
+        assert!(parse_pull_request_target_gerrit("https://github.com/owner/repo/pull/3").is_err());
+        assert!(parse_pull_request_target_gerrit("not a change").is_err());
+    }
+
+    #[test]
+    fn strips_xssi_guard() {
+        assert_eq!(strip_xssi_prefix(")]}'\n{\"a\":1}"), "{\"a\":1}");
+        assert_eq!(strip_xssi_prefix("[]"), "[]");
+    }
+
+    // ---- Backend calls (via a fixture server transport) ----
+
+    impl GerritHttp for FixtureServer {
+        fn request(&self, method: &str, url: &str, body: Option<&str>) -> GerritHttpResult<String> {
+            self.respond(method, url, body)
+                .map_err(|path| GerritHttpError::Failed {
+                    status: Some(404),
+                    body: format!("Not found: {path}"),
+                })
+        }
+    }
+
+    const CHANGE: &str = "/a/changes/platform%2Fbuild~4711";
+
+    fn backend(server: &FixtureServer) -> GerritBackend {
+        GerritBackend::with_transport(Some(gerrit_repo()), Box::new(server.clone()), true)
+    }
+
+    fn pr_details() -> PullRequestDetails {
+        PullRequestDetails {
+            repository: gerrit_repo(),
+            number: 4711,
+            title: "t".to_string(),
+            url: "https://review.example.com/c/platform/build/+/4711".to_string(),
+            state: "OPEN".to_string(),
+            is_draft: false,
+            author: None,
+            head_ref_name: "refs/changes/11/4711/2".to_string(),
+            base_ref_name: "main".to_string(),
+            head_sha: "head222".to_string(),
+            base_sha: "base000".to_string(),
+            body: String::new(),
+            updated_at: None,
+            closed: false,
+            merged_at: None,
+            diff_start_sha: None,
+        }
+    }
+
+    fn body_json(call: &Call) -> serde_json::Value {
+        serde_json::from_str(call.2.as_deref().unwrap()).unwrap()
+    }
+
+    fn inline(line: u32, side: GhSide) -> InlineComment {
+        InlineComment {
+            path: PathBuf::from("src/lib.rs"),
+            line,
+            side,
+            counterpart_line: None,
+            start_line: None,
+            start_side: None,
+            range_anchors: None,
+            old_path: None,
+            body: "please fix".to_string(),
+            comment_id: "c1".to_string(),
+        }
+    }
+
+    const ALL_PATCH_SETS: &str = r#")]}'
+{"_number":4711,"project":"platform/build","current_revision":"head222","revisions":{
+  "head111":{"_number":1,"ref":"refs/changes/11/4711/1","commit":{"subject":"Cache"}},
+  "head222":{"_number":2,"ref":"refs/changes/11/4711/2","commit":{"subject":"Cache"}}}}"#;
+
+    #[test]
+    fn get_pull_request_reads_project_qualified_change() {
+        let server = FixtureServer::new(&[(
+            "GET",
+            CHANGE,
+            r#")]}'
+{"_number":4711,"project":"platform/build","branch":"main","subject":"Cache","status":"NEW",
+ "current_revision":"head222","revisions":{"head222":{"_number":2,"ref":"refs/changes/11/4711/2",
+ "commit":{"parents":[{"commit":"base000"}],"subject":"Cache","message":"Cache\n"}}}}"#,
+        )]);
+        let details = backend(&server)
+            .get_pull_request(PullRequestTarget::number(4711, "4711"))
+            .unwrap();
+        assert_eq!(details.head_sha, "head222");
+        assert_eq!(details.base_sha, "base000");
+        assert_eq!(details.state, "OPEN");
+        assert_eq!(
+            server.calls()[0].1,
+            format!("https://review.example.com{CHANGE}?{CURRENT_CHANGE_OPTIONS}")
+        );
+    }
+
+    #[test]
+    fn anonymous_backend_skips_auth_prefix() {
+        let server = FixtureServer::new(&[("GET", "/changes/", ")]}'\n[]")]);
+        let backend =
+            GerritBackend::with_transport(Some(gerrit_repo()), Box::new(server.clone()), false);
+        let query = PullRequestListQuery::first_page_with_scope(
+            gerrit_repo(),
+            30,
+            PullRequestListScope::ReviewRequested,
+        );
+        backend.list_pull_requests(query).unwrap();
+        let url = &server.calls()[0].1;
+        assert!(url.starts_with("https://review.example.com/changes/?q=status%3Aopen+project%3Aplatform%2Fbuild&n=31&S=0"));
+        assert!(!url.contains("reviewer"));
+    }
+
+    #[test]
+    fn change_on_another_host_goes_anonymously_to_its_web_root() {
+        let server = FixtureServer::new(&[(
+            "GET",
+            "/gerrit/changes/platform%2Fbuild~9",
+            r#")]}'
+{"_number":9,"project":"platform/build","branch":"main","subject":"x","status":"NEW"}"#,
+        )]);
+        let target = parse_pull_request_target_gerrit(
+            "http://other.example:8080/gerrit/c/platform/build/+/9",
+        )
+        .unwrap();
+        let details = backend(&server).get_pull_request(target).unwrap();
+        assert_eq!(
+            server.calls()[0].1,
+            format!(
+                "http://other.example:8080/gerrit/changes/platform%2Fbuild~9?{CURRENT_CHANGE_OPTIONS}"
+            )
+        );
+        assert_eq!(
+            details.url,
+            "http://other.example:8080/gerrit/c/platform/build/+/9"
+        );
+    }
+
+    #[test]
+    fn password_transport_only_authenticates_its_host() {
+        let http = PasswordHttp::new(
+            "review.example.com",
+            Some(GerritCredentials {
+                username: "alice".to_string(),
+                password: "pw".to_string(),
+            }),
+        );
+        let auth = http.auth.as_ref().unwrap();
+        assert!(
+            auth.header_for("https://review.example.com/a/changes/")
+                .is_some()
+        );
+        assert!(auth.header_for("https://evil.example/changes/").is_none());
+    }
+
+    #[test]
+    fn list_requested_scope_filters_on_reviewer_self() {
+        let server = FixtureServer::new(&[(
+            "GET",
+            "/a/changes/",
+            r#")]}'
+[{"_number":1,"project":"platform/build","subject":"one","status":"NEW"},
+ {"_number":2,"project":"platform/build","subject":"two","status":"NEW"}]"#,
+        )]);
+        let query = PullRequestListQuery::first_page_with_scope(
+            gerrit_repo(),
+            1,
+            PullRequestListScope::ReviewRequested,
+        );
+        let page = backend(&server).list_pull_requests(query).unwrap();
+        assert_eq!(page.pull_requests.len(), 1);
+        assert!(page.has_more);
+        assert_eq!(
+            page.pull_requests[0].url,
+            "https://review.example.com/c/platform/build/+/1"
+        );
+        assert!(
+            server.calls()[0]
+                .1
+                .contains("+reviewer%3Aself+-owner%3Aself")
+        );
+    }
+
+    #[test]
+    fn get_pull_request_diff_decodes_revision_patch() {
+        let patch = "From head222 Mon Sep 17 00:00:00 2001\nSubject: [PATCH] Cache\n\n---\n \
+                     src/lib.rs | 2 +-\n\ndiff --git a/src/lib.rs b/src/lib.rs\nindex 1..2 100644\n\
+                     --- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1 @@\n-old\n+new\n";
+        let encoded = BASE64.encode(patch);
+        let server = FixtureServer::new(&[(
+            "GET",
+            "/a/changes/platform%2Fbuild~4711/revisions/head222/patch",
+            encoded.as_str(),
+        )]);
+        let patches = backend(&server)
+            .get_pull_request_diff(&pr_details())
+            .unwrap();
+        assert_eq!(patches.len(), 1);
+        assert_eq!(patches[0].new_path, Some(PathBuf::from("src/lib.rs")));
+        assert!(patches[0].patch.contains("+new"));
+    }
+
+    #[test]
+    fn list_commits_returns_patch_sets() {
+        let server = FixtureServer::new(&[("GET", CHANGE, ALL_PATCH_SETS)]);
+        let commits = backend(&server)
+            .list_pull_request_commits(&pr_details())
+            .unwrap();
+        let oids: Vec<&str> = commits.iter().map(|c| c.oid.as_str()).collect();
+        assert_eq!(oids, vec!["head111", "head222"]);
+        assert_eq!(commits[0].summary, "Patch Set 1: Cache");
+    }
+
+    #[test]
+    fn range_between_patch_sets_rebuilds_interdiff() {
+        let server = FixtureServer::new(&[
+            ("GET", &format!("{CHANGE}?"), ALL_PATCH_SETS),
+            (
+                "GET",
+                &format!("{CHANGE}/revisions/head222/files?base=1"),
+                r#")]}'
+{"/COMMIT_MSG":{"status":"A"},"src/lib.rs":{}}"#,
+            ),
+            (
+                "GET",
+                &format!("{CHANGE}/revisions/head222/files/src%2Flib.rs/diff?base=1"),
+                r#")]}'
+{"content":[{"ab":["keep"]},{"a":["old"],"b":["new"]}]}"#,
+            ),
+        ]);
+        let patches = backend(&server)
+            .get_pull_request_commit_range_diff(&pr_details(), "head111", "head222")
+            .unwrap();
+        assert_eq!(patches.len(), 1);
+        assert_eq!(patches[0].new_path, Some(PathBuf::from("src/lib.rs")));
+        assert_eq!(patches[0].patch, "@@ -1,2 +1,2 @@\n keep\n-old\n+new\n");
+    }
+
+    #[test]
+    fn list_review_threads_marks_earlier_patch_sets_outdated() {
+        let server = FixtureServer::new(&[(
+            "GET",
+            &format!("{CHANGE}/comments"),
+            r#")]}'
+{"src/lib.rs":[
+  {"id":"c1","patch_set":1,"line":3,"message":"old","unresolved":true},
+  {"id":"c2","patch_set":2,"line":4,"message":"new","unresolved":true}]}"#,
+        )]);
+        let threads = backend(&server).list_review_threads(&pr_details()).unwrap();
+        assert_eq!(threads.len(), 2);
+        let outdated: Vec<bool> = threads.iter().map(|t| t.is_outdated).collect();
+        assert_eq!(outdated, vec![true, false]);
+        assert!(threads.iter().all(|t| !t.is_resolved));
+    }
+
+    #[test]
+    fn approve_votes_highest_permitted_code_review() {
+        let server = FixtureServer::new(&[
+            (
+                "GET",
+                &format!("{CHANGE}?o=DETAILED_LABELS"),
+                r#")]}'
+{"_number":4711,"permitted_labels":{"Code-Review":["-2","-1"," 0","+1","+2"]}}"#,
+            ),
+            (
+                "POST",
+                &format!("{CHANGE}/revisions/head222/review"),
+                ")]}'\n{}",
+            ),
+        ]);
+        let request = CreateReviewRequest {
+            event: SubmitEvent::Approve,
+            commit_id: "head222",
+            body: "LGTM",
+            comments: &[inline(10, GhSide::Right), inline(4, GhSide::Left)],
+        };
+        let response = backend(&server)
+            .create_review(&pr_details(), request)
+            .unwrap();
+        assert_eq!(response.state, "APPROVED");
+
+        let calls = server.calls();
+        let body = body_json(&calls[1]);
+        assert_eq!(body["labels"]["Code-Review"], 2);
+        assert_eq!(body["message"], "LGTM");
+        let comments = body["comments"]["src/lib.rs"].as_array().unwrap();
+        assert_eq!(comments[0]["line"], 10);
+        assert_eq!(comments[0]["unresolved"], true);
+        assert!(comments[0].get("side").is_none());
+        assert_eq!(comments[1]["side"], "PARENT");
+    }
+
+    #[test]
+    fn request_changes_votes_minus_one_without_label_lookup() {
+        let server = FixtureServer::new(&[("POST", CHANGE, ")]}'\n{}")]);
+        let request = CreateReviewRequest {
+            event: SubmitEvent::RequestChanges,
+            commit_id: "head222",
+            body: "",
+            comments: &[],
+        };
+        let response = backend(&server)
+            .create_review(&pr_details(), request)
+            .unwrap();
+        assert_eq!(response.state, "CHANGES_REQUESTED");
+        let calls = server.calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(body_json(&calls[0])["labels"]["Code-Review"], -1);
+        assert!(body_json(&calls[0]).get("message").is_none());
+    }
+
+    #[test]
+    fn draft_stages_draft_comments() {
+        let server = FixtureServer::new(&[("PUT", CHANGE, ")]}'\n{\"id\":\"d1\"}")]);
+        let request = CreateReviewRequest {
+            event: SubmitEvent::Draft,
+            commit_id: "head222",
+            body: "overall",
+            comments: &[inline(10, GhSide::Right)],
+        };
+        let response = backend(&server)
+            .create_review(&pr_details(), request)
+            .unwrap();
+        assert_eq!(response.state, "PENDING");
+        let calls = server.calls();
+        assert_eq!(calls.len(), 2);
+        assert!(calls[0].1.ends_with("/revisions/head222/drafts"));
+        assert_eq!(body_json(&calls[0])["path"], "/PATCHSET_LEVEL");
+        assert_eq!(body_json(&calls[1])["path"], "src/lib.rs");
+    }
+
+    #[test]
+    fn resolve_replies_on_the_commented_patch_set() {
+        let server = FixtureServer::new(&[
+            (
+                "GET",
+                &format!("{CHANGE}/comments"),
+                r#")]}'
+{"src/lib.rs":[{"id":"c1","patch_set":1,"commit_id":"head111","line":3,"side":"PARENT",
+  "message":"why?","unresolved":true}]}"#,
+            ),
+            (
+                "POST",
+                &format!("{CHANGE}/revisions/head111/review"),
+                ")]}'\n{}",
+            ),
+        ]);
+        let thread = RemoteReviewThread {
+            id: "c1".to_string(),
+            path: "src/lib.rs".to_string(),
+            line: Some(3),
+            side: RemoteCommentSide::Left,
+            is_resolved: false,
+            is_outdated: true,
+            comments: vec![RemoteReviewComment {
+                id: "c1".to_string(),
+                author: None,
+                body: "why?".to_string(),
+                created_at: None,
+                in_reply_to: None,
+                url: String::new(),
+            }],
+        };
+        backend(&server)
+            .set_review_thread_resolved(&pr_details(), &thread, true)
+            .unwrap();
+
+        let calls = server.calls();
+        let reply = &body_json(&calls[1])["comments"]["src/lib.rs"][0];
+        assert_eq!(reply["in_reply_to"], "c1");
+        assert_eq!(reply["message"], "Done");
+        assert_eq!(reply["unresolved"], false);
+        assert_eq!(reply["line"], 3);
+        assert_eq!(reply["side"], "PARENT");
+    }
+
+    #[test]
+    fn backend_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<GerritBackend>();
+    }
+}
@@ -0,0 +1,10 @@
+//! Gerrit code review integration.
+//!
+//! Talks to the Gerrit REST API directly over HTTPS, like the Gitea backend.
+//! A Gerrit change is a single commit revised by patch sets, so patch sets
+//! fill the role a PR's commits play in the commit selector.
+
+pub mod api;
+pub mod models;
+
+pub use api::GerritBackend;
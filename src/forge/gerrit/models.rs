@@ -0,0 +1,799 @@
+//! Gerrit REST JSON structs and their mapping into tuicr's forge-agnostic
+//! trait types.
+//!
+//! Modeled on `src/forge/gitea/models.rs`. Gerrit differs from the PR forges
+//! in three ways that shape this file: a change is a single commit revised by
+//! patch sets (which stand in for a PR's commits), timestamps are UTC strings
+//! without a zone (`2026-03-01 10:00:00.000000000`), and comments carry no
+//! thread object — threads are `in_reply_to` chains.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Deserialize;
+
+use crate::forge::remote_comments::{
+    RemoteCommentSide, RemoteReviewComment, RemoteReviewState, RemoteReviewSummary,
+    RemoteReviewThread,
+};
+use crate::forge::traits::{
+    ForgeRepository, PullRequestCommit, PullRequestDetails, PullRequestSummary,
+};
+use crate::model::{FilePatch, FileStatus};
+
+/// The label `:submit` votes on.
+pub const CODE_REVIEW_LABEL: &str = "Code-Review";
+
+/// Paths Gerrit uses for comments and files that aren't repository files.
+const MAGIC_PATHS: &[&str] = &["/PATCHSET_LEVEL", "/COMMIT_MSG", "/MERGE_LIST"];
+
+/// Parse a Gerrit timestamp (`yyyy-mm-dd hh:mm:ss.fffffffff`, always UTC).
+pub fn parse_gerrit_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value.trim(), "%Y-%m-%d %H:%M:%S%.f")
+        .ok()
+        .map(|naive| naive.and_utc())
+}
+
+fn timestamp(value: &Option<String>) -> Option<DateTime<Utc>> {
+    value.as_deref().and_then(parse_gerrit_timestamp)
+}
+
+/// True for Gerrit's pseudo-paths (`/COMMIT_MSG`, `/PATCHSET_LEVEL`, …).
+pub fn is_magic_path(path: &str) -> bool {
+    MAGIC_PATHS.contains(&path)
+}
+
+/// Web URL of a change.
+pub fn change_url(repo: &ForgeRepository, number: u64) -> String {
+    format!("{}/c/{}/+/{}", repo.web_base(), repo.slug(), number)
+}
+
+/// A Gerrit account (owner, uploader, comment author).
+#[derive(Debug, Deserialize, Default)]
+pub struct GrAccount {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
+impl GrAccount {
+    /// Best display handle: the username, else the full name, else the email.
+    fn handle(&self) -> Option<String> {
+        [&self.username, &self.name, &self.email]
+            .into_iter()
+            .flatten()
+            .find(|value| !value.is_empty())
+            .cloned()
+    }
+}
+
+/// A change, from `GET /changes/{id}` and each element of `GET /changes/`.
+/// Which revisions (and their commits) are present depends on the `o=`
+/// options of the request.
+#[derive(Debug, Deserialize)]
+pub struct GrChange {
+    #[serde(rename = "_number")]
+    pub number: u64,
+    #[serde(default)]
+    pub project: String,
+    #[serde(default)]
+    pub branch: String,
+    #[serde(default)]
+    pub subject: String,
+    /// `NEW` | `MERGED` | `ABANDONED`.
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub owner: Option<GrAccount>,
+    #[serde(default)]
+    pub updated: Option<String>,
+    #[serde(default)]
+    pub submitted: Option<String>,
+    #[serde(default)]
+    pub work_in_progress: bool,
+    #[serde(default)]
+    pub current_revision: Option<String>,
+    /// Keyed by commit SHA.
+    #[serde(default)]
+    pub revisions: HashMap<String, GrRevision>,
+    /// Votes the caller may cast, e.g. `{"Code-Review": ["-1", " 0", "+1"]}`.
+    /// Only present with `o=DETAILED_LABELS`.
+    #[serde(default)]
+    pub permitted_labels: HashMap<String, Vec<String>>,
+}
+
+/// One patch set of a change.
+#[derive(Debug, Deserialize, Default)]
+pub struct GrRevision {
+    #[serde(rename = "_number", default)]
+    pub number: u32,
+    /// `refs/changes/45/12345/3`.
+    #[serde(default, rename = "ref")]
+    pub ref_name: String,
+    #[serde(default)]
+    pub created: Option<String>,
+    #[serde(default)]
+    pub uploader: Option<GrAccount>,
+    #[serde(default)]
+    pub commit: Option<GrCommit>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct GrCommit {
+    #[serde(default)]
+    pub parents: Vec<GrParent>,
+    #[serde(default)]
+    pub subject: String,
+    #[serde(default)]
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct GrParent {
+    #[serde(default)]
+    pub commit: String,
+}
+
+impl GrChange {
+    fn repository(&self, queried: &ForgeRepository) -> ForgeRepository {
+        ForgeRepository::gerrit(&queried.host, &self.project)
+            .with_web_root(queried.web_root.clone())
+    }
+
+    fn state(&self) -> String {
+        match self.status.to_ascii_uppercase().as_str() {
+            "NEW" => "OPEN".to_string(),
+            "ABANDONED" => "CLOSED".to_string(),
+            other => other.to_string(),
+        }
+    }
+
+    fn current(&self) -> Option<(&str, &GrRevision)> {
+        let sha = self.current_revision.as_deref()?;
+        self.revisions.get(sha).map(|rev| (sha, rev))
+    }
+
+    /// Highest value the caller may vote on `label`, if any.
+    pub fn max_permitted_vote(&self, label: &str) -> Option<i32> {
+        self.permitted_labels
+            .get(label)?
+            .iter()
+            .filter_map(|value| value.trim().trim_start_matches('+').parse::<i32>().ok())
+            .max()
+    }
+
+    /// Patch-set number of the revision with commit `sha`.
+    pub fn patch_set_of(&self, sha: &str) -> Option<u32> {
+        self.revisions.get(sha).map(|rev| rev.number)
+    }
+
+    pub fn into_summary(self, queried: &ForgeRepository) -> PullRequestSummary {
+        let repository = self.repository(queried);
+        PullRequestSummary {
+            url: change_url(&repository, self.number),
+            number: self.number,
+            title: self.subject.clone(),
+            author: self.owner.as_ref().and_then(GrAccount::handle),
+            head_ref_name: self
+                .current()
+                .map(|(_, rev)| rev.ref_name.clone())
+                .unwrap_or_default(),
+            base_ref_name: self.branch.clone(),
+            updated_at: timestamp(&self.updated),
+            state: self.state(),
+            is_draft: self.work_in_progress,
+            repository,
+        }
+    }
+
+    pub fn into_details(self, queried: &ForgeRepository) -> PullRequestDetails {
+        let repository = self.repository(queried);
+        let (head_sha, head_ref_name, commit) = match self.current() {
+            Some((sha, rev)) => (sha.to_string(), rev.ref_name.clone(), rev.commit.as_ref()),
+            None => (String::new(), String::new(), None),
+        };
+        let base_sha = commit
+            .and_then(|c| c.parents.first())
+            .map(|p| p.commit.clone())
+            .unwrap_or_default();
+        // The subject is already the title; the body is the rest of the
+        // commit message.
+        let body = commit
+            .and_then(|c| c.message.split_once('\n'))
+            .map(|(_, rest)| rest.trim().to_string())
+            .unwrap_or_default();
+        let merged = self.status.eq_ignore_ascii_case("MERGED");
+        PullRequestDetails {
+            url: change_url(&repository, self.number),
+            number: self.number,
+            title: self.subject.clone(),
+            state: self.state(),
+            is_draft: self.work_in_progress,
+            author: self.owner.as_ref().and_then(GrAccount::handle),
+            head_ref_name,
+            base_ref_name: self.branch.clone(),
+            head_sha,
+            base_sha,
+            body,
+            updated_at: timestamp(&self.updated),
+            closed: self.status.eq_ignore_ascii_case("ABANDONED"),
+            merged_at: merged
+                .then(|| timestamp(&self.submitted).or_else(|| timestamp(&self.updated)))
+                .flatten(),
+            diff_start_sha: None,
+            repository,
+        }
+    }
+
+    /// Patch sets as commits, oldest first, so the commit selector walks a
+    /// change's history the way it walks a PR's commits.
+    pub fn into_patch_sets(self) -> Vec<PullRequestCommit> {
+        let mut revisions: Vec<(String, GrRevision)> = self.revisions.into_iter().collect();
+        revisions.sort_by_key(|(_, rev)| rev.number);
+        revisions
+            .into_iter()
+            .map(|(sha, rev)| {
+                let subject = rev
+                    .commit
+                    .as_ref()
+                    .map(|c| c.subject.as_str())
+                    .unwrap_or_default();
+                let summary = if subject.is_empty() {
+                    format!("Patch Set {}", rev.number)
+                } else {
+                    format!("Patch Set {}: {subject}", rev.number)
+                };
+                PullRequestCommit {
+                    short_oid: sha.chars().take(8).collect(),
+                    oid: sha,
+                    summary,
+                    author: rev
+                        .uploader
+                        .as_ref()
+                        .and_then(GrAccount::handle)
+                        .unwrap_or_default(),
+                    timestamp: timestamp(&rev.created),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Patch-set number encoded at the end of a change ref
+/// (`refs/changes/45/12345/3` → `3`).
+pub fn patch_set_from_ref(ref_name: &str) -> Option<u32> {
+    ref_name
+        .strip_prefix("refs/changes/")?
+        .rsplit('/')
+        .next()?
+        .parse()
+        .ok()
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct GrRange {
+    pub start_line: u32,
+    #[serde(default)]
+    pub start_character: u32,
+    pub end_line: u32,
+    #[serde(default)]
+    pub end_character: u32,
+}
+
+/// A published comment, from `GET /changes/{id}/comments` (a map of path to
+/// comments, so `path` is usually absent from the entries themselves).
+#[derive(Debug, Deserialize)]
+pub struct GrComment {
+    pub id: String,
+    #[serde(default)]
+    pub path: Option<String>,
+    /// `PARENT` for comments on the base side; absent for the revision.
+    #[serde(default)]
+    pub side: Option<String>,
+    #[serde(default)]
+    pub patch_set: Option<u32>,
+    #[serde(default)]
+    pub commit_id: Option<String>,
+    /// Absent for file-level comments.
+    #[serde(default)]
+    pub line: Option<u32>,
+    #[serde(default)]
+    pub range: Option<GrRange>,
+    #[serde(default)]
+    pub in_reply_to: Option<String>,
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub updated: Option<String>,
+    #[serde(default)]
+    pub author: Option<GrAccount>,
+    #[serde(default)]
+    pub unresolved: Option<bool>,
+}
+
+impl GrComment {
+    pub fn remote_side(&self) -> RemoteCommentSide {
+        match self.side.as_deref() {
+            Some(side) if side.eq_ignore_ascii_case("PARENT") => RemoteCommentSide::Left,
+            _ => RemoteCommentSide::Right,
+        }
+    }
+
+    fn anchor_line(&self) -> Option<u32> {
+        self.line.or(self.range.map(|r| r.end_line))
+    }
+}
+
+/// Flatten the `path → comments` map, filling in each entry's path.
+pub fn flatten_comments(by_path: HashMap<String, Vec<GrComment>>) -> Vec<GrComment> {
+    by_path
+        .into_iter()
+        .flat_map(|(path, comments)| {
+            comments.into_iter().map(move |mut comment| {
+                comment.path.get_or_insert_with(|| path.clone());
+                comment
+            })
+        })
+        .collect()
+}
+
+/// Group comments into threads by following `in_reply_to` to the root.
+///
+/// Gerrit keeps no thread state: a thread is unresolved when its latest
+/// comment is. Threads started on an older patch set than
+/// `current_patch_set` are outdated — their line numbers belong to that
+/// patch set. Patch-set-level and commit-message comments are skipped; they
+/// have no place in the file diff.
+pub fn group_into_review_threads(
+    mut comments: Vec<GrComment>,
+    current_patch_set: Option<u32>,
+    change_url: &str,
+) -> Vec<RemoteReviewThread> {
+    comments.retain(|c| c.path.as_deref().is_some_and(|p| !is_magic_path(p)));
+    comments.sort_by(|a, b| {
+        timestamp(&a.updated)
+            .cmp(&timestamp(&b.updated))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+
+    let parent_of: HashMap<&str, Option<&str>> = comments
+        .iter()
+        .map(|c| (c.id.as_str(), c.in_reply_to.as_deref()))
+        .collect();
+    let root_of = |id: &str| -> String {
+        let mut current = id;
+        // Bounded walk: a malformed reply cycle must not hang the UI.
+        for _ in 0..parent_of.len() {
+            match parent_of.get(current).copied().flatten() {
+                Some(parent) if parent_of.contains_key(parent) => current = parent,
+                _ => break,
+            }
+        }
+        current.to_string()
+    };
+    let roots: Vec<String> = comments.iter().map(|c| root_of(&c.id)).collect();
+
+    let mut threads: Vec<RemoteReviewThread> = Vec::new();
+    let mut index_of: HashMap<String, usize> = HashMap::new();
+    for (comment, root) in comments.into_iter().zip(roots) {
+        let resolved = !comment.unresolved.unwrap_or(false);
+        let remote = RemoteReviewComment {
+            url: format!("{change_url}/comment/{}/", comment.id),
+            id: comment.id.clone(),
+            author: comment.author.as_ref().and_then(GrAccount::handle),
+            body: comment.message.clone(),
+            created_at: timestamp(&comment.updated),
+            in_reply_to: comment.in_reply_to.clone(),
+        };
+        match index_of.get(&root) {
+            Some(&index) => {
+                let thread = &mut threads[index];
+                thread.is_resolved = resolved;
+                thread.comments.push(remote);
+            }
+            None => {
+                index_of.insert(root, threads.len());
+                let is_outdated = match (comment.patch_set, current_patch_set) {
+                    (Some(ps), Some(current)) => ps < current,
+                    _ => false,
+                };
+                threads.push(RemoteReviewThread {
+                    id: remote.id.clone(),
+                    path: comment.path.clone().unwrap_or_default(),
+                    line: comment.anchor_line(),
+                    side: comment.remote_side(),
+                    is_resolved: resolved,
+                    is_outdated,
+                    comments: vec![remote],
+                });
+            }
+        }
+    }
+    threads
+}
+
+/// A change message (the review log), from `GET /changes/{id}/messages`.
+#[derive(Debug, Deserialize)]
+pub struct GrChangeMessage {
+    pub id: String,
+    #[serde(default)]
+    pub author: Option<GrAccount>,
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub date: Option<String>,
+    /// `autogenerated:…` for uploads, rebases, and bot notices.
+    #[serde(default)]
+    pub tag: Option<String>,
+}
+
+impl GrChangeMessage {
+    /// Map a reviewer's message to a review summary. The verdict comes from
+    /// the Code-Review vote in the `Patch Set N: Code-Review+2` header line;
+    /// the body is what follows, minus the `(N comments)` counter. `None`
+    /// for generated messages and bare votes.
+    pub fn into_review_summary(self, change_url: &str) -> Option<RemoteReviewSummary> {
+        if self
+            .tag
+            .as_deref()
+            .is_some_and(|tag| tag.starts_with("autogenerated:"))
+        {
+            return None;
+        }
+        let mut lines = self.message.lines().peekable();
+        let header = lines
+            .next_if(|line| line.starts_with("Patch Set "))
+            .unwrap_or_default();
+        let vote = header
+            .find(CODE_REVIEW_LABEL)
+            .map(|at| &header[at + CODE_REVIEW_LABEL.len()..]);
+        let state = match vote {
+            Some(rest) if rest.starts_with('+') => RemoteReviewState::Approved,
+            Some(rest) if rest.starts_with('-') => RemoteReviewState::ChangesRequested,
+            _ => RemoteReviewState::Commented,
+        };
+        let body = lines
+            .filter(|line| !is_comment_counter(line))
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        if body.is_empty() {
+            return None;
+        }
+        Some(RemoteReviewSummary {
+            url: format!("{change_url}#message-{}", self.id),
+            id: self.id,
+            author: self.author.as_ref().and_then(GrAccount::handle),
+            body,
+            state,
+            created_at: timestamp(&self.date),
+        })
+    }
+}
+
+fn is_comment_counter(line: &str) -> bool {
+    line.trim()
+        .strip_prefix('(')
+        .and_then(|rest| {
+            rest.strip_suffix(" comment)")
+                .or(rest.strip_suffix(" comments)"))
+        })
+        .is_some_and(|count| count.chars().all(|ch| ch.is_ascii_digit()))
+}
+
+/// Entry from `GET /changes/{id}/revisions/{rev}/files`.
+#[derive(Debug, Deserialize, Default)]
+pub struct GrFileInfo {
+    /// `A` | `D` | `R` | `C` | `W` (rewritten); absent means modified.
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub old_path: Option<String>,
+    #[serde(default)]
+    pub binary: bool,
+}
+
+impl GrFileInfo {
+    /// Old and new path plus status for the file at `path`.
+    pub fn paths(&self, path: &str) -> (Option<PathBuf>, Option<PathBuf>, FileStatus) {
+        let new = Some(PathBuf::from(path));
+        let old = Some(PathBuf::from(self.old_path.as_deref().unwrap_or(path)));
+        match self.status.as_deref() {
+            Some("A") => (None, new, FileStatus::Added),
+            Some("D") => (old, None, FileStatus::Deleted),
+            Some("R") => (old, new, FileStatus::Renamed),
+            Some("C") => (old, new, FileStatus::Copied),
+            _ => (old, new, FileStatus::Modified),
+        }
+    }
+}
+
+/// A file diff, from `GET /changes/{id}/revisions/{rev}/files/{path}/diff`.
+#[derive(Debug, Deserialize, Default)]
+pub struct GrDiffInfo {
+    #[serde(default)]
+    pub content: Vec<GrDiffContent>,
+}
+
+/// One run of a diff: lines common to both sides (`ab`), lines only on one
+/// side (`a` / `b`), or `skip` common lines left out by the `context` option.
+#[derive(Debug, Deserialize, Default)]
+pub struct GrDiffContent {
+    #[serde(default)]
+    pub ab: Vec<String>,
+    #[serde(default)]
+    pub a: Vec<String>,
+    #[serde(default)]
+    pub b: Vec<String>,
+    #[serde(default)]
+    pub skip: u32,
+}
+
+/// Lines of context around each change in rebuilt hunks, as in `git diff`.
+const HUNK_CONTEXT: usize = 3;
+
+impl GrDiffInfo {
+    /// Rebuild unified-diff hunks (`@@` headers and `' '`/`-`/`+` lines) from
+    /// Gerrit's run-length content, for `FilePatch::patch`.
+    pub fn to_unified_hunks(&self) -> String {
+        // (origin, text, old line, new line); `None` marks a skipped gap.
+        let mut lines: Vec<Option<(char, &str, u32, u32)>> = Vec::new();
+        let (mut old, mut new) = (1u32, 1u32);
+        for run in &self.content {
+            for text in &run.ab {
+                lines.push(Some((' ', text, old, new)));
+                old += 1;
+                new += 1;
+            }
+            for text in &run.a {
+                lines.push(Some(('-', text, old, new)));
+                old += 1;
+            }
+            for text in &run.b {
+                lines.push(Some(('+', text, old, new)));
+                new += 1;
+            }
+            if run.skip > 0 {
+                lines.push(None);
+                old += run.skip;
+                new += run.skip;
+            }
+        }
+
+        // Keep every change plus HUNK_CONTEXT lines either side of it,
+        // never reaching across a skipped gap.
+        let mut keep = vec![false; lines.len()];
+        for (idx, line) in lines.iter().enumerate() {
+            if !matches!(line, Some((origin, ..)) if *origin != ' ') {
+                continue;
+            }
+            keep[idx] = true;
+            for step in [-1isize, 1] {
+                let mut at = idx as isize;
+                for _ in 0..HUNK_CONTEXT {
+                    at += step;
+                    match lines.get(at as usize) {
+                        Some(Some(_)) if at >= 0 => keep[at as usize] = true,
+                        _ => break,
+                    }
+                }
+            }
+        }
+
+        let mut out = String::new();
+        let mut idx = 0;
+        while idx < lines.len() {
+            if !keep[idx] {
+                idx += 1;
+                continue;
+            }
+            let start = idx;
+            while idx < lines.len() && keep[idx] {
+                idx += 1;
+            }
+            let hunk: Vec<(char, &str, u32, u32)> =
+                lines[start..idx].iter().flatten().copied().collect();
+            let Some(&(_, _, first_old, first_new)) = hunk.first() else {
+                continue;
+            };
+            let old_count = hunk.iter().filter(|(o, ..)| *o != '+').count() as u32;
+            let new_count = hunk.iter().filter(|(o, ..)| *o != '-').count() as u32;
+            // An empty side is addressed by the line before it, as git does.
+            let old_start = if old_count == 0 {
+                first_old - 1
+            } else {
+                first_old
+            };
+            let new_start = if new_count == 0 {
+                first_new - 1
+            } else {
+                first_new
+            };
+            out.push_str(&format!(
+                "@@ -{old_start},{old_count} +{new_start},{new_count} @@\n"
+            ));
+            for (origin, text, ..) in hunk {
+                out.push(origin);
+                out.push_str(text);
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    pub fn into_file_patch(self, path: &str, info: &GrFileInfo) -> FilePatch {
+        let (old_path, new_path, status) = info.paths(path);
+        let mut patch = FilePatch::new(old_path, new_path, status, self.to_unified_hunks());
+        patch.is_binary = info.binary;
+        patch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOST: &str = "review.example.com";
+
+    #[test]
+    fn should_map_change_into_details_from_current_revision() {
+        let json = r#"{
+            "_number": 4711,
+            "project": "platform/build",
+            "branch": "main",
+            "subject": "Cache toolchain downloads",
+            "status": "NEW",
+            "owner": { "name": "Alice Example", "username": "alice" },
+            "updated": "2026-03-01 10:00:00.000000000",
+            "current_revision": "head222",
+            "revisions": {
+                "head111": { "_number": 1, "ref": "refs/changes/11/4711/1" },
+                "head222": {
+                    "_number": 2,
+                    "ref": "refs/changes/11/4711/2",
+                    "commit": {
+                        "parents": [{ "commit": "base000" }],
+                        "subject": "Cache toolchain downloads",
+                        "message": "Cache toolchain downloads\n\nSaves a minute per build.\n\nChange-Id: I0123\n"
+                    }
+                }
+            }
+        }"#;
+        let change: GrChange = serde_json::from_str(json).unwrap();
+        let details = change.into_details(&ForgeRepository::gerrit(HOST, "platform/build"));
+        assert_eq!(
+            details.repository,
+            ForgeRepository::gerrit(HOST, "platform/build")
+        );
+        assert_eq!(details.state, "OPEN");
+        assert_eq!(details.head_sha, "head222");
+        assert_eq!(details.base_sha, "base000");
+        assert_eq!(details.head_ref_name, "refs/changes/11/4711/2");
+        assert_eq!(details.author.as_deref(), Some("alice"));
+        assert_eq!(
+            details.url,
+            "https://review.example.com/c/platform/build/+/4711"
+        );
+        assert!(details.body.starts_with("Saves a minute per build."));
+        assert!(details.updated_at.is_some());
+        assert_eq!(patch_set_from_ref(&details.head_ref_name), Some(2));
+    }
+
+    #[test]
+    fn should_list_patch_sets_oldest_first() {
+        let json = r#"{
+            "_number": 4711,
+            "revisions": {
+                "bbbbbbbbbb": { "_number": 2, "created": "2026-03-02 09:00:00.000000000",
+                                "uploader": { "username": "alice" },
+                                "commit": { "subject": "Cache downloads" } },
+                "aaaaaaaaaa": { "_number": 1, "commit": { "subject": "Cache downloads" } }
+            }
+        }"#;
+        let change: GrChange = serde_json::from_str(json).unwrap();
+        let commits = change.into_patch_sets();
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].oid, "aaaaaaaaaa");
+        assert_eq!(commits[1].summary, "Patch Set 2: Cache downloads");
+        assert_eq!(commits[1].short_oid, "bbbbbbbb");
+        assert_eq!(commits[1].author, "alice");
+        assert!(commits[1].timestamp.is_some());
+    }
+
+    #[test]
+    fn should_pick_highest_permitted_code_review_vote() {
+        let change: GrChange = serde_json::from_str(
+            r#"{ "_number": 1, "permitted_labels": { "Code-Review": ["-2", "-1", " 0", "+1", "+2"] } }"#,
+        )
+        .unwrap();
+        assert_eq!(change.max_permitted_vote(CODE_REVIEW_LABEL), Some(2));
+        assert_eq!(change.max_permitted_vote("Verified"), None);
+    }
+
+    #[test]
+    fn should_thread_replies_and_take_state_from_latest_comment() {
+        let json = r#"{
+            "src/lib.rs": [
+                { "id": "c2", "in_reply_to": "c1", "message": "Done", "patch_set": 2,
+                  "line": 12, "unresolved": false, "updated": "2026-03-01 11:00:00.000000000" },
+                { "id": "c1", "message": "Why?", "patch_set": 2, "line": 12, "unresolved": true,
+                  "author": { "username": "bob" }, "updated": "2026-03-01 10:00:00.000000000" },
+                { "id": "c3", "message": "Old side", "side": "PARENT", "patch_set": 1,
+                  "range": { "start_line": 3, "start_character": 0, "end_line": 5, "end_character": 2 },
+                  "unresolved": true, "updated": "2026-02-28 10:00:00.000000000" }
+            ],
+            "/PATCHSET_LEVEL": [ { "id": "c4", "message": "Overall fine", "patch_set": 2 } ]
+        }"#;
+        let by_path: HashMap<String, Vec<GrComment>> = serde_json::from_str(json).unwrap();
+        let threads = group_into_review_threads(
+            flatten_comments(by_path),
+            Some(2),
+            "https://review.example.com/c/p/+/1",
+        );
+        assert_eq!(threads.len(), 2);
+
+        let old = &threads[0];
+        assert_eq!(old.id, "c3");
+        assert_eq!(old.side, RemoteCommentSide::Left);
+        assert_eq!(old.line, Some(5));
+        assert!(old.is_outdated);
+        assert!(!old.is_resolved);
+
+        let current = &threads[1];
+        assert_eq!(current.id, "c1");
+        assert_eq!(current.path, "src/lib.rs");
+        assert_eq!(current.comments.len(), 2);
+        assert_eq!(current.comments[0].author.as_deref(), Some("bob"));
+        assert_eq!(current.comments[1].in_reply_to.as_deref(), Some("c1"));
+        assert!(current.is_resolved);
+        assert!(!current.is_outdated);
+        assert_eq!(
+            current.comments[0].url,
+            "https://review.example.com/c/p/+/1/comment/c1/"
+        );
+    }
+
+    #[test]
+    fn should_read_verdict_and_body_from_change_messages() {
+        let json = r#"[
+            { "id": "m1", "message": "Uploaded patch set 1.", "tag": "autogenerated:gerrit:newPatchSet" },
+            { "id": "m2", "message": "Patch Set 1: Code-Review-1\n\n(2 comments)\n\nNeeds a test.",
+              "author": { "username": "carol" } },
+            { "id": "m3", "message": "Patch Set 2: Code-Review+2" }
+        ]"#;
+        let messages: Vec<GrChangeMessage> = serde_json::from_str(json).unwrap();
+        let summaries: Vec<_> = messages
+            .into_iter()
+            .filter_map(|m| m.into_review_summary("https://review.example.com/c/p/+/1"))
+            .collect();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].state, RemoteReviewState::ChangesRequested);
+        assert_eq!(summaries[0].body, "Needs a test.");
+        assert_eq!(summaries[0].author.as_deref(), Some("carol"));
+    }
+
+    #[test]
+    fn should_rebuild_unified_hunks_from_diff_content() {
+        let json = r#"{
+            "content": [
+                { "ab": ["one", "two", "three", "four", "five"] },
+                { "a": ["six"], "b": ["SIX"] },
+                { "ab": ["seven"] },
+                { "skip": 40 },
+                { "ab": ["l48", "l49"] },
+                { "b": ["added"] }
+            ]
+        }"#;
+        let diff: GrDiffInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            diff.to_unified_hunks(),
+            "@@ -3,5 +3,5 @@\n three\n four\n five\n-six\n+SIX\n seven\n\
+             @@ -48,2 +48,3 @@\n l48\n l49\n+added\n"
+        );
+    }
+}
@@ -0,0 +1,1171 @@
+//! Gitea / Forgejo backend: `ForgeBackend` over the `/api/v1` REST API.
+//!
+//! Transport is pluggable ([`GiteaHttp`]) so tests can stand in canned
+//! responses; production uses [`TokenHttp`], which calls the API directly
+//! over HTTP(S). Auth is an access token for the repository's host: its
+//! `[forge.tokens]` entry, the token of the `tea` CLI login for that host, or
+//! `GITEA_TOKEN` / `FORGEJO_TOKEN` when `GITEA_HOSTS` lists the host. The
+//! token only ever goes to that host; anything else is requested anonymously,
+//! which is enough to read public repos.
+//!
+//! Unlike Azure DevOps, Gitea serves a unified diff for a PR
+//! (`pulls/{index}.diff`), so no local clone is needed. A clone is still used
+//! opportunistically for file content and commit-range diffs.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde_json::json;
+
+use crate::config::ForgeConfig;
+use crate::error::{Result, TuicrError};
+use crate::forge::remote_comments::{RemoteCommentSide, RemoteReviewSummary, RemoteReviewThread};
+use crate::forge::self_hosted::{
+    HostAuth, configured_token, env_token_for_listed_host, host_listed, local_range_diff,
+    parse_scp_like_remote, process_env, read_blob_with_repo, split_url, strip_git_suffix,
+    trim_url_suffix,
+};
+use crate::forge::submit::{GhSide, SubmitEvent};
+use crate::forge::traits::{
+    CreateReviewRequest, ForgeBackend, ForgeFileLinesRequest, ForgeRepository,
+    GhCreateReviewResponse, PagedPullRequests, PullRequestCommit, PullRequestDetails,
+    PullRequestListQuery, PullRequestListScope, PullRequestReviewMetadata, PullRequestTarget,
+};
+use crate::model::{DiffLine, FilePatch};
+use crate::vcs::git::raw::pair_metadata_with_patch;
+use crate::vcs::slice_context_lines;
+
+use super::models::{
+    GtChangedFile, GtCommit, GtPullRequest, GtReview, GtReviewComment, GtUser,
+    group_into_review_threads,
+};
+
+/// Page size for list endpoints. Gitea's default `MAX_RESPONSE_ITEMS` is 50,
+/// so asking for more is silently capped.
+const PAGE_LIMIT: usize = 50;
+/// Upper bound on pages walked by any paginated fetch.
+const MAX_PAGES: usize = 40;
+/// Public instances recognized without any configuration.
+const KNOWN_GITEA_HOSTS: &[&str] = &["codeberg.org", "gitea.com"];
+/// Env vars checked (in order) for an access token.
+const TOKEN_ENV_VARS: &[&str] = &["GITEA_TOKEN", "FORGEJO_TOKEN"];
+/// Comma-separated extra hosts to treat as Gitea, and the only hosts the
+/// [`TOKEN_ENV_VARS`] token is sent to.
+const HOSTS_ENV_VAR: &str = "GITEA_HOSTS";
+
+// ---------- Transport ----------
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GiteaHttpError {
+    /// 401/403 — missing, expired, or under-scoped token.
+    Auth(String),
+    /// Any other non-2xx status or transport error.
+    Failed { status: Option<u16>, body: String },
+}
+
+pub type GiteaHttpResult<T> = std::result::Result<T, GiteaHttpError>;
+
+/// HTTP transport for the Gitea REST API. `url` is absolute. Returns the raw
+/// 2xx response body.
+pub trait GiteaHttp: Send + Sync {
+    fn request(&self, method: &str, url: &str, body: Option<&str>) -> GiteaHttpResult<String>;
+}
+
+/// Direct REST transport. Sends `Authorization: token <TOKEN>` to the host
+/// the token was configured for, and nowhere else.
+pub struct TokenHttp {
+    auth: Option<HostAuth>,
+    agent: ureq::Agent,
+}
+
+impl TokenHttp {
+    pub fn new(host: &str, token: Option<String>) -> Self {
+        let config = ureq::Agent::config_builder()
+            .timeout_global(Some(Duration::from_secs(30)))
+            .http_status_as_error(false)
+            .build();
+        Self {
+            auth: token.map(|token| HostAuth::new(host, format!("token {token}"))),
+            agent: config.into(),
+        }
+    }
+}
+
+impl GiteaHttp for TokenHttp {
+    fn request(&self, method: &str, url: &str, body: Option<&str>) -> GiteaHttpResult<String> {
+        let auth = self.auth.as_ref().and_then(|auth| auth.header_for(url));
+        let result = match method.to_ascii_uppercase().as_str() {
+            "GET" => {
+                let mut request = self.agent.get(url);
+                if let Some(auth) = auth {
+                    request = request.header("Authorization", auth);
+                }
+                request.call()
+            }
+            "POST" => {
+                let mut request = self
+                    .agent
+                    .post(url)
+                    .header("Content-Type", "application/json");
+                if let Some(auth) = auth {
+                    request = request.header("Authorization", auth);
+                }
+                request.send(body.unwrap_or(""))
+            }
+            other => {
+                return Err(GiteaHttpError::Failed {
+                    status: None,
+                    body: format!("unsupported HTTP method {other}"),
+                });
+            }
+        };
+
+        let response = result.map_err(|err| GiteaHttpError::Failed {
+            status: None,
+            body: err.to_string(),
+        })?;
+        let status = response.status().as_u16();
+        let text = response
+            .into_body()
+            .read_to_string()
+            .map_err(|err| GiteaHttpError::Failed {
+                status: Some(status),
+                body: err.to_string(),
+            })?;
+
+        if (200..300).contains(&status) {
+            Ok(text)
+        } else if status == 401 || status == 403 {
+            Err(GiteaHttpError::Auth(text))
+        } else {
+            Err(GiteaHttpError::Failed {
+                status: Some(status),
+                body: text,
+            })
+        }
+    }
+}
+
+/// Resolve the token for `host`: its `[forge.tokens]` entry, then the `tea`
+/// login whose URL names the same host, then the env vars — but only for a
+/// host listed in `GITEA_HOSTS`, since they name no host of their own.
+fn token_for_host(
+    forge: &ForgeConfig,
+    host: &str,
+    tea_logins: &[TeaLogin],
+    env: &dyn Fn(&str) -> Option<String>,
+) -> Option<String> {
+    configured_token(forge, host)
+        .map(str::to_string)
+        .or_else(|| {
+            tea_logins
+                .iter()
+                .find(|login| login.host.eq_ignore_ascii_case(host))
+                .and_then(|login| login.token.clone())
+        })
+        .or_else(|| env_token_for_listed_host(env, TOKEN_ENV_VARS, HOSTS_ENV_VAR, host))
+}
+
+// ---------- `tea` config ----------
+
+/// One `logins:` entry from the `tea` CLI config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TeaLogin {
+    host: String,
+    token: Option<String>,
+}
+
+fn tea_config_path() -> Option<PathBuf> {
+    let dirs = directories::BaseDirs::new()?;
+    Some(dirs.config_dir().join("tea").join("config.yml"))
+}
+
+/// Logins from `tea`'s `config.yml`. Empty when `tea` isn't configured.
+fn tea_logins() -> Vec<TeaLogin> {
+    tea_config_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|content| parse_tea_logins(&content))
+        .unwrap_or_default()
+}
+
+/// Pull `url` / `token` pairs out of `tea`'s YAML config.
+///
+/// The file is machine-written with a fixed shape (`logins:` followed by a
+/// list of flat mappings), so a line scanner covers it without a YAML
+/// dependency. Unknown keys and other top-level sections are ignored.
+fn parse_tea_logins(content: &str) -> Vec<TeaLogin> {
+    let mut logins = Vec::new();
+    let mut in_logins = false;
+    let mut current: Option<(Option<String>, Option<String>)> = None;
+    let mut flush = |current: &mut Option<(Option<String>, Option<String>)>| {
+        if let Some((Some(url), token)) = current.take()
+            && let Some(host) = host_of_url(&url)
+        {
+            logins.push(TeaLogin { host, token });
+        }
+    };
+
+    for raw in content.lines() {
+        if raw.trim().is_empty() || raw.trim_start().starts_with('#') {
+            continue;
+        }
+        if !raw.starts_with([' ', '-']) {
+            flush(&mut current);
+            in_logins = raw.trim_end() == "logins:";
+            continue;
+        }
+        if !in_logins {
+            continue;
+        }
+        let mut line = raw.trim_start();
+        if let Some(rest) = line.strip_prefix("- ") {
+            flush(&mut current);
+            current = Some((None, None));
+            line = rest.trim_start();
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().trim_matches(['"', '\'']).to_string();
+        if let Some((url, token)) = current.as_mut() {
+            match key.trim() {
+                "url" => *url = Some(value),
+                "token" if !value.is_empty() => *token = Some(value),
+                _ => {}
+            }
+        }
+    }
+    flush(&mut current);
+    logins
+}
+
+fn host_of_url(url: &str) -> Option<String> {
+    split_url(url).map(|parts| parts.host().to_ascii_lowercase())
+}
+
+// ---------- Coordinate helpers ----------
+
+fn repo_api_base(repo: &ForgeRepository) -> String {
+    format!(
+        "{}/api/v1/repos/{}/{}",
+        repo.web_base(),
+        repo.owner,
+        repo.name
+    )
+}
+
+fn with_page(url: &str, page: usize) -> String {
+    let sep = if url.contains('?') { '&' } else { '?' };
+    format!("{url}{sep}page={page}&limit={PAGE_LIMIT}")
+}
+
+/// Percent-encode one path segment of a repository file path.
+fn encode_path_segment(segment: &str) -> String {
+    let mut out = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                out.push(byte as char)
+            }
+            other => out.push_str(&format!("%{other:02X}")),
+        }
+    }
+    out
+}
+
+// ---------- Backend ----------
+
+pub struct GiteaBackend {
+    default_repository: Option<ForgeRepository>,
+    http: Box<dyn GiteaHttp>,
+    local_checkout: Option<PathBuf>,
+}
+
+impl GiteaBackend {
+    /// Build a backend with a token transport for the repository's host.
+    pub fn new(default_repository: Option<ForgeRepository>, forge: &ForgeConfig) -> Self {
+        let host = default_repository
+            .as_ref()
+            .map(|repo| repo.host.clone())
+            .unwrap_or_default();
+        let token = token_for_host(forge, &host, &tea_logins(), &process_env);
+        Self {
+            default_repository,
+            http: Box::new(TokenHttp::new(&host, token)),
+            local_checkout: None,
+        }
+    }
+
+    /// Build a backend with an explicit transport (used in tests).
+    pub fn with_transport(
+        default_repository: Option<ForgeRepository>,
+        http: Box<dyn GiteaHttp>,
+    ) -> Self {
+        Self {
+            default_repository,
+            http,
+            local_checkout: None,
+        }
+    }
+
+    pub fn with_local_checkout(mut self, checkout: Option<PathBuf>) -> Self {
+        self.local_checkout = checkout;
+        self
+    }
+
+    fn resolve_repository(&self, target: &PullRequestTarget) -> Result<ForgeRepository> {
+        target
+            .repository
+            .clone()
+            .or_else(|| self.default_repository.clone())
+            .ok_or_else(|| {
+                TuicrError::Forge(format!(
+                    "Gitea pull request target `{}` does not include a repository",
+                    target.original
+                ))
+            })
+    }
+
+    fn get(&self, repo: &ForgeRepository, url: &str) -> Result<String> {
+        self.http
+            .request("GET", url, None)
+            .map_err(|err| map_http_error(err, &repo.host))
+    }
+
+    fn get_json<T: serde::de::DeserializeOwned>(
+        &self,
+        repo: &ForgeRepository,
+        url: &str,
+    ) -> Result<T> {
+        let output = self.get(repo, url)?;
+        Ok(serde_json::from_str(&output)?)
+    }
+
+    fn post(&self, repo: &ForgeRepository, url: &str, body: &serde_json::Value) -> Result<String> {
+        let body = serde_json::to_string(body)?;
+        self.http
+            .request("POST", url, Some(&body))
+            .map_err(|err| map_http_error(err, &repo.host))
+    }
+
+    /// Walk a paginated list endpoint until a short page or `stop` says the
+    /// caller has enough.
+    fn collect_pages<T: serde::de::DeserializeOwned>(
+        &self,
+        repo: &ForgeRepository,
+        url: &str,
+        mut stop: impl FnMut(&[T]) -> bool,
+    ) -> Result<Vec<T>> {
+        let mut rows: Vec<T> = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let batch: Vec<T> = self.get_json(repo, &with_page(url, page))?;
+            let received = batch.len();
+            rows.extend(batch);
+            if received < PAGE_LIMIT || stop(&rows) {
+                return Ok(rows);
+            }
+        }
+        Ok(rows)
+    }
+
+    fn current_user_login(&self, repo: &ForgeRepository) -> Result<Option<String>> {
+        let url = format!("{}/api/v1/user", repo.web_base());
+        let user: GtUser = self.get_json(repo, &url)?;
+        Ok(Some(user.login).filter(|login| !login.is_empty()))
+    }
+
+    fn list_reviews(&self, pr: &PullRequestDetails) -> Result<Vec<GtReview>> {
+        let url = format!(
+            "{}/pulls/{}/reviews",
+            repo_api_base(&pr.repository),
+            pr.number
+        );
+        let reviews: Vec<GtReview> = self.collect_pages(&pr.repository, &url, |_| false)?;
+        Ok(reviews.into_iter().filter(GtReview::is_review).collect())
+    }
+
+    fn fetch_file_via_api(&self, request: &ForgeFileLinesRequest) -> Result<String> {
+        let path = request
+            .path
+            .to_string_lossy()
+            .replace('\\', "/")
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(encode_path_segment)
+            .collect::<Vec<_>>()
+            .join("/");
+        let url = format!(
+            "{}/raw/{path}?ref={}",
+            repo_api_base(&request.repository),
+            request.sha()
+        );
+        self.get(&request.repository, &url)
+    }
+
+    /// File content at the request's revision: local blob first, REST fallback.
+    fn file_content(&self, request: &ForgeFileLinesRequest) -> Result<String> {
+        let local = self
+            .local_checkout
+            .as_deref()
+            .and_then(|root| read_blob_with_repo(root, request.sha(), request.path.as_path()));
+        match local {
+            Some(content) => Ok(content),
+            None => self.fetch_file_via_api(request),
+        }
+    }
+}
+
+impl ForgeBackend for GiteaBackend {
+    fn list_pull_requests(&self, query: PullRequestListQuery) -> Result<PagedPullRequests> {
+        let page_size = query.page_size.max(1);
+        let wanted = query.already_loaded + page_size + 1;
+        let url = format!(
+            "{}/pulls?state=open&sort=recentupdate",
+            repo_api_base(&query.repository)
+        );
+        // Gitea's PR list has no reviewer filter, so "requested" scope filters
+        // client-side on `requested_reviewers`. Best-effort: without a
+        // resolvable user, fall back to all open PRs.
+        let reviewer = match query.scope {
+            PullRequestListScope::ReviewRequested => {
+                self.current_user_login(&query.repository).ok().flatten()
+            }
+            PullRequestListScope::Open => None,
+        };
+        let matches = |pr: &GtPullRequest| {
+            reviewer
+                .as_deref()
+                .is_none_or(|login| pr.requests_review_from(login))
+        };
+        let rows: Vec<GtPullRequest> = self.collect_pages(&query.repository, &url, |rows| {
+            rows.iter().filter(|pr| matches(pr)).count() >= wanted
+        })?;
+        let rows: Vec<GtPullRequest> = rows.into_iter().filter(|pr| matches(pr)).collect();
+        let has_more = rows.len() > query.already_loaded + page_size;
+        let pull_requests = rows
+            .into_iter()
+            .skip(query.already_loaded)
+            .take(page_size)
+            .map(|pr| pr.into_summary(&query.repository))
+            .collect::<Vec<_>>();
+        let total_loaded = query.already_loaded + pull_requests.len();
+        Ok(PagedPullRequests {
+            pull_requests,
+            has_more,
+            total_loaded,
+        })
+    }
+
+    fn get_pull_request(&self, target: PullRequestTarget) -> Result<PullRequestDetails> {
+        let repository = self.resolve_repository(&target)?;
+        let url = format!("{}/pulls/{}", repo_api_base(&repository), target.number);
+        let pr: GtPullRequest = self.get_json(&repository, &url)?;
+        Ok(pr.into_details(&repository))
+    }
+
+    fn get_pull_request_diff(&self, pr: &PullRequestDetails) -> Result<Vec<FilePatch>> {
+        let base = repo_api_base(&pr.repository);
+        let files_url = format!("{base}/pulls/{}/files", pr.number);
+        let metadata = self
+            .collect_pages::<GtChangedFile>(&pr.repository, &files_url, |_| false)?
+            .into_iter()
+            .map(GtChangedFile::into_metadata)
+            .collect::<Result<Vec<_>>>()?;
+        let patch = self.get(&pr.repository, &format!("{base}/pulls/{}.diff", pr.number))?;
+        pair_metadata_with_patch(metadata, patch.as_bytes())
+    }
+
+    fn get_pull_request_commit_range_diff(
+        &self,
+        _pr: &PullRequestDetails,
+        start_sha: &str,
+        end_sha: &str,
+    ) -> Result<Vec<FilePatch>> {
+        // Gitea's compare API returns commit lists, not a patch, so subranges
+        // come from the local clone.
+        self.local_checkout
+            .as_deref()
+            .and_then(|root| local_range_diff(root, start_sha, end_sha))
+            .ok_or_else(|| {
+                TuicrError::UnsupportedOperation(
+                    "Commit-range diff requires both commits in the local checkout for Gitea"
+                        .to_string(),
+                )
+            })
+    }
+
+    fn local_checkout_path(&self) -> Option<PathBuf> {
+        self.local_checkout.clone()
+    }
+
+    fn fetch_file_lines(&self, request: ForgeFileLinesRequest) -> Result<Vec<DiffLine>> {
+        if request.start_line == 0 || request.start_line > request.end_line {
+            return Ok(Vec::new());
+        }
+        let content = self.file_content(&request)?;
+        Ok(slice_context_lines(
+            &content,
+            request.start_line,
+            request.end_line,
+        ))
+    }
+
+    fn file_line_count(&self, request: ForgeFileLinesRequest) -> Result<u32> {
+        let content = self.file_content(&request)?;
+        Ok(content.lines().count() as u32)
+    }
+
+    fn list_review_threads(&self, pr: &PullRequestDetails) -> Result<Vec<RemoteReviewThread>> {
+        let base = repo_api_base(&pr.repository);
+        let mut comments: Vec<GtReviewComment> = Vec::new();
+        // Inline comments are only reachable per review.
+        for review in self.list_reviews(pr)? {
+            if review.comments_count == 0 {
+                continue;
+            }
+            let url = format!("{base}/pulls/{}/reviews/{}/comments", pr.number, review.id);
+            let batch: Vec<GtReviewComment> = self.get_json(&pr.repository, &url)?;
+            comments.extend(batch);
+        }
+        Ok(group_into_review_threads(comments))
+    }
+
+    fn list_review_summaries(&self, pr: &PullRequestDetails) -> Result<Vec<RemoteReviewSummary>> {
+        Ok(self
+            .list_reviews(pr)?
+            .into_iter()
+            .filter_map(GtReview::into_review_summary)
+            .collect())
+    }
+
+    fn list_pull_request_commits(&self, pr: &PullRequestDetails) -> Result<Vec<PullRequestCommit>> {
+        let url = format!(
+            "{}/pulls/{}/commits?verification=false&files=false",
+            repo_api_base(&pr.repository),
+            pr.number
+        );
+        let rows: Vec<GtCommit> = self.collect_pages(&pr.repository, &url, |_| false)?;
+        let mut commits: Vec<PullRequestCommit> = rows
+            .into_iter()
+            .map(GtCommit::into_pull_request_commit)
+            .collect();
+        // Gitea has listed PR commits both newest-first (`git rev-list`
+        // order) and oldest-first across versions. The head commit pins the
+        // order: it must come last.
+        if commits.len() > 1 && commits.first().is_some_and(|c| c.oid == pr.head_sha) {
+            commits.reverse();
+        }
+        Ok(commits)
+    }
+
+    fn list_pull_request_review_metadata(
+        &self,
+        pr: &PullRequestDetails,
+    ) -> Result<PullRequestReviewMetadata> {
+        let reviews = self.list_reviews(pr)?;
+        Ok(PullRequestReviewMetadata {
+            viewer_login: self.current_user_login(&pr.repository).unwrap_or_default(),
+            reviews: reviews.iter().map(GtReview::review_record).collect(),
+        })
+    }
+
+    fn create_review(
+        &self,
+        pr: &PullRequestDetails,
+        request: CreateReviewRequest<'_>,
+    ) -> Result<GhCreateReviewResponse> {
+        let event = match request.event {
+            SubmitEvent::Approve => "APPROVED",
+            SubmitEvent::RequestChanges => "REQUEST_CHANGES",
+            SubmitEvent::Comment => "COMMENT",
+            SubmitEvent::Draft => "PENDING",
+        };
+        // Gitea anchors on a single line; ranges land on their last line.
+        let comments: Vec<_> = request
+            .comments
+            .iter()
+            .map(|comment| {
+                let (new_position, old_position) = match comment.side {
+                    GhSide::Right => (comment.line, 0),
+                    GhSide::Left => (0, comment.line),
+                };
+                json!({
+                    "path": comment.path.to_string_lossy().replace('\\', "/"),
+                    "body": comment.body,
+                    "new_position": new_position,
+                    "old_position": old_position,
+                })
+            })
+            .collect();
+        let payload = json!({
+            "commit_id": request.commit_id,
+            "body": request.body,
+            "event": event,
+            "comments": comments,
+        });
+        let url = format!(
+            "{}/pulls/{}/reviews",
+            repo_api_base(&pr.repository),
+            pr.number
+        );
+        let output = self.post(&pr.repository, &url, &payload)?;
+        let review: GtReview = serde_json::from_str(&output)?;
+        let html_url = if review.html_url.is_empty() {
+            pr.url.clone()
+        } else {
+            review.html_url
+        };
+        Ok(GhCreateReviewResponse {
+            id: review.id,
+            html_url,
+            state: review.state,
+        })
+    }
+
+    fn reply_to_review_thread(
+        &self,
+        pr: &PullRequestDetails,
+        thread: &RemoteReviewThread,
+        body: &str,
+    ) -> Result<()> {
+        // There is no reply endpoint: the web UI groups comments by path,
+        // side, and line, so a one-comment review at the thread's anchor
+        // lands in the same conversation.
+        let line = thread.line.ok_or_else(|| {
+            TuicrError::UnsupportedOperation(
+                "Cannot reply to a Gitea conversation without a line anchor".to_string(),
+            )
+        })?;
+        let (new_position, old_position) = match thread.side {
+            RemoteCommentSide::Right => (line, 0),
+            RemoteCommentSide::Left => (0, line),
+        };
+        let payload = json!({
+            "commit_id": pr.head_sha,
+            "body": "",
+            "event": "COMMENT",
+            "comments": [{
+                "path": thread.path,
+                "body": body,
+                "new_position": new_position,
+                "old_position": old_position,
+            }],
+        });
+        let url = format!(
+            "{}/pulls/{}/reviews",
+            repo_api_base(&pr.repository),
+            pr.number
+        );
+        self.post(&pr.repository, &url, &payload)?;
+        Ok(())
+    }
+}
+
+fn map_http_error(error: GiteaHttpError, host: &str) -> TuicrError {
+    match error {
+        GiteaHttpError::Auth(detail) => TuicrError::Forge(format!(
+            "{host} rejected the request. Add an access token with repository read/write scope \
+             under [forge.tokens] as `\"{host}\" = \"<token>\"`, log in with `tea login add`, \
+             or list {host} in {HOSTS_ENV_VAR} and set GITEA_TOKEN (or FORGEJO_TOKEN).\n{}",
+            trim_detail(&detail)
+        )),
+        GiteaHttpError::Failed { status, body } => {
+            let status = status.map(|s| format!(" (HTTP {s})")).unwrap_or_default();
+            TuicrError::Forge(format!(
+                "Gitea request to {host} failed{status}: {}",
+                trim_detail(&body)
+            ))
+        }
+    }
+}
+
+/// Keep error detail readable: collapse whitespace and cap the length.
+fn trim_detail(detail: &str) -> String {
+    let collapsed = detail.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.len() > 400 {
+        let cut = (0..=400)
+            .rev()
+            .find(|&i| collapsed.is_char_boundary(i))
+            .unwrap_or(0);
+        format!("{}…", &collapsed[..cut])
+    } else {
+        collapsed
+    }
+}
+
+// ---------- URL & target parsing ----------
+
+/// True when `host` names a Gitea / Forgejo instance: a well-known public
+/// host, a hostname that says so (`gitea.example.com`, `forgejo.internal`),
+/// a host listed in `GITEA_HOSTS`, or a host the `tea` CLI has a login for
+/// (the last two cover custom domains).
+pub(crate) fn is_gitea_host(host: &str) -> bool {
+    let host = host.to_ascii_lowercase();
+    if KNOWN_GITEA_HOSTS.contains(&host.as_str())
+        || host.contains("gitea")
+        || host.contains("forgejo")
+        || host_listed(HOSTS_ENV_VAR, &host)
+    {
+        return true;
+    }
+    tea_logins().iter().any(|login| login.host == host)
+}
+
+/// Parse a Gitea / Forgejo remote (git) URL into a `ForgeRepository`.
+///
+/// Accepts `https://host[:port]/owner/repo[.git]`, `ssh://git@host[:port]/owner/repo`
+/// and `git@host:owner/repo.git`. Returns `None` unless [`is_gitea_host`]
+/// recognizes the host, so it never claims GitHub or GitLab remotes. An
+/// HTTP(S) URL's scheme and port are kept as the repository's web root.
+pub fn parse_gitea_remote_url(remote_url: &str) -> Option<ForgeRepository> {
+    let trimmed = trim_url_suffix(remote_url.trim());
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    if let Some((host, path)) = parse_scp_like_remote(trimmed) {
+        if !is_gitea_host(host) {
+            return None;
+        }
+        return gitea_from_path(host, path);
+    }
+
+    let parts = split_url(trimmed)?;
+    if !is_gitea_host(parts.host()) {
+        return None;
+    }
+    Some(gitea_from_path(parts.host(), parts.path)?.with_web_root(parts.web_root("")))
+}
+
+/// `owner/repo` from the path after the host. Extra segments (a PR or file
+/// URL) are ignored.
+fn gitea_from_path(host: &str, path: &str) -> Option<ForgeRepository> {
+    let mut segments = path.split('/').filter(|s| !s.is_empty());
+    let owner = segments.next()?;
+    let repo = strip_git_suffix(segments.next()?);
+    if owner.is_empty() || repo.is_empty() {
+        return None;
+    }
+    Some(ForgeRepository::gitea(
+        host.to_ascii_lowercase(),
+        owner,
+        repo,
+    ))
+}
+
+/// Parse a PR target: a bare number or a Gitea PR web URL
+/// (`https://host/owner/repo/pulls/<n>`).
+pub fn parse_pull_request_target_gitea(input: &str) -> Result<PullRequestTarget> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return malformed_target(input);
+    }
+
+    if let Some(target) = parse_numeric_target(trimmed) {
+        return Ok(target);
+    }
+    if let Some(target) = parse_gitea_url_target(trimmed) {
+        return Ok(target);
+    }
+    malformed_target(input)
+}
+
+fn parse_numeric_target(target: &str) -> Option<PullRequestTarget> {
+    if !target.chars().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    let number = target.parse::<u64>().ok()?;
+    if number == 0 {
+        return None;
+    }
+    Some(PullRequestTarget::number(number, target))
+}
+
+fn parse_gitea_url_target(target: &str) -> Option<PullRequestTarget> {
+    let url = split_url(target).filter(|url| url.scheme != "ssh")?;
+    let repository = parse_gitea_remote_url(target)?;
+    let mut parts = url.path.split('/').filter(|s| !s.is_empty()).skip(2);
+    if parts.next()? != "pulls" {
+        return None;
+    }
+    let number = parts.next()?.parse::<u64>().ok()?;
+    if number == 0 {
+        return None;
+    }
+    Some(PullRequestTarget::with_repository(
+        repository, number, target,
+    ))
+}
+
+fn malformed_target<T>(input: &str) -> Result<T> {
+    Err(TuicrError::Forge(format!(
+        "Malformed Gitea pull request target: `{input}`"
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::forge::remote_comments::RemoteReviewComment;
+    use crate::forge::submit::InlineComment;
+
+    fn gitea_repo() -> ForgeRepository {
+        ForgeRepository::gitea("codeberg.org", "infra", "deploy-tools")
+    }
+
+    // ---- URL parsing ----
+
+    #[test]
+    fn parses_https_remote_on_known_host() {
+        let repo = parse_gitea_remote_url("https://codeberg.org/infra/deploy-tools.git").unwrap();
+        assert_eq!(repo, gitea_repo());
+    }
+
+    #[test]
+    fn parses_scp_and_ssh_remotes_on_named_host() {
+        let scp = parse_gitea_remote_url("git@forgejo.example.com:infra/deploy-tools.git").unwrap();
+        assert_eq!(
+            scp,
+            ForgeRepository::gitea("forgejo.example.com", "infra", "deploy-tools")
+        );
+        let ssh =
+            parse_gitea_remote_url("ssh://git@gitea.internal:2222/infra/deploy-tools.git").unwrap();
+        assert_eq!(ssh.host, "gitea.internal");
+    }
+
+    #[test]
+    fn rejects_unrecognized_hosts() {
+        assert!(parse_gitea_remote_url("https://github.com/agavra/tuicr").is_none());
+        assert!(parse_gitea_remote_url("git@gitlab.com:owner/repo.git").is_none());
+    }
+
+    #[test]
+    fn parses_pr_web_url_target() {
+        let target =
+            parse_pull_request_target_gitea("https://codeberg.org/infra/deploy-tools/pulls/17")
+                .unwrap();
+        assert_eq!(target.number, 17);
+        assert_eq!(target.repository.unwrap(), gitea_repo());
+    }
+
+    #[test]
+    fn rejects_non_pull_url_target() {
+        assert!(
+            parse_pull_request_target_gitea("https://codeberg.org/infra/deploy-tools/issues/17")
+                .is_err()
+        );
+    }
+
+    // ---- tea config ----
+
+    #[test]
+    fn parses_tea_logins() {
+        let config = r#"logins:
+- name: codeberg
+  url: https://codeberg.org
+  token: abc123
+  default: false
+- name: work
+  url: "https://git.corp.example:3000"
+  token: ""
+preferences:
+  editor: false
+"#;
+        assert_eq!(
+            parse_tea_logins(config),
+            vec![
+                TeaLogin {
+                    host: "codeberg.org".to_string(),
+                    token: Some("abc123".to_string()),
+                },
+                TeaLogin {
+                    host: "git.corp.example".to_string(),
+                    token: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn resolves_tokens_per_host_before_the_env_vars() {
+        let forge = ForgeConfig {
+            tokens: [("git.corp.example".to_string(), "cfg".to_string())].into(),
+            ..ForgeConfig::default()
+        };
+        let logins = vec![
+            TeaLogin {
+                host: "git.corp.example".to_string(),
+                token: Some("tea-corp".to_string()),
+            },
+            TeaLogin {
+                host: "codeberg.org".to_string(),
+                token: Some("tea-codeberg".to_string()),
+            },
+        ];
+        let env = |name: &str| match name {
+            "GITEA_HOSTS" => Some("gitea.internal".to_string()),
+            "GITEA_TOKEN" => Some("env".to_string()),
+            _ => None,
+        };
+        let token = |host: &str| token_for_host(&forge, host, &logins, &env);
+        assert_eq!(token("git.corp.example").as_deref(), Some("cfg"));
+        assert_eq!(token("codeberg.org").as_deref(), Some("tea-codeberg"));
+        assert_eq!(token("gitea.internal").as_deref(), Some("env"));
+        assert_eq!(token("gitea.evil.example"), None);
+    }
+
+    #[test]
+    fn keeps_the_port_of_a_pr_url() {
+        let target = parse_pull_request_target_gitea(
+            "http://gitea.internal:3000/infra/deploy-tools/pulls/17",
+        )
+        .unwrap();
+        let repository = target.repository.unwrap();
+        assert_eq!(target.number, 17);
+        assert_eq!(
+            repo_api_base(&repository),
+            "http://gitea.internal:3000/api/v1/repos/infra/deploy-tools"
+        );
+    }
+
+    // ---- Backend calls (via a recording transport) ----
+
+    type Call = (String, String, Option<String>);
+
+    /// Canned-response stand-in for the HTTP API: records every call and
+    /// replays the queued bodies in order.
+    #[derive(Clone, Default)]
+    struct RecordingHttp {
+        calls: Arc<Mutex<Vec<Call>>>,
+        responses: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl RecordingHttp {
+        fn new(responses: Vec<&str>) -> Self {
+            Self {
+                calls: Arc::default(),
+                responses: Arc::new(Mutex::new(
+                    responses.into_iter().map(str::to_string).collect(),
+                )),
+            }
+        }
+
+        fn calls(&self) -> Vec<Call> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    impl GiteaHttp for RecordingHttp {
+        fn request(&self, method: &str, url: &str, body: Option<&str>) -> GiteaHttpResult<String> {
+            self.calls.lock().unwrap().push((
+                method.to_string(),
+                url.to_string(),
+                body.map(str::to_string),
+            ));
+            let mut responses = self.responses.lock().unwrap();
+            Ok(if responses.is_empty() {
+                "[]".to_string()
+            } else {
+                responses.remove(0)
+            })
+        }
+    }
+
+    fn backend(http: &RecordingHttp) -> GiteaBackend {
+        GiteaBackend::with_transport(Some(gitea_repo()), Box::new(http.clone()))
+    }
+
+    fn pr_details() -> PullRequestDetails {
+        PullRequestDetails {
+            repository: gitea_repo(),
+            number: 17,
+            title: "t".to_string(),
+            url: "https://codeberg.org/infra/deploy-tools/pulls/17".to_string(),
+            state: "OPEN".to_string(),
+            is_draft: false,
+            author: None,
+            head_ref_name: "feature".to_string(),
+            base_ref_name: "main".to_string(),
+            head_sha: "head111".to_string(),
+            base_sha: "base000".to_string(),
+            body: String::new(),
+            updated_at: None,
+            closed: false,
+            merged_at: None,
+            diff_start_sha: None,
+        }
+    }
+
+    fn body_json(call: &Call) -> serde_json::Value {
+        serde_json::from_str(call.2.as_deref().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn get_pull_request_reads_pulls_endpoint() {
+        let http = RecordingHttp::new(vec![
+            r#"{"number":17,"title":"t","state":"open","head":{"ref":"feature","sha":"head111"},"base":{"ref":"main","sha":"base000"}}"#,
+        ]);
+        let details = backend(&http)
+            .get_pull_request(PullRequestTarget::number(17, "17"))
+            .unwrap();
+        assert_eq!(details.head_sha, "head111");
+        assert_eq!(details.state, "OPEN");
+        assert_eq!(
+            http.calls()[0].1,
+            "https://codeberg.org/api/v1/repos/infra/deploy-tools/pulls/17"
+        );
+    }
+
+    #[test]
+    fn get_pull_request_diff_pairs_files_with_unified_diff() {
+        let http = RecordingHttp::new(vec![
+            r#"[{"filename":"src/lib.rs","status":"changed"}]"#,
+            "diff --git a/src/lib.rs b/src/lib.rs\nindex 1111111..2222222 100644\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1 @@\n-old\n+new\n",
+        ]);
+        let patches = backend(&http).get_pull_request_diff(&pr_details()).unwrap();
+        assert_eq!(patches.len(), 1);
+        assert_eq!(patches[0].new_path, Some(PathBuf::from("src/lib.rs")));
+        assert!(patches[0].patch.contains("+new"));
+
+        let calls = http.calls();
+        assert!(calls[0].1.contains("/pulls/17/files?page=1&limit=50"));
+        assert!(calls[1].1.ends_with("/pulls/17.diff"));
+    }
+
+    #[test]
+    fn list_requested_scope_filters_on_requested_reviewers() {
+        let http = RecordingHttp::new(vec![
+            r#"{"login":"alice"}"#,
+            r#"[
+                {"number":1,"title":"mine","state":"open","requested_reviewers":[{"login":"alice"}]},
+                {"number":2,"title":"other","state":"open","requested_reviewers":[{"login":"bob"}]}
+            ]"#,
+        ]);
+        let query = PullRequestListQuery::first_page_with_scope(
+            gitea_repo(),
+            30,
+            PullRequestListScope::ReviewRequested,
+        );
+        let page = backend(&http).list_pull_requests(query).unwrap();
+        assert_eq!(page.pull_requests.len(), 1);
+        assert_eq!(page.pull_requests[0].number, 1);
+        assert!(!page.has_more);
+        assert!(http.calls()[0].1.ends_with("/api/v1/user"));
+    }
+
+    #[test]
+    fn list_commits_returns_oldest_first() {
+        let http = RecordingHttp::new(vec![
+            r#"[
+                {"sha":"head111","commit":{"message":"second","author":{"name":"A"}}},
+                {"sha":"aaa0001","commit":{"message":"first\n\nbody","author":{"name":"A"}}}
+            ]"#,
+        ]);
+        let commits = backend(&http)
+            .list_pull_request_commits(&pr_details())
+            .unwrap();
+        assert_eq!(commits[0].summary, "first");
+        assert_eq!(commits[1].oid, "head111");
+    }
+
+    #[test]
+    fn list_review_threads_fetches_comments_per_review() {
+        let http = RecordingHttp::new(vec![
+            r#"[
+                {"id":5,"state":"COMMENT","comments_count":1},
+                {"id":6,"state":"APPROVED","comments_count":0},
+                {"id":7,"state":"REQUEST_REVIEW","comments_count":0}
+            ]"#,
+            r#"[{"id":40,"body":"why?","user":{"login":"bob"},"path":"src/lib.rs","position":3,"original_position":0}]"#,
+        ]);
+        let threads = backend(&http).list_review_threads(&pr_details()).unwrap();
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].line, Some(3));
+
+        let calls = http.calls();
+        assert_eq!(calls.len(), 2);
+        assert!(calls[1].1.ends_with("/pulls/17/reviews/5/comments"));
+    }
+
+    fn inline(line: u32, side: GhSide) -> InlineComment {
+        InlineComment {
+            path: PathBuf::from("src/lib.rs"),
+            line,
+            side,
+            counterpart_line: None,
+            start_line: None,
+            start_side: None,
+            range_anchors: None,
+            old_path: None,
+            body: "please fix".to_string(),
+            comment_id: "c1".to_string(),
+        }
+    }
+
+    #[test]
+    fn create_review_posts_event_and_positioned_comments() {
+        let http = RecordingHttp::new(vec![
+            r#"{"id":99,"state":"REQUEST_CHANGES","html_url":"https://codeberg.org/infra/deploy-tools/pulls/17#issuecomment-99"}"#,
+        ]);
+        let request = CreateReviewRequest {
+            event: SubmitEvent::RequestChanges,
+            commit_id: "head111",
+            body: "needs work",
+            comments: &[inline(10, GhSide::Right), inline(4, GhSide::Left)],
+        };
+        let response = backend(&http)
+            .create_review(&pr_details(), request)
+            .unwrap();
+        assert_eq!(response.id, 99);
+        assert_eq!(response.state, "REQUEST_CHANGES");
+
+        let calls = http.calls();
+        assert_eq!(calls[0].0, "POST");
+        assert!(calls[0].1.ends_with("/pulls/17/reviews"));
+        let body = body_json(&calls[0]);
+        assert_eq!(body["event"], "REQUEST_CHANGES");
+        assert_eq!(body["commit_id"], "head111");
+        assert_eq!(body["comments"][0]["new_position"], 10);
+        assert_eq!(body["comments"][0]["old_position"], 0);
+        assert_eq!(body["comments"][1]["new_position"], 0);
+        assert_eq!(body["comments"][1]["old_position"], 4);
+    }
+
+    #[test]
+    fn reply_posts_single_comment_review_at_thread_anchor() {
+        let http = RecordingHttp::new(vec![r#"{"id":100,"state":"COMMENT"}"#]);
+        let thread = RemoteReviewThread {
+            id: "40".to_string(),
+            path: "src/lib.rs".to_string(),
+            line: Some(3),
+            side: RemoteCommentSide::Left,
+            is_resolved: false,
+            is_outdated: false,
+            comments: vec![RemoteReviewComment {
+                id: "40".to_string(),
+                author: Some("bob".to_string()),
+                body: "why?".to_string(),
+                created_at: None,
+                in_reply_to: None,
+                url: String::new(),
+            }],
+        };
+        backend(&http)
+            .reply_to_review_thread(&pr_details(), &thread, "because")
+            .unwrap();
+
+        let body = body_json(&http.calls()[0]);
+        assert_eq!(body["event"], "COMMENT");
+        assert_eq!(body["comments"][0]["path"], "src/lib.rs");
+        assert_eq!(body["comments"][0]["old_position"], 3);
+        assert_eq!(body["comments"][0]["body"], "because");
+    }
+
+    #[test]
+    fn backend_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<GiteaBackend>();
+    }
+}
@@ -0,0 +1,10 @@
+//! Gitea / Forgejo forge integration.
+//!
+//! Forgejo is a Gitea fork that keeps the `/api/v1` REST API compatible, so
+//! one backend serves both. It talks to the API directly over HTTPS rather
+//! than through a CLI, since the `tea` CLI exposes no raw API passthrough.
+
+pub mod api;
+pub mod models;
+
+pub use api::GiteaBackend;
@@ -0,0 +1,551 @@
+//! Gitea / Forgejo REST JSON structs and their mapping into tuicr's
+//! forge-agnostic trait types.
+//!
+//! Modeled on `src/forge/azure/models.rs`. Gitea returns bare JSON arrays for
+//! lists (pagination is `?page=N&limit=M`), and many string fields arrive as
+//! `null` on older instances, so optional text is read through `Option`.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::error::{Result, TuicrError};
+use crate::forge::remote_comments::{
+    RemoteCommentSide, RemoteReviewComment, RemoteReviewState, RemoteReviewSummary,
+    RemoteReviewThread,
+};
+use crate::forge::traits::{
+    ForgeRepository, PullRequestCommit, PullRequestDetails, PullRequestReviewRecord,
+    PullRequestSummary,
+};
+use crate::model::FileStatus;
+use crate::vcs::git::raw::FileMetadata;
+
+/// A Gitea account (author, reviewer, resolver, authenticated user).
+#[derive(Debug, Deserialize, Default)]
+pub struct GtUser {
+    #[serde(default)]
+    pub login: String,
+}
+
+impl GtUser {
+    fn handle(&self) -> Option<String> {
+        (!self.login.is_empty()).then(|| self.login.clone())
+    }
+}
+
+/// One side of a PR (`head` / `base`).
+#[derive(Debug, Deserialize, Default)]
+pub struct GtBranch {
+    #[serde(default, rename = "ref")]
+    pub ref_name: String,
+    #[serde(default)]
+    pub sha: String,
+}
+
+/// A pull request as returned by both `GET .../pulls/{index}` and each element
+/// of `GET .../pulls`.
+#[derive(Debug, Deserialize)]
+pub struct GtPullRequest {
+    pub number: u64,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub body: Option<String>,
+    /// `open` | `closed`. Merged PRs are `closed` with `merged = true`.
+    #[serde(default)]
+    pub state: String,
+    /// Reported by Forgejo and newer Gitea; older instances only mark drafts
+    /// with a `WIP:` title prefix.
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub merged: bool,
+    #[serde(default)]
+    pub merged_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub html_url: String,
+    #[serde(default)]
+    pub user: Option<GtUser>,
+    #[serde(default)]
+    pub head: Option<GtBranch>,
+    #[serde(default)]
+    pub base: Option<GtBranch>,
+    /// Merge base of head and base. Gitea's PR diff is taken against this
+    /// commit, so it is the right "base" for context expansion.
+    #[serde(default)]
+    pub merge_base: Option<String>,
+    #[serde(default)]
+    pub requested_reviewers: Option<Vec<GtUser>>,
+}
+
+impl GtPullRequest {
+    fn title(&self) -> String {
+        self.title.clone().unwrap_or_default()
+    }
+
+    fn is_draft(&self) -> bool {
+        let title = self.title.as_deref().unwrap_or_default();
+        self.draft
+            || ["WIP:", "[WIP]"]
+                .iter()
+                .any(|prefix| title.to_ascii_uppercase().starts_with(prefix))
+    }
+
+    fn state(&self) -> String {
+        if self.merged {
+            "MERGED".to_string()
+        } else {
+            self.state.to_ascii_uppercase()
+        }
+    }
+
+    fn ref_name(branch: &Option<GtBranch>) -> String {
+        branch
+            .as_ref()
+            .map(|b| b.ref_name.clone())
+            .unwrap_or_default()
+    }
+
+    /// True when `login` is among the PR's pending review requests.
+    pub fn requests_review_from(&self, login: &str) -> bool {
+        self.requested_reviewers
+            .iter()
+            .flatten()
+            .any(|user| user.login.eq_ignore_ascii_case(login))
+    }
+
+    pub fn into_summary(self, repo: &ForgeRepository) -> PullRequestSummary {
+        PullRequestSummary {
+            repository: repo.clone(),
+            number: self.number,
+            title: self.title(),
+            author: self.user.as_ref().and_then(GtUser::handle),
+            head_ref_name: Self::ref_name(&self.head),
+            base_ref_name: Self::ref_name(&self.base),
+            updated_at: self.updated_at,
+            url: self.html_url.clone(),
+            state: self.state(),
+            is_draft: self.is_draft(),
+        }
+    }
+
+    pub fn into_details(self, repo: &ForgeRepository) -> PullRequestDetails {
+        let head_sha = self
+            .head
+            .as_ref()
+            .map(|b| b.sha.clone())
+            .unwrap_or_default();
+        let base_sha = self
+            .merge_base
+            .clone()
+            .filter(|sha| !sha.is_empty())
+            .or_else(|| self.base.as_ref().map(|b| b.sha.clone()))
+            .unwrap_or_default();
+        let merged_at = if self.merged {
+            self.merged_at.or(self.updated_at)
+        } else {
+            None
+        };
+        PullRequestDetails {
+            repository: repo.clone(),
+            number: self.number,
+            title: self.title(),
+            url: self.html_url.clone(),
+            state: self.state(),
+            is_draft: self.is_draft(),
+            author: self.user.as_ref().and_then(GtUser::handle),
+            head_ref_name: Self::ref_name(&self.head),
+            base_ref_name: Self::ref_name(&self.base),
+            head_sha,
+            base_sha,
+            body: self.body.unwrap_or_default(),
+            updated_at: self.updated_at,
+            closed: !self.merged && self.state.eq_ignore_ascii_case("closed"),
+            merged_at,
+            diff_start_sha: None,
+        }
+    }
+}
+
+/// Entry from `GET .../pulls/{index}/files`.
+#[derive(Debug, Deserialize)]
+pub struct GtChangedFile {
+    pub filename: String,
+    #[serde(default)]
+    pub previous_filename: Option<String>,
+    /// `added` | `deleted` | `changed` | `renamed` | `copied`.
+    #[serde(default)]
+    pub status: String,
+}
+
+impl GtChangedFile {
+    pub(crate) fn into_metadata(self) -> Result<FileMetadata> {
+        let path = PathBuf::from(&self.filename);
+        let previous = || {
+            self.previous_filename
+                .clone()
+                .filter(|p| !p.is_empty())
+                .map(PathBuf::from)
+                .ok_or_else(|| {
+                    TuicrError::Forge(format!(
+                        "Gitea {} file `{}` has no previous_filename",
+                        self.status, self.filename
+                    ))
+                })
+        };
+        Ok(match self.status.as_str() {
+            "added" => FileMetadata {
+                old_path: None,
+                new_path: Some(path),
+                status: FileStatus::Added,
+            },
+            "deleted" | "removed" => FileMetadata {
+                old_path: Some(path),
+                new_path: None,
+                status: FileStatus::Deleted,
+            },
+            "renamed" => FileMetadata {
+                old_path: Some(previous()?),
+                new_path: Some(path),
+                status: FileStatus::Renamed,
+            },
+            "copied" => FileMetadata {
+                old_path: Some(previous()?),
+                new_path: Some(path),
+                status: FileStatus::Copied,
+            },
+            _ => FileMetadata {
+                old_path: Some(path.clone()),
+                new_path: Some(path),
+                status: FileStatus::Modified,
+            },
+        })
+    }
+}
+
+/// A commit on a PR, from `GET .../pulls/{index}/commits`.
+#[derive(Debug, Deserialize)]
+pub struct GtCommit {
+    pub sha: String,
+    #[serde(default)]
+    pub commit: Option<GtCommitDetail>,
+    /// The linked Gitea account, when the commit email maps to one.
+    #[serde(default)]
+    pub author: Option<GtUser>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct GtCommitDetail {
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub author: Option<GtCommitUser>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct GtCommitUser {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub date: Option<DateTime<Utc>>,
+}
+
+impl GtCommit {
+    pub fn into_pull_request_commit(self) -> PullRequestCommit {
+        let short_oid = self.sha.chars().take(8).collect();
+        let detail = self.commit.unwrap_or_default();
+        let summary = detail
+            .message
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        let (git_author, timestamp) = match detail.author {
+            Some(a) => (a.name, a.date),
+            None => (String::new(), None),
+        };
+        let author = self
+            .author
+            .as_ref()
+            .and_then(GtUser::handle)
+            .unwrap_or(git_author);
+        PullRequestCommit {
+            oid: self.sha,
+            short_oid,
+            summary,
+            author,
+            timestamp,
+        }
+    }
+}
+
+/// A submitted (or pending) review, from `GET .../pulls/{index}/reviews` and
+/// the response to `POST .../pulls/{index}/reviews`.
+#[derive(Debug, Deserialize)]
+pub struct GtReview {
+    pub id: u64,
+    #[serde(default)]
+    pub user: Option<GtUser>,
+    #[serde(default)]
+    pub body: Option<String>,
+    /// `APPROVED` | `REQUEST_CHANGES` | `COMMENT` | `PENDING` | `REQUEST_REVIEW`.
+    #[serde(default)]
+    pub state: String,
+    #[serde(default)]
+    pub commit_id: Option<String>,
+    #[serde(default)]
+    pub dismissed: bool,
+    #[serde(default)]
+    pub comments_count: u64,
+    #[serde(default)]
+    pub submitted_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub html_url: String,
+}
+
+impl GtReview {
+    /// `REQUEST_REVIEW` entries are review requests, not reviews.
+    pub fn is_review(&self) -> bool {
+        !self.state.eq_ignore_ascii_case("REQUEST_REVIEW")
+    }
+
+    pub fn review_record(&self) -> PullRequestReviewRecord {
+        PullRequestReviewRecord {
+            author: self.user.as_ref().and_then(GtUser::handle),
+            submitted_at: self.submitted_at,
+            commit_oid: self.commit_id.clone().filter(|sha| !sha.is_empty()),
+        }
+    }
+
+    /// Map to a review-level summary, or `None` for bodiless reviews.
+    pub fn into_review_summary(self) -> Option<RemoteReviewSummary> {
+        let body = self.body.filter(|b| !b.trim().is_empty())?;
+        let state = if self.dismissed {
+            RemoteReviewState::Dismissed
+        } else {
+            match self.state.to_ascii_uppercase().as_str() {
+                "REQUEST_CHANGES" => RemoteReviewState::ChangesRequested,
+                other => RemoteReviewState::parse(other),
+            }
+        };
+        Some(RemoteReviewSummary {
+            id: self.id.to_string(),
+            author: self.user.as_ref().and_then(GtUser::handle),
+            body,
+            state,
+            created_at: self.submitted_at,
+            url: self.html_url,
+        })
+    }
+}
+
+/// An inline comment, from `GET .../pulls/{index}/reviews/{id}/comments`.
+///
+/// Gitea reports the anchor as a plain file line number: `position` on the
+/// new side, or `original_position` on the old side (the other is `0`).
+#[derive(Debug, Deserialize)]
+pub struct GtReviewComment {
+    pub id: u64,
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub user: Option<GtUser>,
+    /// Set when the conversation was marked resolved.
+    #[serde(default)]
+    pub resolver: Option<GtUser>,
+    #[serde(default)]
+    pub path: String,
+    #[serde(default)]
+    pub position: u32,
+    #[serde(default)]
+    pub original_position: u32,
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub html_url: String,
+}
+
+impl GtReviewComment {
+    fn anchor(&self) -> Option<(u32, RemoteCommentSide)> {
+        if self.position != 0 {
+            Some((self.position, RemoteCommentSide::Right))
+        } else if self.original_position != 0 {
+            Some((self.original_position, RemoteCommentSide::Left))
+        } else {
+            None
+        }
+    }
+}
+
+/// Group inline comments into conversations.
+///
+/// Gitea has no thread object: the web UI shows every comment on the same
+/// path, side, and line as one conversation, so the same key groups them
+/// here. The earliest comment is the root and its id is the thread id.
+pub fn group_into_review_threads(mut comments: Vec<GtReviewComment>) -> Vec<RemoteReviewThread> {
+    comments.sort_by_key(|c| (c.created_at, c.id));
+
+    let mut threads: Vec<RemoteReviewThread> = Vec::new();
+    let mut index_of: HashMap<(String, bool, u32), usize> = HashMap::new();
+    for comment in comments {
+        if comment.path.is_empty() || comment.body.trim().is_empty() {
+            continue;
+        }
+        let Some((line, side)) = comment.anchor() else {
+            continue;
+        };
+        let key = (comment.path.clone(), side == RemoteCommentSide::Left, line);
+        let resolved = comment.resolver.is_some();
+        let remote = RemoteReviewComment {
+            id: comment.id.to_string(),
+            author: comment.user.as_ref().and_then(GtUser::handle),
+            body: comment.body,
+            created_at: comment.created_at,
+            in_reply_to: None,
+            url: comment.html_url,
+        };
+        match index_of.get(&key) {
+            Some(&index) => {
+                let thread = &mut threads[index];
+                let mut remote = remote;
+                remote.in_reply_to = thread.root().map(|root| root.id.clone());
+                thread.is_resolved |= resolved;
+                thread.comments.push(remote);
+            }
+            None => {
+                index_of.insert(key, threads.len());
+                threads.push(RemoteReviewThread {
+                    id: remote.id.clone(),
+                    path: comment.path,
+                    line: Some(line),
+                    side,
+                    is_resolved: resolved,
+                    is_outdated: false,
+                    comments: vec![remote],
+                });
+            }
+        }
+    }
+    threads
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gitea_repo() -> ForgeRepository {
+        ForgeRepository::gitea("git.example.com", "infra", "deploy-tools")
+    }
+
+    #[test]
+    fn should_map_open_pr_into_details_using_merge_base() {
+        let json = r#"{
+            "number": 17,
+            "title": "Add retries",
+            "body": null,
+            "state": "open",
+            "merged": false,
+            "html_url": "https://git.example.com/infra/deploy-tools/pulls/17",
+            "user": { "login": "alice" },
+            "head": { "ref": "retries", "sha": "head111" },
+            "base": { "ref": "main", "sha": "tip000" },
+            "merge_base": "base000",
+            "updated_at": "2026-03-01T10:00:00+02:00"
+        }"#;
+        let pr: GtPullRequest = serde_json::from_str(json).unwrap();
+        let details = pr.into_details(&gitea_repo());
+        assert_eq!(details.state, "OPEN");
+        assert_eq!(details.head_sha, "head111");
+        assert_eq!(details.base_sha, "base000");
+        assert_eq!(details.head_ref_name, "retries");
+        assert_eq!(details.base_ref_name, "main");
+        assert_eq!(details.author.as_deref(), Some("alice"));
+        assert_eq!(details.body, "");
+        assert!(!details.is_read_only());
+    }
+
+    #[test]
+    fn should_mark_merged_pr_read_only() {
+        let json = r#"{
+            "number": 3,
+            "title": "Done",
+            "state": "closed",
+            "merged": true,
+            "merged_at": "2026-03-02T00:00:00Z"
+        }"#;
+        let pr: GtPullRequest = serde_json::from_str(json).unwrap();
+        let details = pr.into_details(&gitea_repo());
+        assert_eq!(details.state, "MERGED");
+        assert!(!details.closed);
+        assert_eq!(details.read_only_reason(), Some("merged"));
+    }
+
+    #[test]
+    fn should_treat_wip_title_as_draft() {
+        let json = r#"{ "number": 4, "title": "WIP: half done", "state": "open" }"#;
+        let pr: GtPullRequest = serde_json::from_str(json).unwrap();
+        assert!(pr.into_summary(&gitea_repo()).is_draft);
+    }
+
+    #[test]
+    fn should_map_changed_file_statuses() {
+        let renamed: GtChangedFile = serde_json::from_str(
+            r#"{ "filename": "src/new.rs", "previous_filename": "src/old.rs", "status": "renamed" }"#,
+        )
+        .unwrap();
+        let metadata = renamed.into_metadata().unwrap();
+        assert_eq!(metadata.status, FileStatus::Renamed);
+        assert_eq!(metadata.old_path, Some(PathBuf::from("src/old.rs")));
+
+        let changed: GtChangedFile =
+            serde_json::from_str(r#"{ "filename": "src/lib.rs", "status": "changed" }"#).unwrap();
+        assert_eq!(
+            changed.into_metadata().unwrap().status,
+            FileStatus::Modified
+        );
+    }
+
+    #[test]
+    fn should_group_comments_on_same_line_into_one_thread() {
+        let json = r#"[
+            { "id": 11, "body": "reply", "user": { "login": "bob" }, "path": "src/lib.rs",
+              "position": 12, "original_position": 0, "created_at": "2026-03-01T11:00:00Z",
+              "resolver": { "login": "alice" } },
+            { "id": 10, "body": "why?", "user": { "login": "alice" }, "path": "src/lib.rs",
+              "position": 12, "original_position": 0, "created_at": "2026-03-01T10:00:00Z" },
+            { "id": 12, "body": "old side", "path": "src/lib.rs",
+              "position": 0, "original_position": 12, "created_at": "2026-03-01T12:00:00Z" }
+        ]"#;
+        let comments: Vec<GtReviewComment> = serde_json::from_str(json).unwrap();
+        let threads = group_into_review_threads(comments);
+        assert_eq!(threads.len(), 2);
+        assert_eq!(threads[0].id, "10");
+        assert_eq!(threads[0].side, RemoteCommentSide::Right);
+        assert_eq!(threads[0].comments.len(), 2);
+        assert_eq!(threads[0].comments[1].in_reply_to.as_deref(), Some("10"));
+        assert!(threads[0].is_resolved);
+        assert_eq!(threads[1].side, RemoteCommentSide::Left);
+        assert_eq!(threads[1].line, Some(12));
+    }
+
+    #[test]
+    fn should_skip_bodiless_reviews_in_summaries() {
+        let json = r#"[
+            { "id": 1, "state": "APPROVED", "body": "" },
+            { "id": 2, "state": "REQUEST_CHANGES", "body": "needs tests", "user": { "login": "carol" } }
+        ]"#;
+        let reviews: Vec<GtReview> = serde_json::from_str(json).unwrap();
+        let summaries: Vec<_> = reviews
+            .into_iter()
+            .filter_map(GtReview::into_review_summary)
+            .collect();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].state, RemoteReviewState::ChangesRequested);
+        assert_eq!(summaries[0].author.as_deref(), Some("carol"));
+    }
+}
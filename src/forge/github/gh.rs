@@ -3,11 +3,11 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::error::{Result, TuicrError};
-use crate::forge::remote_comments::{RemoteReviewSummary, RemoteReviewThread};
+use crate::forge::remote_comments::{PendingReview, RemoteReviewSummary, RemoteReviewThread};
 use crate::forge::traits::{
-    ForgeBackend, ForgeFileLinesRequest, ForgeRepository, GhCreateReviewResponse,
-    PagedPullRequests, PullRequestCommit, PullRequestDetails, PullRequestInfo,
-    PullRequestListQuery, PullRequestListScope, PullRequestTarget,
+    ForgeBackend, ForgeFileLinesRequest, ForgeRepository, GhCreateReviewResponse, MergeMethod,
+    PagedPullRequests, PullRequestCheckStatus, PullRequestCommit, PullRequestDetails,
+    PullRequestInfo, PullRequestListQuery, PullRequestListScope, PullRequestTarget,
 };
 use crate::model::{DiffLine, FilePatch};
 use crate::process::{
@@ -17,11 +17,19 @@ use crate::vcs::git::raw::{FileMetadata, pair_metadata_with_patch, run_git_diff}
 use crate::vcs::slice_context_lines;
 
 use super::models::{GhCompare, GhPrCommit, GhPullRequestFile, GhPullRequestSummary};
+use super::pending_review::{
+    ADD_THREAD_MUTATION, DELETE_COMMENT_MUTATION, DELETE_REVIEW_MUTATION, PENDING_REVIEW_QUERY,
+    SUBMIT_REVIEW_MUTATION, UPDATE_COMMENT_MUTATION, UPDATE_REVIEW_MUTATION, combined_body,
+    parse_pending_comments, parse_pending_review, parse_review_mutation_response,
+};
 use super::review_summaries::{
     build_query as build_reviews_query, parse_graphql_page as parse_reviews_page,
 };
 use super::review_threads::{build_query, parse_graphql_page};
 use super::submit::build_review_payload;
+use super::thread_mutations::{
+    REPLY_MUTATION, RESOLVE_MUTATION, UNRESOLVE_MUTATION, check_mutation_response,
+};
 use crate::forge::traits::CreateReviewRequest;
 
 const DEFAULT_GITHUB_HOST: &str = "github.com";
@@ -97,12 +105,12 @@ impl From<CommandOutputError> for GhCommandError {
     fn from(error: CommandOutputError) -> Self {
         match error.kind {
             CommandOutputErrorKind::NotFound => Self::MissingGh,
-            CommandOutputErrorKind::SpawnFailed | CommandOutputErrorKind::Unsuccessful => {
-                Self::Failed {
-                    status: error.status,
-                    stderr: error.stderr,
-                }
-            }
+            CommandOutputErrorKind::SpawnFailed
+            | CommandOutputErrorKind::Unsuccessful
+            | CommandOutputErrorKind::TimedOut => Self::Failed {
+                status: error.status,
+                stderr: error.stderr,
+            },
         }
     }
 }
@@ -174,11 +182,6 @@ impl GitHubGhBackend<SystemGhRunner> {
             show_pr_comments: true,
         }
     }
-
-    pub fn with_local_checkout(mut self, checkout: Option<PathBuf>) -> Self {
-        self.local_checkout = checkout;
-        self
-    }
 }
 
 impl<R> GitHubGhBackend<R>
@@ -195,6 +198,11 @@ where
         }
     }
 
+    pub fn with_local_checkout(mut self, checkout: Option<PathBuf>) -> Self {
+        self.local_checkout = checkout;
+        self
+    }
+
     pub fn set_local_checkout(&mut self, checkout: Option<PathBuf>) {
         self.local_checkout = checkout;
     }
@@ -586,6 +594,298 @@ where
 
         parse_create_review_response(&output)
     }
+
+    fn reply_to_review_thread(
+        &self,
+        pr: &PullRequestDetails,
+        thread: &RemoteReviewThread,
+        body: &str,
+    ) -> Result<()> {
+        let mut args = self.build_thread_mutation_args(pr, REPLY_MUTATION, &thread.id);
+        args.push("-f".to_string());
+        args.push(format!("body={body}"));
+        let output = self.run_gh(args, &pr.repository.host)?;
+        check_mutation_response(&output, "reply to review thread")
+    }
+
+    fn set_review_thread_resolved(
+        &self,
+        pr: &PullRequestDetails,
+        thread: &RemoteReviewThread,
+        resolved: bool,
+    ) -> Result<()> {
+        let (mutation, action) = if resolved {
+            (RESOLVE_MUTATION, "resolve review thread")
+        } else {
+            (UNRESOLVE_MUTATION, "unresolve review thread")
+        };
+        let args = self.build_thread_mutation_args(pr, mutation, &thread.id);
+        let output = self.run_gh(args, &pr.repository.host)?;
+        check_mutation_response(&output, action)
+    }
+
+    fn supports_viewed_files(&self) -> bool {
+        true
+    }
+
+    fn list_viewed_files(&self, pr: &PullRequestDetails) -> Result<Vec<PathBuf>> {
+        let mut all = Vec::new();
+        let mut cursor: Option<String> = None;
+        // 100 files * 30 pages matches the 3000-file cap on the diff itself.
+        for _ in 0..30 {
+            let query = super::viewed_files::build_query(cursor.as_deref());
+            let args = self.build_graphql_args(pr, &query, cursor.as_deref());
+            let output = self.run_gh(args, &pr.repository.host)?;
+            let parsed = super::viewed_files::parse_graphql_page(&output)?;
+            all.extend(parsed.viewed);
+            let Some(page_info) = parsed.page_info else {
+                break;
+            };
+            if !page_info.has_next_page {
+                break;
+            }
+            let Some(end_cursor) = page_info.end_cursor else {
+                break;
+            };
+            cursor = Some(end_cursor);
+        }
+        Ok(all)
+    }
+
+    fn set_file_viewed(&self, pr: &PullRequestDetails, path: &Path, viewed: bool) -> Result<()> {
+        use super::viewed_files::{
+            MARK_VIEWED_MUTATION, PULL_REQUEST_ID_QUERY, UNMARK_VIEWED_MUTATION,
+            parse_pull_request_id,
+        };
+
+        let args = self.build_graphql_args(pr, PULL_REQUEST_ID_QUERY, None);
+        let pull_request_id = parse_pull_request_id(&self.run_gh(args, &pr.repository.host)?)?;
+        let (mutation, action) = if viewed {
+            (MARK_VIEWED_MUTATION, "mark file as viewed")
+        } else {
+            (UNMARK_VIEWED_MUTATION, "unmark file as viewed")
+        };
+        let mut args = vec![
+            "api".to_string(),
+            "graphql".to_string(),
+            "-f".to_string(),
+            format!("query={mutation}"),
+            "-f".to_string(),
+            format!("pullRequestId={pull_request_id}"),
+            "-f".to_string(),
+            format!("path={}", path.to_string_lossy().replace('\\', "/")),
+        ];
+        if pr.repository.host != DEFAULT_GITHUB_HOST {
+            args.push("--hostname".to_string());
+            args.push(pr.repository.host.clone());
+        }
+        let output = self.run_gh(args, &pr.repository.host)?;
+        check_mutation_response(&output, action)
+    }
+
+    fn supports_pending_reviews(&self) -> bool {
+        true
+    }
+
+    fn get_pending_review(&self, pr: &PullRequestDetails) -> Result<Option<PendingReview>> {
+        let args = self.build_graphql_args(pr, PENDING_REVIEW_QUERY, None);
+        let output = self.run_gh(args, &pr.repository.host)?;
+        let Some(mut review) = parse_pending_review(&output)? else {
+            return Ok(None);
+        };
+        let endpoint = format!(
+            "repos/{}/{}/pulls/{}/reviews/{}/comments?per_page=100",
+            pr.repository.owner, pr.repository.name, pr.number, review.database_id,
+        );
+        let mut args = vec!["api".to_string()];
+        if pr.repository.host != DEFAULT_GITHUB_HOST {
+            args.extend(["--hostname".to_string(), pr.repository.host.clone()]);
+        }
+        args.push(endpoint);
+        review.comments = parse_pending_comments(&self.run_gh(args, &pr.repository.host)?)?;
+        Ok(Some(review))
+    }
+
+    fn extend_pending_review(
+        &self,
+        pr: &PullRequestDetails,
+        review: &PendingReview,
+        request: CreateReviewRequest<'_>,
+    ) -> Result<GhCreateReviewResponse> {
+        for comment in request.comments {
+            let mut args = self.build_node_mutation_args(
+                pr,
+                ADD_THREAD_MUTATION,
+                &[
+                    ("reviewId", review.id.as_str()),
+                    ("path", &comment.path.to_string_lossy().replace('\\', "/")),
+                    ("body", &comment.body),
+                    ("side", comment.side.as_str()),
+                ],
+            );
+            args.extend(["-F".to_string(), format!("line={}", comment.line)]);
+            if let (Some(start_line), Some(start_side)) = (comment.start_line, comment.start_side) {
+                args.extend([
+                    "-F".to_string(),
+                    format!("startLine={start_line}"),
+                    "-f".to_string(),
+                    format!("startSide={}", start_side.as_str()),
+                ]);
+            }
+            let output = self.run_gh(args, &pr.repository.host)?;
+            check_mutation_response(&output, "add comment to pending review")?;
+        }
+
+        let body = combined_body(&review.body, request.body);
+        let Some(event) = request.event.github_event() else {
+            // Still a draft: only the body can have changed.
+            if body == review.body {
+                return Ok(GhCreateReviewResponse {
+                    id: review.database_id,
+                    html_url: String::new(),
+                    state: "PENDING".to_string(),
+                });
+            }
+            let args = self.build_node_mutation_args(
+                pr,
+                UPDATE_REVIEW_MUTATION,
+                &[("reviewId", review.id.as_str()), ("body", &body)],
+            );
+            let output = self.run_gh(args, &pr.repository.host)?;
+            check_mutation_response(&output, "update pending review")?;
+            return parse_review_mutation_response(&output, "updatePullRequestReview");
+        };
+        let args = self.build_node_mutation_args(
+            pr,
+            SUBMIT_REVIEW_MUTATION,
+            &[
+                ("reviewId", review.id.as_str()),
+                ("event", event),
+                ("body", &body),
+            ],
+        );
+        let output = self.run_gh(args, &pr.repository.host)?;
+        check_mutation_response(&output, "submit pending review")?;
+        parse_review_mutation_response(&output, "submitPullRequestReview")
+    }
+
+    fn discard_pending_review(
+        &self,
+        pr: &PullRequestDetails,
+        review: &PendingReview,
+    ) -> Result<()> {
+        let args = self.build_node_mutation_args(
+            pr,
+            DELETE_REVIEW_MUTATION,
+            &[("reviewId", review.id.as_str())],
+        );
+        let output = self.run_gh(args, &pr.repository.host)?;
+        check_mutation_response(&output, "discard pending review")
+    }
+
+    fn update_pending_review_comment(
+        &self,
+        pr: &PullRequestDetails,
+        comment_id: &str,
+        body: &str,
+    ) -> Result<()> {
+        let args = self.build_node_mutation_args(
+            pr,
+            UPDATE_COMMENT_MUTATION,
+            &[("commentId", comment_id), ("body", body)],
+        );
+        let output = self.run_gh(args, &pr.repository.host)?;
+        check_mutation_response(&output, "update pending comment")
+    }
+
+    fn delete_pending_review_comment(
+        &self,
+        pr: &PullRequestDetails,
+        comment_id: &str,
+    ) -> Result<()> {
+        let args = self.build_node_mutation_args(
+            pr,
+            DELETE_COMMENT_MUTATION,
+            &[("commentId", comment_id)],
+        );
+        let output = self.run_gh(args, &pr.repository.host)?;
+        check_mutation_response(&output, "delete pending comment")
+    }
+
+    fn merge_pull_request(&self, pr: &PullRequestDetails, method: MergeMethod) -> Result<()> {
+        // `sha` makes GitHub reject the merge (409) if the head moved since
+        // the PR was opened, so tuicr never merges commits nobody reviewed.
+        let payload = serde_json::json!({
+            "merge_method": method.label(),
+            "sha": pr.head_sha,
+        });
+        let endpoint = format!(
+            "repos/{}/{}/pulls/{}/merge",
+            pr.repository.owner, pr.repository.name, pr.number,
+        );
+        self.run_rest_json(pr, "PUT", endpoint, &payload)
+    }
+
+    fn close_pull_request(&self, pr: &PullRequestDetails) -> Result<()> {
+        let endpoint = format!(
+            "repos/{}/{}/pulls/{}",
+            pr.repository.owner, pr.repository.name, pr.number,
+        );
+        self.run_rest_json(
+            pr,
+            "PATCH",
+            endpoint,
+            &serde_json::json!({ "state": "closed" }),
+        )
+    }
+
+    fn request_reviewer(&self, pr: &PullRequestDetails, username: &str) -> Result<()> {
+        let endpoint = format!(
+            "repos/{}/{}/pulls/{}/requested_reviewers",
+            pr.repository.owner, pr.repository.name, pr.number,
+        );
+        let payload = serde_json::json!({ "reviewers": [username] });
+        self.run_rest_json(pr, "POST", endpoint, &payload)
+    }
+
+    fn add_label(&self, pr: &PullRequestDetails, label: &str) -> Result<()> {
+        // PR labels live on the issue side of the API.
+        let endpoint = format!(
+            "repos/{}/{}/issues/{}/labels",
+            pr.repository.owner, pr.repository.name, pr.number,
+        );
+        self.run_rest_json(
+            pr,
+            "POST",
+            endpoint,
+            &serde_json::json!({ "labels": [label] }),
+        )
+    }
+
+    fn fetch_check_log(
+        &self,
+        pr: &PullRequestDetails,
+        check: &PullRequestCheckStatus,
+    ) -> Result<String> {
+        let Some(job_id) = check.url.as_deref().and_then(actions_job_id) else {
+            return Err(TuicrError::UnsupportedOperation(format!(
+                "`{}` is not a GitHub Actions job; its log is only on the check's page",
+                check.name
+            )));
+        };
+        self.run_gh(
+            vec![
+                "run".to_string(),
+                "view".to_string(),
+                "--job".to_string(),
+                job_id.to_string(),
+                "--log-failed".to_string(),
+                "--repo".to_string(),
+                gh_repo_arg(&pr.repository),
+            ],
+            &pr.repository.host,
+        )
+    }
 }
 
 impl<R> GitHubGhBackend<R>
@@ -647,6 +947,81 @@ where
         args
     }
 
+    /// Thread mutations take the thread node id instead of the
+    /// owner/name/number triple the read queries use.
+    fn build_thread_mutation_args(
+        &self,
+        pr: &PullRequestDetails,
+        mutation: &str,
+        thread_id: &str,
+    ) -> Vec<String> {
+        let mut args = vec![
+            "api".to_string(),
+            "graphql".to_string(),
+            "-f".to_string(),
+            format!("query={mutation}"),
+            "-f".to_string(),
+            format!("threadId={thread_id}"),
+        ];
+        if pr.repository.host != DEFAULT_GITHUB_HOST {
+            args.push("--hostname".to_string());
+            args.push(pr.repository.host.clone());
+        }
+        args
+    }
+
+    /// Send `payload` as the JSON body of a REST call. Arrays don't survive
+    /// `-f`/`-F`, so the body goes through stdin like `create_review`.
+    fn run_rest_json(
+        &self,
+        pr: &PullRequestDetails,
+        method: &str,
+        endpoint: String,
+        payload: &serde_json::Value,
+    ) -> Result<()> {
+        let mut args = vec![
+            "api".to_string(),
+            endpoint,
+            "--method".to_string(),
+            method.to_string(),
+            "--input".to_string(),
+            "-".to_string(),
+        ];
+        if pr.repository.host != DEFAULT_GITHUB_HOST {
+            args.push("--hostname".to_string());
+            args.push(pr.repository.host.clone());
+        }
+        self.runner
+            .run_with_stdin(&args, &payload.to_string())
+            .map_err(|err| map_gh_error(err, &pr.repository.host))?;
+        Ok(())
+    }
+
+    /// Pending-review mutations are keyed by review or comment node id;
+    /// `fields` are passed as string variables.
+    fn build_node_mutation_args(
+        &self,
+        pr: &PullRequestDetails,
+        mutation: &str,
+        fields: &[(&str, &str)],
+    ) -> Vec<String> {
+        let mut args = vec![
+            "api".to_string(),
+            "graphql".to_string(),
+            "-f".to_string(),
+            format!("query={mutation}"),
+        ];
+        for (name, value) in fields {
+            args.push("-f".to_string());
+            args.push(format!("{name}={value}"));
+        }
+        if pr.repository.host != DEFAULT_GITHUB_HOST {
+            args.push("--hostname".to_string());
+            args.push(pr.repository.host.clone());
+        }
+        args
+    }
+
     fn fetch_file_via_api(&self, request: &ForgeFileLinesRequest) -> Result<String> {
         // `gh api repos/<owner>/<repo>/contents/<path>?ref=<sha>` returns a
         // JSON object with base64-encoded `content` for text files. The
@@ -788,6 +1163,8 @@ fn parse_repo_hash_target(target: &str) -> Option<PullRequestTarget> {
 fn forge_repo_from_host(host: &str, owner: &str, repo: &str) -> ForgeRepository {
     if host.contains("gitlab") {
         ForgeRepository::gitlab(host, owner, repo)
+    } else if crate::forge::gitea::api::is_gitea_host(host) {
+        ForgeRepository::gitea(host, owner, repo)
     } else {
         ForgeRepository::github(host, owner, repo)
     }
@@ -919,6 +1296,15 @@ fn strip_git_suffix(value: &str) -> &str {
     value.strip_suffix(".git").unwrap_or(value)
 }
 
+/// Job id of an Actions check run, from its
+/// `https://github.com/<owner>/<repo>/actions/runs/<run>/job/<job>` URL.
+fn actions_job_id(url: &str) -> Option<&str> {
+    let (_, rest) = url.split_once("/actions/runs/")?;
+    let (_, job) = rest.split_once("/job/")?;
+    let job = job.split(['/', '?', '#']).next()?;
+    (!job.is_empty() && job.bytes().all(|b| b.is_ascii_digit())).then_some(job)
+}
+
 fn gh_repo_arg(repository: &ForgeRepository) -> String {
     if repository.host == DEFAULT_GITHUB_HOST {
         repository.slug()
@@ -1157,11 +1543,24 @@ index 1111111..2222222 100644
                         .unwrap_or("");
                     if query.contains("reviewThreads(") {
                         Ok(REVIEW_THREADS_JSON.to_string())
+                    } else if query.contains("viewerViewedState") {
+                        Ok(VIEWED_FILES_JSON.to_string())
+                    } else if query.contains("pullRequest(number: $number) { id }") {
+                        Ok(
+                            r#"{"data":{"repository":{"pullRequest":{"id":"PR_kw125"}}}}"#
+                                .to_string(),
+                        )
                     } else if query.contains("viewer { login }") && query.contains("commit { oid }")
                     {
                         Ok(REVIEW_METADATA_JSON.to_string())
+                    } else if query.contains("reviews(states: PENDING") {
+                        Ok(PENDING_REVIEW_JSON.to_string())
                     } else if query.contains("reviews(") {
                         Ok(REVIEW_SUMMARIES_JSON.to_string())
+                    } else if query.contains("submitPullRequestReview(") {
+                        Ok(SUBMIT_PENDING_REVIEW_JSON.to_string())
+                    } else if query.starts_with("query=mutation(") {
+                        Ok(THREAD_MUTATION_JSON.to_string())
                     } else {
                         Err(GhCommandError::Failed {
                             status: Some(1),
@@ -1169,6 +1568,10 @@ index 1111111..2222222 100644
                         })
                     }
                 }
+                Some("run") => Ok(RUN_VIEW_LOG.to_string()),
+                Some("api") if args.iter().any(|a| a.contains("/reviews/77/comments")) => {
+                    Ok(PENDING_COMMENTS_JSON.to_string())
+                }
                 // gh api repos/.../pulls/<n>/commits (commit list).
                 Some("api")
                     if args
@@ -1335,6 +1738,23 @@ index 1111111..2222222 100644
         }
     }"##;
 
+    const THREAD_MUTATION_JSON: &str = r##"{
+        "data": { "resolveReviewThread": { "thread": { "id": "PRRT_1", "isResolved": true } } }
+    }"##;
+
+    const PENDING_REVIEW_JSON: &str = r#"{"data":{"repository":{"pullRequest":{"reviews":{"nodes":[
+        {"id":"PRR_pending","databaseId":77,"body":"Started on the web"}
+    ]}}}}}"#;
+
+    const PENDING_COMMENTS_JSON: &str = r#"[
+        {"node_id":"PRRC_p1","path":"src/lib.rs","line":11,"side":"RIGHT",
+         "body":"Pending note","subject_type":"line"}
+    ]"#;
+
+    const SUBMIT_PENDING_REVIEW_JSON: &str = r#"{"data":{"submitPullRequestReview":{"pullRequestReview":{
+        "databaseId":77,"url":"https://github.com/agavra/tuicr/pull/125#pullrequestreview-77","state":"APPROVED"
+    }}}}"#;
+
     fn repo() -> ForgeRepository {
         ForgeRepository::github("github.com", "agavra", "tuicr")
     }
@@ -1875,6 +2295,366 @@ Match host github-work
         assert!(graphql_call.iter().any(|a| a == "number=125"));
     }
 
+    #[test]
+    fn should_reply_to_review_thread_via_graphql_mutation() {
+        // given
+        let runner = FakeGhRunner::default();
+        let backend = GitHubGhBackend::with_runner(Some(repo()), runner);
+        let details = backend
+            .get_pull_request(parse_pull_request_target("125").unwrap())
+            .unwrap();
+        let thread = backend.list_review_threads(&details).unwrap().remove(0);
+        // when
+        backend
+            .reply_to_review_thread(&details, &thread, "Fixed in the next push")
+            .unwrap();
+        // then — the reply is keyed by the thread node id, not the PR.
+        let calls = backend.runner.calls.borrow();
+        let mutation_call = calls
+            .iter()
+            .find(|args| {
+                args.iter()
+                    .any(|a| a.contains("addPullRequestReviewThreadReply"))
+            })
+            .expect("expected a reply mutation call");
+        assert!(mutation_call.iter().any(|a| a == "threadId=PRRT_1"));
+        assert!(
+            mutation_call
+                .iter()
+                .any(|a| a == "body=Fixed in the next push")
+        );
+        assert!(!mutation_call.iter().any(|a| a.starts_with("number=")));
+    }
+
+    #[test]
+    fn should_pick_resolve_or_unresolve_mutation_for_thread_state() {
+        // given
+        let runner = FakeGhRunner::default();
+        let backend = GitHubGhBackend::with_runner(Some(repo()), runner);
+        let details = backend
+            .get_pull_request(parse_pull_request_target("125").unwrap())
+            .unwrap();
+        let thread = backend.list_review_threads(&details).unwrap().remove(0);
+        // when
+        backend
+            .set_review_thread_resolved(&details, &thread, true)
+            .unwrap();
+        backend
+            .set_review_thread_resolved(&details, &thread, false)
+            .unwrap();
+        // then
+        let calls = backend.runner.calls.borrow();
+        let mutations: Vec<&Vec<String>> = calls
+            .iter()
+            .filter(|args| args.iter().any(|a| a.starts_with("query=mutation(")))
+            .collect();
+        assert_eq!(mutations.len(), 2);
+        assert!(
+            mutations[0]
+                .iter()
+                .any(|a| a.contains("resolveReviewThread("))
+        );
+        assert!(
+            !mutations[0]
+                .iter()
+                .any(|a| a.contains("unresolveReviewThread("))
+        );
+        assert!(
+            mutations[1]
+                .iter()
+                .any(|a| a.contains("unresolveReviewThread("))
+        );
+        assert!(
+            mutations
+                .iter()
+                .all(|m| m.iter().any(|a| a == "threadId=PRRT_1"))
+        );
+    }
+
+    const VIEWED_FILES_JSON: &str = r#"{"data":{"repository":{"pullRequest":{"files":{
+        "pageInfo":{"hasNextPage":false,"endCursor":null},
+        "nodes":[
+            {"path":"src/lib.rs","viewerViewedState":"VIEWED"},
+            {"path":"README.md","viewerViewedState":"UNVIEWED"}
+        ]}}}}}"#;
+
+    #[test]
+    fn should_list_files_the_viewer_marked_viewed() {
+        // given
+        let runner = FakeGhRunner::default();
+        let backend = GitHubGhBackend::with_runner(Some(repo()), runner);
+        let details = backend
+            .get_pull_request(parse_pull_request_target("125").unwrap())
+            .unwrap();
+        // when
+        let viewed = backend.list_viewed_files(&details).unwrap();
+        // then
+        assert!(backend.supports_viewed_files());
+        assert_eq!(viewed, vec![PathBuf::from("src/lib.rs")]);
+    }
+
+    #[test]
+    fn should_mark_and_unmark_a_file_viewed_by_pull_request_node_id() {
+        // given
+        let runner = FakeGhRunner::default();
+        let backend = GitHubGhBackend::with_runner(Some(repo()), runner);
+        let details = backend
+            .get_pull_request(parse_pull_request_target("125").unwrap())
+            .unwrap();
+        // when
+        backend
+            .set_file_viewed(&details, Path::new("src/lib.rs"), true)
+            .unwrap();
+        backend
+            .set_file_viewed(&details, Path::new("src/lib.rs"), false)
+            .unwrap();
+        // then
+        let calls = backend.runner.calls.borrow();
+        let mutations: Vec<&Vec<String>> = calls
+            .iter()
+            .filter(|args| args.iter().any(|a| a.starts_with("query=mutation(")))
+            .collect();
+        assert_eq!(mutations.len(), 2);
+        assert!(mutations[0].iter().any(|a| a.contains("markFileAsViewed(")));
+        assert!(
+            mutations[1]
+                .iter()
+                .any(|a| a.contains("unmarkFileAsViewed("))
+        );
+        assert!(mutations.iter().all(|m| {
+            m.iter().any(|a| a == "pullRequestId=PR_kw125")
+                && m.iter().any(|a| a == "path=src/lib.rs")
+        }));
+    }
+
+    #[test]
+    fn should_fetch_the_pending_review_with_its_comments() {
+        // given
+        let runner = FakeGhRunner::default();
+        let backend = GitHubGhBackend::with_runner(Some(repo()), runner);
+        let details = backend
+            .get_pull_request(parse_pull_request_target("125").unwrap())
+            .unwrap();
+        // when
+        let review = backend.get_pending_review(&details).unwrap().unwrap();
+        // then
+        assert_eq!(review.id, "PRR_pending");
+        assert_eq!(review.database_id, 77);
+        assert_eq!(review.comments.len(), 1);
+        assert_eq!(review.comments[0].id, "PRRC_p1");
+        assert_eq!(review.comments[0].line, Some(11));
+        let calls = backend.runner.calls.borrow();
+        assert!(calls.iter().any(|args| {
+            args.iter()
+                .any(|a| a == "repos/agavra/tuicr/pulls/125/reviews/77/comments?per_page=100")
+        }));
+    }
+
+    #[test]
+    fn should_add_new_comments_before_publishing_the_pending_review() {
+        // given
+        let runner = FakeGhRunner::default();
+        let backend = GitHubGhBackend::with_runner(Some(repo()), runner);
+        let details = backend
+            .get_pull_request(parse_pull_request_target("125").unwrap())
+            .unwrap();
+        let review = backend.get_pending_review(&details).unwrap().unwrap();
+        let comments = vec![inline(42, "Add a test")];
+        // when
+        let response = backend
+            .extend_pending_review(
+                &details,
+                &review,
+                CreateReviewRequest {
+                    event: SubmitEvent::Approve,
+                    commit_id: "abc",
+                    body: "LGTM",
+                    comments: &comments,
+                },
+            )
+            .unwrap();
+        // then
+        assert_eq!(response.id, 77);
+        let calls = backend.runner.calls.borrow();
+        let mutations: Vec<&Vec<String>> = calls
+            .iter()
+            .filter(|args| args.iter().any(|a| a.starts_with("query=mutation(")))
+            .collect();
+        assert_eq!(mutations.len(), 2);
+        assert!(
+            mutations[0]
+                .iter()
+                .any(|a| a.contains("addPullRequestReviewThread("))
+        );
+        assert!(mutations[0].iter().any(|a| a == "reviewId=PRR_pending"));
+        assert!(mutations[0].iter().any(|a| a == "line=42"));
+        assert!(mutations[1].iter().any(|a| a == "event=APPROVE"));
+        assert!(
+            mutations[1]
+                .iter()
+                .any(|a| a == "body=Started on the web\n\nLGTM")
+        );
+    }
+
+    #[test]
+    fn should_edit_and_delete_pending_comments_by_node_id() {
+        // given
+        let runner = FakeGhRunner::default();
+        let backend = GitHubGhBackend::with_runner(Some(repo()), runner);
+        let details = backend
+            .get_pull_request(parse_pull_request_target("125").unwrap())
+            .unwrap();
+        // when
+        backend
+            .update_pending_review_comment(&details, "PRRC_p1", "Reworded")
+            .unwrap();
+        backend
+            .delete_pending_review_comment(&details, "PRRC_p1")
+            .unwrap();
+        // then
+        let calls = backend.runner.calls.borrow();
+        let mutations: Vec<&Vec<String>> = calls
+            .iter()
+            .filter(|args| args.iter().any(|a| a.starts_with("query=mutation(")))
+            .collect();
+        assert!(
+            mutations[0]
+                .iter()
+                .any(|a| a.contains("updatePullRequestReviewComment("))
+        );
+        assert!(mutations[0].iter().any(|a| a == "body=Reworded"));
+        assert!(
+            mutations[1]
+                .iter()
+                .any(|a| a.contains("deletePullRequestReviewComment("))
+        );
+        assert!(
+            mutations
+                .iter()
+                .all(|m| m.iter().any(|a| a == "commentId=PRRC_p1"))
+        );
+    }
+
+    #[test]
+    fn should_merge_against_the_reviewed_head() {
+        // given
+        let backend = GitHubGhBackend::with_runner(Some(repo()), FakeGhRunner::default());
+        let details = backend
+            .get_pull_request(parse_pull_request_target("125").unwrap())
+            .unwrap();
+        // when
+        backend
+            .merge_pull_request(&details, MergeMethod::Squash)
+            .unwrap();
+        // then
+        let stdin_calls = backend.runner.stdin_calls.borrow();
+        let (args, stdin) = &stdin_calls[0];
+        assert_eq!(args[1], "repos/agavra/tuicr/pulls/125/merge");
+        assert!(args.iter().any(|a| a == "PUT"));
+        let payload: serde_json::Value = serde_json::from_str(stdin).unwrap();
+        assert_eq!(payload["merge_method"], "squash");
+        assert_eq!(payload["sha"], details.head_sha.as_str());
+    }
+
+    const RUN_VIEW_LOG: &str =
+        "test\tRun cargo test\t2026-01-01T00:00:00.0000000Z error: src/lib.rs:3:1 failed\n";
+
+    #[test]
+    fn should_fetch_the_failed_steps_of_an_actions_job() {
+        // given
+        let backend = GitHubGhBackend::with_runner(Some(repo()), FakeGhRunner::default());
+        let details = backend
+            .get_pull_request(parse_pull_request_target("125").unwrap())
+            .unwrap();
+        let check = PullRequestCheckStatus {
+            name: "test".to_string(),
+            status: Some("COMPLETED".to_string()),
+            conclusion: Some("FAILURE".to_string()),
+            url: Some("https://github.com/agavra/tuicr/actions/runs/11/job/22".to_string()),
+        };
+        // when
+        let log = backend.fetch_check_log(&details, &check).unwrap();
+        // then
+        assert_eq!(log, RUN_VIEW_LOG);
+        let calls = backend.runner.calls.borrow();
+        assert_eq!(
+            calls.last().unwrap(),
+            &[
+                "run",
+                "view",
+                "--job",
+                "22",
+                "--log-failed",
+                "--repo",
+                "agavra/tuicr"
+            ]
+        );
+    }
+
+    #[test]
+    fn should_refuse_logs_for_checks_outside_actions() {
+        let backend = GitHubGhBackend::with_runner(Some(repo()), FakeGhRunner::default());
+        let details = backend
+            .get_pull_request(parse_pull_request_target("125").unwrap())
+            .unwrap();
+        let check = PullRequestCheckStatus {
+            name: "ci/external".to_string(),
+            status: None,
+            conclusion: Some("FAILURE".to_string()),
+            url: Some("https://ci.example.com/build/9".to_string()),
+        };
+        assert!(matches!(
+            backend.fetch_check_log(&details, &check),
+            Err(TuicrError::UnsupportedOperation(_))
+        ));
+    }
+
+    #[test]
+    fn should_close_request_reviewers_and_label_over_rest() {
+        // given
+        let backend = GitHubGhBackend::with_runner(Some(repo()), FakeGhRunner::default());
+        let details = backend
+            .get_pull_request(parse_pull_request_target("125").unwrap())
+            .unwrap();
+        // when
+        backend.close_pull_request(&details).unwrap();
+        backend.request_reviewer(&details, "octocat").unwrap();
+        backend.add_label(&details, "needs docs").unwrap();
+        // then
+        let stdin_calls = backend.runner.stdin_calls.borrow();
+        let calls: Vec<(&str, &str, serde_json::Value)> = stdin_calls
+            .iter()
+            .map(|(args, stdin)| {
+                let method = args[args.iter().position(|a| a == "--method").unwrap() + 1].as_str();
+                (
+                    args[1].as_str(),
+                    method,
+                    serde_json::from_str(stdin).unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            calls,
+            vec![
+                (
+                    "repos/agavra/tuicr/pulls/125",
+                    "PATCH",
+                    serde_json::json!({ "state": "closed" })
+                ),
+                (
+                    "repos/agavra/tuicr/pulls/125/requested_reviewers",
+                    "POST",
+                    serde_json::json!({ "reviewers": ["octocat"] })
+                ),
+                (
+                    "repos/agavra/tuicr/issues/125/labels",
+                    "POST",
+                    serde_json::json!({ "labels": ["needs docs"] })
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn should_list_review_summaries_via_graphql_api_call() {
         // given
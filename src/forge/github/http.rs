@@ -0,0 +1,623 @@
+//! `GhCommandRunner` that talks to the GitHub API directly instead of `gh`.
+//!
+//! `gh api` invocations map one-to-one onto REST or GraphQL requests. The
+//! three porcelain commands the backend uses are translated: `pr list` and
+//! `pr view --json` become GraphQL queries selecting the same fields (`gh`'s
+//! JSON export is GraphQL field names, and `pr_info` already accepts the
+//! `{ nodes: [...] }` connection shapes), and `pr diff` becomes the pulls
+//! endpoint with the diff media type. `run view --job --log-failed` fetches
+//! the job's log, which over REST is the whole log, not just failed steps.
+
+use crate::config::ForgeConfig;
+use crate::forge::http::{
+    ApiArgs, ConfiguredTokens, HttpClient, HttpRequest, failure_message, join_url,
+};
+
+use super::gh::{GhCommandError, GhCommandResult, GhCommandRunner};
+
+const DEFAULT_GITHUB_HOST: &str = "github.com";
+const TOKEN_HINT: &str =
+    "set GH_TOKEN or GITHUB_TOKEN (GH_ENTERPRISE_TOKEN for GitHub Enterprise), or `[forge.tokens]`";
+
+const PR_LIST_FIELDS: &str =
+    "number title author { login } headRefName baseRefName updatedAt url state isDraft";
+
+pub struct GhHttpRunner {
+    client: HttpClient,
+    configured_tokens: ConfiguredTokens,
+    api_url: Option<String>,
+}
+
+impl GhHttpRunner {
+    pub fn new(config: &ForgeConfig) -> Self {
+        Self {
+            client: HttpClient::new(),
+            configured_tokens: ConfiguredTokens::new(config),
+            api_url: config.api_url.clone(),
+        }
+    }
+
+    fn rest_base(&self, host: &str) -> String {
+        match &self.api_url {
+            Some(url) => url.clone(),
+            None if host == DEFAULT_GITHUB_HOST => "https://api.github.com".to_string(),
+            None => format!("https://{host}/api/v3"),
+        }
+    }
+
+    fn graphql_url(&self, host: &str) -> String {
+        match &self.api_url {
+            Some(url) => join_url(url, "graphql"),
+            None if host == DEFAULT_GITHUB_HOST => "https://api.github.com/graphql".to_string(),
+            None => format!("https://{host}/api/graphql"),
+        }
+    }
+
+    /// `gh` reads `GH_TOKEN`/`GITHUB_TOKEN` for github.com and the
+    /// `*_ENTERPRISE_TOKEN` pair for other hosts; mirror that.
+    fn token(&self, host: &str) -> Option<String> {
+        let names: &[&str] = if host == DEFAULT_GITHUB_HOST {
+            &["GH_TOKEN", "GITHUB_TOKEN"]
+        } else {
+            &["GH_ENTERPRISE_TOKEN", "GITHUB_ENTERPRISE_TOKEN"]
+        };
+        crate::forge::http::resolve_token(names, self.configured_tokens.for_host(host))
+    }
+
+    fn send(&self, host: &str, mut request: HttpRequest) -> GhCommandResult<String> {
+        if !request
+            .headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("Accept"))
+        {
+            request = request.header("Accept", "application/vnd.github+json");
+        }
+        if let Some(token) = self.token(host) {
+            request = request.header("Authorization", format!("Bearer {token}"));
+        }
+        let (status, body) = self.client.send(&request).map_err(failed)?;
+        if (200..300).contains(&status) {
+            Ok(body)
+        } else {
+            Err(failed(failure_message(status, &body, host, TOKEN_HINT)))
+        }
+    }
+
+    /// Run a GraphQL query and return its `data`, failing on GraphQL errors
+    /// the way `gh` does for its own porcelain commands.
+    fn graphql(
+        &self,
+        host: &str,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> GhCommandResult<serde_json::Value> {
+        let body = serde_json::json!({ "query": query, "variables": variables }).to_string();
+        let request = HttpRequest::new("POST", self.graphql_url(host)).body(body);
+        let output = self.send(host, request)?;
+        let mut response: serde_json::Value =
+            serde_json::from_str(&output).map_err(|err| failed(err.to_string()))?;
+        if let Some(errors) = response.get("errors").and_then(|e| e.as_array())
+            && !errors.is_empty()
+        {
+            let messages = errors
+                .iter()
+                .filter_map(|e| e.get("message").and_then(|m| m.as_str()))
+                .collect::<Vec<_>>()
+                .join("; ");
+            return Err(failed(format!("GraphQL error: {messages}")));
+        }
+        Ok(response["data"].take())
+    }
+
+    fn run_api(&self, api: ApiArgs, stdin: &str) -> GhCommandResult<String> {
+        let host = api
+            .hostname
+            .clone()
+            .unwrap_or_else(|| DEFAULT_GITHUB_HOST.to_string());
+        let body = api.body(stdin);
+        let url = if api.is_graphql() {
+            self.graphql_url(&host)
+        } else {
+            join_url(&self.rest_base(&host), &api.endpoint)
+        };
+        let mut request = HttpRequest::new(&api.effective_method(body.is_some()), url);
+        for (name, value) in &api.headers {
+            request = request.header(name, value.clone());
+        }
+        if let Some(body) = body {
+            request = request.body(body);
+        }
+        self.send(&host, request)
+    }
+
+    fn pr_list(&self, flags: &PrFlags) -> GhCommandResult<String> {
+        let (host, owner, name) = flags.repository()?;
+        let limit = flags.limit.unwrap_or(30).max(1);
+        let mut rows: Vec<serde_json::Value> = Vec::new();
+        let mut after: Option<String> = None;
+        while rows.len() < limit {
+            let first = (limit - rows.len()).min(100);
+            let (connection, data) = match &flags.search {
+                Some(search) => {
+                    let query = format!(
+                        "query($q: String!, $first: Int!, $after: String) {{ \
+                         search(query: $q, type: ISSUE, first: $first, after: $after) {{ \
+                         nodes {{ ... on PullRequest {{ {PR_LIST_FIELDS} }} }} \
+                         pageInfo {{ hasNextPage endCursor }} }} }}"
+                    );
+                    let q = format!("repo:{owner}/{name} is:pr is:open sort:created-desc {search}");
+                    let data = self.graphql(
+                        &host,
+                        &query,
+                        serde_json::json!({ "q": q, "first": first, "after": after }),
+                    )?;
+                    ("search", data)
+                }
+                None => {
+                    let query = format!(
+                        "query($owner: String!, $name: String!, $first: Int!, $after: String) {{ \
+                         repository(owner: $owner, name: $name) {{ \
+                         pullRequests(states: OPEN, first: $first, after: $after, \
+                         orderBy: {{ field: CREATED_AT, direction: DESC }}) {{ \
+                         nodes {{ {PR_LIST_FIELDS} }} pageInfo {{ hasNextPage endCursor }} }} }} }}"
+                    );
+                    let data = self.graphql(
+                        &host,
+                        &query,
+                        serde_json::json!({
+                            "owner": owner, "name": name, "first": first, "after": after,
+                        }),
+                    )?;
+                    ("pullRequests", data)
+                }
+            };
+            let page = if connection == "search" {
+                &data["search"]
+            } else {
+                &data["repository"]["pullRequests"]
+            };
+            let nodes = page["nodes"].as_array().cloned().unwrap_or_default();
+            // Search can return issues too; their fragment selects nothing.
+            rows.extend(
+                nodes
+                    .into_iter()
+                    .filter(|node| node.get("number").is_some()),
+            );
+            let info = &page["pageInfo"];
+            if info["hasNextPage"].as_bool() != Some(true) {
+                break;
+            }
+            after = info["endCursor"].as_str().map(str::to_string);
+            if after.is_none() {
+                break;
+            }
+        }
+        rows.truncate(limit);
+        serde_json::to_string(&rows).map_err(|err| failed(err.to_string()))
+    }
+
+    fn pr_view(&self, flags: &PrFlags) -> GhCommandResult<String> {
+        let (host, owner, name) = flags.repository()?;
+        let number = flags.number()?;
+        let selection = flags
+            .json
+            .as_deref()
+            .unwrap_or("number")
+            .split(',')
+            .map(|field| pr_view_selection(field.trim()))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let query = format!(
+            "query($owner: String!, $name: String!, $number: Int!) {{ \
+             repository(owner: $owner, name: $name) {{ pullRequest(number: $number) {{ {selection} }} }} }}"
+        );
+        let mut data = self.graphql(
+            &host,
+            &query,
+            serde_json::json!({ "owner": owner, "name": name, "number": number }),
+        )?;
+        let pull_request = data["repository"]["pullRequest"].take();
+        if pull_request.is_null() {
+            return Err(failed(format!(
+                "no pull request #{number} in {owner}/{name}"
+            )));
+        }
+        Ok(pull_request.to_string())
+    }
+
+    fn pr_diff(&self, flags: &PrFlags) -> GhCommandResult<String> {
+        let (host, owner, name) = flags.repository()?;
+        let number = flags.number()?;
+        let url = join_url(
+            &self.rest_base(&host),
+            &format!("repos/{owner}/{name}/pulls/{number}"),
+        );
+        let request = HttpRequest::new("GET", url).header("Accept", "application/vnd.github.diff");
+        self.send(&host, request)
+    }
+
+    fn run_view_log(&self, flags: &PrFlags) -> GhCommandResult<String> {
+        let (host, owner, name) = flags.repository()?;
+        let job = flags
+            .job
+            .as_deref()
+            .ok_or_else(|| failed("missing --job".to_string()))?;
+        let url = join_url(
+            &self.rest_base(&host),
+            &format!("repos/{owner}/{name}/actions/jobs/{job}/logs"),
+        );
+        self.send(&host, HttpRequest::new("GET", url))
+    }
+}
+
+impl GhCommandRunner for GhHttpRunner {
+    fn run(&self, args: &[String]) -> GhCommandResult<String> {
+        self.run_with_stdin(args, "")
+    }
+
+    fn run_with_stdin(&self, args: &[String], stdin: &str) -> GhCommandResult<String> {
+        if let Some(api) = ApiArgs::parse(args) {
+            return self.run_api(api, stdin);
+        }
+        let flags = PrFlags::parse(args);
+        match (
+            args.first().map(String::as_str),
+            args.get(1).map(String::as_str),
+        ) {
+            (Some("pr"), Some("list")) => self.pr_list(&flags),
+            (Some("pr"), Some("view")) => self.pr_view(&flags),
+            (Some("pr"), Some("diff")) => self.pr_diff(&flags),
+            (Some("run"), Some("view")) => self.run_view_log(&flags),
+            _ => Err(failed(format!(
+                "`gh {}` is not available over the HTTP transport",
+                args.join(" ")
+            ))),
+        }
+    }
+}
+
+/// GraphQL selection for one `gh pr view --json` field. Connections are
+/// selected in the `{ nodes: [...] }` shape `pr_info` accepts, and
+/// `statusCheckRollup` is aliased onto the head commit's rollup.
+fn pr_view_selection(field: &str) -> String {
+    match field {
+        "author" => "author { login }".to_string(),
+        "reviewRequests" => "reviewRequests(first: 100) { nodes { requestedReviewer { \
+             __typename ... on User { login } ... on Bot { login } ... on Mannequin { login } \
+             ... on Team { name slug } } } }"
+            .to_string(),
+        "latestReviews" => {
+            "latestReviews(first: 100) { nodes { author { login } state submittedAt } }".to_string()
+        }
+        "statusCheckRollup" => "statusCheckRollup: commits(last: 1) { nodes { commit { \
+             statusCheckRollup { contexts(first: 100) { nodes { __typename \
+             ... on CheckRun { name status conclusion detailsUrl } \
+             ... on StatusContext { context state targetUrl } } } } } } }"
+            .to_string(),
+        "comments" => {
+            "comments(first: 100) { nodes { author { login } body url createdAt } }".to_string()
+        }
+        scalar => scalar.to_string(),
+    }
+}
+
+/// Flags of the `gh pr <list|view|diff>` and `gh run view` invocations the
+/// backend builds.
+#[derive(Debug, Default)]
+struct PrFlags {
+    number: Option<String>,
+    job: Option<String>,
+    repo: Option<String>,
+    json: Option<String>,
+    limit: Option<usize>,
+    search: Option<String>,
+}
+
+impl PrFlags {
+    fn parse(args: &[String]) -> Self {
+        let mut flags = Self::default();
+        let mut iter = args.iter().skip(2);
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--repo" | "-R" => flags.repo = iter.next().cloned(),
+                "--json" => flags.json = iter.next().cloned(),
+                "--limit" | "-L" => flags.limit = iter.next().and_then(|v| v.parse().ok()),
+                "--search" | "-S" => flags.search = iter.next().cloned(),
+                "--job" => flags.job = iter.next().cloned(),
+                // Flags whose values this transport doesn't need.
+                "--state" | "--color" => {
+                    iter.next();
+                }
+                positional if !positional.starts_with('-') => {
+                    flags.number = Some(positional.to_string());
+                }
+                _ => {}
+            }
+        }
+        flags
+    }
+
+    /// `--repo` is `owner/repo`, or `host/owner/repo` off github.com.
+    fn repository(&self) -> GhCommandResult<(String, String, String)> {
+        let repo = self
+            .repo
+            .as_deref()
+            .ok_or_else(|| failed("missing --repo".to_string()))?;
+        let parts: Vec<&str> = repo.split('/').collect();
+        match parts.as_slice() {
+            [owner, name] => Ok((
+                DEFAULT_GITHUB_HOST.to_string(),
+                owner.to_string(),
+                name.to_string(),
+            )),
+            [host, owner, name] => Ok((host.to_string(), owner.to_string(), name.to_string())),
+            _ => Err(failed(format!("malformed --repo `{repo}`"))),
+        }
+    }
+
+    fn number(&self) -> GhCommandResult<u64> {
+        self.number
+            .as_deref()
+            .and_then(|n| n.parse().ok())
+            .ok_or_else(|| failed("missing pull request number".to_string()))
+    }
+}
+
+fn failed(stderr: String) -> GhCommandError {
+    GhCommandError::Failed {
+        status: None,
+        stderr,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forge::github::gh::GitHubGhBackend;
+    use crate::forge::http::stub::StubServer;
+    use crate::forge::traits::{
+        ForgeBackend, ForgeRepository, PullRequestListQuery, PullRequestListScope,
+        PullRequestTarget,
+    };
+
+    fn runner(server: &StubServer) -> GhHttpRunner {
+        GhHttpRunner::new(&ForgeConfig {
+            api_url: Some(server.base_url.clone()),
+            token: Some("cfg-token".to_string()),
+            ..ForgeConfig::default()
+        })
+    }
+
+    fn repo() -> ForgeRepository {
+        ForgeRepository::github("github.com", "agavra", "tuicr")
+    }
+
+    #[test]
+    fn should_translate_pr_view_into_a_graphql_query() {
+        // given
+        let server = StubServer::start(vec![(
+            "POST",
+            "/graphql",
+            200,
+            r#"{"data":{"repository":{"pullRequest":{
+              "number":125,"title":"Add HTTP","url":"https://github.com/agavra/tuicr/pull/125",
+              "state":"OPEN","isDraft":false,"author":{"login":"alice"},
+              "headRefName":"feat","baseRefName":"main","headRefOid":"abc","baseRefOid":"def",
+              "body":"","updatedAt":"2026-01-01T00:00:00Z","closed":false,"mergedAt":null,
+              "reviewRequests":{"nodes":[{"requestedReviewer":{"__typename":"User","login":"bob"}}]},
+              "latestReviews":{"nodes":[]},"comments":{"nodes":[]}
+            }}}}"#,
+        )]);
+        let backend = GitHubGhBackend::with_runner(Some(repo()), runner(&server));
+        // when
+        let info = backend
+            .get_pull_request_info(PullRequestTarget::number(125, "125"))
+            .unwrap();
+        // then
+        assert_eq!(info.details.title, "Add HTTP");
+        assert_eq!(info.details.head_sha, "abc");
+        assert_eq!(info.requested_reviewers, vec!["bob"]);
+        let requests = server.requests();
+        assert!(
+            requests[0]
+                .header("Authorization")
+                .is_some_and(|value| value.starts_with("Bearer "))
+        );
+        let body: serde_json::Value = serde_json::from_str(&requests[0].body).unwrap();
+        assert_eq!(body["variables"]["number"], 125);
+        let query = body["query"].as_str().unwrap();
+        assert!(query.contains("pullRequest(number: $number)"));
+        assert!(query.contains("reviewRequests(first: 100)"));
+        // Checks are off by default, so the rollup isn't requested.
+        assert!(!query.contains("statusCheckRollup"));
+    }
+
+    #[test]
+    fn should_list_open_pull_requests_over_graphql() {
+        // given
+        let server = StubServer::start(vec![(
+            "POST",
+            "/graphql",
+            200,
+            r#"{"data":{"repository":{"pullRequests":{
+              "nodes":[
+                {"number":2,"title":"two","author":{"login":"a"},"headRefName":"b","baseRefName":"main","url":"u2","state":"OPEN","isDraft":false},
+                {"number":1,"title":"one","author":{"login":"a"},"headRefName":"c","baseRefName":"main","url":"u1","state":"OPEN","isDraft":true}
+              ],
+              "pageInfo":{"hasNextPage":false,"endCursor":null}}}}}"#,
+        )]);
+        let backend = GitHubGhBackend::with_runner(Some(repo()), runner(&server));
+        // when
+        let page = backend
+            .list_pull_requests(PullRequestListQuery::first_page_with_scope(
+                repo(),
+                1,
+                PullRequestListScope::Open,
+            ))
+            .unwrap();
+        // then
+        assert_eq!(page.pull_requests.len(), 1);
+        assert_eq!(page.pull_requests[0].number, 2);
+        assert!(page.has_more);
+    }
+
+    #[test]
+    fn should_search_for_review_requested_pull_requests() {
+        // given
+        let server = StubServer::start(vec![(
+            "POST",
+            "/graphql",
+            200,
+            r#"{"data":{"search":{"nodes":[{}],"pageInfo":{"hasNextPage":false}}}}"#,
+        )]);
+        let backend = GitHubGhBackend::with_runner(Some(repo()), runner(&server));
+        // when
+        let page = backend
+            .list_pull_requests(PullRequestListQuery::first_page_with_scope(
+                repo(),
+                10,
+                PullRequestListScope::ReviewRequested,
+            ))
+            .unwrap();
+        // then — non-PR search hits are dropped
+        assert!(page.pull_requests.is_empty());
+        let body: serde_json::Value = serde_json::from_str(&server.requests()[0].body).unwrap();
+        assert_eq!(
+            body["variables"]["q"],
+            "repo:agavra/tuicr is:pr is:open sort:created-desc review-requested:@me"
+        );
+    }
+
+    #[test]
+    fn should_pass_api_calls_through_to_rest() {
+        // given
+        let server = StubServer::start(vec![(
+            "POST",
+            "/repos/agavra/tuicr/pulls/125/reviews",
+            200,
+            r#"{"id":77,"html_url":"https://x","state":"COMMENTED"}"#,
+        )]);
+        let runner = runner(&server);
+        let args: Vec<String> = [
+            "api",
+            "repos/agavra/tuicr/pulls/125/reviews",
+            "--method",
+            "POST",
+            "--input",
+            "-",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        // when
+        let output = runner
+            .run_with_stdin(&args, r#"{"event":"COMMENT"}"#)
+            .unwrap();
+        // then
+        assert!(output.contains("\"id\":77"));
+        let recorded = &server.requests()[0];
+        assert_eq!(recorded.body, r#"{"event":"COMMENT"}"#);
+        assert_eq!(
+            recorded.header("Accept"),
+            Some("application/vnd.github+json")
+        );
+    }
+
+    #[test]
+    fn should_fetch_the_job_log_for_run_view() {
+        // given
+        let server = StubServer::start(vec![(
+            "GET",
+            "/repos/agavra/tuicr/actions/jobs/22/logs",
+            200,
+            "2026-01-01T00:00:00.0000000Z error: boom\n",
+        )]);
+        let runner = runner(&server);
+        let args: Vec<String> = [
+            "run",
+            "view",
+            "--job",
+            "22",
+            "--log-failed",
+            "--repo",
+            "agavra/tuicr",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        // when
+        let output = runner.run(&args).unwrap();
+        // then
+        assert!(output.ends_with("error: boom\n"));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn should_request_the_diff_media_type_for_pr_diff() {
+        // given
+        let server = StubServer::start(vec![(
+            "GET",
+            "/repos/agavra/tuicr/pulls/125",
+            200,
+            "diff --git a/x b/x\n",
+        )]);
+        let runner = runner(&server);
+        let args: Vec<String> = [
+            "pr",
+            "diff",
+            "125",
+            "--repo",
+            "agavra/tuicr",
+            "--color",
+            "never",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        // when
+        let output = runner.run(&args).unwrap();
+        // then
+        assert!(output.starts_with("diff --git"));
+        assert_eq!(
+            server.requests()[0].header("Accept"),
+            Some("application/vnd.github.diff")
+        );
+    }
+
+    #[test]
+    fn should_report_a_rejected_token_without_suggesting_gh_login() {
+        // given
+        let server = StubServer::start(vec![(
+            "POST",
+            "/graphql",
+            401,
+            r#"{"message":"Requires authentication"}"#,
+        )]);
+        let backend = GitHubGhBackend::with_runner(Some(repo()), runner(&server));
+        // when
+        let err = backend
+            .get_pull_request(PullRequestTarget::number(125, "125"))
+            .unwrap_err()
+            .to_string();
+        // then
+        assert!(err.contains("GH_TOKEN"), "unexpected error: {err}");
+        assert!(!err.contains("gh auth login"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn should_derive_enterprise_api_urls_from_the_host() {
+        let runner = GhHttpRunner::new(&ForgeConfig::default());
+        assert_eq!(
+            runner.rest_base("ghe.example.com"),
+            "https://ghe.example.com/api/v3"
+        );
+        assert_eq!(
+            runner.graphql_url("ghe.example.com"),
+            "https://ghe.example.com/api/graphql"
+        );
+        assert_eq!(
+            runner.graphql_url("github.com"),
+            "https://api.github.com/graphql"
+        );
+    }
+}
@@ -1,7 +1,11 @@
 pub mod gh;
+pub mod http;
 pub mod models;
+pub mod pending_review;
 pub mod pr_info;
 pub mod review_metadata;
 pub mod review_summaries;
 pub mod review_threads;
 pub mod submit;
+pub mod thread_mutations;
+pub mod viewed_files;
@@ -0,0 +1,283 @@
+//! The viewer's pending (unpublished) GitHub review.
+//!
+//! `:submit draft` creates one by omitting the review `event`. GitHub allows
+//! a single pending review per viewer and PR, so later drafts and the final
+//! publish have to go through it instead of creating a new review.
+//!
+//! The review itself is found with GraphQL, which can filter by state:
+//!
+//! ```json
+//! { "reviews": { "nodes": [{ "id": "PRR_kw...", "databaseId": 77, "body": "" }] } }
+//! ```
+//!
+//! Its comments come from REST, the only API that reports the diff side
+//! per comment. Everything that changes the review — adding threads,
+//! editing or deleting a comment, publishing, discarding — is a GraphQL
+//! mutation keyed by node id, the same as the thread mutations.
+
+use serde::Deserialize;
+
+use crate::error::{Result, TuicrError};
+use crate::forge::remote_comments::{PendingReview, PendingReviewComment, RemoteCommentSide};
+use crate::forge::traits::GhCreateReviewResponse;
+
+pub(crate) const PENDING_REVIEW_QUERY: &str = r#"query($owner: String!, $name: String!, $number: Int!) {
+  repository(owner: $owner, name: $name) {
+    pullRequest(number: $number) {
+      reviews(states: PENDING, first: 1) {
+        nodes { id databaseId body }
+      }
+    }
+  }
+}"#;
+
+pub(crate) const ADD_THREAD_MUTATION: &str = r#"mutation($reviewId: ID!, $path: String!, $body: String!, $line: Int!, $side: DiffSide!, $startLine: Int, $startSide: DiffSide) {
+  addPullRequestReviewThread(input: { pullRequestReviewId: $reviewId, path: $path, body: $body, line: $line, side: $side, startLine: $startLine, startSide: $startSide }) {
+    thread { id }
+  }
+}"#;
+
+pub(crate) const UPDATE_REVIEW_MUTATION: &str = r#"mutation($reviewId: ID!, $body: String!) {
+  updatePullRequestReview(input: { pullRequestReviewId: $reviewId, body: $body }) {
+    pullRequestReview { databaseId url state }
+  }
+}"#;
+
+pub(crate) const SUBMIT_REVIEW_MUTATION: &str = r#"mutation($reviewId: ID!, $event: PullRequestReviewEvent!, $body: String!) {
+  submitPullRequestReview(input: { pullRequestReviewId: $reviewId, event: $event, body: $body }) {
+    pullRequestReview { databaseId url state }
+  }
+}"#;
+
+pub(crate) const DELETE_REVIEW_MUTATION: &str = r#"mutation($reviewId: ID!) {
+  deletePullRequestReview(input: { pullRequestReviewId: $reviewId }) {
+    pullRequestReview { id }
+  }
+}"#;
+
+pub(crate) const UPDATE_COMMENT_MUTATION: &str = r#"mutation($commentId: ID!, $body: String!) {
+  updatePullRequestReviewComment(input: { pullRequestReviewCommentId: $commentId, body: $body }) {
+    pullRequestReviewComment { id }
+  }
+}"#;
+
+pub(crate) const DELETE_COMMENT_MUTATION: &str = r#"mutation($commentId: ID!) {
+  deletePullRequestReviewComment(input: { id: $commentId }) {
+    pullRequestReview { id }
+  }
+}"#;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhPendingReviewNode {
+    id: String,
+    database_id: u64,
+    #[serde(default)]
+    body: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhReviewsConn {
+    #[serde(default)]
+    nodes: Vec<GhPendingReviewNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhPullRequest {
+    #[serde(default)]
+    reviews: Option<GhReviewsConn>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhRepository {
+    #[serde(default, rename = "pullRequest")]
+    pull_request: Option<GhPullRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhData {
+    #[serde(default)]
+    repository: Option<GhRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhResponse {
+    #[serde(default)]
+    data: Option<GhData>,
+}
+
+/// REST review comment; only the fields needed to re-anchor it locally.
+#[derive(Debug, Deserialize)]
+struct GhRestReviewComment {
+    node_id: String,
+    path: String,
+    #[serde(default)]
+    line: Option<u32>,
+    #[serde(default)]
+    start_line: Option<u32>,
+    #[serde(default)]
+    side: Option<String>,
+    #[serde(default)]
+    body: String,
+    #[serde(default)]
+    subject_type: Option<String>,
+    #[serde(default)]
+    in_reply_to_id: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhReviewPayload {
+    database_id: u64,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    state: String,
+}
+
+/// Parse the pending-review lookup into a review without comments. `None`
+/// when the viewer has no pending review on the PR.
+pub(crate) fn parse_pending_review(json: &str) -> Result<Option<PendingReview>> {
+    let response: GhResponse = serde_json::from_str(json).map_err(|e| {
+        TuicrError::Forge(format!(
+            "Failed to parse GitHub pending review response: {e}"
+        ))
+    })?;
+    Ok(response
+        .data
+        .and_then(|d| d.repository)
+        .and_then(|r| r.pull_request)
+        .and_then(|pr| pr.reviews)
+        .and_then(|reviews| reviews.nodes.into_iter().next())
+        .map(|node| PendingReview {
+            id: node.id,
+            database_id: node.database_id,
+            body: node.body,
+            comments: Vec::new(),
+        }))
+}
+
+/// Parse `GET .../reviews/<id>/comments`. Pending replies to published
+/// threads are skipped: they keep rendering inside their thread.
+pub(crate) fn parse_pending_comments(json: &str) -> Result<Vec<PendingReviewComment>> {
+    let comments: Vec<GhRestReviewComment> = serde_json::from_str(json).map_err(|e| {
+        TuicrError::Forge(format!(
+            "Failed to parse GitHub pending comments response: {e}"
+        ))
+    })?;
+    Ok(comments
+        .into_iter()
+        .filter(|c| c.in_reply_to_id.is_none())
+        .map(|c| {
+            let file_level = c.subject_type.as_deref() == Some("file");
+            PendingReviewComment {
+                id: c.node_id,
+                path: c.path,
+                line: if file_level { None } else { c.line },
+                start_line: if file_level { None } else { c.start_line },
+                side: c
+                    .side
+                    .as_deref()
+                    .map(RemoteCommentSide::parse)
+                    .unwrap_or(RemoteCommentSide::Right),
+                body: c.body,
+            }
+        })
+        .collect())
+}
+
+/// Read the review returned by the update and submit mutations. `field` is
+/// the mutation's top-level response key.
+pub(crate) fn parse_review_mutation_response(
+    json: &str,
+    field: &str,
+) -> Result<GhCreateReviewResponse> {
+    let value: serde_json::Value = serde_json::from_str(json)
+        .map_err(|e| TuicrError::Forge(format!("Failed to parse GitHub {field} response: {e}")))?;
+    let review: GhReviewPayload =
+        serde_json::from_value(value["data"][field]["pullRequestReview"].clone())
+            .map_err(|_| TuicrError::Forge(format!("GitHub {field} returned no review")))?;
+    Ok(GhCreateReviewResponse {
+        id: review.database_id,
+        html_url: review.url,
+        state: review.state,
+    })
+}
+
+/// Join the pending review's body with what tuicr adds to it, so publishing
+/// never drops text written earlier on GitHub.
+pub(crate) fn combined_body(existing: &str, addition: &str) -> String {
+    match (existing.trim().is_empty(), addition.trim().is_empty()) {
+        (true, _) => addition.to_string(),
+        (false, true) => existing.to_string(),
+        (false, false) => format!("{existing}\n\n{addition}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_read_the_first_pending_review() {
+        // given
+        let json = r#"{"data":{"repository":{"pullRequest":{"reviews":{"nodes":[
+            {"id":"PRR_kw1","databaseId":77,"body":"So far"}
+        ]}}}}}"#;
+
+        // when
+        let review = parse_pending_review(json).unwrap().unwrap();
+
+        // then
+        assert_eq!(review.id, "PRR_kw1");
+        assert_eq!(review.database_id, 77);
+        assert_eq!(review.body, "So far");
+        assert!(review.comments.is_empty());
+    }
+
+    #[test]
+    fn should_report_no_pending_review_when_none_is_open() {
+        let json = r#"{"data":{"repository":{"pullRequest":{"reviews":{"nodes":[]}}}}}"#;
+        assert_eq!(parse_pending_review(json).unwrap(), None);
+    }
+
+    #[test]
+    fn should_anchor_pending_comments_and_skip_replies() {
+        // given
+        let json = r#"[
+            {"node_id":"PRRC_1","path":"src/lib.rs","line":12,"start_line":10,
+             "side":"LEFT","body":"range","subject_type":"line"},
+            {"node_id":"PRRC_2","path":"src/lib.rs","line":null,"body":"whole file",
+             "subject_type":"file"},
+            {"node_id":"PRRC_3","path":"src/lib.rs","line":4,"side":"RIGHT",
+             "body":"reply","in_reply_to_id":9}
+        ]"#;
+
+        // when
+        let comments = parse_pending_comments(json).unwrap();
+
+        // then
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].line, Some(12));
+        assert_eq!(comments[0].start_line, Some(10));
+        assert_eq!(comments[0].side, RemoteCommentSide::Left);
+        assert_eq!(comments[1].line, None);
+        assert_eq!(comments[1].body, "whole file");
+    }
+
+    #[test]
+    fn should_read_the_review_a_submit_mutation_returns() {
+        let json = r#"{"data":{"submitPullRequestReview":{"pullRequestReview":
+            {"databaseId":77,"url":"https://github.com/o/r/pull/1#pullrequestreview-77","state":"APPROVED"}}}}"#;
+        let response = parse_review_mutation_response(json, "submitPullRequestReview").unwrap();
+        assert_eq!(response.id, 77);
+        assert_eq!(response.state, "APPROVED");
+    }
+
+    #[test]
+    fn should_append_to_an_existing_body() {
+        assert_eq!(combined_body("", "new"), "new");
+        assert_eq!(combined_body("old", ""), "old");
+        assert_eq!(combined_body("old", "new"), "old\n\nnew");
+    }
+}
@@ -0,0 +1,114 @@
+//! GraphQL mutations for replying to and resolving GitHub review threads.
+//!
+//! REST can reply to a review comment but has no notion of thread
+//! resolution, so both operations go through the GraphQL API keyed by the
+//! thread node id (`PRRT_...`) that `review_threads` already fetched.
+//!
+//! GitHub reports mutation failures as HTTP 200 with an `errors` array, so
+//! a zero exit from `gh api graphql` is not enough to call it a success:
+//!
+//! ```json
+//! {
+//!   "data": { "resolveReviewThread": null },
+//!   "errors": [{ "message": "Could not resolve to a node with the global id of 'PRRT_x'" }]
+//! }
+//! ```
+
+use serde::Deserialize;
+
+use crate::error::{Result, TuicrError};
+
+pub(crate) const REPLY_MUTATION: &str = r#"mutation($threadId: ID!, $body: String!) {
+  addPullRequestReviewThreadReply(input: { pullRequestReviewThreadId: $threadId, body: $body }) {
+    comment { id }
+  }
+}"#;
+
+pub(crate) const RESOLVE_MUTATION: &str = r#"mutation($threadId: ID!) {
+  resolveReviewThread(input: { threadId: $threadId }) {
+    thread { id isResolved }
+  }
+}"#;
+
+pub(crate) const UNRESOLVE_MUTATION: &str = r#"mutation($threadId: ID!) {
+  unresolveReviewThread(input: { threadId: $threadId }) {
+    thread { id isResolved }
+  }
+}"#;
+
+#[derive(Debug, Deserialize)]
+struct GhGraphqlError {
+    #[serde(default)]
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhMutationResponse {
+    #[serde(default)]
+    errors: Vec<GhGraphqlError>,
+}
+
+/// Surface GraphQL-level errors from a mutation response. `action` names the
+/// operation for the error message (e.g. "reply to review thread").
+pub(crate) fn check_mutation_response(json: &str, action: &str) -> Result<()> {
+    let response: GhMutationResponse = serde_json::from_str(json)
+        .map_err(|e| TuicrError::Forge(format!("Failed to parse GitHub {action} response: {e}")))?;
+    if response.errors.is_empty() {
+        return Ok(());
+    }
+    let detail = response
+        .errors
+        .into_iter()
+        .map(|e| e.message)
+        .filter(|m| !m.is_empty())
+        .collect::<Vec<_>>()
+        .join("; ");
+    Err(TuicrError::Forge(format!(
+        "GitHub could not {action}: {detail}"
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_accept_mutation_response_without_errors() {
+        // given
+        let json =
+            r#"{"data":{"resolveReviewThread":{"thread":{"id":"PRRT_1","isResolved":true}}}}"#;
+
+        // when
+        let result = check_mutation_response(json, "resolve review thread");
+
+        // then
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn should_surface_graphql_errors_from_mutation_response() {
+        // given
+        let json = r#"{"data":{"resolveReviewThread":null},"errors":[{"message":"Could not resolve to a node"},{"message":"second"}]}"#;
+
+        // when
+        let err = check_mutation_response(json, "resolve review thread").unwrap_err();
+
+        // then
+        let TuicrError::Forge(message) = err else {
+            panic!("expected forge error");
+        };
+        assert_eq!(
+            message,
+            "GitHub could not resolve review thread: Could not resolve to a node; second"
+        );
+    }
+
+    #[test]
+    fn should_reject_malformed_mutation_response() {
+        // given / when
+        let result = check_mutation_response("not json", "reply to review thread");
+
+        // then
+        assert!(matches!(result, Err(TuicrError::Forge(_))));
+    }
+}
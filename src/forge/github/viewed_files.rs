@@ -0,0 +1,184 @@
+//! GraphQL for GitHub's per-file "Viewed" checkbox.
+//!
+//! The state is per viewer and lives on `PullRequestChangedFile`:
+//!
+//! ```json
+//! { "path": "src/lib.rs", "viewerViewedState": "VIEWED" }
+//! ```
+//!
+//! `DISMISSED` means the file was viewed but has changed since, which GitHub
+//! renders as unchecked, so only `VIEWED` counts. The mutations are keyed by
+//! the pull request node id rather than owner/name/number.
+
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::error::{Result, TuicrError};
+use crate::forge::github::review_threads::GhPageInfo;
+
+pub(crate) const PULL_REQUEST_ID_QUERY: &str = r#"query($owner: String!, $name: String!, $number: Int!) {
+  repository(owner: $owner, name: $name) {
+    pullRequest(number: $number) { id }
+  }
+}"#;
+
+pub(crate) const MARK_VIEWED_MUTATION: &str = r#"mutation($pullRequestId: ID!, $path: String!) {
+  markFileAsViewed(input: { pullRequestId: $pullRequestId, path: $path }) {
+    clientMutationId
+  }
+}"#;
+
+pub(crate) const UNMARK_VIEWED_MUTATION: &str = r#"mutation($pullRequestId: ID!, $path: String!) {
+  unmarkFileAsViewed(input: { pullRequestId: $pullRequestId, path: $path }) {
+    clientMutationId
+  }
+}"#;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhChangedFile {
+    path: String,
+    #[serde(default)]
+    viewer_viewed_state: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhFilesConn {
+    #[serde(default)]
+    page_info: Option<GhPageInfo>,
+    #[serde(default)]
+    nodes: Vec<GhChangedFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhPullRequest {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    files: Option<GhFilesConn>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhRepository {
+    #[serde(default, rename = "pullRequest")]
+    pull_request: Option<GhPullRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhData {
+    #[serde(default)]
+    repository: Option<GhRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhResponse {
+    #[serde(default)]
+    data: Option<GhData>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ParsedViewedFilesPage {
+    pub viewed: Vec<PathBuf>,
+    pub page_info: Option<GhPageInfo>,
+}
+
+fn parse_pull_request(json: &str) -> Result<Option<GhPullRequest>> {
+    let response: GhResponse = serde_json::from_str(json).map_err(|e| {
+        TuicrError::Forge(format!("Failed to parse GitHub viewed files response: {e}"))
+    })?;
+    Ok(response
+        .data
+        .and_then(|d| d.repository)
+        .and_then(|r| r.pull_request))
+}
+
+pub(crate) fn parse_graphql_page(json: &str) -> Result<ParsedViewedFilesPage> {
+    let Some(conn) = parse_pull_request(json)?.and_then(|pr| pr.files) else {
+        return Ok(ParsedViewedFilesPage {
+            viewed: Vec::new(),
+            page_info: None,
+        });
+    };
+    let viewed = conn
+        .nodes
+        .into_iter()
+        .filter(|file| file.viewer_viewed_state.as_deref() == Some("VIEWED"))
+        .map(|file| PathBuf::from(file.path))
+        .collect();
+    Ok(ParsedViewedFilesPage {
+        viewed,
+        page_info: conn.page_info,
+    })
+}
+
+pub(crate) fn parse_pull_request_id(json: &str) -> Result<String> {
+    parse_pull_request(json)?
+        .and_then(|pr| pr.id)
+        .ok_or_else(|| TuicrError::Forge("GitHub returned no pull request id".to_string()))
+}
+
+pub(crate) fn build_query(after_cursor: Option<&str>) -> String {
+    format!(
+        r#"query($owner: String!, $name: String!, $number: Int!{cursor_param}) {{
+  repository(owner: $owner, name: $name) {{
+    pullRequest(number: $number) {{
+      files(first: 100{cursor_arg}) {{
+        pageInfo {{ hasNextPage endCursor }}
+        nodes {{ path viewerViewedState }}
+      }}
+    }}
+  }}
+}}"#,
+        cursor_param = if after_cursor.is_some() {
+            ", $after: String!"
+        } else {
+            ""
+        },
+        cursor_arg = if after_cursor.is_some() {
+            ", after: $after"
+        } else {
+            ""
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_keep_only_files_marked_viewed() {
+        // given
+        let json = r#"{"data":{"repository":{"pullRequest":{"files":{
+            "pageInfo":{"hasNextPage":true,"endCursor":"Y3Vy"},
+            "nodes":[
+                {"path":"src/a.rs","viewerViewedState":"VIEWED"},
+                {"path":"src/b.rs","viewerViewedState":"UNVIEWED"},
+                {"path":"src/c.rs","viewerViewedState":"DISMISSED"}
+            ]}}}}}"#;
+
+        // when
+        let parsed = parse_graphql_page(json).unwrap();
+
+        // then
+        assert_eq!(parsed.viewed, vec![PathBuf::from("src/a.rs")]);
+        let page_info = parsed.page_info.unwrap();
+        assert!(page_info.has_next_page);
+        assert_eq!(page_info.end_cursor.as_deref(), Some("Y3Vy"));
+    }
+
+    #[test]
+    fn should_read_the_pull_request_node_id() {
+        let json = r#"{"data":{"repository":{"pullRequest":{"id":"PR_kw1"}}}}"#;
+        assert_eq!(parse_pull_request_id(json).unwrap(), "PR_kw1");
+        assert!(parse_pull_request_id(r#"{"data":{"repository":null}}"#).is_err());
+    }
+
+    #[test]
+    fn should_only_declare_the_cursor_on_later_pages() {
+        assert!(!build_query(None).contains("$after"));
+        assert!(build_query(Some("c")).contains("after: $after"));
+    }
+}
@@ -8,9 +8,10 @@ use sha1::{Digest, Sha1};
 use crate::error::{Result, TuicrError};
 use crate::forge::remote_comments::RemoteReviewThread;
 use crate::forge::traits::{
-    ForgeBackend, ForgeFileLinesRequest, ForgeRepository, GhCreateReviewResponse,
-    PagedPullRequests, PullRequestCommit, PullRequestDetails, PullRequestListQuery,
-    PullRequestListScope, PullRequestReviewMetadata, PullRequestReviewRecord, PullRequestTarget,
+    ForgeBackend, ForgeFileLinesRequest, ForgeRepository, GhCreateReviewResponse, MergeMethod,
+    PagedPullRequests, PullRequestCheckStatus, PullRequestCommit, PullRequestDetails,
+    PullRequestInfo, PullRequestListQuery, PullRequestListScope, PullRequestReviewMetadata,
+    PullRequestReviewRecord, PullRequestTarget,
 };
 use crate::model::{DiffLine, FilePatch, LineOrigin};
 use crate::process::{
@@ -20,8 +21,8 @@ use crate::vcs::git::raw::run_git_diff;
 use crate::vcs::slice_context_lines;
 
 use super::models::{
-    GlabApprovalState, GlabCommit, GlabDiff, GlabDiscussion, GlabMrDetails, GlabMrSummary,
-    GlabMrVersion, GlabUser,
+    GlabApprovalState, GlabCommit, GlabDiff, GlabDiscussion, GlabJob, GlabMrDetails,
+    GlabMrReviewers, GlabMrSummary, GlabMrVersion, GlabPipeline, GlabUser, GlabUserRef,
 };
 use crate::forge::submit::{DiffAnchor, GhSide, SubmitEvent};
 use crate::forge::traits::CreateReviewRequest;
@@ -72,12 +73,12 @@ impl From<CommandOutputError> for GlabCommandError {
     fn from(error: CommandOutputError) -> Self {
         match error.kind {
             CommandOutputErrorKind::NotFound => Self::MissingGlab,
-            CommandOutputErrorKind::SpawnFailed | CommandOutputErrorKind::Unsuccessful => {
-                Self::Failed {
-                    status: error.status,
-                    stderr: error.stderr,
-                }
-            }
+            CommandOutputErrorKind::SpawnFailed
+            | CommandOutputErrorKind::Unsuccessful
+            | CommandOutputErrorKind::TimedOut => Self::Failed {
+                status: error.status,
+                stderr: error.stderr,
+            },
         }
     }
 }
@@ -120,6 +121,13 @@ fn local_range_diff(repo_root: &Path, start_sha: &str, end_sha: &str) -> Option<
     run_git_diff(repo_root, &[range.as_str()]).ok()
 }
 
+/// Job id from a `https://<host>/<project>/-/jobs/<id>` URL.
+fn gitlab_job_id(url: &str) -> Option<&str> {
+    let (_, rest) = url.split_once("/-/jobs/")?;
+    let job = rest.split(['/', '?', '#']).next()?;
+    (!job.is_empty() && job.bytes().all(|b| b.is_ascii_digit())).then_some(job)
+}
+
 /// Percent-encode `owner/repo` as `owner%2Frepo` for GitLab project API paths.
 fn gl_project_path(owner: &str, name: &str) -> String {
     format!("{}/{}", owner, name).replace('/', "%2F")
@@ -161,6 +169,7 @@ pub struct GitLabGlabBackend<R = SystemGlabRunner> {
     default_repository: Option<ForgeRepository>,
     runner: R,
     local_checkout: Option<PathBuf>,
+    show_pr_checks: bool,
 }
 
 impl GitLabGlabBackend<SystemGlabRunner> {
@@ -169,13 +178,9 @@ impl GitLabGlabBackend<SystemGlabRunner> {
             default_repository,
             runner: SystemGlabRunner,
             local_checkout: None,
+            show_pr_checks: false,
         }
     }
-
-    pub fn with_local_checkout(mut self, checkout: Option<PathBuf>) -> Self {
-        self.local_checkout = checkout;
-        self
-    }
 }
 
 impl<R> GitLabGlabBackend<R>
@@ -187,9 +192,20 @@ where
             default_repository,
             runner,
             local_checkout: None,
+            show_pr_checks: false,
         }
     }
 
+    pub fn with_local_checkout(mut self, checkout: Option<PathBuf>) -> Self {
+        self.local_checkout = checkout;
+        self
+    }
+
+    pub fn with_pr_checks(mut self, show_pr_checks: bool) -> Self {
+        self.show_pr_checks = show_pr_checks;
+        self
+    }
+
     fn resolve_repository(&self, target: &PullRequestTarget) -> Result<ForgeRepository> {
         target
             .repository
@@ -392,6 +408,19 @@ where
         mr.into_details(&repository)
     }
 
+    fn get_pull_request_info(&self, target: PullRequestTarget) -> Result<PullRequestInfo> {
+        let details = self.get_pull_request(target)?;
+        let checks = if self.show_pr_checks {
+            self.pipeline_checks(&details)?
+        } else {
+            Vec::new()
+        };
+        Ok(PullRequestInfo {
+            checks,
+            ..PullRequestInfo::from_details(details)
+        })
+    }
+
     fn get_pull_request_diff(&self, pr: &PullRequestDetails) -> Result<Vec<FilePatch>> {
         let project = gl_project_path(&pr.repository.owner, &pr.repository.name);
         let mut patches = Vec::new();
@@ -789,12 +818,192 @@ where
             state: state.to_string(),
         })
     }
+
+    fn reply_to_review_thread(
+        &self,
+        pr: &PullRequestDetails,
+        thread: &RemoteReviewThread,
+        body: &str,
+    ) -> Result<()> {
+        // GitLab threads are discussions; the thread id is the discussion id.
+        let project = gl_project_path(&pr.repository.owner, &pr.repository.name);
+        let endpoint = format!(
+            "projects/{}/merge_requests/{}/discussions/{}/notes",
+            project, pr.number, thread.id,
+        );
+        let body_json = serde_json::to_string(&serde_json::json!({ "body": body }))?;
+        let mut args = vec![
+            "api".to_string(),
+            endpoint,
+            "--method".to_string(),
+            "POST".to_string(),
+            "--header".to_string(),
+            "Content-Type: application/json".to_string(),
+            "--input".to_string(),
+            "-".to_string(),
+        ];
+        args.extend(Self::api_hostname_args(&pr.repository));
+        self.runner
+            .run_with_stdin(&args, &body_json)
+            .map_err(|err| map_create_notes_error(err, &pr.repository.host))?;
+        Ok(())
+    }
+
+    fn set_review_thread_resolved(
+        &self,
+        pr: &PullRequestDetails,
+        thread: &RemoteReviewThread,
+        resolved: bool,
+    ) -> Result<()> {
+        let project = gl_project_path(&pr.repository.owner, &pr.repository.name);
+        let endpoint = format!(
+            "projects/{}/merge_requests/{}/discussions/{}?resolved={}",
+            project, pr.number, thread.id, resolved,
+        );
+        let mut args = vec![
+            "api".to_string(),
+            endpoint,
+            "--method".to_string(),
+            "PUT".to_string(),
+        ];
+        args.extend(Self::api_hostname_args(&pr.repository));
+        self.run_glab(args, &pr.repository.host)?;
+        Ok(())
+    }
+
+    fn merge_pull_request(&self, pr: &PullRequestDetails, method: MergeMethod) -> Result<()> {
+        // The merge endpoint only chooses between the project's merge method
+        // and a squash; rebasing is a separate, asynchronous call.
+        let squash = match method {
+            MergeMethod::Merge => false,
+            MergeMethod::Squash => true,
+            MergeMethod::Rebase => {
+                return Err(TuicrError::UnsupportedOperation(
+                    "GitLab merges with the project's merge method; use `:merge` or `:merge squash`"
+                        .to_string(),
+                ));
+            }
+        };
+        let project = gl_project_path(&pr.repository.owner, &pr.repository.name);
+        let endpoint = format!("projects/{}/merge_requests/{}/merge", project, pr.number);
+        // `sha` makes GitLab refuse the merge if the head moved since the MR
+        // was opened.
+        let payload = serde_json::json!({ "sha": pr.head_sha, "squash": squash });
+        self.run_api_json(&pr.repository, "PUT", endpoint, &payload)
+    }
+
+    fn close_pull_request(&self, pr: &PullRequestDetails) -> Result<()> {
+        let project = gl_project_path(&pr.repository.owner, &pr.repository.name);
+        let endpoint = format!("projects/{}/merge_requests/{}", project, pr.number);
+        let payload = serde_json::json!({ "state_event": "close" });
+        self.run_api_json(&pr.repository, "PUT", endpoint, &payload)
+    }
+
+    fn request_reviewer(&self, pr: &PullRequestDetails, username: &str) -> Result<()> {
+        // Reviewers are set by user id, and the update replaces the whole
+        // list, so resolve the user and keep the current reviewers.
+        let output = self.run_api(
+            &pr.repository,
+            format!(
+                "users?username={}",
+                crate::forge::http::encode_query(username)
+            ),
+        )?;
+        let users: Vec<GlabUserRef> = serde_json::from_str(&output)?;
+        let user = users
+            .first()
+            .ok_or_else(|| TuicrError::Forge(format!("GitLab user `{username}` was not found")))?;
+
+        let project = gl_project_path(&pr.repository.owner, &pr.repository.name);
+        let endpoint = format!("projects/{}/merge_requests/{}", project, pr.number);
+        let current: GlabMrReviewers =
+            serde_json::from_str(&self.run_api(&pr.repository, endpoint.clone())?)?;
+        let mut reviewer_ids: Vec<u64> = current.reviewers.iter().map(|r| r.id).collect();
+        if !reviewer_ids.contains(&user.id) {
+            reviewer_ids.push(user.id);
+        }
+        let payload = serde_json::json!({ "reviewer_ids": reviewer_ids });
+        self.run_api_json(&pr.repository, "PUT", endpoint, &payload)
+    }
+
+    fn add_label(&self, pr: &PullRequestDetails, label: &str) -> Result<()> {
+        let project = gl_project_path(&pr.repository.owner, &pr.repository.name);
+        let endpoint = format!("projects/{}/merge_requests/{}", project, pr.number);
+        let payload = serde_json::json!({ "add_labels": label });
+        self.run_api_json(&pr.repository, "PUT", endpoint, &payload)
+    }
+
+    fn fetch_check_log(
+        &self,
+        pr: &PullRequestDetails,
+        check: &PullRequestCheckStatus,
+    ) -> Result<String> {
+        let Some(job_id) = check.url.as_deref().and_then(gitlab_job_id) else {
+            return Err(TuicrError::UnsupportedOperation(format!(
+                "`{}` is not a GitLab CI job",
+                check.name
+            )));
+        };
+        let project = gl_project_path(&pr.repository.owner, &pr.repository.name);
+        self.run_api(
+            &pr.repository,
+            format!("projects/{project}/jobs/{job_id}/trace"),
+        )
+    }
 }
 
 impl<R> GitLabGlabBackend<R>
 where
     R: GlabCommandRunner,
 {
+    /// Jobs of the merge request's latest pipeline, as checks. An MR
+    /// without a pipeline has none.
+    fn pipeline_checks(&self, pr: &PullRequestDetails) -> Result<Vec<PullRequestCheckStatus>> {
+        let project = gl_project_path(&pr.repository.owner, &pr.repository.name);
+        let output = self.run_api(
+            &pr.repository,
+            format!("projects/{project}/merge_requests/{}/pipelines", pr.number),
+        )?;
+        let pipelines: Vec<GlabPipeline> = serde_json::from_str(&output)?;
+        let Some(latest) = pipelines.first() else {
+            return Ok(Vec::new());
+        };
+        let output = self.run_api(
+            &pr.repository,
+            format!(
+                "projects/{project}/pipelines/{}/jobs?per_page=100",
+                latest.id
+            ),
+        )?;
+        let jobs: Vec<GlabJob> = serde_json::from_str(&output)?;
+        Ok(jobs.into_iter().map(GlabJob::into_check).collect())
+    }
+
+    /// Send `payload` as the JSON body of a `glab api` call.
+    fn run_api_json(
+        &self,
+        repo: &ForgeRepository,
+        method: &str,
+        endpoint: String,
+        payload: &serde_json::Value,
+    ) -> Result<()> {
+        let mut args = vec![
+            "api".to_string(),
+            endpoint,
+            "--method".to_string(),
+            method.to_string(),
+            "--header".to_string(),
+            "Content-Type: application/json".to_string(),
+            "--input".to_string(),
+            "-".to_string(),
+        ];
+        args.extend(Self::api_hostname_args(repo));
+        self.runner
+            .run_with_stdin(&args, &payload.to_string())
+            .map_err(|err| map_glab_error(err, &repo.host))?;
+        Ok(())
+    }
+
     fn fetch_file_via_api(&self, request: &ForgeFileLinesRequest) -> Result<String> {
         let project = gl_project_path(&request.repository.owner, &request.repository.name);
         let path_str = request.path.to_string_lossy().replace('\\', "/");
@@ -1239,8 +1448,8 @@ mod tests {
     use super::*;
     use crate::forge::submit::{DiffAnchor, GhSide, InlineComment, RangeAnchors};
     use crate::forge::traits::{
-        CreateReviewRequest, ForgeRepository, PullRequestDetails, PullRequestListQuery,
-        PullRequestListScope,
+        CreateReviewRequest, ForgeRepository, MergeMethod, PullRequestDetails,
+        PullRequestListQuery, PullRequestListScope,
     };
 
     /// Mock runner that records (args, stdin) calls.
@@ -1434,6 +1643,217 @@ mod tests {
         }
     }
 
+    fn discussion_thread(id: &str) -> RemoteReviewThread {
+        RemoteReviewThread {
+            id: id.to_string(),
+            path: "src/lib.rs".to_string(),
+            line: Some(3),
+            side: crate::forge::remote_comments::RemoteCommentSide::Right,
+            is_resolved: false,
+            is_outdated: false,
+            comments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn should_reply_to_discussion_with_json_note() {
+        // given
+        let repo = ForgeRepository::gitlab("gitlab.com", "owner", "repo");
+        let pr = make_pr_details(repo.clone());
+        let runner = RecordingRunner::new_with_responses(vec![r#"{"id":7}"#.to_string()]);
+        let backend = GitLabGlabBackend::with_runner(Some(repo), runner);
+
+        // when
+        backend
+            .reply_to_review_thread(&pr, &discussion_thread("disc-1"), "done")
+            .unwrap();
+
+        // then
+        let calls = backend.runner.calls.borrow();
+        assert_eq!(calls.len(), 1);
+        let (args, stdin) = &calls[0];
+        assert_eq!(
+            args[1],
+            "projects/owner%2Frepo/merge_requests/42/discussions/disc-1/notes"
+        );
+        assert!(args.contains(&"POST".to_string()));
+        let body: serde_json::Value = serde_json::from_str(stdin.as_ref().unwrap()).unwrap();
+        assert_eq!(body["body"], "done");
+    }
+
+    #[test]
+    fn should_toggle_discussion_resolution_with_put() {
+        // given
+        let repo = ForgeRepository::gitlab("gitlab.com", "owner", "repo");
+        let pr = make_pr_details(repo.clone());
+        let runner = RecordingRunner::new_with_responses(vec!["{}".to_string(), "{}".to_string()]);
+        let backend = GitLabGlabBackend::with_runner(Some(repo), runner);
+        let thread = discussion_thread("disc-1");
+
+        // when
+        backend
+            .set_review_thread_resolved(&pr, &thread, true)
+            .unwrap();
+        backend
+            .set_review_thread_resolved(&pr, &thread, false)
+            .unwrap();
+
+        // then
+        let calls = backend.runner.calls.borrow();
+        assert_eq!(
+            calls[0].0[1],
+            "projects/owner%2Frepo/merge_requests/42/discussions/disc-1?resolved=true"
+        );
+        assert_eq!(
+            calls[1].0[1],
+            "projects/owner%2Frepo/merge_requests/42/discussions/disc-1?resolved=false"
+        );
+        assert!(
+            calls
+                .iter()
+                .all(|(args, _)| args.contains(&"PUT".to_string()))
+        );
+    }
+
+    #[test]
+    fn should_merge_with_squash_pinned_to_the_reviewed_head() {
+        // given
+        let repo = ForgeRepository::gitlab("gitlab.com", "owner", "repo");
+        let pr = make_pr_details(repo.clone());
+        let runner = RecordingRunner::new_with_responses(vec!["{}".to_string()]);
+        let backend = GitLabGlabBackend::with_runner(Some(repo), runner);
+
+        // when
+        backend
+            .merge_pull_request(&pr, MergeMethod::Squash)
+            .unwrap();
+        let rebase = backend.merge_pull_request(&pr, MergeMethod::Rebase);
+
+        // then
+        let calls = backend.runner.calls.borrow();
+        assert_eq!(calls.len(), 1);
+        let (args, stdin) = &calls[0];
+        assert_eq!(args[1], "projects/owner%2Frepo/merge_requests/42/merge");
+        assert!(args.contains(&"PUT".to_string()));
+        let body: serde_json::Value = serde_json::from_str(stdin.as_ref().unwrap()).unwrap();
+        assert_eq!(body["sha"], "headsha1");
+        assert_eq!(body["squash"], true);
+        assert!(matches!(rebase, Err(TuicrError::UnsupportedOperation(_))));
+    }
+
+    #[test]
+    fn should_list_the_latest_pipeline_jobs_as_checks() {
+        // given
+        let repo = ForgeRepository::gitlab("gitlab.com", "owner", "repo");
+        let pr = make_pr_details(repo.clone());
+        let runner = RecordingRunner::new_with_responses(vec![
+            r#"[{"id":91},{"id":90}]"#.to_string(),
+            r#"[
+                {"name":"test","status":"failed","web_url":"https://gitlab.com/owner/repo/-/jobs/7"},
+                {"name":"lint","status":"failed","allow_failure":true,"web_url":""},
+                {"name":"build","status":"success"}
+            ]"#
+            .to_string(),
+        ]);
+        let backend = GitLabGlabBackend::with_runner(Some(repo), runner);
+
+        // when
+        let checks = backend.pipeline_checks(&pr).unwrap();
+
+        // then
+        let calls = backend.runner.calls.borrow();
+        assert_eq!(
+            calls[1].0[1],
+            "projects/owner%2Frepo/pipelines/91/jobs?per_page=100"
+        );
+        let conclusions: Vec<_> = checks
+            .iter()
+            .map(|c| (c.name.as_str(), c.conclusion.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            conclusions,
+            vec![
+                ("test", "FAILURE"),
+                ("lint", "NEUTRAL"),
+                ("build", "SUCCESS")
+            ]
+        );
+        assert_eq!(checks[1].url, None);
+    }
+
+    #[test]
+    fn should_fetch_the_job_trace_for_a_check() {
+        // given
+        let repo = ForgeRepository::gitlab("gitlab.com", "owner", "repo");
+        let pr = make_pr_details(repo.clone());
+        let runner = RecordingRunner::new_with_responses(vec!["$ cargo test\n".to_string()]);
+        let backend = GitLabGlabBackend::with_runner(Some(repo), runner);
+        let check = PullRequestCheckStatus {
+            name: "test".to_string(),
+            status: None,
+            conclusion: Some("FAILURE".to_string()),
+            url: Some("https://gitlab.com/owner/repo/-/jobs/7".to_string()),
+        };
+
+        // when
+        let log = backend.fetch_check_log(&pr, &check).unwrap();
+
+        // then
+        assert_eq!(log, "$ cargo test\n");
+        let calls = backend.runner.calls.borrow();
+        assert_eq!(calls[0].0[1], "projects/owner%2Frepo/jobs/7/trace");
+    }
+
+    #[test]
+    fn should_add_a_reviewer_without_dropping_the_current_ones() {
+        // given
+        let repo = ForgeRepository::gitlab("gitlab.com", "owner", "repo");
+        let pr = make_pr_details(repo.clone());
+        let runner = RecordingRunner::new_with_responses(vec![
+            r#"[{"id":9,"username":"carol"}]"#.to_string(),
+            r#"{"iid":42,"reviewers":[{"id":3,"username":"bob"}]}"#.to_string(),
+            "{}".to_string(),
+        ]);
+        let backend = GitLabGlabBackend::with_runner(Some(repo), runner);
+
+        // when
+        backend.request_reviewer(&pr, "carol").unwrap();
+
+        // then
+        let calls = backend.runner.calls.borrow();
+        assert_eq!(calls[0].0[1], "users?username=carol");
+        assert_eq!(calls[1].0[1], "projects/owner%2Frepo/merge_requests/42");
+        let body: serde_json::Value = serde_json::from_str(calls[2].1.as_ref().unwrap()).unwrap();
+        assert_eq!(body["reviewer_ids"], serde_json::json!([3, 9]));
+    }
+
+    #[test]
+    fn should_close_and_label_through_the_merge_request_update() {
+        // given
+        let repo = ForgeRepository::gitlab("gitlab.com", "owner", "repo");
+        let pr = make_pr_details(repo.clone());
+        let runner = RecordingRunner::new_with_responses(vec!["{}".to_string(), "{}".to_string()]);
+        let backend = GitLabGlabBackend::with_runner(Some(repo), runner);
+
+        // when
+        backend.close_pull_request(&pr).unwrap();
+        backend.add_label(&pr, "backend").unwrap();
+
+        // then
+        let calls = backend.runner.calls.borrow();
+        let bodies: Vec<serde_json::Value> = calls
+            .iter()
+            .map(|(_, stdin)| serde_json::from_str(stdin.as_ref().unwrap()).unwrap())
+            .collect();
+        assert_eq!(bodies[0], serde_json::json!({ "state_event": "close" }));
+        assert_eq!(bodies[1], serde_json::json!({ "add_labels": "backend" }));
+        assert!(
+            calls
+                .iter()
+                .all(|(args, _)| args[1] == "projects/owner%2Frepo/merge_requests/42")
+        );
+    }
+
     #[test]
     fn should_fetch_structured_merge_request_diffs_instead_of_injecting_headers() {
         let repo = ForgeRepository::gitlab("gitlab.com", "owner", "repo");
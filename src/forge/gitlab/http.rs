@@ -0,0 +1,340 @@
+//! `GlabCommandRunner` that talks to the GitLab API directly instead of `glab`.
+//!
+//! `glab api` invocations map straight onto `/api/v4` (or `/api/graphql`).
+//! `mr list` and `mr view` are translated to the merge request endpoints
+//! whose JSON `glab --output json` prints verbatim.
+
+use crate::config::ForgeConfig;
+use crate::forge::http::{
+    ApiArgs, ConfiguredTokens, HttpClient, HttpRequest, encode_query, failure_message, join_url,
+    resolve_token,
+};
+
+use super::glab::{GlabCommandError, GlabCommandResult, GlabCommandRunner};
+
+const DEFAULT_GITLAB_HOST: &str = "gitlab.com";
+const TOKEN_HINT: &str =
+    "set GITLAB_TOKEN or `[forge.tokens]` to a personal access token with the `api` scope";
+
+pub struct GlabHttpRunner {
+    client: HttpClient,
+    configured_tokens: ConfiguredTokens,
+    api_url: Option<String>,
+}
+
+impl GlabHttpRunner {
+    pub fn new(config: &ForgeConfig) -> Self {
+        Self {
+            client: HttpClient::new(),
+            configured_tokens: ConfiguredTokens::new(config),
+            api_url: config.api_url.clone(),
+        }
+    }
+
+    fn rest_base(&self, host: &str) -> String {
+        match &self.api_url {
+            Some(url) => url.clone(),
+            None => format!("https://{host}/api/v4"),
+        }
+    }
+
+    fn graphql_url(&self, host: &str) -> String {
+        match &self.api_url {
+            Some(url) => join_url(url, "graphql"),
+            None => format!("https://{host}/api/graphql"),
+        }
+    }
+
+    fn send(&self, host: &str, mut request: HttpRequest) -> GlabCommandResult<String> {
+        if let Some(token) = resolve_token(
+            &["GITLAB_TOKEN", "GL_TOKEN"],
+            self.configured_tokens.for_host(host),
+        ) {
+            request = request.header("Authorization", format!("Bearer {token}"));
+        }
+        let (status, body) = self.client.send(&request).map_err(failed)?;
+        if (200..300).contains(&status) {
+            Ok(body)
+        } else {
+            Err(failed(failure_message(status, &body, host, TOKEN_HINT)))
+        }
+    }
+
+    fn get(&self, host: &str, endpoint: &str) -> GlabCommandResult<String> {
+        let url = join_url(&self.rest_base(host), endpoint);
+        self.send(host, HttpRequest::new("GET", url))
+    }
+
+    fn run_api(&self, api: ApiArgs, stdin: &str) -> GlabCommandResult<String> {
+        let host = api
+            .hostname
+            .clone()
+            .unwrap_or_else(|| DEFAULT_GITLAB_HOST.to_string());
+        let body = api.body(stdin);
+        let url = if api.is_graphql() {
+            self.graphql_url(&host)
+        } else {
+            join_url(&self.rest_base(&host), &api.endpoint)
+        };
+        let mut request = HttpRequest::new(&api.effective_method(body.is_some()), url);
+        for (name, value) in &api.headers {
+            request = request.header(name, value.clone());
+        }
+        if let Some(body) = body {
+            request = request.body(body);
+        }
+        self.send(&host, request)
+    }
+
+    /// `glab mr list --per-page N` returns up to N merge requests; GitLab
+    /// caps a page at 100, so larger requests walk the pages.
+    fn mr_list(&self, flags: &MrFlags) -> GlabCommandResult<String> {
+        let (host, project) = flags.project()?;
+        let wanted = flags.per_page.unwrap_or(30).max(1);
+        let mut filter = String::new();
+        if flags.reviewer_me {
+            let user: serde_json::Value = parse(&self.get(&host, "user")?)?;
+            let username = user["username"]
+                .as_str()
+                .ok_or_else(|| failed("could not determine the authenticated user".to_string()))?;
+            filter = format!("&reviewer_username={}", encode_query(username));
+        }
+        let per_page = wanted.min(100);
+        let mut rows: Vec<serde_json::Value> = Vec::new();
+        for page in 1.. {
+            let endpoint = format!(
+                "projects/{}/merge_requests?state=opened&per_page={per_page}&page={page}{filter}",
+                encode_project(&project)
+            );
+            let batch: Vec<serde_json::Value> = parse(&self.get(&host, &endpoint)?)?;
+            let received = batch.len();
+            rows.extend(batch);
+            if received < per_page || rows.len() >= wanted {
+                break;
+            }
+        }
+        rows.truncate(wanted);
+        serde_json::to_string(&rows).map_err(|err| failed(err.to_string()))
+    }
+
+    fn mr_view(&self, flags: &MrFlags) -> GlabCommandResult<String> {
+        let (host, project) = flags.project()?;
+        let number = flags
+            .number
+            .as_deref()
+            .ok_or_else(|| failed("missing merge request number".to_string()))?;
+        self.get(
+            &host,
+            &format!(
+                "projects/{}/merge_requests/{number}",
+                encode_project(&project)
+            ),
+        )
+    }
+}
+
+impl GlabCommandRunner for GlabHttpRunner {
+    fn run(&self, args: &[String]) -> GlabCommandResult<String> {
+        self.run_with_stdin(args, "")
+    }
+
+    fn run_with_stdin(&self, args: &[String], stdin: &str) -> GlabCommandResult<String> {
+        if let Some(api) = ApiArgs::parse(args) {
+            return self.run_api(api, stdin);
+        }
+        let flags = MrFlags::parse(args);
+        match (
+            args.first().map(String::as_str),
+            args.get(1).map(String::as_str),
+        ) {
+            (Some("mr"), Some("list")) => self.mr_list(&flags),
+            (Some("mr"), Some("view")) => self.mr_view(&flags),
+            _ => Err(failed(format!(
+                "`glab {}` is not available over the HTTP transport",
+                args.join(" ")
+            ))),
+        }
+    }
+}
+
+/// Flags of the `glab mr <list|view>` invocations the backend builds.
+#[derive(Debug, Default)]
+struct MrFlags {
+    number: Option<String>,
+    repo: Option<String>,
+    per_page: Option<usize>,
+    reviewer_me: bool,
+}
+
+impl MrFlags {
+    fn parse(args: &[String]) -> Self {
+        let mut flags = Self::default();
+        let mut iter = args.iter().skip(2);
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--repo" | "-R" => flags.repo = iter.next().cloned(),
+                "--per-page" | "-P" => flags.per_page = iter.next().and_then(|v| v.parse().ok()),
+                "--reviewer=@me" => flags.reviewer_me = true,
+                "--output" | "-F" => {
+                    iter.next();
+                }
+                positional if !positional.starts_with('-') => {
+                    flags.number = Some(positional.to_string());
+                }
+                _ => {}
+            }
+        }
+        flags
+    }
+
+    /// `--repo` is `group/project` on gitlab.com, or the full project URL
+    /// for any other instance.
+    fn project(&self) -> GlabCommandResult<(String, String)> {
+        let repo = self
+            .repo
+            .as_deref()
+            .ok_or_else(|| failed("missing --repo".to_string()))?;
+        let without_scheme = repo
+            .strip_prefix("https://")
+            .or_else(|| repo.strip_prefix("http://"));
+        match without_scheme {
+            Some(rest) => rest
+                .split_once('/')
+                .map(|(host, path)| (host.to_string(), path.trim_end_matches('/').to_string()))
+                .ok_or_else(|| failed(format!("malformed --repo `{repo}`"))),
+            None => Ok((DEFAULT_GITLAB_HOST.to_string(), repo.to_string())),
+        }
+    }
+}
+
+fn encode_project(path: &str) -> String {
+    path.replace('/', "%2F")
+}
+
+fn parse<T: serde::de::DeserializeOwned>(output: &str) -> GlabCommandResult<T> {
+    serde_json::from_str(output).map_err(|err| failed(err.to_string()))
+}
+
+fn failed(stderr: String) -> GlabCommandError {
+    GlabCommandError::Failed {
+        status: None,
+        stderr,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forge::gitlab::GitLabGlabBackend;
+    use crate::forge::http::stub::StubServer;
+    use crate::forge::traits::{
+        ForgeBackend, ForgeRepository, PullRequestListQuery, PullRequestListScope,
+        PullRequestTarget,
+    };
+
+    const MR_JSON: &str = r#"{
+      "iid": 7, "title": "Add HTTP", "author": {"username": "alice"},
+      "source_branch": "feat", "target_branch": "main", "web_url": "https://gitlab.com/g/p/-/merge_requests/7",
+      "state": "opened", "draft": false, "description": "",
+      "diff_refs": {"base_sha": "def", "head_sha": "abc", "start_sha": "def"}
+    }"#;
+
+    fn runner(server: &StubServer) -> GlabHttpRunner {
+        GlabHttpRunner::new(&ForgeConfig {
+            api_url: Some(server.base_url.clone()),
+            token: Some("cfg-token".to_string()),
+            ..ForgeConfig::default()
+        })
+    }
+
+    #[test]
+    fn should_fetch_a_merge_request_from_the_rest_api() {
+        // given
+        let server = StubServer::start(vec![(
+            "GET",
+            "/projects/group%2Fteam%2Fproject/merge_requests/7",
+            200,
+            MR_JSON,
+        )]);
+        let repo = ForgeRepository::gitlab("git.example.com", "group/team", "project");
+        let backend = GitLabGlabBackend::with_runner(Some(repo), runner(&server));
+        // when
+        let details = backend
+            .get_pull_request(PullRequestTarget::number(7, "7"))
+            .unwrap();
+        // then
+        assert_eq!(details.title, "Add HTTP");
+        assert!(
+            server.requests()[0]
+                .header("Authorization")
+                .is_some_and(|value| value.starts_with("Bearer "))
+        );
+    }
+
+    #[test]
+    fn should_resolve_the_current_user_for_the_reviewer_scope() {
+        // given
+        let server = StubServer::start(vec![
+            ("GET", "/user", 200, r#"{"username":"bob"}"#),
+            ("GET", "/projects/g%2Fp/merge_requests", 200, "[]"),
+        ]);
+        let repo = ForgeRepository::gitlab("gitlab.com", "g", "p");
+        let backend = GitLabGlabBackend::with_runner(Some(repo.clone()), runner(&server));
+        // when
+        let page = backend
+            .list_pull_requests(PullRequestListQuery::first_page_with_scope(
+                repo,
+                10,
+                PullRequestListScope::ReviewRequested,
+            ))
+            .unwrap();
+        // then
+        assert!(page.pull_requests.is_empty());
+        assert_eq!(
+            server.requests()[1].path,
+            "/projects/g%2Fp/merge_requests?state=opened&per_page=11&page=1&reviewer_username=bob"
+        );
+    }
+
+    #[test]
+    fn should_send_graphql_fields_as_variables() {
+        // given
+        let server = StubServer::start(vec![("POST", "/graphql", 200, r#"{"data":{}}"#)]);
+        let runner = runner(&server);
+        let args: Vec<String> = [
+            "api",
+            "graphql",
+            "-f",
+            "query=mutation { x }",
+            "-f",
+            "iid=7",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        // when
+        runner.run(&args).unwrap();
+        // then
+        let body: serde_json::Value = serde_json::from_str(&server.requests()[0].body).unwrap();
+        assert_eq!(body["query"], "mutation { x }");
+        assert_eq!(body["variables"]["iid"], "7");
+    }
+
+    #[test]
+    fn should_split_the_host_out_of_a_url_repo_arg() {
+        let flags = MrFlags::parse(&[
+            "mr".to_string(),
+            "view".to_string(),
+            "7".to_string(),
+            "--repo".to_string(),
+            "https://git.example.com/group/team/project".to_string(),
+        ]);
+        assert_eq!(
+            flags.project().unwrap(),
+            (
+                "git.example.com".to_string(),
+                "group/team/project".to_string()
+            )
+        );
+    }
+}
@@ -1,4 +1,5 @@
 pub mod glab;
+pub mod http;
 pub mod models;
 
 pub use glab::GitLabGlabBackend;
@@ -5,7 +5,8 @@ use std::path::PathBuf;
 use crate::error::{Result, TuicrError};
 use crate::forge::remote_comments::{RemoteCommentSide, RemoteReviewComment, RemoteReviewThread};
 use crate::forge::traits::{
-    ForgeRepository, PullRequestCommit, PullRequestDetails, PullRequestSummary,
+    ForgeRepository, PullRequestCheckStatus, PullRequestCommit, PullRequestDetails,
+    PullRequestSummary,
 };
 use crate::model::{FilePatch, FileStatus};
 use crate::vcs::git::raw::is_binary_patch;
@@ -175,6 +176,61 @@ pub struct GlabUser {
     pub name: String,
 }
 
+/// A user as returned by `GET users?username=`; only the id is needed to
+/// request a review.
+#[derive(Debug, Deserialize)]
+pub struct GlabUserRef {
+    pub id: u64,
+}
+
+/// The reviewers currently on a merge request.
+#[derive(Debug, Deserialize, Default)]
+pub struct GlabMrReviewers {
+    #[serde(default)]
+    pub reviewers: Vec<GlabUserRef>,
+}
+
+/// A merge request pipeline; only its id is needed to list its jobs.
+#[derive(Debug, Deserialize)]
+pub struct GlabPipeline {
+    pub id: u64,
+}
+
+/// One job of a pipeline, shown as a check in the PR info panel.
+#[derive(Debug, Deserialize)]
+pub struct GlabJob {
+    pub name: String,
+    pub status: String,
+    #[serde(default)]
+    pub web_url: Option<String>,
+    #[serde(default)]
+    pub allow_failure: bool,
+}
+
+impl GlabJob {
+    /// Map the job status onto the check conclusions GitHub reports, which
+    /// is what the panel and the check-log view understand. A failure the
+    /// pipeline allows doesn't count as failing.
+    pub fn into_check(self) -> PullRequestCheckStatus {
+        let conclusion = match self.status.as_str() {
+            "success" => "SUCCESS",
+            "failed" if self.allow_failure => "NEUTRAL",
+            "failed" => "FAILURE",
+            "canceled" => "CANCELLED",
+            "skipped" => "SKIPPED",
+            "running" => "IN_PROGRESS",
+            "manual" => "NEUTRAL",
+            _ => "PENDING",
+        };
+        PullRequestCheckStatus {
+            name: self.name,
+            status: None,
+            conclusion: Some(conclusion.to_string()),
+            url: self.web_url.filter(|url| !url.is_empty()),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct GlabMrVersion {
     #[serde(default)]
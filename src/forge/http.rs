@@ -0,0 +1,609 @@
+//! Direct HTTPS transport for the forges that otherwise shell out to a CLI.
+//!
+//! The GitHub, GitLab and Bitbucket Cloud backends build CLI argv (`gh`,
+//! `glab`, `bkt`) and hand it to a runner trait. With `[forge] transport =
+//! "http"` those argv go to an HTTP runner instead, which issues the same
+//! REST/GraphQL call the CLI would have made. The backends are unchanged, so
+//! both transports share one code path for parsing and error mapping.
+//!
+//! This module holds the pieces the three runners share: the agent, the
+//! parser for `<cli> api ...` argv, and token lookup.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::config::ForgeConfig;
+
+/// One outgoing request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+impl HttpRequest {
+    pub fn new(method: &str, url: impl Into<String>) -> Self {
+        Self {
+            method: method.to_ascii_uppercase(),
+            url: url.into(),
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    fn has_header(&self, name: &str) -> bool {
+        self.headers
+            .iter()
+            .any(|(key, _)| key.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Shared ureq agent. Non-2xx statuses come back as values so each runner
+/// can shape the error the way its backend's error mapping expects.
+pub struct HttpClient {
+    agent: ureq::Agent,
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HttpClient {
+    pub fn new() -> Self {
+        let config = ureq::Agent::config_builder()
+            .timeout_global(Some(Duration::from_secs(30)))
+            .http_status_as_error(false)
+            .build();
+        Self {
+            agent: config.into(),
+        }
+    }
+
+    /// Send `request`, returning the status and body. `Err` is reserved for
+    /// transport failures (DNS, TLS, connection refused).
+    pub fn send(&self, request: &HttpRequest) -> std::result::Result<(u16, String), String> {
+        let result = match request.method.as_str() {
+            verb @ ("GET" | "DELETE") => {
+                let mut builder = if verb == "GET" {
+                    self.agent.get(&request.url)
+                } else {
+                    self.agent.delete(&request.url)
+                };
+                for (name, value) in &request.headers {
+                    builder = builder.header(name.as_str(), value.as_str());
+                }
+                builder.call()
+            }
+            verb @ ("POST" | "PUT" | "PATCH") => {
+                let mut builder = match verb {
+                    "POST" => self.agent.post(&request.url),
+                    "PUT" => self.agent.put(&request.url),
+                    _ => self.agent.patch(&request.url),
+                };
+                if !request.has_header("Content-Type") {
+                    builder = builder.header("Content-Type", "application/json");
+                }
+                for (name, value) in &request.headers {
+                    builder = builder.header(name.as_str(), value.as_str());
+                }
+                builder.send(request.body.as_deref().unwrap_or(""))
+            }
+            other => return Err(format!("unsupported HTTP method {other}")),
+        };
+        let response = result.map_err(|err| err.to_string())?;
+        let status = response.status().as_u16();
+        let text = response
+            .into_body()
+            .read_to_string()
+            .map_err(|err| err.to_string())?;
+        Ok((status, text))
+    }
+}
+
+/// The flags of a `<cli> api ...` invocation, across `gh`, `glab` and `bkt`.
+///
+/// Only the flags the backends actually pass are recognized. `--jq` is
+/// accepted and dropped: callers use it to shrink a response they then
+/// parse with serde, which ignores the extra fields of the full one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApiArgs {
+    pub endpoint: String,
+    pub method: Option<String>,
+    pub hostname: Option<String>,
+    pub headers: Vec<(String, String)>,
+    /// `-f key=value`: always a string.
+    pub raw_fields: Vec<(String, String)>,
+    /// `-F key=value`: numbers, booleans and `null` keep their JSON type.
+    pub typed_fields: Vec<(String, String)>,
+    /// `bkt api -P key=value`: query-string parameters.
+    pub params: Vec<(String, String)>,
+    /// `--input`: `-` means the stdin payload, anything else is literal JSON.
+    pub input: Option<String>,
+}
+
+impl ApiArgs {
+    /// Parse `args` when they start with `api`; `None` for any other command.
+    pub fn parse(args: &[String]) -> Option<Self> {
+        let mut iter = args.iter();
+        if iter.next().map(String::as_str) != Some("api") {
+            return None;
+        }
+        let mut parsed = Self::default();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "-X" | "--method" => parsed.method = iter.next().map(|m| m.to_ascii_uppercase()),
+                "--hostname" => parsed.hostname = iter.next().cloned(),
+                "-H" | "--header" => {
+                    if let Some((name, value)) = iter.next().and_then(|h| h.split_once(':')) {
+                        parsed
+                            .headers
+                            .push((name.trim().to_string(), value.trim().to_string()));
+                    }
+                }
+                "-f" | "--raw-field" => parsed
+                    .raw_fields
+                    .extend(iter.next().and_then(|f| split_kv(f))),
+                "-F" | "--field" => parsed
+                    .typed_fields
+                    .extend(iter.next().and_then(|f| split_kv(f))),
+                "-P" => parsed.params.extend(iter.next().and_then(|f| split_kv(f))),
+                "--input" => parsed.input = iter.next().cloned(),
+                "--jq" | "-q" => {
+                    iter.next();
+                }
+                endpoint => parsed.endpoint = endpoint.to_string(),
+            }
+        }
+        Some(parsed)
+    }
+
+    pub fn is_graphql(&self) -> bool {
+        self.endpoint == "graphql"
+    }
+
+    /// The request body: the `--input` payload, or the fields as JSON. For
+    /// the `graphql` endpoint `query` is lifted out and the remaining
+    /// fields become `variables`, matching `gh api graphql`.
+    pub fn body(&self, stdin: &str) -> Option<String> {
+        if let Some(input) = &self.input {
+            return Some(if input == "-" {
+                stdin.to_string()
+            } else {
+                input.clone()
+            });
+        }
+        if self.raw_fields.is_empty() && self.typed_fields.is_empty() {
+            return None;
+        }
+        let mut fields = serde_json::Map::new();
+        for (key, value) in &self.raw_fields {
+            fields.insert(key.clone(), serde_json::Value::String(value.clone()));
+        }
+        for (key, value) in &self.typed_fields {
+            fields.insert(key.clone(), typed_field_value(value));
+        }
+        let body = if self.is_graphql() {
+            let query = fields
+                .remove("query")
+                .unwrap_or(serde_json::Value::String(String::new()));
+            serde_json::json!({ "query": query, "variables": fields })
+        } else {
+            serde_json::Value::Object(fields)
+        };
+        Some(body.to_string())
+    }
+
+    /// GET unless `--method` says otherwise or there is a body to send.
+    pub fn effective_method(&self, has_body: bool) -> String {
+        self.method.clone().unwrap_or_else(|| {
+            if has_body || self.is_graphql() {
+                "POST".to_string()
+            } else {
+                "GET".to_string()
+            }
+        })
+    }
+
+    /// `-P` parameters as a query string appended to `url`.
+    pub fn url_with_params(&self, url: String) -> String {
+        if self.params.is_empty() {
+            return url;
+        }
+        let query = self
+            .params
+            .iter()
+            .map(|(key, value)| format!("{}={}", encode_query(key), encode_query(value)))
+            .collect::<Vec<_>>()
+            .join("&");
+        let separator = if url.contains('?') { '&' } else { '?' };
+        format!("{url}{separator}{query}")
+    }
+}
+
+fn split_kv(field: &str) -> Option<(String, String)> {
+    field
+        .split_once('=')
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+}
+
+fn typed_field_value(value: &str) -> serde_json::Value {
+    match value {
+        "true" => serde_json::Value::Bool(true),
+        "false" => serde_json::Value::Bool(false),
+        "null" => serde_json::Value::Null,
+        _ => value
+            .parse::<i64>()
+            .map(serde_json::Value::from)
+            .unwrap_or_else(|_| serde_json::Value::String(value.to_string())),
+    }
+}
+
+/// Percent-encode a query-string key or value.
+pub fn encode_query(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
+
+/// Join an API base URL and an endpoint, which may or may not start with `/`.
+/// A full URL (a pagination `next` link) is returned unchanged.
+pub fn join_url(base: &str, endpoint: &str) -> String {
+    if endpoint.starts_with("http://") || endpoint.starts_with("https://") {
+        return endpoint.to_string();
+    }
+    format!(
+        "{}/{}",
+        base.trim_end_matches('/'),
+        endpoint.trim_start_matches('/')
+    )
+}
+
+/// The configured fallback tokens, looked up by the host a request goes to.
+///
+/// `[forge.tokens]` names the host each token belongs to. The bare `[forge]
+/// token` names none, so it is only sent when `api_url` pins every request to
+/// one endpoint; otherwise a GitHub token would also go out to GitLab.
+#[derive(Debug, Clone, Default)]
+pub struct ConfiguredTokens {
+    by_host: BTreeMap<String, String>,
+    pinned: Option<String>,
+}
+
+impl ConfiguredTokens {
+    pub fn new(config: &ForgeConfig) -> Self {
+        Self {
+            by_host: config.tokens.clone(),
+            pinned: config.api_url.as_ref().and(config.token.clone()),
+        }
+    }
+
+    pub fn for_host(&self, host: &str) -> Option<&str> {
+        self.by_host
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(host))
+            .map(|(_, token)| token.as_str())
+            .or(self.pinned.as_deref())
+    }
+}
+
+/// First non-empty value among the environment variables `names`, falling
+/// back to the configured token for the host.
+pub fn resolve_token(names: &[&str], configured: Option<&str>) -> Option<String> {
+    names
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .chain(configured.map(str::to_string))
+        .map(|value| value.trim().to_string())
+        .find(|value| !value.is_empty())
+}
+
+/// Render a non-2xx response as the `stderr` a runner reports.
+///
+/// 401 deliberately avoids the phrases the backends' `looks_like_auth_failure`
+/// checks match on: those would tell the user to run `gh auth login`, which
+/// does nothing for this transport. 403 keeps `HTTP 403` so the permission
+/// hints (missing scopes, not a reviewer) still fire.
+pub fn failure_message(status: u16, body: &str, host: &str, token_hint: &str) -> String {
+    if status == 401 {
+        format!("HTTP 401: {host} rejected the request; {token_hint}")
+    } else {
+        let body = body.trim();
+        let body = if body.len() > 500 {
+            let mut end = 500;
+            while !body.is_char_boundary(end) {
+                end -= 1;
+            }
+            &body[..end]
+        } else {
+            body
+        };
+        format!("HTTP {status}: {body}")
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod stub {
+    //! Minimal HTTP/1.1 server for exercising the runners end to end: each
+    //! route maps a method and path prefix to a canned response, and every
+    //! request is recorded.
+
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Clone)]
+    pub struct Recorded {
+        pub method: String,
+        pub path: String,
+        pub headers: Vec<(String, String)>,
+        pub body: String,
+    }
+
+    impl Recorded {
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        }
+    }
+
+    pub struct StubServer {
+        pub base_url: String,
+        requests: Arc<Mutex<Vec<Recorded>>>,
+    }
+
+    impl StubServer {
+        /// Serve `routes` of `(method, path prefix, status, body)`. The first
+        /// matching route wins; anything else gets a 404.
+        pub fn start(routes: Vec<(&str, &str, u16, &str)>) -> Self {
+            let routes: Vec<(String, String, u16, String)> = routes
+                .into_iter()
+                .map(|(m, p, s, b)| (m.to_string(), p.to_string(), s, b.to_string()))
+                .collect();
+            let listener = TcpListener::bind("127.0.0.1:0").expect("bind stub server");
+            let base_url = format!("http://{}", listener.local_addr().unwrap());
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let recorded = Arc::clone(&requests);
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let Ok(mut stream) = stream else { continue };
+                    let Some(request) = read_request(&mut stream) else {
+                        continue;
+                    };
+                    let (status, body) = routes
+                        .iter()
+                        .find(|(method, prefix, _, _)| {
+                            *method == request.method && request.path.starts_with(prefix.as_str())
+                        })
+                        .map(|(_, _, status, body)| (*status, body.clone()))
+                        .unwrap_or((404, "{\"message\":\"Not Found\"}".to_string()));
+                    recorded.lock().unwrap().push(request);
+                    let response = format!(
+                        "HTTP/1.1 {status} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    );
+                    let _ = stream.write_all(response.as_bytes());
+                }
+            });
+            Self { base_url, requests }
+        }
+
+        pub fn requests(&self) -> Vec<Recorded> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    fn read_request(stream: &mut std::net::TcpStream) -> Option<Recorded> {
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let mut parts = line.split_whitespace();
+        let method = parts.next()?.to_string();
+        let path = parts.next()?.to_string();
+        let mut headers = Vec::new();
+        let mut content_length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).ok()?;
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                let (name, value) = (name.trim().to_string(), value.trim().to_string());
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.parse().unwrap_or(0);
+                }
+                headers.push((name, value));
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).ok()?;
+        Some(Recorded {
+            method,
+            path,
+            headers,
+            body: String::from_utf8_lossy(&body).into_owned(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn should_ignore_non_api_commands() {
+        assert!(ApiArgs::parse(&args(&["pr", "view", "1"])).is_none());
+    }
+
+    #[test]
+    fn should_parse_gh_style_api_flags() {
+        // given
+        let parsed = ApiArgs::parse(&args(&[
+            "api",
+            "-H",
+            "Accept: application/vnd.github.raw",
+            "--hostname",
+            "ghe.example.com",
+            "repos/o/r/contents/a.rs?ref=abc",
+        ]))
+        .unwrap();
+        // then
+        assert_eq!(parsed.endpoint, "repos/o/r/contents/a.rs?ref=abc");
+        assert_eq!(parsed.hostname.as_deref(), Some("ghe.example.com"));
+        assert_eq!(
+            parsed.headers,
+            vec![(
+                "Accept".to_string(),
+                "application/vnd.github.raw".to_string()
+            )]
+        );
+        assert_eq!(parsed.effective_method(false), "GET");
+    }
+
+    #[test]
+    fn should_build_a_graphql_body_with_typed_variables() {
+        // given
+        let parsed = ApiArgs::parse(&args(&[
+            "api",
+            "graphql",
+            "-f",
+            "query=query { viewer { login } }",
+            "-F",
+            "number=125",
+            "-F",
+            "owner=agavra",
+            "-f",
+            "iid=7",
+        ]))
+        .unwrap();
+        // when
+        let body: serde_json::Value = serde_json::from_str(&parsed.body("").unwrap()).unwrap();
+        // then — `-F` keeps numbers numeric, `-f` keeps them strings
+        assert_eq!(body["query"], "query { viewer { login } }");
+        assert_eq!(body["variables"]["number"], 125);
+        assert_eq!(body["variables"]["owner"], "agavra");
+        assert_eq!(body["variables"]["iid"], "7");
+        assert_eq!(parsed.effective_method(true), "POST");
+    }
+
+    #[test]
+    fn should_read_stdin_or_literal_input() {
+        let stdin = ApiArgs::parse(&args(&["api", "x", "--input", "-"])).unwrap();
+        assert_eq!(stdin.body("{\"a\":1}").as_deref(), Some("{\"a\":1}"));
+        let literal = ApiArgs::parse(&args(&["api", "x", "--input", "{\"b\":2}"])).unwrap();
+        assert_eq!(literal.body("ignored").as_deref(), Some("{\"b\":2}"));
+    }
+
+    #[test]
+    fn should_encode_bkt_params_into_the_query_string() {
+        // given
+        let parsed = ApiArgs::parse(&args(&[
+            "api",
+            "/2.0/repositories/ws/r/pullrequests",
+            "-P",
+            "q=state=\"OPEN\"",
+            "-P",
+            "pagelen=50",
+        ]))
+        .unwrap();
+        // when
+        let url = parsed.url_with_params(join_url("https://api.bitbucket.org", &parsed.endpoint));
+        // then
+        assert_eq!(
+            url,
+            "https://api.bitbucket.org/2.0/repositories/ws/r/pullrequests?q=state%3D%22OPEN%22&pagelen=50"
+        );
+    }
+
+    #[test]
+    fn should_keep_absolute_urls_when_joining() {
+        assert_eq!(
+            join_url(
+                "https://api.bitbucket.org",
+                "https://api.bitbucket.org/2.0/x?page=2"
+            ),
+            "https://api.bitbucket.org/2.0/x?page=2"
+        );
+        assert_eq!(
+            join_url("http://h/api/", "/v4/user"),
+            "http://h/api/v4/user"
+        );
+    }
+
+    #[test]
+    fn should_only_hand_a_configured_token_to_its_host() {
+        let scoped = ConfiguredTokens::new(&ForgeConfig {
+            token: Some("shared".to_string()),
+            tokens: BTreeMap::from([("github.com".to_string(), "gh".to_string())]),
+            ..ForgeConfig::default()
+        });
+        assert_eq!(scoped.for_host("GitHub.com"), Some("gh"));
+        assert_eq!(scoped.for_host("gitlab.com"), None);
+
+        let pinned = ConfiguredTokens::new(&ForgeConfig {
+            token: Some("shared".to_string()),
+            api_url: Some("http://127.0.0.1:8080".to_string()),
+            ..ForgeConfig::default()
+        });
+        assert_eq!(pinned.for_host("gitlab.com"), Some("shared"));
+    }
+
+    #[test]
+    fn should_not_suggest_cli_login_for_a_rejected_token() {
+        // The backends' auth heuristics would otherwise point at `gh auth
+        // login`, which does nothing for the HTTP transport.
+        let message = failure_message(401, "Requires authentication", "github.com", "set GH_TOKEN");
+        let lower = message.to_ascii_lowercase();
+        assert!(!lower.contains("requires authentication"));
+        assert!(!lower.contains("401 unauthorized"));
+        assert!(message.contains("set GH_TOKEN"));
+        assert!(failure_message(403, "nope", "h", "x").contains("HTTP 403"));
+    }
+
+    #[test]
+    fn should_send_requests_to_the_stub_server() {
+        // given
+        let server = stub::StubServer::start(vec![("POST", "/echo", 201, "{\"ok\":true}")]);
+        let client = HttpClient::new();
+        let request = HttpRequest::new("post", format!("{}/echo", server.base_url))
+            .header("Authorization", "Bearer t")
+            .body("{}");
+        // when
+        let (status, body) = client.send(&request).unwrap();
+        // then
+        assert_eq!(status, 201);
+        assert_eq!(body, "{\"ok\":true}");
+        let recorded = &server.requests()[0];
+        assert_eq!(recorded.header("Authorization"), Some("Bearer t"));
+        assert_eq!(recorded.header("Content-Type"), Some("application/json"));
+        assert_eq!(recorded.body, "{}");
+    }
+}
@@ -7,13 +7,19 @@
 
 pub mod azure;
 pub mod bitbucket;
+pub mod bitbucket_dc;
 pub mod canonical;
+pub mod check_log;
 pub mod context;
+pub mod gerrit;
+pub mod gitea;
 pub mod github;
 pub mod gitlab;
+pub mod http;
 pub mod pr_open;
 pub mod remote_comments;
 pub mod selector;
+pub mod self_hosted;
 pub mod submit;
 pub mod traits;
 
@@ -23,6 +29,9 @@ use git2::Repository;
 
 use crate::forge::azure::az::parse_azure_remote_url;
 use crate::forge::bitbucket::bkt::parse_bitbucket_remote_url;
+use crate::forge::bitbucket_dc::api::parse_bitbucket_dc_remote_url;
+use crate::forge::gerrit::api::parse_gerrit_remote_url;
+use crate::forge::gitea::api::parse_gitea_remote_url;
 use crate::forge::github::gh::parse_github_remote_url;
 use crate::forge::gitlab::glab::parse_gitlab_remote_url;
 use crate::forge::traits::ForgeRepository;
@@ -110,16 +119,23 @@ pub fn detect_azure_repository(repo_root: &Path) -> Option<ForgeRepository> {
 
 /// Parse `url` as a forge remote repository.
 ///
-/// Order matters. Bitbucket and GitLab both gate on the hostname, so trying
-/// them first won't claim GitHub Enterprise remotes. Azure next — its parser
-/// filters to `dev.azure.com` / `*.visualstudio.com` hosts. GitHub must stay
-/// last because its parser accepts *any* host (covers github.com and GHE hosts
-/// whose hostname does not literally contain "github") — it would otherwise
-/// swallow every Bitbucket, self-hosted GitLab, and Azure remote.
+/// Order matters. Bitbucket Cloud, Bitbucket Data Center, and GitLab gate on
+/// the hostname (Data Center also on its `/scm/` clone path and SSH port), so
+/// trying them first won't claim GitHub Enterprise remotes. Azure next — its parser
+/// filters to `dev.azure.com` / `*.visualstudio.com` hosts — then Gitea, which
+/// only accepts known, Gitea-named, or `tea`-configured hosts, and Gerrit,
+/// which only accepts known or Gerrit-named hosts and its SSH port. GitHub
+/// must stay last because its parser accepts *any* host (covers github.com
+/// and GHE hosts whose hostname does not literally contain "github") — it
+/// would otherwise swallow every Bitbucket, self-hosted GitLab, Azure, Gitea,
+/// and Gerrit remote.
 pub fn parse_any_remote_url(url: &str) -> Option<ForgeRepository> {
     parse_bitbucket_remote_url(url)
+        .or_else(|| parse_bitbucket_dc_remote_url(url))
         .or_else(|| parse_gitlab_remote_url(url))
         .or_else(|| parse_azure_remote_url(url))
+        .or_else(|| parse_gitea_remote_url(url))
+        .or_else(|| parse_gerrit_remote_url(url))
         .or_else(|| parse_github_remote_url(url))
 }
 
@@ -160,6 +176,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn detects_gitea_repository_before_github_fallback() {
+        let dir = init_repo_with_origin("git@codeberg.org:forgejo/forgejo.git");
+        assert_eq!(
+            detect_forge_repository(dir.path()),
+            Some(ForgeRepository::gitea("codeberg.org", "forgejo", "forgejo"))
+        );
+    }
+
+    #[test]
+    fn detects_bitbucket_data_center_repository_from_scm_remote() {
+        let dir = init_repo_with_origin("https://git.corp.example/scm/plat/build.git");
+        assert_eq!(
+            detect_forge_repository(dir.path()),
+            Some(ForgeRepository::bitbucket_data_center(
+                "git.corp.example",
+                "PLAT",
+                "build"
+            ))
+        );
+    }
+
+    #[test]
+    fn detects_gerrit_repository_on_ssh_port_before_github_fallback() {
+        let dir = init_repo_with_origin("ssh://alice@review.example.com:29418/platform/build");
+        assert_eq!(
+            detect_forge_repository(dir.path()),
+            Some(ForgeRepository::gerrit(
+                "review.example.com",
+                "platform/build"
+            ))
+        );
+    }
+
     #[test]
     fn local_checkout_matches_when_origin_equals_target() {
         let dir = init_repo_with_origin("https://github.com/agavra/tuicr");
@@ -1,13 +1,18 @@
 //! Remote review comment/thread models.
 //!
-//! These types carry existing GitHub review discussions into the App for
-//! read-only display, filtering, and export. They are deliberately
-//! source-of-truth-on-remote: we never mutate, reply to, or persist them
-//! locally past the in-memory cache.
+//! These types carry existing forge review discussions into the App for
+//! display, filtering, and export. They are deliberately
+//! source-of-truth-on-remote: we never persist them locally past the
+//! in-memory cache. Replies are queued as local drafts on the session
+//! (`ReviewSession::thread_replies`) and resolve/unresolve goes straight to
+//! the forge; either way the threads themselves are refetched afterwards
+//! rather than patched in place.
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::model::comment::Comment;
+
 /// Which side of the diff a remote comment anchors to.
 ///
 /// Mirrors GitHub's submission model: `RIGHT` is the head side (added/context
@@ -138,6 +143,53 @@ impl RemoteReviewThread {
     }
 }
 
+/// The viewer's unpublished review on a PR. Only its author can see a
+/// pending review, so at most one exists per viewer and PR.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingReview {
+    /// Forge-assigned review node ID, used by the publish/discard calls.
+    pub id: String,
+    /// Numeric review ID, stamped on comments as `remote_review_id`.
+    pub database_id: u64,
+    /// Review body written so far. Publishing appends to it.
+    pub body: String,
+    pub comments: Vec<PendingReviewComment>,
+}
+
+/// One inline comment of a [`PendingReview`]. Unlike published comments
+/// these carry their own anchor, since tuicr imports them as local
+/// comments rather than rendering them as threads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingReviewComment {
+    /// Forge-assigned comment node ID; matches the root comment id of the
+    /// thread the same comment shows up in.
+    pub id: String,
+    pub path: String,
+    /// Anchor line on `side`. `None` for file-level comments.
+    pub line: Option<u32>,
+    /// First line of a multi-line comment.
+    pub start_line: Option<u32>,
+    pub side: RemoteCommentSide,
+    pub body: String,
+}
+
+/// Drop the threads rooted at a comment of `pending`. Those comments are
+/// imported as editable local comments, so rendering their threads too
+/// would show every pending comment twice.
+pub fn without_pending_threads(
+    threads: Vec<RemoteReviewThread>,
+    pending: &PendingReview,
+) -> Vec<RemoteReviewThread> {
+    threads
+        .into_iter()
+        .filter(|thread| {
+            thread
+                .root()
+                .is_none_or(|root| !pending.comments.iter().any(|c| c.id == root.id))
+        })
+        .collect()
+}
+
 /// User-controlled visibility for remote review comments in PR mode.
 ///
 /// Persisted per-session so visibility survives reopen. Default is
@@ -205,13 +257,18 @@ pub fn filter_threads(
 /// - 1 header line for the root comment (`╭─ [github @author] L42 ──`)
 /// - 1 separator line per reply (`├─ ↳ @author ──`)
 /// - 1 body line per `\n`-split line in each comment's body
+/// - 1 separator + 1 body line per `\n`-split line for each queued local
+///   reply in `replies` (`├─ ↳ reply draft ──`)
 /// - 1 footer line at the end of the thread (`╰────`)
-pub fn thread_display_lines(thread: &RemoteReviewThread) -> usize {
+pub fn thread_display_lines(thread: &RemoteReviewThread, replies: &[Comment]) -> usize {
     let mut total = 0;
     for comment in &thread.comments {
         // header (root) or separator (reply) + body lines
         total += 1 + comment.body.split('\n').count();
     }
+    for reply in replies {
+        total += 1 + reply.content.split('\n').count();
+    }
     // single closing rule for the whole thread
     total += 1;
     total
@@ -402,4 +459,31 @@ mod tests {
         // unknown defaults to RIGHT (head side) — safer for display
         assert_eq!(RemoteCommentSide::parse(""), RemoteCommentSide::Right);
     }
+
+    #[test]
+    fn should_drop_threads_rooted_at_a_pending_comment() {
+        // given
+        let threads = vec![
+            make_thread("a", "src/lib.rs", Some(10), false, false),
+            make_thread("b", "src/lib.rs", Some(20), false, false),
+        ];
+        let pending = PendingReview {
+            id: "PRR_1".to_string(),
+            database_id: 1,
+            body: String::new(),
+            comments: vec![PendingReviewComment {
+                id: "b-root".to_string(),
+                path: "src/lib.rs".to_string(),
+                line: Some(20),
+                start_line: None,
+                side: RemoteCommentSide::Right,
+                body: "Root body".to_string(),
+            }],
+        };
+        // when
+        let kept = without_pending_threads(threads, &pending);
+        // then
+        let ids: Vec<&str> = kept.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["a"]);
+    }
 }
@@ -0,0 +1,372 @@
+//! Pieces shared by the backends that call a self-hosted forge's REST API
+//! directly (Gitea / Forgejo, Bitbucket Data Center, Gerrit).
+//!
+//! Those instances live on arbitrary hosts, often on a custom port or under a
+//! context path (`https://git.corp:8443/bitbucket`), and a pasted PR URL can
+//! name any host at all. So this module keeps the web root a URL was served
+//! from, and binds configured credentials to the one host they were
+//! configured for: a URL on any other host goes out anonymously.
+
+use std::ffi::OsStr;
+use std::path::Path;
+
+use crate::config::ForgeConfig;
+use crate::model::FilePatch;
+use crate::process::run_command_output;
+use crate::vcs::git::raw::run_git_diff;
+
+// ---------- URLs ----------
+
+/// `user@host:path`, the scp-like shorthand git accepts for SSH remotes.
+pub(crate) fn parse_scp_like_remote(remote_url: &str) -> Option<(&str, &str)> {
+    if remote_url.contains("://") {
+        return None;
+    }
+    let (host_part, path) = remote_url.split_once(':')?;
+    if host_part.contains('/') || path.is_empty() {
+        return None;
+    }
+    let host = host_part
+        .rsplit_once('@')
+        .map(|(_, host)| host)
+        .unwrap_or(host_part);
+    Some((host, path))
+}
+
+pub(crate) fn strip_scheme(value: &str) -> Option<&str> {
+    value
+        .strip_prefix("https://")
+        .or_else(|| value.strip_prefix("http://"))
+        .or_else(|| value.strip_prefix("ssh://"))
+}
+
+pub(crate) fn trim_url_suffix(value: &str) -> &str {
+    value
+        .split(['?', '#'])
+        .next()
+        .unwrap_or(value)
+        .trim_end_matches('/')
+}
+
+pub(crate) fn strip_port(host: &str) -> &str {
+    match host.rsplit_once(':') {
+        Some((h, port)) if !port.is_empty() && port.bytes().all(|b| b.is_ascii_digit()) => h,
+        _ => host,
+    }
+}
+
+pub(crate) fn strip_git_suffix(value: &str) -> &str {
+    value.strip_suffix(".git").unwrap_or(value)
+}
+
+/// A `scheme://[user@]host[:port][/path]` URL split at the host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct UrlParts<'a> {
+    pub scheme: &'a str,
+    /// `host[:port]`, without any user info.
+    pub authority: &'a str,
+    /// Everything after the authority, without the leading `/`.
+    pub path: &'a str,
+}
+
+impl<'a> UrlParts<'a> {
+    pub fn host(&self) -> &'a str {
+        strip_port(self.authority)
+    }
+
+    pub fn port(&self) -> Option<&'a str> {
+        let host = self.host();
+        (host.len() < self.authority.len()).then(|| &self.authority[host.len() + 1..])
+    }
+
+    /// Base URL of a web UI served under `prefix` (the path segments before
+    /// the forge's own routes). `None` when that is the default
+    /// `https://<host>`, or when the URL is SSH, which says nothing about
+    /// where the web UI lives.
+    pub fn web_root(&self, prefix: &str) -> Option<String> {
+        let prefix = prefix.trim_matches('/');
+        let default_port = match self.scheme {
+            "https" => "443",
+            "http" => "80",
+            _ => return None,
+        };
+        let port = self.port().filter(|port| *port != default_port);
+        if self.scheme == "https" && port.is_none() && prefix.is_empty() {
+            return None;
+        }
+        let mut root = format!("{}://{}", self.scheme, self.host().to_ascii_lowercase());
+        if let Some(port) = port {
+            root.push(':');
+            root.push_str(port);
+        }
+        if !prefix.is_empty() {
+            root.push('/');
+            root.push_str(prefix);
+        }
+        Some(root)
+    }
+}
+
+/// Split an `https://`, `http://` or `ssh://` URL. Query and fragment are
+/// dropped, as is a trailing `/`.
+pub(crate) fn split_url(url: &str) -> Option<UrlParts<'_>> {
+    let (scheme, rest) = trim_url_suffix(url.trim()).split_once("://")?;
+    if !matches!(scheme, "https" | "http" | "ssh") {
+        return None;
+    }
+    let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+    let authority = authority
+        .rsplit_once('@')
+        .map(|(_, host)| host)
+        .unwrap_or(authority);
+    if strip_port(authority).is_empty() {
+        return None;
+    }
+    Some(UrlParts {
+        scheme,
+        authority,
+        path,
+    })
+}
+
+// ---------- Credentials ----------
+
+/// Whether `host` is listed in the comma-separated host list `env_var`.
+pub(crate) fn host_listed(env_var: &str, host: &str) -> bool {
+    std::env::var(env_var).is_ok_and(|hosts| hosts_list_contains(&hosts, host))
+}
+
+fn hosts_list_contains(hosts: &str, host: &str) -> bool {
+    hosts
+        .split(',')
+        .any(|listed| listed.trim().eq_ignore_ascii_case(host))
+}
+
+/// The `[forge.tokens]` entry for `host`.
+pub(crate) fn configured_token<'a>(forge: &'a ForgeConfig, host: &str) -> Option<&'a str> {
+    forge
+        .tokens
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(host))
+        .map(|(_, token)| token.as_str())
+}
+
+/// The first non-empty value among `env_vars`, but only when `host` is
+/// listed in `hosts_env_var`. The variables name no host, so sending them
+/// anywhere else would hand them to whichever host a pasted URL names.
+pub(crate) fn env_token_for_listed_host(
+    env: &dyn Fn(&str) -> Option<String>,
+    env_vars: &[&str],
+    hosts_env_var: &str,
+    host: &str,
+) -> Option<String> {
+    if !env(hosts_env_var).is_some_and(|hosts| hosts_list_contains(&hosts, host)) {
+        return None;
+    }
+    env_vars
+        .iter()
+        .filter_map(|name| env(name))
+        .map(|value| value.trim().to_string())
+        .find(|value| !value.is_empty())
+}
+
+/// Process environment lookup for [`env_token_for_listed_host`].
+pub(crate) fn process_env(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+/// An `Authorization` header value bound to the host it was configured for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct HostAuth {
+    host: String,
+    header: String,
+}
+
+impl HostAuth {
+    pub fn new(host: &str, header: String) -> Self {
+        Self {
+            host: host.to_ascii_lowercase(),
+            header,
+        }
+    }
+
+    /// The header for a request to `url`; `None` when `url` is on another
+    /// host.
+    pub fn header_for(&self, url: &str) -> Option<&str> {
+        split_url(url)
+            .is_some_and(|parts| parts.host().eq_ignore_ascii_case(&self.host))
+            .then_some(self.header.as_str())
+    }
+
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+}
+
+// ---------- Local git helpers (mirror gh/glab) ----------
+
+/// Read a git blob from a checkout via `git show <sha>:<path>`. `None` on any
+/// failure so callers fall back to the REST API.
+pub(crate) fn read_blob_with_repo(repo_root: &Path, sha: &str, path: &Path) -> Option<String> {
+    let spec = format!("{}:{}", sha, path.to_string_lossy());
+    let exists = run_command_output(
+        "git",
+        Some(repo_root),
+        ["cat-file", "-e", spec.as_str()]
+            .iter()
+            .map(|s| OsStr::new(*s)),
+    );
+    if exists.is_err() {
+        return None;
+    }
+    run_command_output(
+        "git",
+        Some(repo_root),
+        ["show", spec.as_str()].iter().map(|s| OsStr::new(*s)),
+    )
+    .ok()
+}
+
+/// Return `Some(diff)` when both SHAs exist locally, via `git diff <start>..<end>`.
+pub(crate) fn local_range_diff(
+    repo_root: &Path,
+    start_sha: &str,
+    end_sha: &str,
+) -> Option<Vec<FilePatch>> {
+    for sha in [start_sha, end_sha] {
+        let exists = run_command_output(
+            "git",
+            Some(repo_root),
+            ["cat-file", "-e", sha].iter().map(|s| OsStr::new(*s)),
+        );
+        if exists.is_err() {
+            return None;
+        }
+    }
+    let range = format!("{start_sha}..{end_sha}");
+    run_git_diff(repo_root, &[range.as_str()]).ok()
+}
+
+#[cfg(test)]
+pub(crate) mod fixture {
+    use std::sync::{Arc, Mutex};
+
+    /// `(method, url, body)` of one request.
+    pub(crate) type Call = (String, String, Option<String>);
+
+    /// Stand-in for a forge server: answers each request from a route table
+    /// keyed by method and path prefix (first match wins) and records every
+    /// call. Each backend's tests implement its transport trait on top of
+    /// [`FixtureServer::respond`].
+    #[derive(Clone, Default)]
+    pub(crate) struct FixtureServer {
+        routes: Arc<Vec<(String, String, String)>>,
+        calls: Arc<Mutex<Vec<Call>>>,
+    }
+
+    impl FixtureServer {
+        pub(crate) fn new(routes: &[(&str, &str, &str)]) -> Self {
+            Self {
+                routes: Arc::new(
+                    routes
+                        .iter()
+                        .map(|(m, p, b)| (m.to_string(), p.to_string(), b.to_string()))
+                        .collect(),
+                ),
+                calls: Arc::default(),
+            }
+        }
+
+        pub(crate) fn calls(&self) -> Vec<Call> {
+            self.calls.lock().unwrap().clone()
+        }
+
+        /// Record the call and return the routed body, or the path (URL
+        /// without its scheme and authority) that no route matched.
+        pub(crate) fn respond(
+            &self,
+            method: &str,
+            url: &str,
+            body: Option<&str>,
+        ) -> Result<String, String> {
+            self.calls.lock().unwrap().push((
+                method.to_string(),
+                url.to_string(),
+                body.map(str::to_string),
+            ));
+            let path = url
+                .split_once("://")
+                .and_then(|(_, rest)| rest.find('/').map(|slash| &rest[slash..]))
+                .unwrap_or(url);
+            self.routes
+                .iter()
+                .find(|(m, prefix, _)| m == method && path.starts_with(prefix.as_str()))
+                .map(|(_, _, body)| body.clone())
+                .ok_or_else(|| path.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    #[test]
+    fn keeps_port_and_context_path_in_the_web_root() {
+        let parts = split_url("http://alice@Git.Corp.example:7990/bitbucket/projects/P").unwrap();
+        assert_eq!(parts.host(), "Git.Corp.example");
+        assert_eq!(parts.port(), Some("7990"));
+        assert_eq!(
+            parts.web_root("bitbucket").as_deref(),
+            Some("http://git.corp.example:7990/bitbucket")
+        );
+        let default = split_url("https://review.example.com:443/c/p/+/1").unwrap();
+        assert_eq!(default.web_root(""), None);
+        let ssh = split_url("ssh://git@review.example.com:29418/p").unwrap();
+        assert_eq!(ssh.web_root(""), None);
+    }
+
+    #[test]
+    fn only_attaches_auth_to_its_own_host() {
+        let auth = HostAuth::new("Review.Example.com", "Basic abc".to_string());
+        assert_eq!(
+            auth.header_for("https://review.example.com:8443/r/a/changes/1"),
+            Some("Basic abc")
+        );
+        assert_eq!(auth.header_for("https://evil.example/c/x/+/1"), None);
+        assert_eq!(
+            auth.header_for("https://review.example.com.evil.example/a/changes/1"),
+            None
+        );
+    }
+
+    #[test]
+    fn only_reads_env_tokens_for_listed_hosts() {
+        let env = |name: &str| match name {
+            "FORGE_HOSTS" => Some("git.corp.example, other.example".to_string()),
+            "FORGE_TOKEN" => Some(" secret ".to_string()),
+            _ => None,
+        };
+        assert_eq!(
+            env_token_for_listed_host(&env, &["FORGE_TOKEN"], "FORGE_HOSTS", "GIT.corp.example")
+                .as_deref(),
+            Some("secret")
+        );
+        assert_eq!(
+            env_token_for_listed_host(&env, &["FORGE_TOKEN"], "FORGE_HOSTS", "evil.example"),
+            None
+        );
+    }
+
+    #[test]
+    fn looks_up_configured_tokens_by_host() {
+        let forge = ForgeConfig {
+            tokens: BTreeMap::from([("git.corp.example".to_string(), "t".to_string())]),
+            ..ForgeConfig::default()
+        };
+        assert_eq!(configured_token(&forge, "Git.Corp.Example"), Some("t"));
+        assert_eq!(configured_token(&forge, "evil.example"), None);
+    }
+}
@@ -8,10 +8,13 @@
 //! PR 5 wires the local preflight, resolver, and final-confirmation modal
 //! against these types. The actual `gh api` call is deferred to PR 6.
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use crate::config::ForgeConfig;
-use crate::model::comment::Comment;
+use crate::forge::remote_comments::RemoteReviewThread;
+use crate::forge::traits::ForgeKind;
+use crate::model::comment::{Comment, CommentType, is_suggestion_fence};
 use crate::model::{DiffFile, FileStatus, LineOrigin, LineRange, LineSide};
 
 /// Which forge review event a `:submit*` command corresponds to.
@@ -148,6 +151,9 @@ pub enum UnmappableReason {
     /// for line comments authored through tuicr today, but we keep the
     /// variant so the resolver can surface a clear message if it ever does.
     LineNotInDiff,
+    /// Draft carried forward from an older PR head whose line could not be
+    /// found on the current head.
+    Outdated,
 }
 
 impl UnmappableReason {
@@ -158,6 +164,7 @@ impl UnmappableReason {
             UnmappableReason::BinaryFile => "binary file",
             UnmappableReason::TooLargeFile => "file too large",
             UnmappableReason::LineNotInDiff => "line not in current diff",
+            UnmappableReason::Outdated => "outdated by new commits",
         }
     }
 }
@@ -173,9 +180,44 @@ pub enum MappedComment {
     },
 }
 
+/// A queued reply to an existing remote review thread, ready to post. The
+/// thread is carried whole because some forges address a reply by more than
+/// the thread id (Azure DevOps parents it to the root comment).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThreadReply {
+    pub thread: RemoteReviewThread,
+    pub body: String,
+    /// Source `Comment.id`, used to lock the draft once it is posted.
+    pub comment_id: String,
+}
+
+/// Pair every local-draft reply in `replies` with its remote thread, in
+/// thread display order. Replies whose thread is no longer in `threads`
+/// (deleted on the forge, or not fetched yet) stay queued and are skipped.
+pub fn collect_thread_replies(
+    replies: &HashMap<String, Vec<Comment>>,
+    threads: &[RemoteReviewThread],
+    config: &ForgeConfig,
+) -> Vec<ThreadReply> {
+    threads
+        .iter()
+        .filter_map(|thread| Some((thread, replies.get(&thread.id)?)))
+        .flat_map(|(thread, drafts)| {
+            drafts
+                .iter()
+                .filter(|comment| !comment.is_locked())
+                .map(|comment| ThreadReply {
+                    thread: thread.clone(),
+                    body: build_inline_body(comment, false, config),
+                    comment_id: comment.id.clone(),
+                })
+        })
+        .collect()
+}
+
 /// Compute the inline body for `comment` honoring the `[TYPE]` prefix toggle.
 /// File-level bodies are prefixed `[TYPE] File-level:`.
-fn build_inline_body(comment: &Comment, file_level: bool, config: &ForgeConfig) -> String {
+pub fn build_inline_body(comment: &Comment, file_level: bool, config: &ForgeConfig) -> String {
     if !config.comment_type_prefix {
         return comment.content.clone();
     }
@@ -191,9 +233,59 @@ fn build_inline_body(comment: &Comment, file_level: bool, config: &ForgeConfig)
     } else {
         type_tag
     };
+    // A fence only parses at the start of a line, so a body that opens
+    // with one (a suggested change) keeps it on its own line.
+    if !prefix.is_empty() && comment.content.starts_with("```") {
+        return format!("{}\n{body}", prefix.trim_end(), body = comment.content);
+    }
     format!("{prefix}{body}", body = comment.content)
 }
 
+/// A comment body read back from the forge, split into what
+/// [`build_inline_body`] combined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedInlineBody {
+    pub comment_type: CommentType,
+    pub content: String,
+    /// The body carried the `File-level:` marker.
+    pub file_level: bool,
+}
+
+/// Inverse of [`build_inline_body`] for comments fetched from a pending
+/// review. A leading `[TYPE]` tag is only stripped when it names one of
+/// `type_ids`, so a body that merely opens with brackets keeps them.
+pub fn parse_inline_body(body: &str, type_ids: &[&str], config: &ForgeConfig) -> ParsedInlineBody {
+    let mut parsed = ParsedInlineBody {
+        comment_type: CommentType::None,
+        content: body.to_string(),
+        file_level: false,
+    };
+    if !config.comment_type_prefix {
+        return parsed;
+    }
+    let mut rest = body;
+    if let Some(tagged) = rest.strip_prefix('[')
+        && let Some((tag, after)) = tagged.split_once(']')
+        && let Some(id) = type_ids.iter().find(|id| id.eq_ignore_ascii_case(tag))
+    {
+        parsed.comment_type = CommentType::from_id(id);
+        rest = strip_prefix_separator(after);
+    }
+    if let Some(after) = rest.strip_prefix("File-level:") {
+        parsed.file_level = true;
+        rest = strip_prefix_separator(after);
+    }
+    parsed.content = rest.to_string();
+    parsed
+}
+
+/// Drop the space (or, before a fence, the newline) that follows a prefix.
+fn strip_prefix_separator(text: &str) -> &str {
+    text.strip_prefix(' ')
+        .or_else(|| text.strip_prefix('\n'))
+        .unwrap_or(text)
+}
+
 /// Where a local comment is anchored. The caller knows this from how it
 /// walked the session (`file_comments` vs `line_comments[key]`); supplying
 /// it explicitly avoids inferring file-level-ness from missing fields on
@@ -213,14 +305,59 @@ pub enum CommentAnchor {
 /// `Unmappable` outcome. `file` must be the diff file that produced this
 /// comment (lookup is the caller's responsibility — it owns the current
 /// `diff_files` / `range_diff_files` slice).
+///
+/// Suggested-change fences pass through untouched for GitHub; for GitLab
+/// they are rewritten to `suggestion:-N+0` so the suggestion covers the
+/// whole commented range rather than only its last line.
 pub fn map_comment(
     comment: &Comment,
     anchor: CommentAnchor,
     file: &DiffFile,
     config: &ForgeConfig,
+    forge: ForgeKind,
+) -> MappedComment {
+    let mut mapped = map_comment_anchor(comment, anchor, file, config);
+    if forge == ForgeKind::GitLab
+        && let MappedComment::Inline(inline) = &mut mapped
+    {
+        let lines_above = inline
+            .start_line
+            .map_or(0, |start| inline.line.saturating_sub(start));
+        inline.body = gitlab_suggestion_body(&inline.body, lines_above);
+    }
+    mapped
+}
+
+/// Rewrite each suggestion fence in `body` to GitLab's relative form,
+/// replacing `lines_above` lines above the anchor line plus the line itself.
+fn gitlab_suggestion_body(body: &str, lines_above: u32) -> String {
+    body.split('\n')
+        .map(|line| {
+            if is_suggestion_fence(line) {
+                format!("```suggestion:-{lines_above}+0")
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn map_comment_anchor(
+    comment: &Comment,
+    anchor: CommentAnchor,
+    file: &DiffFile,
+    config: &ForgeConfig,
 ) -> MappedComment {
     let path = file.display_path().clone();
 
+    if comment.outdated {
+        return MappedComment::Unmappable {
+            comment: comment.clone(),
+            file: path,
+            reason: UnmappableReason::Outdated,
+        };
+    }
     if file.is_binary {
         return MappedComment::Unmappable {
             comment: comment.clone(),
@@ -574,7 +711,9 @@ pub fn build_review_body(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::comment::{Comment, CommentType, LineContext, LineRange, LineSide};
+    use crate::model::comment::{
+        Comment, CommentLifecycleState, CommentType, LineContext, LineRange, LineSide,
+    };
     use crate::model::diff_types::{DiffHunk, DiffLine, FileStatus, LineOrigin};
     use std::path::PathBuf;
 
@@ -596,6 +735,7 @@ mod tests {
             new_start: 1,
             new_count: 0,
             lines,
+            raw_body: None,
         }
     }
 
@@ -609,6 +749,7 @@ mod tests {
             is_too_large: false,
             is_commit_message: false,
             content_hash: 0,
+            review_key: None,
         }
     }
 
@@ -721,6 +862,7 @@ mod tests {
             anchor_from(&comment),
             &typical_file(),
             &default_config(),
+            ForgeKind::GitHub,
         );
         match mapped {
             MappedComment::Inline(inline) => {
@@ -742,6 +884,7 @@ mod tests {
             anchor_from(&comment),
             &typical_file(),
             &default_config(),
+            ForgeKind::GitHub,
         );
         assert!(matches!(
             mapped,
@@ -763,6 +906,7 @@ mod tests {
             anchor_from(&comment),
             &typical_file(),
             &default_config(),
+            ForgeKind::GitHub,
         );
         match mapped {
             MappedComment::Inline(inline) => {
@@ -785,6 +929,7 @@ mod tests {
             anchor_from(&comment),
             &typical_file(),
             &default_config(),
+            ForgeKind::GitHub,
         );
         match mapped {
             MappedComment::Inline(inline) => {
@@ -804,6 +949,7 @@ mod tests {
             anchor_from(&comment),
             &typical_file(),
             &default_config(),
+            ForgeKind::GitHub,
         );
         match mapped {
             MappedComment::Inline(inline) => {
@@ -824,7 +970,13 @@ mod tests {
             line(LineOrigin::Addition, Some(12), None),
         ])]);
         let comment = comment_range(LineSide::New, LineRange::new(10, 12));
-        let mapped = map_comment(&comment, anchor_from(&comment), &file, &default_config());
+        let mapped = map_comment(
+            &comment,
+            anchor_from(&comment),
+            &file,
+            &default_config(),
+            ForgeKind::GitHub,
+        );
         match mapped {
             MappedComment::Inline(inline) => {
                 assert_eq!(inline.line, 12);
@@ -836,6 +988,97 @@ mod tests {
         }
     }
 
+    fn suggestion_range(range: LineRange) -> Comment {
+        Comment::new_with_range(
+            "```suggestion\nlet a = 1;\n```".to_string(),
+            CommentType::from_id("suggestion"),
+            Some(LineSide::New),
+            range,
+        )
+    }
+
+    fn three_added_lines() -> DiffFile {
+        file_with_hunks(vec![hunk(vec![
+            line(LineOrigin::Addition, Some(10), None),
+            line(LineOrigin::Addition, Some(11), None),
+            line(LineOrigin::Addition, Some(12), None),
+        ])])
+    }
+
+    #[test]
+    fn should_keep_suggestion_fence_intact_for_github() {
+        // given
+        let comment = suggestion_range(LineRange::new(10, 12));
+
+        // when
+        let mapped = map_comment(
+            &comment,
+            CommentAnchor::Range,
+            &three_added_lines(),
+            &default_config(),
+            ForgeKind::GitHub,
+        );
+
+        // then
+        match mapped {
+            MappedComment::Inline(inline) => {
+                assert_eq!(inline.body, "[SUGGESTION]\n```suggestion\nlet a = 1;\n```")
+            }
+            other => panic!("expected Inline, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn should_translate_suggestion_fence_to_gitlab_relative_range() {
+        // given a suggestion over lines 10-12, anchored on line 12
+        let comment = suggestion_range(LineRange::new(10, 12));
+        let config = ForgeConfig {
+            comment_type_prefix: false,
+            ..ForgeConfig::default()
+        };
+
+        // when
+        let mapped = map_comment(
+            &comment,
+            CommentAnchor::Range,
+            &three_added_lines(),
+            &config,
+            ForgeKind::GitLab,
+        );
+
+        // then
+        match mapped {
+            MappedComment::Inline(inline) => {
+                assert_eq!(inline.body, "```suggestion:-2+0\nlet a = 1;\n```")
+            }
+            other => panic!("expected Inline, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn should_translate_single_line_suggestion_for_gitlab() {
+        // given
+        let comment = suggestion_range(LineRange::single(11));
+
+        // when
+        let mapped = map_comment(
+            &comment,
+            CommentAnchor::Range,
+            &three_added_lines(),
+            &default_config(),
+            ForgeKind::GitLab,
+        );
+
+        // then
+        match mapped {
+            MappedComment::Inline(inline) => assert_eq!(
+                inline.body,
+                "[SUGGESTION]\n```suggestion:-0+0\nlet a = 1;\n```"
+            ),
+            other => panic!("expected Inline, got {other:?}"),
+        }
+    }
+
     #[test]
     fn should_map_old_side_range_to_left_start_and_end() {
         let file = file_with_hunks(vec![hunk(vec![
@@ -844,7 +1087,13 @@ mod tests {
             line(LineOrigin::Deletion, None, Some(22)),
         ])]);
         let comment = comment_range(LineSide::Old, LineRange::new(20, 22));
-        let mapped = map_comment(&comment, anchor_from(&comment), &file, &default_config());
+        let mapped = map_comment(
+            &comment,
+            anchor_from(&comment),
+            &file,
+            &default_config(),
+            ForgeKind::GitHub,
+        );
         match mapped {
             MappedComment::Inline(inline) => {
                 assert_eq!(inline.line, 22);
@@ -860,7 +1109,13 @@ mod tests {
     fn should_flatten_single_line_range_to_inline_without_start_fields() {
         let file = file_with_hunks(vec![hunk(vec![line(LineOrigin::Addition, Some(15), None)])]);
         let comment = comment_range(LineSide::New, LineRange::single(15));
-        let mapped = map_comment(&comment, anchor_from(&comment), &file, &default_config());
+        let mapped = map_comment(
+            &comment,
+            anchor_from(&comment),
+            &file,
+            &default_config(),
+            ForgeKind::GitHub,
+        );
         match mapped {
             MappedComment::Inline(inline) => {
                 assert_eq!(inline.line, 15);
@@ -881,7 +1136,13 @@ mod tests {
             line(LineOrigin::Addition, Some(11), None),
         ])]);
         let comment = comment_range(LineSide::New, LineRange::new(10, 11));
-        let mapped = map_comment(&comment, anchor_from(&comment), &file, &default_config());
+        let mapped = map_comment(
+            &comment,
+            anchor_from(&comment),
+            &file,
+            &default_config(),
+            ForgeKind::GitHub,
+        );
         match mapped {
             MappedComment::Inline(inline) => {
                 let anchors = inline.range_anchors.expect("range anchors");
@@ -916,7 +1177,13 @@ mod tests {
             line(LineOrigin::Context, Some(12), Some(7)),
         ])]);
         let comment = comment_range(LineSide::New, LineRange::new(10, 12));
-        let mapped = map_comment(&comment, anchor_from(&comment), &file, &default_config());
+        let mapped = map_comment(
+            &comment,
+            anchor_from(&comment),
+            &file,
+            &default_config(),
+            ForgeKind::GitHub,
+        );
         match mapped {
             MappedComment::Inline(inline) => {
                 assert_eq!(inline.counterpart_line, Some(7));
@@ -943,7 +1210,13 @@ mod tests {
             line(LineOrigin::Deletion, None, Some(22)),
         ])]);
         let comment = comment_range(LineSide::New, LineRange::new(20, 22));
-        let mapped = map_comment(&comment, anchor_from(&comment), &file, &default_config());
+        let mapped = map_comment(
+            &comment,
+            anchor_from(&comment),
+            &file,
+            &default_config(),
+            ForgeKind::GitHub,
+        );
         match mapped {
             MappedComment::Unmappable { reason, .. } => {
                 assert_eq!(reason, UnmappableReason::MixedSideRange);
@@ -962,6 +1235,7 @@ mod tests {
             anchor_from(&comment),
             &typical_file(),
             &default_config(),
+            ForgeKind::GitHub,
         );
         match mapped {
             MappedComment::Inline(inline) => {
@@ -978,7 +1252,13 @@ mod tests {
         // Pure deletion file: nothing on the New side.
         let file = file_with_hunks(vec![hunk(vec![line(LineOrigin::Deletion, None, Some(5))])]);
         let comment = comment_file_level();
-        let mapped = map_comment(&comment, anchor_from(&comment), &file, &default_config());
+        let mapped = map_comment(
+            &comment,
+            anchor_from(&comment),
+            &file,
+            &default_config(),
+            ForgeKind::GitHub,
+        );
         match mapped {
             MappedComment::Unmappable { reason, .. } => {
                 assert_eq!(reason, UnmappableReason::FileLevelNoAnchor);
@@ -992,7 +1272,13 @@ mod tests {
         let mut file = typical_file();
         file.is_binary = true;
         let comment = comment_with_line(LineSide::New, Some(11), None);
-        let mapped = map_comment(&comment, anchor_from(&comment), &file, &default_config());
+        let mapped = map_comment(
+            &comment,
+            anchor_from(&comment),
+            &file,
+            &default_config(),
+            ForgeKind::GitHub,
+        );
         assert!(matches!(
             mapped,
             MappedComment::Unmappable {
@@ -1007,7 +1293,13 @@ mod tests {
         let mut file = typical_file();
         file.is_too_large = true;
         let comment = comment_file_level();
-        let mapped = map_comment(&comment, anchor_from(&comment), &file, &default_config());
+        let mapped = map_comment(
+            &comment,
+            anchor_from(&comment),
+            &file,
+            &default_config(),
+            ForgeKind::GitHub,
+        );
         assert!(matches!(
             mapped,
             MappedComment::Unmappable {
@@ -1017,6 +1309,26 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn should_mark_outdated_comment_as_unmappable() {
+        let mut comment = comment_file_level();
+        comment.outdated = true;
+        let mapped = map_comment(
+            &comment,
+            anchor_from(&comment),
+            &typical_file(),
+            &default_config(),
+            ForgeKind::GitHub,
+        );
+        assert!(matches!(
+            mapped,
+            MappedComment::Unmappable {
+                reason: UnmappableReason::Outdated,
+                ..
+            }
+        ));
+    }
+
     // Body prefix toggle
 
     #[test]
@@ -1024,8 +1336,15 @@ mod tests {
         let comment = comment_with_line(LineSide::New, Some(11), None);
         let cfg = ForgeConfig {
             comment_type_prefix: false,
+            ..ForgeConfig::default()
         };
-        let mapped = map_comment(&comment, anchor_from(&comment), &typical_file(), &cfg);
+        let mapped = map_comment(
+            &comment,
+            anchor_from(&comment),
+            &typical_file(),
+            &cfg,
+            ForgeKind::GitHub,
+        );
         match mapped {
             MappedComment::Inline(inline) => {
                 assert!(!inline.body.contains("[ISSUE]"));
@@ -1035,6 +1354,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn should_recover_type_and_content_from_a_built_body() {
+        // given
+        let cfg = default_config();
+        let comment = Comment::new(
+            "```suggestion\nfoo\n```".to_string(),
+            CommentType::from_id("issue"),
+            None,
+        );
+        let body = build_inline_body(&comment, true, &cfg);
+        // when
+        let parsed = parse_inline_body(&body, &["note", "issue"], &cfg);
+        // then
+        assert_eq!(parsed.comment_type, CommentType::from_id("issue"));
+        assert_eq!(parsed.content, comment.content);
+        assert!(parsed.file_level);
+    }
+
+    #[test]
+    fn should_keep_brackets_that_are_not_a_configured_type() {
+        let parsed = parse_inline_body("[WIP] not a type", &["note"], &default_config());
+        assert_eq!(parsed.comment_type, CommentType::None);
+        assert_eq!(parsed.content, "[WIP] not a type");
+        assert!(!parsed.file_level);
+    }
+
     // build_review_body
 
     fn note(content: &str) -> Comment {
@@ -1082,6 +1427,7 @@ mod tests {
     fn should_omit_type_prefix_in_body_when_disabled() {
         let cfg = ForgeConfig {
             comment_type_prefix: false,
+            ..ForgeConfig::default()
         };
         let comments = vec![note("just text")];
         let body = build_review_body(&comments, &[], &cfg);
@@ -1116,6 +1462,52 @@ mod tests {
         );
     }
 
+    // collect_thread_replies
+
+    fn remote_thread(id: &str) -> RemoteReviewThread {
+        RemoteReviewThread {
+            id: id.to_string(),
+            path: "src/lib.rs".to_string(),
+            line: Some(1),
+            side: crate::forge::remote_comments::RemoteCommentSide::Right,
+            is_resolved: false,
+            is_outdated: false,
+            comments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn should_collect_draft_replies_in_thread_order() {
+        // given
+        let mut sent = Comment::new("sent".to_string(), CommentType::None, None);
+        sent.lifecycle_state = CommentLifecycleState::Submitted;
+        let mut replies = HashMap::new();
+        replies.insert(
+            "t2".to_string(),
+            vec![Comment::new("second".to_string(), CommentType::None, None)],
+        );
+        replies.insert(
+            "t1".to_string(),
+            vec![
+                sent,
+                Comment::new("first".to_string(), CommentType::None, None),
+            ],
+        );
+        replies.insert(
+            "gone".to_string(),
+            vec![Comment::new("orphan".to_string(), CommentType::None, None)],
+        );
+        let threads = vec![remote_thread("t1"), remote_thread("t2")];
+
+        // when
+        let collected = collect_thread_replies(&replies, &threads, &default_config());
+
+        // then
+        let bodies: Vec<&str> = collected.iter().map(|r| r.body.as_str()).collect();
+        assert_eq!(bodies, vec!["first", "second"]);
+        assert_eq!(collected[0].thread.id, "t1");
+    }
+
     // SubmitEvent
 
     #[test]
@@ -1,9 +1,9 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::error::Result;
-use crate::forge::remote_comments::RemoteReviewThread;
+use crate::error::{Result, TuicrError};
+use crate::forge::remote_comments::{PendingReview, RemoteReviewThread};
 use crate::forge::submit::SubmitEvent;
 use crate::model::{DiffLine, FilePatch, FileStatus};
 
@@ -13,10 +13,17 @@ pub enum ForgeKind {
     GitHub,
     GitLab,
     /// Bitbucket Cloud only. Data Center speaks an unrelated REST 1.0 API and
-    /// is rejected during remote-URL parsing.
+    /// has its own kind, [`ForgeKind::BitbucketDataCenter`].
     Bitbucket,
     #[serde(rename = "azure_devops")]
     AzureDevOps,
+    /// Gitea and its Forgejo fork, which share the `/api/v1` REST API.
+    Gitea,
+    /// Self-hosted Bitbucket Data Center (formerly Server), REST 1.0.
+    BitbucketDataCenter,
+    /// Gerrit Code Review. A change maps onto a PR and its patch sets onto
+    /// the PR's commits.
+    Gerrit,
 }
 
 impl ForgeKind {
@@ -27,18 +34,39 @@ impl ForgeKind {
             ForgeKind::GitLab => "GitLab",
             ForgeKind::Bitbucket => "Bitbucket",
             ForgeKind::AzureDevOps => "Azure DevOps",
+            ForgeKind::Gitea => "Gitea",
+            ForgeKind::BitbucketDataCenter => "Bitbucket Data Center",
+            ForgeKind::Gerrit => "Gerrit",
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ForgeRepository {
     pub kind: ForgeKind,
     pub host: String,
     pub owner: String,
     pub name: String,
+    /// Where a self-hosted instance serves its web UI and API when that is
+    /// not `https://<host>`: another scheme or port, or a context path
+    /// (`http://git.corp:7990/bitbucket`). `None` for the default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub web_root: Option<String>,
+}
+
+/// The web root says how to reach an instance, not which repository it is:
+/// an SSH remote and a PR URL on a custom port name the same repository.
+impl PartialEq for ForgeRepository {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.host == other.host
+            && self.owner == other.owner
+            && self.name == other.name
+    }
 }
 
+impl Eq for ForgeRepository {}
+
 impl ForgeRepository {
     pub fn github(
         host: impl Into<String>,
@@ -50,6 +78,7 @@ impl ForgeRepository {
             host: host.into(),
             owner: owner.into(),
             name: name.into(),
+            web_root: None,
         }
     }
 
@@ -63,6 +92,7 @@ impl ForgeRepository {
             host: host.into(),
             owner: owner.into(),
             name: name.into(),
+            web_root: None,
         }
     }
 
@@ -77,6 +107,7 @@ impl ForgeRepository {
             host: host.into(),
             owner: owner.into(),
             name: name.into(),
+            web_root: None,
         }
     }
 
@@ -97,10 +128,76 @@ impl ForgeRepository {
             host: host.into(),
             owner: owner.into(),
             name: name.into(),
+            web_root: None,
         }
     }
 
+    /// Gitea / Forgejo instances are self-hosted, so `host` is always part
+    /// of the coordinate (there is no canonical default host).
+    pub fn gitea(
+        host: impl Into<String>,
+        owner: impl Into<String>,
+        name: impl Into<String>,
+    ) -> Self {
+        Self {
+            kind: ForgeKind::Gitea,
+            host: host.into(),
+            owner: owner.into(),
+            name: name.into(),
+            web_root: None,
+        }
+    }
+
+    /// Data Center repositories live under a project key (`PROJ`, or `~user`
+    /// for personal repos), stored as `owner`. Self-hosted, so `host` is
+    /// always part of the coordinate.
+    pub fn bitbucket_data_center(
+        host: impl Into<String>,
+        project_key: impl Into<String>,
+        name: impl Into<String>,
+    ) -> Self {
+        Self {
+            kind: ForgeKind::BitbucketDataCenter,
+            host: host.into(),
+            owner: project_key.into(),
+            name: name.into(),
+            web_root: None,
+        }
+    }
+
+    /// Gerrit project names are arbitrary paths (`nova`, `platform/build`),
+    /// so the last segment becomes `name` and the rest `owner` — empty for a
+    /// top-level project. Like Gitea, the host is always part of the
+    /// coordinate.
+    pub fn gerrit(host: impl Into<String>, project: impl AsRef<str>) -> Self {
+        let project = project.as_ref().trim_matches('/');
+        let (owner, name) = project.rsplit_once('/').unwrap_or(("", project));
+        Self {
+            kind: ForgeKind::Gerrit,
+            host: host.into(),
+            owner: owner.to_string(),
+            name: name.to_string(),
+            web_root: None,
+        }
+    }
+
+    /// Set where the instance is served from; see [`Self::web_root`].
+    pub fn with_web_root(mut self, web_root: Option<String>) -> Self {
+        self.web_root = web_root;
+        self
+    }
+
+    /// Base URL of the web UI: `web_root`, or `https://<host>`.
+    pub fn web_base(&self) -> String {
+        self.web_root
+            .clone()
+            .unwrap_or_else(|| format!("https://{}", self.host))
+    }
+
     pub fn slug(&self) -> String {
+        if self.owner.is_empty() {
+            return self.name.clone();
+        }
         format!("{}/{}", self.owner, self.name)
     }
 
@@ -169,6 +266,28 @@ impl PullRequestListScope {
     }
 }
 
+/// How `:merge` lands the PR on its base branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeMethod {
+    /// A merge commit.
+    #[default]
+    Merge,
+    /// All commits squashed into one.
+    Squash,
+    /// The commits replayed onto the base branch.
+    Rebase,
+}
+
+impl MergeMethod {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Merge => "merge",
+            Self::Squash => "squash",
+            Self::Rebase => "rebase",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PullRequestListQuery {
     pub repository: ForgeRepository,
@@ -261,7 +380,8 @@ impl PullRequestDetails {
 /// Sessions are keyed by forge kind + host + owner/repo + PR number + head
 /// SHA per the spec. Two opens of the same PR at the same head SHA must
 /// produce equal keys so persistence reattaches local comments and reviewed
-/// markers; a PR that advances to a new head opens a new session.
+/// markers; a PR that advances to a new head opens a new session, seeded
+/// with the local drafts carried forward from the previous head.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PrSessionKey {
     pub repository: ForgeRepository,
@@ -552,6 +672,164 @@ pub trait ForgeBackend {
         pr: &PullRequestDetails,
         request: CreateReviewRequest<'_>,
     ) -> Result<GhCreateReviewResponse>;
+
+    /// Post `body` as a reply at the end of an existing review thread.
+    /// Default returns [`TuicrError::UnsupportedOperation`]; backends whose
+    /// forge exposes threaded replies override this.
+    fn reply_to_review_thread(
+        &self,
+        _pr: &PullRequestDetails,
+        _thread: &RemoteReviewThread,
+        _body: &str,
+    ) -> Result<()> {
+        Err(TuicrError::UnsupportedOperation(
+            "Replying to review threads is not supported for this forge".to_string(),
+        ))
+    }
+
+    /// Whether the forge keeps a per-viewer "Viewed" mark on each changed
+    /// file that [`Self::list_viewed_files`] and [`Self::set_file_viewed`]
+    /// can read and write.
+    fn supports_viewed_files(&self) -> bool {
+        false
+    }
+
+    /// Paths of the files the authenticated viewer has marked "Viewed" on
+    /// the forge. Default returns an empty list.
+    fn list_viewed_files(&self, _pr: &PullRequestDetails) -> Result<Vec<PathBuf>> {
+        Ok(Vec::new())
+    }
+
+    /// Set or clear the viewer's "Viewed" mark on one changed file. Default
+    /// returns [`TuicrError::UnsupportedOperation`].
+    fn set_file_viewed(&self, _pr: &PullRequestDetails, _path: &Path, _viewed: bool) -> Result<()> {
+        Err(TuicrError::UnsupportedOperation(
+            "Marking files as viewed is not supported for this forge".to_string(),
+        ))
+    }
+
+    /// Whether the forge exposes the viewer's pending review through
+    /// [`Self::get_pending_review`] and the calls that edit and finish it.
+    fn supports_pending_reviews(&self) -> bool {
+        false
+    }
+
+    /// The authenticated viewer's pending review on `pr`, if one exists.
+    /// Default returns `None`.
+    fn get_pending_review(&self, _pr: &PullRequestDetails) -> Result<Option<PendingReview>> {
+        Ok(None)
+    }
+
+    /// Add `request.comments` to an existing pending review, then publish it
+    /// with `request.event` and `request.body` appended to its body. A
+    /// [`SubmitEvent::Draft`] request leaves the review pending. Default
+    /// returns [`TuicrError::UnsupportedOperation`].
+    fn extend_pending_review(
+        &self,
+        _pr: &PullRequestDetails,
+        _review: &PendingReview,
+        _request: CreateReviewRequest<'_>,
+    ) -> Result<GhCreateReviewResponse> {
+        Err(TuicrError::UnsupportedOperation(
+            "Pending reviews are not supported for this forge".to_string(),
+        ))
+    }
+
+    /// Delete a pending review together with its comments. Default returns
+    /// [`TuicrError::UnsupportedOperation`].
+    fn discard_pending_review(
+        &self,
+        _pr: &PullRequestDetails,
+        _review: &PendingReview,
+    ) -> Result<()> {
+        Err(TuicrError::UnsupportedOperation(
+            "Pending reviews are not supported for this forge".to_string(),
+        ))
+    }
+
+    /// Replace the body of one comment of the pending review. Default
+    /// returns [`TuicrError::UnsupportedOperation`].
+    fn update_pending_review_comment(
+        &self,
+        _pr: &PullRequestDetails,
+        _comment_id: &str,
+        _body: &str,
+    ) -> Result<()> {
+        Err(TuicrError::UnsupportedOperation(
+            "Pending reviews are not supported for this forge".to_string(),
+        ))
+    }
+
+    /// Remove one comment from the pending review. Default returns
+    /// [`TuicrError::UnsupportedOperation`].
+    fn delete_pending_review_comment(
+        &self,
+        _pr: &PullRequestDetails,
+        _comment_id: &str,
+    ) -> Result<()> {
+        Err(TuicrError::UnsupportedOperation(
+            "Pending reviews are not supported for this forge".to_string(),
+        ))
+    }
+
+    /// Mark an existing review thread resolved (`resolved = true`) or reopen
+    /// it. Default returns [`TuicrError::UnsupportedOperation`].
+    fn set_review_thread_resolved(
+        &self,
+        _pr: &PullRequestDetails,
+        _thread: &RemoteReviewThread,
+        _resolved: bool,
+    ) -> Result<()> {
+        Err(TuicrError::UnsupportedOperation(
+            "Resolving review threads is not supported for this forge".to_string(),
+        ))
+    }
+
+    /// Merge the PR with `method`. Backends should refuse when the remote
+    /// head has moved past `pr.head_sha`. Default returns
+    /// [`TuicrError::UnsupportedOperation`].
+    fn merge_pull_request(&self, _pr: &PullRequestDetails, _method: MergeMethod) -> Result<()> {
+        Err(TuicrError::UnsupportedOperation(
+            "Merging pull requests is not supported for this forge".to_string(),
+        ))
+    }
+
+    /// Close the PR without merging it. Default returns
+    /// [`TuicrError::UnsupportedOperation`].
+    fn close_pull_request(&self, _pr: &PullRequestDetails) -> Result<()> {
+        Err(TuicrError::UnsupportedOperation(
+            "Closing pull requests is not supported for this forge".to_string(),
+        ))
+    }
+
+    /// Ask `username` to review the PR, keeping the reviewers already
+    /// requested. Default returns [`TuicrError::UnsupportedOperation`].
+    fn request_reviewer(&self, _pr: &PullRequestDetails, _username: &str) -> Result<()> {
+        Err(TuicrError::UnsupportedOperation(
+            "Requesting reviewers is not supported for this forge".to_string(),
+        ))
+    }
+
+    /// Add `label` to the PR, keeping its other labels. Default returns
+    /// [`TuicrError::UnsupportedOperation`].
+    fn add_label(&self, _pr: &PullRequestDetails, _label: &str) -> Result<()> {
+        Err(TuicrError::UnsupportedOperation(
+            "Labels are not supported for this forge".to_string(),
+        ))
+    }
+    /// Fetch the raw CI log behind `check`, normally a failing one. The
+    /// text still carries the forge's timestamps and escape codes; see
+    /// [`crate::forge::check_log::clean_log`]. Default returns
+    /// [`TuicrError::UnsupportedOperation`].
+    fn fetch_check_log(
+        &self,
+        _pr: &PullRequestDetails,
+        _check: &PullRequestCheckStatus,
+    ) -> Result<String> {
+        Err(TuicrError::UnsupportedOperation(
+            "CI logs are not available for this forge".to_string(),
+        ))
+    }
 }
 
 #[cfg(test)]
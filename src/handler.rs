@@ -1,18 +1,26 @@
-use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use std::path::{Path, PathBuf};
+
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::layout::Position;
 
 use crate::app::{
     self, App, CommandCompletionState, ExpandDirection, FileTreeItem, FileTreePrompt, FocusedPanel,
-    GapCursorHit, InputMode, TargetTab, VisualSelection,
+    GapCursorHit, InputMode, PrAction, TargetTab, VisualSelection,
 };
+use crate::config::ExportConfig;
 use crate::forge::remote_comments::PrCommentsVisibility;
 use crate::forge::submit::SubmitEvent;
-use crate::input::Action;
+use crate::forge::traits::MergeMethod;
+use crate::input::{Action, ChordPrefix, map_file_tree_prompt_mode, map_target_filter_mode};
 use crate::model::{ClearScope, LineSide};
-use crate::output::{copy_text_to_clipboard, export_to_clipboard, generate_export_content};
+use crate::output::email::generate_email_replies;
+use crate::output::{
+    ExportFormat, copy_text_to_clipboard, export_to_clipboard, generate_export_content,
+};
 use crate::text_edit::{
     delete_char_before, delete_word_before, next_char_boundary, prev_char_boundary,
 };
+use crate::vcs::HunkOperation;
 
 const WHEEL_LINES: usize = 3;
 /// Columns scrolled per horizontal mouse wheel tick. Matches the default
@@ -28,6 +36,22 @@ const COMMAND_SPECS: &[CommandSpec] = &[
     CommandSpec::new(&["e", "reload"], CommandKind::Reload),
     CommandSpec::new(&["edit"], CommandKind::Edit),
     CommandSpec::new(&["clip", "export"], CommandKind::Export),
+    CommandSpec::new(
+        &["clip markdown", "export markdown"],
+        CommandKind::ExportAs(ExportFormat::Markdown),
+    ),
+    CommandSpec::new(
+        &["clip json", "export json"],
+        CommandKind::ExportAs(ExportFormat::Json),
+    ),
+    CommandSpec::new(
+        &["clip sarif", "export sarif"],
+        CommandKind::ExportAs(ExportFormat::Sarif),
+    ),
+    CommandSpec::new(&["export email"], CommandKind::ExportEmail),
+    CommandSpec::new(&["send"], CommandKind::Send),
+    CommandSpec::new(&["diagnostics"], CommandKind::Diagnostics),
+    CommandSpec::new(&["diagnostics clear"], CommandKind::ClearDiagnostics),
     CommandSpec::new(&["copy-url"], CommandKind::CopyUrl),
     CommandSpec::new(
         &["clear"],
@@ -65,6 +89,9 @@ const COMMAND_SPECS: &[CommandSpec] = &[
         &["reviewed", "set reviewed!"],
         CommandKind::ToggleShowReviewed,
     ),
+    CommandSpec::new(&["set blame"], CommandKind::SetBlame(true)),
+    CommandSpec::new(&["set noblame"], CommandKind::SetBlame(false)),
+    CommandSpec::new(&["blame", "set blame!"], CommandKind::ToggleBlame),
     CommandSpec::new(&["diff"], CommandKind::Diff),
     CommandSpec::new(&["focus", "f"], CommandKind::Focus),
     CommandSpec::new(&["stage"], CommandKind::Stage),
@@ -86,6 +113,7 @@ const COMMAND_SPECS: &[CommandSpec] = &[
         &["submit request-changes"],
         CommandKind::Submit(SubmitEvent::RequestChanges),
     ),
+    CommandSpec::new(&["submit discard"], CommandKind::DiscardPendingReview),
     CommandSpec::new(&["submit draft"], CommandKind::Submit(SubmitEvent::Draft)),
     CommandSpec::new(&["summary"], CommandKind::Summary),
     CommandSpec::new(
@@ -100,6 +128,22 @@ const COMMAND_SPECS: &[CommandSpec] = &[
         &["comments hide"],
         CommandKind::Comments(PrCommentsVisibility::Hide),
     ),
+    CommandSpec::new(&["reply"], CommandKind::Reply),
+    CommandSpec::new(&["resolve"], CommandKind::SetThreadResolved(true)),
+    CommandSpec::new(&["unresolve"], CommandKind::SetThreadResolved(false)),
+    CommandSpec::new(
+        &["merge", "merge merge"],
+        CommandKind::MergePr(MergeMethod::Merge),
+    ),
+    CommandSpec::new(&["merge squash"], CommandKind::MergePr(MergeMethod::Squash)),
+    CommandSpec::new(&["merge rebase"], CommandKind::MergePr(MergeMethod::Rebase)),
+    CommandSpec::new(&["close"], CommandKind::ClosePr),
+    CommandSpec::new(&["check-log"], CommandKind::CheckLog),
+    CommandSpec::new(
+        &["request-reviewer"],
+        CommandKind::MissingArgument("request-reviewer <user>"),
+    ),
+    CommandSpec::new(&["label"], CommandKind::MissingArgument("label <name>")),
 ];
 
 /// CommandSpec is the single registry entry used by both completion and
@@ -128,6 +172,15 @@ enum CommandKind {
     Reload,
     Edit,
     Export,
+    /// `:clip <format>`: export once in `format`, ignoring the configured one.
+    ExportAs(ExportFormat),
+    /// `:export email`: write inline replies for a `--patch` mail series.
+    ExportEmail,
+    /// `:send` without a name: pipe the review to the only export target.
+    Send,
+    /// `:diagnostics` without a file: run the configured `diagnostics_command`.
+    Diagnostics,
+    ClearDiagnostics,
     CopyUrl,
     Clear(ClearScope),
     Help,
@@ -145,13 +198,27 @@ enum CommandKind {
     ToggleCommits,
     SetShowReviewed(bool),
     ToggleShowReviewed,
+    SetBlame(bool),
+    ToggleBlame,
     Diff,
     Focus,
     Stage,
     Targets(TargetTab),
     SubmitPicker,
     Submit(SubmitEvent),
+    /// `:submit discard`: delete the viewer's pending review.
+    DiscardPendingReview,
     Comments(PrCommentsVisibility),
+    Reply,
+    SetThreadResolved(bool),
+    /// `:merge [merge|squash|rebase]`: merge the PR on the forge.
+    MergePr(MergeMethod),
+    /// `:close`: close the PR without merging.
+    ClosePr,
+    /// `:check-log`: show the log of a failing CI check.
+    CheckLog,
+    /// A command whose argument was left out; holds its usage.
+    MissingArgument(&'static str),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -166,6 +233,50 @@ enum CompletionDirection {
     Reverse,
 }
 
+/// Map a key press to the action to dispatch, tracking two-key chords (`zz`,
+/// `ZZ`, `dd`, `<leader>e` and any `[keys]` chords) in `pending_chord`.
+///
+/// `None` means the key was consumed: it started a chord, or finished one
+/// the user bound to `none`. The second key of an unbound chord is mapped on
+/// its own.
+pub fn resolve_key_action(
+    app: &mut App,
+    pending_chord: &mut Option<ChordPrefix>,
+    key: KeyEvent,
+) -> Option<Action> {
+    if let Some(prefix) = pending_chord.take()
+        && let Some(action) = app.key_bindings.map_chord(prefix, key)
+    {
+        return (action != Action::None).then_some(action);
+    }
+
+    // Editing the PR-tab filter is a sub-state of CommitSelect; route through
+    // the filter-specific key map so typed characters update the filter
+    // buffer rather than driving commit-list navigation.
+    let action = if app.input_mode == InputMode::CommitSelect && app.pr_filter_editing() {
+        map_target_filter_mode(key)
+    } else if app.input_mode == InputMode::Normal && app.file_tree_prompt_editing() {
+        // An open file-tree prompt (`i`/`e`/`/`) captures all input until
+        // Enter/Esc, like the PR filter above.
+        map_file_tree_prompt_mode(key)
+    } else if app.input_mode == InputMode::Normal && app.focused_panel == FocusedPanel::FileList {
+        // The tree claims i/e/I/E and `/` for filtering; the diff keeps its
+        // own meanings for those keys.
+        app.key_bindings.map_file_tree_key(key, app.leader_key)
+    } else {
+        app.key_bindings
+            .map_key(key, app.input_mode, app.leader_key)
+    };
+
+    // Chord prefixes wait for the next key (these work in any mode).
+    if let Action::PendingChord(prefix) = action {
+        *pending_chord = Some(prefix);
+        app.pending_count = None;
+        return None;
+    }
+    Some(action)
+}
+
 pub fn handle_mouse_event(app: &mut App, event: MouseEvent) {
     let pos = Position::new(event.column, event.row);
     match event.kind {
@@ -182,6 +293,7 @@ pub fn handle_mouse_event(app: &mut App, event: MouseEvent) {
             match app.input_mode {
                 InputMode::Help | InputMode::MessageDetails => handle_help_action(app, action),
                 InputMode::Summary => handle_summary_action(app, action),
+                InputMode::CheckLog => handle_check_log_action(app, action),
                 InputMode::CommitSelect | InputMode::Normal if over_commit_list => {
                     wheel_commit_list(app, scroll_up);
                 }
@@ -389,13 +501,22 @@ fn handle_copy_pr_url(app: &mut App) {
 /// Export review: either to clipboard or set pending stdout output based on app.output_to_stdout.
 /// When output_to_stdout is true, stores the content and sets should_quit.
 fn handle_export(app: &mut App) {
+    handle_export_as(app, app.export.format());
+}
+
+fn handle_export_as(app: &mut App, format: ExportFormat) {
     let slug = app.session_slug();
+    let export = ExportConfig {
+        format: Some(format),
+        ..app.export.clone()
+    };
     if app.output_to_stdout {
         match generate_export_content(
             &app.session,
+            &app.diff_files,
             &app.diff_source,
             &app.comment_types,
-            &app.export,
+            &export,
             &app.forge_review_threads,
             slug.as_deref(),
         ) {
@@ -408,9 +529,10 @@ fn handle_export(app: &mut App) {
     } else {
         match export_to_clipboard(
             &app.session,
+            &app.diff_files,
             &app.diff_source,
             &app.comment_types,
-            &app.export,
+            &export,
             &app.forge_review_threads,
             slug.as_deref(),
         ) {
@@ -420,6 +542,38 @@ fn handle_export(app: &mut App) {
     }
 }
 
+/// Write inline email replies for the mail series under review. Without a
+/// path they go next to where tuicr was started, named after the patch file.
+fn handle_export_email(app: &mut App, path: Option<&str>) {
+    let Some(series) = &app.patch_series else {
+        app.set_warning("Email replies need a mail series opened with --patch");
+        return;
+    };
+    let path = match path {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let stem = app
+                .vcs_info
+                .branch_name
+                .as_deref()
+                .and_then(|label| Path::new(label).file_stem())
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "review".to_string());
+            PathBuf::from(format!("{stem}-reply.mbox"))
+        }
+    };
+    let written =
+        generate_email_replies(series, &app.session, &app.comment_types).and_then(|mbox| {
+            std::fs::write(&path, &mbox.text)?;
+            Ok(mbox.replies)
+        });
+    match written {
+        Ok(1) => app.set_message(format!("Wrote 1 reply to {}", path.display())),
+        Ok(n) => app.set_message(format!("Wrote {n} replies to {}", path.display())),
+        Err(e) => app.set_warning(format!("{e}")),
+    }
+}
+
 /// Copy just the comment under the cursor (`Y`). Unlike `y`, the rest of the
 /// review stays out of the clipboard, so a single comment can go straight into
 /// a chat message or an agent prompt.
@@ -606,6 +760,31 @@ pub fn handle_summary_action(app: &mut App, action: Action) {
     }
 }
 
+/// Handle selection, search, and file jumps in the CI log view.
+pub fn handle_check_log_action(app: &mut App, action: Action) {
+    match action {
+        Action::CursorDown(n) | Action::MouseScrollDown(n) => app.check_log_select_down(n),
+        Action::CursorUp(n) | Action::MouseScrollUp(n) => app.check_log_select_up(n),
+        Action::HalfPageDown => app.check_log_select_down(app.check_log_page() / 2),
+        Action::HalfPageUp => app.check_log_select_up(app.check_log_page() / 2),
+        Action::PageDown => app.check_log_select_down(app.check_log_page()),
+        Action::PageUp => app.check_log_select_up(app.check_log_page()),
+        Action::GoToTop => app.check_log_select_up(usize::MAX),
+        Action::GoToBottom => app.check_log_select_down(usize::MAX),
+        Action::EnterSearchMode => app.enter_search_mode(),
+        Action::SearchNext => {
+            app.search_next_in_check_log();
+        }
+        Action::SearchPrev => {
+            app.search_prev_in_check_log();
+        }
+        Action::SubmitInput => app.jump_to_check_log_target(),
+        Action::ExitMode => app.close_check_log(),
+        Action::Quit => app.should_quit = true,
+        _ => {}
+    }
+}
+
 /// Handle actions in Command mode (text input for :commands)
 pub fn handle_command_action(app: &mut App, action: Action) {
     match action {
@@ -638,6 +817,19 @@ pub fn handle_command_action(app: &mut App, action: Action) {
             let cmd = app.command_buffer.trim().to_string();
             let after_dispatch = if let Some(spec) = command_spec_for(&cmd) {
                 dispatch_command(app, spec.kind)
+            } else if let Some(path) = parse_export_email_command(&cmd) {
+                handle_export_email(app, Some(path));
+                CommandAfterDispatch::ExitCommandMode
+            } else if let Some(target) = parse_send_command(&cmd) {
+                app.send_to_export_target(Some(target));
+                CommandAfterDispatch::ExitCommandMode
+            } else if let Some(path) = parse_diagnostics_command(&cmd) {
+                app.load_diagnostics_file(Path::new(path));
+                CommandAfterDispatch::ExitCommandMode
+            } else if let Some(pr_action) = parse_pr_action_command(&cmd) {
+                app.exit_command_mode();
+                app.request_pr_action(pr_action);
+                CommandAfterDispatch::KeepMode
             } else if let Some((lineno, side)) = parse_lineno_command(&cmd) {
                 app.go_to_source_line(lineno, side);
                 CommandAfterDispatch::ExitCommandMode
@@ -866,6 +1058,26 @@ fn dispatch_command(app: &mut App, kind: CommandKind) -> CommandAfterDispatch {
             handle_export(app);
             CommandAfterDispatch::ExitCommandMode
         }
+        CommandKind::ExportAs(format) => {
+            handle_export_as(app, format);
+            CommandAfterDispatch::ExitCommandMode
+        }
+        CommandKind::ExportEmail => {
+            handle_export_email(app, None);
+            CommandAfterDispatch::ExitCommandMode
+        }
+        CommandKind::Send => {
+            app.send_to_export_target(None);
+            CommandAfterDispatch::ExitCommandMode
+        }
+        CommandKind::Diagnostics => {
+            app.run_diagnostics_command();
+            CommandAfterDispatch::ExitCommandMode
+        }
+        CommandKind::ClearDiagnostics => {
+            app.clear_diagnostics();
+            CommandAfterDispatch::ExitCommandMode
+        }
         CommandKind::CopyUrl => {
             handle_copy_pr_url(app);
             CommandAfterDispatch::ExitCommandMode
@@ -941,6 +1153,14 @@ fn dispatch_command(app: &mut App, kind: CommandKind) -> CommandAfterDispatch {
             app.toggle_show_reviewed();
             CommandAfterDispatch::ExitCommandMode
         }
+        CommandKind::SetBlame(enabled) => {
+            app.set_show_blame(enabled);
+            CommandAfterDispatch::ExitCommandMode
+        }
+        CommandKind::ToggleBlame => {
+            app.toggle_blame();
+            CommandAfterDispatch::ExitCommandMode
+        }
         CommandKind::Diff => {
             app.toggle_diff_view_mode();
             CommandAfterDispatch::ExitCommandMode
@@ -977,10 +1197,45 @@ fn dispatch_command(app: &mut App, kind: CommandKind) -> CommandAfterDispatch {
             app.start_submit(event);
             CommandAfterDispatch::KeepMode
         }
+        CommandKind::DiscardPendingReview => {
+            app.exit_command_mode();
+            app.request_discard_pending_review();
+            CommandAfterDispatch::KeepMode
+        }
+        CommandKind::MergePr(method) => {
+            app.exit_command_mode();
+            app.request_pr_action(PrAction::Merge(method));
+            CommandAfterDispatch::KeepMode
+        }
+        CommandKind::ClosePr => {
+            app.exit_command_mode();
+            app.request_pr_action(PrAction::Close);
+            CommandAfterDispatch::KeepMode
+        }
+        CommandKind::CheckLog => {
+            app.exit_command_mode();
+            app.open_check_log();
+            CommandAfterDispatch::KeepMode
+        }
+        CommandKind::MissingArgument(usage) => {
+            app.set_warning(format!("Usage: :{usage}"));
+            CommandAfterDispatch::ExitCommandMode
+        }
         CommandKind::Comments(visibility) => {
             set_remote_comments_visibility(app, visibility);
             CommandAfterDispatch::ExitCommandMode
         }
+        CommandKind::Reply => {
+            app.exit_command_mode();
+            if !app.enter_thread_reply_mode() {
+                app.set_message("Move cursor to a review thread to reply");
+            }
+            CommandAfterDispatch::KeepMode
+        }
+        CommandKind::SetThreadResolved(resolved) => {
+            app.set_thread_resolved_at_cursor(resolved);
+            CommandAfterDispatch::ExitCommandMode
+        }
     }
 }
 
@@ -1084,6 +1339,34 @@ fn complete_command(app: &mut App, direction: CompletionDirection) {
     }
 }
 
+/// Parse `:export email <path>`.
+fn parse_export_email_command(cmd: &str) -> Option<&str> {
+    let path = cmd.strip_prefix("export email ")?.trim();
+    (!path.is_empty()).then_some(path)
+}
+
+/// Parse `:send <target>`.
+fn parse_send_command(cmd: &str) -> Option<&str> {
+    let target = cmd.strip_prefix("send ")?.trim();
+    (!target.is_empty()).then_some(target)
+}
+
+/// Parse `:diagnostics <file>`.
+fn parse_diagnostics_command(cmd: &str) -> Option<&str> {
+    let path = cmd.strip_prefix("diagnostics ")?.trim();
+    (!path.is_empty()).then_some(path)
+}
+
+/// Parse `:request-reviewer <user>` and `:label <name>`.
+fn parse_pr_action_command(cmd: &str) -> Option<PrAction> {
+    if let Some(user) = cmd.strip_prefix("request-reviewer ") {
+        let user = user.trim().trim_start_matches('@');
+        return (!user.is_empty()).then(|| PrAction::RequestReviewer(user.to_string()));
+    }
+    let label = cmd.strip_prefix("label ")?.trim();
+    (!label.is_empty()).then(|| PrAction::AddLabel(label.to_string()))
+}
+
 /// Parse `:<n>` (new-side) or `:o<n>` (old-side) jump targets. The leading `:`
 /// has already been stripped by the time we get here.
 fn parse_lineno_command(cmd: &str) -> Option<(u32, LineSide)> {
@@ -1129,6 +1412,8 @@ pub fn handle_search_action(app: &mut App, action: Action) {
         Action::SubmitInput => {
             if app.searching_help() {
                 app.search_in_help_from_scroll();
+            } else if app.searching_check_log() {
+                app.search_in_check_log_from_selection();
             } else {
                 app.search_in_diff_from_cursor();
             }
@@ -1191,6 +1476,45 @@ pub fn handle_comment_action(app: &mut App, action: Action) {
 
 /// Handle actions in Confirm mode (Y/N prompts)
 pub fn handle_confirm_action(app: &mut App, action: Action) {
+    if let Some(app::ConfirmAction::OpenBlameCommit(_)) = app.pending_confirm {
+        match action {
+            Action::ConfirmYes => app.confirm_pending_blame_commit(),
+            Action::ConfirmNo => app.exit_confirm_mode(),
+            Action::Quit => app.should_quit = true,
+            _ => {}
+        }
+        return;
+    }
+    if let Some(app::ConfirmAction::DiscardPendingReview) = app.pending_confirm {
+        match action {
+            Action::ConfirmYes => app.confirm_discard_pending_review(),
+            Action::ConfirmNo => app.exit_confirm_mode(),
+            Action::Quit => app.should_quit = true,
+            _ => {}
+        }
+        return;
+    }
+    if let Some(app::ConfirmAction::PrAction(_)) = app.pending_confirm {
+        match action {
+            Action::ConfirmYes => app.confirm_pr_action(),
+            Action::ConfirmNo => app.exit_confirm_mode(),
+            Action::Quit => app.should_quit = true,
+            _ => {}
+        }
+        return;
+    }
+    if let Some(app::ConfirmAction::DiscardHunks(_)) = app.pending_confirm {
+        match action {
+            Action::ConfirmYes => app.confirm_pending_discard(),
+            Action::ConfirmNo => {
+                app.exit_confirm_mode();
+                app.set_message("Discard cancelled");
+            }
+            Action::Quit => app.should_quit = true,
+            _ => {}
+        }
+        return;
+    }
     match action {
         Action::ConfirmYes => {
             if let Some(app::ConfirmAction::CopyAndQuit) = app.pending_confirm {
@@ -1198,6 +1522,7 @@ pub fn handle_confirm_action(app: &mut App, action: Action) {
                 if app.output_to_stdout {
                     match generate_export_content(
                         &app.session,
+                        &app.diff_files,
                         &app.diff_source,
                         &app.comment_types,
                         &app.export,
@@ -1210,6 +1535,7 @@ pub fn handle_confirm_action(app: &mut App, action: Action) {
                 } else {
                     match export_to_clipboard(
                         &app.session,
+                        &app.diff_files,
                         &app.diff_source,
                         &app.comment_types,
                         &app.export,
@@ -1400,6 +1726,10 @@ pub fn handle_visual_action(app: &mut App, action: Action) {
                 app.exit_visual_mode();
             }
         }
+        Action::AddSuggestion => app.enter_suggestion_from_visual(),
+        Action::StageHunk => app.change_hunks_at_cursor(HunkOperation::Stage),
+        Action::UnstageHunk => app.change_hunks_at_cursor(HunkOperation::Unstage),
+        Action::DiscardHunk => app.change_hunks_at_cursor(HunkOperation::Discard),
         Action::ExportToClipboard => {
             match app.copy_visual_selection() {
                 Ok(0) => app.set_message("Nothing to copy"),
@@ -1516,21 +1846,17 @@ pub fn handle_comment_navigator_action(app: &mut App, action: Action) {
 }
 
 /// Enter edit mode for the comment under the cursor, placing the text cursor at
-/// the end (vim `A` / non-vim default) or beginning (vim `i`). Surfaces the
-/// right message when the comment is read-only or absent.
+/// the end (vim `A` / non-vim default) or beginning (vim `i`). On a remote
+/// thread it opens the thread's reply draft instead. Surfaces the right
+/// message when the comment is read-only or absent.
 fn edit_comment_at_cursor(app: &mut App, cursor_at_end: bool) {
     if app.cursor_on_locked_comment() {
         let forge = app.forge_display_name();
         app.set_message(format!(
             "Comment already pushed to {forge} — read only in tuicr"
         ));
-    } else if !app.enter_edit_mode(cursor_at_end) {
-        if app.cursor_on_remote_thread() {
-            let forge = app.forge_display_name();
-            app.set_message(format!("{forge} comment — read only in tuicr"));
-        } else {
-            app.set_message("No comment at cursor");
-        }
+    } else if !app.enter_edit_mode(cursor_at_end) && !app.enter_thread_reply_mode() {
+        app.set_message("No comment at cursor");
     }
 }
 
@@ -1625,6 +1951,9 @@ fn handle_shared_normal_action(app: &mut App, action: Action) {
         Action::PrevComment => app.prev_comment(),
         Action::ToggleReviewed => app.toggle_reviewed(),
         Action::ToggleHunkReviewed => app.toggle_hunk_reviewed(),
+        Action::StageHunk => app.change_hunks_at_cursor(HunkOperation::Stage),
+        Action::UnstageHunk => app.change_hunks_at_cursor(HunkOperation::Unstage),
+        Action::DiscardHunk => app.change_hunks_at_cursor(HunkOperation::Discard),
         Action::ToggleFocus => {
             let has_selector = app.has_inline_commit_selector();
             let has_comments = app.has_comment_navigator_items();
@@ -1680,11 +2009,12 @@ fn handle_shared_normal_action(app: &mut App, action: Action) {
             let line = app.get_line_at_cursor();
             if line.is_some() {
                 app.enter_comment_mode(false, line);
-            } else {
+            } else if !app.enter_thread_reply_mode() {
                 app.set_message("Move cursor to a diff line to add a line comment");
             }
         }
         Action::AddFileComment => app.enter_comment_mode(true, None),
+        Action::AddSuggestion => app.enter_suggestion_at_cursor(),
         // `i` edits the comment at cursor. In vim mode the text cursor starts at
         // the beginning; otherwise (and for `A`) it starts at the end.
         Action::EditComment => edit_comment_at_cursor(app, !app.comment_vim_enabled),
@@ -1730,10 +2060,66 @@ fn handle_shared_normal_action(app: &mut App, action: Action) {
             }
         }
         Action::EditFile => app.queue_editor_for_focused_item(),
+        Action::CenterCursor => app.center_cursor(),
+        Action::CursorToViewTop => app.cursor_to_top(),
+        Action::CursorToViewBottom => app.cursor_to_bottom(),
+        Action::ForceQuit => app.should_quit = true,
+        // `ZZ`: save session, export, and quit (same as :wq)
+        Action::ExportAndQuit => {
+            let _ = app.save_current_session_merging_external();
+            if app.session.has_comments() {
+                handle_export_and_quit(app);
+            } else {
+                app.should_quit = true;
+            }
+        }
+        Action::DeleteCommentAtCursor => delete_comment_at_cursor(app),
+        Action::AddReviewComment => app.enter_review_comment_mode(),
+        Action::ShowBlameCommit => app.show_blame_commit_at_cursor(),
+        Action::PromoteDiagnostic => app.promote_diagnostic_at_cursor(),
+        Action::ToggleFileList => app.toggle_file_list(),
+        Action::FocusFileList if app.show_file_list => {
+            app.focused_panel = FocusedPanel::FileList;
+        }
+        Action::FocusDiff => app.focused_panel = FocusedPanel::Diff,
+        Action::FocusPaneUp => {
+            if app.focused_panel == FocusedPanel::Comments {
+                app.focused_panel = FocusedPanel::FileList;
+            } else if app.has_inline_commit_selector() {
+                app.focused_panel = FocusedPanel::CommitSelector;
+            }
+        }
+        Action::FocusPaneDown => {
+            if app.focused_panel == FocusedPanel::FileList && app.has_comment_navigator_items() {
+                app.focused_panel = FocusedPanel::Comments;
+            } else {
+                app.focused_panel = FocusedPanel::Diff;
+            }
+        }
+        Action::ToggleCommitSelector => app.toggle_commit_selector(),
+        Action::ToggleSingleFileView => app.toggle_single_file_view(),
         _ => {}
     }
 }
 
+/// `dd`: delete the local comment or reply draft under the cursor. Pushed
+/// and remote comments are read only.
+fn delete_comment_at_cursor(app: &mut App) {
+    if app.cursor_on_locked_comment() {
+        let forge = app.forge_display_name();
+        app.set_message(format!(
+            "Comment already pushed to {forge} — read only in tuicr"
+        ));
+    } else if !app.delete_comment_at_cursor() && !app.delete_thread_reply_at_cursor() {
+        if app.cursor_on_remote_thread() {
+            let forge = app.forge_display_name();
+            app.set_message(format!("{forge} comment — read only in tuicr"));
+        } else {
+            app.set_message("No comment at cursor");
+        }
+    }
+}
+
 /// Handle actions in the submit resolver modal: pick what to do with each
 /// comment that did not map to an inline GitHub review comment.
 pub fn handle_submit_resolver_action(app: &mut App, action: Action) {
@@ -1785,7 +2171,12 @@ pub fn handle_submit_confirm_action(app: &mut App, action: Action) {
 
 #[cfg(test)]
 mod command_tests {
-    use super::{CommandKind, command_spec_for};
+    use super::{
+        CommandKind, command_spec_for, parse_diagnostics_command, parse_export_email_command,
+        parse_pr_action_command, parse_send_command,
+    };
+    use crate::app::PrAction;
+    use crate::forge::traits::MergeMethod;
 
     #[test]
     fn parses_relative_line_number_commands() {
@@ -1803,6 +2194,48 @@ mod command_tests {
         );
     }
 
+    #[test]
+    fn parses_blame_commands() {
+        assert_eq!(
+            command_spec_for("set blame").map(|spec| spec.kind),
+            Some(CommandKind::SetBlame(true))
+        );
+        assert_eq!(
+            command_spec_for("set noblame").map(|spec| spec.kind),
+            Some(CommandKind::SetBlame(false))
+        );
+        for command in ["blame", "set blame!"] {
+            assert_eq!(
+                command_spec_for(command).map(|spec| spec.kind),
+                Some(CommandKind::ToggleBlame)
+            );
+        }
+    }
+
+    #[test]
+    fn parses_pr_action_commands() {
+        for command in ["merge", "merge merge"] {
+            assert_eq!(
+                command_spec_for(command).map(|spec| spec.kind),
+                Some(CommandKind::MergePr(MergeMethod::Merge))
+            );
+        }
+        assert_eq!(
+            command_spec_for("merge squash").map(|spec| spec.kind),
+            Some(CommandKind::MergePr(MergeMethod::Squash))
+        );
+        assert_eq!(
+            parse_pr_action_command("request-reviewer @octocat"),
+            Some(PrAction::RequestReviewer("octocat".to_string()))
+        );
+        assert_eq!(
+            parse_pr_action_command("label needs docs"),
+            Some(PrAction::AddLabel("needs docs".to_string()))
+        );
+        assert_eq!(parse_pr_action_command("label  "), None);
+        assert_eq!(parse_pr_action_command("labels"), None);
+    }
+
     #[test]
     fn parses_copy_url_command() {
         assert_eq!(
@@ -1811,6 +2244,68 @@ mod command_tests {
         );
     }
 
+    #[test]
+    fn parses_export_format_commands() {
+        use crate::output::ExportFormat;
+
+        assert_eq!(
+            command_spec_for("clip").map(|spec| spec.kind),
+            Some(CommandKind::Export)
+        );
+        for (command, format) in [
+            ("clip markdown", ExportFormat::Markdown),
+            ("clip json", ExportFormat::Json),
+            ("export sarif", ExportFormat::Sarif),
+        ] {
+            assert_eq!(
+                command_spec_for(command).map(|spec| spec.kind),
+                Some(CommandKind::ExportAs(format))
+            );
+        }
+    }
+
+    #[test]
+    fn parses_export_email_with_and_without_a_path() {
+        assert_eq!(
+            command_spec_for("export email").map(|spec| spec.kind),
+            Some(CommandKind::ExportEmail)
+        );
+        assert_eq!(
+            parse_export_email_command("export email out/reply.mbox"),
+            Some("out/reply.mbox")
+        );
+        assert_eq!(parse_export_email_command("export email  "), None);
+        assert_eq!(parse_export_email_command("export emails"), None);
+    }
+
+    #[test]
+    fn parses_send_with_and_without_a_target() {
+        assert_eq!(
+            command_spec_for("send").map(|spec| spec.kind),
+            Some(CommandKind::Send)
+        );
+        assert_eq!(parse_send_command("send claude"), Some("claude"));
+        assert_eq!(parse_send_command("send  "), None);
+        assert_eq!(parse_send_command("sender"), None);
+    }
+
+    #[test]
+    fn parses_diagnostics_with_a_file_or_clear() {
+        assert_eq!(
+            command_spec_for("diagnostics").map(|spec| spec.kind),
+            Some(CommandKind::Diagnostics)
+        );
+        assert_eq!(
+            command_spec_for("diagnostics clear").map(|spec| spec.kind),
+            Some(CommandKind::ClearDiagnostics)
+        );
+        assert_eq!(
+            parse_diagnostics_command("diagnostics target/clippy.json"),
+            Some("target/clippy.json")
+        );
+        assert_eq!(parse_diagnostics_command("diagnostics  "), None);
+    }
+
     #[test]
     fn parses_every_reviewed_visibility_command_form() {
         // Mirrors `:set commits` / `:set nocommits` / `:set commits!`, plus a
@@ -20,9 +20,9 @@ pub enum Action {
     PrevHunk,
     NextComment,
     PrevComment,
-    PendingZCommand,
-    PendingShiftZCommand,
-    PendingLeaderCommand,
+    /// First key of a two-key chord (`zz`, `ZZ`, `dd`, `<leader>e`, ...);
+    /// the next key is resolved by `map_chord`.
+    PendingChord(ChordPrefix),
     ScrollLeft(usize),
     ScrollRight(usize),
     ScrollViewDown(usize),
@@ -30,20 +30,54 @@ pub enum Action {
     MouseScrollUp(usize),
     MouseScrollDown(usize),
 
+    /// Center the cursor line in the viewport (`zz`).
+    CenterCursor,
+    /// Scroll so the cursor line is at the top of the viewport (`zt`).
+    CursorToViewTop,
+    /// Scroll so the cursor line is at the bottom of the viewport (`zb`).
+    CursorToViewBottom,
+
     // Panel focus
     ToggleFocus,
     ToggleFocusReverse,
     SelectFile,
+    /// Show or hide the file list (`<leader>e`).
+    ToggleFileList,
+    /// Focus the file list when it is visible (`<leader>h`).
+    FocusFileList,
+    /// Focus the diff (`<leader>l`).
+    FocusDiff,
+    /// Move focus to the pane above (`<leader>k`).
+    FocusPaneUp,
+    /// Move focus to the pane below (`<leader>j`).
+    FocusPaneDown,
+    /// Show or hide the inline commit selector (`<leader>s`).
+    ToggleCommitSelector,
+    /// Toggle single-file view (`<leader>f`).
+    ToggleSingleFileView,
 
     // Review actions
     ToggleReviewed,
     ToggleHunkReviewed,
+    /// Stage the hunk at cursor, or the visual selection (`a`).
+    StageHunk,
+    /// Unstage the hunk at cursor, or the visual selection (`u`).
+    UnstageHunk,
+    /// Discard the hunk at cursor, or the visual selection, after confirming (`X`).
+    DiscardHunk,
     AddLineComment,
     AddFileComment,
     EditComment,
     /// Edit the comment at cursor with the text cursor at end (vim `A`).
     EditCommentAtEnd,
-    PendingDCommand,
+    /// Delete the local comment or reply draft at cursor (`dd`).
+    DeleteCommentAtCursor,
+    /// Start a review-level comment (`<leader>c`).
+    AddReviewComment,
+    /// Show the commit that last touched the cursor line (`<leader>b`).
+    ShowBlameCommit,
+    /// Turn the diagnostic at cursor into a line comment (`p`).
+    PromoteDiagnostic,
     EditFile,
     SearchNext,
     SearchPrev,
@@ -52,9 +86,15 @@ pub enum Action {
     // Visual selection mode
     EnterVisualMode,
     AddRangeComment,
+    /// Start a suggested-change comment on the line or selection (`s`).
+    AddSuggestion,
 
     // Session
     Quit,
+    /// Quit without exporting or the unsaved-changes warning (`ZQ`).
+    ForceQuit,
+    /// Save, export and quit, like `:wq` (`ZZ`).
+    ExportAndQuit,
     ExportToClipboard,
     /// Copy just the comment under the cursor (`Y`), not the whole review.
     CopyCommentAtCursor,
@@ -155,6 +195,35 @@ pub enum Action {
     None,
 }
 
+/// First key of a two-key chord in normal mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChordPrefix {
+    /// The configurable leader key (`;` by default).
+    Leader,
+    /// `g`. Only a prefix once `[keys]` binds a `g` chord, since a lone `g`
+    /// jumps to the first file.
+    G,
+    /// `z`, for viewport positioning.
+    Z,
+    /// `Z`, for `ZZ` / `ZQ`.
+    ShiftZ,
+    /// `d`, for `dd`.
+    D,
+}
+
+impl ChordPrefix {
+    /// The prefix key as written in a `[keys]` chord.
+    pub fn label(self, leader_key: char) -> String {
+        match self {
+            ChordPrefix::Leader => leader_key.to_string(),
+            ChordPrefix::G => "g".to_string(),
+            ChordPrefix::Z => "z".to_string(),
+            ChordPrefix::ShiftZ => "Z".to_string(),
+            ChordPrefix::D => "d".to_string(),
+        }
+    }
+}
+
 pub fn map_key_to_action(key: KeyEvent, mode: InputMode, leader_key: char) -> Action {
     match mode {
         InputMode::Normal => map_normal_mode(key, leader_key),
@@ -167,6 +236,7 @@ pub fn map_key_to_action(key: KeyEvent, mode: InputMode, leader_key: char) -> Ac
             action => action,
         },
         InputMode::Summary => map_summary_mode(key),
+        InputMode::CheckLog => map_check_log_mode(key),
         InputMode::Confirm => map_confirm_mode(key),
         InputMode::CommitSelect => map_commit_select_mode(key),
         InputMode::VisualSelect => map_visual_mode(key),
@@ -179,7 +249,7 @@ pub fn map_key_to_action(key: KeyEvent, mode: InputMode, leader_key: char) -> Ac
 fn map_normal_mode(key: KeyEvent, leader_key: char) -> Action {
     match (key.code, key.modifiers) {
         (KeyCode::Char(key), KeyModifiers::NONE) if key == leader_key => {
-            Action::PendingLeaderCommand
+            Action::PendingChord(ChordPrefix::Leader)
         }
 
         // Cursor movement (vim-like: cursor moves, scroll follows when needed)
@@ -195,8 +265,8 @@ fn map_normal_mode(key: KeyEvent, leader_key: char) -> Action {
         (KeyCode::PageUp, KeyModifiers::NONE) => Action::PageUp,
         (KeyCode::Char('g'), KeyModifiers::NONE) => Action::GoToTop,
         (KeyCode::Char('G'), _) => Action::GoToBottom,
-        (KeyCode::Char('z'), KeyModifiers::NONE) => Action::PendingZCommand,
-        (KeyCode::Char('Z'), _) => Action::PendingShiftZCommand,
+        (KeyCode::Char('z'), KeyModifiers::NONE) => Action::PendingChord(ChordPrefix::Z),
+        (KeyCode::Char('Z'), _) => Action::PendingChord(ChordPrefix::ShiftZ),
 
         // File navigation (use _ for modifiers since shift is implicit in the character)
         (KeyCode::Char('}'), _) => Action::NextFile,
@@ -221,11 +291,16 @@ fn map_normal_mode(key: KeyEvent, leader_key: char) -> Action {
         // Review actions
         (KeyCode::Char('r'), KeyModifiers::NONE) => Action::ToggleReviewed,
         (KeyCode::Char('R'), _) => Action::ToggleHunkReviewed,
+        (KeyCode::Char('a'), KeyModifiers::NONE) => Action::StageHunk,
+        (KeyCode::Char('u'), KeyModifiers::NONE) => Action::UnstageHunk,
+        (KeyCode::Char('X'), _) => Action::DiscardHunk,
         (KeyCode::Char('c'), KeyModifiers::NONE) => Action::AddLineComment,
         (KeyCode::Char('C'), _) => Action::AddFileComment,
+        (KeyCode::Char('s'), KeyModifiers::NONE) => Action::AddSuggestion,
         (KeyCode::Char('i'), KeyModifiers::NONE) => Action::EditComment,
         (KeyCode::Char('A'), _) => Action::EditCommentAtEnd,
-        (KeyCode::Char('d'), KeyModifiers::NONE) => Action::PendingDCommand,
+        (KeyCode::Char('p'), KeyModifiers::NONE) => Action::PromoteDiagnostic,
+        (KeyCode::Char('d'), KeyModifiers::NONE) => Action::PendingChord(ChordPrefix::D),
         (KeyCode::Char('v') | KeyCode::Char('V'), _) => Action::EnterVisualMode,
         (KeyCode::Char('y'), KeyModifiers::NONE) => Action::ExportToClipboard,
         (KeyCode::Char('Y'), _) => Action::CopyCommentAtCursor,
@@ -252,6 +327,39 @@ fn map_normal_mode(key: KeyEvent, leader_key: char) -> Action {
     }
 }
 
+/// Default meaning of the key pressed after a chord prefix. `Action::None`
+/// means the chord is unbound and the key is handled on its own.
+pub fn map_chord(prefix: ChordPrefix, key: KeyEvent) -> Action {
+    if key
+        .modifiers
+        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    {
+        return Action::None;
+    }
+    let KeyCode::Char(c) = key.code else {
+        return Action::None;
+    };
+    match (prefix, c) {
+        (ChordPrefix::Leader, 'e') => Action::ToggleFileList,
+        (ChordPrefix::Leader, 'h') => Action::FocusFileList,
+        (ChordPrefix::Leader, 'l') => Action::FocusDiff,
+        (ChordPrefix::Leader, 'k') => Action::FocusPaneUp,
+        (ChordPrefix::Leader, 'j') => Action::FocusPaneDown,
+        (ChordPrefix::Leader, 'c') => Action::AddReviewComment,
+        (ChordPrefix::Leader, 's') => Action::ToggleCommitSelector,
+        (ChordPrefix::Leader, 'f') => Action::ToggleSingleFileView,
+        (ChordPrefix::Leader, 'b') => Action::ShowBlameCommit,
+        (ChordPrefix::G, 'g') => Action::GoToTop,
+        (ChordPrefix::Z, 'z') => Action::CenterCursor,
+        (ChordPrefix::Z, 't') => Action::CursorToViewTop,
+        (ChordPrefix::Z, 'b') => Action::CursorToViewBottom,
+        (ChordPrefix::ShiftZ, 'Z') => Action::ExportAndQuit,
+        (ChordPrefix::ShiftZ, 'Q') => Action::ForceQuit,
+        (ChordPrefix::D, 'd') => Action::DeleteCommentAtCursor,
+        _ => Action::None,
+    }
+}
+
 fn map_command_mode(key: KeyEvent) -> Action {
     match (key.code, key.modifiers) {
         (KeyCode::Esc, KeyModifiers::NONE) => Action::ExitMode,
@@ -396,6 +504,16 @@ fn map_summary_mode(key: KeyEvent) -> Action {
     }
 }
 
+/// The summary's keys plus search.
+fn map_check_log_mode(key: KeyEvent) -> Action {
+    match (key.code, key.modifiers) {
+        (KeyCode::Char('/'), _) => Action::EnterSearchMode,
+        (KeyCode::Char('n'), KeyModifiers::NONE) => Action::SearchNext,
+        (KeyCode::Char('N'), _) => Action::SearchPrev,
+        _ => map_summary_mode(key),
+    }
+}
+
 fn map_confirm_mode(key: KeyEvent) -> Action {
     match key.code {
         KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => Action::ConfirmYes,
@@ -514,6 +632,10 @@ fn map_visual_mode(key: KeyEvent) -> Action {
         (KeyCode::Char('k') | KeyCode::Up, KeyModifiers::NONE) => Action::CursorUp(1),
         (KeyCode::Char('c'), KeyModifiers::NONE) => Action::AddRangeComment,
         (KeyCode::Enter, KeyModifiers::NONE) => Action::AddRangeComment,
+        (KeyCode::Char('s'), KeyModifiers::NONE) => Action::AddSuggestion,
+        (KeyCode::Char('a'), KeyModifiers::NONE) => Action::StageHunk,
+        (KeyCode::Char('u'), KeyModifiers::NONE) => Action::UnstageHunk,
+        (KeyCode::Char('X'), _) => Action::DiscardHunk,
         (KeyCode::Char('y'), KeyModifiers::NONE) => Action::ExportToClipboard,
         (KeyCode::Esc, KeyModifiers::NONE) => Action::ExitMode,
         (KeyCode::Char('v') | KeyCode::Char('V'), _) => Action::ExitMode,
@@ -620,7 +742,7 @@ mod tests {
         // `leader = "e"` would otherwise be swallowed by the exclude filter.
         assert_eq!(
             map_file_tree_mode(key(KeyCode::Char('e')), 'e'),
-            Action::PendingLeaderCommand
+            Action::PendingChord(ChordPrefix::Leader)
         );
     }
 
@@ -683,6 +805,12 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_map_lowercase_p_to_promote_diagnostic_in_normal_mode() {
+        let action = map_normal_mode(key(KeyCode::Char('p')), DEFAULT_LEADER_KEY);
+        assert_eq!(action, Action::PromoteDiagnostic);
+    }
+
     #[test]
     fn should_map_uppercase_g_to_go_to_bottom_in_normal_mode() {
         let action = map_normal_mode(key_shift('G'), DEFAULT_LEADER_KEY);
@@ -770,6 +898,18 @@ mod tests {
         assert_eq!(action, Action::ToggleHunkReviewed);
     }
 
+    #[test]
+    fn should_map_hunk_staging_keys_in_normal_and_visual_mode() {
+        for map in [
+            |k| map_normal_mode(k, DEFAULT_LEADER_KEY),
+            map_visual_mode as fn(KeyEvent) -> Action,
+        ] {
+            assert_eq!(map(key(KeyCode::Char('a'))), Action::StageHunk);
+            assert_eq!(map(key(KeyCode::Char('u'))), Action::UnstageHunk);
+            assert_eq!(map(key_shift('X')), Action::DiscardHunk);
+        }
+    }
+
     #[test]
     fn should_map_m_to_comment_navigation_in_normal_mode() {
         let action = map_normal_mode(key(KeyCode::Char('m')), DEFAULT_LEADER_KEY);
@@ -845,7 +985,7 @@ mod tests {
     #[test]
     fn should_map_configured_leader_to_pending_leader_action() {
         let action = map_key_to_action(key(KeyCode::Char(',')), InputMode::Normal, ',');
-        assert_eq!(action, Action::PendingLeaderCommand);
+        assert_eq!(action, Action::PendingChord(ChordPrefix::Leader));
     }
 
     #[test]
@@ -871,7 +1011,7 @@ mod tests {
             InputMode::Normal,
             DEFAULT_LEADER_KEY,
         );
-        assert_eq!(action, Action::PendingLeaderCommand);
+        assert_eq!(action, Action::PendingChord(ChordPrefix::Leader));
     }
 
     #[test]
@@ -0,0 +1,695 @@
+//! User key bindings from the `[keys]` config table.
+//!
+//! Bindings are layered over the built-in maps in `keybindings.rs`: a key
+//! the user bound resolves to their action, everything else keeps its
+//! default meaning. Tables are per mode (`[keys.normal]`, `[keys.visual]`,
+//! ...), keys are chords written vim style and values are action names:
+//!
+//! ```toml
+//! [keys.normal]
+//! "<C-n>" = "next_file"
+//! "<leader>t" = "toggle_file_list"
+//! "gt" = "next_hunk"
+//! "q" = "none"
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use super::keybindings::{Action, ChordPrefix, map_chord, map_file_tree_mode, map_key_to_action};
+use crate::app::InputMode;
+
+/// Modes that accept a `[keys.<mode>]` table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyMode {
+    Normal,
+    Visual,
+    Comment,
+    Help,
+    CommitSelect,
+}
+
+impl KeyMode {
+    pub const ALL: [KeyMode; 5] = [
+        KeyMode::Normal,
+        KeyMode::Visual,
+        KeyMode::Comment,
+        KeyMode::Help,
+        KeyMode::CommitSelect,
+    ];
+
+    /// Table name under `[keys]`.
+    pub fn name(self) -> &'static str {
+        match self {
+            KeyMode::Normal => "normal",
+            KeyMode::Visual => "visual",
+            KeyMode::Comment => "comment",
+            KeyMode::Help => "help",
+            KeyMode::CommitSelect => "commit_select",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.name() == name)
+    }
+
+    fn for_input_mode(mode: InputMode) -> Option<Self> {
+        match mode {
+            InputMode::Normal => Some(KeyMode::Normal),
+            InputMode::VisualSelect => Some(KeyMode::Visual),
+            InputMode::Comment => Some(KeyMode::Comment),
+            InputMode::Help | InputMode::MessageDetails => Some(KeyMode::Help),
+            InputMode::CommitSelect => Some(KeyMode::CommitSelect),
+            _ => None,
+        }
+    }
+}
+
+/// Action names accepted as `[keys]` values. Counted motions bind with a
+/// count of one (or the default horizontal step) and still scale with a
+/// `{count}` prefix.
+const BINDABLE_ACTIONS: &[(&str, Action)] = &[
+    ("none", Action::None),
+    // Navigation
+    ("cursor_down", Action::CursorDown(1)),
+    ("cursor_up", Action::CursorUp(1)),
+    ("scroll_view_down", Action::ScrollViewDown(1)),
+    ("scroll_view_up", Action::ScrollViewUp(1)),
+    ("scroll_left", Action::ScrollLeft(4)),
+    ("scroll_right", Action::ScrollRight(4)),
+    ("half_page_down", Action::HalfPageDown),
+    ("half_page_up", Action::HalfPageUp),
+    ("page_down", Action::PageDown),
+    ("page_up", Action::PageUp),
+    ("go_to_top", Action::GoToTop),
+    ("go_to_bottom", Action::GoToBottom),
+    ("next_file", Action::NextFile),
+    ("prev_file", Action::PrevFile),
+    ("next_hunk", Action::NextHunk),
+    ("prev_hunk", Action::PrevHunk),
+    ("next_comment", Action::NextComment),
+    ("prev_comment", Action::PrevComment),
+    ("center_cursor", Action::CenterCursor),
+    ("cursor_to_view_top", Action::CursorToViewTop),
+    ("cursor_to_view_bottom", Action::CursorToViewBottom),
+    ("cycle_commit_next", Action::CycleCommitNext),
+    ("cycle_commit_prev", Action::CycleCommitPrev),
+    // Panels
+    ("toggle_focus", Action::ToggleFocus),
+    ("toggle_focus_reverse", Action::ToggleFocusReverse),
+    ("select", Action::SelectFile),
+    ("select_full", Action::SelectFileFull),
+    ("toggle_file_list", Action::ToggleFileList),
+    ("focus_file_list", Action::FocusFileList),
+    ("focus_diff", Action::FocusDiff),
+    ("focus_pane_up", Action::FocusPaneUp),
+    ("focus_pane_down", Action::FocusPaneDown),
+    ("toggle_commit_selector", Action::ToggleCommitSelector),
+    ("toggle_single_file_view", Action::ToggleSingleFileView),
+    ("toggle_expand", Action::ToggleExpand),
+    ("expand_all", Action::ExpandAll),
+    ("collapse_all", Action::CollapseAll),
+    ("file_tree_filter_include", Action::FileTreeFilterInclude),
+    ("file_tree_filter_exclude", Action::FileTreeFilterExclude),
+    ("file_tree_clear_include", Action::FileTreeClearInclude),
+    ("file_tree_clear_exclude", Action::FileTreeClearExclude),
+    ("file_tree_search", Action::FileTreeSearch),
+    // Review
+    ("toggle_reviewed", Action::ToggleReviewed),
+    ("toggle_hunk_reviewed", Action::ToggleHunkReviewed),
+    ("stage_hunk", Action::StageHunk),
+    ("unstage_hunk", Action::UnstageHunk),
+    ("discard_hunk", Action::DiscardHunk),
+    ("add_line_comment", Action::AddLineComment),
+    ("add_file_comment", Action::AddFileComment),
+    ("add_review_comment", Action::AddReviewComment),
+    ("add_range_comment", Action::AddRangeComment),
+    ("add_suggestion", Action::AddSuggestion),
+    ("edit_comment", Action::EditComment),
+    ("edit_comment_at_end", Action::EditCommentAtEnd),
+    ("delete_comment", Action::DeleteCommentAtCursor),
+    ("show_blame_commit", Action::ShowBlameCommit),
+    ("promote_diagnostic", Action::PromoteDiagnostic),
+    ("edit_file", Action::EditFile),
+    ("enter_visual_mode", Action::EnterVisualMode),
+    // Search
+    ("search", Action::EnterSearchMode),
+    ("search_next", Action::SearchNext),
+    ("search_prev", Action::SearchPrev),
+    ("clear_search_highlight", Action::ClearSearchHighlight),
+    // Session
+    ("command", Action::EnterCommandMode),
+    ("help", Action::ToggleHelp),
+    ("exit_mode", Action::ExitMode),
+    ("quit", Action::Quit),
+    ("force_quit", Action::ForceQuit),
+    ("export_and_quit", Action::ExportAndQuit),
+    ("export", Action::ExportToClipboard),
+    ("copy_comment", Action::CopyCommentAtCursor),
+    // Comment editing
+    ("submit", Action::SubmitInput),
+    ("insert_newline", Action::InsertChar('\n')),
+    ("cycle_comment_type", Action::CycleCommentType),
+    (
+        "cycle_comment_type_reverse",
+        Action::CycleCommentTypeReverse,
+    ),
+    ("delete_char", Action::DeleteChar),
+    ("delete_word", Action::DeleteWord),
+    ("clear_line", Action::ClearLine),
+    ("text_cursor_left", Action::TextCursorLeft),
+    ("text_cursor_right", Action::TextCursorRight),
+    ("text_cursor_line_start", Action::TextCursorLineStart),
+    ("text_cursor_line_end", Action::TextCursorLineEnd),
+    ("text_cursor_word_left", Action::TextCursorWordLeft),
+    ("text_cursor_word_right", Action::TextCursorWordRight),
+    // Commit selection
+    ("commit_select_down", Action::CommitSelectDown),
+    ("commit_select_up", Action::CommitSelectUp),
+    ("toggle_commit_select", Action::ToggleCommitSelect),
+    ("confirm_commit_select", Action::ConfirmCommitSelect),
+];
+
+/// Look up a `[keys]` action name.
+pub fn action_from_name(name: &str) -> Option<Action> {
+    BINDABLE_ACTIONS
+        .iter()
+        .find(|(candidate, _)| *candidate == name)
+        .map(|(_, action)| action.clone())
+}
+
+/// A single key press, normalized so it compares equal to the crossterm
+/// event it describes. Shift is folded into the character (`G`, not
+/// `S-g`), since terminals disagree on whether they report it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyPress {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyPress {
+    pub fn from_event(key: KeyEvent) -> Self {
+        let modifiers = match key.code {
+            KeyCode::Char(_) | KeyCode::BackTab => key.modifiers - KeyModifiers::SHIFT,
+            _ => key.modifiers,
+        };
+        Self {
+            code: key.code,
+            modifiers,
+        }
+    }
+
+    fn is_plain_char(self, c: char) -> bool {
+        self.code == KeyCode::Char(c) && self.modifiers.is_empty()
+    }
+}
+
+impl FromStr for KeyPress {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Ok(Self {
+                code: KeyCode::Char(c),
+                modifiers: KeyModifiers::NONE,
+            });
+        }
+        let inner = s
+            .strip_prefix('<')
+            .and_then(|rest| rest.strip_suffix('>'))
+            .ok_or_else(|| format!("'{s}' is not a key"))?;
+
+        // The key name is everything after the last modifier, so `<C-->`
+        // is not supported but `<C-d>` and `<A-Enter>` are.
+        let mut parts: Vec<&str> = inner.split('-').collect();
+        let name = parts.pop().unwrap_or_default();
+        let mut modifiers = KeyModifiers::NONE;
+        for part in parts {
+            modifiers |= match part.to_ascii_lowercase().as_str() {
+                "c" | "ctrl" => KeyModifiers::CONTROL,
+                "a" | "alt" | "m" | "meta" => KeyModifiers::ALT,
+                "s" | "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier '{part}' in '{s}'")),
+            };
+        }
+
+        let code = match name.to_ascii_lowercase().as_str() {
+            "enter" | "cr" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            "tab" => KeyCode::Tab,
+            "bs" | "backspace" => KeyCode::Backspace,
+            "space" => KeyCode::Char(' '),
+            "lt" => KeyCode::Char('<'),
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "del" | "delete" => KeyCode::Delete,
+            _ => {
+                let mut chars = name.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+                        KeyCode::Char(c.to_ascii_uppercase())
+                    }
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return Err(format!("unknown key '{name}' in '{s}'")),
+                }
+            }
+        };
+        Ok(Self::from_event(KeyEvent::new(code, modifiers)))
+    }
+}
+
+impl fmt::Display for KeyPress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char('<') => "lt".to_string(),
+            KeyCode::Char(c) if self.modifiers.is_empty() => return write!(f, "{c}"),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::BackTab => return f.write_str("<S-Tab>"),
+            KeyCode::Backspace => "BS".to_string(),
+            KeyCode::Up => "Up".to_string(),
+            KeyCode::Down => "Down".to_string(),
+            KeyCode::Left => "Left".to_string(),
+            KeyCode::Right => "Right".to_string(),
+            KeyCode::PageUp => "PageUp".to_string(),
+            KeyCode::PageDown => "PageDown".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::Delete => "Del".to_string(),
+            other => format!("{other:?}"),
+        };
+        f.write_str("<")?;
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            f.write_str("C-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            f.write_str("A-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            f.write_str("S-")?;
+        }
+        write!(f, "{name}>")
+    }
+}
+
+/// A key, optionally behind a chord prefix: `j`, `<C-d>`, `<leader>e`,
+/// `gt`, `zz`, `ZQ`, `dd`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyChord {
+    pub prefix: Option<ChordPrefix>,
+    pub key: KeyPress,
+}
+
+impl KeyChord {
+    /// Human-readable chord with the configured leader filled in.
+    pub fn label(&self, leader_key: char) -> String {
+        match self.prefix {
+            Some(prefix) => format!("{}{}", prefix.label(leader_key), self.key),
+            None => self.key.to_string(),
+        }
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(rest) = s
+            .get(..8)
+            .filter(|head| head.eq_ignore_ascii_case("<leader>"))
+            .map(|_| &s[8..])
+        {
+            return Ok(Self {
+                prefix: Some(ChordPrefix::Leader),
+                key: rest.parse()?,
+            });
+        }
+        if let Ok(key) = s.parse() {
+            return Ok(Self { prefix: None, key });
+        }
+        let mut chars = s.chars();
+        let prefix = match chars.next() {
+            Some('g') => ChordPrefix::G,
+            Some('z') => ChordPrefix::Z,
+            Some('Z') => ChordPrefix::ShiftZ,
+            Some('d') => ChordPrefix::D,
+            _ => return Err(s.parse::<KeyPress>().unwrap_err()),
+        };
+        let key = chars
+            .as_str()
+            .parse()
+            .map_err(|_| format!("'{s}' is not a key; chords start with <leader>, g, z, Z or d"))?;
+        Ok(Self {
+            prefix: Some(prefix),
+            key,
+        })
+    }
+}
+
+/// One `[keys]` entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub mode: KeyMode,
+    pub chord: KeyChord,
+    /// Action name as written in the config.
+    pub action_name: String,
+    pub action: Action,
+}
+
+/// The user's `[keys]` bindings, consulted before the built-in maps.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyBindings {
+    bindings: Vec<KeyBinding>,
+}
+
+impl KeyBindings {
+    pub fn new(bindings: Vec<KeyBinding>) -> Self {
+        Self { bindings }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bindings.is_empty()
+    }
+
+    pub fn bindings(&self) -> &[KeyBinding] {
+        &self.bindings
+    }
+
+    /// Resolve a key in `mode`, preferring the user's binding.
+    pub fn map_key(&self, key: KeyEvent, mode: InputMode, leader_key: char) -> Action {
+        if let Some(action) = self.normal_override(key, mode) {
+            return action;
+        }
+        map_key_to_action(key, mode, leader_key)
+    }
+
+    /// `map_key` for normal mode while the file tree has focus.
+    pub fn map_file_tree_key(&self, key: KeyEvent, leader_key: char) -> Action {
+        if let Some(action) = self.normal_override(key, InputMode::Normal) {
+            return action;
+        }
+        map_file_tree_mode(key, leader_key)
+    }
+
+    /// Resolve the key pressed after `prefix`. `None` means the chord is
+    /// unbound; a chord the user bound to `none` is `Some(Action::None)`.
+    pub fn map_chord(&self, prefix: ChordPrefix, key: KeyEvent) -> Option<Action> {
+        self.lookup(KeyMode::Normal, Some(prefix), KeyPress::from_event(key))
+            .or_else(|| Some(map_chord(prefix, key)).filter(|action| *action != Action::None))
+    }
+
+    /// The chords that trigger `action` in `mode` once the user's bindings
+    /// are applied: each of its `defaults` that was not rebound, then every
+    /// chord the user bound to it. Empty when the action is unbound.
+    pub fn effective_chords(
+        &self,
+        mode: KeyMode,
+        action: &Action,
+        defaults: &[KeyChord],
+        leader_key: char,
+    ) -> Vec<KeyChord> {
+        let mut chords = Vec::new();
+        for &default in defaults {
+            // With a `g` chord bound, a lone `g` waits and `gg` jumps instead.
+            let chord = if mode == KeyMode::Normal
+                && default.prefix.is_none()
+                && default.key.is_plain_char('g')
+                && self.binds_prefix(ChordPrefix::G)
+            {
+                KeyChord {
+                    prefix: Some(ChordPrefix::G),
+                    key: default.key,
+                }
+            } else {
+                default
+            };
+            let prefix_kept = chord.prefix.is_none_or(|prefix| {
+                let key = KeyPress {
+                    code: KeyCode::Char(prefix.label(leader_key).chars().next().unwrap_or(' ')),
+                    modifiers: KeyModifiers::NONE,
+                };
+                self.lookup(mode, None, key).is_none()
+            });
+            let kept = self
+                .lookup(mode, chord.prefix, chord.key)
+                .is_none_or(|bound| bound == *action);
+            if prefix_kept && kept && !chords.contains(&chord) {
+                chords.push(chord);
+            }
+        }
+        for binding in &self.bindings {
+            let effective = self.lookup(mode, binding.chord.prefix, binding.chord.key);
+            if binding.mode == mode
+                && effective.as_ref() == Some(action)
+                && !chords.contains(&binding.chord)
+            {
+                chords.push(binding.chord);
+            }
+        }
+        chords
+    }
+
+    fn normal_override(&self, key: KeyEvent, mode: InputMode) -> Option<Action> {
+        let mode = KeyMode::for_input_mode(mode)?;
+        let press = KeyPress::from_event(key);
+        if let Some(action) = self.lookup(mode, None, press) {
+            return Some(action);
+        }
+        // A lone `g` is `go_to_top`; it only waits for a second key once a
+        // `g` chord is bound, and `gg` then takes over `go_to_top`.
+        (mode == KeyMode::Normal && press.is_plain_char('g') && self.binds_prefix(ChordPrefix::G))
+            .then_some(Action::PendingChord(ChordPrefix::G))
+    }
+
+    fn lookup(&self, mode: KeyMode, prefix: Option<ChordPrefix>, key: KeyPress) -> Option<Action> {
+        self.bindings
+            .iter()
+            .rev()
+            .find(|binding| {
+                binding.mode == mode && binding.chord.prefix == prefix && binding.chord.key == key
+            })
+            .map(|binding| binding.action.clone())
+    }
+
+    fn binds_prefix(&self, prefix: ChordPrefix) -> bool {
+        self.bindings
+            .iter()
+            .any(|binding| binding.chord.prefix == Some(prefix))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DEFAULT_LEADER_KEY;
+
+    fn key(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    fn binding(mode: KeyMode, chord: &str, action: &str) -> KeyBinding {
+        KeyBinding {
+            mode,
+            chord: chord.parse().expect("valid chord"),
+            action_name: action.to_string(),
+            action: action_from_name(action).expect("known action"),
+        }
+    }
+
+    #[test]
+    fn should_parse_plain_special_and_modified_keys() {
+        let parse = |s: &str| s.parse::<KeyPress>().expect("valid key");
+        assert_eq!(parse("j"), KeyPress::from_event(key('j')));
+        assert_eq!(
+            parse("<C-n>"),
+            KeyPress::from_event(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            parse("<A-Enter>"),
+            KeyPress::from_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::ALT))
+        );
+        assert_eq!(
+            parse("<S-Tab>"),
+            KeyPress::from_event(KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT))
+        );
+        // Shift is folded into the character either way.
+        assert_eq!(parse("<S-g>"), parse("G"));
+        assert_eq!(
+            parse("G"),
+            KeyPress::from_event(KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT))
+        );
+        assert_eq!(parse("<Space>"), KeyPress::from_event(key(' ')));
+    }
+
+    #[test]
+    fn should_parse_prefixed_chords() {
+        let chord = |s: &str| s.parse::<KeyChord>().expect("valid chord");
+        assert_eq!(chord("<leader>e").prefix, Some(ChordPrefix::Leader));
+        assert_eq!(chord("<Leader><C-e>").prefix, Some(ChordPrefix::Leader));
+        assert_eq!(chord("gt").prefix, Some(ChordPrefix::G));
+        assert_eq!(chord("zz").prefix, Some(ChordPrefix::Z));
+        assert_eq!(chord("ZQ").prefix, Some(ChordPrefix::ShiftZ));
+        assert_eq!(chord("dd").prefix, Some(ChordPrefix::D));
+        assert_eq!(chord("g").prefix, None);
+    }
+
+    #[test]
+    fn should_reject_unknown_keys_and_prefixes() {
+        assert!("xy".parse::<KeyChord>().is_err());
+        assert!("<C-nope>".parse::<KeyChord>().is_err());
+        assert!("<X-a>".parse::<KeyChord>().is_err());
+        assert!("gxy".parse::<KeyChord>().is_err());
+    }
+
+    #[test]
+    fn should_round_trip_chord_labels() {
+        for raw in [
+            "j",
+            "G",
+            "<C-n>",
+            "<A-Enter>",
+            "<S-Tab>",
+            "gt",
+            "dd",
+            "<Space>",
+        ] {
+            let chord: KeyChord = raw.parse().expect("valid chord");
+            assert_eq!(chord.label(DEFAULT_LEADER_KEY), raw);
+        }
+        let leader: KeyChord = "<leader>e".parse().expect("valid chord");
+        assert_eq!(leader.label(','), ",e");
+    }
+
+    #[test]
+    fn should_prefer_user_bindings_and_fall_back_to_defaults() {
+        let keys = KeyBindings::new(vec![
+            binding(KeyMode::Normal, "<C-n>", "next_file"),
+            binding(KeyMode::Normal, "q", "none"),
+            binding(KeyMode::Visual, "x", "add_range_comment"),
+        ]);
+        let ctrl_n = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL);
+
+        assert_eq!(
+            keys.map_key(ctrl_n, InputMode::Normal, DEFAULT_LEADER_KEY),
+            Action::NextFile
+        );
+        assert_eq!(
+            keys.map_key(key('q'), InputMode::Normal, DEFAULT_LEADER_KEY),
+            Action::None
+        );
+        assert_eq!(
+            keys.map_key(key('j'), InputMode::Normal, DEFAULT_LEADER_KEY),
+            Action::CursorDown(1)
+        );
+        assert_eq!(
+            keys.map_key(key('x'), InputMode::VisualSelect, DEFAULT_LEADER_KEY),
+            Action::AddRangeComment
+        );
+        // Bindings are per mode.
+        assert_eq!(
+            keys.map_key(key('x'), InputMode::Normal, DEFAULT_LEADER_KEY),
+            Action::None
+        );
+        assert_eq!(
+            keys.map_file_tree_key(ctrl_n, DEFAULT_LEADER_KEY),
+            Action::NextFile
+        );
+    }
+
+    #[test]
+    fn should_resolve_user_and_default_chords() {
+        let keys = KeyBindings::new(vec![
+            binding(KeyMode::Normal, "<leader>t", "toggle_file_list"),
+            binding(KeyMode::Normal, "dd", "none"),
+        ]);
+
+        assert_eq!(
+            keys.map_chord(ChordPrefix::Leader, key('t')),
+            Some(Action::ToggleFileList)
+        );
+        assert_eq!(
+            keys.map_chord(ChordPrefix::Leader, key('e')),
+            Some(Action::ToggleFileList)
+        );
+        assert_eq!(keys.map_chord(ChordPrefix::D, key('d')), Some(Action::None));
+        assert_eq!(keys.map_chord(ChordPrefix::D, key('j')), None);
+        assert_eq!(
+            keys.map_chord(ChordPrefix::Z, key('z')),
+            Some(Action::CenterCursor)
+        );
+    }
+
+    #[test]
+    fn should_list_the_chords_that_trigger_an_action() {
+        let chords = |raw: &[&str]| -> Vec<KeyChord> {
+            raw.iter()
+                .map(|c| c.parse().expect("valid chord"))
+                .collect()
+        };
+        let keys = KeyBindings::new(vec![
+            binding(KeyMode::Normal, "j", "none"),
+            binding(KeyMode::Normal, "<C-n>", "cursor_down"),
+            binding(KeyMode::Normal, ";", "quit"),
+            binding(KeyMode::Normal, "gt", "next_hunk"),
+        ]);
+        let effective = |action: Action, defaults: &[&str]| {
+            keys.effective_chords(KeyMode::Normal, &action, &chords(defaults), ';')
+        };
+
+        assert_eq!(effective(Action::CursorDown(1), &["j"]), chords(&["<C-n>"]));
+        assert_eq!(effective(Action::CursorUp(1), &["k"]), chords(&["k"]));
+        // The leader now quits, so its chords are out of reach.
+        assert_eq!(
+            effective(Action::ToggleFileList, &["<leader>e"]),
+            Vec::new()
+        );
+        assert_eq!(effective(Action::GoToTop, &["g"]), chords(&["gg"]));
+        assert_eq!(effective(Action::NextHunk, &["]"]), chords(&["]", "gt"]));
+        // Other modes keep their defaults.
+        assert_eq!(
+            keys.effective_chords(
+                KeyMode::Visual,
+                &Action::CursorDown(1),
+                &chords(&["j"]),
+                ';'
+            ),
+            chords(&["j"])
+        );
+    }
+
+    #[test]
+    fn should_only_make_g_a_prefix_once_a_g_chord_is_bound() {
+        let defaults = KeyBindings::default();
+        assert_eq!(
+            defaults.map_key(key('g'), InputMode::Normal, DEFAULT_LEADER_KEY),
+            Action::GoToTop
+        );
+
+        let keys = KeyBindings::new(vec![binding(KeyMode::Normal, "gt", "next_hunk")]);
+        assert_eq!(
+            keys.map_key(key('g'), InputMode::Normal, DEFAULT_LEADER_KEY),
+            Action::PendingChord(ChordPrefix::G)
+        );
+        assert_eq!(
+            keys.map_chord(ChordPrefix::G, key('t')),
+            Some(Action::NextHunk)
+        );
+        assert_eq!(
+            keys.map_chord(ChordPrefix::G, key('g')),
+            Some(Action::GoToTop)
+        );
+    }
+}
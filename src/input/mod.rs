@@ -1,8 +1,10 @@
 pub mod handler;
 pub mod keybindings;
+pub mod keymap;
 pub mod mode;
 
 pub use keybindings::{
-    Action, map_file_tree_mode, map_file_tree_prompt_mode, map_key_to_action,
+    Action, ChordPrefix, map_file_tree_mode, map_file_tree_prompt_mode, map_key_to_action,
     map_target_filter_mode,
 };
+pub use keymap::KeyBindings;
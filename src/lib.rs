@@ -2,12 +2,15 @@ pub mod app;
 pub mod cli;
 pub mod comment_vim;
 pub mod config;
+pub mod control;
+pub mod diagnostics;
 pub mod editor;
 pub mod error;
 pub mod forge;
 pub mod handler;
 pub mod hash;
 pub mod input;
+pub mod mcp;
 pub mod model;
 pub mod output;
 pub mod persistence;
@@ -15,6 +18,7 @@ pub mod process;
 pub mod profile;
 pub mod review_cli;
 pub mod review_store;
+pub mod review_watch;
 pub mod slug;
 pub mod syntax;
 pub mod terminal_state;
@@ -13,20 +13,18 @@ use tuicr::app::{self, App, AppStartupOptions, FocusedPanel, InputMode};
 use tuicr::cli::parse_cli_args;
 use tuicr::editor::{EditorCommand, EditorError, EditorLaunch, EditorSurface, EditorTarget};
 use tuicr::handler::{
-    handle_command_action, handle_comment_action, handle_comment_navigator_action,
-    handle_commit_select_action, handle_commit_selector_action, handle_confirm_action,
-    handle_diff_action, handle_file_list_action, handle_help_action, handle_mouse_event,
-    handle_search_action, handle_submit_action_picker_action, handle_submit_confirm_action,
-    handle_submit_resolver_action, handle_summary_action, handle_visual_action,
-};
-use tuicr::input::{
-    Action, map_file_tree_mode, map_file_tree_prompt_mode, map_key_to_action,
-    map_target_filter_mode,
+    handle_check_log_action, handle_command_action, handle_comment_action,
+    handle_comment_navigator_action, handle_commit_select_action, handle_commit_selector_action,
+    handle_confirm_action, handle_diff_action, handle_file_list_action, handle_help_action,
+    handle_mouse_event, handle_search_action, handle_submit_action_picker_action,
+    handle_submit_confirm_action, handle_submit_resolver_action, handle_summary_action,
+    handle_visual_action, resolve_key_action,
 };
+use tuicr::input::{Action, ChordPrefix};
 use tuicr::terminal_state::{TerminalFeatures, TerminalSession};
 use tuicr::theme::resolve_theme_with_config;
 use tuicr::vcs::{DiffWhitespaceMode, GitBackendPreference};
-use tuicr::{config, handler, profile, ui, update};
+use tuicr::{config, profile, ui, update};
 
 /// Timeout for the "press Ctrl+C again to exit" feature
 const CTRL_C_EXIT_TIMEOUT: Duration = Duration::from_secs(2);
@@ -71,7 +69,15 @@ fn main() -> anyhow::Result<()> {
         return Ok(());
     }
     if let Some(review_command) = cli_args.review_command.take() {
-        tuicr::review_cli::run(review_command)?;
+        return match tuicr::review_cli::run(review_command) {
+            Err(tuicr::error::TuicrError::SessionClosed(_)) => {
+                std::process::exit(tuicr::review_watch::SESSION_CLOSED_EXIT_CODE)
+            }
+            result => Ok(result?),
+        };
+    }
+    if cli_args.mcp_command {
+        tuicr::mcp::run()?;
         return Ok(());
     }
 
@@ -202,6 +208,7 @@ fn main() -> anyhow::Result<()> {
                 path_filter: cli_args.path_filter.as_deref(),
                 file_path: cli_args.file_path.as_deref(),
                 all_files: cli_args.all_files,
+                patch: cli_args.patch.as_deref(),
                 show_pr_checks: config_outcome
                     .config
                     .as_ref()
@@ -220,6 +227,11 @@ fn main() -> anyhow::Result<()> {
                     .repo_url
                     .as_deref()
                     .and_then(tuicr::forge::parse_any_remote_url),
+                forge_config: config_outcome
+                    .config
+                    .as_ref()
+                    .and_then(|cfg| cfg.forge.clone())
+                    .unwrap_or_default(),
             },
         )
     }) {
@@ -233,6 +245,9 @@ fn main() -> anyhow::Result<()> {
                 if let Some(leader) = cfg.leader {
                     app.leader_key = leader;
                 }
+                if let Some(keys) = cfg.keys.clone() {
+                    app.key_bindings = keys;
+                }
                 app.comment_vim_enabled = cfg.comment_vim.unwrap_or(false);
                 if let Some(w) = cfg.comment_tab_width {
                     app.comment_tab_width = w;
@@ -275,6 +290,18 @@ fn main() -> anyhow::Result<()> {
     app.commit_order = commit_order;
     app.commit_selection_start = commit_selection;
 
+    // Bind before the session file is written so the active-session entry
+    // advertises the socket from the start. Off by default where there are
+    // no Unix domain sockets; an explicit `true` there still warns.
+    let control_socket = config_outcome
+        .config
+        .as_ref()
+        .and_then(|cfg| cfg.control_socket)
+        .unwrap_or(cfg!(unix));
+    if control_socket && let Err(e) = app.start_control_socket() {
+        startup_warnings.push(format!("Failed to open the control socket: {e}"));
+    }
+
     if let Err(e) = app.ensure_ephemeral_session_file() {
         startup_warnings.push(format!("Failed to initialize review session file: {e}"));
     }
@@ -341,6 +368,7 @@ fn main() -> anyhow::Result<()> {
             app.toggle_single_file_view();
         }
         app.export = cfg.resolved_export();
+        app.diagnostics_command = cfg.diagnostics_command.clone();
         if cfg.cursor_line == Some(false) {
             app.cursor_line_highlight = false;
         }
@@ -358,6 +386,14 @@ fn main() -> anyhow::Result<()> {
         }
     }
 
+    if let Some(format) = cli_args.export_format {
+        app.export.format = Some(format);
+    }
+
+    if let Some(path) = cli_args.diagnostics.as_deref() {
+        app.load_diagnostics_file(std::path::Path::new(path));
+    }
+
     // On narrow terminals, start with only the diff panel visible.
     if let Ok((width, _)) = crossterm::terminal::size()
         && width < MIN_WIDTH_FOR_FILE_LIST
@@ -370,14 +406,8 @@ fn main() -> anyhow::Result<()> {
         app.set_warning(message.clone());
     }
 
-    // Track pending z command for zz centering
-    let mut pending_z = false;
-    // Track pending Z command for ZZ export+quit / ZQ quit
-    let mut pending_shift_z = false;
-    // Track pending d command for dd delete
-    let mut pending_d = false;
-    // Track pending leader command for leader-prefixed actions.
-    let mut pending_leader = false;
+    // First key of a pending two-key chord (`zz`, `ZZ`, `dd`, `<leader>e`, ...).
+    let mut pending_chord: Option<ChordPrefix> = None;
     // Track pending Ctrl+C for "press twice to exit" (with timestamp for 2s timeout)
     let mut pending_ctrl_c: Option<Instant> = None;
     // Only re-render when state actually changed; the diff renderer rebuilds
@@ -418,6 +448,14 @@ fn main() -> anyhow::Result<()> {
         app.poll_pr_range_reload_events();
         app.poll_pr_threads_events();
         app.poll_pr_submit_events();
+        app.poll_pr_thread_resolve_events();
+        app.poll_pr_viewed_sync_events();
+        app.poll_pr_pending_review_events();
+        app.poll_pr_action_events();
+        app.poll_check_log_events();
+        app.poll_export_send_events();
+        app.poll_diagnostics_events();
+        needs_redraw |= app.poll_control_requests();
         needs_redraw |= app.poll_editor_launches();
         needs_redraw |= app.poll_persisted_session_changes();
         needs_redraw |= app.poll_diff_watch_changes();
@@ -510,184 +548,22 @@ fn main() -> anyhow::Result<()> {
                         app.message = None;
                     }
 
-                    // Handle pending z command for zz/zt/zb viewport positioning
-                    if pending_z {
-                        pending_z = false;
-                        match key.code {
-                            crossterm::event::KeyCode::Char('z') => {
-                                app.center_cursor();
-                                continue;
-                            }
-                            crossterm::event::KeyCode::Char('t') => {
-                                app.cursor_to_top();
-                                continue;
-                            }
-                            crossterm::event::KeyCode::Char('b') => {
-                                app.cursor_to_bottom();
-                                continue;
-                            }
-                            _ => {} // Fall through to normal handling
-                        }
-                    }
-
-                    // Handle pending Z command for ZZ (export+quit) / ZQ (quit)
-                    if pending_shift_z {
-                        pending_shift_z = false;
-                        match key.code {
-                            crossterm::event::KeyCode::Char('Z') => {
-                                // ZZ: save session, export, and quit (same as :wq)
-                                let _ = app.save_current_session_merging_external();
-                                if app.session.has_comments() {
-                                    handler::handle_export_and_quit(&mut app);
-                                } else {
-                                    app.should_quit = true;
-                                }
-                                continue;
-                            }
-                            crossterm::event::KeyCode::Char('Q') => {
-                                // ZQ: quit without exporting (same as q)
-                                app.should_quit = true;
-                                continue;
-                            }
-                            _ => {} // Fall through to normal handling
-                        }
-                    }
-
-                    // Handle pending d command for dd delete comment
-                    if pending_d {
-                        pending_d = false;
-                        if key.code == crossterm::event::KeyCode::Char('d') {
-                            if app.cursor_on_locked_comment() {
-                                let forge = app.forge_display_name();
-                                app.set_message(format!(
-                                    "Comment already pushed to {forge} — read only in tuicr"
-                                ));
-                            } else if !app.delete_comment_at_cursor() {
-                                if app.cursor_on_remote_thread() {
-                                    let forge = app.forge_display_name();
-                                    app.set_message(format!(
-                                        "{forge} comment — read only in tuicr"
-                                    ));
-                                } else {
-                                    app.set_message("No comment at cursor");
-                                }
-                            }
-                            continue;
-                        }
-                        // Otherwise fall through to normal handling
-                    }
-
-                    // Handle pending leader command for panel focus, file list toggle, and review comments.
-                    if pending_leader {
-                        pending_leader = false;
-                        match key.code {
-                            crossterm::event::KeyCode::Char('e') => {
-                                app.toggle_file_list();
-                                continue;
-                            }
-                            crossterm::event::KeyCode::Char('h') => {
-                                if app.show_file_list {
-                                    app.focused_panel = app::FocusedPanel::FileList;
-                                }
-                                continue;
-                            }
-                            crossterm::event::KeyCode::Char('l') => {
-                                app.focused_panel = app::FocusedPanel::Diff;
-                                continue;
-                            }
-                            crossterm::event::KeyCode::Char('k') => {
-                                if app.focused_panel == app::FocusedPanel::Comments {
-                                    app.focused_panel = app::FocusedPanel::FileList;
-                                } else if app.has_inline_commit_selector() {
-                                    app.focused_panel = app::FocusedPanel::CommitSelector;
-                                }
-                                continue;
-                            }
-                            crossterm::event::KeyCode::Char('j') => {
-                                if app.focused_panel == app::FocusedPanel::FileList
-                                    && app.has_comment_navigator_items()
-                                {
-                                    app.focused_panel = app::FocusedPanel::Comments;
-                                } else {
-                                    app.focused_panel = app::FocusedPanel::Diff;
-                                }
-                                continue;
-                            }
-                            crossterm::event::KeyCode::Char('c') => {
-                                app.enter_review_comment_mode();
-                                continue;
-                            }
-                            // `<leader>s` toggles the commit selector pane.
-                            crossterm::event::KeyCode::Char('s') => {
-                                app.toggle_commit_selector();
-                                continue;
-                            }
-                            crossterm::event::KeyCode::Char('f') => {
-                                app.toggle_single_file_view();
-                                continue;
-                            }
-                            _ => {}
-                        }
-                        // Otherwise fall through to normal handling
-                    }
-
                     // Vim modal editing: route comment-box keys to the edtui
-                    // overlay (app-level keys handled inside).
+                    // overlay (app-level keys handled inside). No chord can
+                    // start from the overlay, so drop any left over.
                     if app.input_mode == InputMode::Comment && app.comment_vim_enabled {
+                        pending_chord = None;
                         app.ensure_comment_vim_editor();
                         if handle_comment_vim_key(&mut app, key) {
                             continue;
                         }
                     }
 
-                    // Editing the PR-tab filter is a sub-state of CommitSelect;
-                    // route through the filter-specific key map so typed
-                    // characters update the filter buffer rather than driving
-                    // commit-list navigation.
-                    let mut action = if app.input_mode == InputMode::CommitSelect
-                        && app.pr_filter_editing()
-                    {
-                        map_target_filter_mode(key)
-                    } else if app.input_mode == InputMode::Normal && app.file_tree_prompt_editing()
-                    {
-                        // An open file-tree prompt (`i`/`e`/`/`) captures all
-                        // input until Enter/Esc, like the PR filter above.
-                        map_file_tree_prompt_mode(key)
-                    } else if app.input_mode == InputMode::Normal
-                        && app.focused_panel == FocusedPanel::FileList
-                    {
-                        // The tree claims i/e/I/E and `/` for filtering; the
-                        // diff keeps its own meanings for those keys.
-                        map_file_tree_mode(key, app.leader_key)
-                    } else {
-                        map_key_to_action(key, app.input_mode, app.leader_key)
+                    let Some(mut action) = resolve_key_action(&mut app, &mut pending_chord, key)
+                    else {
+                        continue;
                     };
 
-                    // Handle pending command setters (these work in any mode)
-                    match action {
-                        Action::PendingZCommand => {
-                            pending_z = true;
-                            app.pending_count = None;
-                            continue;
-                        }
-                        Action::PendingShiftZCommand => {
-                            pending_shift_z = true;
-                            app.pending_count = None;
-                            continue;
-                        }
-                        Action::PendingDCommand => {
-                            pending_d = true;
-                            app.pending_count = None;
-                            continue;
-                        }
-                        Action::PendingLeaderCommand => {
-                            pending_leader = true;
-                            app.pending_count = None;
-                            continue;
-                        }
-                        _ => {}
-                    }
-
                     // Vim-style {count}{motion} (Normal mode only): digits accumulate
                     // into `pending_count`, then a following motion either scales its
                     // inner count parameter or is dispatched repeatedly. `{count}G`
@@ -834,6 +710,7 @@ fn dispatch_action(app: &mut App, action: Action) {
     match app.input_mode {
         InputMode::Help | InputMode::MessageDetails => handle_help_action(app, action),
         InputMode::Summary => handle_summary_action(app, action),
+        InputMode::CheckLog => handle_check_log_action(app, action),
         InputMode::Command => handle_command_action(app, action),
         InputMode::Search => handle_search_action(app, action),
         InputMode::Comment => handle_comment_action(app, action),
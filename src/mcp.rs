@@ -0,0 +1,871 @@
+//! `tuicr mcp`: a Model Context Protocol server over stdio.
+//!
+//! Coding agents attach to persisted review sessions through this instead
+//! of shelling out to `tuicr review` and parsing its JSON. Each message is
+//! one line of JSON-RPC 2.0 on stdin, each reply one line on stdout; stderr
+//! is left for diagnostics.
+//!
+//! Everything goes through [`ReviewStore`], so the TUI and the server can
+//! work on the same session: the store locks and re-reads the session file
+//! around each write, and the TUI picks external comments up the same way
+//! it does for `tuicr review add`.
+//!
+//! Sessions are also listed as resources, `tuicr://session/<slug>` for the
+//! comments and `tuicr://session/<slug>/diff` for the diff under review.
+
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use serde_json::{Value, json};
+
+use crate::app::{App, DiffSource, create_forge_backend};
+use crate::config::{self, AppConfig};
+use crate::error::{Result, TuicrError};
+use crate::forge::traits::{ForgeBackend, PullRequestDetails, PullRequestTarget};
+use crate::model::{CommentType, DiffFile, FilePatch, LineOrigin, ReviewSession};
+use crate::output::json::build_review_export;
+use crate::persistence::storage::{normalize_path_for_comparison, slug_for_session};
+use crate::review_cli::{
+    CommentOutput, SessionSummaryOutput, add_request_from_json, diff_source_for_session,
+    find_comment_output, resolve_cli_author, resolve_session_ref,
+};
+use crate::review_store::{AddCommentRequest, ReviewStore, SessionRef};
+use crate::slug::short_sha;
+use crate::syntax::SyntaxHighlighter;
+use crate::vcs::diff_parser::parse_file_patches;
+use crate::vcs::{DiffWhitespaceMode, GitBackendPreference, detect_vcs};
+
+/// Protocol revisions this server speaks, newest first. A client asking
+/// for one of them gets it back; anything else gets the newest.
+const PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+const RESOURCE_PREFIX: &str = "tuicr://session/";
+const DIFF_RESOURCE_SUFFIX: &str = "/diff";
+
+// JSON-RPC error codes.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Serve MCP on stdin/stdout until the client closes stdin.
+pub fn run() -> Result<()> {
+    let config = config::load_config()
+        .ok()
+        .and_then(|outcome| outcome.config)
+        .unwrap_or_default();
+    let mut server = McpServer::new(ReviewStore::new(), config);
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+    server.serve(stdin.lock(), &mut stdout)
+}
+
+pub struct McpServer {
+    store: ReviewStore,
+    config: AppConfig,
+    /// `clientInfo.name` from `initialize`, the author of comments that do
+    /// not name one.
+    client_name: Option<String>,
+}
+
+/// A JSON-RPC error reply.
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn invalid_params(message: impl Into<String>) -> Self {
+        Self {
+            code: INVALID_PARAMS,
+            message: message.into(),
+        }
+    }
+}
+
+impl McpServer {
+    pub fn new(store: ReviewStore, config: AppConfig) -> Self {
+        Self {
+            store,
+            config,
+            client_name: None,
+        }
+    }
+
+    /// Answer each line of `input` on `out`. Blank lines are skipped and
+    /// notifications get no reply.
+    pub fn serve(&mut self, input: impl BufRead, out: &mut impl Write) -> Result<()> {
+        for line in input.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(reply) = self.handle_line(&line) {
+                serde_json::to_writer(&mut *out, &reply)?;
+                writeln!(out)?;
+                out.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_line(&mut self, line: &str) -> Option<Value> {
+        let message: Value = match serde_json::from_str(line) {
+            Ok(message) => message,
+            Err(e) => {
+                return Some(error_reply(
+                    Value::Null,
+                    RpcError {
+                        code: PARSE_ERROR,
+                        message: format!("invalid JSON: {e}"),
+                    },
+                ));
+            }
+        };
+        // Batches were dropped from the protocol in 2025-06-18, and no
+        // client sends them to a stdio server.
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            return message.get("id").map(|id| {
+                error_reply(
+                    id.clone(),
+                    RpcError {
+                        code: INVALID_REQUEST,
+                        message: "expected a JSON-RPC request".to_string(),
+                    },
+                )
+            });
+        };
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        let result = self.dispatch(method, &params);
+        // Requests carry an id; notifications never get a reply, not even
+        // an error.
+        let id = message.get("id")?.clone();
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(e) => error_reply(id, e),
+        })
+    }
+
+    fn dispatch(&mut self, method: &str, params: &Value) -> std::result::Result<Value, RpcError> {
+        match method {
+            "initialize" => Ok(self.initialize(params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+            "tools/call" => self.call_tool(params),
+            "resources/list" => Ok(self.list_resources()),
+            "resources/templates/list" => Ok(json!({ "resourceTemplates": resource_templates() })),
+            "resources/read" => self.read_resource(params),
+            _ if method.starts_with("notifications/") => Ok(Value::Null),
+            _ => Err(RpcError {
+                code: METHOD_NOT_FOUND,
+                message: format!("unknown method {method}"),
+            }),
+        }
+    }
+
+    fn initialize(&mut self, params: &Value) -> Value {
+        self.client_name = params["clientInfo"]["name"]
+            .as_str()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string);
+        let requested = params["protocolVersion"].as_str().unwrap_or_default();
+        let version = PROTOCOL_VERSIONS
+            .iter()
+            .find(|version| **version == requested)
+            .unwrap_or(&PROTOCOL_VERSIONS[0]);
+        json!({
+            "protocolVersion": version,
+            "capabilities": { "tools": {}, "resources": {} },
+            "serverInfo": { "name": "tuicr", "version": env!("CARGO_PKG_VERSION") },
+            "instructions": "Review sessions are what a human reviews in the tuicr TUI. \
+                Call list_sessions, prefer the one marked active, then read its comments \
+                with get_comments. Only add comments when asked to review.",
+        })
+    }
+
+    /// Run a tool. Failures inside the tool come back as an `isError`
+    /// result, as MCP asks, so the model sees them; only a call that names
+    /// no known tool is a protocol error.
+    fn call_tool(&self, params: &Value) -> std::result::Result<Value, RpcError> {
+        let name = params["name"]
+            .as_str()
+            .ok_or_else(|| RpcError::invalid_params("tools/call needs a tool name"))?;
+        let args = params
+            .get("arguments")
+            .cloned()
+            .unwrap_or_else(|| json!({}));
+        let outcome = match name {
+            "list_sessions" => self.list_sessions(&args),
+            "get_comments" => self.get_comments(&args),
+            "add_comment" => self.add_comment(&args),
+            "resolve_comments" => self.resolve_comments(&args),
+            "get_diff" => self.get_diff(&args).map(Value::String),
+            _ => return Err(RpcError::invalid_params(format!("unknown tool {name}"))),
+        };
+        Ok(match outcome {
+            Ok(Value::String(text)) => tool_text(text, false),
+            Ok(value) => tool_text(pretty(&value), false),
+            Err(e) => tool_text(e.to_string(), true),
+        })
+    }
+
+    fn list_sessions(&self, args: &Value) -> Result<Value> {
+        let summaries = if args["all"].as_bool().unwrap_or(false) {
+            self.store.list_all_sessions()?
+        } else {
+            self.store.list_sessions_for_repo(repo_arg(args))?
+        };
+        let output: Vec<_> = summaries
+            .into_iter()
+            .map(SessionSummaryOutput::from)
+            .collect();
+        Ok(serde_json::to_value(output)?)
+    }
+
+    /// The session as the JSON export document, with each comment's diff
+    /// lines. A diff that cannot be loaded leaves `context` empty rather
+    /// than failing the read.
+    fn get_comments(&self, args: &Value) -> Result<Value> {
+        let (_, session) = self.load_session(args)?;
+        let (diff_files, diff_error) = match self.load_diff(&session) {
+            Ok(files) => (files, None),
+            Err(e) => (Vec::new(), Some(e.to_string())),
+        };
+        let slug = slug_for_session(&session).ok().map(|slug| slug.to_string());
+        let comment_types = App::resolve_comment_types(self.config.comment_types.clone());
+        let export =
+            build_review_export(&session, &diff_files, None, &comment_types, slug.as_deref());
+        let mut value = serde_json::to_value(export)?;
+        if let Some(error) = diff_error {
+            value["diff_error"] = Value::String(error);
+        }
+        Ok(value)
+    }
+
+    fn add_comment(&self, args: &Value) -> Result<Value> {
+        let session_ref = self.session_ref(args)?;
+        let parts = add_request_from_json(&args.to_string())?;
+        let author = resolve_cli_author(parts.username.or_else(|| self.client_name.clone()));
+        let comment = self.store.add_comment(
+            &session_ref,
+            AddCommentRequest {
+                target: parts.target.clone(),
+                content: parts.content,
+                comment_type: CommentType::from_id(&parts.comment_type),
+                author,
+                commit_id: None,
+            },
+        )?;
+        Ok(serde_json::to_value(CommentOutput::from_target(
+            &parts.target,
+            &comment,
+        ))?)
+    }
+
+    fn resolve_comments(&self, args: &Value) -> Result<Value> {
+        let session_ref = self.session_ref(args)?;
+        let ids: Vec<&str> = args["ids"]
+            .as_array()
+            .map(|ids| ids.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        if ids.is_empty() {
+            return Err(TuicrError::InvalidInput(
+                "ids must list at least one comment id".to_string(),
+            ));
+        }
+        let resolved = args["resolved"].as_bool().unwrap_or(true);
+        for id in &ids {
+            self.store
+                .set_comment_resolved(&session_ref, id, resolved)?;
+        }
+        let session = self.store.get_review(&session_ref)?;
+        let updated = ids
+            .iter()
+            .map(|id| find_comment_output(&session, id))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(serde_json::to_value(updated)?)
+    }
+
+    fn get_diff(&self, args: &Value) -> Result<String> {
+        let (_, session) = self.load_session(args)?;
+        Ok(unified_diff(&self.load_diff(&session)?))
+    }
+
+    fn list_resources(&self) -> Value {
+        let summaries = self.store.list_sessions_for_repo(".").unwrap_or_default();
+        let resources: Vec<Value> = summaries
+            .iter()
+            .flat_map(|summary| {
+                let description = format!(
+                    "{} comments on {}{}",
+                    summary.comment_count,
+                    summary.anchor,
+                    if summary.active {
+                        " (open in tuicr)"
+                    } else {
+                        ""
+                    }
+                );
+                [
+                    json!({
+                        "uri": format!("{RESOURCE_PREFIX}{}", summary.slug),
+                        "name": summary.slug,
+                        "description": description,
+                        "mimeType": "application/json",
+                    }),
+                    json!({
+                        "uri": format!("{RESOURCE_PREFIX}{}{DIFF_RESOURCE_SUFFIX}", summary.slug),
+                        "name": format!("{} diff", summary.slug),
+                        "mimeType": "text/x-diff",
+                    }),
+                ]
+            })
+            .collect();
+        json!({ "resources": resources })
+    }
+
+    fn read_resource(&self, params: &Value) -> std::result::Result<Value, RpcError> {
+        let uri = params["uri"]
+            .as_str()
+            .ok_or_else(|| RpcError::invalid_params("resources/read needs a uri"))?;
+        let slug = uri
+            .strip_prefix(RESOURCE_PREFIX)
+            .ok_or_else(|| RpcError::invalid_params(format!("unknown resource {uri}")))?;
+        let (text, mime_type) = match slug.strip_suffix(DIFF_RESOURCE_SUFFIX) {
+            Some(slug) => (self.get_diff(&json!({ "session": slug })), "text/x-diff"),
+            None => (
+                self.get_comments(&json!({ "session": slug }))
+                    .map(|value| pretty(&value)),
+                "application/json",
+            ),
+        };
+        let text = text.map_err(|e| RpcError::invalid_params(e.to_string()))?;
+        Ok(json!({
+            "contents": [{ "uri": uri, "mimeType": mime_type, "text": text }],
+        }))
+    }
+
+    fn session_ref(&self, args: &Value) -> Result<SessionRef> {
+        let session = args["session"].as_str().ok_or_else(|| {
+            TuicrError::InvalidInput("session is required; call list_sessions".to_string())
+        })?;
+        resolve_session_ref(&self.store, &repo_arg(args), session)
+    }
+
+    fn load_session(&self, args: &Value) -> Result<(SessionRef, ReviewSession)> {
+        let session_ref = self.session_ref(args)?;
+        let session = self.store.get_review(&session_ref)?;
+        Ok((session_ref, session))
+    }
+
+    /// Load the diff a session reviews. Local sessions are read from the
+    /// checkout the server runs in, which must be the session's; PR
+    /// sessions are fetched from the forge at the head the session was
+    /// opened on, which its comment line numbers refer to.
+    fn load_diff(&self, session: &ReviewSession) -> Result<Vec<DiffFile>> {
+        let highlighter = SyntaxHighlighter::default();
+        let source = diff_source_for_session(session);
+        if let DiffSource::PullRequest(pr) = &source {
+            let forge_config = self.config.forge.clone().unwrap_or_default();
+            let backend =
+                create_forge_backend(&pr.key.repository, None, false, false, &forge_config);
+            let details = backend.get_pull_request(PullRequestTarget::with_repository(
+                pr.key.repository.clone(),
+                pr.key.number,
+                pr.key.number.to_string(),
+            ))?;
+            let patches = pull_request_patches_at(backend.as_ref(), &details, &pr.key.head_sha)?;
+            return parse_file_patches(patches, &highlighter);
+        }
+
+        let whitespace_mode = if self.config.ignore_whitespace.unwrap_or(false) {
+            DiffWhitespaceMode::IgnoreAll
+        } else {
+            DiffWhitespaceMode::Normal
+        };
+        let vcs = detect_vcs(
+            GitBackendPreference::from_config(self.config.backend.as_deref()),
+            whitespace_mode,
+        )?;
+        let root = vcs.info().root_path.clone();
+        if !same_path(&root, &session.repo_path) {
+            return Err(TuicrError::InvalidInput(format!(
+                "the session reviews {}; start `tuicr mcp` in that checkout to read its diff",
+                session.repo_path.display()
+            )));
+        }
+        App::fetch_diff_files_for_source(vcs.as_ref(), &root, &source, &highlighter, None)
+    }
+}
+
+/// The PR's diff as of `head_sha`. After a push the forge's current diff
+/// no longer matches the session, so the reviewed range is fetched instead.
+fn pull_request_patches_at(
+    backend: &dyn ForgeBackend,
+    details: &PullRequestDetails,
+    head_sha: &str,
+) -> Result<Vec<FilePatch>> {
+    if details.head_sha == head_sha {
+        return backend.get_pull_request_diff(details);
+    }
+    backend
+        .get_pull_request_commit_range_diff(details, &details.base_sha, head_sha)
+        .map_err(|e| {
+            TuicrError::VcsCommand(format!(
+                "the session reviews {} but the pull request is now at {}, \
+                 and the reviewed diff could not be fetched: {e}",
+                short_sha(head_sha),
+                short_sha(&details.head_sha),
+            ))
+        })
+}
+
+fn repo_arg(args: &Value) -> PathBuf {
+    PathBuf::from(args["repo"].as_str().unwrap_or("."))
+}
+
+fn same_path(a: &Path, b: &Path) -> bool {
+    normalize_path_for_comparison(a) == normalize_path_for_comparison(b)
+}
+
+fn error_reply(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+fn tool_text(text: String, is_error: bool) -> Value {
+    json!({
+        "content": [{ "type": "text", "text": text }],
+        "isError": is_error,
+    })
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+}
+
+/// Render diff files as unified diff text an agent can apply. Hunk bodies
+/// are written exactly as the VCS or forge returned them; only hunks with
+/// no raw body (which never come from a real diff) are rebuilt from their
+/// display lines. Commit messages shown as pseudo-files in the TUI are left
+/// out.
+fn unified_diff(files: &[DiffFile]) -> String {
+    let mut out = String::new();
+    for file in files.iter().filter(|file| !file.is_commit_message) {
+        let path = file.file_path().display();
+        let old = file.old_path.as_deref().unwrap_or(file.file_path());
+        out.push_str(&format!("diff --git a/{} b/{path}\n", old.display()));
+        if file.is_binary {
+            out.push_str("Binary files differ\n");
+            continue;
+        }
+        match &file.old_path {
+            Some(old) => out.push_str(&format!("--- a/{}\n", old.display())),
+            None => out.push_str("--- /dev/null\n"),
+        }
+        match &file.new_path {
+            Some(new) => out.push_str(&format!("+++ b/{}\n", new.display())),
+            None => out.push_str("+++ /dev/null\n"),
+        }
+        for hunk in &file.hunks {
+            out.push_str(&hunk.header);
+            out.push('\n');
+            if let Some(raw_body) = &hunk.raw_body {
+                out.push_str(raw_body);
+                continue;
+            }
+            for line in &hunk.lines {
+                out.push(match line.origin {
+                    LineOrigin::Context => ' ',
+                    LineOrigin::Addition => '+',
+                    LineOrigin::Deletion => '-',
+                });
+                out.push_str(&line.content);
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+fn session_property() -> Value {
+    json!({
+        "type": "string",
+        "description": "Session slug from list_sessions (local or PR), or a session JSON path",
+    })
+}
+
+fn repo_property() -> Value {
+    json!({
+        "type": "string",
+        "description": "Checkout path or owner/repo used to resolve a local slug. Defaults to the server's directory.",
+    })
+}
+
+fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "list_sessions",
+            "description": "List persisted tuicr review sessions for a repo. `active` marks sessions open in the TUI right now.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "repo": repo_property(),
+                    "all": { "type": "boolean", "description": "List every session, ignoring repo" },
+                },
+            },
+            "annotations": { "readOnlyHint": true },
+        },
+        {
+            "name": "get_comments",
+            "description": "Read a session's comments with the diff lines each one covers, as tuicr's JSON export.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "session": session_property(), "repo": repo_property() },
+                "required": ["session"],
+            },
+            "annotations": { "readOnlyHint": true },
+        },
+        {
+            "name": "get_diff",
+            "description": "Read the diff a session reviews as unified diff text.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "session": session_property(), "repo": repo_property() },
+                "required": ["session"],
+            },
+            "annotations": { "readOnlyHint": true },
+        },
+        {
+            "name": "add_comment",
+            "description": "Add a local draft comment to a session. Omit file for a review comment, omit line for a file comment, add end_line for a range.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "session": session_property(),
+                    "repo": repo_property(),
+                    "content": { "type": "string" },
+                    "type": { "type": "string", "description": "Comment type id from comment_types, e.g. issue or suggestion" },
+                    "file": { "type": "string" },
+                    "line": { "type": "integer", "minimum": 1 },
+                    "end_line": { "type": "integer", "minimum": 1 },
+                    "side": { "type": "string", "enum": ["old", "new"] },
+                    "username": { "type": "string", "description": "Author shown on the comment. Defaults to the client's name." },
+                },
+                "required": ["session", "content"],
+            },
+        },
+        {
+            "name": "resolve_comments",
+            "description": "Mark comments addressed, or open again with resolved: false.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "session": session_property(),
+                    "repo": repo_property(),
+                    "ids": { "type": "array", "items": { "type": "string" } },
+                    "resolved": { "type": "boolean", "default": true },
+                },
+                "required": ["session", "ids"],
+            },
+            "annotations": { "idempotentHint": true },
+        },
+    ])
+}
+
+fn resource_templates() -> Value {
+    json!([
+        {
+            "uriTemplate": format!("{RESOURCE_PREFIX}{{slug}}"),
+            "name": "Review session",
+            "description": "A session's comments with line context",
+            "mimeType": "application/json",
+        },
+        {
+            "uriTemplate": format!("{RESOURCE_PREFIX}{{slug}}{DIFF_RESOURCE_SUFFIX}"),
+            "name": "Review session diff",
+            "mimeType": "text/x-diff",
+        },
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{FileStatus, SessionDiffSource};
+
+    fn server_with_session() -> (tempfile::TempDir, McpServer, String) {
+        let temp = tempfile::tempdir().unwrap();
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        let store = ReviewStore::with_reviews_dir(temp.path().join("reviews"));
+        let mut session = ReviewSession::new(
+            repo,
+            "abc1234".to_string(),
+            Some("main".to_string()),
+            SessionDiffSource::WorkingTree,
+        );
+        session.add_file(PathBuf::from("src/main.rs"), FileStatus::Modified, 0);
+        let session_ref = store.save_review(&session).unwrap();
+        let path = session_ref.path().display().to_string();
+        (temp, McpServer::new(store, AppConfig::default()), path)
+    }
+
+    fn request(server: &mut McpServer, id: u64, method: &str, params: Value) -> Value {
+        let line = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        server.handle_line(&line.to_string()).unwrap()
+    }
+
+    fn call(server: &mut McpServer, name: &str, arguments: Value) -> Value {
+        let reply = request(
+            server,
+            9,
+            "tools/call",
+            json!({ "name": name, "arguments": arguments }),
+        );
+        reply["result"].clone()
+    }
+
+    fn text(result: &Value) -> &str {
+        result["content"][0]["text"].as_str().unwrap()
+    }
+
+    #[test]
+    fn should_negotiate_the_protocol_and_remember_the_client() {
+        // given
+        let (_temp, mut server, _) = server_with_session();
+
+        // when
+        let reply = request(
+            &mut server,
+            1,
+            "initialize",
+            json!({ "protocolVersion": "2024-11-05", "clientInfo": { "name": "agent-x" } }),
+        );
+
+        // then
+        assert_eq!(reply["id"], 1);
+        assert_eq!(reply["result"]["protocolVersion"], "2024-11-05");
+        assert_eq!(reply["result"]["serverInfo"]["name"], "tuicr");
+        assert_eq!(server.client_name.as_deref(), Some("agent-x"));
+    }
+
+    #[test]
+    fn should_not_answer_notifications_and_reject_unknown_methods() {
+        let (_temp, mut server, _) = server_with_session();
+
+        let notification = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+        assert_eq!(server.handle_line(&notification.to_string()), None);
+
+        let reply = request(&mut server, 2, "sessions/delete", json!({}));
+        assert_eq!(reply["error"]["code"], METHOD_NOT_FOUND);
+
+        let reply = server.handle_line("{not json").unwrap();
+        assert_eq!(reply["error"]["code"], PARSE_ERROR);
+    }
+
+    #[test]
+    fn should_add_comments_as_the_client_and_read_them_back() {
+        // given
+        let (_temp, mut server, session) = server_with_session();
+        request(
+            &mut server,
+            1,
+            "initialize",
+            json!({ "clientInfo": { "name": "agent-x" } }),
+        );
+
+        // when
+        let added = call(
+            &mut server,
+            "add_comment",
+            json!({
+                "session": session,
+                "content": "Handle the empty case",
+                "type": "issue",
+                "file": "src/main.rs",
+                "line": 7,
+            }),
+        );
+        let comments = call(&mut server, "get_comments", json!({ "session": session }));
+
+        // then
+        assert_eq!(added["isError"], false);
+        let added: Value = serde_json::from_str(text(&added)).unwrap();
+        assert_eq!(added["location"], "src/main.rs:7");
+        let export: Value = serde_json::from_str(text(&comments)).unwrap();
+        let comment = &export["comments"][0];
+        assert_eq!(comment["id"], added["id"]);
+        assert_eq!(comment["author"], "agent-x");
+        assert_eq!(comment["type"], "issue");
+        // The temp checkout is not the server's, so there is no diff.
+        assert!(export["diff_error"].is_string());
+    }
+
+    #[test]
+    fn should_resolve_comments_and_report_tool_errors_in_the_result() {
+        // given
+        let (_temp, mut server, session) = server_with_session();
+        let added = call(
+            &mut server,
+            "add_comment",
+            json!({ "session": session, "content": "nit" }),
+        );
+        let id = serde_json::from_str::<Value>(text(&added)).unwrap()["id"].clone();
+
+        // when
+        let resolved = call(
+            &mut server,
+            "resolve_comments",
+            json!({ "session": session, "ids": [id] }),
+        );
+        let missing = call(
+            &mut server,
+            "resolve_comments",
+            json!({ "session": session, "ids": ["nope"] }),
+        );
+
+        // then
+        let resolved: Value = serde_json::from_str(text(&resolved)).unwrap();
+        assert_eq!(resolved[0]["resolved"], true);
+        assert_eq!(missing["isError"], true);
+        assert!(text(&missing).contains("nope"));
+    }
+
+    #[test]
+    fn should_read_a_session_resource() {
+        // given
+        let (_temp, mut server, session) = server_with_session();
+
+        // when
+        let reply = request(
+            &mut server,
+            3,
+            "resources/read",
+            json!({ "uri": format!("{RESOURCE_PREFIX}{session}") }),
+        );
+
+        // then
+        let contents = &reply["result"]["contents"][0];
+        assert_eq!(contents["mimeType"], "application/json");
+        let export: Value = serde_json::from_str(contents["text"].as_str().unwrap()).unwrap();
+        assert_eq!(export["files"][0]["path"], "src/main.rs");
+    }
+
+    #[test]
+    fn should_render_parsed_files_as_their_raw_unified_diff() {
+        // given
+        let patch = crate::model::FilePatch::new(
+            Some(PathBuf::from("src/lib.rs")),
+            Some(PathBuf::from("src/lib.rs")),
+            FileStatus::Modified,
+            "@@ -1,2 +1,2 @@\n \tkeep\n-old\n+new\n\\ No newline at end of file".to_string(),
+        );
+        let files = parse_file_patches(vec![patch], &SyntaxHighlighter::default()).unwrap();
+
+        // when
+        let diff = unified_diff(&files);
+
+        // then
+        assert_eq!(
+            diff,
+            "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n\
+             @@ -1,2 +1,2 @@\n \tkeep\n-old\n+new\n\\ No newline at end of file\n"
+        );
+    }
+
+    /// Records which diff was asked for.
+    struct RangeBackend {
+        calls: std::cell::RefCell<Vec<String>>,
+    }
+
+    impl ForgeBackend for RangeBackend {
+        fn list_pull_requests(
+            &self,
+            _query: crate::forge::traits::PullRequestListQuery,
+        ) -> Result<crate::forge::traits::PagedPullRequests> {
+            unimplemented!()
+        }
+        fn get_pull_request(&self, _target: PullRequestTarget) -> Result<PullRequestDetails> {
+            unimplemented!()
+        }
+        fn get_pull_request_diff(&self, _pr: &PullRequestDetails) -> Result<Vec<FilePatch>> {
+            self.calls.borrow_mut().push("head".to_string());
+            Ok(Vec::new())
+        }
+        fn fetch_file_lines(
+            &self,
+            _req: crate::forge::traits::ForgeFileLinesRequest,
+        ) -> Result<Vec<crate::model::DiffLine>> {
+            unimplemented!()
+        }
+        fn list_review_threads(
+            &self,
+            _pr: &PullRequestDetails,
+        ) -> Result<Vec<crate::forge::remote_comments::RemoteReviewThread>> {
+            unimplemented!()
+        }
+        fn list_pull_request_commits(
+            &self,
+            _pr: &PullRequestDetails,
+        ) -> Result<Vec<crate::forge::traits::PullRequestCommit>> {
+            unimplemented!()
+        }
+        fn get_pull_request_commit_range_diff(
+            &self,
+            _pr: &PullRequestDetails,
+            start_sha: &str,
+            end_sha: &str,
+        ) -> Result<Vec<FilePatch>> {
+            self.calls
+                .borrow_mut()
+                .push(format!("{start_sha}..{end_sha}"));
+            Ok(Vec::new())
+        }
+        fn create_review(
+            &self,
+            _pr: &PullRequestDetails,
+            _request: crate::forge::traits::CreateReviewRequest<'_>,
+        ) -> Result<crate::forge::traits::GhCreateReviewResponse> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn should_fetch_the_reviewed_head_after_the_pull_request_moves() {
+        // given
+        let backend = RangeBackend {
+            calls: Default::default(),
+        };
+        let details = PullRequestDetails {
+            repository: crate::forge::traits::ForgeRepository::github("github.com", "o", "r"),
+            number: 7,
+            title: String::new(),
+            url: String::new(),
+            state: "OPEN".to_string(),
+            is_draft: false,
+            author: None,
+            head_ref_name: "feat".to_string(),
+            base_ref_name: "main".to_string(),
+            head_sha: "newhead".to_string(),
+            base_sha: "base".to_string(),
+            body: String::new(),
+            updated_at: None,
+            closed: false,
+            merged_at: None,
+            diff_start_sha: None,
+        };
+
+        // when
+        pull_request_patches_at(&backend, &details, "newhead").unwrap();
+        pull_request_patches_at(&backend, &details, "oldhead").unwrap();
+
+        // then
+        assert_eq!(*backend.calls.borrow(), ["head", "base..oldhead"]);
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
@@ -49,10 +51,11 @@ impl LineRange {
 
 /// Lifecycle state of a local comment relative to the remote forge.
 ///
-/// `LocalDraft` is editable in tuicr. `PushedDraft` and `Submitted` are locked:
-/// they have been written to GitHub and edits/deletions in tuicr would diverge
-/// from the remote. PR 5 introduces the field and the lock check; PR 6 wires
-/// the transitions on successful submit.
+/// `LocalDraft` lives only in tuicr. `PushedDraft` and `Submitted` are locked
+/// out of the next submit because they have been written to the forge.
+/// `Submitted` comments are read only; `PushedDraft` comments read back from
+/// the viewer's pending review stay editable, with every edit and deletion
+/// forwarded to the forge (see [`Comment::is_read_only`]).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum CommentLifecycleState {
@@ -142,6 +145,41 @@ pub struct LineContext {
     pub content: String,
 }
 
+/// Opening fence of a suggested-change block. GitHub applies the fenced
+/// lines as a replacement for the commented range.
+pub const SUGGESTION_FENCE: &str = "```suggestion";
+
+/// Wrap `lines` in a suggestion fence, ready to be edited into the
+/// replacement.
+pub fn suggestion_block(lines: &[&str]) -> String {
+    format!("{SUGGESTION_FENCE}\n{}\n```", lines.join("\n"))
+}
+
+/// Replacement text of the first suggestion fence in `content`. `None`
+/// when there is no fence or it is never closed. An empty block (a
+/// suggestion to delete the lines) yields `Some("")`.
+pub fn suggested_replacement(content: &str) -> Option<String> {
+    let mut lines = content.lines();
+    lines.find(|line| is_suggestion_fence(line))?;
+    let mut replacement = Vec::new();
+    for line in lines {
+        if line.trim_end() == "```" {
+            return Some(replacement.join("\n"));
+        }
+        replacement.push(line);
+    }
+    None
+}
+
+/// True for the opening line of a suggestion block. GitLab's
+/// `suggestion:-N+M` form counts, so an already-translated body is still
+/// recognised.
+pub fn is_suggestion_fence(line: &str) -> bool {
+    line.trim_end()
+        .strip_prefix(SUGGESTION_FENCE)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(':'))
+}
+
 /// Default author used when a comment is created or deserialized without
 /// an explicit author. Distinguishes human-authored comments from agent /
 /// remote comments which set their own author string.
@@ -192,6 +230,22 @@ pub struct Comment {
     /// comments are always shown.
     #[serde(default)]
     pub commit_id: Option<String>,
+    /// Set when a draft carried forward from an older PR head could not be
+    /// re-anchored on the new head's diff. Outdated comments are kept (as
+    /// file- or review-level comments) instead of being dropped, and the
+    /// submit resolver asks what to do with them.
+    #[serde(default)]
+    pub outdated: bool,
+    /// Marked addressed, typically by an agent reporting which comments it
+    /// handled through `tuicr review resolve`. Resolved comments stay in the
+    /// session but render muted; `reopen` clears the flag.
+    #[serde(default)]
+    pub resolved: bool,
+    /// When `:send` last delivered this comment to each `only_new` export
+    /// target, keyed by target name. Cleared when the comment is edited so
+    /// the new text goes out again.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sent_to: BTreeMap<String, DateTime<Utc>>,
 }
 
 impl Comment {
@@ -209,6 +263,9 @@ impl Comment {
             remote_review_id: None,
             remote_comment_id: None,
             commit_id: None,
+            outdated: false,
+            resolved: false,
+            sent_to: BTreeMap::new(),
         }
     }
 
@@ -232,6 +289,9 @@ impl Comment {
             remote_review_id: None,
             remote_comment_id: None,
             commit_id: None,
+            outdated: false,
+            resolved: false,
+            sent_to: BTreeMap::new(),
         }
     }
 
@@ -252,11 +312,22 @@ impl Comment {
         self
     }
 
-    /// True if this comment has been pushed/submitted to the forge and is
-    /// therefore locked from local edits/deletions.
+    /// True if this comment has been pushed/submitted to the forge, so the
+    /// next submit must not send it again.
     pub fn is_locked(&self) -> bool {
         self.lifecycle_state.is_locked()
     }
+
+    /// True if tuicr can no longer edit or delete this comment: it was
+    /// published, or pushed to a pending review that has not been read back
+    /// yet, so there is no remote comment id to send the change to.
+    pub fn is_read_only(&self) -> bool {
+        match self.lifecycle_state {
+            CommentLifecycleState::LocalDraft => false,
+            CommentLifecycleState::PushedDraft => self.remote_comment_id.is_none(),
+            CommentLifecycleState::Submitted => true,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -496,6 +567,21 @@ mod tests {
             assert!(submitted.is_locked());
         }
 
+        #[test]
+        fn should_keep_pending_comments_with_a_remote_id_editable() {
+            // given
+            let mut pushed = Comment::new("p".to_string(), CommentType::None, None);
+            pushed.lifecycle_state = CommentLifecycleState::PushedDraft;
+            let mut submitted = Comment::new("s".to_string(), CommentType::None, None);
+            submitted.lifecycle_state = CommentLifecycleState::Submitted;
+            submitted.remote_comment_id = Some("PRRC_2".to_string());
+            // then — a pushed draft is read only until it is read back
+            assert!(pushed.is_read_only());
+            pushed.remote_comment_id = Some("PRRC_1".to_string());
+            assert!(!pushed.is_read_only());
+            assert!(submitted.is_read_only());
+        }
+
         #[test]
         fn should_roundtrip_lifecycle_fields_via_serde() {
             // given
@@ -535,4 +621,49 @@ mod tests {
             assert_eq!(comment.content, "pre-pr5");
         }
     }
+
+    mod suggestion_tests {
+        use super::*;
+
+        #[test]
+        fn should_extract_replacement_from_suggestion_fence() {
+            // given
+            let content = "Use the helper.\n```suggestion\nlet a = helper();\nlet b = a;\n```";
+
+            // when
+            let replacement = suggested_replacement(content);
+
+            // then
+            assert_eq!(
+                replacement.as_deref(),
+                Some("let a = helper();\nlet b = a;")
+            );
+        }
+
+        #[test]
+        fn should_treat_empty_suggestion_as_deletion() {
+            assert_eq!(
+                suggested_replacement("```suggestion\n```").as_deref(),
+                Some("")
+            );
+        }
+
+        #[test]
+        fn should_ignore_unterminated_or_plain_fences() {
+            assert_eq!(suggested_replacement("```suggestion\nlet a = 1;"), None);
+            assert_eq!(suggested_replacement("```rust\nlet a = 1;\n```"), None);
+            assert_eq!(suggested_replacement("```suggestions\nx\n```"), None);
+        }
+
+        #[test]
+        fn should_recognise_gitlab_fence_and_roundtrip_block() {
+            // given
+            let block = suggestion_block(&["one", "two"]);
+
+            // then
+            assert_eq!(block, "```suggestion\none\ntwo\n```");
+            assert!(is_suggestion_fence("```suggestion:-1+0"));
+            assert_eq!(suggested_replacement(&block).as_deref(), Some("one\ntwo"));
+        }
+    }
 }
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, path::PathBuf};
 
 use crate::hash::Fnv1aHasher;
-use crate::model::comment::LineSide;
+use crate::model::comment::{LineRange, LineSide};
 
 /// Backend-provided file metadata paired with that file's opaque patch text.
 ///
@@ -17,6 +17,8 @@ pub struct FilePatch {
     pub patch: String,
     pub is_binary: bool,
     pub is_too_large: bool,
+    /// See [`DiffFile::review_key`].
+    pub review_key: Option<PathBuf>,
 }
 
 impl FilePatch {
@@ -33,6 +35,7 @@ impl FilePatch {
             patch: patch.into(),
             is_binary: false,
             is_too_large: false,
+            review_key: None,
         }
     }
 
@@ -95,6 +98,10 @@ pub struct DiffHunk {
     pub new_start: u32,
     /// Number of lines from the new file in this hunk
     pub new_count: u32,
+    /// Body lines exactly as the VCS printed them, with tabs, line endings
+    /// and `\ No newline at end of file` markers intact. `None` for hunks
+    /// that were not parsed from a unified diff.
+    pub raw_body: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -107,6 +114,11 @@ pub struct DiffFile {
     pub is_too_large: bool,
     pub is_commit_message: bool,
     pub content_hash: u64,
+    /// Key the review tracks the file under when its path alone is
+    /// ambiguous: a mail series touching one path in several patches keys
+    /// each patch's copy by `(patch id, path)`, spelled `<path> (<patch id>)`.
+    /// `old_path` / `new_path` stay the file's own.
+    pub review_key: Option<PathBuf>,
 }
 
 impl DiffHunk {
@@ -115,6 +127,25 @@ impl DiffHunk {
         write_hunk_content_hash(&mut hasher, &self.lines);
         hasher.finish()
     }
+
+    /// Verbatim text of each line in `lines`, in order, read from
+    /// `raw_body`: tabs and trailing whitespace intact, without the `+`/`-`/` `
+    /// prefix and line ending. `None` when the hunk has no raw body or it
+    /// doesn't line up with `lines`.
+    pub fn raw_line_texts(&self) -> Option<Vec<&str>> {
+        let texts: Vec<&str> = self
+            .raw_body
+            .as_deref()?
+            .split_inclusive('\n')
+            .filter(|line| !line.starts_with('\\'))
+            .map(|line| {
+                let line = line.strip_suffix('\n').unwrap_or(line);
+                let line = line.strip_suffix('\r').unwrap_or(line);
+                line.get(1..).unwrap_or("")
+            })
+            .collect();
+        (texts.len() == self.lines.len()).then_some(texts)
+    }
 }
 
 impl DiffFile {
@@ -180,7 +211,14 @@ impl DiffFile {
             .unwrap_or(0)
     }
 
+    /// The path the review keys the file by: [`Self::review_key`] when set,
+    /// otherwise [`Self::file_path`].
     pub fn display_path(&self) -> &PathBuf {
+        self.review_key.as_ref().unwrap_or_else(|| self.file_path())
+    }
+
+    /// The file's path in the repository.
+    pub fn file_path(&self) -> &PathBuf {
         self.new_path
             .as_ref()
             .or(self.old_path.as_ref())
@@ -222,6 +260,33 @@ impl DiffFile {
         None
     }
 
+    /// New-side text of every line in `range`, in order. `None` unless the
+    /// diff shows each of those lines, since a suggested change must
+    /// replace a contiguous block the reviewer could see.
+    ///
+    /// The text is the file's own (tabs intact) wherever the hunk kept its
+    /// raw body; `DiffLine::content` is expanded for display and would
+    /// rewrite indentation if suggested back.
+    pub fn new_side_lines(&self, range: LineRange) -> Option<Vec<&str>> {
+        let mut lines = Vec::new();
+        for hunk in &self.hunks {
+            let raw = hunk.raw_line_texts();
+            for (index, line) in hunk.lines.iter().enumerate() {
+                if line.origin == LineOrigin::Deletion {
+                    continue;
+                }
+                if let Some(n) = line.new_lineno
+                    && range.contains(n)
+                    && n == range.start + lines.len() as u32
+                {
+                    let text = raw.as_ref().map_or(line.content.as_str(), |raw| raw[index]);
+                    lines.push(text);
+                }
+            }
+        }
+        (lines.len() as u32 == range.end - range.start + 1).then_some(lines)
+    }
+
     /// Returns `(additions, deletions)` for this file.
     pub fn stat(&self) -> (usize, usize) {
         let mut additions = 0;
@@ -25,6 +25,9 @@ pub struct FileReview {
     pub reviewed_hunks: BTreeSet<String>,
     #[serde(default)]
     pub content_hash: Option<u64>,
+    /// The file's own path when `path` is a [`DiffFile::review_key`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<PathBuf>,
 }
 
 impl FileReview {
@@ -37,9 +40,15 @@ impl FileReview {
             line_comments: HashMap::new(),
             reviewed_hunks: BTreeSet::new(),
             content_hash: Some(content_hash),
+            file_path: None,
         }
     }
 
+    /// The path exports name the file by: its own, not its review key.
+    pub fn export_path(&self) -> &PathBuf {
+        self.file_path.as_ref().unwrap_or(&self.path)
+    }
+
     pub fn comment_count(&self) -> usize {
         self.file_comments.len() + self.line_comments.values().map(|v| v.len()).sum::<usize>()
     }
@@ -84,6 +93,10 @@ pub enum SessionDiffSource {
     /// `base_commit` for these sessions starts with `"pristine:"` so the
     /// reload path can match by prefix instead of exact HEAD.
     Pristine,
+    /// A raw patch reviewed with `--patch`. The persisted `base_commit` is
+    /// `"patch:<hash>"`, a hash of the patch text, so the same patch reopens
+    /// the same session wherever it is read from.
+    Patch,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -118,6 +131,10 @@ pub struct ReviewSession {
     pub updated_at: DateTime<Utc>,
     #[serde(default)]
     pub review_comments: Vec<Comment>,
+    /// Replies queued against existing remote review threads, keyed by the
+    /// forge's thread id. They go out with the next `:submit`.
+    #[serde(default)]
+    pub thread_replies: HashMap<String, Vec<Comment>>,
     pub files: HashMap<PathBuf, FileReview>,
     pub session_notes: Option<String>,
 }
@@ -144,6 +161,7 @@ impl ReviewSession {
             created_at: now,
             updated_at: now,
             review_comments: Vec::new(),
+            thread_replies: HashMap::new(),
             files: HashMap::new(),
             session_notes: None,
         }
@@ -180,6 +198,7 @@ impl ReviewSession {
         let path = file.display_path().clone();
         let invalidated = self.add_file(path.clone(), file.status, file.content_hash);
         if let Some(review) = self.files.get_mut(&path) {
+            review.file_path = file.review_key.is_some().then(|| file.file_path().clone());
             let valid_hunks: BTreeSet<_> = file.hunk_review_keys().into_iter().collect();
             review
                 .reviewed_hunks
@@ -191,7 +210,12 @@ impl ReviewSession {
     /// Register a transient filtered diff without dropping hunk keys that
     /// belong to the broader persisted review scope.
     pub fn add_diff_file_preserving_hunks(&mut self, file: &DiffFile) -> bool {
-        self.add_file(file.display_path().clone(), file.status, file.content_hash)
+        let path = file.display_path().clone();
+        let invalidated = self.add_file(path.clone(), file.status, file.content_hash);
+        if let Some(review) = self.files.get_mut(&path) {
+            review.file_path = file.review_key.is_some().then(|| file.file_path().clone());
+        }
+        invalidated
     }
 
     pub fn get_file_mut(&mut self, path: &PathBuf) -> Option<&mut FileReview> {
@@ -199,13 +223,75 @@ impl ReviewSession {
     }
 
     pub fn has_comments(&self) -> bool {
-        !self.review_comments.is_empty() || self.files.values().any(|f| f.comment_count() > 0)
+        !self.review_comments.is_empty()
+            || self.thread_reply_count() > 0
+            || self.files.values().any(|f| f.comment_count() > 0)
+    }
+
+    /// Review-, file- or line-level comment with the given id. Thread
+    /// replies are not searched; they are addressed through their thread.
+    pub fn comment_mut(&mut self, id: &str) -> Option<&mut Comment> {
+        if let Some(comment) = self.review_comments.iter_mut().find(|c| c.id == id) {
+            return Some(comment);
+        }
+        self.files.values_mut().find_map(|review| {
+            review
+                .file_comments
+                .iter_mut()
+                .chain(review.line_comments.values_mut().flatten())
+                .find(|c| c.id == id)
+        })
+    }
+
+    /// Remove and return the review-, file- or line-level comment with the
+    /// given id, dropping its line entry once empty.
+    pub fn remove_comment(&mut self, id: &str) -> Option<Comment> {
+        if let Some(index) = self.review_comments.iter().position(|c| c.id == id) {
+            return Some(self.review_comments.remove(index));
+        }
+        for review in self.files.values_mut() {
+            if let Some(index) = review.file_comments.iter().position(|c| c.id == id) {
+                return Some(review.file_comments.remove(index));
+            }
+            let found = review.line_comments.iter().find_map(|(line, comments)| {
+                Some((*line, comments.iter().position(|c| c.id == id)?))
+            });
+            if let Some((line, index)) = found {
+                let comments = review.line_comments.get_mut(&line)?;
+                let comment = comments.remove(index);
+                if comments.is_empty() {
+                    review.line_comments.remove(&line);
+                }
+                return Some(comment);
+            }
+        }
+        None
+    }
+
+    pub fn add_thread_reply(&mut self, thread_id: String, comment: Comment) {
+        self.thread_replies
+            .entry(thread_id)
+            .or_default()
+            .push(comment);
+    }
+
+    /// Queued replies for one remote thread, in the order they were written.
+    pub fn thread_replies_for(&self, thread_id: &str) -> &[Comment] {
+        self.thread_replies
+            .get(thread_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn thread_reply_count(&self) -> usize {
+        self.thread_replies.values().map(Vec::len).sum()
     }
 
     pub fn clear_comments(&mut self, scope: ClearScope) -> (usize, usize) {
-        let mut cleared = self.review_comments.len();
+        let mut cleared = self.review_comments.len() + self.thread_reply_count();
         let mut unreviewed = 0;
         self.review_comments.clear();
+        self.thread_replies.clear();
         for file in self.files.values_mut() {
             cleared += file.comment_count();
             file.file_comments.clear();
@@ -264,6 +350,7 @@ mod tests {
             old_count: 1,
             new_start,
             new_count: 1,
+            raw_body: None,
         }
     }
 
@@ -278,6 +365,7 @@ mod tests {
             is_too_large: false,
             is_commit_message: false,
             content_hash,
+            review_key: None,
         }
     }
 
@@ -309,6 +397,23 @@ mod tests {
         assert!(session.review_comments.is_empty());
     }
 
+    #[test]
+    fn should_count_and_clear_queued_thread_replies() {
+        let mut session = test_session();
+        let reply = || Comment::new("done".to_string(), CommentType::from_id("note"), None);
+        session.add_thread_reply("PRRT_1".to_string(), reply());
+        session.add_thread_reply("PRRT_1".to_string(), reply());
+        session.add_thread_reply("PRRT_2".to_string(), reply());
+
+        assert!(session.has_comments());
+        assert_eq!(session.thread_reply_count(), 3);
+
+        let (cleared, _) = session.clear_comments(ClearScope::CommentsOnly);
+        assert_eq!(cleared, 3);
+        assert!(session.thread_replies.is_empty());
+        assert!(!session.has_comments());
+    }
+
     #[test]
     fn should_clear_file_and_line_comments() {
         let mut session = test_session();
@@ -857,6 +962,7 @@ mod tests {
                 line_comments: HashMap::new(),
                 reviewed_hunks: BTreeSet::new(),
                 content_hash: None,
+                file_path: None,
             },
         );
 
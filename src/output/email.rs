@@ -0,0 +1,563 @@
+//! Inline email replies for a reviewed mail series.
+//!
+//! Each commented patch gets one reply threaded under it: the commented hunks
+//! are quoted with `> ` and every comment follows the line it is anchored
+//! to, the way list reviews are written by hand. Review-level comments and
+//! the summary answer the cover letter, or the first patch without one. The
+//! replies are written as one mbox, which `git send-email` and `mutt -f`
+//! read directly.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+
+use crate::app::CommentTypeDefinition;
+use crate::error::{Result, TuicrError};
+use crate::model::{Comment, DiffFile, FilePatch, LineOrigin, LineSide, ReviewSession};
+use crate::syntax::SyntaxHighlighter;
+use crate::vcs::diff_parser::parse_file_patches;
+use crate::vcs::mbox::{PatchMail, PatchSeries};
+
+use super::markdown::export_comment_type_label;
+
+/// The replies for a series, as one mbox.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplyMbox {
+    pub text: String,
+    pub replies: usize,
+}
+
+/// A file comment, or a line comment with the line and side it follows.
+type Anchored<'a> = (Option<(u32, LineSide)>, &'a Comment);
+
+/// Comments of one message, sorted into what they are anchored to.
+#[derive(Default)]
+struct MailComments<'a> {
+    general: Vec<&'a Comment>,
+    message: Vec<(u32, &'a Comment)>,
+    files: BTreeMap<usize, Vec<Anchored<'a>>>,
+}
+
+impl MailComments<'_> {
+    fn is_empty(&self) -> bool {
+        self.general.is_empty() && self.message.is_empty() && self.files.is_empty()
+    }
+}
+
+/// Build one reply per commented message of `series`.
+///
+/// # Errors
+///
+/// Returns [`TuicrError::NoComments`] when no comment belongs to a message
+/// of the series.
+pub fn generate_email_replies(
+    series: &PatchSeries,
+    session: &ReviewSession,
+    comment_types: &[CommentTypeDefinition],
+) -> Result<ReplyMbox> {
+    // Files of every patch under the names the review used for them.
+    let mut owners: HashMap<PathBuf, (usize, usize)> = HashMap::new();
+    let mut parsed: Vec<Vec<DiffFile>> = Vec::new();
+    let highlighter = SyntaxHighlighter::plain();
+    for idx in 0..series.patches.len() {
+        let files = parse_file_patches(series.display_files(idx), &highlighter)?;
+        for (file_idx, file) in files.iter().enumerate() {
+            owners.insert(file.display_path().clone(), (idx, file_idx));
+        }
+        parsed.push(files);
+    }
+
+    let mut by_patch: Vec<MailComments> =
+        series.patches.iter().map(|_| Default::default()).collect();
+    let mut cover = MailComments::default();
+
+    let mut paths: Vec<_> = session.files.iter().collect();
+    paths.sort_by_key(|(path, _)| *path);
+    for (path, review) in paths {
+        if let Some(idx) = commit_message_owner(series, path) {
+            for (line, comments) in &review.line_comments {
+                by_patch[idx]
+                    .message
+                    .extend(comments.iter().map(|c| (anchor_line(*line, c), c)));
+            }
+            continue;
+        }
+        let Some(&(idx, file_idx)) = owners.get(path) else {
+            continue;
+        };
+        if review.comment_count() == 0 {
+            continue;
+        }
+        let entry = by_patch[idx].files.entry(file_idx).or_default();
+        entry.extend(review.file_comments.iter().map(|c| (None, c)));
+        for (line, comments) in &review.line_comments {
+            entry.extend(comments.iter().map(|c| {
+                let side = c.side.unwrap_or(LineSide::New);
+                (Some((anchor_line(*line, c), side)), c)
+            }));
+        }
+    }
+
+    let general: Vec<&Comment> = session.review_comments.iter().collect();
+    match (&series.cover, by_patch.first_mut()) {
+        (Some(_), _) => cover.general = general,
+        (None, Some(first)) => first.general = general,
+        (None, None) => {}
+    }
+
+    let summary = session
+        .session_notes
+        .as_deref()
+        .filter(|s| !s.trim().is_empty());
+    let mut text = String::new();
+    let mut replies = 0;
+    if let Some(mail) = &series.cover
+        && (!cover.is_empty() || summary.is_some())
+    {
+        write_reply(&mut text, mail, &cover, summary, &[], &[], comment_types);
+        replies += 1;
+    }
+    for (idx, comments) in by_patch.iter().enumerate() {
+        let summary = summary.filter(|_| series.cover.is_none() && idx == 0);
+        if comments.is_empty() && summary.is_none() {
+            continue;
+        }
+        let mail = &series.patches[idx];
+        write_reply(
+            &mut text,
+            mail,
+            comments,
+            summary,
+            &mail.files,
+            &parsed[idx],
+            comment_types,
+        );
+        replies += 1;
+    }
+
+    if replies == 0 {
+        return Err(TuicrError::NoComments);
+    }
+    Ok(ReplyMbox { text, replies })
+}
+
+/// Patch whose `Commit Message (<short id>)` pseudo-file `path` is.
+fn commit_message_owner(series: &PatchSeries, path: &Path) -> Option<usize> {
+    let short_id = path
+        .to_str()?
+        .strip_prefix("Commit Message (")?
+        .strip_suffix(')')?;
+    series.patches.iter().position(|p| p.short_id() == short_id)
+}
+
+/// Multi-line comments follow the last line of their range.
+fn anchor_line(line: u32, comment: &Comment) -> u32 {
+    comment.line_range.map_or(line, |range| range.end)
+}
+
+fn write_reply(
+    out: &mut String,
+    mail: &PatchMail,
+    comments: &MailComments<'_>,
+    summary: Option<&str>,
+    real_files: &[FilePatch],
+    files: &[DiffFile],
+    comment_types: &[CommentTypeDefinition],
+) {
+    let headers = &mail.headers;
+    let _ = writeln!(out, "From tuicr Mon Sep 17 00:00:00 2001");
+    if let Some(from) = &headers.from {
+        let _ = writeln!(out, "To: {from}");
+    }
+    let cc: Vec<&str> = [&headers.to, &headers.cc]
+        .into_iter()
+        .flatten()
+        .map(String::as_str)
+        .collect();
+    if !cc.is_empty() {
+        let _ = writeln!(out, "Cc: {}", cc.join(", "));
+    }
+    let subject = headers.subject.as_deref().unwrap_or(&mail.summary);
+    let _ = writeln!(out, "Subject: {}", reply_subject(subject));
+    if let Some(message_id) = &headers.message_id {
+        let _ = writeln!(out, "In-Reply-To: {message_id}");
+        let earlier = headers.references.as_ref().or(headers.in_reply_to.as_ref());
+        match earlier {
+            Some(refs) => {
+                let _ = writeln!(out, "References: {refs} {message_id}");
+            }
+            None => {
+                let _ = writeln!(out, "References: {message_id}");
+            }
+        }
+    }
+    let _ = writeln!(out, "MIME-Version: 1.0");
+    let _ = writeln!(out, "Content-Type: text/plain; charset=UTF-8");
+    let _ = writeln!(out, "Content-Transfer-Encoding: 8bit");
+    let _ = writeln!(out);
+
+    let mut body = String::new();
+    if let Some(summary) = summary {
+        let _ = writeln!(body, "{}", summary.trim_end());
+        let _ = writeln!(body);
+    }
+    for comment in &comments.general {
+        write_comment(&mut body, comment, comment_types);
+    }
+
+    let author = mail.author();
+    match headers.date.as_deref() {
+        Some(date) => {
+            let _ = writeln!(body, "On {}, {author} wrote:", date.trim());
+        }
+        None => {
+            let _ = writeln!(body, "{author} wrote:");
+        }
+    }
+
+    if !comments.message.is_empty() {
+        let lines: Vec<String> = mail.message().lines().map(str::to_string).collect();
+        let mut anchored = comments.message.clone();
+        anchored.sort_by_key(|(line, _)| *line);
+        let mut next = 0;
+        for (line, comment) in anchored {
+            let end = (line as usize).min(lines.len());
+            for quoted in lines.iter().take(end).skip(next) {
+                quote(&mut body, quoted);
+            }
+            next = next.max(end);
+            let _ = writeln!(body);
+            write_comment(&mut body, comment, comment_types);
+        }
+    }
+
+    for (&file_idx, anchored) in &comments.files {
+        let (Some(real), Some(file)) = (real_files.get(file_idx), files.get(file_idx)) else {
+            continue;
+        };
+        write_file(&mut body, real, file, anchored, comment_types);
+    }
+
+    for line in body.trim_end().lines() {
+        let _ = writeln!(out, "{}", escape_from(line));
+    }
+    let _ = writeln!(out);
+}
+
+/// Quote one file: its headers, then every commented hunk up to its last
+/// comment. Comments whose line is outside every hunk follow the headers.
+fn write_file(
+    out: &mut String,
+    real: &FilePatch,
+    file: &DiffFile,
+    anchored: &[Anchored<'_>],
+    comment_types: &[CommentTypeDefinition],
+) {
+    let old = real.old_path.as_deref().map(Path::display);
+    let new = real.new_path.as_deref().map(Path::display);
+    let _ = writeln!(out);
+    let old_name = old
+        .as_ref()
+        .map_or("/dev/null".to_string(), |p| format!("a/{p}"));
+    let new_name = new
+        .as_ref()
+        .map_or("/dev/null".to_string(), |p| format!("b/{p}"));
+    let header_old = old.as_ref().or(new.as_ref()).map(|p| p.to_string());
+    let header_new = new.as_ref().or(old.as_ref()).map(|p| p.to_string());
+    if let (Some(a), Some(b)) = (header_old, header_new) {
+        quote(out, &format!("diff --git a/{a} b/{b}"));
+    }
+    quote(out, &format!("--- {old_name}"));
+    quote(out, &format!("+++ {new_name}"));
+
+    // (hunk, line) -> comments after that line
+    let mut in_hunks: BTreeMap<(usize, usize), Vec<&Comment>> = BTreeMap::new();
+    let mut unplaced = Vec::new();
+    for (anchor, comment) in anchored {
+        let position = anchor.and_then(|(line, side)| {
+            file.hunks.iter().enumerate().find_map(|(hunk_idx, hunk)| {
+                let line_idx = hunk.lines.iter().position(|l| {
+                    let lineno = match side {
+                        LineSide::Old => l.old_lineno,
+                        LineSide::New => l.new_lineno,
+                    };
+                    lineno == Some(line)
+                })?;
+                Some((hunk_idx, line_idx))
+            })
+        });
+        match position {
+            Some(position) => in_hunks.entry(position).or_default().push(comment),
+            None => unplaced.push((*anchor, *comment)),
+        }
+    }
+
+    for (anchor, comment) in unplaced {
+        let _ = writeln!(out);
+        if let Some((line, side)) = anchor {
+            let marker = if side == LineSide::Old { "~" } else { "" };
+            let _ = write!(out, "Line {marker}{line}: ");
+        }
+        write_comment(out, comment, comment_types);
+    }
+
+    let mut quoted_to: Option<(usize, usize)> = None;
+    for (&(hunk_idx, line_idx), comments) in &in_hunks {
+        let hunk = &file.hunks[hunk_idx];
+        let from = match quoted_to {
+            Some((h, l)) if h == hunk_idx => l + 1,
+            _ => {
+                quote(out, &hunk.header);
+                0
+            }
+        };
+        // Quote the patch's own bytes where the hunk kept them: the display
+        // content has tabs expanded, and a reply should match the mail it
+        // answers.
+        let raw = hunk.raw_line_texts();
+        for (index, line) in hunk.lines.iter().enumerate().take(line_idx + 1).skip(from) {
+            let prefix = match line.origin {
+                LineOrigin::Context => ' ',
+                LineOrigin::Addition => '+',
+                LineOrigin::Deletion => '-',
+            };
+            let text = raw.as_ref().map_or(line.content.as_str(), |raw| raw[index]);
+            quote(out, &format!("{prefix}{text}"));
+        }
+        quoted_to = Some((hunk_idx, line_idx));
+        let _ = writeln!(out);
+        for comment in comments {
+            write_comment(out, comment, comment_types);
+        }
+    }
+}
+
+fn write_comment(out: &mut String, comment: &Comment, comment_types: &[CommentTypeDefinition]) {
+    let label = export_comment_type_label(&comment.comment_type, comment_types);
+    if label.is_empty() {
+        let _ = writeln!(out, "{}", comment.content.trim_end());
+    } else {
+        let _ = writeln!(out, "[{label}] {}", comment.content.trim_end());
+    }
+    let _ = writeln!(out);
+}
+
+fn quote(out: &mut String, line: &str) {
+    let _ = writeln!(out, "{}", format!("> {line}").trim_end());
+}
+
+/// `Re: ` once, however deep the thread already is. Non-ASCII subjects are
+/// written as an RFC 2047 encoded word.
+fn reply_subject(subject: &str) -> String {
+    let subject = subject.trim();
+    let already = subject
+        .get(..3)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("re:"));
+    let subject = if already {
+        subject.to_string()
+    } else {
+        format!("Re: {subject}")
+    };
+    if subject.is_ascii() {
+        subject
+    } else {
+        format!("=?UTF-8?B?{}?=", BASE64.encode(subject))
+    }
+}
+
+/// mboxrd: a body line that would read as a message separator gets one more
+/// `>`, which readers strip again.
+fn escape_from(line: &str) -> String {
+    if line.trim_start_matches('>').starts_with("From ") {
+        format!(">{line}")
+    } else {
+        line.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{CommentType, FileStatus, LineRange};
+
+    const SERIES: &str = "\
+From 1111111111111111111111111111111111111111 Mon Sep 17 00:00:00 2001
+From: Alice <alice@example.com>
+To: list@example.com
+Cc: Bob <bob@example.com>
+Date: Tue, 7 May 2024 10:00:00 +0200
+Subject: [PATCH 1/1] parser: handle tabs
+Message-Id: <one@example.com>
+In-Reply-To: <cover@example.com>
+References: <cover@example.com>
+
+Tabs were expanded twice.
+---
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@ fn main()
+ keep
+-old
++new
+ tail
+
+From 0000000000000000000000000000000000000000 Mon Sep 17 00:00:00 2001
+From: Alice <alice@example.com>
+Subject: [PATCH 0/1] Tabs
+Message-Id: <cover@example.com>
+
+Cover.
+";
+
+    fn session_with_line_comment() -> ReviewSession {
+        let mut session = ReviewSession::new(
+            PathBuf::from("/tmp/repo"),
+            "1111111".to_string(),
+            None,
+            crate::model::SessionDiffSource::CommitRange,
+        );
+        session.add_file(PathBuf::from("src/lib.rs"), FileStatus::Modified, 0);
+        let review = session.files.get_mut(Path::new("src/lib.rs")).unwrap();
+        review
+            .line_comments
+            .entry(2)
+            .or_default()
+            .push(Comment::new(
+                "Extract a helper.\nFrom here on it repeats.".to_string(),
+                CommentType::from_id("issue"),
+                Some(LineSide::New),
+            ));
+        session
+    }
+
+    #[test]
+    fn quotes_the_hunk_up_to_the_comment_and_threads_under_the_patch() {
+        let series = PatchSeries::parse(SERIES).unwrap();
+        let session = session_with_line_comment();
+
+        let replies = generate_email_replies(&series, &session, &[]).unwrap();
+
+        assert_eq!(replies.replies, 1);
+        assert_eq!(
+            replies.text,
+            "\
+From tuicr Mon Sep 17 00:00:00 2001
+To: Alice <alice@example.com>
+Cc: list@example.com, Bob <bob@example.com>
+Subject: Re: [PATCH 1/1] parser: handle tabs
+In-Reply-To: <one@example.com>
+References: <cover@example.com> <one@example.com>
+MIME-Version: 1.0
+Content-Type: text/plain; charset=UTF-8
+Content-Transfer-Encoding: 8bit
+
+On Tue, 7 May 2024 10:00:00 +0200, Alice wrote:
+
+> diff --git a/src/lib.rs b/src/lib.rs
+> --- a/src/lib.rs
+> +++ b/src/lib.rs
+> @@ -1,3 +1,3 @@ fn main()
+>  keep
+> -old
+> +new
+
+[ISSUE] Extract a helper.
+>From here on it repeats.
+
+"
+        );
+    }
+
+    #[test]
+    fn quotes_the_patch_text_with_its_tabs() {
+        let series = PatchSeries::parse(&SERIES.replace("+new", "+\tnew")).unwrap();
+        let session = session_with_line_comment();
+
+        let replies = generate_email_replies(&series, &session, &[]).unwrap();
+
+        assert!(replies.text.contains("\n> +\tnew\n"), "{}", replies.text);
+    }
+
+    #[test]
+    fn answers_review_comments_and_summary_on_the_cover_letter() {
+        let series = PatchSeries::parse(SERIES).unwrap();
+        let mut session = session_with_line_comment();
+        session.files.clear();
+        session.session_notes = Some("Looks good overall.".to_string());
+        session.review_comments.push(Comment::new(
+            "Please rebase.".to_string(),
+            CommentType::None,
+            None,
+        ));
+
+        let replies = generate_email_replies(&series, &session, &[]).unwrap();
+
+        assert_eq!(replies.replies, 1);
+        assert!(replies.text.contains("Subject: Re: [PATCH 0/1] Tabs\n"));
+        assert!(replies.text.contains("References: <cover@example.com>\n"));
+        assert!(
+            replies
+                .text
+                .contains("\n\nLooks good overall.\n\nPlease rebase.\n\nAlice wrote:\n")
+        );
+    }
+
+    #[test]
+    fn places_commit_message_and_out_of_hunk_comments() {
+        let series = PatchSeries::parse(SERIES).unwrap();
+        let mut session = session_with_line_comment();
+        let review = session.files.get_mut(Path::new("src/lib.rs")).unwrap();
+        review.line_comments.clear();
+        let mut ranged = Comment::new("Far away.".to_string(), CommentType::None, None);
+        ranged.side = Some(LineSide::New);
+        ranged.line_range = Some(LineRange::new(40, 42));
+        review.line_comments.entry(40).or_default().push(ranged);
+        session.add_file(
+            PathBuf::from("Commit Message (1111111)"),
+            FileStatus::Added,
+            0,
+        );
+        let message = session
+            .files
+            .get_mut(Path::new("Commit Message (1111111)"))
+            .unwrap();
+        message
+            .line_comments
+            .entry(1)
+            .or_default()
+            .push(Comment::new(
+                "Prefix with the subsystem.".to_string(),
+                CommentType::None,
+                Some(LineSide::New),
+            ));
+
+        let text = generate_email_replies(&series, &session, &[]).unwrap().text;
+
+        assert!(text.contains("> parser: handle tabs\n\nPrefix with the subsystem.\n"));
+        assert!(text.contains("> +++ b/src/lib.rs\n\nLine 42: Far away.\n"));
+    }
+
+    #[test]
+    fn refuses_when_nothing_is_commented() {
+        let series = PatchSeries::parse(SERIES).unwrap();
+        let mut session = session_with_line_comment();
+        session.files.clear();
+
+        assert!(matches!(
+            generate_email_replies(&series, &session, &[]),
+            Err(TuicrError::NoComments)
+        ));
+    }
+
+    #[test]
+    fn encodes_non_ascii_subjects_once() {
+        assert_eq!(reply_subject("Re: [PATCH] x"), "Re: [PATCH] x");
+        assert_eq!(
+            reply_subject("[PATCH] ü"),
+            "=?UTF-8?B?UmU6IFtQQVRDSF0gw7w=?="
+        );
+    }
+}
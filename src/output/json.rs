@@ -0,0 +1,519 @@
+//! Structured JSON export.
+//!
+//! The document is a stable interface for CI jobs and bots: fields may be
+//! added within a [`SCHEMA_VERSION`], but are never renamed or removed
+//! without bumping it. The SARIF export is built from the same document.
+
+use serde::Serialize;
+
+use crate::app::{CommentTypeDefinition, DiffSource};
+use crate::error::Result;
+use crate::forge::traits::ForgeKind;
+use crate::model::comment::CommentLifecycleState;
+use crate::model::{
+    Comment, DiffFile, FileStatus, LineOrigin, LineRange, LineSide, ReviewSession,
+    SessionDiffSource,
+};
+
+/// Version of the JSON document layout.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewExport {
+    pub schema_version: u32,
+    pub tool: ToolInfo,
+    pub session: SessionInfo,
+    pub files: Vec<FileEntry>,
+    pub comments: Vec<CommentEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolInfo {
+    pub name: &'static str,
+    pub version: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionInfo {
+    pub id: String,
+    /// Slug accepted by `tuicr review --session`, when one can be derived.
+    pub slug: Option<String>,
+    pub repo_path: String,
+    pub branch: Option<String>,
+    pub base_commit: String,
+    pub diff_source: SessionDiffSource,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commits: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pull_request: Option<PullRequestInfo>,
+    pub summary: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PullRequestInfo {
+    pub forge: ForgeKind,
+    pub host: String,
+    pub repository: String,
+    pub number: u64,
+    pub head_sha: String,
+    /// Only known while the pull request is open in the TUI.
+    pub title: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileEntry {
+    pub path: String,
+    pub status: FileStatus,
+    pub reviewed: bool,
+    pub comment_count: usize,
+}
+
+/// What a comment is attached to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentScope {
+    Review,
+    File,
+    Line,
+    Range,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CommentEntry {
+    pub id: String,
+    pub scope: CommentScope,
+    pub path: Option<String>,
+    pub side: Option<LineSide>,
+    pub start_line: Option<u32>,
+    pub end_line: Option<u32>,
+    /// Comment type id, `none` for untyped comments.
+    #[serde(rename = "type")]
+    pub comment_type: String,
+    /// Display label from `comment_types`, `None` for untyped comments.
+    pub type_label: Option<String>,
+    pub author: String,
+    pub commit_id: Option<String>,
+    pub lifecycle_state: CommentLifecycleState,
+    pub resolved: bool,
+    pub created_at: String,
+    pub content: String,
+    /// Diff lines the comment covers. Empty when the diff was not loaded,
+    /// as with `tuicr review export`, or no longer shows those lines.
+    pub context: Vec<ContextLine>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContextLine {
+    pub origin: ContextOrigin,
+    pub old_line: Option<u32>,
+    pub new_line: Option<u32>,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContextOrigin {
+    Context,
+    Addition,
+    Deletion,
+}
+
+impl From<LineOrigin> for ContextOrigin {
+    fn from(origin: LineOrigin) -> Self {
+        match origin {
+            LineOrigin::Context => ContextOrigin::Context,
+            LineOrigin::Addition => ContextOrigin::Addition,
+            LineOrigin::Deletion => ContextOrigin::Deletion,
+        }
+    }
+}
+
+/// Render the review as pretty-printed JSON.
+pub fn generate_json(
+    session: &ReviewSession,
+    diff_files: &[DiffFile],
+    diff_source: Option<&DiffSource>,
+    comment_types: &[CommentTypeDefinition],
+    session_slug: Option<&str>,
+) -> Result<String> {
+    let export = build_review_export(
+        session,
+        diff_files,
+        diff_source,
+        comment_types,
+        session_slug,
+    );
+    let mut json = serde_json::to_string_pretty(&export)?;
+    json.push('\n');
+    Ok(json)
+}
+
+/// Flatten the session into the export document. Comments are ordered the
+/// same way as the Markdown export: review comments first, then files by
+/// path with file comments ahead of line comments.
+pub fn build_review_export(
+    session: &ReviewSession,
+    diff_files: &[DiffFile],
+    diff_source: Option<&DiffSource>,
+    comment_types: &[CommentTypeDefinition],
+    session_slug: Option<&str>,
+) -> ReviewExport {
+    let type_label = |comment: &Comment| -> Option<String> {
+        if comment.comment_type.is_none() {
+            return None;
+        }
+        let id = comment.comment_type.id();
+        Some(
+            comment_types
+                .iter()
+                .find(|definition| definition.id == id)
+                .map(|definition| definition.label.clone())
+                .unwrap_or_else(|| id.to_string()),
+        )
+    };
+
+    let mut comments = Vec::new();
+    for comment in &session.review_comments {
+        comments.push(comment_entry(
+            comment,
+            CommentScope::Review,
+            None,
+            None,
+            type_label(comment),
+            Vec::new(),
+        ));
+    }
+
+    let mut files: Vec<_> = session.files.iter().collect();
+    files.sort_by_key(|(path, _)| path.to_string_lossy().to_string());
+
+    let mut file_entries = Vec::with_capacity(files.len());
+    for (path, review) in files {
+        let path_str = review.export_path().display().to_string();
+        file_entries.push(FileEntry {
+            path: path_str.clone(),
+            status: review.status,
+            reviewed: review.reviewed,
+            comment_count: review.comment_count(),
+        });
+
+        for comment in &review.file_comments {
+            comments.push(comment_entry(
+                comment,
+                CommentScope::File,
+                Some(&path_str),
+                None,
+                type_label(comment),
+                Vec::new(),
+            ));
+        }
+
+        let diff_file = diff_files
+            .iter()
+            .find(|diff_file| diff_file.display_path() == path);
+        let mut line_comments: Vec<_> = review.line_comments.iter().collect();
+        line_comments.sort_by_key(|(line, _)| *line);
+        for (line, line_comments) in line_comments {
+            for comment in line_comments {
+                let range = comment.line_range.unwrap_or(LineRange::single(*line));
+                let side = comment.side.unwrap_or_default();
+                let context = diff_file
+                    .map(|file| context_lines(file, range, side))
+                    .filter(|lines| !lines.is_empty())
+                    .or_else(|| stored_context(comment))
+                    .unwrap_or_default();
+                let scope = if range.is_single() {
+                    CommentScope::Line
+                } else {
+                    CommentScope::Range
+                };
+                comments.push(comment_entry(
+                    comment,
+                    scope,
+                    Some(&path_str),
+                    Some((range, side)),
+                    type_label(comment),
+                    context,
+                ));
+            }
+        }
+    }
+
+    ReviewExport {
+        schema_version: SCHEMA_VERSION,
+        tool: ToolInfo {
+            name: "tuicr",
+            version: env!("CARGO_PKG_VERSION"),
+        },
+        session: session_info(session, diff_source, session_slug),
+        files: file_entries,
+        comments,
+    }
+}
+
+fn session_info(
+    session: &ReviewSession,
+    diff_source: Option<&DiffSource>,
+    session_slug: Option<&str>,
+) -> SessionInfo {
+    let open_pr = match diff_source {
+        Some(DiffSource::PullRequest(pr)) => Some(pr.as_ref()),
+        _ => None,
+    };
+    let pull_request = session.pr_session_key.as_ref().map(|key| PullRequestInfo {
+        forge: key.repository.kind,
+        host: key.repository.host.clone(),
+        repository: key.repository.slug(),
+        number: key.number,
+        head_sha: key.head_sha.clone(),
+        title: open_pr.map(|pr| pr.title.clone()),
+        url: open_pr.map(|pr| pr.url.clone()),
+    });
+    SessionInfo {
+        id: session.id.clone(),
+        slug: session_slug.map(str::to_string),
+        repo_path: session.repo_path.display().to_string(),
+        branch: session.branch_name.clone(),
+        base_commit: session.base_commit.clone(),
+        diff_source: session.diff_source,
+        commits: session.commit_range.clone(),
+        pull_request,
+        summary: session.session_notes.clone(),
+        created_at: session.created_at.to_rfc3339(),
+        updated_at: session.updated_at.to_rfc3339(),
+    }
+}
+
+fn comment_entry(
+    comment: &Comment,
+    scope: CommentScope,
+    path: Option<&str>,
+    location: Option<(LineRange, LineSide)>,
+    type_label: Option<String>,
+    context: Vec<ContextLine>,
+) -> CommentEntry {
+    CommentEntry {
+        id: comment.id.clone(),
+        scope,
+        path: path.map(str::to_string),
+        side: location.map(|(_, side)| side),
+        start_line: location.map(|(range, _)| range.start),
+        end_line: location.map(|(range, _)| range.end),
+        comment_type: comment.comment_type.id().to_string(),
+        type_label,
+        author: comment.author.clone(),
+        commit_id: comment.commit_id.clone(),
+        lifecycle_state: comment.lifecycle_state,
+        resolved: comment.resolved,
+        created_at: comment.created_at.to_rfc3339(),
+        content: comment.content.clone(),
+        context,
+    }
+}
+
+/// Diff rows whose line number on `side` falls in `range`. Old-side
+/// comments see context and deletions, new-side ones context and additions.
+fn context_lines(file: &DiffFile, range: LineRange, side: LineSide) -> Vec<ContextLine> {
+    file.hunks
+        .iter()
+        .flat_map(|hunk| &hunk.lines)
+        .filter(|line| {
+            let lineno = match side {
+                LineSide::Old => line.old_lineno,
+                LineSide::New => line.new_lineno,
+            };
+            lineno.is_some_and(|n| range.contains(n))
+        })
+        .map(|line| ContextLine {
+            origin: line.origin.into(),
+            old_line: line.old_lineno,
+            new_line: line.new_lineno,
+            content: line.content.clone(),
+        })
+        .collect()
+}
+
+/// Falls back on the line captured when the comment was pushed to a forge.
+fn stored_context(comment: &Comment) -> Option<Vec<ContextLine>> {
+    let stored = comment.line_context.as_ref()?;
+    Some(vec![ContextLine {
+        origin: match (stored.old_line, stored.new_line) {
+            (Some(_), None) => ContextOrigin::Deletion,
+            (None, Some(_)) => ContextOrigin::Addition,
+            _ => ContextOrigin::Context,
+        },
+        old_line: stored.old_line,
+        new_line: stored.new_line,
+        content: stored.content.clone(),
+    }])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{CommentType, DiffHunk, DiffLine};
+    use std::path::PathBuf;
+
+    fn session_with_comments() -> ReviewSession {
+        let mut session = ReviewSession::new(
+            PathBuf::from("/tmp/repo"),
+            "abc1234deadbeef".to_string(),
+            Some("main".to_string()),
+            SessionDiffSource::WorkingTree,
+        );
+        session.review_comments.push(Comment::new(
+            "Looks good overall".to_string(),
+            CommentType::None,
+            None,
+        ));
+        session.add_file(PathBuf::from("src/lib.rs"), FileStatus::Modified, 0);
+        let review = session
+            .get_file_mut(&PathBuf::from("src/lib.rs"))
+            .expect("file was just added");
+        review.add_file_comment(Comment::new(
+            "Needs tests".to_string(),
+            CommentType::from_id("issue"),
+            None,
+        ));
+        let mut ranged = Comment::new(
+            "Collapse these".to_string(),
+            CommentType::from_id("suggestion"),
+            Some(LineSide::New),
+        );
+        ranged.line_range = Some(LineRange::new(2, 3));
+        review.add_line_comment(3, ranged);
+        review.add_line_comment(
+            1,
+            Comment::new(
+                "Why drop this?".to_string(),
+                CommentType::from_id("note"),
+                Some(LineSide::Old),
+            ),
+        );
+        session
+    }
+
+    fn diff_files() -> Vec<DiffFile> {
+        let line = |origin, old, new, content: &str| DiffLine {
+            origin,
+            content: content.to_string(),
+            old_lineno: old,
+            new_lineno: new,
+            highlighted_spans: None,
+        };
+        let hunks = vec![DiffHunk {
+            header: "@@ -1,2 +1,3 @@".to_string(),
+            lines: vec![
+                line(LineOrigin::Deletion, Some(1), None, "old first"),
+                line(LineOrigin::Addition, None, Some(1), "new first"),
+                line(LineOrigin::Context, Some(2), Some(2), "shared"),
+                line(LineOrigin::Addition, None, Some(3), "added"),
+            ],
+            old_start: 1,
+            old_count: 2,
+            new_start: 1,
+            new_count: 3,
+            raw_body: None,
+        }];
+        vec![DiffFile {
+            old_path: None,
+            new_path: Some(PathBuf::from("src/lib.rs")),
+            status: FileStatus::Modified,
+            content_hash: DiffFile::compute_content_hash(&hunks),
+            hunks,
+            is_binary: false,
+            is_too_large: false,
+            is_commit_message: false,
+            review_key: None,
+        }]
+    }
+
+    fn comment_types() -> Vec<CommentTypeDefinition> {
+        vec![CommentTypeDefinition {
+            id: "note".to_string(),
+            label: "question".to_string(),
+            definition: None,
+            color: None,
+        }]
+    }
+
+    #[test]
+    fn should_order_comments_like_the_markdown_export() {
+        let export = build_review_export(
+            &session_with_comments(),
+            &diff_files(),
+            None,
+            &comment_types(),
+            Some("agavra/tuicr@main/worktree"),
+        );
+
+        let summary: Vec<_> = export
+            .comments
+            .iter()
+            .map(|c| (c.scope, c.content.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (CommentScope::Review, "Looks good overall"),
+                (CommentScope::File, "Needs tests"),
+                (CommentScope::Line, "Why drop this?"),
+                (CommentScope::Range, "Collapse these"),
+            ]
+        );
+        assert_eq!(export.files.len(), 1);
+        assert_eq!(export.files[0].comment_count, 3);
+        assert_eq!(
+            export.session.slug.as_deref(),
+            Some("agavra/tuicr@main/worktree")
+        );
+    }
+
+    #[test]
+    fn should_attach_diff_context_for_the_commented_side() {
+        let export = build_review_export(
+            &session_with_comments(),
+            &diff_files(),
+            None,
+            &comment_types(),
+            None,
+        );
+
+        let old_side = &export.comments[2];
+        assert_eq!(old_side.type_label.as_deref(), Some("question"));
+        assert_eq!(old_side.context.len(), 1);
+        assert_eq!(old_side.context[0].origin, ContextOrigin::Deletion);
+        assert_eq!(old_side.context[0].content, "old first");
+
+        let range = &export.comments[3];
+        let lines: Vec<_> = range.context.iter().map(|l| l.content.as_str()).collect();
+        assert_eq!(lines, vec!["shared", "added"]);
+        assert_eq!((range.start_line, range.end_line), (Some(2), Some(3)));
+    }
+
+    #[test]
+    fn should_serialize_a_stable_schema() {
+        let json = generate_json(&session_with_comments(), &[], None, &[], None)
+            .expect("export should serialize");
+        let value: serde_json::Value = serde_json::from_str(&json).expect("valid JSON");
+
+        assert_eq!(value["schema_version"], 1);
+        assert_eq!(value["tool"]["name"], "tuicr");
+        assert_eq!(value["session"]["diff_source"], "working_tree");
+        assert_eq!(value["session"]["base_commit"], "abc1234deadbeef");
+        assert!(value["session"].get("pull_request").is_none());
+        let range = &value["comments"][3];
+        assert_eq!(range["scope"], "range");
+        assert_eq!(range["path"], "src/lib.rs");
+        assert_eq!(range["side"], "new");
+        assert_eq!(range["type"], "suggestion");
+        assert_eq!(range["author"], "user");
+        assert_eq!(range["lifecycle_state"], "local_draft");
+        assert_eq!(range["context"], serde_json::json!([]));
+        assert_eq!(value["comments"][0]["type"], "none");
+        assert!(value["comments"][0]["type_label"].is_null());
+    }
+}
@@ -1,6 +1,8 @@
+use std::borrow::Cow;
 use std::collections::HashSet;
 use std::fmt::Write;
 use std::io::Write as IoWrite;
+use std::path::Path;
 
 use arboard::Clipboard;
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
@@ -11,8 +13,12 @@ use crate::error::{Result, TuicrError};
 use crate::forge::remote_comments::{
     PrCommentsVisibility, RemoteReviewThread, filter_threads, group_threads_by_path,
 };
-use crate::model::{CommentType, LineRange, LineSide, ReviewSession};
+use crate::model::comment::{is_suggestion_fence, suggested_replacement};
+use crate::model::{CommentType, DiffFile, LineRange, LineSide, ReviewSession};
 use crate::slug::short_sha;
+
+use super::ExportFormat;
+
 /// (file_path, line_range, side, comment_type, content, commit_id)
 type CommentEntry<'a> = (
     String,
@@ -23,10 +29,11 @@ type CommentEntry<'a> = (
     Option<&'a str>,
 );
 
-/// Generate markdown content from the review session.
-/// Returns the markdown string or an error if there are no comments.
+/// Generate export content from the review session in the configured
+/// [`ExportFormat`]. Returns an error if there are no comments.
 pub fn generate_export_content(
     session: &ReviewSession,
+    diff_files: &[DiffFile],
     diff_source: &DiffSource,
     comment_types: &[CommentTypeDefinition],
     export: &ExportConfig,
@@ -41,8 +48,30 @@ pub fn generate_export_content(
     if !session.has_comments() && !has_remote {
         return Err(TuicrError::NoComments);
     }
+    match export.format() {
+        ExportFormat::Markdown => {}
+        ExportFormat::Json => {
+            return super::json::generate_json(
+                session,
+                diff_files,
+                Some(diff_source),
+                comment_types,
+                session_slug,
+            );
+        }
+        ExportFormat::Sarif => {
+            return super::sarif::generate_sarif(
+                session,
+                diff_files,
+                Some(diff_source),
+                comment_types,
+                session_slug,
+            );
+        }
+    }
     Ok(generate_markdown(
         session,
+        diff_files,
         diff_source,
         comment_types,
         export,
@@ -53,6 +82,7 @@ pub fn generate_export_content(
 
 pub fn export_to_clipboard(
     session: &ReviewSession,
+    diff_files: &[DiffFile],
     diff_source: &DiffSource,
     comment_types: &[CommentTypeDefinition],
     export: &ExportConfig,
@@ -61,6 +91,7 @@ pub fn export_to_clipboard(
 ) -> Result<String> {
     let content = generate_export_content(
         session,
+        diff_files,
         diff_source,
         comment_types,
         export,
@@ -68,10 +99,15 @@ pub fn export_to_clipboard(
         session_slug,
     )?;
     let via_terminal = copy_text_to_clipboard(&content)?;
+    let what = match export.format() {
+        ExportFormat::Markdown => "Review",
+        ExportFormat::Json => "Review JSON",
+        ExportFormat::Sarif => "Review SARIF",
+    };
     Ok(if via_terminal {
-        "Review copied to clipboard (via terminal)".to_string()
+        format!("{what} copied to clipboard (via terminal)")
     } else {
-        "Review copied to clipboard".to_string()
+        format!("{what} copied to clipboard")
     })
 }
 
@@ -258,6 +294,7 @@ fn scope_banner(diff_source: &DiffSource) -> Option<String> {
 
 fn generate_markdown(
     session: &ReviewSession,
+    diff_files: &[DiffFile],
     diff_source: &DiffSource,
     comment_types: &[CommentTypeDefinition],
     export: &ExportConfig,
@@ -285,18 +322,25 @@ fn generate_markdown(
     match diff_source {
         DiffSource::PullRequest(pr) => {
             let mut wrote_any = false;
+            // `tuicr review export` rebuilds the source from the saved session,
+            // which keeps no title or URL.
             if export.scope_line() {
-                let _ = writeln!(
+                let _ = write!(
                     md,
-                    "Reviewing pull request {}#{}: {}",
+                    "Reviewing pull request {}#{}",
                     pr.key.repository.display_name(),
                     pr.key.number,
-                    pr.title
                 );
+                if !pr.title.is_empty() {
+                    let _ = write!(md, ": {}", pr.title);
+                }
+                let _ = writeln!(md);
                 wrote_any = true;
             }
             if export.pr_metadata() {
-                let _ = writeln!(md, "URL: {}", pr.url);
+                if !pr.url.is_empty() {
+                    let _ = writeln!(md, "URL: {}", pr.url);
+                }
                 let _ = writeln!(md, "Head: {}", pr.key.short_head());
                 wrote_any = true;
             }
@@ -419,6 +463,12 @@ fn generate_markdown(
         all_comments.iter().enumerate()
     {
         let number = i + 1;
+        let content = expand_suggestion(content, *line_range, *side, file, diff_files);
+        // Name files by their own path, not a mail series' review key.
+        let file = session.files.get(Path::new(file)).map_or_else(
+            || file.clone(),
+            |review| review.export_path().display().to_string(),
+        );
         let location = match (line_range, side) {
             // Range on deleted side (old lines)
             (Some(range), Some(LineSide::Old)) if range.is_single() => {
@@ -512,6 +562,52 @@ fn generate_markdown(
     md
 }
 
+/// Rewrite a suggested-change fence as explicit before/after snippets, so
+/// an agent can apply it without knowing forge suggestion syntax. Content
+/// without a fence, or whose lines are no longer in `diff_files`, is kept
+/// as written.
+fn expand_suggestion<'a>(
+    content: &'a str,
+    line_range: Option<LineRange>,
+    side: Option<LineSide>,
+    file: &str,
+    diff_files: &[DiffFile],
+) -> Cow<'a, str> {
+    if side == Some(LineSide::Old) {
+        return Cow::Borrowed(content);
+    }
+    let Some(after) = suggested_replacement(content) else {
+        return Cow::Borrowed(content);
+    };
+    let Some(before) = line_range.and_then(|range| {
+        diff_files
+            .iter()
+            .find(|diff_file| diff_file.display_path().display().to_string() == file)?
+            .new_side_lines(range)
+    }) else {
+        return Cow::Borrowed(content);
+    };
+
+    let mut lines = content.split('\n');
+    let mut out: Vec<&str> = lines
+        .by_ref()
+        .take_while(|line| !is_suggestion_fence(line))
+        .collect();
+    lines.by_ref().find(|line| line.trim_end() == "```");
+    out.push("Before:");
+    out.push("```");
+    out.extend(before);
+    out.push("```");
+    out.push("After:");
+    out.push("```");
+    if !after.is_empty() {
+        out.extend(after.split('\n'));
+    }
+    out.push("```");
+    out.extend(lines);
+    Cow::Owned(out.join("\n"))
+}
+
 fn collect_used_comment_type_ids(session: &ReviewSession) -> HashSet<String> {
     let mut ids = HashSet::new();
     for c in &session.review_comments {
@@ -532,7 +628,7 @@ fn collect_used_comment_type_ids(session: &ReviewSession) -> HashSet<String> {
 
 /// Export label for a comment type. Returns an empty string for
 /// [`CommentType::None`] so the caller omits the `**[TYPE]**` marker.
-fn export_comment_type_label(
+pub(super) fn export_comment_type_label(
     comment_type: &CommentType,
     comment_types: &[CommentTypeDefinition],
 ) -> String {
@@ -599,6 +695,7 @@ mod tests {
 
         let markdown = generate_markdown(
             &session,
+            &[],
             &sample_pr_diff_source(),
             &comment_types(),
             &export,
@@ -630,6 +727,7 @@ mod tests {
 
         let markdown = generate_markdown(
             &session,
+            &[],
             &sample_pr_diff_source(),
             &comment_types(),
             &export,
@@ -660,6 +758,7 @@ mod tests {
 
         let markdown = generate_markdown(
             &session,
+            &[],
             &DiffSource::Unstaged,
             &comment_types(),
             &export,
@@ -682,6 +781,7 @@ mod tests {
 
         let markdown = generate_markdown(
             &session,
+            &[],
             &sample_pr_diff_source(),
             &comment_types(),
             &export,
@@ -704,6 +804,7 @@ mod tests {
 
         let markdown = generate_markdown(
             &session,
+            &[],
             &sample_pr_diff_source(),
             &comment_types(),
             &export,
@@ -728,6 +829,7 @@ mod tests {
 
         let markdown = generate_markdown(
             &session,
+            &[],
             &sample_pr_diff_source(),
             &comment_types(),
             &export,
@@ -753,6 +855,7 @@ mod tests {
 
         let markdown = generate_markdown(
             &session,
+            &[],
             &DiffSource::WorkingTree,
             &comment_types(),
             &export,
@@ -774,6 +877,7 @@ mod tests {
 
         let markdown = generate_markdown(
             &session,
+            &[],
             &DiffSource::WorkingTree,
             &comment_types(),
             &export,
@@ -854,6 +958,7 @@ mod tests {
 
         let markdown = generate_markdown(
             &session,
+            &[],
             &diff_source,
             &comment_types(),
             &ExportConfig::default(),
@@ -874,6 +979,7 @@ mod tests {
 
         let markdown = generate_markdown(
             &session,
+            &[],
             &diff_source,
             &comment_types(),
             &ExportConfig::default(),
@@ -893,6 +999,7 @@ mod tests {
         // when
         let markdown = generate_markdown(
             &session,
+            &[],
             &diff_source,
             &comment_types(),
             &ExportConfig::default(),
@@ -956,6 +1063,7 @@ mod tests {
             DiffSource::CommitRange(vec!["ed50028".to_string(), "c17beb2".to_string()]);
         let markdown = generate_markdown(
             &session,
+            &[],
             &diff_source,
             &comment_types(),
             &ExportConfig::default(),
@@ -999,6 +1107,7 @@ mod tests {
 
         let markdown = generate_markdown(
             &session,
+            &[],
             &DiffSource::WorkingTree,
             &custom_types,
             &ExportConfig::default(),
@@ -1019,6 +1128,7 @@ mod tests {
         // when
         let markdown = generate_markdown(
             &session,
+            &[],
             &diff_source,
             &comment_types(),
             &ExportConfig::default(),
@@ -1051,6 +1161,7 @@ mod tests {
 
         let markdown = generate_markdown(
             &session,
+            &[],
             &DiffSource::WorkingTree,
             &comment_types(),
             &ExportConfig::default(),
@@ -1094,6 +1205,7 @@ mod tests {
 
         let markdown = generate_markdown(
             &session,
+            &[],
             &DiffSource::WorkingTree,
             &comment_types(),
             &ExportConfig::default(),
@@ -1122,6 +1234,7 @@ mod tests {
 
         let markdown = generate_markdown(
             &session,
+            &[],
             &DiffSource::WorkingTree,
             &comment_types(),
             &ExportConfig::default(),
@@ -1144,6 +1257,7 @@ mod tests {
 
         let markdown = generate_markdown(
             &session,
+            &[],
             &DiffSource::CommitRange(vec!["abc1234567890".to_string()]),
             &comment_types(),
             &ExportConfig::default(),
@@ -1174,6 +1288,7 @@ mod tests {
 
         let markdown = generate_markdown(
             &session,
+            &[],
             &DiffSource::CommitRange(vec!["abc1234567890".to_string()]),
             &comment_types(),
             &ExportConfig::default(),
@@ -1193,6 +1308,7 @@ mod tests {
 
         let markdown = generate_markdown(
             &session,
+            &[],
             &DiffSource::WorkingTree,
             &comment_types(),
             &ExportConfig::default(),
@@ -1221,6 +1337,7 @@ mod tests {
         // when
         let result = export_to_clipboard(
             &session,
+            &[],
             &diff_source,
             &comment_types(),
             &ExportConfig::default(),
@@ -1242,6 +1359,7 @@ mod tests {
         // when
         let result = generate_export_content(
             &session,
+            &[],
             &diff_source,
             &comment_types(),
             &ExportConfig::default(),
@@ -1271,6 +1389,7 @@ mod tests {
         // when
         let result = generate_export_content(
             &session,
+            &[],
             &diff_source,
             &comment_types(),
             &ExportConfig::default(),
@@ -1295,6 +1414,7 @@ mod tests {
         // when
         let markdown = generate_markdown(
             &session,
+            &[],
             &diff_source,
             &comment_types(),
             &ExportConfig::default(),
@@ -1315,6 +1435,7 @@ mod tests {
         // when
         let markdown = generate_markdown(
             &session,
+            &[],
             &diff_source,
             &comment_types(),
             &ExportConfig::default(),
@@ -1383,6 +1504,7 @@ mod tests {
         let diff_source = DiffSource::WorkingTree;
         let markdown = generate_markdown(
             &session,
+            &[],
             &diff_source,
             &comment_types(),
             &ExportConfig::default(),
@@ -1432,6 +1554,7 @@ mod tests {
         // when
         let markdown = generate_markdown(
             &session,
+            &[],
             &diff_source,
             &comment_types(),
             &ExportConfig::default(),
@@ -1444,6 +1567,121 @@ mod tests {
         assert!(!markdown.contains("`src/main.rs:42-42`"));
     }
 
+    #[test]
+    fn should_export_suggestion_as_before_and_after_snippets() {
+        // given a suggestion over lines 10-11 of a file in the diff
+        use crate::model::{DiffHunk, DiffLine, LineOrigin};
+        let mut session = ReviewSession::new(
+            PathBuf::from("/tmp/test-repo"),
+            "abc1234def".to_string(),
+            Some("main".to_string()),
+            SessionDiffSource::WorkingTree,
+        );
+        session.add_file(PathBuf::from("src/main.rs"), FileStatus::Modified, 0);
+        if let Some(review) = session.get_file_mut(&PathBuf::from("src/main.rs")) {
+            review.add_line_comment(
+                11,
+                Comment::new_with_range(
+                    "Inline it.\n```suggestion\nlet b = 2;\n```".to_string(),
+                    CommentType::from_id("suggestion"),
+                    Some(LineSide::New),
+                    LineRange::new(10, 11),
+                ),
+            );
+        }
+        let diff_line = |content: &str, n: u32| DiffLine {
+            origin: LineOrigin::Addition,
+            content: content.to_string(),
+            old_lineno: None,
+            new_lineno: Some(n),
+            highlighted_spans: None,
+        };
+        let diff_file = DiffFile {
+            old_path: Some(PathBuf::from("src/main.rs")),
+            new_path: Some(PathBuf::from("src/main.rs")),
+            status: FileStatus::Modified,
+            hunks: vec![DiffHunk {
+                header: "@@".to_string(),
+                old_start: 10,
+                old_count: 0,
+                new_start: 10,
+                new_count: 2,
+                lines: vec![diff_line("let a = 1;", 10), diff_line("let b = a + 1;", 11)],
+                raw_body: None,
+            }],
+            is_binary: false,
+            is_too_large: false,
+            is_commit_message: false,
+            content_hash: 0,
+            review_key: None,
+        };
+
+        // when
+        let markdown = generate_markdown(
+            &session,
+            &[diff_file],
+            &DiffSource::WorkingTree,
+            &comment_types(),
+            &legend_off(),
+            &[],
+            None,
+        );
+
+        // then
+        let expected = [
+            "`src/main.rs:10-11` - Inline it.",
+            "   Before:",
+            "   ```",
+            "   let a = 1;",
+            "   let b = a + 1;",
+            "   ```",
+            "   After:",
+            "   ```",
+            "   let b = 2;",
+            "   ```",
+        ]
+        .join("\n");
+        assert!(markdown.contains(&expected), "got:\n{markdown}");
+        assert!(!markdown.contains("```suggestion"));
+    }
+
+    #[test]
+    fn should_keep_suggestion_fence_when_lines_are_not_in_diff() {
+        // given a suggestion whose file is absent from the diff
+        let mut session = ReviewSession::new(
+            PathBuf::from("/tmp/test-repo"),
+            "abc1234def".to_string(),
+            Some("main".to_string()),
+            SessionDiffSource::WorkingTree,
+        );
+        session.add_file(PathBuf::from("src/main.rs"), FileStatus::Modified, 0);
+        if let Some(review) = session.get_file_mut(&PathBuf::from("src/main.rs")) {
+            review.add_line_comment(
+                4,
+                Comment::new_with_range(
+                    "```suggestion\nx\n```".to_string(),
+                    CommentType::from_id("suggestion"),
+                    Some(LineSide::New),
+                    LineRange::single(4),
+                ),
+            );
+        }
+
+        // when
+        let markdown = generate_markdown(
+            &session,
+            &[],
+            &DiffSource::WorkingTree,
+            &comment_types(),
+            &legend_off(),
+            &[],
+            None,
+        );
+
+        // then
+        assert!(markdown.contains("```suggestion"));
+    }
+
     #[test]
     fn should_export_line_range_with_start_and_end() {
         // given - a comment spanning multiple lines
@@ -1472,6 +1710,7 @@ mod tests {
         // when
         let markdown = generate_markdown(
             &session,
+            &[],
             &diff_source,
             &comment_types(),
             &ExportConfig::default(),
@@ -1512,6 +1751,7 @@ mod tests {
         // when
         let markdown = generate_markdown(
             &session,
+            &[],
             &diff_source,
             &comment_types(),
             &ExportConfig::default(),
@@ -1551,6 +1791,7 @@ mod tests {
         // when
         let markdown = generate_markdown(
             &session,
+            &[],
             &diff_source,
             &comment_types(),
             &ExportConfig::default(),
@@ -1590,6 +1831,7 @@ mod tests {
         // when
         let markdown = generate_markdown(
             &session,
+            &[],
             &diff_source,
             &comment_types(),
             &ExportConfig::default(),
@@ -1608,6 +1850,7 @@ mod tests {
 
         let markdown = generate_markdown(
             &session,
+            &[],
             &diff_source,
             &comment_types(),
             &legend_off(),
@@ -1639,6 +1882,7 @@ mod tests {
 
         let markdown = generate_markdown(
             &session,
+            &[],
             &DiffSource::WorkingTree,
             &comment_types(),
             &ExportConfig::default(),
@@ -1682,6 +1926,7 @@ mod tests {
         }];
         let markdown = generate_markdown(
             &session,
+            &[],
             &DiffSource::WorkingTree,
             &none_only,
             &ExportConfig::default(),
@@ -1781,6 +2026,7 @@ mod tests {
         // when
         let markdown = generate_markdown(
             &session,
+            &[],
             &sample_pr_diff_source(),
             &comment_types(),
             &ExportConfig::default(),
@@ -1816,6 +2062,7 @@ mod tests {
         // when
         let result = generate_export_content(
             &session,
+            &[],
             &sample_pr_diff_source(),
             &comment_types(),
             &ExportConfig::default(),
@@ -1854,6 +2101,7 @@ mod tests {
         // when
         let markdown = generate_markdown(
             &session,
+            &[],
             &DiffSource::WorkingTree,
             &comment_types(),
             &ExportConfig::default(),
@@ -1899,6 +2147,7 @@ mod tests {
 
         let markdown = generate_markdown(
             &session,
+            &[],
             &DiffSource::WorkingTree,
             &custom_types,
             &ExportConfig::default(),
@@ -1,3 +1,56 @@
+pub mod email;
+pub mod json;
 pub mod markdown;
+pub mod sarif;
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
 
 pub use markdown::{copy_text_to_clipboard, export_to_clipboard, generate_export_content};
+
+/// Document format produced by `y`, `:clip`, `--stdout` and
+/// `tuicr review export`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// Numbered Markdown list aimed at coding agents.
+    #[default]
+    Markdown,
+    /// Versioned JSON document for scripts and bots.
+    Json,
+    /// SARIF 2.1.0 log for code-scanning tools.
+    Sarif,
+}
+
+impl ExportFormat {
+    pub fn id(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "markdown",
+            ExportFormat::Json => "json",
+            ExportFormat::Sarif => "sarif",
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "markdown" | "md" => Ok(ExportFormat::Markdown),
+            "json" => Ok(ExportFormat::Json),
+            "sarif" => Ok(ExportFormat::Sarif),
+            other => Err(format!(
+                "unknown export format '{other}', expected markdown, json or sarif"
+            )),
+        }
+    }
+}
@@ -0,0 +1,358 @@
+//! SARIF 2.1.0 export, so review comments can be uploaded as code-scanning
+//! results or read by any SARIF consumer.
+//!
+//! Each comment becomes one result and each comment type one rule
+//! (`tuicr/<type>`). Only new-side line comments carry a region: SARIF
+//! regions address the current file, which old-side line numbers do not.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use super::json::{CommentEntry, CommentScope, ReviewExport, build_review_export};
+use crate::app::{CommentTypeDefinition, DiffSource};
+use crate::error::Result;
+use crate::model::{CommentType, DiffFile, LineSide, ReviewSession};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const INFORMATION_URI: &str = "https://github.com/agavra/tuicr";
+/// Rule for untyped comments.
+const UNTYPED_RULE_ID: &str = "tuicr/comment";
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run {
+    tool: Tool,
+    results: Vec<SarifResult>,
+    properties: RunProperties,
+}
+
+#[derive(Debug, Serialize)]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    id: String,
+    name: String,
+    short_description: Message,
+    default_configuration: RuleConfiguration,
+}
+
+#[derive(Debug, Serialize)]
+struct RuleConfiguration {
+    level: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    level: &'static str,
+    message: Message,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    locations: Vec<Location>,
+    partial_fingerprints: BTreeMap<&'static str, String>,
+    properties: ResultProperties,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<Region>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactLocation {
+    uri: String,
+    uri_base_id: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: u32,
+    end_line: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct ResultProperties {
+    scope: CommentScope,
+    side: Option<LineSide>,
+    start_line: Option<u32>,
+    end_line: Option<u32>,
+    author: String,
+    commit_id: Option<String>,
+    resolved: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct RunProperties {
+    session: super::json::SessionInfo,
+}
+
+/// Render the review as a SARIF 2.1.0 log.
+pub fn generate_sarif(
+    session: &ReviewSession,
+    diff_files: &[DiffFile],
+    diff_source: Option<&DiffSource>,
+    comment_types: &[CommentTypeDefinition],
+    session_slug: Option<&str>,
+) -> Result<String> {
+    let export = build_review_export(
+        session,
+        diff_files,
+        diff_source,
+        comment_types,
+        session_slug,
+    );
+    let mut sarif = serde_json::to_string_pretty(&sarif_log(export, comment_types))?;
+    sarif.push('\n');
+    Ok(sarif)
+}
+
+fn sarif_log(export: ReviewExport, comment_types: &[CommentTypeDefinition]) -> SarifLog {
+    let mut rules: BTreeMap<String, Rule> = BTreeMap::new();
+    let results = export
+        .comments
+        .into_iter()
+        .map(|comment| {
+            let rule_id = rule_id(&comment.comment_type);
+            rules
+                .entry(rule_id.clone())
+                .or_insert_with(|| rule(&comment, comment_types));
+            sarif_result(rule_id, comment)
+        })
+        .collect();
+
+    SarifLog {
+        schema: SARIF_SCHEMA,
+        version: SARIF_VERSION,
+        runs: vec![Run {
+            tool: Tool {
+                driver: Driver {
+                    name: export.tool.name,
+                    version: export.tool.version,
+                    information_uri: INFORMATION_URI,
+                    rules: rules.into_values().collect(),
+                },
+            },
+            results,
+            properties: RunProperties {
+                session: export.session,
+            },
+        }],
+    }
+}
+
+fn rule_id(comment_type: &str) -> String {
+    if comment_type == CommentType::NONE_ID {
+        UNTYPED_RULE_ID.to_string()
+    } else {
+        format!("tuicr/{comment_type}")
+    }
+}
+
+fn rule(comment: &CommentEntry, comment_types: &[CommentTypeDefinition]) -> Rule {
+    let definition = comment_types
+        .iter()
+        .find(|definition| definition.id == comment.comment_type);
+    let name = comment
+        .type_label
+        .clone()
+        .unwrap_or_else(|| "comment".to_string());
+    let description = definition
+        .and_then(|definition| definition.definition.clone())
+        .unwrap_or_else(|| format!("tuicr review {name}"));
+    Rule {
+        id: rule_id(&comment.comment_type),
+        name,
+        short_description: Message { text: description },
+        default_configuration: RuleConfiguration {
+            level: level(&comment.comment_type),
+        },
+    }
+}
+
+/// `issue` comments flag problems to fix; everything else is advisory.
+fn level(comment_type: &str) -> &'static str {
+    if comment_type == "issue" {
+        "warning"
+    } else {
+        "note"
+    }
+}
+
+fn sarif_result(rule_id: String, comment: CommentEntry) -> SarifResult {
+    let region = match (comment.side, comment.start_line, comment.end_line) {
+        (Some(LineSide::New), Some(start_line), Some(end_line)) => Some(Region {
+            start_line,
+            end_line,
+        }),
+        _ => None,
+    };
+    let locations = comment
+        .path
+        .as_ref()
+        .map(|path| Location {
+            physical_location: PhysicalLocation {
+                artifact_location: ArtifactLocation {
+                    uri: path.clone(),
+                    uri_base_id: "%SRCROOT%",
+                },
+                region,
+            },
+        })
+        .into_iter()
+        .collect();
+    SarifResult {
+        rule_id,
+        level: level(&comment.comment_type),
+        message: Message {
+            text: comment.content,
+        },
+        locations,
+        partial_fingerprints: BTreeMap::from([("tuicrCommentId/v1", comment.id)]),
+        properties: ResultProperties {
+            scope: comment.scope,
+            side: comment.side,
+            start_line: comment.start_line,
+            end_line: comment.end_line,
+            author: comment.author,
+            commit_id: comment.commit_id,
+            resolved: comment.resolved,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Comment, FileStatus, LineRange, SessionDiffSource};
+    use std::path::PathBuf;
+
+    fn session() -> ReviewSession {
+        let mut session = ReviewSession::new(
+            PathBuf::from("/tmp/repo"),
+            "abc1234".to_string(),
+            Some("main".to_string()),
+            SessionDiffSource::WorkingTree,
+        );
+        session
+            .review_comments
+            .push(Comment::new("Ship it".to_string(), CommentType::None, None));
+        session.add_file(PathBuf::from("src/lib.rs"), FileStatus::Modified, 0);
+        let review = session
+            .get_file_mut(&PathBuf::from("src/lib.rs"))
+            .expect("file was just added");
+        let mut issue = Comment::new(
+            "Off by one".to_string(),
+            CommentType::from_id("issue"),
+            Some(LineSide::New),
+        );
+        issue.line_range = Some(LineRange::new(4, 6));
+        review.add_line_comment(6, issue);
+        review.add_line_comment(
+            2,
+            Comment::new(
+                "Removed too early".to_string(),
+                CommentType::from_id("issue"),
+                Some(LineSide::Old),
+            ),
+        );
+        session
+    }
+
+    fn generate(session: &ReviewSession) -> serde_json::Value {
+        let types = vec![CommentTypeDefinition {
+            id: "issue".to_string(),
+            label: "issue".to_string(),
+            definition: Some("problems to fix".to_string()),
+            color: None,
+        }];
+        let sarif = generate_sarif(session, &[], None, &types, None).expect("sarif serializes");
+        serde_json::from_str(&sarif).expect("valid JSON")
+    }
+
+    #[test]
+    fn should_emit_a_sarif_2_1_0_log_with_one_rule_per_comment_type() {
+        let value = generate(&session());
+
+        assert_eq!(value["version"], "2.1.0");
+        assert_eq!(value["$schema"], SARIF_SCHEMA);
+        let run = &value["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "tuicr");
+        let rules: Vec<_> = run["tool"]["driver"]["rules"]
+            .as_array()
+            .expect("rules array")
+            .iter()
+            .map(|rule| rule["id"].as_str().unwrap_or_default())
+            .collect();
+        assert_eq!(rules, vec!["tuicr/comment", "tuicr/issue"]);
+        assert_eq!(
+            run["tool"]["driver"]["rules"][1]["shortDescription"]["text"],
+            "problems to fix"
+        );
+        assert_eq!(run["properties"]["session"]["base_commit"], "abc1234");
+        assert_eq!(run["results"].as_array().map(Vec::len), Some(3));
+    }
+
+    #[test]
+    fn should_only_place_new_side_comments_in_a_region() {
+        let value = generate(&session());
+        let results = &value["runs"][0]["results"];
+
+        // Review comments have no location at all.
+        assert_eq!(results[0]["ruleId"], "tuicr/comment");
+        assert_eq!(results[0]["level"], "note");
+        assert!(results[0].get("locations").is_none());
+
+        let old_side = &results[1];
+        assert_eq!(old_side["level"], "warning");
+        let location = &old_side["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/lib.rs");
+        assert!(location.get("region").is_none());
+        assert_eq!(old_side["properties"]["side"], "old");
+        assert_eq!(old_side["properties"]["start_line"], 2);
+
+        let region = &results[2]["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(region["startLine"], 4);
+        assert_eq!(region["endLine"], 6);
+        assert!(results[2]["partialFingerprints"]["tuicrCommentId/v1"].is_string());
+    }
+}
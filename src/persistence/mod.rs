@@ -1,4 +1,6 @@
 pub mod manifest;
 pub mod storage;
 
-pub use storage::{load_latest_session_for_context, load_pr_session, save_session};
+pub use storage::{
+    load_latest_session_for_context, load_pr_session, load_previous_head_pr_session, save_session,
+};
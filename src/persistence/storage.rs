@@ -17,7 +17,7 @@
 //! walking `sessions/`.
 
 use std::cmp::Reverse;
-use std::collections::HashSet;
+use std::collections::HashMap;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -203,14 +203,21 @@ fn remove_session_at(path: &Path, reviews_dir: &Path, session: &ReviewSession) -
     Ok(())
 }
 
-pub(crate) fn mark_session_active(session: &ReviewSession, path: &Path) -> Result<()> {
+/// Record this process as the TUI showing `session`. `socket` is the
+/// control socket clients can connect to, when one is listening.
+pub(crate) fn mark_session_active(
+    session: &ReviewSession,
+    path: &Path,
+    socket: Option<&Path>,
+) -> Result<()> {
     let reviews_dir = get_reviews_dir()?;
-    mark_session_active_in_dir(session, path, &reviews_dir)
+    mark_session_active_in_dir(session, path, socket, &reviews_dir)
 }
 
 pub(crate) fn mark_session_active_in_dir(
     session: &ReviewSession,
     path: &Path,
+    socket: Option<&Path>,
     reviews_dir: &Path,
 ) -> Result<()> {
     maybe_migrate(reviews_dir)?;
@@ -228,6 +235,7 @@ pub(crate) fn mark_session_active_in_dir(
             slug,
             path,
             last_seen_at: Utc::now(),
+            socket: socket.map(Path::to_path_buf),
         });
         save_active_sessions_unlocked(reviews_dir, &active)
     })
@@ -248,14 +256,18 @@ pub(crate) fn clear_active_session_for_pid_in_dir(reviews_dir: &Path) -> Result<
     })
 }
 
-pub(crate) fn active_session_paths_in_dir(reviews_dir: &Path) -> Result<HashSet<PathBuf>> {
+/// Sessions open in a live TUI, keyed by normalized session path, with the
+/// control socket of the TUI showing each.
+pub(crate) fn active_sessions_in_dir(
+    reviews_dir: &Path,
+) -> Result<HashMap<PathBuf, Option<PathBuf>>> {
     maybe_migrate(reviews_dir)?;
     let active = load_active_sessions_unlocked(reviews_dir).unwrap_or_default();
     Ok(active
         .sessions
         .into_iter()
         .filter(|entry| entry.is_fresh())
-        .map(|entry| normalize_active_path(&entry.path))
+        .map(|entry| (normalize_active_path(&entry.path), entry.socket))
         .collect())
 }
 
@@ -287,6 +299,8 @@ struct ActiveSessionEntry {
     slug: String,
     path: PathBuf,
     last_seen_at: chrono::DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    socket: Option<PathBuf>,
 }
 
 impl ActiveSessionEntry {
@@ -661,6 +675,68 @@ pub fn load_pr_session(key: &PrSessionKey) -> Result<Option<(PathBuf, ReviewSess
     }
 }
 
+/// Find the most recently updated session for the same PR at an older head
+/// SHA, so its drafts can be carried onto a new head. Returns `None` when the
+/// manifest already tracks a session for `key`'s head. The manifest keeps a
+/// single entry per PR slug, so when it has no usable entry (e.g. an empty
+/// new-head session was cleaned up) the session files are scanned directly.
+pub fn load_previous_head_pr_session(
+    key: &PrSessionKey,
+) -> Result<Option<(PathBuf, ReviewSession)>> {
+    let reviews_dir = get_reviews_dir()?;
+    load_previous_head_pr_session_in_dir(key, &reviews_dir)
+}
+
+pub(crate) fn load_previous_head_pr_session_in_dir(
+    key: &PrSessionKey,
+    reviews_dir: &Path,
+) -> Result<Option<(PathBuf, ReviewSession)>> {
+    maybe_migrate(reviews_dir)?;
+
+    let slug: Slug = key.into();
+    let manifest = manifest::load_manifest(reviews_dir).unwrap_or_default();
+    if let Some(entry) = manifest.get_pr(&slug.to_string()) {
+        if matches!(&entry.kind, ManifestKind::Pr { head_sha, .. } if head_sha == &key.head_sha) {
+            return Ok(None);
+        }
+        let path = reviews_dir.join(&entry.path);
+        if let Ok(session) = load_session(&path)
+            && is_previous_head_session(&session, key, &path, reviews_dir)
+        {
+            return Ok(Some((path, session)));
+        }
+    }
+
+    let sessions_dir = reviews_dir.join(SESSIONS_DIRNAME);
+    let Ok(read_dir) = fs::read_dir(&sessions_dir) else {
+        return Ok(None);
+    };
+    Ok(read_dir
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| load_session(&path).ok().map(|session| (path, session)))
+        .filter(|(path, session)| is_previous_head_session(session, key, path, reviews_dir))
+        .max_by_key(|(_, session)| session.updated_at))
+}
+
+/// A session only counts as an older head of `key` when its embedded key
+/// names the same PR and the file sits at the path derived from that key, so
+/// a misplaced or hand-edited file is never migrated.
+fn is_previous_head_session(
+    session: &ReviewSession,
+    key: &PrSessionKey,
+    path: &Path,
+    reviews_dir: &Path,
+) -> bool {
+    let Some(session_key) = session.pr_session_key.as_ref() else {
+        return false;
+    };
+    session_key.repository == key.repository
+        && session_key.number == key.number
+        && session_key.head_sha != key.head_sha
+        && session_path_in_dir(session, reviews_dir).is_ok_and(|expected| expected == path)
+}
+
 /// Derive the slug for a session from its embedded fields. Local sessions
 /// require resolving the repo's `origin` remote (I/O); PR sessions are
 /// derived purely from the embedded `pr_session_key`.
@@ -997,13 +1073,17 @@ mod tests {
         );
         let path = save_session(&session).unwrap();
 
-        mark_session_active_in_dir(&session, &path, &reviews_dir).unwrap();
-        let active = active_session_paths_in_dir(&reviews_dir).unwrap();
-        assert!(active.contains(&normalize_path_for_comparison(&path)));
+        let socket = reviews_dir.join("1.sock");
+        mark_session_active_in_dir(&session, &path, Some(&socket), &reviews_dir).unwrap();
+        let active = active_sessions_in_dir(&reviews_dir).unwrap();
+        assert_eq!(
+            active.get(&normalize_path_for_comparison(&path)),
+            Some(&Some(socket))
+        );
 
         clear_active_session_for_pid_in_dir(&reviews_dir).unwrap();
-        let active = active_session_paths_in_dir(&reviews_dir).unwrap();
-        assert!(!active.contains(&normalize_path_for_comparison(&path)));
+        let active = active_sessions_in_dir(&reviews_dir).unwrap();
+        assert!(!active.contains_key(&normalize_path_for_comparison(&path)));
     }
 
     #[test]
@@ -1401,6 +1481,63 @@ mod tests {
         assert!(loaded.is_none());
     }
 
+    #[test]
+    fn should_find_previous_head_session_through_manifest() {
+        let _g = with_test_reviews_dir();
+        let old_key = make_pr_key(125, "abcdef0123456789");
+        let old_path = save_session(&make_pr_session(&old_key)).unwrap();
+
+        let new_key = make_pr_key(125, "9999999999999999");
+        let (path, previous) = load_previous_head_pr_session(&new_key).unwrap().unwrap();
+        assert_eq!(path, old_path);
+        assert_eq!(previous.pr_session_key.as_ref(), Some(&old_key));
+    }
+
+    #[test]
+    fn should_not_return_previous_head_when_current_head_is_tracked() {
+        let _g = with_test_reviews_dir();
+        let old_key = make_pr_key(125, "abcdef0123456789");
+        save_session(&make_pr_session(&old_key)).unwrap();
+        let new_key = make_pr_key(125, "9999999999999999");
+        save_session(&make_pr_session(&new_key)).unwrap();
+
+        assert!(load_previous_head_pr_session(&new_key).unwrap().is_none());
+    }
+
+    #[test]
+    fn should_scan_for_newest_previous_head_when_manifest_has_no_entry() {
+        let g = with_test_reviews_dir();
+        let older_key = make_pr_key(125, "1111111111111111");
+        let mut older = make_pr_session(&older_key);
+        older.updated_at = Utc::now() - chrono::Duration::hours(2);
+        let newer_key = make_pr_key(125, "2222222222222222");
+        let mut newer = make_pr_session(&newer_key);
+        newer.updated_at = Utc::now() - chrono::Duration::hours(1);
+        let other_pr = make_pr_session(&make_pr_key(148, "3333333333333333"));
+        for session in [&older, &newer, &other_pr] {
+            save_session(session).unwrap();
+        }
+        manifest::save_manifest(&g.path, &Manifest::new()).unwrap();
+
+        let key = make_pr_key(125, "9999999999999999");
+        let (_path, previous) = load_previous_head_pr_session(&key).unwrap().unwrap();
+        assert_eq!(previous.pr_session_key.as_ref(), Some(&newer_key));
+    }
+
+    #[test]
+    fn should_skip_previous_head_session_stored_under_another_key() {
+        let g = with_test_reviews_dir();
+        let stored_key = make_pr_key(125, "abcdef0123456789");
+        let path = save_session(&make_pr_session(&stored_key)).unwrap();
+        let mut misplaced = make_pr_session(&make_pr_key(125, "1111111111111111"));
+        misplaced.updated_at = Utc::now();
+        fs::write(&path, serde_json::to_vec_pretty(&misplaced).unwrap()).unwrap();
+        manifest::save_manifest(&g.path, &Manifest::new()).unwrap();
+
+        let key = make_pr_key(125, "9999999999999999");
+        assert!(load_previous_head_pr_session(&key).unwrap().is_none());
+    }
+
     #[test]
     fn should_separate_pr_sessions_by_number() {
         let _g = with_test_reviews_dir();
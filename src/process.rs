@@ -1,13 +1,16 @@
 use std::ffi::OsStr;
-use std::io::Write;
+use std::io::{Read, Write};
 use std::path::Path;
-use std::process::{Command, Stdio};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CommandOutputErrorKind {
     NotFound,
     SpawnFailed,
     Unsuccessful,
+    /// Killed after running past its timeout.
+    TimedOut,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -82,6 +85,37 @@ pub fn run_command_output_with_stdin<I, S>(
     args: I,
     stdin: &str,
 ) -> CommandOutputResult<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    run_with_stdin(program, current_dir, args, stdin, None)
+}
+
+/// `run_command_output_with_stdin` for commands that may hang, such as
+/// user-configured shell commands. Past `timeout` the child is killed (on
+/// Unix with everything it spawned) and the call fails with `TimedOut`.
+pub fn run_command_output_with_stdin_timeout<I, S>(
+    program: &str,
+    current_dir: Option<&Path>,
+    args: I,
+    stdin: &str,
+    timeout: Duration,
+) -> CommandOutputResult<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    run_with_stdin(program, current_dir, args, stdin, Some(timeout))
+}
+
+fn run_with_stdin<I, S>(
+    program: &str,
+    current_dir: Option<&Path>,
+    args: I,
+    stdin: &str,
+    timeout: Option<Duration>,
+) -> CommandOutputResult<String>
 where
     I: IntoIterator<Item = S>,
     S: AsRef<OsStr>,
@@ -90,6 +124,11 @@ where
     if let Some(current_dir) = current_dir {
         command.current_dir(current_dir);
     }
+    // Its own process group, so a timeout can kill a shell's children too.
+    #[cfg(unix)]
+    if timeout.is_some() {
+        std::os::unix::process::CommandExt::process_group(&mut command, 0);
+    }
 
     let mut child = command
         .args(args)
@@ -110,25 +149,53 @@ where
             }
         })?;
 
-    // Write the stdin payload before waiting on stdout, then drop the handle
-    // so the child sees EOF and can finish.
-    if let Some(mut child_stdin) = child.stdin.take() {
-        child_stdin
-            .write_all(stdin.as_bytes())
-            .map_err(|err| CommandOutputError {
-                kind: CommandOutputErrorKind::SpawnFailed,
-                status: None,
-                stderr: err.to_string(),
-            })?;
-        // `drop(child_stdin)` happens when the value goes out of scope, which
-        // closes the pipe and signals EOF.
-    }
+    // Write the stdin payload on its own thread while `wait_with_output`
+    // drains stdout/stderr, so a child that prints a lot before reading
+    // cannot deadlock against us. Dropping the handle closes the pipe and
+    // signals EOF. A child may exit without reading stdin at all; the
+    // resulting broken pipe is not an error, its exit status is the answer.
+    let writer = child.stdin.take().map(|mut child_stdin| {
+        let payload = stdin.as_bytes().to_vec();
+        std::thread::spawn(move || match child_stdin.write_all(&payload) {
+            Err(err) if err.kind() != std::io::ErrorKind::BrokenPipe => Err(err),
+            _ => Ok(()),
+        })
+    });
 
-    let output = child.wait_with_output().map_err(|err| CommandOutputError {
+    let output = match timeout {
+        None => child.wait_with_output(),
+        Some(timeout) => match wait_with_output_until(child, timeout) {
+            Ok(Some(output)) => Ok(output),
+            Ok(None) => {
+                return Err(CommandOutputError {
+                    kind: CommandOutputErrorKind::TimedOut,
+                    status: None,
+                    stderr: format!("timed out after {}s", timeout.as_secs()),
+                });
+            }
+            Err(err) => Err(err),
+        },
+    }
+    .map_err(|err| CommandOutputError {
         kind: CommandOutputErrorKind::SpawnFailed,
         status: None,
         stderr: err.to_string(),
     })?;
+    let write_result = writer.map_or(Ok(()), |writer| {
+        writer
+            .join()
+            .unwrap_or_else(|_| Err(std::io::Error::other("stdin writer panicked")))
+    });
+
+    if output.status.success()
+        && let Err(err) = write_result
+    {
+        return Err(CommandOutputError {
+            kind: CommandOutputErrorKind::SpawnFailed,
+            status: None,
+            stderr: err.to_string(),
+        });
+    }
 
     if output.status.success() {
         Ok(String::from_utf8_lossy(&output.stdout).into_owned())
@@ -140,3 +207,113 @@ where
         })
     }
 }
+
+/// `Child::wait_with_output` that gives up after `timeout`: `None` once the
+/// child had to be killed. Output still held by a killed child's leftovers
+/// is abandoned rather than waited for.
+fn wait_with_output_until(
+    mut child: Child,
+    timeout: Duration,
+) -> std::io::Result<Option<std::process::Output>> {
+    fn drain(pipe: Option<impl Read + Send + 'static>) -> std::sync::mpsc::Receiver<Vec<u8>> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        if let Some(mut pipe) = pipe {
+            std::thread::spawn(move || {
+                let mut buf = Vec::new();
+                let _ = pipe.read_to_end(&mut buf);
+                let _ = tx.send(buf);
+            });
+        } else {
+            let _ = tx.send(Vec::new());
+        }
+        rx
+    }
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+    let status: ExitStatus = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            kill_tree(&mut child);
+            return Ok(None);
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    };
+    Ok(Some(std::process::Output {
+        status,
+        stdout: stdout.recv().unwrap_or_default(),
+        stderr: stderr.recv().unwrap_or_default(),
+    }))
+}
+
+/// Kill `child` and, on Unix, the process group it leads.
+fn kill_tree(child: &mut Child) {
+    #[cfg(unix)]
+    let _ = Command::new("kill")
+        .args(["-KILL", "--", &format!("-{}", child.id())])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_report_exit_status_of_a_child_that_ignores_stdin() {
+        let payload = "x".repeat(1 << 20);
+        let err =
+            run_command_output_with_stdin("sh", None, ["-c", "echo offline >&2; exit 3"], &payload)
+                .unwrap_err();
+        assert_eq!(err.kind, CommandOutputErrorKind::Unsuccessful);
+        assert_eq!(err.status, Some(3));
+        assert_eq!(err.stderr, "offline");
+    }
+
+    #[test]
+    fn should_not_deadlock_when_the_child_writes_before_reading() {
+        let payload = "x".repeat(1 << 20);
+        let output = run_command_output_with_stdin(
+            "sh",
+            None,
+            ["-c", "head -c 1048576 /dev/zero; wc -c"],
+            &payload,
+        )
+        .unwrap();
+        assert!(output.trim_end().ends_with("1048576"));
+    }
+
+    #[test]
+    fn should_kill_a_child_that_outlives_its_timeout() {
+        let started = Instant::now();
+        let err = run_command_output_with_stdin_timeout(
+            "sh",
+            None,
+            ["-c", "sleep 30 & wait"],
+            "",
+            Duration::from_millis(200),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind, CommandOutputErrorKind::TimedOut);
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn should_return_output_of_a_child_that_finishes_in_time() {
+        let output = run_command_output_with_stdin_timeout(
+            "sh",
+            None,
+            ["-c", "cat"],
+            "payload",
+            Duration::from_secs(10),
+        )
+        .unwrap();
+        assert_eq!(output, "payload");
+    }
+}
@@ -6,11 +6,14 @@ use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
+use crate::app::{App, DiffSource, PullRequestDiffSource};
 use crate::cli::{LineSideArg, ReviewCommand};
 use crate::config;
 use crate::error::{Result, TuicrError};
 use crate::model::comment::{self, CommentLifecycleState};
-use crate::model::{Comment, CommentType, LineRange, LineSide, ReviewSession};
+use crate::model::{Comment, CommentType, LineRange, LineSide, ReviewSession, SessionDiffSource};
+use crate::output::{ExportFormat, generate_export_content, json, sarif};
+use crate::persistence::storage::slug_for_session;
 use crate::review_store::{
     AddCommentRequest, CommentTarget, ReviewStore, SessionRef, SessionSummary,
 };
@@ -51,6 +54,35 @@ fn run_with_writer(command: ReviewCommand, out: &mut impl Write) -> Result<()> {
             out,
         ),
         ReviewCommand::Comments { session, repo } => show_comments(&session, &repo, out),
+        ReviewCommand::Edit {
+            session,
+            repo,
+            comment_type,
+            id,
+            content,
+        } => edit_comment(&session, &repo, &id, content, comment_type, out),
+        ReviewCommand::Delete { session, repo, ids } => delete_comments(&session, &repo, &ids, out),
+        ReviewCommand::Resolve { session, repo, ids } => {
+            set_comments_resolved(&session, &repo, &ids, true, out)
+        }
+        ReviewCommand::Reopen { session, repo, ids } => {
+            set_comments_resolved(&session, &repo, &ids, false, out)
+        }
+        ReviewCommand::Export {
+            session,
+            repo,
+            format,
+        } => export_session(&session, &repo, format, out),
+        ReviewCommand::Watch {
+            session,
+            repo,
+            interval_ms,
+        } => crate::review_watch::watch(
+            &session,
+            &repo,
+            std::time::Duration::from_millis(interval_ms),
+            out,
+        ),
     }
 }
 
@@ -109,11 +141,11 @@ fn add_comment(
     Ok(())
 }
 
-struct AddRequestParts {
-    target: CommentTarget,
-    comment_type: String,
-    content: String,
-    username: Option<String>,
+pub(crate) struct AddRequestParts {
+    pub(crate) target: CommentTarget,
+    pub(crate) comment_type: String,
+    pub(crate) content: String,
+    pub(crate) username: Option<String>,
 }
 
 /// Resolve the author for a CLI-authored comment.
@@ -121,7 +153,7 @@ struct AddRequestParts {
 /// Priority: explicit `--username` / JSON `username` ► config `username` ►
 /// `Comment::DEFAULT_AUTHOR`. Trims whitespace so `--username " "` doesn't
 /// produce an awkward all-whitespace badge.
-fn resolve_cli_author(explicit: Option<String>) -> String {
+pub(crate) fn resolve_cli_author(explicit: Option<String>) -> String {
     if let Some(name) = explicit.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
         return name.to_string();
     }
@@ -139,6 +171,35 @@ fn resolve_cli_author(explicit: Option<String>) -> String {
 }
 
 fn build_add_request_parts(options: AddCommentOptions) -> Result<AddRequestParts> {
+    let payload = options
+        .input
+        .as_deref()
+        .map(|input| read_json_input(input).and_then(|json| parse_add_payload(&json)))
+        .transpose()?;
+    merge_add_request_parts(options, payload)
+}
+
+/// Build an add request from a `review add --input` style JSON payload
+/// alone, as `tuicr mcp` receives it. Untyped unless the payload says so.
+pub(crate) fn add_request_from_json(payload: &str) -> Result<AddRequestParts> {
+    let options = AddCommentOptions {
+        input: None,
+        comment_type: "none".to_string(),
+        file: None,
+        line: None,
+        end_line: None,
+        side: LineSideArg::New,
+        username: None,
+        content: None,
+    };
+    merge_add_request_parts(options, Some(parse_add_payload(payload)?))
+}
+
+/// Overlay the JSON payload, when there is one, on the flag values.
+fn merge_add_request_parts(
+    options: AddCommentOptions,
+    payload: Option<AddCommentPayload>,
+) -> Result<AddRequestParts> {
     let mut comment_type = options.comment_type;
     let mut content = options.content;
     let mut file = options.file;
@@ -148,8 +209,7 @@ fn build_add_request_parts(options: AddCommentOptions) -> Result<AddRequestParts
     let mut username = options.username;
     let mut target = None;
 
-    if let Some(input) = options.input {
-        let payload = parse_add_payload(&read_json_input(&input)?)?;
+    if let Some(payload) = payload {
         if let Some(payload_comment_type) = payload.comment_type {
             comment_type = payload_comment_type;
         }
@@ -335,7 +395,152 @@ fn show_comments(session: &str, repo: &Path, out: &mut impl Write) -> Result<()>
     Ok(())
 }
 
-fn resolve_session_ref(store: &ReviewStore, repo: &Path, session: &str) -> Result<SessionRef> {
+/// Print the session the way the TUI would export it. The diff is not
+/// loaded, so JSON and SARIF comments carry no line context and Markdown
+/// suggestions are left unexpanded.
+fn export_session(
+    session: &str,
+    repo: &Path,
+    format: ExportFormat,
+    out: &mut impl Write,
+) -> Result<()> {
+    let store = ReviewStore::new();
+    let session_ref = resolve_session_ref(&store, repo, session)?;
+    let session = store.get_review(&session_ref)?;
+    let slug = slug_for_session(&session).ok().map(|slug| slug.to_string());
+    let config = config::load_config()
+        .ok()
+        .and_then(|outcome| outcome.config)
+        .unwrap_or_default();
+    let comment_types = App::resolve_comment_types(config.comment_types.clone());
+    let content = match format {
+        ExportFormat::Json => {
+            json::generate_json(&session, &[], None, &comment_types, slug.as_deref())?
+        }
+        ExportFormat::Sarif => {
+            sarif::generate_sarif(&session, &[], None, &comment_types, slug.as_deref())?
+        }
+        ExportFormat::Markdown => {
+            let mut export = config.resolved_export();
+            export.format = Some(ExportFormat::Markdown);
+            generate_export_content(
+                &session,
+                &[],
+                &diff_source_for_session(&session),
+                &comment_types,
+                &export,
+                &[],
+                slug.as_deref(),
+            )?
+        }
+    };
+    out.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+/// Best-effort `DiffSource` for a session loaded without its diff. Only
+/// the scope banner reads it, and pull request titles and URLs are not
+/// persisted, so those come back empty.
+pub(crate) fn diff_source_for_session(session: &ReviewSession) -> DiffSource {
+    let commits = || session.commit_range.clone().unwrap_or_default();
+    match session.diff_source {
+        SessionDiffSource::WorkingTree | SessionDiffSource::Pristine | SessionDiffSource::Patch => {
+            DiffSource::WorkingTree
+        }
+        SessionDiffSource::Staged => DiffSource::Staged,
+        SessionDiffSource::Unstaged => DiffSource::Unstaged,
+        SessionDiffSource::StagedAndUnstaged => DiffSource::StagedAndUnstaged,
+        SessionDiffSource::CommitRange => DiffSource::CommitRange(commits()),
+        SessionDiffSource::WorkingTreeAndCommits | SessionDiffSource::StagedUnstagedAndCommits => {
+            DiffSource::StagedUnstagedAndCommits(commits())
+        }
+        SessionDiffSource::PullRequest => match &session.pr_session_key {
+            Some(key) => DiffSource::PullRequest(Box::new(PullRequestDiffSource {
+                key: key.clone(),
+                base_sha: String::new(),
+                title: String::new(),
+                url: String::new(),
+                head_ref_name: String::new(),
+                base_ref_name: String::new(),
+                state: String::new(),
+                closed: false,
+                merged: false,
+            })),
+            None => DiffSource::WorkingTree,
+        },
+    }
+}
+
+fn edit_comment(
+    session: &str,
+    repo: &Path,
+    id: &str,
+    content: String,
+    comment_type: Option<String>,
+    out: &mut impl Write,
+) -> Result<()> {
+    let store = ReviewStore::new();
+    let session_ref = resolve_session_ref(&store, repo, session)?;
+    let comment_type = comment_type.as_deref().map(CommentType::from_id);
+    store.edit_comment(&session_ref, id, content, comment_type)?;
+    let output = find_comment_output(&store.get_review(&session_ref)?, id)?;
+    serde_json::to_writer_pretty(&mut *out, &output)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+/// Delete each id in order and print the removed comments. Stops at the
+/// first failure; earlier deletions stay applied.
+fn delete_comments(session: &str, repo: &Path, ids: &[String], out: &mut impl Write) -> Result<()> {
+    let store = ReviewStore::new();
+    let session_ref = resolve_session_ref(&store, repo, session)?;
+    let loaded = store.get_review(&session_ref)?;
+    let mut deleted = Vec::with_capacity(ids.len());
+    for id in ids {
+        // Capture the location before the comment leaves the session.
+        let output = find_comment_output(&loaded, id)?;
+        store.delete_comment(&session_ref, id)?;
+        deleted.push(output);
+    }
+    serde_json::to_writer_pretty(&mut *out, &deleted)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+fn set_comments_resolved(
+    session: &str,
+    repo: &Path,
+    ids: &[String],
+    resolved: bool,
+    out: &mut impl Write,
+) -> Result<()> {
+    let store = ReviewStore::new();
+    let session_ref = resolve_session_ref(&store, repo, session)?;
+    for id in ids {
+        store.set_comment_resolved(&session_ref, id, resolved)?;
+    }
+    let loaded = store.get_review(&session_ref)?;
+    let updated = ids
+        .iter()
+        .map(|id| find_comment_output(&loaded, id))
+        .collect::<Result<Vec<_>>>()?;
+    serde_json::to_writer_pretty(&mut *out, &updated)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+pub(crate) fn find_comment_output(session: &ReviewSession, id: &str) -> Result<CommentOutput> {
+    collect_comments(session)
+        .into_iter()
+        .find(|comment| comment.id == id)
+        .ok_or_else(|| TuicrError::InvalidInput(format!("session has no comment with id {id}")))
+}
+
+pub(crate) fn resolve_session_ref(
+    store: &ReviewStore,
+    repo: &Path,
+    session: &str,
+) -> Result<SessionRef> {
     let direct_path = PathBuf::from(session);
     if direct_path.exists() || direct_path.is_absolute() || session.ends_with(".json") {
         return Ok(SessionRef::from_path(direct_path));
@@ -419,7 +624,7 @@ fn validate_line(line: u32, name: &str) -> Result<()> {
     Ok(())
 }
 
-fn collect_comments(session: &ReviewSession) -> Vec<CommentOutput> {
+pub(crate) fn collect_comments(session: &ReviewSession) -> Vec<CommentOutput> {
     let mut comments = Vec::new();
     for comment in &session.review_comments {
         comments.push(CommentOutput::from_parts(
@@ -513,7 +718,7 @@ fn lifecycle_id(state: CommentLifecycleState) -> &'static str {
 }
 
 #[derive(Debug, Serialize)]
-struct SessionSummaryOutput {
+pub(crate) struct SessionSummaryOutput {
     slug: String,
     kind: &'static str,
     path: String,
@@ -523,6 +728,8 @@ struct SessionSummaryOutput {
     file_count: usize,
     anchor: String,
     active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    socket: Option<String>,
 }
 
 impl From<SessionSummary> for SessionSummaryOutput {
@@ -537,26 +744,28 @@ impl From<SessionSummary> for SessionSummaryOutput {
             file_count: summary.file_count,
             anchor: summary.anchor,
             active: summary.active,
+            socket: summary.socket.map(|socket| socket.display().to_string()),
         }
     }
 }
 
-#[derive(Debug, Serialize)]
-struct CommentOutput {
-    id: String,
-    location: String,
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct CommentOutput {
+    pub(crate) id: String,
+    pub(crate) location: String,
     path: Option<String>,
     start_line: Option<u32>,
     end_line: Option<u32>,
     side: Option<&'static str>,
-    comment_type: String,
-    lifecycle_state: &'static str,
+    pub(crate) comment_type: String,
+    pub(crate) lifecycle_state: &'static str,
+    pub(crate) resolved: bool,
     created_at: String,
-    content: String,
+    pub(crate) content: String,
 }
 
 impl CommentOutput {
-    fn from_target(target: &CommentTarget, comment: &Comment) -> Self {
+    pub(crate) fn from_target(target: &CommentTarget, comment: &Comment) -> Self {
         let (path, start_line, end_line, side) = match target {
             CommentTarget::Review => (None, None, None, None),
             CommentTarget::File { path } => (Some(path.display().to_string()), None, None, None),
@@ -600,6 +809,7 @@ impl CommentOutput {
             side: side_id(side),
             comment_type: comment.comment_type.id().to_string(),
             lifecycle_state: lifecycle_id(comment.lifecycle_state),
+            resolved: comment.resolved,
             created_at: comment.created_at.to_rfc3339(),
             content: comment.content.clone(),
         }
@@ -866,4 +1076,103 @@ mod tests {
         assert_eq!(value[0]["location"], "src/main.rs:42");
         assert_eq!(value[0]["content"], "check this");
     }
+
+    #[test]
+    fn should_report_resolved_and_deleted_comments_through_store() {
+        // given
+        let temp = tempdir().unwrap();
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        let store = ReviewStore::with_reviews_dir(temp.path().join("reviews"));
+        let session_ref = store.save_review(&test_session(repo)).unwrap();
+        let add = |content: &str| {
+            store
+                .add_comment(
+                    &session_ref,
+                    AddCommentRequest {
+                        target: CommentTarget::Review,
+                        content: content.to_string(),
+                        comment_type: CommentType::from_id("note"),
+                        author: crate::model::comment::DEFAULT_AUTHOR.to_string(),
+                        commit_id: None,
+                    },
+                )
+                .unwrap()
+        };
+        let kept = add("handled by the agent");
+        let dropped = add("never mind");
+
+        // when
+        store
+            .set_comment_resolved(&session_ref, &kept.id, true)
+            .unwrap();
+        store.delete_comment(&session_ref, &dropped.id).unwrap();
+
+        // then
+        let loaded = store.get_review(&session_ref).unwrap();
+        let output = find_comment_output(&loaded, &kept.id).unwrap();
+        assert!(output.resolved);
+        let value = serde_json::to_value(&output).unwrap();
+        assert_eq!(value["resolved"], true);
+        assert!(find_comment_output(&loaded, &dropped.id).is_err());
+    }
+
+    #[test]
+    fn should_export_a_session_in_each_format() {
+        let temp = tempdir().unwrap();
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        let store = ReviewStore::with_reviews_dir(temp.path().join("reviews"));
+        let session_ref = store.save_review(&test_session(repo.clone())).unwrap();
+        store
+            .add_comment(
+                &session_ref,
+                AddCommentRequest {
+                    target: CommentTarget::Line {
+                        path: PathBuf::from("src/main.rs"),
+                        line: 7,
+                        side: LineSide::New,
+                    },
+                    content: "check this".to_string(),
+                    comment_type: CommentType::from_id("issue"),
+                    author: "agent".to_string(),
+                    commit_id: None,
+                },
+            )
+            .unwrap();
+        let session_arg = session_ref.path().display().to_string();
+        let export = |format| {
+            let mut out = Vec::new();
+            export_session(&session_arg, &repo, format, &mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        let json: serde_json::Value = serde_json::from_str(&export(ExportFormat::Json)).unwrap();
+        assert_eq!(json["schema_version"], 1);
+        assert_eq!(json["comments"][0]["path"], "src/main.rs");
+        assert_eq!(json["comments"][0]["author"], "agent");
+
+        let sarif: serde_json::Value = serde_json::from_str(&export(ExportFormat::Sarif)).unwrap();
+        assert_eq!(sarif["runs"][0]["results"][0]["ruleId"], "tuicr/issue");
+
+        let markdown = export(ExportFormat::Markdown);
+        assert!(markdown.contains("`src/main.rs:7` - check this"));
+    }
+
+    #[test]
+    fn should_rebuild_pull_request_scope_without_title_or_url() {
+        let mut session = test_session(PathBuf::from("forge:github.com/agavra/tuicr"));
+        session.diff_source = SessionDiffSource::PullRequest;
+        session.pr_session_key = Some(crate::forge::traits::PrSessionKey::new(
+            crate::forge::traits::ForgeRepository::github("github.com", "agavra", "tuicr"),
+            42,
+            "abcdef0123",
+        ));
+
+        let DiffSource::PullRequest(pr) = diff_source_for_session(&session) else {
+            panic!("PR sessions should export with a PR scope");
+        };
+        assert_eq!(pr.key.number, 42);
+        assert!(pr.title.is_empty());
+    }
 }
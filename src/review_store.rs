@@ -37,10 +37,10 @@ impl ReviewStore {
     ) -> Result<Vec<SessionSummary>> {
         let reviews_dir = self.reviews_dir()?;
         let entries = storage::list_sessions_for_selector_in_dir(&reviews_dir, selector.as_ref())?;
-        let active_paths = storage::active_session_paths_in_dir(&reviews_dir)?;
+        let active = storage::active_sessions_in_dir(&reviews_dir)?;
         Ok(entries
             .into_iter()
-            .map(|(slug, entry)| summary_from_entry(&reviews_dir, &active_paths, slug, entry))
+            .map(|(slug, entry)| summary_from_entry(&reviews_dir, &active, slug, entry))
             .collect())
     }
 
@@ -49,10 +49,10 @@ impl ReviewStore {
     pub fn list_all_sessions(&self) -> Result<Vec<SessionSummary>> {
         let reviews_dir = self.reviews_dir()?;
         let entries = storage::list_all_sessions_in_dir(&reviews_dir)?;
-        let active_paths = storage::active_session_paths_in_dir(&reviews_dir)?;
+        let active = storage::active_sessions_in_dir(&reviews_dir)?;
         Ok(entries
             .into_iter()
-            .map(|(slug, entry)| summary_from_entry(&reviews_dir, &active_paths, slug, entry))
+            .map(|(slug, entry)| summary_from_entry(&reviews_dir, &active, slug, entry))
             .collect())
     }
 
@@ -83,6 +83,56 @@ impl ReviewStore {
         Ok(comment)
     }
 
+    /// Replace the text (and optionally the type) of a local draft comment.
+    pub fn edit_comment(
+        &self,
+        session_ref: &SessionRef,
+        id: &str,
+        content: String,
+        comment_type: Option<CommentType>,
+    ) -> Result<Comment> {
+        self.update_comment(session_ref, |session| {
+            edit_comment_in_session(session, id, content, comment_type)
+        })
+    }
+
+    /// Remove a local draft comment and return it.
+    pub fn delete_comment(&self, session_ref: &SessionRef, id: &str) -> Result<Comment> {
+        self.update_comment(session_ref, |session| {
+            delete_comment_from_session(session, id)
+        })
+    }
+
+    /// Mark a comment addressed (`resolved = true`) or open again.
+    pub fn set_comment_resolved(
+        &self,
+        session_ref: &SessionRef,
+        id: &str,
+        resolved: bool,
+    ) -> Result<Comment> {
+        self.update_comment(session_ref, |session| {
+            set_comment_resolved_in_session(session, id, resolved)
+        })
+    }
+
+    fn update_comment(
+        &self,
+        session_ref: &SessionRef,
+        update: impl FnOnce(&mut ReviewSession) -> Result<Comment>,
+    ) -> Result<Comment> {
+        let reviews_dir = self.reviews_dir()?;
+        let (_session, comment) =
+            storage::update_session_in_dir(session_ref.path(), &reviews_dir, update)?;
+        Ok(comment)
+    }
+
+    /// Whether a live TUI has the session open.
+    pub fn is_session_active(&self, session_ref: &SessionRef) -> Result<bool> {
+        let reviews_dir = self.reviews_dir()?;
+        let active = storage::active_sessions_in_dir(&reviews_dir)?;
+        Ok(active.contains_key(&storage::normalize_path_for_comparison(session_ref.path())))
+    }
+
     /// Save a session through this store's storage root.
     pub fn save_review(&self, session: &ReviewSession) -> Result<SessionRef> {
         let reviews_dir = self.reviews_dir()?;
@@ -101,12 +151,12 @@ impl ReviewStore {
 /// path and active state. Shared by the per-repo and `--all` listings.
 fn summary_from_entry(
     reviews_dir: &Path,
-    active_paths: &std::collections::HashSet<PathBuf>,
+    active_sessions: &std::collections::HashMap<PathBuf, Option<PathBuf>>,
     slug: String,
     entry: ManifestEntry,
 ) -> SessionSummary {
     let path = reviews_dir.join(entry.path);
-    let active = active_paths.contains(&storage::normalize_path_for_comparison(&path));
+    let live = active_sessions.get(&storage::normalize_path_for_comparison(&path));
     let kind = match entry.kind {
         ManifestKind::Local => SessionKind::Local,
         ManifestKind::Pr { .. } => SessionKind::Pr,
@@ -120,7 +170,8 @@ fn summary_from_entry(
         reviewed_count: entry.display.reviewed_count,
         file_count: entry.display.file_count,
         anchor: entry.display.anchor,
-        active,
+        active: live.is_some(),
+        socket: live.cloned().flatten(),
     }
 }
 
@@ -168,6 +219,8 @@ pub struct SessionSummary {
     pub file_count: usize,
     pub anchor: String,
     pub active: bool,
+    /// Control socket of the TUI showing the session, when it is active.
+    pub socket: Option<PathBuf>,
 }
 
 /// Request to add a local draft comment to a session.
@@ -264,6 +317,74 @@ pub fn add_comment_to_session(
     Ok(comment)
 }
 
+/// Replace the text of an in-memory comment. Comments already pushed to a
+/// forge are read only and rejected.
+pub fn edit_comment_in_session(
+    session: &mut ReviewSession,
+    id: &str,
+    content: String,
+    comment_type: Option<CommentType>,
+) -> Result<Comment> {
+    let content = content.trim().to_string();
+    if content.is_empty() {
+        return Err(TuicrError::InvalidInput(
+            "comment cannot be empty".to_string(),
+        ));
+    }
+    let comment = unlocked_comment_mut(session, id)?;
+    comment.content = content;
+    if let Some(comment_type) = comment_type {
+        comment.comment_type = comment_type;
+    }
+    comment.sent_to.clear();
+    let comment = comment.clone();
+    session.updated_at = Utc::now();
+    Ok(comment)
+}
+
+/// Remove an in-memory comment. Comments already pushed to a forge are read
+/// only and rejected.
+pub fn delete_comment_from_session(session: &mut ReviewSession, id: &str) -> Result<Comment> {
+    unlocked_comment_mut(session, id)?;
+    let comment = session
+        .remove_comment(id)
+        .ok_or_else(|| comment_not_found(id))?;
+    session.updated_at = Utc::now();
+    Ok(comment)
+}
+
+/// Set the resolved flag on an in-memory comment. Resolution is local
+/// bookkeeping, so it applies to pushed comments too.
+pub fn set_comment_resolved_in_session(
+    session: &mut ReviewSession,
+    id: &str,
+    resolved: bool,
+) -> Result<Comment> {
+    let comment = session
+        .comment_mut(id)
+        .ok_or_else(|| comment_not_found(id))?;
+    comment.resolved = resolved;
+    let comment = comment.clone();
+    session.updated_at = Utc::now();
+    Ok(comment)
+}
+
+fn unlocked_comment_mut<'a>(session: &'a mut ReviewSession, id: &str) -> Result<&'a mut Comment> {
+    let comment = session
+        .comment_mut(id)
+        .ok_or_else(|| comment_not_found(id))?;
+    if comment.is_locked() {
+        return Err(TuicrError::InvalidInput(format!(
+            "comment {id} was already pushed to the forge and is read only"
+        )));
+    }
+    Ok(comment)
+}
+
+fn comment_not_found(id: &str) -> TuicrError {
+    TuicrError::InvalidInput(format!("session has no comment with id {id}"))
+}
+
 fn file_review_mut<'a>(
     session: &'a mut ReviewSession,
     path: &Path,
@@ -376,6 +497,106 @@ mod tests {
         assert!(matches!(err, TuicrError::InvalidInput(_)));
     }
 
+    fn add_line_note(session: &mut ReviewSession, line: u32) -> Comment {
+        add_comment_to_session(
+            session,
+            AddCommentRequest {
+                target: CommentTarget::Line {
+                    path: PathBuf::from("src/main.rs"),
+                    line,
+                    side: LineSide::New,
+                },
+                content: "line note".to_string(),
+                comment_type: CommentType::from_id("note"),
+                author: crate::model::comment::DEFAULT_AUTHOR.to_string(),
+                commit_id: None,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn should_edit_comment_text_and_type() {
+        // given
+        let mut session = test_session(PathBuf::from("/repo"));
+        let comment = add_line_note(&mut session, 7);
+
+        // when
+        let edited = edit_comment_in_session(
+            &mut session,
+            &comment.id,
+            "  reworded  ".to_string(),
+            Some(CommentType::from_id("issue")),
+        )
+        .unwrap();
+
+        // then
+        assert_eq!(edited.content, "reworded");
+        assert_eq!(edited.comment_type, CommentType::from_id("issue"));
+        let review = session.files.get(&PathBuf::from("src/main.rs")).unwrap();
+        assert_eq!(review.line_comments.get(&7), Some(&vec![edited]));
+    }
+
+    #[test]
+    fn should_delete_comment_and_drop_empty_line_entry() {
+        // given
+        let mut session = test_session(PathBuf::from("/repo"));
+        let comment = add_line_note(&mut session, 7);
+
+        // when
+        let deleted = delete_comment_from_session(&mut session, &comment.id).unwrap();
+
+        // then
+        assert_eq!(deleted, comment);
+        let review = session.files.get(&PathBuf::from("src/main.rs")).unwrap();
+        assert!(review.line_comments.is_empty());
+    }
+
+    #[test]
+    fn should_reject_edit_and_delete_of_pushed_comment() {
+        // given
+        let mut session = test_session(PathBuf::from("/repo"));
+        let comment = add_line_note(&mut session, 7);
+        session.comment_mut(&comment.id).unwrap().lifecycle_state =
+            crate::model::comment::CommentLifecycleState::Submitted;
+
+        // when
+        let edit_err = edit_comment_in_session(&mut session, &comment.id, "new".to_string(), None)
+            .unwrap_err();
+        let delete_err = delete_comment_from_session(&mut session, &comment.id).unwrap_err();
+
+        // then
+        assert!(matches!(edit_err, TuicrError::InvalidInput(_)));
+        assert!(matches!(delete_err, TuicrError::InvalidInput(_)));
+        assert!(session.comment_mut(&comment.id).is_some());
+    }
+
+    #[test]
+    fn should_resolve_and_reopen_pushed_comment() {
+        // given
+        let mut session = test_session(PathBuf::from("/repo"));
+        let comment = add_line_note(&mut session, 7);
+        session.comment_mut(&comment.id).unwrap().lifecycle_state =
+            crate::model::comment::CommentLifecycleState::Submitted;
+
+        // when
+        let resolved = set_comment_resolved_in_session(&mut session, &comment.id, true).unwrap();
+        let reopened = set_comment_resolved_in_session(&mut session, &comment.id, false).unwrap();
+
+        // then
+        assert!(resolved.resolved);
+        assert!(!reopened.resolved);
+    }
+
+    #[test]
+    fn should_reject_unknown_comment_id() {
+        let mut session = test_session(PathBuf::from("/repo"));
+
+        let err = set_comment_resolved_in_session(&mut session, "missing", true).unwrap_err();
+
+        assert!(matches!(err, TuicrError::InvalidInput(_)));
+    }
+
     #[test]
     fn should_list_and_update_sessions_through_store() {
         let temp = tempfile::tempdir().unwrap();
@@ -396,6 +617,7 @@ mod tests {
         crate::persistence::storage::mark_session_active_in_dir(
             &session,
             session_ref.path(),
+            None,
             &reviews_dir,
         )
         .unwrap();
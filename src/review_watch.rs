@@ -0,0 +1,416 @@
+//! `tuicr review watch`: stream changes to a persisted session as
+//! newline-delimited JSON.
+//!
+//! The watcher polls the session file and diffs successive snapshots, so it
+//! sees edits from the TUI, the review CLI and the MCP server alike. It stops
+//! once the TUI showing the session exits.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::error::{Result, TuicrError};
+use crate::model::ReviewSession;
+use crate::persistence::storage::slug_for_session;
+use crate::review_cli::{CommentOutput, collect_comments, resolve_session_ref};
+use crate::review_store::{ReviewStore, SessionRef};
+
+/// Exit status of `tuicr review watch` when the TUI closes the session.
+pub const SESSION_CLOSED_EXIT_CODE: i32 = 3;
+
+const LOCAL_DRAFT: &str = "local_draft";
+
+/// One line of the `review watch` stream.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub(crate) enum WatchEvent {
+    /// First line, once the baseline snapshot is loaded.
+    Ready {
+        slug: String,
+        path: String,
+        active: bool,
+        comment_count: usize,
+    },
+    CommentAdded {
+        comment: CommentOutput,
+    },
+    /// Content, type, location or resolved state changed.
+    CommentEdited {
+        comment: CommentOutput,
+    },
+    CommentDeleted {
+        comment: CommentOutput,
+    },
+    FileReviewed {
+        path: String,
+    },
+    FileUnreviewed {
+        path: String,
+    },
+    /// Local drafts were sent to the forge, as a review (`submitted`) or a
+    /// pending review (`pushed_draft`).
+    SessionSubmitted {
+        lifecycle_state: &'static str,
+        comment_ids: Vec<String>,
+    },
+    /// Last line: the TUI showing the session exited.
+    SessionClosed {
+        slug: String,
+    },
+}
+
+pub(crate) fn watch(
+    session: &str,
+    repo: &Path,
+    interval: Duration,
+    out: &mut impl Write,
+) -> Result<()> {
+    let store = ReviewStore::new();
+    let session_ref = resolve_session_ref(&store, repo, session)?;
+    let mut watcher = SessionWatcher::new(store, session_ref)?;
+    watcher.start(out)?;
+    loop {
+        std::thread::sleep(interval);
+        if !watcher.poll(out)? {
+            return Err(TuicrError::SessionClosed(watcher.slug));
+        }
+    }
+}
+
+struct SessionWatcher {
+    store: ReviewStore,
+    session_ref: SessionRef,
+    slug: String,
+    previous: ReviewSession,
+    /// Whether a TUI has had the session open since the watch started. A
+    /// session nobody has opened yet is watched until one does and exits.
+    seen_active: bool,
+}
+
+impl SessionWatcher {
+    fn new(store: ReviewStore, session_ref: SessionRef) -> Result<Self> {
+        let previous = store.get_review(&session_ref)?;
+        let slug = slug_for_session(&previous)?.to_string();
+        Ok(Self {
+            store,
+            session_ref,
+            slug,
+            previous,
+            seen_active: false,
+        })
+    }
+
+    fn start(&mut self, out: &mut impl Write) -> Result<()> {
+        self.seen_active = self.store.is_session_active(&self.session_ref)?;
+        write_event(
+            out,
+            &WatchEvent::Ready {
+                slug: self.slug.clone(),
+                path: self.session_ref.path().display().to_string(),
+                active: self.seen_active,
+                comment_count: collect_comments(&self.previous).len(),
+            },
+        )
+    }
+
+    /// Emit what changed since the last poll. Returns `false` once the
+    /// session has closed.
+    fn poll(&mut self, out: &mut impl Write) -> Result<bool> {
+        let active = self.store.is_session_active(&self.session_ref)?;
+        // The TUI removes an untouched session file on exit.
+        if !self.session_ref.path().exists() {
+            return self.close(out);
+        }
+        // A read can race a save; the next poll sees the finished file.
+        if let Ok(current) = self.store.get_review(&self.session_ref) {
+            for event in session_events(&self.previous, &current) {
+                write_event(out, &event)?;
+            }
+            self.previous = current;
+        }
+        if self.seen_active && !active {
+            return self.close(out);
+        }
+        self.seen_active |= active;
+        Ok(true)
+    }
+
+    fn close(&mut self, out: &mut impl Write) -> Result<bool> {
+        write_event(
+            out,
+            &WatchEvent::SessionClosed {
+                slug: self.slug.clone(),
+            },
+        )?;
+        Ok(false)
+    }
+}
+
+fn write_event(out: &mut impl Write, event: &WatchEvent) -> Result<()> {
+    serde_json::to_writer(&mut *out, event)?;
+    writeln!(out)?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Events that turn `before` into `after`. Submitted comments that later
+/// leave the session are not reported as deleted: they were pruned because
+/// the forge now holds them.
+pub(crate) fn session_events(before: &ReviewSession, after: &ReviewSession) -> Vec<WatchEvent> {
+    let mut events = Vec::new();
+    let before_comments: HashMap<String, CommentOutput> = collect_comments(before)
+        .into_iter()
+        .map(|comment| (comment.id.clone(), comment))
+        .collect();
+    let after_comments = collect_comments(after);
+
+    let mut submitted: BTreeMap<&'static str, Vec<String>> = BTreeMap::new();
+    for comment in &after_comments {
+        match before_comments.get(&comment.id) {
+            None => events.push(WatchEvent::CommentAdded {
+                comment: comment.clone(),
+            }),
+            Some(old)
+                if old.lifecycle_state == LOCAL_DRAFT && comment.lifecycle_state != LOCAL_DRAFT =>
+            {
+                submitted
+                    .entry(comment.lifecycle_state)
+                    .or_default()
+                    .push(comment.id.clone());
+            }
+            Some(old) if was_edited(old, comment) => events.push(WatchEvent::CommentEdited {
+                comment: comment.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    let mut deleted: Vec<_> = before_comments
+        .into_values()
+        .filter(|old| old.lifecycle_state == LOCAL_DRAFT)
+        .filter(|old| !after_comments.iter().any(|comment| comment.id == old.id))
+        .collect();
+    deleted.sort_by(|a, b| a.location.cmp(&b.location).then(a.id.cmp(&b.id)));
+    events.extend(
+        deleted
+            .into_iter()
+            .map(|comment| WatchEvent::CommentDeleted { comment }),
+    );
+
+    let mut files: Vec<_> = after.files.iter().collect();
+    files.sort_by_key(|(path, _)| path.as_os_str().to_os_string());
+    for (path, review) in files {
+        let was_reviewed = before.files.get(path).is_some_and(|old| old.reviewed);
+        if review.reviewed == was_reviewed {
+            continue;
+        }
+        let path = path.display().to_string();
+        events.push(if review.reviewed {
+            WatchEvent::FileReviewed { path }
+        } else {
+            WatchEvent::FileUnreviewed { path }
+        });
+    }
+
+    events.extend(submitted.into_iter().map(|(lifecycle_state, comment_ids)| {
+        WatchEvent::SessionSubmitted {
+            lifecycle_state,
+            comment_ids,
+        }
+    }));
+    events
+}
+
+fn was_edited(old: &CommentOutput, new: &CommentOutput) -> bool {
+    old.content != new.content
+        || old.comment_type != new.comment_type
+        || old.location != new.location
+        || old.resolved != new.resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    use serde_json::Value;
+
+    use crate::model::comment::CommentLifecycleState;
+    use crate::model::{Comment, CommentType, FileStatus, LineSide, SessionDiffSource};
+    use crate::persistence::storage;
+
+    fn test_session() -> ReviewSession {
+        let mut session = ReviewSession::new(
+            PathBuf::from("/tmp/repo"),
+            "abc1234".to_string(),
+            Some("main".to_string()),
+            SessionDiffSource::WorkingTree,
+        );
+        session.add_file(PathBuf::from("src/lib.rs"), FileStatus::Modified, 0);
+        session.add_file(PathBuf::from("src/main.rs"), FileStatus::Modified, 0);
+        session
+    }
+
+    fn add_line_comment(session: &mut ReviewSession, line: u32, content: &str) -> String {
+        let comment = Comment::new(content.to_string(), CommentType::None, Some(LineSide::New));
+        let id = comment.id.clone();
+        session
+            .files
+            .get_mut(&PathBuf::from("src/main.rs"))
+            .unwrap()
+            .line_comments
+            .entry(line)
+            .or_default()
+            .push(comment);
+        id
+    }
+
+    fn line_comment(session: &mut ReviewSession, line: u32) -> &mut Comment {
+        &mut session
+            .files
+            .get_mut(&PathBuf::from("src/main.rs"))
+            .unwrap()
+            .line_comments
+            .get_mut(&line)
+            .unwrap()[0]
+    }
+
+    fn event_values(events: &[WatchEvent]) -> Vec<Value> {
+        events
+            .iter()
+            .map(|event| serde_json::to_value(event).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn should_report_added_edited_and_deleted_comments() {
+        // given
+        let mut before = test_session();
+        let edited = add_line_comment(&mut before, 3, "first");
+        let deleted = add_line_comment(&mut before, 9, "gone");
+        let mut after = before.clone();
+        line_comment(&mut after, 3).content = "first, reworded".to_string();
+        after
+            .files
+            .get_mut(&PathBuf::from("src/main.rs"))
+            .unwrap()
+            .line_comments
+            .remove(&9);
+        let added = add_line_comment(&mut after, 5, "new");
+
+        // when
+        let events = event_values(&session_events(&before, &after));
+
+        // then
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0]["event"], "comment_edited");
+        assert_eq!(events[0]["comment"]["id"], edited.as_str());
+        assert_eq!(events[0]["comment"]["content"], "first, reworded");
+        assert_eq!(events[1]["event"], "comment_added");
+        assert_eq!(events[1]["comment"]["id"], added.as_str());
+        assert_eq!(events[2]["event"], "comment_deleted");
+        assert_eq!(events[2]["comment"]["id"], deleted.as_str());
+    }
+
+    #[test]
+    fn should_report_reviewed_and_unreviewed_files() {
+        // given
+        let mut before = test_session();
+        before
+            .files
+            .get_mut(&PathBuf::from("src/lib.rs"))
+            .unwrap()
+            .reviewed = true;
+        let mut after = before.clone();
+        after
+            .files
+            .get_mut(&PathBuf::from("src/lib.rs"))
+            .unwrap()
+            .reviewed = false;
+        after
+            .files
+            .get_mut(&PathBuf::from("src/main.rs"))
+            .unwrap()
+            .reviewed = true;
+
+        // when
+        let events = event_values(&session_events(&before, &after));
+
+        // then
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0]["event"], "file_unreviewed");
+        assert_eq!(events[0]["path"], "src/lib.rs");
+        assert_eq!(events[1]["event"], "file_reviewed");
+        assert_eq!(events[1]["path"], "src/main.rs");
+    }
+
+    #[test]
+    fn should_report_a_submit_once_and_ignore_pruned_submitted_comments() {
+        // given
+        let mut drafted = test_session();
+        let first = add_line_comment(&mut drafted, 3, "one");
+        let second = add_line_comment(&mut drafted, 7, "two");
+        let mut submitted = drafted.clone();
+        line_comment(&mut submitted, 3).lifecycle_state = CommentLifecycleState::Submitted;
+        line_comment(&mut submitted, 7).lifecycle_state = CommentLifecycleState::Submitted;
+        let mut pruned = submitted.clone();
+        pruned
+            .files
+            .get_mut(&PathBuf::from("src/main.rs"))
+            .unwrap()
+            .line_comments
+            .clear();
+
+        // when
+        let on_submit = event_values(&session_events(&drafted, &submitted));
+        let on_prune = session_events(&submitted, &pruned);
+
+        // then
+        assert_eq!(on_submit.len(), 1);
+        assert_eq!(on_submit[0]["event"], "session_submitted");
+        assert_eq!(on_submit[0]["lifecycle_state"], "submitted");
+        assert_eq!(
+            on_submit[0]["comment_ids"],
+            serde_json::json!([first, second])
+        );
+        assert!(on_prune.is_empty());
+    }
+
+    #[test]
+    fn should_stream_changes_and_close_when_the_tui_exits() {
+        // given
+        let dir = tempfile::tempdir().unwrap();
+        let store = ReviewStore::with_reviews_dir(dir.path());
+        let mut session = test_session();
+        let session_ref = store.save_review(&session).unwrap();
+        storage::mark_session_active_in_dir(&session, session_ref.path(), None, dir.path())
+            .unwrap();
+        let mut watcher = SessionWatcher::new(store.clone(), session_ref.clone()).unwrap();
+        let mut out = Vec::new();
+        watcher.start(&mut out).unwrap();
+
+        // when
+        add_line_comment(&mut session, 4, "from the TUI");
+        store.save_review(&session).unwrap();
+        let open_after_comment = watcher.poll(&mut out).unwrap();
+        storage::clear_active_session_for_pid_in_dir(dir.path()).unwrap();
+        let open_after_exit = watcher.poll(&mut out).unwrap();
+
+        // then
+        assert!(open_after_comment);
+        assert!(!open_after_exit);
+        let lines: Vec<Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["event"], "ready");
+        assert_eq!(lines[0]["active"], true);
+        assert_eq!(lines[1]["event"], "comment_added");
+        assert_eq!(lines[1]["comment"]["content"], "from the TUI");
+        assert_eq!(lines[2]["event"], "session_closed");
+    }
+}
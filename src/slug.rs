@@ -13,7 +13,7 @@
 //! `~<short-sha>` for detached / anonymous heads, and `<source>` is one of the
 //! diff-source variants (`worktree/<head>`, `staged/<head>`,
 //! `unstaged/<head>`, `staged-and-unstaged/<head>`, `pristine`,
-//! `commits/<base>..<head>`, etc.).
+//! `patch/<hash>`, `commits/<base>..<head>`, etc.).
 //!
 //! The "live" working-tree sources (`worktree`, `staged`, `unstaged`,
 //! `staged-and-unstaged`) embed the short SHA of the current HEAD so that a
@@ -73,6 +73,8 @@ pub enum SlugSource {
     Unstaged(String),
     StagedAndUnstaged(String),
     Pristine,
+    /// Raw patch review. Carries the hash of the patch text.
+    Patch(String),
     Commits(CommitRange),
     WorktreeAndCommits(CommitRange),
     StagedUnstagedAndCommits(CommitRange),
@@ -112,7 +114,13 @@ impl fmt::Display for PrSlug {
             ForgeKind::GitLab => "gl",
             ForgeKind::Bitbucket => "bb",
             ForgeKind::AzureDevOps => "az",
+            ForgeKind::Gitea => "gt",
+            ForgeKind::BitbucketDataCenter => "bd",
+            ForgeKind::Gerrit => "gr",
         };
+        if self.owner.is_empty() {
+            return write!(f, "{}:{}/pr/{}", kind, self.repo, self.number);
+        }
         write!(
             f,
             "{}:{}/{}/pr/{}",
@@ -138,6 +146,7 @@ impl fmt::Display for SlugSource {
             SlugSource::Unstaged(head) => write!(f, "unstaged/{head}"),
             SlugSource::StagedAndUnstaged(head) => write!(f, "staged-and-unstaged/{head}"),
             SlugSource::Pristine => f.write_str("pristine"),
+            SlugSource::Patch(hash) => write!(f, "patch/{hash}"),
             SlugSource::Commits(r) => write!(f, "commits/{}..{}", r.base, r.head),
             SlugSource::WorktreeAndCommits(r) => {
                 write!(f, "worktree-and-commits/{}..{}", r.base, r.head)
@@ -183,6 +192,9 @@ impl FromStr for Slug {
                 "gl" => ForgeKind::GitLab,
                 "bb" => ForgeKind::Bitbucket,
                 "az" => ForgeKind::AzureDevOps,
+                "gt" => ForgeKind::Gitea,
+                "bd" => ForgeKind::BitbucketDataCenter,
+                "gr" => ForgeKind::Gerrit,
                 other => return Err(SlugParseError::UnknownForge(other.to_string())),
             };
             return parse_pr(forge, rest).map(Slug::Pr);
@@ -195,9 +207,11 @@ fn parse_pr(forge: ForgeKind, rest: &str) -> Result<PrSlug, SlugParseError> {
     // Shape: `<owner>/<repo>/pr/<number>`. `<owner>` may itself contain
     // slashes (GitLab subgroups, Azure DevOps `org/project`), so anchor on the
     // trailing `pr/<number>` and treat everything before `<repo>` as the owner.
+    // Only Gerrit has ownerless (top-level) projects: `gr:<repo>/pr/<number>`.
     let parts: Vec<&str> = rest.split('/').collect();
     let n = parts.len();
-    if n < 4 || parts[n - 2] != "pr" {
+    let min_parts = if forge == ForgeKind::Gerrit { 3 } else { 4 };
+    if n < min_parts || parts[n - 2] != "pr" {
         return Err(SlugParseError::InvalidShape(rest.to_string()));
     }
     let number: u64 = parts[n - 1]
@@ -205,7 +219,7 @@ fn parse_pr(forge: ForgeKind, rest: &str) -> Result<PrSlug, SlugParseError> {
         .map_err(|_| SlugParseError::InvalidPrNumber(parts[n - 1].to_string()))?;
     let repo = parts[n - 3];
     let owner = parts[..n - 3].join("/");
-    if owner.is_empty() || repo.is_empty() {
+    if (owner.is_empty() && forge != ForgeKind::Gerrit) || repo.is_empty() {
         return Err(SlugParseError::InvalidShape(rest.to_string()));
     }
     Ok(PrSlug {
@@ -275,6 +289,9 @@ fn parse_source(s: &str) -> Result<SlugSource, SlugParseError> {
     if s == "pristine" {
         return Ok(SlugSource::Pristine);
     }
+    if let Some(hash) = s.strip_prefix("patch/") {
+        return live_source(hash, s, SlugSource::Patch);
+    }
     if let Some(head) = s.strip_prefix("worktree/") {
         return live_source(head, s, SlugSource::Worktree);
     }
@@ -531,6 +548,12 @@ fn build_source(
         SessionDiffSource::Unstaged => Ok(SlugSource::Unstaged(live_head())),
         SessionDiffSource::StagedAndUnstaged => Ok(SlugSource::StagedAndUnstaged(live_head())),
         SessionDiffSource::Pristine => Ok(SlugSource::Pristine),
+        SessionDiffSource::Patch => Ok(SlugSource::Patch(
+            head_commit
+                .strip_prefix(crate::vcs::patch::PATCH_SESSION_PREFIX)
+                .unwrap_or(head_commit)
+                .to_string(),
+        )),
         SessionDiffSource::CommitRange => {
             Ok(SlugSource::Commits(range_from(commit_range, diff_source)?))
         }
@@ -653,6 +676,7 @@ mod tests {
         assert_roundtrip("agavra/tuicr@main/unstaged/abc1234");
         assert_roundtrip("agavra/tuicr@feature-login/staged-and-unstaged/abc1234");
         assert_roundtrip("agavra/tuicr@main/pristine");
+        assert_roundtrip("tuicr@fix.patch/patch/0123456789abcdef");
         assert_roundtrip("agavra/tuicr@~abc1234/worktree/abc1234");
         assert_roundtrip("agavra/tuicr@main/commits/abc1234..def5678");
         assert_roundtrip("agavra/tuicr@main/worktree-and-commits/abc1234..def5678");
@@ -683,6 +707,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn should_roundtrip_gitea_pr_slug() {
+        assert_roundtrip("gt:infra/deploy-tools/pr/17");
+        let parsed: Slug = "gt:infra/deploy-tools/pr/17".parse().unwrap();
+        match parsed {
+            Slug::Pr(pr) => assert_eq!(pr.forge, ForgeKind::Gitea),
+            other => panic!("expected PR slug, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn should_roundtrip_bitbucket_data_center_pr_slug() {
+        assert_roundtrip("bd:~alice/dotfiles/pr/3");
+        let parsed: Slug = "bd:PLAT/build/pr/42".parse().unwrap();
+        match parsed {
+            Slug::Pr(pr) => {
+                assert_eq!(pr.forge, ForgeKind::BitbucketDataCenter);
+                assert_eq!(pr.owner, "PLAT");
+            }
+            other => panic!("expected PR slug, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn should_roundtrip_gerrit_pr_slugs_with_and_without_owner() {
+        assert_roundtrip("gr:platform/build/pr/4711");
+        assert_roundtrip("gr:nova/pr/12");
+        let parsed: Slug = "gr:nova/pr/12".parse().unwrap();
+        match parsed {
+            Slug::Pr(pr) => {
+                assert_eq!(pr.forge, ForgeKind::Gerrit);
+                assert_eq!(pr.owner, "");
+                assert_eq!(pr.repo, "nova");
+            }
+            other => panic!("expected PR slug, got {other:?}"),
+        }
+        assert!("gh:nova/pr/12".parse::<Slug>().is_err());
+    }
+
     // ---------- Parse errors ----------
 
     #[test]
@@ -915,6 +978,22 @@ mod tests {
         assert_eq!(slug.to_string(), "tuicr@main/worktree/none");
     }
 
+    #[test]
+    fn should_key_patch_slug_by_full_content_hash() {
+        let slug = build_local_slug(
+            (None, "tuicr".to_string()),
+            Some("0001-fix.patch"),
+            "patch:0123456789abcdef",
+            SessionDiffSource::Patch,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            slug.to_string(),
+            "tuicr@0001-fix.patch/patch/0123456789abcdef"
+        );
+    }
+
     #[test]
     fn should_build_commits_slug_from_range() {
         let range = vec![
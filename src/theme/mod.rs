@@ -41,6 +41,9 @@ pub struct Theme {
     pub diff_add_bg: Color,
     pub diff_del: Color,
     pub diff_del_bg: Color,
+    /// Background for the changed words inside a paired deletion/addition.
+    pub diff_add_emphasis_bg: Color,
+    pub diff_del_emphasis_bg: Color,
     pub diff_context: Color,
     pub diff_hunk_header: Color,
     pub expanded_context_fg: Color,
@@ -118,6 +121,8 @@ impl Theme {
             diff_add_bg: Color::Rgb(0, 60, 20),
             diff_del: Color::Rgb(240, 90, 90),
             diff_del_bg: Color::Rgb(70, 0, 0),
+            diff_add_emphasis_bg: Color::Rgb(24, 108, 50),
+            diff_del_emphasis_bg: Color::Rgb(121, 27, 27),
             diff_context: Color::Rgb(200, 200, 200),
             diff_hunk_header: Color::Rgb(90, 200, 255),
             expanded_context_fg: Color::Rgb(140, 140, 140),
@@ -189,7 +194,9 @@ impl Theme {
             diff_add_bg: Color::Rgb(220, 255, 220), // Very light green bg
             diff_del: Color::Rgb(120, 0, 0),        // Dark red text
             diff_del_bg: Color::Rgb(255, 240, 240), // Very light pink bg
-            diff_context: Color::Rgb(0, 0, 0),      // Black for max readability
+            diff_add_emphasis_bg: Color::Rgb(154, 202, 154),
+            diff_del_emphasis_bg: Color::Rgb(214, 168, 168),
+            diff_context: Color::Rgb(0, 0, 0), // Black for max readability
             diff_hunk_header: Color::Rgb(0, 60, 140),
             expanded_context_fg: Color::Rgb(60, 60, 60),
 
@@ -271,6 +278,8 @@ impl Theme {
             diff_add_bg: Color::Rgb(222, 240, 205),
             diff_del: Color::Rgb(140, 0, 0),
             diff_del_bg: Color::Rgb(252, 225, 224),
+            diff_add_emphasis_bg: Color::Rgb(155, 192, 143),
+            diff_del_emphasis_bg: Color::Rgb(218, 157, 156),
             diff_context: base00,
             diff_hunk_header: blue,
             expanded_context_fg: base1,
@@ -344,6 +353,8 @@ impl Theme {
             diff_add_bg: Color::Rgb(0, 60, 20),
             diff_del: Color::Rgb(240, 90, 90),
             diff_del_bg: Color::Rgb(70, 0, 0),
+            diff_add_emphasis_bg: Color::Rgb(24, 108, 50),
+            diff_del_emphasis_bg: Color::Rgb(121, 27, 27),
             diff_context: base0,
             diff_hunk_header: blue,
             expanded_context_fg: base01,
@@ -501,6 +512,8 @@ impl Theme {
             diff_add_bg: Color::Rgb(238, 247, 208),
             diff_del: Color::Rgb(240, 113, 120),
             diff_del_bg: Color::Rgb(253, 235, 236),
+            diff_add_emphasis_bg: Color::Rgb(206, 226, 145),
+            diff_del_emphasis_bg: Color::Rgb(249, 198, 201),
             diff_context: Color::Rgb(92, 103, 115),
             diff_hunk_header: Color::Rgb(54, 163, 217),
             expanded_context_fg: Color::Rgb(130, 140, 153),
@@ -590,6 +603,8 @@ impl Theme {
             diff_add_bg: Color::Rgb(35, 53, 41),
             diff_del: red,
             diff_del_bg: Color::Rgb(58, 36, 41),
+            diff_add_emphasis_bg: Color::Rgb(65, 102, 61),
+            diff_del_emphasis_bg: Color::Rgb(113, 61, 68),
             diff_context: fg,
             diff_hunk_header: blue,
             expanded_context_fg: dim,
@@ -652,6 +667,8 @@ impl Theme {
             diff_add_bg: Color::Rgb(44, 56, 43),
             diff_del: Color::Rgb(224, 108, 117),
             diff_del_bg: Color::Rgb(58, 45, 47),
+            diff_add_emphasis_bg: Color::Rgb(76, 97, 66),
+            diff_del_emphasis_bg: Color::Rgb(107, 63, 68),
             diff_context: Color::Rgb(171, 178, 191),
             diff_hunk_header: Color::Rgb(86, 182, 194),
             expanded_context_fg: Color::Rgb(92, 99, 112),
@@ -720,6 +737,8 @@ impl Theme {
             diff_add_bg: Color::Rgb(230, 255, 236),
             diff_del: Color::Rgb(207, 34, 46),
             diff_del_bg: Color::Rgb(255, 235, 233),
+            diff_add_emphasis_bg: Color::Rgb(168, 216, 181),
+            diff_del_emphasis_bg: Color::Rgb(240, 174, 176),
             diff_context: Color::Rgb(31, 35, 40),
             diff_hunk_header: Color::Rgb(9, 105, 218),
             expanded_context_fg: Color::Rgb(110, 119, 129),
@@ -780,6 +799,8 @@ impl Theme {
             diff_add_bg: Color::Rgb(16, 35, 28),
             diff_del: Color::Rgb(248, 81, 73),
             diff_del_bg: Color::Rgb(48, 27, 31),
+            diff_add_emphasis_bg: Color::Rgb(30, 80, 43),
+            diff_del_emphasis_bg: Color::Rgb(108, 43, 43),
             diff_context: Color::Rgb(230, 237, 243),
             diff_hunk_header: Color::Rgb(88, 166, 255),
             expanded_context_fg: Color::Rgb(139, 148, 158),
@@ -839,6 +860,8 @@ impl Theme {
             diff_add_bg: Color::Rgb(0x18, 0x30, 0x51),
             diff_del: Color::Rgb(0xf0, 0x88, 0x3e),
             diff_del_bg: Color::Rgb(0x41, 0x28, 0x1b),
+            diff_add_emphasis_bg: Color::Rgb(43, 83, 133),
+            diff_del_emphasis_bg: Color::Rgb(117, 68, 37),
             diff_context: Color::Rgb(0xf0, 0xf6, 0xfc),
             diff_hunk_header: Color::Rgb(0x44, 0x93, 0xf8),
             expanded_context_fg: Color::Rgb(0x65, 0x6c, 0x76),
@@ -915,6 +938,8 @@ impl Theme {
             diff_add_bg: Color::Rgb(32, 48, 59), // #20303b
             diff_del: red,
             diff_del_bg: Color::Rgb(55, 34, 44), // #37222c
+            diff_add_emphasis_bg: Color::Rgb(69, 95, 73),
+            diff_del_emphasis_bg: Color::Rgb(112, 59, 73),
             diff_context: fg_dark,
             diff_hunk_header: blue,
             expanded_context_fg: dark3,
@@ -992,6 +1017,8 @@ impl Theme {
             diff_add_bg: Color::Rgb(197, 221, 230), // #c5dde6 (light blue-tinted)
             diff_del: red,
             diff_del_bg: Color::Rgb(243, 197, 203), // #f3c5cb (light rose)
+            diff_add_emphasis_bg: Color::Rgb(164, 189, 178),
+            diff_del_emphasis_bg: Color::Rgb(243, 150, 172),
             diff_context: fg,
             diff_hunk_header: blue,
             expanded_context_fg: dark3,
@@ -1343,6 +1370,8 @@ fn catppuccin_theme(flavor: CatppuccinFlavor, syntect_theme: EmbeddedThemeName)
     let diff_del_bg = blend(flavor.base, flavor.red, 20);
     let syntax_add_bg = blend(flavor.base, flavor.green, 16);
     let syntax_del_bg = blend(flavor.base, flavor.red, 16);
+    let diff_add_emphasis_bg = blend(flavor.base, flavor.green, 40);
+    let diff_del_emphasis_bg = blend(flavor.base, flavor.red, 40);
 
     Theme {
         highlighter: OnceLock::new(),
@@ -1359,6 +1388,8 @@ fn catppuccin_theme(flavor: CatppuccinFlavor, syntect_theme: EmbeddedThemeName)
         diff_add_bg,
         diff_del: flavor.red,
         diff_del_bg,
+        diff_add_emphasis_bg,
+        diff_del_emphasis_bg,
         diff_context: flavor.text,
         diff_hunk_header: flavor.blue,
         expanded_context_fg: flavor.overlay1,
@@ -1435,6 +1466,8 @@ fn gruvbox_theme(flavor: GruvboxFlavor) -> Theme {
         diff_add_bg: flavor.bg_green,
         diff_del: flavor.red,
         diff_del_bg: flavor.bg_red,
+        diff_add_emphasis_bg: blend(flavor.bg_green, flavor.green, 30),
+        diff_del_emphasis_bg: blend(flavor.bg_red, flavor.red, 30),
         diff_context: flavor.fg0,
         diff_hunk_header: flavor.blue,
         expanded_context_fg: flavor.grey1,
@@ -1506,6 +1539,8 @@ fn everforest_theme(flavor: EverforestFlavor) -> Theme {
         diff_add_bg: flavor.bg_green,
         diff_del: flavor.red,
         diff_del_bg: flavor.bg_red,
+        diff_add_emphasis_bg: blend(flavor.bg_green, flavor.green, 30),
+        diff_del_emphasis_bg: blend(flavor.bg_red, flavor.red, 30),
         diff_context: flavor.fg,
         diff_hunk_header: flavor.blue,
         expanded_context_fg: flavor.grey1,
@@ -1557,6 +1592,8 @@ fn nord_theme(flavor: NordFlavor) -> Theme {
     let diff_del_bg = blend(flavor.bg0, flavor.red, 15);
     let syntax_add_bg = blend(flavor.bg0, flavor.green, 10);
     let syntax_del_bg = blend(flavor.bg0, flavor.red, 10);
+    let diff_add_emphasis_bg = blend(flavor.bg0, flavor.green, 35);
+    let diff_del_emphasis_bg = blend(flavor.bg0, flavor.red, 35);
 
     Theme {
         highlighter: OnceLock::new(),
@@ -1571,6 +1608,8 @@ fn nord_theme(flavor: NordFlavor) -> Theme {
         diff_add_bg,
         diff_del: flavor.red,
         diff_del_bg,
+        diff_add_emphasis_bg,
+        diff_del_emphasis_bg,
         diff_context: flavor.fg0,
         diff_hunk_header: flavor.frost1,
         expanded_context_fg: flavor.bg3,
@@ -2026,6 +2065,17 @@ fn require_local_theme_color(table: &toml::Table, key: &str) -> Result<Color, St
     parse_color_value(raw).ok_or_else(|| format!("Theme key '{key}' could not be parsed"))
 }
 
+fn parse_optional_local_theme_color(
+    table: &toml::Table,
+    key: &str,
+) -> Result<Option<Color>, String> {
+    if table.contains_key(key) {
+        require_local_theme_color(table, key).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
 fn parse_optional_local_theme_string(
     table: &toml::Table,
     key: &str,
@@ -2086,6 +2136,8 @@ const LOCAL_THEME_KEYS: &[&str] = &[
     "diff_add_bg",
     "diff_del",
     "diff_del_bg",
+    "diff_add_emphasis_bg",
+    "diff_del_emphasis_bg",
     "diff_context",
     "diff_hunk_header",
     "expanded_context_fg",
@@ -2155,6 +2207,17 @@ fn load_local_theme_from_path(path: &Path) -> Result<(Theme, Vec<String>), Strin
         None => SyntaxThemeSource::Embedded(fallback_embedded_theme_for_panel_bg(panel_bg)),
     };
 
+    let diff_add = require_local_theme_color(table, "diff_add")?;
+    let diff_add_bg = require_local_theme_color(table, "diff_add_bg")?;
+    let diff_del = require_local_theme_color(table, "diff_del")?;
+    let diff_del_bg = require_local_theme_color(table, "diff_del_bg")?;
+    // Word emphasis is optional so themes written before it keep loading;
+    // the fallback tints the line background toward the diff foreground.
+    let diff_add_emphasis_bg = parse_optional_local_theme_color(table, "diff_add_emphasis_bg")?
+        .unwrap_or_else(|| blend(diff_add_bg, diff_add, 30));
+    let diff_del_emphasis_bg = parse_optional_local_theme_color(table, "diff_del_emphasis_bg")?
+        .unwrap_or_else(|| blend(diff_del_bg, diff_del, 30));
+
     let theme = Theme {
         highlighter: OnceLock::new(),
         panel_bg,
@@ -2162,10 +2225,12 @@ fn load_local_theme_from_path(path: &Path) -> Result<(Theme, Vec<String>), Strin
         fg_primary: require_local_theme_color(table, "fg_primary")?,
         fg_secondary: require_local_theme_color(table, "fg_secondary")?,
         fg_dim: require_local_theme_color(table, "fg_dim")?,
-        diff_add: require_local_theme_color(table, "diff_add")?,
-        diff_add_bg: require_local_theme_color(table, "diff_add_bg")?,
-        diff_del: require_local_theme_color(table, "diff_del")?,
-        diff_del_bg: require_local_theme_color(table, "diff_del_bg")?,
+        diff_add,
+        diff_add_bg,
+        diff_del,
+        diff_del_bg,
+        diff_add_emphasis_bg,
+        diff_del_emphasis_bg,
         diff_context: require_local_theme_color(table, "diff_context")?,
         diff_hunk_header: require_local_theme_color(table, "diff_hunk_header")?,
         expanded_context_fg: require_local_theme_color(table, "expanded_context_fg")?,
@@ -2688,6 +2753,36 @@ mode_bg = "#82aaff"
         assert_eq!(theme.search_match_bg, Color::Rgb(68, 78, 68));
     }
 
+    #[test]
+    fn should_derive_word_emphasis_bg_when_local_theme_omits_it() {
+        let dir = tempdir().expect("failed to create temp dir");
+        let path = write_local_theme(dir.path(), "local-teal", &sample_local_theme_body(""));
+
+        let (theme, warnings) =
+            load_local_theme_from_path(&path).expect("local theme should load successfully");
+        assert!(warnings.is_empty());
+        assert_eq!(theme.diff_add_emphasis_bg, Color::Rgb(61, 95, 49));
+        assert_eq!(theme.diff_del_emphasis_bg, Color::Rgb(108, 43, 42));
+    }
+
+    #[test]
+    fn should_use_explicit_word_emphasis_bg_from_local_theme() {
+        let dir = tempdir().expect("failed to create temp dir");
+        let path = write_local_theme(
+            dir.path(),
+            "local-teal",
+            &sample_local_theme_body(
+                "diff_add_emphasis_bg = \"#1f5f33\"\ndiff_del_emphasis_bg = \"#6b2222\"",
+            ),
+        );
+
+        let (theme, warnings) =
+            load_local_theme_from_path(&path).expect("local theme should load successfully");
+        assert!(warnings.is_empty());
+        assert_eq!(theme.diff_add_emphasis_bg, Color::Rgb(0x1f, 0x5f, 0x33));
+        assert_eq!(theme.diff_del_emphasis_bg, Color::Rgb(0x6b, 0x22, 0x22));
+    }
+
     #[test]
     fn should_load_checked_in_tuicr_teal_example() {
         let path = Path::new(env!("CARGO_MANIFEST_DIR"))
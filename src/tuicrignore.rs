@@ -14,7 +14,7 @@ pub fn filter_diff_files(repo_root: &Path, diff_files: Vec<DiffFile>) -> Vec<Dif
         .into_iter()
         .filter(|file| {
             !matcher
-                .matched_path_or_any_parents(file.display_path(), false)
+                .matched_path_or_any_parents(file.file_path(), false)
                 .is_ignore()
         })
         .collect()
@@ -84,6 +84,7 @@ mod tests {
             is_too_large: false,
             is_commit_message: false,
             content_hash: 0,
+            review_key: None,
         }
     }
 
@@ -232,6 +233,7 @@ mod tests {
             is_too_large: false,
             is_commit_message: false,
             content_hash: 0,
+            review_key: None,
         };
         let kept = make_diff_file("src/lib.rs");
 
@@ -4,13 +4,16 @@ use ratatui::{
     widgets::Block,
 };
 
-use crate::app::{App, InputMode};
+use crate::app::{App, ConfirmAction, InputMode};
 use crate::ui::comment_navigator::render_comment_navigator;
 use crate::ui::diff_view::render_diff_view;
 use crate::ui::file_list::render_file_list;
 use crate::ui::inline_commit_selector::render_inline_commit_selector;
 use crate::ui::selector::render_commit_select;
-use crate::ui::{comment_panel, help_popup, status_bar, styles, submit_modals, summary_popup};
+use crate::ui::{
+    check_log_view, comment_panel, diff_view, help_popup, status_bar, styles, submit_modals,
+    summary_popup,
+};
 
 const FILE_LIST_MIN_HEIGHT: u16 = 4;
 const COMMENT_NAVIGATOR_MIN_HEIGHT: u16 = 4;
@@ -74,11 +77,26 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         help_popup::render_help(frame, app);
     }
 
-    // Comment input is now rendered inline in the diff view
+    // Comment input is rendered inline in the diff view, except replies to
+    // remote threads, which have no anchor row of their own.
+    if app.input_mode == InputMode::Comment && app.comment_reply_thread_id.is_some() {
+        let comment_type = diff_view::comment_type_presentation(app, &app.comment_type);
+        comment_panel::render_thread_reply_input(frame, app, comment_type);
+    }
 
     // Render confirm dialog if in confirm mode
-    if app.input_mode == InputMode::Confirm {
-        comment_panel::render_confirm_dialog(frame, app, "Copy review to clipboard?");
+    if app.input_mode == InputMode::Confirm
+        && let Some(action) = &app.pending_confirm
+    {
+        match action {
+            ConfirmAction::OpenBlameCommit(commit) => {
+                comment_panel::render_blame_commit_popup(frame, app, commit);
+            }
+            ConfirmAction::PrAction(pr_action) => {
+                comment_panel::render_confirm_dialog(frame, app, &pr_action.prompt());
+            }
+            _ => comment_panel::render_confirm_dialog(frame, app, action.prompt()),
+        }
     }
 
     // Submit-flow modals.
@@ -174,6 +192,8 @@ fn render_content_view(frame: &mut Frame, app: &mut App, area: Rect) {
     app.diff_area = Some(area);
     if app.input_mode == InputMode::Summary {
         summary_popup::render_summary(frame, app, area);
+    } else if app.input_mode == InputMode::CheckLog || app.searching_check_log() {
+        check_log_view::render_check_log(frame, app, area);
     } else {
         render_diff_view(frame, app, area);
     }
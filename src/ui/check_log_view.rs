@@ -0,0 +1,68 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::app::App;
+use crate::ui::styles;
+
+/// Gutter marking lines that Enter can jump from.
+const TARGET_MARKER: &str = "→ ";
+const NO_MARKER: &str = "  ";
+
+/// Render the CI log opened by `:check-log` in place of the diff.
+pub fn render_check_log(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = &app.theme;
+    let Some(log) = app.check_log.as_mut() else {
+        return;
+    };
+    let block = Block::default()
+        .title(format!(
+            " {} — j/k select, Enter to jump, / search, Esc to return ",
+            log.check_name
+        ))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .style(styles::panel_style(theme))
+        .border_style(styles::border_style(theme, true));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    log.viewport_height = inner.height as usize;
+    let max_offset = log.lines.len().saturating_sub(log.viewport_height);
+    log.scroll_offset = log.scroll_offset.min(max_offset);
+    log.keep_selection_visible();
+
+    let selected_style = styles::selected_style(theme);
+    let marker_style = styles::current_line_indicator_style(theme);
+    let visible: Vec<Line> = log
+        .lines
+        .iter()
+        .zip(&log.targets)
+        .enumerate()
+        .skip(log.scroll_offset)
+        .take(log.viewport_height)
+        .map(|(idx, (text, target))| {
+            let marker = if target.is_some() {
+                TARGET_MARKER
+            } else {
+                NO_MARKER
+            };
+            let line = Line::from(vec![
+                Span::styled(marker, marker_style),
+                Span::raw(text.clone()),
+            ]);
+            if idx == log.selected {
+                line.style(selected_style)
+            } else {
+                line
+            }
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(visible).style(styles::panel_style(theme)),
+        inner,
+    );
+}
@@ -3,7 +3,7 @@ use ratatui::{
     layout::{Constraint, Flex, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
 };
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
@@ -12,6 +12,7 @@ use crate::forge::traits::ForgeKind;
 use crate::model::LineRange;
 use crate::theme::Theme;
 use crate::ui::styles;
+use crate::vcs::CommitInfo;
 
 /// Content prefix used on every comment-body line. 4 pad chars + `│` + 2 spaces.
 /// The 4-char left pad lets the bar painter draw `│` up through diff lines
@@ -295,10 +296,12 @@ pub fn format_comment_input_lines(
 ///
 /// Visually distinct from local drafts: the `[forge @author]` badge on
 /// the root header, and a muted palette throughout for resolved/outdated
-/// threads.
+/// threads. Queued local `replies` render last, inside the same box, with a
+/// `↳ reply draft` separator so they read as not-yet-posted.
 pub fn format_remote_thread_lines(
     theme: &Theme,
     thread: &crate::forge::remote_comments::RemoteReviewThread,
+    replies: &[crate::model::Comment],
     muted: bool,
     forge_kind: Option<ForgeKind>,
 ) -> Vec<Line<'static>> {
@@ -363,6 +366,26 @@ pub fn format_remote_thread_lines(
         let _ = iter.peek();
     }
 
+    for reply in replies {
+        // Sent replies linger until the next thread fetch replaces them.
+        let (label, reply_style) = if reply.is_locked() {
+            ("↳ reply sent ", Style::default().fg(theme.fg_dim))
+        } else {
+            ("↳ reply draft ", Style::default().fg(theme.pending))
+        };
+        result.push(Line::from(vec![
+            Span::styled("    ├── ".to_string(), border_style),
+            Span::styled(label.to_string(), reply_style),
+            Span::styled("─".repeat(26), border_style),
+        ]));
+        for line in reply.content.split('\n') {
+            result.push(Line::from(vec![
+                Span::styled("    │  ".to_string(), border_style),
+                Span::styled(line.to_string(), reply_style),
+            ]));
+        }
+    }
+
     result.push(Line::from(vec![Span::styled(
         "    ╰".to_string() + &"─".repeat(39),
         border_style,
@@ -371,6 +394,31 @@ pub fn format_remote_thread_lines(
     result
 }
 
+/// Format a linter or compiler diagnostic as a single unboxed row under the
+/// line it points at: `◆ warning[code]: message L12:5`.
+pub fn format_diagnostic_line(
+    theme: &Theme,
+    diagnostic: &crate::diagnostics::Diagnostic,
+) -> Line<'static> {
+    let severity_style = styles::diagnostic_style(theme, diagnostic.severity);
+    let location = match diagnostic.column {
+        Some(column) => format!(" L{}:{column}", diagnostic.line),
+        None => format!(" L{}", diagnostic.line),
+    };
+    Line::from(vec![
+        Span::styled("    ◆ ".to_string(), severity_style),
+        Span::styled(
+            diagnostic.title(),
+            severity_style.add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(": {}", diagnostic.message),
+            Style::default().fg(theme.fg_secondary),
+        ),
+        Span::styled(location, styles::dim_style(theme)),
+    ])
+}
+
 /// Format a remote review summary (the body of a `PullRequestReview`) as a
 /// box with a `[forge @author <state>]` header. Renders at review scope —
 /// no line anchor — so the top corner is `╭`, not the line-anchored `├`.
@@ -422,18 +470,14 @@ fn forge_badge_label(kind: Option<ForgeKind>) -> &'static str {
         Some(ForgeKind::GitHub) => "github",
         Some(ForgeKind::GitLab) => "gitlab",
         Some(ForgeKind::Bitbucket) => "bitbucket",
+        Some(ForgeKind::BitbucketDataCenter) => "bitbucket-dc",
         Some(ForgeKind::AzureDevOps) => "azure",
+        Some(ForgeKind::Gitea) => "gitea",
+        Some(ForgeKind::Gerrit) => "gerrit",
         None => "forge",
     }
 }
 
-/// Format a comment as multiple lines with a box border (themed version).
-///
-/// `author` advertises the comment's author in the top-row badge and tints
-/// the box border. Callers pass `Some(name)` for non-self comments — the
-/// resulting badge reads `[TYPE @name]`, mirroring the remote forge badge
-/// format used for remote PR threads. `None` keeps the existing neutral
-/// `[TYPE]` badge and theme border.
 /// Render `content` as markdown-highlighted, border-prefixed, pre-wrapped lines
 /// (no cursor). Colors come from the active syntect theme. Used for displayed
 /// comment bodies; the editor box does its own variant with cursor handling.
@@ -463,6 +507,16 @@ fn markdown_body_lines(
     out
 }
 
+/// Format a comment as multiple lines with a box border (themed version).
+///
+/// `author` advertises the comment's author in the top-row badge and tints
+/// the box border. Callers pass `Some(name)` for non-self comments — the
+/// resulting badge reads `[TYPE @name]`, mirroring the remote forge badge
+/// format used for remote PR threads. `None` keeps the existing neutral
+/// `[TYPE]` badge and theme border.
+///
+/// `resolved` comments keep their box but render in the muted palette with
+/// a `resolved` badge, like resolved remote threads.
 pub fn format_comment_lines(
     theme: &Theme,
     comment_type: CommentTypePresentation,
@@ -470,22 +524,40 @@ pub fn format_comment_lines(
     line_range: Option<LineRange>,
     width: usize,
     author: Option<&str>,
+    resolved: bool,
 ) -> Vec<Line<'static>> {
-    let type_style = styles::comment_type_style(theme, comment_type.color);
-    let border_style = match author {
-        Some(name) => Style::default()
-            .fg(styles::author_color_for(name))
-            .add_modifier(ratatui::style::Modifier::BOLD),
-        None => styles::comment_border_style(theme, comment_type.color),
+    let (type_style, border_style) = if resolved {
+        let muted = Style::default().fg(theme.fg_dim);
+        (muted, muted)
+    } else {
+        let border_style = match author {
+            Some(name) => Style::default()
+                .fg(styles::author_color_for(name))
+                .add_modifier(ratatui::style::Modifier::BOLD),
+            None => styles::comment_border_style(theme, comment_type.color),
+        };
+        (
+            styles::comment_type_style(theme, comment_type.color),
+            border_style,
+        )
     };
 
     // `None` comments have an empty label: drop the `[TYPE]` badge, keeping the
     // author tag when present so per-author coloring still reads.
-    let badge_text = match (author, comment_type.label.is_empty()) {
-        (Some(name), true) => format!("[@{name}] "),
-        (Some(name), false) => format!("[{} @{name}] ", comment_type.label),
-        (None, true) => String::new(),
-        (None, false) => format!("[{}] ", comment_type.label),
+    let mut tags: Vec<String> = Vec::new();
+    if !comment_type.label.is_empty() {
+        tags.push(comment_type.label.clone());
+    }
+    if let Some(name) = author {
+        tags.push(format!("@{name}"));
+    }
+    if resolved {
+        tags.push("resolved".to_string());
+    }
+    let badge_text = if tags.is_empty() {
+        String::new()
+    } else {
+        format!("[{}] ", tags.join(" "))
     };
     let badge_width = badge_text.width();
 
@@ -517,12 +589,24 @@ pub fn format_comment_lines(
     ]));
 
     // Content lines — markdown-highlighted, pre-wrapped at content_area.
-    result.extend(markdown_body_lines(
-        theme,
-        content,
-        content_area,
-        border_style,
-    ));
+    // Resolved bodies skip highlighting so the whole box reads as muted.
+    if resolved {
+        for text in content.split('\n') {
+            for seg in wrap_segments(text, content_area) {
+                result.push(Line::from(vec![
+                    Span::styled(BORDER_PREFIX, border_style),
+                    Span::styled(seg.to_string(), border_style),
+                ]));
+            }
+        }
+    } else {
+        result.extend(markdown_body_lines(
+            theme,
+            content,
+            content_area,
+            border_style,
+        ));
+    }
 
     // Bottom border — "    ╰" = 5 chars, fill to width
     result.push(Line::from(vec![Span::styled(
@@ -566,6 +650,119 @@ pub fn render_confirm_dialog(frame: &mut Frame, app: &App, message: &str) {
     frame.render_widget(paragraph, inner);
 }
 
+/// Blame popup: the full message of the commit that last touched the cursor
+/// line, with the prompt to open it as a review of its own.
+pub fn render_blame_commit_popup(frame: &mut Frame, app: &App, commit: &CommitInfo) {
+    let theme = &app.theme;
+    let area = centered_rect(60, 50, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" Commit {} ", commit.short_id))
+        .borders(Borders::ALL)
+        .style(styles::popup_style(theme))
+        .border_style(styles::border_style(theme, true));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let mut lines = vec![
+        Line::from(vec![Span::styled("Commit: ", bold), Span::raw(&commit.id)]),
+        Line::from(vec![
+            Span::styled("Author: ", bold),
+            Span::raw(&commit.author),
+        ]),
+        Line::from(vec![
+            Span::styled("Date:   ", bold),
+            Span::raw(commit.time.format("%Y-%m-%d %H:%M").to_string()),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(&commit.summary, bold)),
+    ];
+    if let Some(body) = commit.body.as_deref().filter(|b| !b.trim().is_empty()) {
+        lines.push(Line::from(""));
+        lines.extend(body.lines().map(Line::raw));
+    }
+
+    let [text_area, prompt_area] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .style(styles::popup_style(theme))
+            .wrap(Wrap { trim: false }),
+        text_area,
+    );
+    let prompt = Line::from(vec![
+        Span::styled("[Y]", bold),
+        Span::raw(" review this commit    "),
+        Span::styled("[N]", bold),
+        Span::raw(" close"),
+    ]);
+    frame.render_widget(
+        Paragraph::new(prompt)
+            .style(styles::popup_style(theme))
+            .alignment(ratatui::layout::Alignment::Center),
+        prompt_area,
+    );
+}
+
+/// Reply input for a remote review thread. A reply has no diff anchor of its
+/// own, so the input floats over the diff instead of rendering inline.
+pub fn render_thread_reply_input(
+    frame: &mut Frame,
+    app: &mut App,
+    comment_type: CommentTypePresentation,
+) {
+    let theme = &app.theme;
+    let area = centered_rect(60, 40, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let author = app
+        .comment_reply_thread_id
+        .as_deref()
+        .and_then(|id| app.forge_review_threads.iter().find(|t| t.id == id))
+        .and_then(|t| t.root())
+        .and_then(|c| c.author.as_deref())
+        .unwrap_or("unknown");
+    let block = Block::default()
+        .title(format!(" Reply to @{author} "))
+        .borders(Borders::ALL)
+        .style(styles::popup_style(theme))
+        .border_style(styles::border_style(theme, true));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let vim_mode = app.comment_vim_mode_label();
+    let (lines, cursor_info) = format_comment_input_lines(
+        theme,
+        comment_type,
+        &app.comment_buffer,
+        app.comment_cursor,
+        None,
+        app.editing_comment_id.is_some(),
+        inner.width as usize,
+        vim_mode.as_ref().map(|(t, w)| (t.as_str(), *w)),
+    );
+    // Keep the cursor row visible once the draft outgrows the popup.
+    let scroll = cursor_info
+        .line_offset
+        .saturating_sub(inner.height.saturating_sub(1) as usize) as u16;
+
+    let paragraph = Paragraph::new(lines)
+        .style(styles::popup_style(theme))
+        .scroll((scroll, 0));
+    frame.render_widget(paragraph, inner);
+
+    app.comment_cursor_screen_pos = Some((
+        inner.x + cursor_info.column,
+        inner.y + cursor_info.line_offset as u16 - scroll,
+    ));
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
     let vertical = Layout::vertical([Constraint::Percentage(percent_y)]).flex(Flex::Center);
     let horizontal = Layout::horizontal([Constraint::Percentage(percent_x)]).flex(Flex::Center);
@@ -623,6 +820,7 @@ mod tests {
                     // cursor-indicator column.
                     viewport_width.saturating_sub(1),
                     None,
+                    false,
                 );
                 assert_eq!(
                     App::comment_display_lines(&comment, viewport_width),
@@ -967,6 +1165,7 @@ mod tests {
             None,
             80,
             None,
+            false,
         );
         // Header + footer wrap the body; reconstruct must round-trip the text.
         assert_eq!(reconstruct(&lines), content);
@@ -978,6 +1177,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resolved_comment_is_muted_with_resolved_badge() {
+        let theme = test_theme();
+        let content = "**bold** and `code`";
+        let lines = format_comment_lines(
+            &theme,
+            CommentTypePresentation {
+                label: "NOTE".to_string(),
+                color: Color::Blue,
+            },
+            content,
+            None,
+            80,
+            None,
+            true,
+        );
+        let header: String = lines[0].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert!(header.contains("resolved"), "header={header:?}");
+        assert_eq!(reconstruct(&lines), content);
+        assert!(
+            lines[1]
+                .spans
+                .iter()
+                .all(|span| span.style.fg == Some(theme.fg_dim)),
+            "resolved body should render in the muted color"
+        );
+    }
+
     #[test]
     fn markdown_highlighting_splits_line_into_runs() {
         // An inline-code line should yield multiple styled content spans (proof
@@ -1012,7 +1239,7 @@ mod tests {
         };
 
         let lines =
-            format_remote_thread_lines(&test_theme(), &thread, false, Some(ForgeKind::GitLab));
+            format_remote_thread_lines(&test_theme(), &thread, &[], false, Some(ForgeKind::GitLab));
         let header = lines[0]
             .spans
             .iter()
@@ -1023,6 +1250,46 @@ mod tests {
         assert!(!header.contains("[github @alice]"));
     }
 
+    #[test]
+    fn remote_thread_renders_queued_replies_inside_the_box() {
+        let thread = crate::forge::remote_comments::RemoteReviewThread {
+            id: "thread".to_string(),
+            path: "src/lib.rs".to_string(),
+            line: Some(1),
+            side: crate::forge::remote_comments::RemoteCommentSide::Right,
+            is_resolved: false,
+            is_outdated: false,
+            comments: vec![crate::forge::remote_comments::RemoteReviewComment {
+                id: "comment".to_string(),
+                author: Some("alice".to_string()),
+                body: "why?".to_string(),
+                created_at: None,
+                in_reply_to: None,
+                url: String::new(),
+            }],
+        };
+        let replies = vec![crate::model::Comment::new(
+            "because\nof this".to_string(),
+            crate::model::CommentType::None,
+            None,
+        )];
+
+        let lines = format_remote_thread_lines(&test_theme(), &thread, &replies, false, None);
+        let text: Vec<String> = lines
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+
+        assert_eq!(
+            lines.len(),
+            crate::forge::remote_comments::thread_display_lines(&thread, &replies)
+        );
+        assert!(text[2].contains("↳ reply draft"));
+        assert!(text[3].ends_with("because"));
+        assert!(text[4].ends_with("of this"));
+        assert!(text[5].contains('╰'));
+    }
+
     #[test]
     fn remote_summary_badge_uses_github_for_github_comments() {
         let summary = crate::forge::remote_comments::RemoteReviewSummary {
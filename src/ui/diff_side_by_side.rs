@@ -14,16 +14,18 @@ use crate::model::{DiffLine, FileStatus, LineOrigin, LineRange, LineSide};
 use crate::theme::Theme;
 use crate::ui::comment_panel;
 use crate::ui::diff_view::{
-    apply_horizontal_scroll, comment_type_presentation, cursor_indicator, cursor_indicator_spaced,
-    diff_stat_title, hunk_header_text_and_style, paint_cursor_line_highlight,
-    paint_visual_selection_overlay, populate_row_to_annotation, render_expander_line,
-    render_hidden_lines, scroll_comment_input_into_view, skip_comment_box,
+    apply_horizontal_scroll, comment_presentation, comment_type_presentation, cursor_indicator,
+    cursor_indicator_spaced, diff_stat_title, hunk_header_text_and_style,
+    paint_cursor_line_highlight, paint_visual_selection_overlay, populate_row_to_annotation,
+    render_expander_line, render_hidden_lines, scroll_comment_input_into_view, skip_comment_box,
 };
 use crate::ui::styles;
 use crate::ui::text_utils::{
-    apply_search_highlight_pairs, apply_search_highlight_spans, apply_search_highlight_text,
-    truncate_or_pad, truncate_or_pad_pairs_by_chars, truncate_or_pad_spans, wrap_spans,
+    apply_emphasis_pairs, apply_emphasis_spans, apply_search_highlight_pairs,
+    apply_search_highlight_spans, apply_search_highlight_text, truncate_or_pad,
+    truncate_or_pad_pairs_by_chars, truncate_or_pad_spans, wrap_spans,
 };
+use crate::ui::word_diff::{line_text, word_emphasis};
 use crate::vcs::git::calculate_gap;
 
 #[derive(Clone, Default)]
@@ -40,6 +42,7 @@ fn content_spans_for_diff_line(
     theme: &Theme,
     dl: &DiffLine,
     origin: LineOrigin,
+    emphasis: Option<&[(usize, usize)]>,
     search: Option<(&str, Style)>,
 ) -> Vec<Span<'static>> {
     let base = match origin {
@@ -52,6 +55,12 @@ fn content_spans_for_diff_line(
     } else {
         vec![Span::styled(dl.content.clone(), base)]
     };
+    let spans = match emphasis {
+        Some(ranges) => {
+            apply_emphasis_spans(spans, ranges, styles::diff_emphasis_style(theme, origin))
+        }
+        None => spans,
+    };
     match search {
         Some((needle, hl)) => apply_search_highlight_spans(spans, needle, hl),
         None => spans,
@@ -120,6 +129,8 @@ fn pad_spans_to_width(
 
 struct SideSpec {
     lineno: Option<u32>,
+    /// Blame label drawn into the line-number padding, new side only.
+    blame: Option<String>,
     marker: &'static str,
     marker_style: Style,
 }
@@ -140,6 +151,7 @@ fn sbs_row_prefixes(
         .lineno
         .map(|n| format!("{n:>lw$}"))
         .unwrap_or_else(|| " ".repeat(lw));
+    let new_num = crate::ui::diff_view::overlay_blame(new_num, right.blame);
 
     let left_prefix = vec![
         Span::styled(indicator, styles::current_line_indicator_style(theme)),
@@ -214,6 +226,10 @@ impl SideBySideContext<'_> {
         Some((needle, self.search_style))
     }
 
+    fn blame_for(&self, file_idx: usize, new_lineno: Option<u32>) -> Option<String> {
+        crate::ui::diff_view::blame_label(self.app, file_idx, new_lineno)
+    }
+
     fn display_lineno(&self, source_line: Option<u32>, line_idx: usize) -> Option<u32> {
         source_line.map(|line| {
             if self.app.relative_line_numbers {
@@ -381,11 +397,12 @@ pub(super) fn render_side_by_side_diff(frame: &mut Frame, app: &mut App, area: R
             }
             let comment_lines = comment_panel::format_comment_lines(
                 &app.theme,
-                comment_type_presentation(app, &comment.comment_type),
+                comment_presentation(app, comment),
                 &comment.content,
                 None,
                 ctx.panel_width.saturating_sub(1),
                 (comment.author != app.username).then_some(comment.author.as_str()),
+                comment.resolved,
             );
             for mut comment_line in comment_lines {
                 let indicator = cursor_indicator(line_idx, ctx.current_line_idx);
@@ -415,6 +432,7 @@ pub(super) fn render_side_by_side_diff(frame: &mut Frame, app: &mut App, area: R
                 let thread_lines = comment_panel::format_remote_thread_lines(
                     &app.theme,
                     thread,
+                    app.session.thread_replies_for(&thread.id),
                     muted,
                     app.forge_kind(),
                 );
@@ -572,11 +590,12 @@ pub(super) fn render_side_by_side_diff(frame: &mut Frame, app: &mut App, area: R
                     }
                     let comment_lines = comment_panel::format_comment_lines(
                         &app.theme,
-                        comment_type_presentation(app, &comment.comment_type),
+                        comment_presentation(app, comment),
                         &comment.content,
                         None,
                         ctx.panel_width.saturating_sub(1),
                         (comment.author != app.username).then_some(comment.author.as_str()),
+                        comment.resolved,
                     );
                     for mut comment_line in comment_lines {
                         let indicator = cursor_indicator(line_idx, ctx.current_line_idx);
@@ -677,6 +696,7 @@ pub(super) fn render_side_by_side_diff(frame: &mut Frame, app: &mut App, area: R
                                 &mut lines,
                                 &mut line_idx,
                                 expanded_line,
+                                file_idx,
                                 &ctx,
                             );
                         }
@@ -750,6 +770,7 @@ pub(super) fn render_side_by_side_diff(frame: &mut Frame, app: &mut App, area: R
                                 &mut lines,
                                 &mut line_idx,
                                 expanded_line,
+                                file_idx,
                                 &ctx,
                             );
                         }
@@ -825,6 +846,7 @@ pub(super) fn render_side_by_side_diff(frame: &mut Frame, app: &mut App, area: R
                             &mut lines,
                             &mut line_idx,
                             expanded_line,
+                            file_idx,
                             &ctx,
                         );
                     }
@@ -858,6 +880,7 @@ pub(super) fn render_side_by_side_diff(frame: &mut Frame, app: &mut App, area: R
                             &mut lines,
                             &mut line_idx,
                             expanded_line,
+                            file_idx,
                             &ctx,
                         );
                     }
@@ -1077,6 +1100,7 @@ fn render_sbs_expanded_context_line(
     lines: &mut Vec<Line<'_>>,
     line_idx: &mut usize,
     expanded_line: &crate::model::DiffLine,
+    file_idx: usize,
     ctx: &SideBySideContext,
 ) {
     let theme = ctx.theme;
@@ -1091,6 +1115,10 @@ fn render_sbs_expanded_context_line(
         .display_lineno(expanded_line.new_lineno, *line_idx)
         .map(|n| format!("{n:>lw$} "))
         .unwrap_or_else(|| " ".repeat(lw + 1));
+    let new_line_num = crate::ui::diff_view::overlay_blame(
+        new_line_num,
+        ctx.blame_for(file_idx, expanded_line.new_lineno),
+    );
     let ec_style = styles::expanded_context_style(theme);
     let content_cell = plain_cell_spans(
         &expanded_line.content,
@@ -1255,6 +1283,10 @@ fn render_context_line_side_by_side(
             .display_lineno(diff_line.new_lineno, line_idx)
             .map(|n| format!("{n:>w$}"))
             .unwrap_or_else(|| " ".repeat(w));
+        let new_line_num = crate::ui::diff_view::overlay_blame(
+            new_line_num,
+            ctx.blame_for(file_idx, diff_line.new_lineno),
+        );
 
         let indicator = cursor_indicator(line_idx, ctx.current_line_idx);
 
@@ -1301,18 +1333,20 @@ fn render_context_line_side_by_side(
         lines.push(Line::from(spans));
 
         let content =
-            content_spans_for_diff_line(ctx.theme, diff_line, LineOrigin::Context, search);
+            content_spans_for_diff_line(ctx.theme, diff_line, LineOrigin::Context, None, search);
         let ctx_style = styles::diff_context_style(ctx.theme);
         let (lp, rp) = sbs_row_prefixes(
             ctx.theme,
             indicator,
             SideSpec {
                 lineno: ctx.display_lineno(diff_line.old_lineno, line_idx),
+                blame: None,
                 marker: " ",
                 marker_style: ctx_style,
             },
             SideSpec {
                 lineno: ctx.display_lineno(diff_line.new_lineno, line_idx),
+                blame: ctx.blame_for(file_idx, diff_line.new_lineno),
                 marker: " ",
                 marker_style: ctx_style,
             },
@@ -1357,6 +1391,7 @@ fn render_context_line_side_by_side(
                 line_idx,
                 lines,
             );
+            line_idx = add_diagnostics_to_line(new_ln, ctx, file.display_path(), line_idx, lines);
         }
     }
 
@@ -1397,6 +1432,10 @@ fn render_deletion_addition_pair_side_by_side(
         let del_opt = (offset < del_count).then(|| &hunk_lines[start_idx + offset]);
         let add_opt = (offset < add_count).then(|| &hunk_lines[add_start + offset]);
         if ctx.is_visible(line_idx) {
+            let emphasis = match (del_opt, add_opt) {
+                (Some(dl), Some(al)) => word_emphasis(&line_text(dl), &line_text(al)),
+                _ => None,
+            };
             let indicator = cursor_indicator(line_idx, ctx.current_line_idx);
 
             let mut spans = vec![Span::styled(
@@ -1413,6 +1452,7 @@ fn render_deletion_addition_pair_side_by_side(
                     ctx.content_width,
                     ctx.lineno_width,
                     ctx.display_lineno(del_line.old_lineno, line_idx),
+                    emphasis.as_ref().map(|e| e.old.as_slice()),
                     ctx.search_for(line_idx),
                 );
             } else {
@@ -1430,6 +1470,8 @@ fn render_deletion_addition_pair_side_by_side(
                     ctx.content_width,
                     ctx.lineno_width,
                     ctx.display_lineno(add_line.new_lineno, line_idx),
+                    ctx.blame_for(file_idx, add_line.new_lineno),
+                    emphasis.as_ref().map(|e| e.new.as_slice()),
                     ctx.search_for(line_idx),
                 );
             } else {
@@ -1446,6 +1488,7 @@ fn render_deletion_addition_pair_side_by_side(
                             ctx.theme,
                             dl,
                             LineOrigin::Deletion,
+                            emphasis.as_ref().map(|e| e.old.as_slice()),
                             ctx.search_for(line_idx),
                         ),
                         column_pad_style(ctx.theme, dl, LineOrigin::Deletion),
@@ -1462,6 +1505,7 @@ fn render_deletion_addition_pair_side_by_side(
                             ctx.theme,
                             al,
                             LineOrigin::Addition,
+                            emphasis.as_ref().map(|e| e.new.as_slice()),
                             ctx.search_for(line_idx),
                         ),
                         column_pad_style(ctx.theme, al, LineOrigin::Addition),
@@ -1476,11 +1520,13 @@ fn render_deletion_addition_pair_side_by_side(
                 indicator,
                 SideSpec {
                     lineno: left_lineno,
+                    blame: None,
                     marker: left_marker,
                     marker_style: left_marker_style,
                 },
                 SideSpec {
                     lineno: right_lineno,
+                    blame: add_opt.and_then(|al| ctx.blame_for(file_idx, al.new_lineno)),
                     marker: right_marker,
                     marker_style: right_marker_style,
                 },
@@ -1557,6 +1603,8 @@ fn render_deletion_addition_pair_side_by_side(
                     line_idx,
                     lines,
                 );
+                line_idx =
+                    add_diagnostics_to_line(new_ln, ctx, file.display_path(), line_idx, lines);
             }
         }
     }
@@ -1590,6 +1638,8 @@ fn render_standalone_addition_side_by_side(
             ctx.content_width,
             ctx.lineno_width,
             ctx.display_lineno(diff_line.new_lineno, line_idx),
+            ctx.blame_for(file_idx, diff_line.new_lineno),
+            None,
             ctx.search_for(line_idx),
         );
 
@@ -1600,6 +1650,7 @@ fn render_standalone_addition_side_by_side(
             ctx.theme,
             diff_line,
             LineOrigin::Addition,
+            None,
             ctx.search_for(line_idx),
         );
         let right_pad = column_pad_style(ctx.theme, diff_line, LineOrigin::Addition);
@@ -1608,11 +1659,13 @@ fn render_standalone_addition_side_by_side(
             indicator,
             SideSpec {
                 lineno: None,
+                blame: None,
                 marker: " ",
                 marker_style: Style::default(),
             },
             SideSpec {
                 lineno: ctx.display_lineno(diff_line.new_lineno, line_idx),
+                blame: ctx.blame_for(file_idx, diff_line.new_lineno),
                 marker: "▌",
                 marker_style: styles::diff_add_style(ctx.theme),
             },
@@ -1657,6 +1710,7 @@ fn render_standalone_addition_side_by_side(
                 line_idx,
                 lines,
             );
+            line_idx = add_diagnostics_to_line(new_ln, ctx, file.display_path(), line_idx, lines);
         }
     }
 
@@ -1717,6 +1771,7 @@ fn render_commit_message_line_side_by_side(
                 line_idx,
                 lines,
             );
+            line_idx = add_diagnostics_to_line(new_ln, ctx, file.display_path(), line_idx, lines);
         }
     }
 
@@ -1724,6 +1779,7 @@ fn render_commit_message_line_side_by_side(
 }
 
 /// Add deletion line spans to the spans vector
+#[allow(clippy::too_many_arguments)]
 fn add_deletion_spans(
     theme: &Theme,
     spans: &mut Vec<Span>,
@@ -1731,6 +1787,7 @@ fn add_deletion_spans(
     content_width: usize,
     lw: usize,
     display_lineno: Option<u32>,
+    emphasis: Option<&[(usize, usize)]>,
     search: Option<(&str, Style)>,
 ) {
     let line_num = display_lineno
@@ -1743,12 +1800,32 @@ fn add_deletion_spans(
     ));
     spans.push(Span::styled("▌".to_string(), styles::diff_del_style(theme)));
 
+    let emphasis_style = styles::diff_emphasis_style(theme, LineOrigin::Deletion);
     // Use syntax highlighting if available
     if let Some(ref highlighted) = diff_line.highlighted_spans {
         let syntax_pad_style = Style::default().fg(theme.diff_del).bg(theme.syntax_del_bg);
-        let content_spans =
-            searched_cell_spans(highlighted, content_width, syntax_pad_style, search);
+        let content_spans = match emphasis {
+            Some(ranges) => searched_cell_spans(
+                &apply_emphasis_pairs(highlighted, ranges, emphasis_style),
+                content_width,
+                syntax_pad_style,
+                search,
+            ),
+            None => searched_cell_spans(highlighted, content_width, syntax_pad_style, search),
+        };
         spans.extend(content_spans);
+    } else if let Some(ranges) = emphasis {
+        let style = styles::diff_del_style(theme);
+        spans.extend(searched_cell_spans(
+            &apply_emphasis_pairs(
+                &[(style, diff_line.content.clone())],
+                ranges,
+                emphasis_style,
+            ),
+            content_width,
+            style,
+            search,
+        ));
     } else {
         spans.extend(plain_cell_spans(
             &diff_line.content,
@@ -1760,6 +1837,7 @@ fn add_deletion_spans(
 }
 
 /// Add addition line spans to the spans vector
+#[allow(clippy::too_many_arguments)]
 fn add_addition_spans(
     theme: &Theme,
     spans: &mut Vec<Span>,
@@ -1767,11 +1845,14 @@ fn add_addition_spans(
     content_width: usize,
     lw: usize,
     display_lineno: Option<u32>,
+    blame: Option<String>,
+    emphasis: Option<&[(usize, usize)]>,
     search: Option<(&str, Style)>,
 ) {
     let line_num = display_lineno
         .map(|n| format!("{n:>lw$}"))
         .unwrap_or_else(|| " ".repeat(lw));
+    let line_num = crate::ui::diff_view::overlay_blame(line_num, blame);
 
     spans.push(Span::styled(
         format!("{line_num} "),
@@ -1779,12 +1860,32 @@ fn add_addition_spans(
     ));
     spans.push(Span::styled("▌".to_string(), styles::diff_add_style(theme)));
 
+    let emphasis_style = styles::diff_emphasis_style(theme, LineOrigin::Addition);
     // Use syntax highlighting if available
     if let Some(ref highlighted) = diff_line.highlighted_spans {
         let syntax_pad_style = Style::default().fg(theme.diff_add).bg(theme.syntax_add_bg);
-        let content_spans =
-            searched_cell_spans(highlighted, content_width, syntax_pad_style, search);
+        let content_spans = match emphasis {
+            Some(ranges) => searched_cell_spans(
+                &apply_emphasis_pairs(highlighted, ranges, emphasis_style),
+                content_width,
+                syntax_pad_style,
+                search,
+            ),
+            None => searched_cell_spans(highlighted, content_width, syntax_pad_style, search),
+        };
         spans.extend(content_spans);
+    } else if let Some(ranges) = emphasis {
+        let style = styles::diff_add_style(theme);
+        spans.extend(searched_cell_spans(
+            &apply_emphasis_pairs(
+                &[(style, diff_line.content.clone())],
+                ranges,
+                emphasis_style,
+            ),
+            content_width,
+            style,
+            search,
+        ));
     } else {
         spans.extend(plain_cell_spans(
             &diff_line.content,
@@ -1846,6 +1947,7 @@ fn add_remote_threads_to_line(
         let thread_lines = comment_panel::format_remote_thread_lines(
             ctx.theme,
             thread,
+            ctx.app.session.thread_replies_for(&thread.id),
             muted,
             ctx.app.forge_kind(),
         );
@@ -1868,6 +1970,33 @@ fn add_remote_threads_to_line(
     line_idx
 }
 
+/// Render the diagnostics anchored at new-side `line_num`, one full-width
+/// row each.
+fn add_diagnostics_to_line(
+    line_num: u32,
+    ctx: &SideBySideContext,
+    file_path: &std::path::Path,
+    mut line_idx: usize,
+    lines: &mut Vec<Line>,
+) -> usize {
+    let diagnostics = &ctx.app.diagnostics;
+    for diagnostic in
+        &diagnostics[crate::diagnostics::diagnostics_at(diagnostics, file_path, line_num)]
+    {
+        let mut diagnostic_line = comment_panel::format_diagnostic_line(ctx.theme, diagnostic);
+        diagnostic_line.spans.insert(
+            0,
+            Span::styled(
+                cursor_indicator(line_idx, ctx.current_line_idx),
+                styles::current_line_indicator_style(ctx.theme),
+            ),
+        );
+        lines.push(diagnostic_line);
+        line_idx += 1;
+    }
+    line_idx
+}
+
 fn add_comments_to_line(
     line_num: u32,
     line_comments: &std::collections::HashMap<u32, Vec<crate::model::Comment>>,
@@ -1958,6 +2087,7 @@ fn add_comments_to_line(
                             line_range,
                             ctx.panel_width.saturating_sub(1),
                             (comment.author != ctx.app.username).then_some(comment.author.as_str()),
+                            comment.resolved,
                         );
                         for mut comment_line in comment_lines {
                             let indicator = cursor_indicator(line_idx, ctx.current_line_idx);
@@ -2122,6 +2252,7 @@ mod remote_comments_side_by_side_snapshot_tests {
             old_count: 1,
             new_start: 1,
             new_count: 2,
+            raw_body: None,
         };
         let hunks = vec![hunk];
         let content_hash = DiffFile::compute_content_hash(&hunks);
@@ -2134,6 +2265,7 @@ mod remote_comments_side_by_side_snapshot_tests {
             is_too_large: false,
             is_commit_message: false,
             content_hash,
+            review_key: None,
         }
     }
 
@@ -2252,6 +2384,7 @@ mod remote_comments_side_by_side_snapshot_tests {
             old_count: 0,
             new_start: 1,
             new_count: 120,
+            raw_body: None,
         }];
         let content_hash = DiffFile::compute_content_hash(&hunks);
         let path = PathBuf::from("src/lib.rs");
@@ -2264,6 +2397,7 @@ mod remote_comments_side_by_side_snapshot_tests {
             is_too_large: false,
             is_commit_message: false,
             content_hash,
+            review_key: None,
         };
 
         let mut app = make_pr_app_with(vec![file]);
@@ -2353,6 +2487,7 @@ mod remote_comments_side_by_side_snapshot_tests {
             old_count: 1,
             new_start: 1,
             new_count: 1,
+            raw_body: None,
         }];
         let content_hash = DiffFile::compute_content_hash(&hunks);
         DiffFile {
@@ -2364,6 +2499,7 @@ mod remote_comments_side_by_side_snapshot_tests {
             is_too_large: false,
             is_commit_message: false,
             content_hash,
+            review_key: None,
         }
     }
 
@@ -2382,6 +2518,7 @@ mod remote_comments_side_by_side_snapshot_tests {
             old_count: 1,
             new_start: 0,
             new_count: 0,
+            raw_body: None,
         }];
         let content_hash = DiffFile::compute_content_hash(&hunks);
         DiffFile {
@@ -2393,6 +2530,7 @@ mod remote_comments_side_by_side_snapshot_tests {
             is_too_large: false,
             is_commit_message: false,
             content_hash,
+            review_key: None,
         }
     }
 
@@ -2551,6 +2689,7 @@ mod remote_comments_side_by_side_snapshot_tests {
             old_count: 0,
             new_start: 1,
             new_count,
+            raw_body: None,
         }];
         let content_hash = DiffFile::compute_content_hash(&hunks);
         DiffFile {
@@ -2562,6 +2701,7 @@ mod remote_comments_side_by_side_snapshot_tests {
             is_too_large: false,
             is_commit_message: true,
             content_hash,
+            review_key: None,
         }
     }
 
@@ -2596,4 +2736,45 @@ mod remote_comments_side_by_side_snapshot_tests {
             "expected the commit message body to render exactly once, got {checked}"
         );
     }
+
+    fn find_cell(buf: &Buffer, needle: &str) -> Option<(u16, u16)> {
+        let needle: Vec<String> = needle.chars().map(String::from).collect();
+        for y in 0..buf.area.height {
+            let row: Vec<String> = (0..buf.area.width).map(|x| char_at(buf, x, y)).collect();
+            if let Some(x) = row
+                .windows(needle.len())
+                .position(|w| w == needle.as_slice())
+            {
+                return Some((x as u16, y));
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn should_emphasize_changed_words_in_side_by_side_pair() {
+        // given
+        let mut app = make_pr_app();
+        app.diff_files = vec![diff_file_with_pair(
+            "let alpha = OLDTOKEN;",
+            "let alpha = NEWTOKEN;",
+        )];
+        app.rebuild_annotations();
+        let (del_bg, del_emphasis, add_emphasis) = (
+            app.theme.diff_del_bg,
+            app.theme.diff_del_emphasis_bg,
+            app.theme.diff_add_emphasis_bg,
+        );
+
+        // when
+        let buf = draw_sbs(&mut app, 160, 20);
+
+        // then
+        let (old_x, old_y) = find_cell(&buf, "OLDTOKEN").expect("old token rendered");
+        let (new_x, new_y) = find_cell(&buf, "NEWTOKEN").expect("new token rendered");
+        let (let_x, let_y) = find_cell(&buf, "let alpha").expect("shared prefix rendered");
+        assert_eq!(buf[(old_x, old_y)].bg, del_emphasis);
+        assert_eq!(buf[(new_x, new_y)].bg, add_emphasis);
+        assert_eq!(buf[(let_x, let_y)].bg, del_bg);
+    }
 }
@@ -15,8 +15,8 @@ use crate::model::{FileStatus, LineOrigin, LineRange, LineSide};
 use crate::theme::Theme;
 use crate::ui::comment_panel;
 use crate::ui::diff_view::{
-    apply_horizontal_scroll, comment_box_visible, comment_type_presentation, cursor_indicator,
-    cursor_indicator_spaced, diff_stat_title, hunk_header_text_and_style,
+    apply_horizontal_scroll, comment_box_visible, comment_presentation, comment_type_presentation,
+    cursor_indicator, cursor_indicator_spaced, diff_stat_title, hunk_header_text_and_style,
     paint_cursor_line_highlight, paint_unified_diff_rows_with, paint_visual_selection_overlay,
     populate_row_to_annotation, push_comment_bar, render_expander_line, render_hidden_lines,
     scroll_comment_input_into_view, skip_comment_box, unified_line_bg_style,
@@ -166,11 +166,12 @@ pub(super) fn render_unified_diff(frame: &mut Frame, app: &mut App, area: Rect)
             }
             let comment_lines = comment_panel::format_comment_lines(
                 &app.theme,
-                comment_type_presentation(app, &comment.comment_type),
+                comment_presentation(app, comment),
                 &comment.content,
                 None,
                 comment_width,
                 (comment.author != app.username).then_some(comment.author.as_str()),
+                comment.resolved,
             );
             for mut comment_line in comment_lines {
                 let indicator = cursor_indicator(line_idx, current_line_idx);
@@ -200,6 +201,7 @@ pub(super) fn render_unified_diff(frame: &mut Frame, app: &mut App, area: Rect)
                 let thread_lines = comment_panel::format_remote_thread_lines(
                     &app.theme,
                     thread,
+                    app.session.thread_replies_for(&thread.id),
                     muted,
                     app.forge_kind(),
                 );
@@ -366,11 +368,12 @@ pub(super) fn render_unified_diff(frame: &mut Frame, app: &mut App, area: Rect)
                     }
                     let comment_lines = comment_panel::format_comment_lines(
                         &app.theme,
-                        comment_type_presentation(app, &comment.comment_type),
+                        comment_presentation(app, comment),
                         &comment.content,
                         None,
                         comment_width,
                         (comment.author != app.username).then_some(comment.author.as_str()),
+                        comment.resolved,
                     );
                     for mut comment_line in comment_lines {
                         let indicator = cursor_indicator(line_idx, current_line_idx);
@@ -481,6 +484,11 @@ pub(super) fn render_unified_diff(frame: &mut Frame, app: &mut App, area: Rect)
                                 lw,
                                 app.relative_line_numbers,
                                 line_search,
+                                crate::ui::diff_view::blame_label(
+                                    app,
+                                    file_idx,
+                                    expanded_line.new_lineno,
+                                ),
                             );
                         }
                     }
@@ -561,6 +569,11 @@ pub(super) fn render_unified_diff(frame: &mut Frame, app: &mut App, area: Rect)
                                 lw,
                                 app.relative_line_numbers,
                                 line_search,
+                                crate::ui::diff_view::blame_label(
+                                    app,
+                                    file_idx,
+                                    expanded_line.new_lineno,
+                                ),
                             );
                         }
                     }
@@ -581,7 +594,7 @@ pub(super) fn render_unified_diff(frame: &mut Frame, app: &mut App, area: Rect)
                 }
 
                 // Diff lines
-                for diff_line in &hunk.lines {
+                for (hunk_line_idx, diff_line) in hunk.lines.iter().enumerate() {
                     // Hot path: skip span/style allocation entirely for diff
                     // lines outside the viewport. Comment handling below still
                     // runs so `line_idx` stays exact and any comment box that
@@ -610,6 +623,17 @@ pub(super) fn render_unified_diff(frame: &mut Frame, app: &mut App, area: Rect)
                         } else {
                             crate::ui::diff_view::unified_line_number_field(diff_line, lw)
                         };
+                        let line_num_str = match diff_line.origin {
+                            LineOrigin::Deletion => line_num_str,
+                            _ => crate::ui::diff_view::overlay_blame(
+                                line_num_str,
+                                crate::ui::diff_view::blame_label(
+                                    app,
+                                    file_idx,
+                                    diff_line.new_lineno,
+                                ),
+                            ),
+                        };
                         let prefix = crate::ui::diff_view::unified_line_origin_marker(diff_line);
 
                         let indicator = cursor_indicator(line_idx, current_line_idx);
@@ -660,6 +684,19 @@ pub(super) fn render_unified_diff(frame: &mut Frame, app: &mut App, area: Rect)
                             Span::styled(String::new(), eol_style)
                         });
 
+                        // Word emphasis goes on after the EOL style is taken
+                        // from the last span so it never bleeds into the row fill.
+                        if let Some(ranges) =
+                            crate::ui::word_diff::line_emphasis(&hunk.lines, hunk_line_idx)
+                        {
+                            let content_spans = line_spans.split_off(content_start);
+                            line_spans.extend(crate::ui::text_utils::apply_emphasis_spans(
+                                content_spans,
+                                &ranges,
+                                styles::diff_emphasis_style(&app.theme, diff_line.origin),
+                            ));
+                        }
+
                         if let Some(needle) = app.search_paint_at(line_idx) {
                             let content_spans = line_spans.split_off(content_start);
                             line_spans.extend(crate::ui::text_utils::apply_search_highlight_spans(
@@ -778,6 +815,7 @@ pub(super) fn render_unified_diff(frame: &mut Frame, app: &mut App, area: Rect)
                                                 comment_width,
                                                 (comment.author != app.username)
                                                     .then_some(comment.author.as_str()),
+                                                comment.resolved,
                                             );
                                             for mut comment_line in comment_lines {
                                                 let is_current = line_idx == current_line_idx;
@@ -964,6 +1002,7 @@ pub(super) fn render_unified_diff(frame: &mut Frame, app: &mut App, area: Rect)
                                                 comment_width,
                                                 (comment.author != app.username)
                                                     .then_some(comment.author.as_str()),
+                                                comment.resolved,
                                             );
                                             for mut comment_line in comment_lines {
                                                 let indicator =
@@ -1002,6 +1041,14 @@ pub(super) fn render_unified_diff(frame: &mut Frame, app: &mut App, area: Rect)
                             LineSide::New,
                             &mut comment_bars,
                         );
+                        render_diagnostics_for_anchor(
+                            &mut lines,
+                            &mut line_idx,
+                            current_line_idx,
+                            app,
+                            path,
+                            new_ln,
+                        );
 
                         // Render inline input for new line comment (new side)
                         if is_line_comment_mode && app.editing_comment_id.is_none() {
@@ -1092,6 +1139,11 @@ pub(super) fn render_unified_diff(frame: &mut Frame, app: &mut App, area: Rect)
                             lw,
                             app.relative_line_numbers,
                             line_search,
+                            crate::ui::diff_view::blame_label(
+                                app,
+                                file_idx,
+                                expanded_line.new_lineno,
+                            ),
                         );
                     }
                 }
@@ -1132,6 +1184,11 @@ pub(super) fn render_unified_diff(frame: &mut Frame, app: &mut App, area: Rect)
                             lw,
                             app.relative_line_numbers,
                             line_search,
+                            crate::ui::diff_view::blame_label(
+                                app,
+                                file_idx,
+                                expanded_line.new_lineno,
+                            ),
                         );
                     }
                 }
@@ -1390,8 +1447,13 @@ fn render_remote_threads_for_anchor(
 
         // Render the entire thread as one fused box so it reads as a
         // single discussion unit.
-        let thread_lines =
-            comment_panel::format_remote_thread_lines(&app.theme, thread, muted, app.forge_kind());
+        let thread_lines = comment_panel::format_remote_thread_lines(
+            &app.theme,
+            thread,
+            app.session.thread_replies_for(&thread.id),
+            muted,
+            app.forge_kind(),
+        );
         let box_top_row = *line_idx;
         for mut comment_line in thread_lines {
             let indicator = cursor_indicator(*line_idx, current_line_idx);
@@ -1413,6 +1475,31 @@ fn render_remote_threads_for_anchor(
     }
 }
 
+/// Render the diagnostics anchored at new-side `line` of `file_path`, one
+/// row each.
+fn render_diagnostics_for_anchor(
+    lines: &mut Vec<ratatui::text::Line<'static>>,
+    line_idx: &mut usize,
+    current_line_idx: usize,
+    app: &App,
+    file_path: &std::path::Path,
+    line: u32,
+) {
+    let range = crate::diagnostics::diagnostics_at(&app.diagnostics, file_path, line);
+    for diagnostic in &app.diagnostics[range] {
+        let mut diagnostic_line = comment_panel::format_diagnostic_line(&app.theme, diagnostic);
+        diagnostic_line.spans.insert(
+            0,
+            ratatui::text::Span::styled(
+                cursor_indicator(*line_idx, current_line_idx),
+                styles::current_line_indicator_style(&app.theme),
+            ),
+        );
+        lines.push(diagnostic_line);
+        *line_idx += 1;
+    }
+}
+
 /// Render a single expanded context line (shared by unified + side-by-side via unified path)
 #[allow(clippy::too_many_arguments)]
 fn render_expanded_context_line(
@@ -1424,6 +1511,7 @@ fn render_expanded_context_line(
     lw: usize,
     relative_line_numbers: bool,
     search: Option<(&str, Style)>,
+    blame: Option<String>,
 ) {
     let indicator = cursor_indicator(*line_idx, current_line_idx);
     let line_num = if relative_line_numbers {
@@ -1436,6 +1524,7 @@ fn render_expanded_context_line(
     } else {
         crate::ui::diff_view::expanded_context_lineno_field(expanded_line, lw)
     };
+    let line_num = crate::ui::diff_view::overlay_blame(line_num, blame);
     let mut line_spans = vec![
         Span::styled(indicator, styles::current_line_indicator_style(theme)),
         Span::styled(line_num, styles::expanded_context_style(theme)),
@@ -1553,6 +1642,7 @@ mod remote_comments_snapshot_tests {
             old_count: 1,
             new_start: 1,
             new_count: 2,
+            raw_body: None,
         };
         let hunks = vec![hunk];
         let content_hash = DiffFile::compute_content_hash(&hunks);
@@ -1565,6 +1655,7 @@ mod remote_comments_snapshot_tests {
             is_too_large: false,
             is_commit_message: false,
             content_hash,
+            review_key: None,
         }
     }
 
@@ -1580,6 +1671,7 @@ mod remote_comments_snapshot_tests {
             is_too_large: false,
             is_commit_message: false,
             content_hash,
+            review_key: None,
         }
     }
 
@@ -1734,6 +1826,7 @@ mod remote_comments_snapshot_tests {
             old_count: 0,
             new_start: 1,
             new_count,
+            raw_body: None,
         }];
         let content_hash = DiffFile::compute_content_hash(&hunks);
         DiffFile {
@@ -1745,6 +1838,7 @@ mod remote_comments_snapshot_tests {
             is_too_large: false,
             is_commit_message: true,
             content_hash,
+            review_key: None,
         }
     }
 
@@ -1967,6 +2061,7 @@ mod remote_comments_snapshot_tests {
             old_count: 0,
             new_start: 1,
             new_count: 1,
+            raw_body: None,
         };
         let hunks = vec![hunk];
         let content_hash = DiffFile::compute_content_hash(&hunks);
@@ -1979,6 +2074,7 @@ mod remote_comments_snapshot_tests {
             is_too_large: false,
             is_commit_message: false,
             content_hash,
+            review_key: None,
         };
         let mut app = make_revision_app(vec![file]);
         app.set_diff_wrap(true);
@@ -2033,6 +2129,7 @@ mod remote_comments_snapshot_tests {
             old_count: 0,
             new_start: 1,
             new_count: 120,
+            raw_body: None,
         }];
         let content_hash = DiffFile::compute_content_hash(&hunks);
         let path = PathBuf::from("src/lib.rs");
@@ -2045,6 +2142,7 @@ mod remote_comments_snapshot_tests {
             is_too_large: false,
             is_commit_message: false,
             content_hash,
+            review_key: None,
         };
 
         let mut app = make_revision_app(vec![file]);
@@ -2119,6 +2217,7 @@ mod remote_comments_snapshot_tests {
             old_count: 0,
             new_start: 1,
             new_count: 31,
+            raw_body: None,
         };
         let hunks = vec![hunk];
         let content_hash = DiffFile::compute_content_hash(&hunks);
@@ -2131,6 +2230,7 @@ mod remote_comments_snapshot_tests {
             is_too_large: false,
             is_commit_message: false,
             content_hash,
+            review_key: None,
         };
         let mut app = make_revision_app(vec![file]);
         app.set_diff_wrap(true);
@@ -2184,6 +2284,7 @@ mod remote_comments_snapshot_tests {
             old_count: 0,
             new_start: 1,
             new_count: 1,
+            raw_body: None,
         };
         let hunks = vec![hunk];
         let content_hash = DiffFile::compute_content_hash(&hunks);
@@ -2197,6 +2298,7 @@ mod remote_comments_snapshot_tests {
             is_too_large: false,
             is_commit_message: false,
             content_hash,
+            review_key: None,
         };
         let mut app = make_revision_app(vec![file]);
         app.set_diff_wrap(true);
@@ -2259,4 +2361,75 @@ mod remote_comments_snapshot_tests {
             );
         }
     }
+
+    #[test]
+    fn should_emphasize_changed_words_in_unified_pair() {
+        // given
+        let lines = vec![
+            DiffLine {
+                origin: LineOrigin::Deletion,
+                content: "let alpha = OLDTOKEN;".to_string(),
+                old_lineno: Some(1),
+                new_lineno: None,
+                highlighted_spans: None,
+            },
+            DiffLine {
+                origin: LineOrigin::Addition,
+                content: "let alpha = NEWTOKEN;".to_string(),
+                old_lineno: None,
+                new_lineno: Some(1),
+                highlighted_spans: None,
+            },
+        ];
+        let hunks = vec![DiffHunk {
+            header: "@@ -1,1 +1,1 @@".to_string(),
+            lines,
+            old_start: 1,
+            old_count: 1,
+            new_start: 1,
+            new_count: 1,
+            raw_body: None,
+        }];
+        let content_hash = DiffFile::compute_content_hash(&hunks);
+        let file = DiffFile {
+            old_path: Some(PathBuf::from("src/lib.rs")),
+            new_path: Some(PathBuf::from("src/lib.rs")),
+            status: FileStatus::Modified,
+            hunks,
+            is_binary: false,
+            is_too_large: false,
+            is_commit_message: false,
+            content_hash,
+            review_key: None,
+        };
+        let mut app = make_revision_app(vec![file]);
+        app.rebuild_annotations();
+        let (del_bg, del_emphasis, add_emphasis) = (
+            app.theme.diff_del_bg,
+            app.theme.diff_del_emphasis_bg,
+            app.theme.diff_add_emphasis_bg,
+        );
+
+        // when
+        let buffer = draw_unified_diff(&mut app);
+
+        // then
+        let find = |needle: &str| {
+            let needle: Vec<String> = needle.chars().map(String::from).collect();
+            (0..buffer.area.height).find_map(|y| {
+                let row: Vec<String> = (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol().to_string())
+                    .collect();
+                row.windows(needle.len())
+                    .position(|w| w == needle.as_slice())
+                    .map(|x| (x as u16, y))
+            })
+        };
+        let old = find("OLDTOKEN").expect("deleted line rendered");
+        let new = find("NEWTOKEN").expect("added line rendered");
+        let shared = find("alpha").expect("shared words rendered");
+        assert_eq!(buffer[old].bg, del_emphasis);
+        assert_eq!(buffer[new].bg, add_emphasis);
+        assert_eq!(buffer[shared].bg, del_bg);
+    }
 }
@@ -6,7 +6,8 @@ use ratatui::{
 };
 
 use crate::app::{
-    AnnotatedLine, App, DiffViewMode, ExpandDirection, GAP_EXPAND_BATCH, VisualSelection,
+    AnnotatedLine, App, BLAME_GUTTER_WIDTH, DiffViewMode, ExpandDirection, GAP_EXPAND_BATCH,
+    VisualSelection,
 };
 use crate::model::{Comment, DiffFile, DiffHunk, DiffLine, LineOrigin, LineSide};
 use crate::theme::Theme;
@@ -14,7 +15,8 @@ use crate::ui::comment_panel;
 use crate::ui::diff_side_by_side::render_side_by_side_diff;
 use crate::ui::diff_unified::render_unified_diff;
 use crate::ui::styles;
-use unicode_width::UnicodeWidthStr;
+use crate::vcs::BlameLine;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 /// Static header rule used for file/section headers; avoids `"═".repeat(40)` per frame.
 pub(super) const HEADER_RULE: &str = "════════════════════════════════════════";
@@ -91,6 +93,86 @@ pub(super) fn relative_line_number_field(
         .unwrap_or_else(|| " ".repeat(lw + 1))
 }
 
+/// Blame gutter text for a new-side line, exactly `BLAME_GUTTER_WIDTH`
+/// cells wide, or `None` when blame is off or the line has none.
+pub(super) fn blame_label(app: &App, file_idx: usize, new_lineno: Option<u32>) -> Option<String> {
+    let line = app.blame_line(file_idx, new_lineno?)?;
+    Some(format_blame_label(line))
+}
+
+fn format_blame_label(line: &BlameLine) -> String {
+    let Some(commit_id) = &line.commit_id else {
+        return format!("{:<BLAME_GUTTER_WIDTH$}", "not committed yet");
+    };
+    let short_id: String = commit_id.chars().take(7).collect();
+    let mut author = String::new();
+    let mut author_width = 0;
+    for c in line.author.chars() {
+        let w = c.width().unwrap_or(0);
+        if author_width + w > 10 {
+            break;
+        }
+        author.push(c);
+        author_width += w;
+    }
+    let date = line
+        .time
+        .map(|t| t.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    format!(
+        "{short_id:<7} {author}{} {date:<10} ",
+        " ".repeat(10 - author_width)
+    )
+}
+
+/// Write a blame label over the leading padding of a right-aligned
+/// line-number field. `lineno_width` reserves that padding while blame is on.
+pub(super) fn overlay_blame(field: String, label: Option<String>) -> String {
+    match label {
+        Some(label)
+            if field.len() >= BLAME_GUTTER_WIDTH
+                && field.as_bytes()[..BLAME_GUTTER_WIDTH]
+                    .iter()
+                    .all(|&b| b == b' ') =>
+        {
+            format!("{label}{}", &field[BLAME_GUTTER_WIDTH..])
+        }
+        _ => field,
+    }
+}
+
+#[cfg(test)]
+mod blame_label_tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn fills_the_reserved_padding_with_a_fixed_width_label() {
+        let line = BlameLine {
+            commit_id: Some("0123456789abcdef".to_string()),
+            author: "Alexandra Longname".to_string(),
+            time: chrono::Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).single(),
+        };
+        let label = format_blame_label(&line);
+        assert_eq!(label, "0123456 Alexandra  2024-05-01 ");
+        assert_eq!(label.width(), BLAME_GUTTER_WIDTH);
+
+        let field = format!("{:>w$} ", 42, w = BLAME_GUTTER_WIDTH + 4);
+        assert_eq!(
+            overlay_blame(field, Some(label)),
+            "0123456 Alexandra  2024-05-01   42 "
+        );
+    }
+
+    #[test]
+    fn labels_uncommitted_lines_and_keeps_fields_without_room() {
+        let label = format_blame_label(&BlameLine::uncommitted());
+        assert!(label.starts_with("not committed yet"));
+        assert_eq!(label.width(), BLAME_GUTTER_WIDTH);
+        assert_eq!(overlay_blame("  42 ".to_string(), Some(label)), "  42 ");
+    }
+}
+
 #[cfg(test)]
 mod relative_line_number_tests {
     use super::relative_line_number_field;
@@ -338,6 +420,23 @@ pub(super) fn comment_type_presentation(
     }
 }
 
+/// Badge for a stored comment: its type, plus an `outdated` marker for drafts
+/// carried from an older PR head whose line no longer exists.
+pub(super) fn comment_presentation(
+    app: &App,
+    comment: &crate::model::Comment,
+) -> comment_panel::CommentTypePresentation {
+    let mut presentation = comment_type_presentation(app, &comment.comment_type);
+    if comment.outdated {
+        presentation.label = if presentation.label.is_empty() {
+            "outdated".to_string()
+        } else {
+            format!("{} outdated", presentation.label)
+        };
+    }
+    presentation
+}
+
 /// Adjust scroll_offset so the comment input box is visible in the viewport.
 ///
 /// The input box is rendered inline in the diff view, so without this
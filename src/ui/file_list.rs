@@ -319,6 +319,7 @@ mod tests {
             is_too_large: false,
             is_commit_message: false,
             content_hash: 0,
+            review_key: None,
         }
     }
 
@@ -7,6 +7,8 @@ use ratatui::{
 };
 
 use crate::app::App;
+use crate::input::keymap::{KeyChord, KeyMode};
+use crate::input::{Action, KeyBindings};
 use crate::ui::styles;
 
 pub fn render_message_details(frame: &mut Frame, app: &mut App) {
@@ -82,44 +84,55 @@ pub fn render_help(frame: &mut Frame, app: &mut App) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let help_text = vec![
+    let mut help_text = custom_key_lines(app);
+    help_text.extend(vec![
         Line::from(Span::styled(
             "Navigation",
             Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
         )),
         Line::from(""),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  j/k       ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::CursorDown(1), &["j"]), (Action::CursorUp(1), &["k"])],
             ),
             Span::raw("Scroll down/up"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  Ctrl-e/y  ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::ScrollViewDown(1), &["<C-e>"]), (Action::ScrollViewUp(1), &["<C-y>"])],
             ),
             Span::raw("Scroll view down/up"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  Ctrl-d/u  ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::HalfPageDown, &["<C-d>"]), (Action::HalfPageUp, &["<C-u>"])],
             ),
             Span::raw("Half page down/up"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  Ctrl-f/b  ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::PageDown, &["<C-f>"]), (Action::PageUp, &["<C-b>"])],
             ),
             Span::raw("Full page down/up"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  g/G       ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::GoToTop, &["g"]), (Action::GoToBottom, &["G"])],
             ),
             Span::raw("Go to first/last file"),
         ]),
@@ -131,107 +144,137 @@ pub fn render_help(frame: &mut Frame, app: &mut App) {
             Span::raw("Go to source line N in current file"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  {/}       ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::PrevFile, &["{"]), (Action::NextFile, &["}"])],
             ),
             Span::raw("Jump to prev/next file"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  [/]       ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::PrevHunk, &["["]), (Action::NextHunk, &["]"])],
             ),
             Span::raw("Jump to prev/next hunk"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  m/M       ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::NextComment, &["m"]), (Action::PrevComment, &["M"])],
             ),
             Span::raw("Jump to next/previous comment"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  /         ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::EnterSearchMode, &["/"])],
             ),
             Span::raw("Search within diff (case-insensitive)"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  n/N       ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::SearchNext, &["n"]), (Action::SearchPrev, &["N"])],
             ),
             Span::raw("Next/prev search match (wraps)"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  Esc       ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::ClearSearchHighlight, &["<Esc>"])],
             ),
             Span::raw("Clear search highlighting"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  Enter     ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::SelectFile, &["<Enter>"])],
             ),
             Span::raw("Expand/collapse context (20 lines)"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  S-Enter   ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::SelectFileFull, &["<S-Enter>"])],
             ),
             Span::raw("Expand/collapse all hidden context"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  Tab/S-Tab ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::ToggleFocus, &["<Tab>"]), (Action::ToggleFocusReverse, &["<S-Tab>"])],
             ),
             Span::raw("Toggle focus next/previous panel"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 format!("  {}h/{}l     ", app.leader_key, app.leader_key),
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::FocusFileList, &["<leader>h"]), (Action::FocusDiff, &["<leader>l"])],
             ),
             Span::raw("Focus file list/diff"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 format!("  {}k/{}j     ", app.leader_key, app.leader_key),
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::FocusPaneUp, &["<leader>k"]), (Action::FocusPaneDown, &["<leader>j"])],
             ),
             Span::raw("Move focus up/down between panes"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 format!("  {}e        ", app.leader_key),
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::ToggleFileList, &["<leader>e"])],
             ),
             Span::raw("Toggle file list visibility"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 format!("  {}s        ", app.leader_key),
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::ToggleCommitSelector, &["<leader>s"])],
             ),
             Span::raw("Toggle commit selector visibility (also `:set commits!`)"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 format!("  {}f        ", app.leader_key),
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::ToggleSingleFileView, &["<leader>f"])],
             ),
             Span::raw("Toggle single-file view (also `:focus` / `:f`)"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  h/l       ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::ScrollLeft(4), &["h"]), (Action::ScrollRight(4), &["l"])],
             ),
             Span::raw("Scroll diff left/right (or ←/→)"),
         ]),
@@ -306,23 +349,29 @@ pub fn render_help(frame: &mut Frame, app: &mut App) {
             Span::raw("Switch Local / Pull Requests tab"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::CommitSelect,
                 "  j/k       ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::CommitSelectDown, &["j"]), (Action::CommitSelectUp, &["k"])],
             ),
             Span::raw("Move row"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::CommitSelect,
                 "  Space     ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::ToggleCommitSelect, &["<Space>"])],
             ),
             Span::raw("Toggle local commit selection (no-op on PR tab)"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::CommitSelect,
                 "  Enter     ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::ConfirmCommitSelect, &["<Enter>"])],
             ),
             Span::raw("Open selected target or load more"),
         ]),
@@ -341,9 +390,11 @@ pub fn render_help(frame: &mut Frame, app: &mut App) {
             Span::raw("Toggle PRs requesting your review"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::CommitSelect,
                 "  Esc/q     ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::ExitMode, &["<Esc>"]), (Action::Quit, &["q"])],
             ),
             Span::raw("Quit / return"),
         ]),
@@ -381,58 +432,74 @@ pub fn render_help(frame: &mut Frame, app: &mut App) {
         )),
         Line::from(""),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  Space     ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::ToggleExpand, &["<Space>"])],
             ),
             Span::raw("Toggle expand directory"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  Enter     ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::SelectFile, &["<Enter>"])],
             ),
             Span::raw("Expand dir / Jump to file"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  o         ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::ExpandAll, &["o"])],
             ),
             Span::raw("Expand all directories"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  O         ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::CollapseAll, &["O"])],
             ),
             Span::raw("Collapse all directories"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  i         ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::FileTreeFilterInclude, &["i"])],
             ),
             Span::raw("Filter to files matching a regex (hides others from tree + diff)"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  e         ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::FileTreeFilterExclude, &["e"])],
             ),
             Span::raw("Filter out files matching a regex"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  I/E       ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::FileTreeClearInclude, &["I"]), (Action::FileTreeClearExclude, &["E"])],
             ),
             Span::raw("Clear the include/exclude filter"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  /         ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::FileTreeSearch, &["/"])],
             ),
             Span::raw("Search file paths; n/N step matches (selection only)"),
         ]),
@@ -476,79 +543,146 @@ pub fn render_help(frame: &mut Frame, app: &mut App) {
         )),
         Line::from(""),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  r         ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::ToggleReviewed, &["r"])],
             ),
             Span::raw("Toggle file reviewed"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  R         ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::ToggleHunkReviewed, &["R"])],
             ),
             Span::raw("Toggle hunk reviewed"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
+                "  a/u       ",
+                &[(Action::StageHunk, &["a"]), (Action::UnstageHunk, &["u"])],
+            ),
+            Span::raw("Stage/unstage hunk at cursor"),
+        ]),
+        Line::from(vec![
+            key_span(
+                app,
+                KeyMode::Normal,
+                "  X         ",
+                &[(Action::DiscardHunk, &["X"])],
+            ),
+            Span::raw("Discard hunk at cursor (confirms)"),
+        ]),
+        Line::from(vec![
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  c         ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::AddLineComment, &["c"])],
             ),
-            Span::raw("Add line comment"),
+            Span::raw("Add line comment (reply when on a remote thread)"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  C         ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::AddFileComment, &["C"])],
             ),
             Span::raw("Add file comment"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
+                "  s         ",
+                &[(Action::AddSuggestion, &["s"])],
+            ),
+            Span::raw("Suggest a change to the line at cursor"),
+        ]),
+        Line::from(vec![
+            key_span(
+                app,
+                KeyMode::Normal,
                 format!("  {}c        ", app.leader_key),
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::AddReviewComment, &["<leader>c"])],
             ),
             Span::raw("Add review comment"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  i         ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::EditComment, &["i"])],
             ),
             Span::raw("Edit comment at cursor"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  dd        ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::DeleteCommentAtCursor, &["dd"])],
             ),
             Span::raw("Delete comment at cursor"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
+                format!("  {}b        ", app.leader_key),
+                &[(Action::ShowBlameCommit, &["<leader>b"])],
+            ),
+            Span::raw("Show blamed commit for the cursor line"),
+        ]),
+        Line::from(vec![
+            key_span(
+                app,
+                KeyMode::Normal,
+                "  p         ",
+                &[(Action::PromoteDiagnostic, &["p"])],
+            ),
+            Span::raw("Turn diagnostic at cursor into a comment"),
+        ]),
+        Line::from(vec![
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  y         ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::ExportToClipboard, &["y"])],
             ),
             Span::raw("Yank: mouse selection if any, else review to clipboard"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  Y         ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::CopyCommentAtCursor, &["Y"])],
             ),
             Span::raw("Copy comment at cursor to clipboard"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  e         ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::EditFile, &["e"])],
             ),
             Span::raw("Open focused file in $EDITOR"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Normal,
                 "  v/V       ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::EnterVisualMode, &["v", "V"])],
             ),
             Span::raw("Enter visual mode for range comments"),
         ]),
@@ -559,23 +693,47 @@ pub fn render_help(frame: &mut Frame, app: &mut App) {
         )),
         Line::from(""),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Visual,
                 "  j/k       ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::CursorDown(1), &["j"]), (Action::CursorUp(1), &["k"])],
             ),
             Span::raw("Extend selection down/up"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Visual,
                 "  c/Enter   ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::AddRangeComment, &["c", "<Enter>"])],
             ),
             Span::raw("Create comment for selected range"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Visual,
+                "  s         ",
+                &[(Action::AddSuggestion, &["s"])],
+            ),
+            Span::raw("Suggest a change to the selected lines"),
+        ]),
+        Line::from(vec![
+            key_span(
+                app,
+                KeyMode::Visual,
+                "  a/u/X     ",
+                &[(Action::StageHunk, &["a"]), (Action::UnstageHunk, &["u"]), (Action::DiscardHunk, &["X"])],
+            ),
+            Span::raw("Stage/unstage/discard selected lines"),
+        ]),
+        Line::from(vec![
+            key_span(
+                app,
+                KeyMode::Visual,
                 "  Esc/v/V   ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::ExitMode, &["<Esc>", "v", "V"])],
             ),
             Span::raw("Cancel visual selection"),
         ]),
@@ -586,37 +744,47 @@ pub fn render_help(frame: &mut Frame, app: &mut App) {
         )),
         Line::from(""),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Comment,
                 "  Tab/S-Tab ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::CycleCommentType, &["<Tab>"]), (Action::CycleCommentTypeReverse, &["<S-Tab>"])],
             ),
             Span::raw("Cycle comment type next/previous"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Comment,
                 "  Enter     ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::SubmitInput, &["<Enter>"])],
             ),
             Span::raw("Save comment"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Comment,
                 "  Ctrl-S    ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::SubmitInput, &["<C-s>"])],
             ),
             Span::raw("Save comment"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Comment,
                 "  Shift-Enter/Alt-Enter/Ctrl-J",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::InsertChar('\n'), &["<S-Enter>", "<A-Enter>", "<C-j>"])],
             ),
             Span::raw("Insert newline"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Comment,
                 "  Ctrl-A/E  ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::TextCursorLineStart, &["<C-a>"]), (Action::TextCursorLineEnd, &["<C-e>"])],
             ),
             Span::raw("Line start/end"),
         ]),
@@ -635,9 +803,11 @@ pub fn render_help(frame: &mut Frame, app: &mut App) {
             Span::raw("Line start/end (macOS)"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Comment,
                 "  Esc/Ctrl-C",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::ExitMode, &["<Esc>", "<C-c>"])],
             ),
             Span::raw("Cancel"),
         ]),
@@ -707,7 +877,28 @@ pub fn render_help(frame: &mut Frame, app: &mut App) {
                 "  :clip     ",
                 Style::default().add_modifier(Modifier::BOLD),
             ),
-            Span::raw("Copy review to clipboard"),
+            Span::raw("Copy review to clipboard (:clip json|sarif|markdown)"),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                "  :export email ",
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Write email replies for a --patch series"),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                "  :send <target> ",
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Pipe review to an [export.targets] command"),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                "  :diagnostics [file|clear] ",
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Show linter output on changed lines"),
         ]),
         Line::from(vec![
             Span::styled(
@@ -810,6 +1001,21 @@ pub fn render_help(frame: &mut Frame, app: &mut App) {
             ),
             Span::raw("  Hide remote comments in PR mode"),
         ]),
+        Line::from(vec![
+            Span::styled("  :reply", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("  Reply to the remote thread at cursor (sent with :submit)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :resolve", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("  Resolve the remote thread at cursor on the forge"),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                "  :unresolve",
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  Reopen the remote thread at cursor on the forge"),
+        ]),
         Line::from(vec![
             Span::styled(
                 "  :submit       ",
@@ -847,6 +1053,48 @@ pub fn render_help(frame: &mut Frame, app: &mut App) {
             ),
             Span::raw("  Push a pending (draft) review to the forge"),
         ]),
+        Line::from(vec![
+            Span::styled(
+                "  :submit discard",
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  Delete your pending review on the forge"),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                "  :merge [method]",
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  Merge the PR: merge, squash or rebase (asks first)"),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                "  :close",
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  Close the PR without merging (asks first)"),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                "  :request-reviewer <user>",
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  Request a review from <user> (asks first)"),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                "  :label <name>",
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  Add a label to the PR (asks first)"),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                "  :check-log",
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  Show the log of the failing CI check under the cursor"),
+        ]),
         Line::from(vec![
             Span::styled(
                 "  :set relativenumber[!]",
@@ -861,6 +1109,20 @@ pub fn render_help(frame: &mut Frame, app: &mut App) {
             ),
             Span::raw("  Disable relative rendered-row numbers"),
         ]),
+        Line::from(vec![
+            Span::styled(
+                "  :set blame[!]",
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  Show/toggle the blame gutter (also :blame)"),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                "  :set noblame",
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  Hide the blame gutter"),
+        ]),
         Line::from(vec![
             Span::styled(
                 "  :set commits",
@@ -933,27 +1195,33 @@ pub fn render_help(frame: &mut Frame, app: &mut App) {
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Help,
                 "  /         ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::EnterSearchMode, &["/"])],
             ),
             Span::raw("Search within this help (case-insensitive)"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Help,
                 "  n/N       ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::SearchNext, &["n"]), (Action::SearchPrev, &["N"])],
             ),
             Span::raw("Next/previous help search match"),
         ]),
         Line::from(vec![
-            Span::styled(
+            key_span(
+                app,
+                KeyMode::Help,
                 "  ?         ",
-                Style::default().add_modifier(Modifier::BOLD),
+                &[(Action::ToggleHelp, &["?"])],
             ),
             Span::raw("Toggle this help"),
         ]),
-    ];
+    ]);
 
     // Update help state with total lines and viewport height
     let total_lines = help_text.len();
@@ -1018,6 +1286,87 @@ pub fn render_help(frame: &mut Frame, app: &mut App) {
     }
 }
 
+/// Lists the `[keys]` bindings from the config. The sections below already
+/// show the keys in effect; this is the config as written.
+fn custom_key_lines(app: &App) -> Vec<Line<'static>> {
+    let bindings = app.key_bindings.bindings();
+    if bindings.is_empty() {
+        return Vec::new();
+    }
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "Your Key Bindings ([keys] in config.toml)",
+            Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+        )),
+        Line::from(""),
+    ];
+    for binding in bindings {
+        let action = match binding.action_name.as_str() {
+            "none" => "unbound",
+            name => name,
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<10}", binding.chord.label(app.leader_key)),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!("{action} ({})", binding.mode.name())),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines
+}
+
+/// Key column of a help row for bindable actions. Shows `default` while
+/// each action keeps exactly its default keys, otherwise the keys that
+/// trigger it with `[keys]` applied.
+fn key_span(
+    app: &App,
+    mode: KeyMode,
+    default: impl Into<String>,
+    actions: &[(Action, &[&str])],
+) -> Span<'static> {
+    let style = Style::default().add_modifier(Modifier::BOLD);
+    match effective_keys_label(&app.key_bindings, mode, app.leader_key, actions) {
+        Some(label) => Span::styled(format!("  {label:<10}"), style),
+        None => Span::styled(default.into(), style),
+    }
+}
+
+/// `a,b/c` for the chords of each action (`unbound` when it has none), or
+/// `None` when no action's keys differ from its defaults.
+fn effective_keys_label(
+    bindings: &KeyBindings,
+    mode: KeyMode,
+    leader_key: char,
+    actions: &[(Action, &[&str])],
+) -> Option<String> {
+    if bindings.is_empty() {
+        return None;
+    }
+    let mut changed = false;
+    let labels: Vec<String> = actions
+        .iter()
+        .map(|(action, defaults)| {
+            let defaults: Vec<KeyChord> = defaults
+                .iter()
+                .map(|chord| chord.parse().expect("valid default chord"))
+                .collect();
+            let chords = bindings.effective_chords(mode, action, &defaults, leader_key);
+            changed |= chords != defaults;
+            if chords.is_empty() {
+                return "unbound".to_string();
+            }
+            chords
+                .iter()
+                .map(|chord| chord.label(leader_key))
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .collect();
+    changed.then(|| labels.join("/"))
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
     let vertical = Layout::vertical([Constraint::Percentage(percent_y)]).flex(Flex::Center);
     let horizontal = Layout::horizontal([Constraint::Percentage(percent_x)]).flex(Flex::Center);
@@ -1025,3 +1374,47 @@ fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
     let [area] = horizontal.areas(area);
     area
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::keymap::{KeyBinding, action_from_name};
+
+    fn bindings(entries: &[(KeyMode, &str, &str)]) -> KeyBindings {
+        KeyBindings::new(
+            entries
+                .iter()
+                .map(|(mode, chord, action)| KeyBinding {
+                    mode: *mode,
+                    chord: chord.parse().expect("valid chord"),
+                    action_name: action.to_string(),
+                    action: action_from_name(action).expect("known action"),
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn shows_the_keys_in_effect_for_rebound_actions() {
+        let keys = bindings(&[
+            (KeyMode::Normal, "j", "none"),
+            (KeyMode::Normal, "<C-n>", "cursor_down"),
+            (KeyMode::Normal, "q", "none"),
+        ]);
+        let label = |actions: &[(Action, &[&str])]| {
+            effective_keys_label(&keys, KeyMode::Normal, ';', actions)
+        };
+
+        assert_eq!(
+            label(&[
+                (Action::CursorDown(1), &["j"]),
+                (Action::CursorUp(1), &["k"])
+            ])
+            .as_deref(),
+            Some("<C-n>/k")
+        );
+        assert_eq!(label(&[(Action::Quit, &["q"])]).as_deref(), Some("unbound"));
+        // Untouched rows keep their hand-written label.
+        assert_eq!(label(&[(Action::NextHunk, &["]"])]), None);
+    }
+}
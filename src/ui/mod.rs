@@ -1,4 +1,5 @@
 pub mod app_layout;
+pub mod check_log_view;
 pub mod comment_navigator;
 pub mod comment_panel;
 pub mod commit_row;
@@ -16,5 +17,6 @@ pub mod styles;
 pub mod submit_modals;
 pub mod summary_popup;
 pub mod text_utils;
+pub mod word_diff;
 
 pub use app_layout::render;
@@ -61,6 +61,18 @@ pub fn is_cursor_in_pr_info(app: &App) -> bool {
     pr_info_render_height(app) > 0 && app.diff_state.cursor_line < pr_info_render_height(app)
 }
 
+/// The check whose row the cursor is on. Checks are the panel's last
+/// rows, one each.
+pub fn check_at_cursor(app: &App) -> Option<&PullRequestCheckStatus> {
+    let info = app.pr_info.as_ref()?;
+    let height = pr_info_render_height(app);
+    let first = height.checked_sub(info.checks.len())?;
+    let cursor = app.diff_state.cursor_line;
+    (first..height)
+        .contains(&cursor)
+        .then(|| &info.checks[cursor - first])
+}
+
 pub fn is_cursor_in_issue_comments(app: &App) -> bool {
     let start = app.issue_comments_start_line();
     let end = start + issue_comments_render_height(app);
@@ -293,6 +305,7 @@ fn format_issue_comment_lines(
         None,
         width,
         comment.author.as_deref(),
+        false,
     )
 }
 
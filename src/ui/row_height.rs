@@ -70,6 +70,7 @@ pub(crate) fn annotation_row_height(app: &App, idx: usize) -> usize {
                 comment_panel::format_remote_thread_lines(
                     &app.theme,
                     thread,
+                    app.session.thread_replies_for(&thread.id),
                     muted,
                     app.forge_kind(),
                 )
@@ -78,6 +79,16 @@ pub(crate) fn annotation_row_height(app: &App, idx: usize) -> usize {
             })
             .map_or(1, |line| formatted_line_height(line, viewport_width)),
 
+        AnnotatedLine::Diagnostic { diagnostic_idx, .. } => app
+            .diagnostics
+            .get(*diagnostic_idx)
+            .map_or(1, |diagnostic| {
+                formatted_line_height(
+                    comment_panel::format_diagnostic_line(&app.theme, diagnostic),
+                    viewport_width,
+                )
+            }),
+
         // Pre-wrapped by comment_panel::wrap_segments to inner width - 1.
         AnnotatedLine::ReviewComment { .. }
         | AnnotatedLine::FileComment { .. }
@@ -376,7 +387,8 @@ fn full_row_text(app: &App, annotation: &AnnotatedLine) -> String {
         | AnnotatedLine::FileComment { .. }
         | AnnotatedLine::LineComment { .. }
         | AnnotatedLine::IssueComment { .. }
-        | AnnotatedLine::RemoteThreadLine { .. } => indicator.to_string(),
+        | AnnotatedLine::RemoteThreadLine { .. }
+        | AnnotatedLine::Diagnostic { .. } => indicator.to_string(),
     }
 }
 
@@ -405,6 +417,7 @@ mod tests {
         App, DiffSource, DiffViewMode, ExpandDirection, GapId, InputMode, PullRequestDiffSource,
         SummaryCommentTarget,
     };
+    use crate::diagnostics::{Diagnostic, DiagnosticSeverity};
     use crate::error::Result as TuicrResult;
     use crate::error::TuicrError;
     use crate::forge::remote_comments::{
@@ -517,6 +530,7 @@ mod tests {
             old_count: 2,
             new_start: 30,
             new_count: 3,
+            raw_body: None,
         };
         let hunk2 = DiffHunk {
             header: "@@ -100,1 +100,1 @@ fn bar".to_string(),
@@ -531,6 +545,7 @@ mod tests {
             old_count: 1,
             new_start: 100,
             new_count: 1,
+            raw_body: None,
         };
         let hunks = vec![hunk1, hunk2];
         let content_hash = DiffFile::compute_content_hash(&hunks);
@@ -543,6 +558,7 @@ mod tests {
             is_too_large: false,
             is_commit_message: false,
             content_hash,
+            review_key: None,
         }
     }
 
@@ -556,6 +572,7 @@ mod tests {
             is_too_large: false,
             is_commit_message: false,
             content_hash: 0,
+            review_key: None,
         }
     }
 
@@ -641,6 +658,14 @@ mod tests {
                 url: "https://example.com/comment".to_string(),
             }],
         }];
+        app.diagnostics = vec![Diagnostic {
+            path: PathBuf::from("src/lib.rs"),
+            line: 31,
+            column: Some(5),
+            severity: DiagnosticSeverity::Warning,
+            code: Some("clippy::needless_return".to_string()),
+            message: "unneeded `return` statement in a long diagnostic ".repeat(4),
+        }];
         // `sort_files_by_directory` reorders diff_files by parent directory,
         // so `assets/logo.png` lands before `src/lib.rs`; find the code file
         // by path rather than assuming its post-sort index.
@@ -734,6 +759,7 @@ mod tests {
             AnnotatedLine::LineComment { .. } => Some("LineComment"),
             AnnotatedLine::FileComment { .. } => Some("FileComment"),
             AnnotatedLine::RemoteThreadLine { .. } => Some("RemoteThreadLine"),
+            AnnotatedLine::Diagnostic { .. } => Some("Diagnostic"),
             AnnotatedLine::Spacing => Some("Spacing"),
             AnnotatedLine::BinaryOrEmpty { .. } => Some("BinaryOrEmpty"),
             AnnotatedLine::ReviewedBanner { .. } => Some("ReviewedBanner"),
@@ -766,6 +792,7 @@ mod tests {
             "LineComment",
             "FileComment",
             "RemoteThreadLine",
+            "Diagnostic",
             "Spacing",
             "BinaryOrEmpty",
         ]
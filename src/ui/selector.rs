@@ -99,7 +99,11 @@ fn render_top_bar(frame: &mut Frame, app: &App, area: Rect) {
         TargetTab::PullRequests => pr_status_hint_span(app, strip_bg),
         TargetTab::Local => {
             let vcs_type = &app.vcs_info.vcs_type;
-            let branch = app.vcs_info.branch_name.as_deref().unwrap_or("detached");
+            let branch = app
+                .vcs
+                .display_ref()
+                .or(app.vcs_info.branch_name.as_deref())
+                .unwrap_or("detached");
             let content = format!(" {vcs_type}:{branch} ");
             let width = content.chars().count();
             (Span::styled(content, strip_style), width)
@@ -13,6 +13,7 @@ use crate::app::{App, DiffSource, FocusedPanel, InputMode, Message, MessageType}
 use crate::theme::Theme;
 use crate::ui::commit_row::CURSOR_GLYPH;
 use crate::ui::styles;
+use crate::vcs::traits::VcsType;
 
 /// Maximum visible completion candidates in the command prompt popup.
 const COMMAND_COMPLETION_MAX_ROWS: usize = 7;
@@ -78,7 +79,11 @@ pub fn render_header(frame: &mut Frame, app: &App, area: Rect) {
         chunks.push("PR Mode".to_string());
     } else {
         let vcs_type = &app.vcs_info.vcs_type;
-        let branch = app.vcs_info.branch_name.as_deref().unwrap_or("detached");
+        let branch = app
+            .vcs
+            .display_ref()
+            .or(app.vcs_info.branch_name.as_deref())
+            .unwrap_or("detached");
         chunks.push(format!("{vcs_type}:{branch}"));
     }
     if let Some(source) = header_source_chunk(app) {
@@ -168,9 +173,11 @@ pub fn render_header(frame: &mut Frame, app: &App, area: Rect) {
 ///
 /// Pristine sessions store a synthetic `pristine:<head>:<hash>` key rather than
 /// a sha, and that mode already renders its own chip further down the header.
+/// Patch sessions likewise store `patch:<hash>`, and the `patch:<name>` chip
+/// already names what is under review.
 /// An empty repository has no HEAD at all.
 fn head_commit_label(app: &App) -> Option<String> {
-    if app.is_pristine_mode {
+    if app.is_pristine_mode || app.vcs_info.vcs_type == VcsType::Patch {
         return None;
     }
     let head = app.vcs_info.head_commit.as_str();
@@ -290,6 +297,7 @@ pub fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
             InputMode::SubmitResolver => " RESOLVE ".to_string(),
             InputMode::SubmitConfirm => " SUBMIT ".to_string(),
             InputMode::SubmitActionPicker => " SUBMIT ".to_string(),
+            InputMode::CheckLog => " CI LOG ".to_string(),
         };
 
         let mode_span = Span::styled(mode_str, styles::mode_style(theme));
@@ -319,6 +327,9 @@ pub fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
                 InputMode::Summary => {
                     Cow::Borrowed("   j/k select \u{00b7} \u{21b5} jump \u{00b7} q/esc close")
                 }
+                InputMode::CheckLog => Cow::Borrowed(
+                    "   j/k select \u{00b7} \u{21b5} jump to file \u{00b7} / search \u{00b7} n/N match \u{00b7} q/esc close",
+                ),
                 InputMode::Confirm => Cow::Borrowed("   y yes \u{00b7} n no"),
                 InputMode::CommitSelect => Cow::Borrowed(
                     "   j/k navigate \u{00b7} space select \u{00b7} \u{21b5} confirm \u{00b7} esc back",
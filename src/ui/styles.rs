@@ -1,5 +1,7 @@
 use ratatui::style::{Color, Modifier, Style};
 
+use crate::diagnostics::DiagnosticSeverity;
+use crate::model::LineOrigin;
 use crate::theme::Theme;
 
 pub fn selected_style(theme: &Theme) -> Style {
@@ -18,6 +20,16 @@ pub fn diff_del_style(theme: &Theme) -> Style {
     Style::default().fg(theme.diff_del).bg(theme.diff_del_bg)
 }
 
+/// Background-only overlay for the changed words of a paired line; patched
+/// over the syntax or diff style so foreground colors survive.
+pub fn diff_emphasis_style(theme: &Theme, origin: LineOrigin) -> Style {
+    match origin {
+        LineOrigin::Addition => Style::default().bg(theme.diff_add_emphasis_bg),
+        LineOrigin::Deletion => Style::default().bg(theme.diff_del_emphasis_bg),
+        LineOrigin::Context => Style::default(),
+    }
+}
+
 pub fn diff_context_style(theme: &Theme) -> Style {
     Style::default().fg(theme.diff_context)
 }
@@ -181,6 +193,15 @@ pub fn error_inline_style(theme: &Theme) -> Style {
         .add_modifier(Modifier::BOLD)
 }
 
+pub fn diagnostic_style(theme: &Theme, severity: DiagnosticSeverity) -> Style {
+    let color = match severity {
+        DiagnosticSeverity::Error => theme.comment_issue,
+        DiagnosticSeverity::Warning => theme.file_modified,
+        DiagnosticSeverity::Note => theme.comment_note,
+    };
+    Style::default().fg(color)
+}
+
 pub fn pseudo_commit_tag_style(theme: &Theme) -> Style {
     Style::default().fg(theme.file_modified)
 }
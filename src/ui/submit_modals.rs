@@ -9,13 +9,13 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
 };
 
-use crate::app::{App, SUBMIT_PICKER_EVENTS};
+use crate::app::{App, SUBMIT_PICKER_DISCARD_LABEL, SUBMIT_PICKER_EVENTS};
 use crate::forge::submit::{ResolverAction, SubmitEvent, UnmappableItem};
 use crate::ui::styles;
 
 /// Render the bare-`:submit` action picker. Lists the available review
-/// events plus a Cancel row; the user picks one with j/k + Enter (or
-/// Esc to cancel).
+/// events, plus a discard row while a pending review exists; the user picks
+/// one with j/k + Enter (or Esc to cancel).
 pub fn render_submit_action_picker(frame: &mut Frame, app: &App) {
     let theme = &app.theme;
     let area = centered_rect(40, 50, modal_anchor(app, frame.area()));
@@ -32,7 +32,15 @@ pub fn render_submit_action_picker(frame: &mut Frame, app: &App) {
 
     let mut lines: Vec<Line> = Vec::new();
     lines.push(Line::from(""));
-    for (i, (label, _)) in SUBMIT_PICKER_EVENTS.iter().enumerate() {
+    let discard_row = app
+        .pending_review
+        .as_ref()
+        .map(|_| SUBMIT_PICKER_DISCARD_LABEL);
+    let labels = SUBMIT_PICKER_EVENTS
+        .iter()
+        .map(|(label, _)| *label)
+        .chain(discard_row);
+    for (i, label) in labels.enumerate() {
         let style = if i == app.submit_picker_cursor {
             Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD)
         } else {
@@ -165,6 +173,12 @@ pub fn render_submit_confirm(frame: &mut Frame, app: &App) {
     lines.push(Line::from(format!("Inline: {n}", n = state.mappable.len())));
     lines.push(Line::from(format!("Moved to summary: {moved_count}")));
     lines.push(Line::from(format!("Omitted: {omit_count}")));
+    if !state.replies.is_empty() {
+        lines.push(Line::from(format!(
+            "Thread replies: {n}",
+            n = state.replies.len()
+        )));
+    }
     lines.push(Line::from(format!("Body: {body_summary}")));
     lines.push(Line::from(format!(
         "Head: {sha}",
@@ -502,6 +516,7 @@ mod tests {
                 ),
             ],
             resolver_choices: vec![ResolverAction::MoveToSummary, ResolverAction::MoveToSummary],
+            replies: Vec::new(),
             resolver_cursor: 0,
             commit_id: "abcdef0123".to_string(),
             skip_confirm: false,
@@ -533,6 +548,7 @@ mod tests {
                 UnmappableReason::FileLevelNoAnchor,
             )],
             resolver_choices: vec![ResolverAction::Omit],
+            replies: Vec::new(),
             resolver_cursor: 0,
             commit_id: "abcdef0123".to_string(),
             skip_confirm: false,
@@ -550,6 +566,7 @@ mod tests {
             mappable: vec![inline(11), inline(12)],
             unmappable: Vec::new(),
             resolver_choices: Vec::new(),
+            replies: Vec::new(),
             resolver_cursor: 0,
             commit_id: "abcdef0123".to_string(),
             skip_confirm: false,
@@ -566,6 +583,37 @@ mod tests {
         assert!(!text.contains("Warning: this review targets"));
     }
 
+    #[test]
+    fn confirm_counts_queued_thread_replies() {
+        let mut app = make_pr_app();
+        let thread = crate::forge::remote_comments::RemoteReviewThread {
+            id: "PRRT_1".to_string(),
+            path: "src/lib.rs".to_string(),
+            line: Some(11),
+            side: crate::forge::remote_comments::RemoteCommentSide::Right,
+            is_resolved: false,
+            is_outdated: false,
+            comments: Vec::new(),
+        };
+        app.submit_state = Some(SubmitState {
+            event: SubmitEvent::Comment,
+            mappable: Vec::new(),
+            unmappable: Vec::new(),
+            resolver_choices: Vec::new(),
+            replies: vec![crate::forge::submit::ThreadReply {
+                thread,
+                body: "done".to_string(),
+                comment_id: "c1".to_string(),
+            }],
+            resolver_cursor: 0,
+            commit_id: "abcdef0123".to_string(),
+            skip_confirm: false,
+        });
+        let buffer = draw_confirm(&app);
+        let text = buffer_text(&buffer);
+        assert!(text.contains("Thread replies: 1"), "replies line: {text}");
+    }
+
     #[test]
     fn confirm_shows_stale_head_warning_with_reload_option() {
         let mut app = make_pr_app();
@@ -575,6 +623,7 @@ mod tests {
             mappable: vec![inline(11)],
             unmappable: Vec::new(),
             resolver_choices: Vec::new(),
+            replies: Vec::new(),
             resolver_cursor: 0,
             commit_id: "abcdef0123".to_string(),
             skip_confirm: false,
@@ -595,6 +644,7 @@ mod tests {
             mappable: vec![inline(11)],
             unmappable: Vec::new(),
             resolver_choices: Vec::new(),
+            replies: Vec::new(),
             resolver_cursor: 0,
             commit_id: "abcdef0123".to_string(),
             skip_confirm: false,
@@ -616,6 +666,7 @@ mod tests {
             mappable: vec![inline(11)],
             unmappable: Vec::new(),
             resolver_choices: Vec::new(),
+            replies: Vec::new(),
             resolver_cursor: 0,
             commit_id: "abcdef0123".to_string(),
             skip_confirm: false,
@@ -652,6 +703,7 @@ mod tests {
                 ),
             ],
             resolver_choices: vec![ResolverAction::MoveToSummary, ResolverAction::Omit],
+            replies: Vec::new(),
             resolver_cursor: 0,
             commit_id: "abcdef0123".to_string(),
             skip_confirm: false,
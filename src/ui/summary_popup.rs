@@ -444,6 +444,7 @@ mod tests {
             old_count: count,
             new_start: start,
             new_count: count,
+            raw_body: None,
         }
     }
 
@@ -458,6 +459,7 @@ mod tests {
             is_too_large: false,
             is_commit_message: false,
             content_hash,
+            review_key: None,
         }
     }
 
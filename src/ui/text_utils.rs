@@ -261,6 +261,30 @@ pub(super) fn apply_search_highlight_spans(
         .collect()
 }
 
+/// Overlay `highlight` on the word-diff byte `ranges` of a line's pairs.
+pub(super) fn apply_emphasis_pairs(
+    pairs: &[(Style, String)],
+    ranges: &[(usize, usize)],
+    highlight: Style,
+) -> Vec<(Style, String)> {
+    split_pairs_at_ranges(pairs, ranges.to_vec(), highlight)
+}
+
+pub(super) fn apply_emphasis_spans(
+    spans: Vec<Span<'static>>,
+    ranges: &[(usize, usize)],
+    highlight: Style,
+) -> Vec<Span<'static>> {
+    let pairs: Vec<(Style, String)> = spans
+        .into_iter()
+        .map(|span| (span.style, span.content.into_owned()))
+        .collect();
+    apply_emphasis_pairs(&pairs, ranges, highlight)
+        .into_iter()
+        .map(|(style, text)| Span::styled(text, style))
+        .collect()
+}
+
 fn split_pairs_at_ranges(
     pairs: &[(Style, String)],
     ranges: Vec<(usize, usize)>,
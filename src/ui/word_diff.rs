@@ -0,0 +1,353 @@
+//! Intra-line (word-level) change detection for modified lines.
+//!
+//! Inside a hunk, a run of deletions followed directly by a run of additions
+//! is a replacement block. Its lines are paired by position (the k-th
+//! deletion with the k-th addition), which is the same pairing the
+//! side-by-side view uses for its rows. Each pair is diffed token by token,
+//! and the byte ranges that differ are handed back so the renderers can
+//! emphasize them on top of the line background and syntax colors.
+//!
+//! Everything here runs at render time for visible rows only, so `DiffLine`
+//! stays a plain parse result.
+
+use std::borrow::Cow;
+
+use crate::model::{DiffLine, LineOrigin};
+
+/// Upper bound on the token LCS table. Longer pairs fall back to trimming
+/// the common prefix and suffix, which still isolates a single edit.
+const MAX_LCS_CELLS: usize = 40_000;
+
+/// Changed byte ranges `(start, end)` for each side of a line pair, relative
+/// to [`line_text`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct WordEmphasis {
+    pub old: Vec<(usize, usize)>,
+    pub new: Vec<(usize, usize)>,
+}
+
+/// The text a renderer paints for `line`. Syntax spans are joined when
+/// present so emphasis offsets line up with the spans they split.
+pub(crate) fn line_text(line: &DiffLine) -> Cow<'_, str> {
+    match &line.highlighted_spans {
+        Some(spans) => Cow::Owned(spans.iter().map(|(_, text)| text.as_str()).collect()),
+        None => Cow::Borrowed(&line.content),
+    }
+}
+
+/// Index of the line paired with `idx` in its replacement block, or `None`
+/// for context lines and for the unpaired tail of an uneven block.
+pub(crate) fn paired_line(lines: &[DiffLine], idx: usize) -> Option<usize> {
+    let run_start = |end: usize, origin: LineOrigin| {
+        let mut start = end;
+        while start > 0 && lines[start - 1].origin == origin {
+            start -= 1;
+        }
+        start
+    };
+    let run_end = |start: usize, origin: LineOrigin| {
+        let mut end = start;
+        while end < lines.len() && lines[end].origin == origin {
+            end += 1;
+        }
+        end
+    };
+
+    match lines.get(idx)?.origin {
+        LineOrigin::Context => None,
+        LineOrigin::Deletion => {
+            let del_start = run_start(idx, LineOrigin::Deletion);
+            let add_start = run_end(idx, LineOrigin::Deletion);
+            let add_end = run_end(add_start, LineOrigin::Addition);
+            let pair = add_start + (idx - del_start);
+            (pair < add_end).then_some(pair)
+        }
+        LineOrigin::Addition => {
+            let add_start = run_start(idx, LineOrigin::Addition);
+            let del_start = run_start(add_start, LineOrigin::Deletion);
+            let pair = del_start + (idx - add_start);
+            (pair < add_start).then_some(pair)
+        }
+    }
+}
+
+/// Emphasis ranges for the line at `idx`, when it is half of a pair that
+/// shares enough content for a word diff to be meaningful.
+pub(crate) fn line_emphasis(lines: &[DiffLine], idx: usize) -> Option<Vec<(usize, usize)>> {
+    let other = paired_line(lines, idx)?;
+    let is_old = lines[idx].origin == LineOrigin::Deletion;
+    let (old, new) = if is_old { (idx, other) } else { (other, idx) };
+    let emphasis = word_emphasis(&line_text(&lines[old]), &line_text(&lines[new]))?;
+    Some(if is_old { emphasis.old } else { emphasis.new })
+}
+
+/// Diff `old` against `new` by token. Returns `None` when the lines are
+/// identical or share no non-whitespace token, since highlighting every
+/// word of a rewritten line adds noise rather than signal.
+pub(crate) fn word_emphasis(old: &str, new: &str) -> Option<WordEmphasis> {
+    if old == new {
+        return None;
+    }
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+    let old_text = |i: usize| &old[old_tokens[i].0..old_tokens[i].1];
+    let new_text = |j: usize| &new[new_tokens[j].0..new_tokens[j].1];
+
+    let mut old_kept = vec![false; old_tokens.len()];
+    let mut new_kept = vec![false; new_tokens.len()];
+
+    let mut prefix = 0;
+    while prefix < old_tokens.len()
+        && prefix < new_tokens.len()
+        && old_text(prefix) == new_text(prefix)
+    {
+        old_kept[prefix] = true;
+        new_kept[prefix] = true;
+        prefix += 1;
+    }
+    let mut suffix = 0;
+    while suffix < old_tokens.len() - prefix
+        && suffix < new_tokens.len() - prefix
+        && old_text(old_tokens.len() - 1 - suffix) == new_text(new_tokens.len() - 1 - suffix)
+    {
+        old_kept[old_tokens.len() - 1 - suffix] = true;
+        new_kept[new_tokens.len() - 1 - suffix] = true;
+        suffix += 1;
+    }
+
+    let old_mid = prefix..old_tokens.len() - suffix;
+    let new_mid = prefix..new_tokens.len() - suffix;
+    let rows = old_mid.len();
+    let cols = new_mid.len();
+    if rows > 0 && cols > 0 && rows * cols <= MAX_LCS_CELLS {
+        // lcs[i][j] = LCS length of old_mid[i..] and new_mid[j..].
+        let mut lcs = vec![0u32; (rows + 1) * (cols + 1)];
+        let at = |i: usize, j: usize| i * (cols + 1) + j;
+        for i in (0..rows).rev() {
+            for j in (0..cols).rev() {
+                lcs[at(i, j)] = if old_text(old_mid.start + i) == new_text(new_mid.start + j) {
+                    lcs[at(i + 1, j + 1)] + 1
+                } else {
+                    lcs[at(i + 1, j)].max(lcs[at(i, j + 1)])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < rows && j < cols {
+            if old_text(old_mid.start + i) == new_text(new_mid.start + j) {
+                old_kept[old_mid.start + i] = true;
+                new_kept[new_mid.start + j] = true;
+                i += 1;
+                j += 1;
+            } else if lcs[at(i + 1, j)] >= lcs[at(i, j + 1)] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+    }
+
+    let shares_content = old_kept
+        .iter()
+        .zip(0..)
+        .any(|(kept, i)| *kept && !old_text(i).trim().is_empty());
+    if !shares_content {
+        return None;
+    }
+
+    Some(WordEmphasis {
+        old: changed_ranges(old, &old_tokens, &old_kept),
+        new: changed_ranges(new, &new_tokens, &new_kept),
+    })
+}
+
+/// Split `text` into word runs, whitespace runs, and single punctuation
+/// characters, as byte ranges.
+fn tokenize(text: &str) -> Vec<(usize, usize)> {
+    #[derive(PartialEq)]
+    enum Class {
+        Word,
+        Space,
+        Punct,
+    }
+    let class = |ch: char| {
+        if ch.is_alphanumeric() || ch == '_' {
+            Class::Word
+        } else if ch.is_whitespace() {
+            Class::Space
+        } else {
+            Class::Punct
+        }
+    };
+
+    let mut tokens: Vec<(usize, usize)> = Vec::new();
+    let mut prev: Option<Class> = None;
+    for (offset, ch) in text.char_indices() {
+        let current = class(ch);
+        let extends = current != Class::Punct && prev.as_ref() == Some(&current);
+        match tokens.last_mut() {
+            Some(last) if extends => last.1 = offset + ch.len_utf8(),
+            _ => tokens.push((offset, offset + ch.len_utf8())),
+        }
+        prev = Some(current);
+    }
+    tokens
+}
+
+/// Byte ranges of the tokens not `kept`. A kept whitespace token between
+/// two changed tokens is absorbed so a replaced phrase reads as one block.
+fn changed_ranges(text: &str, tokens: &[(usize, usize)], kept: &[bool]) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for (i, &(start, end)) in tokens.iter().enumerate() {
+        let bridges = i > 0
+            && i + 1 < tokens.len()
+            && !kept[i - 1]
+            && !kept[i + 1]
+            && text[start..end].trim().is_empty();
+        if kept[i] && !bridges {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some(last) if last.1 == start => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(origin: LineOrigin, content: &str) -> DiffLine {
+        DiffLine {
+            origin,
+            content: content.to_string(),
+            old_lineno: None,
+            new_lineno: None,
+            highlighted_spans: None,
+        }
+    }
+
+    fn slices<'a>(text: &'a str, ranges: &[(usize, usize)]) -> Vec<&'a str> {
+        ranges.iter().map(|&(s, e)| &text[s..e]).collect()
+    }
+
+    #[test]
+    fn should_emphasize_only_the_changed_token() {
+        // given
+        let old = "let total = compute(items, 10);";
+        let new = "let total = compute(items, 20);";
+
+        // when
+        let emphasis = word_emphasis(old, new).expect("pair shares content");
+
+        // then
+        assert_eq!(slices(old, &emphasis.old), vec!["10"]);
+        assert_eq!(slices(new, &emphasis.new), vec!["20"]);
+    }
+
+    #[test]
+    fn should_join_replaced_phrase_across_whitespace() {
+        // given
+        let old = "call(first second);";
+        let new = "call(third fourth);";
+
+        // when
+        let emphasis = word_emphasis(old, new).expect("pair shares content");
+
+        // then
+        assert_eq!(slices(old, &emphasis.old), vec!["first second"]);
+        assert_eq!(slices(new, &emphasis.new), vec!["third fourth"]);
+    }
+
+    #[test]
+    fn should_leave_side_without_changes_unemphasized_on_pure_insertion() {
+        // given
+        let old = "call(a)";
+        let new = "call(a, b)";
+
+        // when
+        let emphasis = word_emphasis(old, new).expect("pair shares content");
+
+        // then
+        assert!(emphasis.old.is_empty());
+        assert_eq!(slices(new, &emphasis.new), vec![", b"]);
+    }
+
+    #[test]
+    fn should_skip_lines_with_nothing_in_common() {
+        // given / when / then
+        assert_eq!(word_emphasis("alpha beta", "gamma delta"), None);
+        assert_eq!(word_emphasis("same", "same"), None);
+    }
+
+    #[test]
+    fn should_use_byte_offsets_for_multibyte_text() {
+        // given
+        let old = "msg = \"héllo wörld\"";
+        let new = "msg = \"héllo welt\"";
+
+        // when
+        let emphasis = word_emphasis(old, new).expect("pair shares content");
+
+        // then
+        assert_eq!(slices(old, &emphasis.old), vec!["wörld"]);
+        assert_eq!(slices(new, &emphasis.new), vec!["welt"]);
+    }
+
+    #[test]
+    fn should_pair_lines_by_position_within_replacement_block() {
+        // given
+        let lines = vec![
+            line(LineOrigin::Context, "ctx"),
+            line(LineOrigin::Deletion, "a1"),
+            line(LineOrigin::Deletion, "a2"),
+            line(LineOrigin::Addition, "b1"),
+            line(LineOrigin::Addition, "b2"),
+            line(LineOrigin::Addition, "b3"),
+            line(LineOrigin::Addition, "lonely"),
+        ];
+
+        // when / then
+        assert_eq!(paired_line(&lines, 0), None);
+        assert_eq!(paired_line(&lines, 1), Some(3));
+        assert_eq!(paired_line(&lines, 2), Some(4));
+        assert_eq!(paired_line(&lines, 3), Some(1));
+        assert_eq!(paired_line(&lines, 4), Some(2));
+        assert_eq!(paired_line(&lines, 5), None);
+        assert_eq!(paired_line(&lines, 6), None);
+    }
+
+    #[test]
+    fn should_not_pair_additions_separated_from_deletions_by_context() {
+        // given
+        let lines = vec![
+            line(LineOrigin::Deletion, "a"),
+            line(LineOrigin::Context, "ctx"),
+            line(LineOrigin::Addition, "b"),
+        ];
+
+        // when / then
+        assert_eq!(paired_line(&lines, 0), None);
+        assert_eq!(paired_line(&lines, 2), None);
+    }
+
+    #[test]
+    fn should_measure_emphasis_against_highlighted_text() {
+        // given
+        use ratatui::style::Style;
+        let mut old = line(LineOrigin::Deletion, "x = 1");
+        old.highlighted_spans = Some(vec![
+            (Style::default(), "x".to_string()),
+            (Style::default(), " = 1".to_string()),
+        ]);
+        let lines = vec![old, line(LineOrigin::Addition, "x = 2")];
+
+        // when
+        let ranges = line_emphasis(&lines, 0).expect("paired");
+
+        // then
+        assert_eq!(ranges, vec![(4, 5)]);
+    }
+}
@@ -51,6 +51,7 @@ fn materialize_file_patch(patch: FilePatch, highlighter: &SyntaxHighlighter) ->
         is_too_large: patch.is_too_large,
         is_commit_message: false,
         content_hash,
+        review_key: patch.review_key,
     })
 }
 
@@ -60,10 +61,10 @@ fn parse_hunks(
     highlighter: &SyntaxHighlighter,
 ) -> Result<Vec<DiffHunk>> {
     let mut hunks = Vec::new();
-    let mut lines = patch.lines().peekable();
+    let mut lines = patch.split_inclusive('\n').peekable();
     let mut parsed_hunk = false;
 
-    while let Some(line) = lines.next() {
+    while let Some(line) = lines.next().map(strip_line_ending) {
         if line.starts_with("@@ ") {
             hunks.push(parse_hunk(line, &mut lines, file_path, highlighter)?);
             parsed_hunk = true;
@@ -111,13 +112,14 @@ where
     let mut new_lineno = new_start;
     let mut old_remaining = old_count;
     let mut new_remaining = new_count;
+    let mut raw_body = String::new();
 
     // Counts from the hunk header are the grammar. Prefix-looking content is
     // still content: deleting `-- comment` yields `--- comment`, and adding
     // `++i` yields `+++i`. We consume until both declared sides are complete,
     // rather than looking for path-like sentinel strings inside the body.
     while old_remaining > 0 || new_remaining > 0 {
-        let line = lines.next().ok_or_else(|| {
+        let raw_line = lines.next().ok_or_else(|| {
             invalid_patch(
                 file_path,
                 format!(
@@ -125,6 +127,8 @@ where
                 ),
             )
         })?;
+        push_raw_line(&mut raw_body, raw_line);
+        let line = strip_line_ending(raw_line);
 
         if line.starts_with('\\') {
             // `\ No newline at end of file` does not consume either side.
@@ -184,6 +188,11 @@ where
         line_numbers.push((old_ln, new_ln));
     }
 
+    // A marker after the final body line still belongs to this hunk.
+    while let Some(raw_line) = lines.next_if(|line| line.starts_with('\\')) {
+        push_raw_line(&mut raw_body, raw_line);
+    }
+
     let highlight_sequences =
         SyntaxHighlighter::split_diff_lines_for_highlighting(&line_contents, &line_origins);
     let (old_highlighted_lines, new_highlighted_lines) = if !needs_full_file_highlight(file_path) {
@@ -225,9 +234,26 @@ where
         old_count,
         new_start,
         new_count,
+        raw_body: Some(raw_body),
     })
 }
 
+/// Trim the `\n` or `\r\n` that `split_inclusive` leaves on a line, the
+/// same way `str::lines` would.
+fn strip_line_ending(line: &str) -> &str {
+    let line = line.strip_suffix('\n').unwrap_or(line);
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+/// Keep the line verbatim, terminating the last one when the patch text
+/// was trimmed.
+fn push_raw_line(raw_body: &mut String, line: &str) {
+    raw_body.push_str(line);
+    if !line.ends_with('\n') {
+        raw_body.push('\n');
+    }
+}
+
 fn invalid_patch(path: &Path, detail: String) -> TuicrError {
     TuicrError::VcsCommand(format!(
         "invalid unified diff for {}: {detail}",
@@ -235,7 +261,7 @@ fn invalid_patch(path: &Path, detail: String) -> TuicrError {
     ))
 }
 
-fn parse_hunk_header(line: &str) -> Option<(u32, u32, u32, u32)> {
+pub(crate) fn parse_hunk_header(line: &str) -> Option<(u32, u32, u32, u32)> {
     let tail = line.strip_prefix("@@ ")?;
     let (ranges, _) = tail.split_once(" @@")?;
     let mut parts = ranges.split_whitespace();
@@ -350,6 +376,19 @@ mod tests {
         assert_eq!(files[0].new_path.as_ref(), Some(&expected));
     }
 
+    #[test]
+    fn keeps_raw_hunk_body_with_crlf_and_missing_newline_markers() {
+        // Forges hand back patch text without its final newline.
+        let files = parse("@@ -1 +1 @@\n-\told\r\n+\tnew\n\\ No newline at end of file").unwrap();
+        let hunk = &files[0].hunks[0];
+
+        assert_eq!(hunk.lines[0].content, "    old");
+        assert_eq!(
+            hunk.raw_body.as_deref(),
+            Some("-\told\r\n+\tnew\n\\ No newline at end of file\n")
+        );
+    }
+
     #[test]
     fn keeps_deleted_lines_whose_content_looks_like_headers() {
         let files =
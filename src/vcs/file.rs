@@ -186,6 +186,7 @@ impl FileBackend {
                 is_too_large: true,
                 is_commit_message: false,
                 content_hash,
+                review_key: None,
             });
         }
 
@@ -264,6 +265,7 @@ impl FileBackend {
             old_count,
             new_start: 1,
             new_count: total_lines,
+            raw_body: None,
         };
 
         let hunks = vec![hunk];
@@ -277,6 +279,7 @@ impl FileBackend {
             is_too_large: false,
             is_commit_message: false,
             content_hash,
+            review_key: None,
         })
     }
 }
@@ -0,0 +1,163 @@
+use chrono::{TimeZone, Utc};
+use git2::{BlameOptions, Repository};
+use std::path::Path;
+
+use crate::error::{Result, TuicrError};
+use crate::vcs::BlameLine;
+
+/// Blame a file with libgit2.
+///
+/// When `ref_commit` is `Some`, blames the file as of that commit. Otherwise
+/// the working-tree content is blamed against HEAD, so lines that only exist
+/// on disk come back as [`BlameLine::uncommitted`].
+pub fn blame_file(
+    repo: &Repository,
+    file_path: &Path,
+    ref_commit: Option<&str>,
+) -> Result<Vec<BlameLine>> {
+    let mut opts = BlameOptions::new();
+    let line_count = match ref_commit {
+        Some(spec) => {
+            let commit = repo.revparse_single(spec)?.peel_to_commit()?;
+            opts.newest_commit(commit.id());
+            let entry = commit.tree()?.get_path(file_path)?;
+            let blob = repo.find_blob(entry.id())?;
+            count_lines(blob.content())
+        }
+        None => {
+            let workdir = repo.workdir().ok_or(TuicrError::NotARepository)?;
+            let content = std::fs::read(workdir.join(file_path))?;
+            let committed = match repo.blame_file(file_path, Some(&mut opts)) {
+                Ok(blame) => blame,
+                // Untracked files and unborn branches have no history at all.
+                Err(_) => return Ok(vec![BlameLine::uncommitted(); count_lines(&content)]),
+            };
+            let blame = committed.blame_buffer(&content)?;
+            return Ok(collect_lines(&blame, count_lines(&content)));
+        }
+    };
+
+    let blame = repo.blame_file(file_path, Some(&mut opts))?;
+    Ok(collect_lines(&blame, line_count))
+}
+
+fn collect_lines(blame: &git2::Blame<'_>, line_count: usize) -> Vec<BlameLine> {
+    let mut lines = vec![BlameLine::uncommitted(); line_count];
+    for hunk in blame.iter() {
+        let id = hunk.final_commit_id();
+        let line = if id.is_zero() {
+            BlameLine::uncommitted()
+        } else {
+            let signature = hunk.final_signature();
+            BlameLine {
+                commit_id: Some(id.to_string()),
+                author: signature.name().unwrap_or_default().to_string(),
+                time: Utc.timestamp_opt(signature.when().seconds(), 0).single(),
+            }
+        };
+        let start = hunk.final_start_line().saturating_sub(1);
+        let end = (start + hunk.lines_in_hunk()).min(line_count);
+        for slot in lines.iter_mut().take(end).skip(start) {
+            *slot = line.clone();
+        }
+    }
+    lines
+}
+
+/// Number of lines as `str::lines` counts them: a trailing newline does not
+/// start another line.
+fn count_lines(content: &[u8]) -> usize {
+    String::from_utf8_lossy(content).lines().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::process::Command;
+
+    fn git(workdir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .current_dir(workdir)
+            .args([
+                "-c",
+                "commit.gpgsign=false",
+                "-c",
+                "user.name=Alice",
+                "-c",
+                "user.email=alice@example.com",
+            ])
+            .args(args)
+            .output()
+            .expect("failed to run git");
+        assert!(
+            output.status.success(),
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    #[test]
+    fn should_mark_working_tree_edits_as_uncommitted() {
+        // given
+        let temp = tempfile::tempdir().expect("temp dir");
+        let root = temp.path();
+        git(root, &["init", "-q"]);
+        fs::write(root.join("a.txt"), "one\ntwo\n").unwrap();
+        git(root, &["add", "a.txt"]);
+        git(root, &["commit", "-q", "-m", "first"]);
+        fs::write(root.join("a.txt"), "one\nchanged\nthree\n").unwrap();
+        let repo = Repository::open(root).unwrap();
+        let head = repo.head().unwrap().target().unwrap().to_string();
+
+        // when
+        let lines = blame_file(&repo, Path::new("a.txt"), None).unwrap();
+
+        // then
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].commit_id.as_deref(), Some(head.as_str()));
+        assert_eq!(lines[0].author, "Alice");
+        assert_eq!(lines[1], BlameLine::uncommitted());
+        assert_eq!(lines[2], BlameLine::uncommitted());
+    }
+
+    #[test]
+    fn should_blame_file_as_of_ref_commit() {
+        // given
+        let temp = tempfile::tempdir().expect("temp dir");
+        let root = temp.path();
+        git(root, &["init", "-q"]);
+        fs::write(root.join("a.txt"), "one\n").unwrap();
+        git(root, &["add", "a.txt"]);
+        git(root, &["commit", "-q", "-m", "first"]);
+        fs::write(root.join("a.txt"), "one\ntwo\n").unwrap();
+        git(root, &["commit", "-q", "-am", "second"]);
+        let repo = Repository::open(root).unwrap();
+        let second = repo.head().unwrap().peel_to_commit().unwrap();
+        let first = second.parent(0).unwrap().id().to_string();
+
+        // when
+        let lines = blame_file(&repo, Path::new("a.txt"), Some(&first)).unwrap();
+
+        // then
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].commit_id.as_deref(), Some(first.as_str()));
+    }
+
+    #[test]
+    fn should_treat_untracked_file_as_uncommitted() {
+        // given
+        let temp = tempfile::tempdir().expect("temp dir");
+        let root = temp.path();
+        git(root, &["init", "-q"]);
+        fs::write(root.join("new.txt"), "a\nb\n").unwrap();
+        let repo = Repository::open(root).unwrap();
+
+        // when
+        let lines = blame_file(&repo, Path::new("new.txt"), None).unwrap();
+
+        // then
+        assert_eq!(lines, vec![BlameLine::uncommitted(); 2]);
+    }
+}
@@ -9,6 +9,7 @@ use chrono::{TimeZone, Utc};
 
 use crate::error::{Result, TuicrError};
 use crate::model::{DiffFile, DiffHunk, DiffLine, FileStatus, LineOrigin, LineSide};
+use crate::process::run_command_output_with_stdin;
 use crate::syntax::SyntaxHighlighter;
 use crate::vcs::diff_parser;
 use crate::vcs::git::raw::{
@@ -16,13 +17,14 @@ use crate::vcs::git::raw::{
     patch_text_from_raw_patch_output, split_patch_blocks,
 };
 use crate::vcs::{
-    ChangeKind, CommitInfo, DiffWhitespaceMode, ResolvedRevisionRange, RevisionDiffTarget,
-    VcsBackend, VcsChangeStatus, VcsInfo,
+    BlameLine, ChangeKind, CommitInfo, DiffWhitespaceMode, HunkChange, HunkChangeBase,
+    ResolvedRevisionRange, RevisionDiffTarget, VcsBackend, VcsChangeStatus, VcsInfo,
 };
 use crate::vcs::{
     container_file_paths, enhance_with_full_file_highlight, slice_context_lines, tabify,
 };
 
+use super::staging::{self, PatchTarget};
 use super::{
     GitRepoMode, RevisionExpression, git_bool_config_enabled, git_command_error,
     git_fsmonitor_config_enabled, run_git_command,
@@ -404,6 +406,22 @@ impl VcsBackend for GitCliBackend {
         )
     }
 
+    fn blame(&self, file_path: &Path, ref_commit: Option<&str>) -> Result<Vec<BlameLine>> {
+        let mut args = strings(["blame", "--porcelain"]);
+        args.extend(ref_commit.map(str::to_string));
+        args.push("--".to_string());
+        args.push(file_path.to_string_lossy().into_owned());
+        match run_git_command_strings(&self.root_path, args) {
+            Ok(output) => Ok(parse_blame_porcelain(&output)),
+            // Untracked files and unborn branches have no history at all.
+            Err(_) if ref_commit.is_none() => {
+                let content = fs::read_to_string(self.root_path.join(file_path))?;
+                Ok(vec![BlameLine::uncommitted(); content.lines().count()])
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     fn stage_file(&self, path: &Path) -> Result<()> {
         let output = Command::new("git")
             .current_dir(&self.root_path)
@@ -421,6 +439,34 @@ impl VcsBackend for GitCliBackend {
 
         Ok(())
     }
+
+    fn apply_hunk_change(&self, change: &HunkChange<'_>) -> Result<()> {
+        let path = staging::check_hunk_change(change, self.whitespace_mode)?;
+        let mut args = strings(["--literal-pathspecs", "diff", "--no-ext-diff", "--no-color"]);
+        match change.base {
+            HunkChangeBase::Unstaged => {}
+            HunkChangeBase::Staged => args.push("--cached".to_string()),
+            HunkChangeBase::StagedAndUnstaged => args.push("HEAD".to_string()),
+        }
+        args.push("--".to_string());
+        args.push(path.to_string_lossy().into_owned());
+        let raw = run_git_command_strings(&self.root_path, args)?;
+
+        for step in staging::hunk_change_steps(change)? {
+            let patch = staging::synthesize_patch(&raw, path, change, step.direction)?;
+            let mut args = vec!["apply", "--whitespace=nowarn"];
+            if step.target == PatchTarget::Index {
+                args.push("--cached");
+            }
+            args.push("-");
+            match run_command_output_with_stdin("git", Some(&self.root_path), args, &patch) {
+                Ok(_) => {}
+                Err(_) if !step.required => {}
+                Err(err) => return Err(staging::patch_rejected(&err.stderr)),
+            }
+        }
+        Ok(())
+    }
 }
 
 fn strings<const N: usize>(args: [&str; N]) -> Vec<String> {
@@ -836,6 +882,7 @@ fn build_untracked_diff_file(
         old_count: 0,
         new_start: 1,
         new_count,
+        raw_body: None,
     }];
     let content_hash = DiffFile::compute_content_hash(&hunks);
 
@@ -848,6 +895,7 @@ fn build_untracked_diff_file(
         is_too_large: false,
         is_commit_message: false,
         content_hash,
+        review_key: None,
     })
 }
 
@@ -861,6 +909,7 @@ fn diff_file_without_hunks(path: &Path, is_binary: bool, is_too_large: bool) ->
         is_too_large,
         is_commit_message: false,
         content_hash: 0,
+        review_key: None,
     }
 }
 
@@ -1150,6 +1199,55 @@ fn parse_commit_record(
     })
 }
 
+/// Parse `git blame --porcelain` output. Each line's header carries the
+/// commit and final line number; author fields only follow the first line
+/// attributed to a given commit, so they are remembered per commit.
+fn parse_blame_porcelain(output: &str) -> Vec<BlameLine> {
+    let mut lines: Vec<BlameLine> = Vec::new();
+    let mut authors: HashMap<String, (String, Option<i64>)> = HashMap::new();
+    let mut current: Option<(String, usize)> = None;
+
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            let Some((sha, final_line)) = current.take() else {
+                continue;
+            };
+            let entry = if sha.bytes().all(|b| b == b'0') {
+                BlameLine::uncommitted()
+            } else {
+                let (author, time) = authors.get(&sha).cloned().unwrap_or_default();
+                BlameLine {
+                    commit_id: Some(sha),
+                    author,
+                    time: time.and_then(|t| Utc.timestamp_opt(t, 0).single()),
+                }
+            };
+            if lines.len() < final_line {
+                lines.resize(final_line, BlameLine::uncommitted());
+            }
+            lines[final_line - 1] = entry;
+        } else if let Some((sha, _)) = &current {
+            let info = authors.entry(sha.clone()).or_default();
+            if let Some(name) = line.strip_prefix("author ") {
+                info.0 = name.to_string();
+            } else if let Some(time) = line.strip_prefix("author-time ") {
+                info.1 = time.parse().ok();
+            }
+        } else {
+            let mut fields = line.split(' ');
+            let (Some(sha), Some(_orig), Some(final_line)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+            if let Ok(final_line @ 1..) = final_line.parse::<usize>() {
+                current = Some((sha.to_string(), final_line));
+            }
+        }
+    }
+    lines
+}
+
 fn parse_commit_message(message: &str) -> (String, Option<String>) {
     let mut lines = message.lines();
     let summary = lines.next().unwrap_or("(no message)").to_string();
@@ -1270,6 +1368,7 @@ where
 mod tests {
     use super::*;
     use crate::vcs::git::{diff, repository};
+    use crate::vcs::{HunkOperation, HunkSelection};
 
     fn git(workdir: &Path, args: &[&str]) {
         // `-c commit.gpgsign=false` overrides any global signing config so
@@ -1289,6 +1388,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parses_blame_porcelain_with_repeated_commits() {
+        let sha = "a".repeat(40);
+        let zero = "0".repeat(40);
+        let output = format!(
+            "{sha} 1 1 2\nauthor Alice\nauthor-mail <alice@example.com>\nauthor-time 1700000000\nsummary first\nfilename a.txt\n\tone\n\
+             {sha} 2 2\n\ttwo\n\
+             {zero} 3 3 1\nauthor Not Committed Yet\nauthor-time 1800000000\nfilename a.txt\n\tthree\n"
+        );
+
+        let lines = parse_blame_porcelain(&output);
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].commit_id.as_deref(), Some(sha.as_str()));
+        assert_eq!(lines[0].author, "Alice");
+        assert_eq!(lines[0].time.map(|t| t.timestamp()), Some(1_700_000_000));
+        assert_eq!(lines[1], lines[0]);
+        assert_eq!(lines[2], BlameLine::uncommitted());
+    }
+
     #[test]
     fn parses_porcelain_status_sides() {
         assert_eq!(
@@ -1612,6 +1731,63 @@ mod tests {
         assert!(binary.is_binary);
     }
 
+    #[test]
+    fn stages_a_selected_line_and_discards_the_rest_of_a_hunk() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let workdir = temp_dir.path();
+        git(workdir, &["init"]);
+        git(workdir, &["config", "user.email", "test@example.com"]);
+        git(workdir, &["config", "user.name", "Test User"]);
+        write_file(workdir, "f.txt", "one\ntwo\nthree\n");
+        git(workdir, &["add", "."]);
+        git(workdir, &["commit", "-m", "initial"]);
+        write_file(workdir, "f.txt", "one\ntwo\tedited\nthree\nfour\n");
+
+        let backend = GitCliBackend::discover_from(workdir, DiffWhitespaceMode::Normal)
+            .expect("failed to discover CLI backend");
+        let highlighter = SyntaxHighlighter::default();
+        let file = backend.get_unstaged_diff(&highlighter).unwrap().remove(0);
+        let added = file.hunks[0]
+            .lines
+            .iter()
+            .position(|line| line.origin == LineOrigin::Addition)
+            .unwrap();
+
+        // Stage only `-two` / `+two\tedited`, leaving `+four` unstaged.
+        let selections = [HunkSelection {
+            hunk_idx: 0,
+            lines: Some(added - 1..=added),
+        }];
+        backend
+            .apply_hunk_change(&HunkChange {
+                file: &file,
+                base: HunkChangeBase::Unstaged,
+                operation: HunkOperation::Stage,
+                selections: &selections,
+            })
+            .expect("failed to stage selected lines");
+        let staged = run_git_command(workdir, &["diff", "--cached"]).unwrap();
+        assert!(staged.contains("+two\tedited"));
+        assert!(!staged.contains("+four"));
+
+        let file = backend.get_unstaged_diff(&highlighter).unwrap().remove(0);
+        backend
+            .apply_hunk_change(&HunkChange {
+                file: &file,
+                base: HunkChangeBase::Unstaged,
+                operation: HunkOperation::Discard,
+                selections: &[HunkSelection {
+                    hunk_idx: 0,
+                    lines: None,
+                }],
+            })
+            .expect("failed to discard hunk");
+        assert_eq!(
+            fs::read_to_string(workdir.join("f.txt")).unwrap(),
+            "one\ntwo\tedited\nthree\n"
+        );
+    }
+
     #[test]
     fn reads_staged_diff_and_stages_files_in_sparse_index() {
         let (temp_dir, backend, _ids) = setup_sparse_index_repo();
@@ -308,6 +308,7 @@ fn parse_diff(diff: &Diff, highlighter: &SyntaxHighlighter) -> Result<Vec<DiffFi
             is_too_large,
             is_commit_message: false,
             content_hash,
+            review_key: None,
         });
     }
 
@@ -341,18 +342,32 @@ fn parse_hunks(
             let mut line_contents: Vec<String> = Vec::new();
             let mut line_origins: Vec<LineOrigin> = Vec::new();
             let mut line_numbers: Vec<(Option<u32>, Option<u32>)> = Vec::new();
+            let mut raw_body = String::new();
 
             for line_idx in 0..patch.num_lines_in_hunk(hunk_idx)? {
                 let line = patch.line_in_hunk(hunk_idx, line_idx)?;
+                let raw = String::from_utf8_lossy(line.content());
+                match line.origin() {
+                    // The marker's content already carries the newline the
+                    // previous line lacks plus the `\ No newline` text.
+                    '=' | '>' | '<' => raw_body.push_str(&raw),
+                    origin => {
+                        raw_body.push(origin);
+                        raw_body.push_str(&raw);
+                    }
+                }
 
                 let origin = match line.origin() {
                     '+' => LineOrigin::Addition,
                     '-' => LineOrigin::Deletion,
-                    ' ' => LineOrigin::Context,
+                    // `\ No newline at end of file` markers belong to the
+                    // previous line, not a row of their own. Skipping them
+                    // keeps line indices aligned with the CLI backend and
+                    // with the raw patch that hunk staging rebuilds.
+                    '=' | '>' | '<' => continue,
                     _ => LineOrigin::Context,
                 };
 
-                let raw = String::from_utf8_lossy(line.content());
                 let content = tabify(raw.trim_end_matches(['\n', '\r']));
 
                 line_contents.push(content);
@@ -401,6 +416,7 @@ fn parse_hunks(
                 old_count,
                 new_start,
                 new_count,
+                raw_body: Some(raw_body),
             });
         }
     }
@@ -779,4 +795,40 @@ mod tests {
             assert_files_match(&highlighted, &plain, label);
         }
     }
+
+    #[test]
+    fn should_keep_raw_hunk_bodies_for_both_git_backends() {
+        let repo = TempRepo::with_changes(&[("a.txt", "\tindented\nlast")]);
+
+        let backends: Vec<(&str, Box<dyn VcsBackend>)> = vec![
+            (
+                "libgit2",
+                Box::new(
+                    Libgit2Backend::discover_from(repo.path(), DiffWhitespaceMode::Normal)
+                        .expect("failed to open libgit2 backend"),
+                ),
+            ),
+            (
+                "git cli",
+                Box::new(
+                    GitCliBackend::discover_from(repo.path(), DiffWhitespaceMode::Normal)
+                        .expect("failed to open git cli backend"),
+                ),
+            ),
+        ];
+
+        for (label, backend) in backends {
+            let files = backend
+                .get_working_tree_diff(&SyntaxHighlighter::plain())
+                .unwrap_or_else(|e| panic!("{label}: fetch failed: {e}"));
+            let hunk = &files[0].hunks[0];
+
+            assert_eq!(hunk.lines[0].content, "    indented", "{label}");
+            assert_eq!(
+                hunk.raw_body.as_deref(),
+                Some("+\tindented\n+last\n\\ No newline at end of file\n"),
+                "{label}"
+            );
+        }
+    }
 }
@@ -6,9 +6,10 @@ use crate::error::{Result, TuicrError};
 use crate::model::{DiffFile, DiffLine, FileStatus};
 use crate::syntax::SyntaxHighlighter;
 
-use super::{context, diff, repository, staging};
+use super::{blame, context, diff, repository, staging};
 use crate::vcs::traits::{
-    ChangeKind, CommitInfo, DiffWhitespaceMode, ResolvedRevisionRange, VcsBackend, VcsInfo, VcsType,
+    BlameLine, ChangeKind, CommitInfo, DiffWhitespaceMode, HunkChange, ResolvedRevisionRange,
+    VcsBackend, VcsInfo, VcsType,
 };
 
 /// Git backend implementation using the git2/libgit2 library.
@@ -204,9 +205,17 @@ impl VcsBackend for Libgit2Backend {
         )
     }
 
+    fn blame(&self, file_path: &Path, ref_commit: Option<&str>) -> Result<Vec<BlameLine>> {
+        blame::blame_file(&self.repo, file_path, ref_commit)
+    }
+
     fn stage_file(&self, path: &Path) -> Result<()> {
         staging::stage_file(&self.repo, path)
     }
+
+    fn apply_hunk_change(&self, change: &HunkChange<'_>) -> Result<()> {
+        staging::apply_hunk_change(&self.repo, self.whitespace_mode, change)
+    }
 }
 
 #[cfg(test)]
@@ -1,3 +1,4 @@
+pub mod blame;
 mod cli;
 pub mod context;
 pub mod diff;
@@ -15,8 +16,8 @@ use crate::process::{CommandOutputError, CommandOutputErrorKind, run_command_out
 use crate::syntax::SyntaxHighlighter;
 
 use super::traits::{
-    ChangeKind, CommitInfo, DiffWhitespaceMode, ResolvedRevisionRange, VcsBackend, VcsChangeStatus,
-    VcsInfo,
+    BlameLine, ChangeKind, CommitInfo, DiffWhitespaceMode, HunkChange, ResolvedRevisionRange,
+    VcsBackend, VcsChangeStatus, VcsInfo,
 };
 use cli::GitCliBackend;
 pub use libgit2::Libgit2Backend;
@@ -210,7 +211,9 @@ fn run_git_command(workdir: &Path, args: &[&str]) -> Result<String> {
 
 pub(super) fn git_command_error(error: CommandOutputError) -> TuicrError {
     match error.kind {
-        CommandOutputErrorKind::Unsuccessful => TuicrError::VcsCommand(error.stderr),
+        CommandOutputErrorKind::Unsuccessful | CommandOutputErrorKind::TimedOut => {
+            TuicrError::VcsCommand(error.stderr)
+        }
         CommandOutputErrorKind::NotFound | CommandOutputErrorKind::SpawnFailed => {
             TuicrError::VcsCommand(format!("Failed to run git: {}", error.stderr))
         }
@@ -383,12 +386,26 @@ impl VcsBackend for GitBackend {
         }
     }
 
+    fn blame(&self, file_path: &Path, ref_commit: Option<&str>) -> Result<Vec<BlameLine>> {
+        match self {
+            Self::Libgit2(backend) => backend.blame(file_path, ref_commit),
+            Self::Cli(backend) => backend.blame(file_path, ref_commit),
+        }
+    }
+
     fn stage_file(&self, path: &Path) -> Result<()> {
         match self {
             Self::Libgit2(backend) => backend.stage_file(path),
             Self::Cli(backend) => backend.stage_file(path),
         }
     }
+
+    fn apply_hunk_change(&self, change: &HunkChange<'_>) -> Result<()> {
+        match self {
+            Self::Libgit2(backend) => backend.apply_hunk_change(change),
+            Self::Cli(backend) => backend.apply_hunk_change(change),
+        }
+    }
 }
 
 #[cfg(test)]
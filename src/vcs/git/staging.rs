@@ -1,7 +1,11 @@
-use git2::Repository;
+use git2::{ApplyLocation, Diff, DiffOptions, Patch, Repository};
 use std::path::Path;
 
-use crate::error::Result;
+use crate::error::{Result, TuicrError};
+use crate::model::{DiffHunk, FileStatus, LineOrigin};
+use crate::vcs::traits::{
+    DiffWhitespaceMode, HunkChange, HunkChangeBase, HunkOperation, HunkSelection,
+};
 
 pub fn stage_file(repo: &Repository, path: &Path) -> Result<()> {
     let mut index = repo.index()?;
@@ -10,10 +14,349 @@ pub fn stage_file(repo: &Repository, path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Apply a hunk-level stage/unstage/discard through libgit2.
+pub fn apply_hunk_change(
+    repo: &Repository,
+    whitespace_mode: DiffWhitespaceMode,
+    change: &HunkChange<'_>,
+) -> Result<()> {
+    let path = check_hunk_change(change, whitespace_mode)?;
+    let raw = raw_file_patch(repo, path, change.base)?;
+    for step in hunk_change_steps(change)? {
+        let patch = synthesize_patch(&raw, path, change, step.direction)?;
+        let diff = Diff::from_buffer(patch.as_bytes())?;
+        let location = match step.target {
+            PatchTarget::Index => ApplyLocation::Index,
+            PatchTarget::Worktree => ApplyLocation::WorkDir,
+        };
+        match repo.apply(&diff, location, None) {
+            Ok(()) => {}
+            Err(_) if !step.required => {}
+            Err(err) => return Err(patch_rejected(err.message())),
+        }
+    }
+    Ok(())
+}
+
+/// Re-diff one path for `base`, returning the unified patch text.
+fn raw_file_patch(repo: &Repository, path: &Path, base: HunkChangeBase) -> Result<String> {
+    let head = repo.head().ok().and_then(|h| h.peel_to_tree().ok());
+    let mut opts = DiffOptions::new();
+    opts.pathspec(path).disable_pathspec_match(true);
+    let diff = match base {
+        HunkChangeBase::Unstaged => repo.diff_index_to_workdir(None, Some(&mut opts))?,
+        HunkChangeBase::Staged => repo.diff_tree_to_index(head.as_ref(), None, Some(&mut opts))?,
+        HunkChangeBase::StagedAndUnstaged => {
+            repo.diff_tree_to_workdir_with_index(head.as_ref(), Some(&mut opts))?
+        }
+    };
+    let mut patch = Patch::from_diff(&diff, 0)?.ok_or_else(stale_diff_error)?;
+    let buf = patch.to_buf()?;
+    patch_text(buf.to_vec())
+}
+
+/// Where a synthesized patch is applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum PatchTarget {
+    Index,
+    Worktree,
+}
+
+/// Whether a patch replays the diff (old → new) or reverts it (new → old).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum PatchDirection {
+    Forward,
+    Reverse,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct PatchStep {
+    pub target: PatchTarget,
+    pub direction: PatchDirection,
+    /// Best-effort steps are skipped when their patch does not apply.
+    pub required: bool,
+}
+
+/// The patches a hunk change applies, in order.
+///
+/// Discarding from the combined HEAD → worktree view reverts the worktree,
+/// then also reverts the index when the same change was staged, so the
+/// discarded lines don't linger in the next commit.
+pub(super) fn hunk_change_steps(change: &HunkChange<'_>) -> Result<Vec<PatchStep>> {
+    if !change.base.supports(change.operation) {
+        return Err(TuicrError::InvalidInput(match change.operation {
+            HunkOperation::Stage => "Hunk is already staged".to_string(),
+            HunkOperation::Unstage => "Hunk has no staged changes".to_string(),
+            HunkOperation::Discard => "Unstage the hunk before discarding it".to_string(),
+        }));
+    }
+    let step = |target, direction, required| PatchStep {
+        target,
+        direction,
+        required,
+    };
+    Ok(match change.operation {
+        HunkOperation::Stage => vec![step(PatchTarget::Index, PatchDirection::Forward, true)],
+        HunkOperation::Unstage => vec![step(PatchTarget::Index, PatchDirection::Reverse, true)],
+        HunkOperation::Discard => match change.base {
+            HunkChangeBase::StagedAndUnstaged => vec![
+                step(PatchTarget::Worktree, PatchDirection::Reverse, true),
+                step(PatchTarget::Index, PatchDirection::Reverse, false),
+            ],
+            _ => vec![step(PatchTarget::Worktree, PatchDirection::Reverse, true)],
+        },
+    })
+}
+
+/// Preflight shared by both Git backends. Returns the path to re-diff.
+pub(super) fn check_hunk_change<'a>(
+    change: &HunkChange<'a>,
+    whitespace_mode: DiffWhitespaceMode,
+) -> Result<&'a Path> {
+    // Whitespace-insensitive hunks don't describe the bytes on disk, so a
+    // patch built from them can't be applied.
+    if whitespace_mode.ignores_all() {
+        return Err(TuicrError::InvalidInput(
+            "Hunk staging is unavailable while whitespace changes are ignored".to_string(),
+        ));
+    }
+    let file = change.file;
+    if file.status != FileStatus::Modified || file.is_binary || file.is_too_large {
+        return Err(TuicrError::InvalidInput(
+            "Hunk staging only works on modified text files; mark the file reviewed and use :stage instead"
+                .to_string(),
+        ));
+    }
+    file.new_path
+        .as_deref()
+        .ok_or_else(|| TuicrError::InvalidInput("File has no path to stage".to_string()))
+}
+
+pub(super) fn patch_text(bytes: Vec<u8>) -> Result<String> {
+    String::from_utf8(bytes)
+        .map_err(|_| TuicrError::InvalidInput("Hunk staging needs UTF-8 file content".to_string()))
+}
+
+pub(super) fn patch_rejected(detail: &str) -> TuicrError {
+    TuicrError::VcsCommand(format!("Patch did not apply: {}", detail.trim()))
+}
+
+fn stale_diff_error() -> TuicrError {
+    TuicrError::VcsCommand(
+        "The diff changed since it was loaded; reload (:e) and try again".to_string(),
+    )
+}
+
+/// Build the patch for `change` out of the freshly generated `raw` patch.
+///
+/// The displayed `DiffFile` can't be used directly: its line content is
+/// normalized for rendering (tabs expanded, trailing `\r` and missing-newline
+/// markers dropped). `raw` carries the exact bytes, and the displayed hunks
+/// only serve as the selection map and a staleness check.
+pub(super) fn synthesize_patch(
+    raw: &str,
+    path: &Path,
+    change: &HunkChange<'_>,
+    direction: PatchDirection,
+) -> Result<String> {
+    build_partial_patch(raw, path, &change.file.hunks, change.selections, direction)?
+        .ok_or_else(|| TuicrError::InvalidInput("Selection has no changed lines".to_string()))
+}
+
+struct RawLine<'a> {
+    origin: LineOrigin,
+    text: &'a str,
+    no_newline: bool,
+}
+
+struct RawHunk<'a> {
+    old_start: u32,
+    new_start: u32,
+    lines: Vec<RawLine<'a>>,
+}
+
+fn parse_range(range: &str) -> Option<(u32, u32)> {
+    match range.split_once(',') {
+        Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+fn parse_hunk_header(line: &str) -> Option<(u32, u32, u32, u32)> {
+    let ranges = line.strip_prefix("@@ -")?.split_once(" @@")?.0;
+    let (old, new) = ranges.split_once(" +")?;
+    let (old_start, old_count) = parse_range(old)?;
+    let (new_start, new_count) = parse_range(new)?;
+    Some((old_start, old_count, new_start, new_count))
+}
+
+/// Split a single-file unified patch into hunks. Counts in the hunk header
+/// decide where a body ends, so content that looks like a header (`--- x`)
+/// stays content.
+fn parse_raw_hunks(patch: &str) -> Option<Vec<RawHunk<'_>>> {
+    let mut lines = patch.split('\n').peekable();
+    let mut hunks = Vec::new();
+    while let Some(line) = lines.next() {
+        if !line.starts_with("@@ -") {
+            continue;
+        }
+        let (old_start, mut old_remaining, new_start, mut new_remaining) = parse_hunk_header(line)?;
+        let mut body: Vec<RawLine<'_>> = Vec::new();
+        while old_remaining > 0 || new_remaining > 0 {
+            let line = lines.next()?;
+            if line.starts_with('\\') {
+                body.last_mut()?.no_newline = true;
+                continue;
+            }
+            let (origin, text) = match line.split_at_checked(1) {
+                Some(("+", text)) => (LineOrigin::Addition, text),
+                Some(("-", text)) => (LineOrigin::Deletion, text),
+                Some((" ", text)) => (LineOrigin::Context, text),
+                // Some tools strip the space off blank context lines.
+                _ if line.is_empty() => (LineOrigin::Context, ""),
+                _ => return None,
+            };
+            if origin != LineOrigin::Addition {
+                old_remaining = old_remaining.checked_sub(1)?;
+            }
+            if origin != LineOrigin::Deletion {
+                new_remaining = new_remaining.checked_sub(1)?;
+            }
+            body.push(RawLine {
+                origin,
+                text,
+                no_newline: false,
+            });
+        }
+        if lines.peek().is_some_and(|next| next.starts_with('\\')) {
+            lines.next();
+            body.last_mut()?.no_newline = true;
+        }
+        hunks.push(RawHunk {
+            old_start,
+            new_start,
+            lines: body,
+        });
+    }
+    Some(hunks)
+}
+
+fn hunks_match(raw: &[RawHunk<'_>], expected: &[DiffHunk]) -> bool {
+    raw.len() == expected.len()
+        && raw.iter().zip(expected).all(|(raw, expected)| {
+            raw.lines.len() == expected.lines.len()
+                && raw
+                    .lines
+                    .iter()
+                    .zip(&expected.lines)
+                    .all(|(raw, expected)| raw.origin == expected.origin)
+        })
+}
+
+/// Keep the selected changes of `raw`, turning the rest back into context.
+///
+/// For `Forward` the result applies to the diff's old side: unselected
+/// additions are dropped and unselected deletions become context. `Reverse`
+/// mirrors that against the new side, with `+`/`-` swapped, so both
+/// directions apply as plain forward patches. `None` when the selection holds
+/// no changed line.
+pub(super) fn build_partial_patch(
+    raw: &str,
+    path: &Path,
+    expected: &[DiffHunk],
+    selections: &[HunkSelection],
+    direction: PatchDirection,
+) -> Result<Option<String>> {
+    let hunks = parse_raw_hunks(raw)
+        .filter(|hunks| hunks_match(hunks, expected))
+        .ok_or_else(stale_diff_error)?;
+
+    let mut ordered: Vec<&HunkSelection> = selections.iter().collect();
+    ordered.sort_by_key(|selection| selection.hunk_idx);
+
+    let mut body = String::new();
+    // Net lines added by earlier hunks of this patch; keeps each new-side
+    // start accurate, which libgit2 relies on to place the hunk.
+    let mut offset: i64 = 0;
+    for selection in ordered {
+        let hunk = hunks.get(selection.hunk_idx).ok_or_else(stale_diff_error)?;
+        let mut lines = String::new();
+        let (mut old_count, mut new_count) = (0i64, 0i64);
+        let mut changed = false;
+        for (idx, line) in hunk.lines.iter().enumerate() {
+            let selected = selection
+                .lines
+                .as_ref()
+                .is_none_or(|range| range.contains(&idx));
+            let prefix = match (line.origin, direction) {
+                (LineOrigin::Context, _) => ' ',
+                (LineOrigin::Addition, PatchDirection::Forward)
+                | (LineOrigin::Deletion, PatchDirection::Reverse) => {
+                    if !selected {
+                        continue;
+                    }
+                    '+'
+                }
+                (LineOrigin::Deletion, PatchDirection::Forward)
+                | (LineOrigin::Addition, PatchDirection::Reverse) => {
+                    if selected {
+                        '-'
+                    } else {
+                        ' '
+                    }
+                }
+            };
+            changed |= prefix != ' ';
+            if prefix != '+' {
+                old_count += 1;
+            }
+            if prefix != '-' {
+                new_count += 1;
+            }
+            lines.push(prefix);
+            lines.push_str(line.text);
+            lines.push('\n');
+            if line.no_newline {
+                lines.push_str("\\ No newline at end of file\n");
+            }
+        }
+        if !changed {
+            continue;
+        }
+
+        let old_start = i64::from(match direction {
+            PatchDirection::Forward => hunk.old_start,
+            PatchDirection::Reverse => hunk.new_start,
+        });
+        // An empty side names the line *before* the hunk; the first line the
+        // hunk touches is one past it.
+        let anchor = if old_count == 0 {
+            old_start + 1
+        } else {
+            old_start
+        };
+        let new_start = anchor + offset;
+        body.push_str(&format!(
+            "@@ -{old_start},{old_count} +{new_start},{new_count} @@\n{lines}"
+        ));
+        offset += new_count - old_count;
+    }
+
+    if body.is_empty() {
+        return Ok(None);
+    }
+    let path = path.to_string_lossy().replace('\\', "/");
+    Ok(Some(format!(
+        "diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n{body}"
+    )))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::DiffFile;
     use std::fs;
+    use std::process::Command;
 
     #[test]
     fn stage_file_adds_to_index() {
@@ -28,4 +371,276 @@ mod tests {
         let index = repo.index().unwrap();
         assert!(index.get_path(Path::new("test.txt"), 0).is_some());
     }
+
+    const RAW: &str = concat!(
+        "diff --git a/f.txt b/f.txt\n",
+        "index 1111111..2222222 100644\n",
+        "--- a/f.txt\n",
+        "+++ b/f.txt\n",
+        "@@ -1,4 +1,4 @@\n",
+        " one\n",
+        "-two\n",
+        "+TWO\n",
+        "+extra\n",
+        " three\n",
+        "-four\n",
+    );
+
+    fn hunks_from(raw: &str) -> Vec<DiffHunk> {
+        parse_raw_hunks(raw)
+            .unwrap()
+            .into_iter()
+            .map(|hunk| DiffHunk {
+                header: String::new(),
+                lines: hunk
+                    .lines
+                    .iter()
+                    .map(|line| crate::model::DiffLine {
+                        origin: line.origin,
+                        content: line.text.to_string(),
+                        old_lineno: None,
+                        new_lineno: None,
+                        highlighted_spans: None,
+                    })
+                    .collect(),
+                old_start: hunk.old_start,
+                old_count: 0,
+                new_start: hunk.new_start,
+                new_count: 0,
+                raw_body: None,
+            })
+            .collect()
+    }
+
+    fn whole(hunk_idx: usize) -> HunkSelection {
+        HunkSelection {
+            hunk_idx,
+            lines: None,
+        }
+    }
+
+    #[test]
+    fn builds_whole_hunk_patch_forward() {
+        let patch = build_partial_patch(
+            RAW,
+            Path::new("f.txt"),
+            &hunks_from(RAW),
+            &[whole(0)],
+            PatchDirection::Forward,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            patch,
+            concat!(
+                "diff --git a/f.txt b/f.txt\n--- a/f.txt\n+++ b/f.txt\n",
+                "@@ -1,4 +1,4 @@\n one\n-two\n+TWO\n+extra\n three\n-four\n",
+            )
+        );
+    }
+
+    #[test]
+    fn partial_forward_patch_keeps_unselected_deletions_as_context() {
+        // Lines 1..=2 are `-two` and `+TWO`.
+        let selection = HunkSelection {
+            hunk_idx: 0,
+            lines: Some(1..=2),
+        };
+        let patch = build_partial_patch(
+            RAW,
+            Path::new("f.txt"),
+            &hunks_from(RAW),
+            &[selection],
+            PatchDirection::Forward,
+        )
+        .unwrap()
+        .unwrap();
+        assert!(patch.ends_with("@@ -1,4 +1,4 @@\n one\n-two\n+TWO\n three\n four\n"));
+    }
+
+    #[test]
+    fn partial_reverse_patch_swaps_signs_against_new_side() {
+        // Revert only `+extra` (line 3).
+        let selection = HunkSelection {
+            hunk_idx: 0,
+            lines: Some(3..=3),
+        };
+        let patch = build_partial_patch(
+            RAW,
+            Path::new("f.txt"),
+            &hunks_from(RAW),
+            &[selection],
+            PatchDirection::Reverse,
+        )
+        .unwrap()
+        .unwrap();
+        assert!(patch.ends_with("@@ -1,4 +1,3 @@\n one\n TWO\n-extra\n three\n"));
+    }
+
+    #[test]
+    fn context_only_selection_yields_no_patch() {
+        let selection = HunkSelection {
+            hunk_idx: 0,
+            lines: Some(0..=0),
+        };
+        let patch = build_partial_patch(
+            RAW,
+            Path::new("f.txt"),
+            &hunks_from(RAW),
+            &[selection],
+            PatchDirection::Forward,
+        )
+        .unwrap();
+        assert!(patch.is_none());
+    }
+
+    #[test]
+    fn keeps_missing_newline_markers() {
+        let raw =
+            "@@ -1 +1 @@\n-old\n\\ No newline at end of file\n+new\n\\ No newline at end of file\n";
+        let patch = build_partial_patch(
+            raw,
+            Path::new("f.txt"),
+            &hunks_from(raw),
+            &[whole(0)],
+            PatchDirection::Forward,
+        )
+        .unwrap()
+        .unwrap();
+        assert!(patch.ends_with(
+            "@@ -1,1 +1,1 @@\n-old\n\\ No newline at end of file\n+new\n\\ No newline at end of file\n"
+        ));
+    }
+
+    #[test]
+    fn rejects_stale_display_hunks() {
+        let mut expected = hunks_from(RAW);
+        expected[0].lines.pop();
+        let err = build_partial_patch(
+            RAW,
+            Path::new("f.txt"),
+            &expected,
+            &[whole(0)],
+            PatchDirection::Forward,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("reload"));
+    }
+
+    // ---- libgit2 apply against a real repository ----
+
+    fn git(workdir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .current_dir(workdir)
+            .args(["-c", "commit.gpgsign=false"])
+            .args(args)
+            .output()
+            .expect("failed to run git");
+        assert!(
+            output.status.success(),
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    }
+
+    /// A committed ten-line file with two separate edits, so the unstaged
+    /// diff has two hunks.
+    fn repo_with_two_hunks() -> (tempfile::TempDir, Repository) {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let root = temp_dir.path();
+        git(root, &["init", "-q"]);
+        git(root, &["config", "user.email", "test@example.com"]);
+        git(root, &["config", "user.name", "Test"]);
+        let original: String = (1..=10).map(|n| format!("line {n}\n")).collect();
+        fs::write(root.join("f.txt"), original).unwrap();
+        git(root, &["add", "f.txt"]);
+        git(root, &["commit", "-q", "-m", "init"]);
+        let edited = (1..=10)
+            .map(|n| match n {
+                1 => "line 1 edited\n".to_string(),
+                10 => "line 10\tedited\n".to_string(),
+                n => format!("line {n}\n"),
+            })
+            .collect::<String>();
+        fs::write(root.join("f.txt"), edited).unwrap();
+        let repo = Repository::open(root).unwrap();
+        (temp_dir, repo)
+    }
+
+    fn unstaged_file(repo: &Repository) -> DiffFile {
+        let highlighter = crate::syntax::SyntaxHighlighter::default();
+        let mut files =
+            super::super::diff::get_unstaged_diff(repo, DiffWhitespaceMode::Normal, &highlighter)
+                .unwrap();
+        files.remove(0)
+    }
+
+    #[test]
+    fn stages_one_hunk_into_the_index() {
+        let (dir, repo) = repo_with_two_hunks();
+        let file = unstaged_file(&repo);
+        assert_eq!(file.hunks.len(), 2);
+
+        let change = HunkChange {
+            file: &file,
+            base: HunkChangeBase::Unstaged,
+            operation: HunkOperation::Stage,
+            selections: &[whole(1)],
+        };
+        apply_hunk_change(&repo, DiffWhitespaceMode::Normal, &change).unwrap();
+
+        let staged = git(dir.path(), &["diff", "--cached"]);
+        assert!(staged.contains("+line 10\tedited"));
+        assert!(!staged.contains("line 1 edited"));
+        let unstaged = git(dir.path(), &["diff"]);
+        assert!(unstaged.contains("+line 1 edited"));
+    }
+
+    #[test]
+    fn unstages_and_discards_hunks() {
+        let (dir, repo) = repo_with_two_hunks();
+        git(dir.path(), &["add", "f.txt"]);
+        let highlighter = crate::syntax::SyntaxHighlighter::default();
+        let staged_file =
+            super::super::diff::get_staged_diff(&repo, DiffWhitespaceMode::Normal, &highlighter)
+                .unwrap()
+                .remove(0);
+
+        let unstage = HunkChange {
+            file: &staged_file,
+            base: HunkChangeBase::Staged,
+            operation: HunkOperation::Unstage,
+            selections: &[whole(0)],
+        };
+        apply_hunk_change(&repo, DiffWhitespaceMode::Normal, &unstage).unwrap();
+        assert!(!git(dir.path(), &["diff", "--cached"]).contains("line 1 edited"));
+
+        let file = unstaged_file(&repo);
+        let discard = HunkChange {
+            file: &file,
+            base: HunkChangeBase::Unstaged,
+            operation: HunkOperation::Discard,
+            selections: &[whole(0)],
+        };
+        apply_hunk_change(&repo, DiffWhitespaceMode::Normal, &discard).unwrap();
+        let content = fs::read_to_string(dir.path().join("f.txt")).unwrap();
+        assert!(content.starts_with("line 1\n"));
+        assert!(content.ends_with("line 10\tedited\n"));
+    }
+
+    #[test]
+    fn refuses_operations_that_do_not_fit_the_view() {
+        let (_dir, repo) = repo_with_two_hunks();
+        let file = unstaged_file(&repo);
+        let change = HunkChange {
+            file: &file,
+            base: HunkChangeBase::Unstaged,
+            operation: HunkOperation::Unstage,
+            selections: &[whole(0)],
+        };
+        let err = apply_hunk_change(&repo, DiffWhitespaceMode::Normal, &change).unwrap_err();
+        assert!(matches!(err, TuicrError::InvalidInput(_)));
+    }
 }
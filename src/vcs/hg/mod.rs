@@ -13,8 +13,8 @@ use crate::vcs::git::raw::{
     FileMetadata, pair_metadata_with_patch, path_buf_from_bytes, split_patch_blocks,
 };
 use crate::vcs::traits::{
-    CommitInfo, DiffWhitespaceMode, ResolvedRevisionRange, RevisionDiffTarget, VcsBackend, VcsInfo,
-    VcsType,
+    BlameLine, CommitInfo, DiffWhitespaceMode, ResolvedRevisionRange, RevisionDiffTarget,
+    VcsBackend, VcsInfo, VcsType,
 };
 use crate::vcs::{
     BATCH_BOUNDARY, apply_container_full_file_highlight, parse_batched_files, slice_context_lines,
@@ -566,6 +566,50 @@ impl VcsBackend for HgBackend {
         )?;
         Ok(files)
     }
+
+    fn blame(&self, file_path: &Path, ref_commit: Option<&str>) -> Result<Vec<BlameLine>> {
+        let rev = ref_commit.unwrap_or("wdir()");
+        let template = "{lines % \"{node}\\x00{user|person}\\x00{date|hgdate}\\n\"}";
+        let output = run_hg_command(
+            &self.info.root_path,
+            [
+                "annotate",
+                "-r",
+                rev,
+                "--template",
+                template,
+                &hg_path_pattern(file_path),
+            ],
+        )?;
+        Ok(parse_hg_annotate(&output))
+    }
+}
+
+/// Parse `hg annotate` output rendered by `HgBackend::blame`: one
+/// `node\0person\0hgdate` record per line. The working-directory pseudo
+/// revision reports an all-`f` node.
+fn parse_hg_annotate(output: &str) -> Vec<BlameLine> {
+    output
+        .lines()
+        .map(|record| {
+            let mut fields = record.split('\x00');
+            let node = fields.next().unwrap_or_default();
+            if node.is_empty() || node.bytes().all(|b| b == b'f') {
+                return BlameLine::uncommitted();
+            }
+            let author = fields.next().unwrap_or_default().to_string();
+            let time = fields
+                .next()
+                .and_then(|date| date.split_whitespace().next())
+                .and_then(|ts| ts.parse::<i64>().ok())
+                .and_then(|ts| Utc.timestamp_opt(ts, 0).single());
+            BlameLine {
+                commit_id: Some(node.to_string()),
+                author,
+                time,
+            }
+        })
+        .collect()
 }
 
 /// Render `path` as an hg file pattern that matches it and nothing else.
@@ -942,6 +986,21 @@ mod tests {
         Some(temp_dir)
     }
 
+    #[test]
+    fn test_parse_hg_annotate_marks_working_directory_lines() {
+        let node = "a".repeat(40);
+        let wdir = "f".repeat(40);
+        let output = format!("{node}\0Alice\01700000000 0\n{wdir}\0Bob\01800000000 0\n");
+
+        let lines = parse_hg_annotate(&output);
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].commit_id.as_deref(), Some(node.as_str()));
+        assert_eq!(lines[0].author, "Alice");
+        assert_eq!(lines[0].time.map(|t| t.timestamp()), Some(1_700_000_000));
+        assert_eq!(lines[1], BlameLine::uncommitted());
+    }
+
     #[test]
     fn test_hg_get_recent_commits() {
         let Some(temp) = setup_test_repo_with_commits() else {
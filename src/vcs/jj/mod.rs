@@ -13,8 +13,8 @@ use crate::syntax::SyntaxHighlighter;
 use crate::vcs::diff_parser;
 use crate::vcs::git::raw::{FileMetadata, pair_metadata_with_patch};
 use crate::vcs::traits::{
-    CommitInfo, DiffWhitespaceMode, ResolvedRevisionRange, RevisionDiffTarget, VcsBackend, VcsInfo,
-    VcsType,
+    BlameLine, CommitInfo, DiffWhitespaceMode, ResolvedRevisionRange, RevisionDiffTarget,
+    VcsBackend, VcsInfo, VcsType,
 };
 use crate::vcs::{
     BATCH_BOUNDARY, apply_container_full_file_highlight, parse_batched_files, slice_context_lines,
@@ -468,6 +468,47 @@ impl VcsBackend for JjBackend {
         )?;
         Ok(files)
     }
+
+    fn blame(&self, file_path: &Path, ref_commit: Option<&str>) -> Result<Vec<BlameLine>> {
+        let rev = ref_commit.unwrap_or("@");
+        let template = r#"commit.commit_id() ++ "\x00" ++ commit.author().name() ++ "\x00" ++ commit.author().timestamp().format("%Y-%m-%dT%H:%M:%S%:z") ++ "\x00" ++ if(commit.current_working_copy(), "wc") ++ "\n""#;
+        let output = run_jj_command(
+            &self.info.root_path,
+            [
+                "file",
+                "annotate",
+                "-r",
+                rev,
+                "-T",
+                template,
+                &jj_fileset_arg(file_path),
+            ],
+        )?;
+        Ok(parse_jj_annotate(&output))
+    }
+}
+
+/// Parse `jj file annotate` output rendered by `JjBackend::blame`: one
+/// `commit_id\0name\0timestamp\0wc` record per line. jj snapshots the
+/// working copy into a commit, so lines from it are reported as uncommitted
+/// to match the other backends.
+fn parse_jj_annotate(output: &str) -> Vec<BlameLine> {
+    output
+        .lines()
+        .map(|record| {
+            let parts: Vec<&str> = record.split('\x00').collect();
+            if parts.len() < 4 || parts[3] == "wc" {
+                return BlameLine::uncommitted();
+            }
+            BlameLine {
+                commit_id: Some(parts[0].to_string()),
+                author: parts[1].to_string(),
+                time: DateTime::parse_from_rfc3339(parts[2])
+                    .ok()
+                    .map(|dt| dt.with_timezone(&Utc)),
+            }
+        })
+        .collect()
 }
 
 /// Render `path` as a jj fileset argument that matches it and nothing else.
@@ -664,6 +705,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parses_jj_annotate_and_marks_working_copy_lines() {
+        let id = "a".repeat(40);
+        let output = format!(
+            "{id}\0Alice\02024-05-01T10:00:00+00:00\0\n{id}\0Bob\02024-05-02T10:00:00+00:00\0wc\n"
+        );
+
+        let lines = parse_jj_annotate(&output);
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].commit_id.as_deref(), Some(id.as_str()));
+        assert_eq!(lines[0].author, "Alice");
+        assert!(lines[0].time.is_some());
+        assert_eq!(lines[1], BlameLine::uncommitted());
+    }
+
     #[test]
     fn test_jj_discover() {
         let Some(temp) = setup_test_repo() else {
@@ -0,0 +1,534 @@
+//! Mail-formatted patch series: `git format-patch` output and mailing-list
+//! mbox files.
+//!
+//! Every message that carries a diff becomes one entry in the inline commit
+//! selector, with its subject and body as the commit message. Replies in the
+//! thread (`Re:`) are skipped, and a `[PATCH 0/N]` cover letter is kept only
+//! as the place review-level comments are answered on. The headers needed to
+//! answer on-list are kept verbatim so [`crate::output::email`] can thread
+//! its replies under the original messages.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use chrono::{DateTime, Utc};
+
+use crate::model::FilePatch;
+
+use super::patch::split_patch;
+use super::traits::CommitInfo;
+
+/// A patch series read from an mbox.
+#[derive(Debug, Clone)]
+pub struct PatchSeries {
+    /// The `[PATCH 0/N]` cover letter, when the series has one.
+    pub cover: Option<PatchMail>,
+    /// Messages carrying a diff, in series order.
+    pub patches: Vec<PatchMail>,
+}
+
+/// One message of a series.
+#[derive(Debug, Clone)]
+pub struct PatchMail {
+    /// The commit SHA from the mbox `From <sha>` separator, or a hash of the
+    /// message when the mbox does not carry one.
+    pub id: String,
+    pub headers: MailHeaders,
+    /// Subject without its `[PATCH v2 1/3]` tags.
+    pub summary: String,
+    /// Commit message body: everything between the headers and the `---`
+    /// that starts the diffstat.
+    pub body: Option<String>,
+    pub files: Vec<FilePatch>,
+}
+
+/// Header values as they appear in the message, unfolded but still
+/// RFC 2047 encoded, so a reply can copy them unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MailHeaders {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub cc: Option<String>,
+    pub date: Option<String>,
+    pub subject: Option<String>,
+    pub message_id: Option<String>,
+    pub in_reply_to: Option<String>,
+    pub references: Option<String>,
+}
+
+impl PatchSeries {
+    /// Read the series out of `text`. Returns `None` when no message has both
+    /// mail headers and a diff, which is how a plain diff is told apart.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut cover = None;
+        let mut patches: Vec<(Option<u32>, PatchMail)> = Vec::new();
+        let mut seen_ids = HashSet::new();
+
+        for message in split_messages(text) {
+            let Some(mut mail) = parse_message(&message) else {
+                continue;
+            };
+            let number = series_number(mail.headers.subject.as_deref().unwrap_or_default());
+            if mail.files.is_empty() {
+                if number == Some(0) && cover.is_none() {
+                    cover = Some(mail);
+                }
+                continue;
+            }
+            if !seen_ids.insert(mail.id.clone()) {
+                // The same commit sent twice (a resend in one mbox): key the
+                // copy by its position so both stay selectable.
+                mail.id = format!(
+                    "{:016x}",
+                    fingerprint(&message.lines) ^ patches.len() as u64
+                );
+                seen_ids.insert(mail.id.clone());
+            }
+            patches.push((number, mail));
+        }
+
+        if patches.is_empty() {
+            return None;
+        }
+        // Archives list messages in arrival order, which need not be the
+        // order the series applies in.
+        patches.sort_by_key(|(number, _)| number.unwrap_or(u32::MAX));
+        Some(Self {
+            cover,
+            patches: patches.into_iter().map(|(_, mail)| mail).collect(),
+        })
+    }
+
+    pub fn patch(&self, id: &str) -> Option<(usize, &PatchMail)> {
+        self.patches.iter().enumerate().find(|(_, p)| p.id == id)
+    }
+
+    /// Files of the patch at `index`, keyed the way the review tracks them.
+    ///
+    /// A path an earlier patch of the series already touched is keyed by
+    /// `(patch id, path)` (see [`FilePatch::review_key`]), so every file of
+    /// the series keeps one stable key no matter which patches are selected
+    /// together. Paths themselves are left alone.
+    pub fn display_files(&self, index: usize) -> Vec<FilePatch> {
+        let earlier: HashSet<&Path> = self.patches[..index]
+            .iter()
+            .flat_map(|p| &p.files)
+            .filter_map(FilePatch::display_path)
+            .collect();
+        let short_id = self.patches[index].short_id();
+        self.patches[index]
+            .files
+            .iter()
+            .map(|file| {
+                let mut file = file.clone();
+                file.review_key = file
+                    .display_path()
+                    .filter(|path| earlier.contains(path))
+                    .map(|path| PathBuf::from(format!("{} ({short_id})", path.display())));
+                file
+            })
+            .collect()
+    }
+}
+
+impl PatchMail {
+    pub fn short_id(&self) -> &str {
+        &self.id[..7.min(self.id.len())]
+    }
+
+    /// Name of the sender, falling back to the address.
+    pub fn author(&self) -> String {
+        let from = decode_header(self.headers.from.as_deref().unwrap_or_default());
+        match from.split_once('<') {
+            Some((name, addr)) => {
+                let name = name.trim().trim_matches('"').trim();
+                if name.is_empty() {
+                    addr.trim_end_matches('>').trim().to_string()
+                } else {
+                    name.to_string()
+                }
+            }
+            None => from.trim().to_string(),
+        }
+    }
+
+    pub fn time(&self) -> DateTime<Utc> {
+        self.headers
+            .date
+            .as_deref()
+            .and_then(|date| DateTime::parse_from_rfc2822(date.trim()).ok())
+            .map(|date| date.with_timezone(&Utc))
+            .unwrap_or_default()
+    }
+
+    /// The commit message as the review shows it: summary, blank line, body.
+    pub fn message(&self) -> String {
+        match &self.body {
+            Some(body) => format!("{}\n\n{}", self.summary, body),
+            None => self.summary.clone(),
+        }
+    }
+
+    pub fn commit_info(&self) -> CommitInfo {
+        CommitInfo {
+            id: self.id.clone(),
+            short_id: self.short_id().to_string(),
+            branch_name: None,
+            summary: self.summary.clone(),
+            body: self.body.clone(),
+            author: self.author(),
+            time: self.time(),
+        }
+    }
+}
+
+/// One message of the mbox, before its headers are read.
+struct RawMessage<'a> {
+    /// The token after `From ` on the separator line.
+    envelope: Option<&'a str>,
+    lines: Vec<&'a str>,
+}
+
+/// Split an mbox on its `From ` separator lines. A file that starts straight
+/// with headers (a single saved `.eml`) is one message.
+fn split_messages(text: &str) -> Vec<RawMessage<'_>> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut messages = Vec::new();
+    let mut current: Option<RawMessage> = None;
+
+    for (idx, line) in lines.iter().enumerate() {
+        let starts_message = line.starts_with("From ")
+            && (idx == 0 || lines[idx - 1].is_empty())
+            && lines.get(idx + 1).is_some_and(|next| is_header_line(next));
+        if starts_message {
+            messages.extend(current.take());
+            current = Some(RawMessage {
+                envelope: line[5..].split_whitespace().next(),
+                lines: Vec::new(),
+            });
+        } else if let Some(message) = current.as_mut() {
+            message.lines.push(line);
+        } else if idx == 0 && is_header_line(line) {
+            current = Some(RawMessage {
+                envelope: None,
+                lines: vec![line],
+            });
+        }
+    }
+    messages.extend(current);
+    messages
+}
+
+fn is_header_line(line: &str) -> bool {
+    line.split_once(':').is_some_and(|(name, _)| {
+        !name.is_empty() && name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+    })
+}
+
+fn parse_message(message: &RawMessage<'_>) -> Option<PatchMail> {
+    let mut headers = MailHeaders::default();
+    let mut lines = message.lines.iter().copied().peekable();
+
+    while let Some(line) = lines.next() {
+        if line.is_empty() {
+            break;
+        }
+        let mut value = line.split_once(':').map(|(_, v)| v.trim().to_string())?;
+        while let Some(cont) = lines.next_if(|l| l.starts_with([' ', '\t'])) {
+            value.push(' ');
+            value.push_str(cont.trim());
+        }
+        let name = line.split_once(':').map(|(n, _)| n.to_ascii_lowercase())?;
+        let slot = match name.as_str() {
+            "from" => &mut headers.from,
+            "to" => &mut headers.to,
+            "cc" => &mut headers.cc,
+            "date" => &mut headers.date,
+            "subject" => &mut headers.subject,
+            "message-id" => &mut headers.message_id,
+            "in-reply-to" => &mut headers.in_reply_to,
+            "references" => &mut headers.references,
+            _ => continue,
+        };
+        slot.get_or_insert(value);
+    }
+
+    let subject = decode_header(headers.subject.as_deref()?);
+    if subject
+        .get(..3)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("re:"))
+    {
+        return None;
+    }
+
+    // mboxrd escapes body lines that look like separators with a `>`.
+    let body: Vec<String> = lines
+        .map(|line| {
+            let unescaped = line.trim_start_matches('>');
+            if unescaped.starts_with("From ") && unescaped.len() < line.len() {
+                line[1..].to_string()
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    let files = split_patch(&body.join("\n"));
+
+    let id = match message.envelope {
+        Some(sha) if sha.len() == 40 && sha.bytes().all(|b| b.is_ascii_hexdigit()) => {
+            sha.to_ascii_lowercase()
+        }
+        _ => format!("{:016x}", fingerprint(&message.lines)),
+    };
+
+    Some(PatchMail {
+        id,
+        summary: strip_subject_tags(&subject).to_string(),
+        body: commit_body(&body),
+        headers,
+        files,
+    })
+}
+
+fn fingerprint(lines: &[&str]) -> u64 {
+    crate::hash::fnv1a_64(lines.join("\n").as_bytes())
+}
+
+/// Text between the headers and the diff, without trailing blank lines.
+fn commit_body(body: &[String]) -> Option<String> {
+    let end = body
+        .iter()
+        .enumerate()
+        .position(|(idx, line)| {
+            line == "---"
+                || line.starts_with("diff --git ")
+                || line.starts_with("Index: ")
+                || (line.starts_with("--- ")
+                    && body.get(idx + 1).is_some_and(|n| n.starts_with("+++ ")))
+        })
+        .unwrap_or(body.len());
+    let text = body[..end].join("\n");
+    let text = text.trim_matches('\n').trim_end();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Drop leading `[PATCH v2 1/3]` style tags.
+fn strip_subject_tags(subject: &str) -> &str {
+    let mut rest = subject.trim();
+    while let Some(tagged) = rest.strip_prefix('[')
+        && let Some((_, after)) = tagged.split_once(']')
+    {
+        rest = after.trim_start();
+    }
+    rest
+}
+
+/// Position of a message in its series, from the `N/M` in its subject tags.
+fn series_number(subject: &str) -> Option<u32> {
+    let subject = decode_header(subject);
+    let tags = subject.trim().strip_prefix('[')?.split_once(']')?.0;
+    tags.split_whitespace().find_map(|word| {
+        let (n, total) = word.split_once('/')?;
+        total.parse::<u32>().ok()?;
+        n.parse().ok()
+    })
+}
+
+/// Decode RFC 2047 encoded words (`=?UTF-8?q?...?=`). Whitespace between two
+/// adjacent encoded words is dropped, as the RFC requires.
+pub(crate) fn decode_header(value: &str) -> String {
+    let mut out = String::new();
+    let mut rest = value;
+    let mut after_word = false;
+    while let Some(start) = rest.find("=?") {
+        let before = &rest[..start];
+        match decode_word(&rest[start..]) {
+            Some((decoded, len)) => {
+                if !(after_word && before.trim().is_empty()) {
+                    out.push_str(before);
+                }
+                out.push_str(&decoded);
+                rest = &rest[start + len..];
+                after_word = true;
+            }
+            None => {
+                out.push_str(&rest[..start + 2]);
+                rest = &rest[start + 2..];
+                after_word = false;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Decode one encoded word at the start of `s`, returning the text and the
+/// number of bytes it spanned.
+fn decode_word(s: &str) -> Option<(String, usize)> {
+    let inner = s.strip_prefix("=?")?;
+    let (charset, rest) = inner.split_once('?')?;
+    let (encoding, rest) = rest.split_once('?')?;
+    let (text, _) = rest.split_once("?=")?;
+    let bytes = match encoding {
+        "B" | "b" => BASE64.decode(text).ok()?,
+        "Q" | "q" => {
+            let mut bytes = Vec::new();
+            let mut iter = text.bytes();
+            while let Some(b) = iter.next() {
+                match b {
+                    b'_' => bytes.push(b' '),
+                    b'=' => {
+                        let hex = [iter.next()?, iter.next()?];
+                        bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+                    }
+                    other => bytes.push(other),
+                }
+            }
+            bytes
+        }
+        _ => return None,
+    };
+    // Charset names may carry an RFC 2231 language suffix (`utf-8*en`).
+    let charset = charset.split('*').next().unwrap_or(charset);
+    let decoded =
+        if charset.eq_ignore_ascii_case("iso-8859-1") || charset.eq_ignore_ascii_case("latin1") {
+            bytes.iter().map(|&b| b as char).collect()
+        } else {
+            String::from_utf8_lossy(&bytes).into_owned()
+        };
+    let len = 2 + charset.len() + 1 + encoding.len() + 1 + text.len() + 2;
+    Some((decoded, len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SERIES: &str = "\
+From 2222222222222222222222222222222222222222 Mon Sep 17 00:00:00 2001
+From: =?UTF-8?q?J=C3=B6rg?= Example <jorg@example.com>
+Date: Tue, 7 May 2024 10:00:00 +0200
+Subject: [PATCH v2 2/2] parser: handle tabs
+Message-Id: <two@example.com>
+In-Reply-To: <cover@example.com>
+References: <cover@example.com>
+
+Tabs were expanded twice.
+---
+ src/lib.rs | 2 +-
+
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1 +1 @@
+-old
++new
+--
+2.43.0
+
+From 0000000000000000000000000000000000000000 Mon Sep 17 00:00:00 2001
+From: =?UTF-8?q?J=C3=B6rg?= Example <jorg@example.com>
+Subject: [PATCH v2 0/2] Tab handling
+Message-Id: <cover@example.com>
+
+Cover text.
+
+From 1111111111111111111111111111111111111111 Mon Sep 17 00:00:00 2001
+From: =?UTF-8?q?J=C3=B6rg?= Example <jorg@example.com>
+Date: Tue, 7 May 2024 10:00:00 +0200
+Subject: [PATCH v2 1/2] parser: add a
+ long folded subject
+Message-Id: <one@example.com>
+In-Reply-To: <cover@example.com>
+References: <cover@example.com>
+
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1 +1 @@
+-older
++old
+
+From mboxrd@z Thu Jan  1 00:00:00 1970
+From: Reviewer <r@example.com>
+Subject: Re: [PATCH v2 1/2] parser: add a long folded subject
+
+> quoted
+";
+
+    #[test]
+    fn reads_patches_in_series_order_with_cover_and_without_replies() {
+        let series = PatchSeries::parse(SERIES).expect("series");
+
+        let ids: Vec<&str> = series.patches.iter().map(|p| p.short_id()).collect();
+        assert_eq!(ids, vec!["1111111", "2222222"]);
+        assert_eq!(
+            series
+                .cover
+                .as_ref()
+                .and_then(|c| c.headers.message_id.as_deref()),
+            Some("<cover@example.com>")
+        );
+
+        let first = &series.patches[0];
+        assert_eq!(first.summary, "parser: add a long folded subject");
+        assert_eq!(first.body, None);
+        assert_eq!(first.author(), "Jörg Example");
+
+        let second = &series.patches[1];
+        assert_eq!(second.body.as_deref(), Some("Tabs were expanded twice."));
+        assert_eq!(second.files.len(), 1);
+        assert_eq!(
+            second.commit_info().time.to_rfc3339(),
+            "2024-05-07T08:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn keys_files_touched_again_by_a_later_patch_by_that_patch() {
+        let series = PatchSeries::parse(SERIES).expect("series");
+
+        let first = series.display_files(0);
+        let second = series.display_files(1);
+
+        assert_eq!(first[0].review_key, None);
+        assert_eq!(
+            second[0].review_key.as_deref(),
+            Some(Path::new("src/lib.rs (2222222)"))
+        );
+        assert_eq!(second[0].old_path.as_deref(), Some(Path::new("src/lib.rs")));
+        assert_eq!(second[0].new_path.as_deref(), Some(Path::new("src/lib.rs")));
+    }
+
+    #[test]
+    fn ignores_plain_diffs_and_synthesizes_ids_without_a_sha() {
+        assert!(PatchSeries::parse("--- a/x\n+++ b/x\n@@ -1 +1 @@\n-a\n+b\n").is_none());
+
+        let eml = "\
+From: a@example.com
+Subject: [PATCH] fix
+Message-Id: <x@example.com>
+
+diff --git a/x b/x
+--- a/x
++++ b/x
+@@ -1 +1 @@
+-a
++b
+";
+        let series = PatchSeries::parse(eml).expect("single message");
+        assert_eq!(series.patches.len(), 1);
+        assert_eq!(series.patches[0].id.len(), 16);
+        assert_eq!(series.patches[0].author(), "a@example.com");
+    }
+
+    #[test]
+    fn decodes_encoded_words() {
+        assert_eq!(
+            decode_header("=?utf-8?B?SsO2cmc=?= =?iso-8859-1?q?_M=FCller?= <j@x>"),
+            "Jörg Müller <j@x>"
+        );
+        assert_eq!(decode_header("plain =?bogus"), "plain =?bogus");
+    }
+}
@@ -16,6 +16,8 @@ pub mod file;
 pub mod git;
 mod hg;
 mod jj;
+pub mod mbox;
+pub mod patch;
 pub mod pr_noop;
 pub mod pristine;
 pub(crate) mod traits;
@@ -24,10 +26,12 @@ pub use file::FileBackend;
 pub use git::{GitBackend, GitBackendPreference};
 pub use hg::HgBackend;
 pub use jj::JjBackend;
+pub use patch::PatchBackend;
 pub use pr_noop::PrNoopVcs;
 pub use traits::{
-    ChangeKind, CommitInfo, DiffWhitespaceMode, ResolvedRevisionRange, RevisionDiffTarget,
-    VcsBackend, VcsChangeStatus, VcsInfo,
+    BlameLine, ChangeKind, CommitInfo, DiffWhitespaceMode, HunkChange, HunkChangeBase,
+    HunkOperation, HunkSelection, ResolvedRevisionRange, RevisionDiffTarget, VcsBackend,
+    VcsChangeStatus, VcsInfo,
 };
 
 use std::collections::HashMap;
@@ -446,6 +450,7 @@ mod tests {
             old_count: 1,
             new_start: target_line,
             new_count: 1,
+            raw_body: None,
         };
         DiffFile {
             old_path: Some(path.clone()),
@@ -456,6 +461,7 @@ mod tests {
             is_too_large: false,
             is_commit_message: false,
             content_hash: 0,
+            review_key: None,
         }
     }
 
@@ -0,0 +1,853 @@
+//! Review a raw unified or git-format diff that was never applied (`--patch`).
+//!
+//! The patch text is the only source of file identity here, so this module
+//! reads the `diff --git`, `---`/`+++`, rename and mode headers itself and
+//! hands each file's hunks to [`diff_parser`](super::diff_parser). Anything
+//! between file sections (mail headers, commit messages, diffstats, `-- `
+//! signatures) is skipped, which lets `git format-patch` output load as-is.
+//!
+//! Mail-formatted input (`git format-patch` output, a mailing-list mbox) is
+//! read as a [`PatchSeries`] instead: each message is a commit in the inline
+//! commit selector and the selection is materialized as the concatenation of
+//! the selected messages' files.
+//!
+//! Context expansion reads from the directory the review was started in, but
+//! only for files whose on-disk content matches one side of the patch. An
+//! unapplied patch matches on its old side and an applied one on its new
+//! side; files that match neither simply have no context to expand.
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::error::{Result, TuicrError};
+use crate::model::{DiffFile, DiffLine, FilePatch, FileStatus, LineOrigin, LineSide};
+use crate::syntax::SyntaxHighlighter;
+
+use super::diff_parser::{parse_file_patches, parse_hunk_header};
+use super::mbox::PatchSeries;
+use super::tabify;
+use super::traits::{CommitInfo, ResolvedRevisionRange, VcsBackend, VcsInfo, VcsType};
+
+/// Prefix of the synthetic `head_commit` a patch session is keyed by. The
+/// rest is the FNV-1a hash of the patch text, so reopening the same patch
+/// resumes its comments while a different patch starts fresh.
+pub const PATCH_SESSION_PREFIX: &str = "patch:";
+
+/// The `branch_name` of every patch session. Where the text came from
+/// (`stdin`, a file name) is not part of its identity, so piping a patch in
+/// and opening the same patch from a file resume one session.
+pub const PATCH_SESSION_BRANCH: &str = "patch";
+
+pub struct PatchBackend {
+    info: VcsInfo,
+    /// Where the text came from, shown in the header.
+    label: String,
+    patches: Vec<FilePatch>,
+    /// Set when the text is a mail series rather than a bare diff.
+    series: Option<PatchSeries>,
+    /// Files in `info.root_path` that match a side of the patch, keyed by
+    /// display path. Refreshed every time the diff is materialized.
+    checkout: Mutex<HashMap<PathBuf, CheckoutFile>>,
+}
+
+/// A checkout file the patch lines up with, and how to map new-side line
+/// numbers onto it.
+#[derive(Debug, Clone)]
+struct CheckoutFile {
+    /// Path of the file to read, relative to the root.
+    path: PathBuf,
+    /// Which side of the patch the file on disk holds.
+    side: LineSide,
+    /// `(old_start, old_count, new_start, new_count)` for every hunk.
+    hunks: Vec<(u32, u32, u32, u32)>,
+}
+
+/// Read the patch named on the command line. `-` reads stdin. Returns a short
+/// label for the header alongside the patch text.
+pub fn read_patch(arg: &str) -> Result<(String, String)> {
+    if arg == "-" {
+        let mut bytes = Vec::new();
+        std::io::stdin().read_to_end(&mut bytes)?;
+        return Ok((
+            "stdin".to_string(),
+            String::from_utf8_lossy(&bytes).into_owned(),
+        ));
+    }
+    let bytes = std::fs::read(arg).map_err(|e| {
+        TuicrError::Io(std::io::Error::new(
+            e.kind(),
+            format!("Cannot open '{}': {}", arg, e),
+        ))
+    })?;
+    let label = Path::new(arg)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| arg.to_string());
+    Ok((label, String::from_utf8_lossy(&bytes).into_owned()))
+}
+
+impl PatchBackend {
+    /// Create a backend for `text`, resolving context against files under
+    /// `root`. `label` names the patch in the header.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TuicrError::NoChanges`] when the text contains no file
+    /// sections at all.
+    pub fn new(label: impl Into<String>, text: &str, root: PathBuf) -> Result<Self> {
+        let series = PatchSeries::parse(text);
+        let patches = match &series {
+            Some(series) => (0..series.patches.len())
+                .flat_map(|idx| series.display_files(idx))
+                .collect(),
+            None => split_patch(text),
+        };
+        if patches.is_empty() {
+            return Err(TuicrError::NoChanges);
+        }
+        let info = VcsInfo {
+            root_path: root,
+            head_commit: format!(
+                "{PATCH_SESSION_PREFIX}{:016x}",
+                crate::hash::fnv1a_64(text.as_bytes())
+            ),
+            branch_name: Some(PATCH_SESSION_BRANCH.to_string()),
+            vcs_type: VcsType::Patch,
+        };
+        Ok(Self {
+            info,
+            label: label.into(),
+            patches,
+            series,
+            checkout: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// The mail series, when the patch was one.
+    pub fn series(&self) -> Option<&PatchSeries> {
+        self.series.as_ref()
+    }
+
+    /// Parse `patches` and remember which of their files the checkout
+    /// matches, for context expansion.
+    fn materialize(
+        &self,
+        patches: Vec<FilePatch>,
+        highlighter: &SyntaxHighlighter,
+    ) -> Result<Vec<DiffFile>> {
+        let files = parse_file_patches(patches, highlighter)?;
+        let matched = files
+            .iter()
+            .filter_map(|file| {
+                let checkout = match_checkout(&self.info.root_path, file)?;
+                Some((file.display_path().clone(), checkout))
+            })
+            .collect();
+        *self.checkout.lock().expect("checkout map poisoned") = matched;
+        Ok(files)
+    }
+
+    fn checkout_file(&self, file_path: &Path) -> Result<CheckoutFile> {
+        self.checkout
+            .lock()
+            .expect("checkout map poisoned")
+            .get(file_path)
+            .cloned()
+            .ok_or_else(|| {
+                TuicrError::UnsupportedOperation(format!(
+                    "No context for {}: the checkout does not match this patch",
+                    file_path.display()
+                ))
+            })
+    }
+}
+
+impl VcsBackend for PatchBackend {
+    fn info(&self) -> &VcsInfo {
+        &self.info
+    }
+
+    fn display_ref(&self) -> Option<&str> {
+        Some(&self.label)
+    }
+
+    fn get_working_tree_diff(&self, highlighter: &SyntaxHighlighter) -> Result<Vec<DiffFile>> {
+        self.materialize(self.patches.clone(), highlighter)
+    }
+
+    fn get_commit_range_diff(
+        &self,
+        revision_range: &ResolvedRevisionRange<'_>,
+        highlighter: &SyntaxHighlighter,
+    ) -> Result<Vec<DiffFile>> {
+        let Some(series) = &self.series else {
+            return Err(TuicrError::UnsupportedOperation(
+                "Commit range diff needs a mail-formatted patch series".into(),
+            ));
+        };
+        let patches: Vec<FilePatch> = revision_range
+            .commit_ids
+            .iter()
+            .filter_map(|id| series.patch(id))
+            .flat_map(|(idx, _)| series.display_files(idx))
+            .collect();
+        if patches.is_empty() {
+            return Err(TuicrError::NoChanges);
+        }
+        self.materialize(patches, highlighter)
+    }
+
+    fn get_commits_info(&self, ids: &[String]) -> Result<Vec<CommitInfo>> {
+        let Some(series) = &self.series else {
+            return Ok(Vec::new());
+        };
+        Ok(ids
+            .iter()
+            .filter_map(|id| series.patch(id))
+            .map(|(_, mail)| mail.commit_info())
+            .collect())
+    }
+
+    fn fetch_context_lines(
+        &self,
+        file_path: &Path,
+        _file_status: FileStatus,
+        _ref_commit: Option<&str>,
+        start_line: u32,
+        end_line: u32,
+    ) -> Result<Vec<DiffLine>> {
+        if start_line > end_line || start_line == 0 {
+            return Ok(Vec::new());
+        }
+        let checkout = self.checkout_file(file_path)?;
+        let content = read_confined(&self.info.root_path, &checkout.path).unwrap_or_default();
+        let lines: Vec<&str> = content.lines().collect();
+
+        let mut result = Vec::new();
+        for new_lineno in start_line..=end_line {
+            let disk_lineno = match checkout.side {
+                LineSide::New => new_lineno,
+                LineSide::Old => old_lineno_for(&checkout.hunks, new_lineno),
+            };
+            let Some(line) = disk_lineno
+                .checked_sub(1)
+                .and_then(|idx| lines.get(idx as usize))
+            else {
+                break;
+            };
+            result.push(DiffLine {
+                origin: LineOrigin::Context,
+                content: tabify(line),
+                old_lineno: Some(old_lineno_for(&checkout.hunks, new_lineno)),
+                new_lineno: Some(new_lineno),
+                highlighted_spans: None,
+            });
+        }
+        Ok(result)
+    }
+
+    fn file_line_count(
+        &self,
+        file_path: &Path,
+        _file_status: FileStatus,
+        _ref_commit: Option<&str>,
+    ) -> Result<u32> {
+        let checkout = self.checkout_file(file_path)?;
+        let content = read_confined(&self.info.root_path, &checkout.path).unwrap_or_default();
+        let disk_count = content.lines().count() as i64;
+        let count = match checkout.side {
+            LineSide::New => disk_count,
+            LineSide::Old => {
+                let growth: i64 = checkout
+                    .hunks
+                    .iter()
+                    .map(|&(_, old_count, _, new_count)| new_count as i64 - old_count as i64)
+                    .sum();
+                disk_count + growth
+            }
+        };
+        Ok(count.max(0) as u32)
+    }
+}
+
+/// Old-side line number of a new-side line that sits outside every hunk.
+fn old_lineno_for(hunks: &[(u32, u32, u32, u32)], new_lineno: u32) -> u32 {
+    // A zero-length range names the line *before* the change, so its
+    // exclusive end is one past the start rather than `start + 0`.
+    let range_end = |start: u32, count: u32| start as i64 + count.max(1) as i64;
+    let delta = hunks
+        .iter()
+        .take_while(|&&(_, _, new_start, new_count)| {
+            range_end(new_start, new_count) <= new_lineno as i64
+        })
+        .last()
+        .map_or(0, |&(old_start, old_count, new_start, new_count)| {
+            range_end(new_start, new_count) - range_end(old_start, old_count)
+        });
+    (new_lineno as i64 - delta).max(1) as u32
+}
+
+/// Find the side of `file` the checkout holds, by comparing every hunk line
+/// on that side with the file on disk.
+fn match_checkout(root: &Path, file: &DiffFile) -> Option<CheckoutFile> {
+    if file.is_binary || file.is_too_large || file.hunks.is_empty() {
+        return None;
+    }
+    let hunks = file
+        .hunks
+        .iter()
+        .map(|h| (h.old_start, h.old_count, h.new_start, h.new_count))
+        .collect::<Vec<_>>();
+    let sides = [
+        (LineSide::New, file.new_path.as_ref()),
+        (LineSide::Old, file.old_path.as_ref()),
+    ];
+    sides.into_iter().find_map(|(side, path)| {
+        let path = path?;
+        let content = read_confined(root, path)?;
+        let lines: Vec<&str> = content.lines().collect();
+        let matches = file.hunks.iter().flat_map(|h| &h.lines).all(|line| {
+            let lineno = match side {
+                LineSide::New => line.new_lineno,
+                LineSide::Old => line.old_lineno,
+            };
+            lineno.is_none_or(|n| {
+                n.checked_sub(1)
+                    .and_then(|idx| lines.get(idx as usize))
+                    .is_some_and(|disk| tabify(disk) == line.content)
+            })
+        });
+        matches.then(|| CheckoutFile {
+            path: path.clone(),
+            side,
+            hunks: hunks.clone(),
+        })
+    })
+}
+
+/// Read `rel` under `root`, refusing paths that resolve outside of it. Patch
+/// headers are untrusted input, so `../` must not reach arbitrary files.
+fn read_confined(root: &Path, rel: &Path) -> Option<String> {
+    let root = std::fs::canonicalize(root).ok()?;
+    let canonical = std::fs::canonicalize(root.join(rel)).ok()?;
+    if !canonical.starts_with(&root) || !canonical.is_file() {
+        return None;
+    }
+    std::fs::read_to_string(canonical).ok()
+}
+
+/// One file section while its headers are being read.
+struct PendingFile {
+    old_path: Option<PathBuf>,
+    new_path: Option<PathBuf>,
+    status: FileStatus,
+    is_binary: bool,
+    /// Started by `diff --git`, so extended headers may follow.
+    is_git: bool,
+    /// `---`/`+++` were read; another pair starts the next file.
+    saw_markers: bool,
+    hunks: String,
+}
+
+impl PendingFile {
+    fn new(old_path: Option<PathBuf>, new_path: Option<PathBuf>, is_git: bool) -> Self {
+        Self {
+            old_path,
+            new_path,
+            status: FileStatus::Modified,
+            is_binary: false,
+            is_git,
+            saw_markers: false,
+            hunks: String::new(),
+        }
+    }
+
+    fn into_file_patch(self) -> Option<FilePatch> {
+        if self.old_path.is_none() && self.new_path.is_none() {
+            return None;
+        }
+        let status = match (&self.old_path, &self.new_path) {
+            (None, Some(_)) => FileStatus::Added,
+            (Some(_), None) => FileStatus::Deleted,
+            _ => self.status,
+        };
+        let mut patch = FilePatch::new(self.old_path, self.new_path, status, self.hunks);
+        patch.is_binary = self.is_binary;
+        Some(patch)
+    }
+}
+
+/// Split patch text into per-file patches, taking paths and status from the
+/// headers and copying each hunk by the line counts its `@@` header declares.
+pub(crate) fn split_patch(text: &str) -> Vec<FilePatch> {
+    let mut files = Vec::new();
+    let mut current: Option<PendingFile> = None;
+    let mut lines = text.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            files.extend(current.take().and_then(PendingFile::into_file_patch));
+            let (old, new) = parse_git_header_paths(rest).unzip();
+            current = Some(PendingFile::new(old, new, true));
+        } else if line.starts_with("--- ") && lines.peek().is_some_and(|n| n.starts_with("+++ ")) {
+            let new_line = lines.next().unwrap_or_default();
+            let mut file = match current.take() {
+                Some(file) if file.is_git && !file.saw_markers && file.hunks.is_empty() => file,
+                other => {
+                    files.extend(other.and_then(PendingFile::into_file_patch));
+                    PendingFile::new(None, None, false)
+                }
+            };
+            // `/dev/null` on either side is what marks an addition or deletion
+            // in a plain diff, and it agrees with git's mode headers.
+            file.old_path = marker_path(&line[4..], "a/");
+            file.new_path = marker_path(&new_line[4..], "b/");
+            file.saw_markers = true;
+            current = Some(file);
+        } else if line.starts_with("@@ ")
+            && let Some(file) = current.as_mut()
+        {
+            file.hunks.push_str(line);
+            file.hunks.push('\n');
+            // A malformed header is left for `diff_parser` to report.
+            let (mut old_left, mut new_left) = parse_hunk_header(line)
+                .map_or((0, 0), |(_, old_count, _, new_count)| {
+                    (old_count, new_count)
+                });
+            while old_left > 0 || new_left > 0 {
+                let Some(body) = lines.next() else { break };
+                match body.as_bytes().first() {
+                    Some(b'+') => new_left = new_left.saturating_sub(1),
+                    Some(b'-') => old_left = old_left.saturating_sub(1),
+                    Some(b'\\') => {}
+                    _ => {
+                        old_left = old_left.saturating_sub(1);
+                        new_left = new_left.saturating_sub(1);
+                    }
+                }
+                // Mail clients strip the trailing space off blank context lines.
+                file.hunks
+                    .push_str(if body.is_empty() { " " } else { body });
+                file.hunks.push('\n');
+            }
+            while let Some(marker) = lines.next_if(|l| l.starts_with('\\')) {
+                file.hunks.push_str(marker);
+                file.hunks.push('\n');
+            }
+        } else if let Some(file) = current
+            .as_mut()
+            .filter(|f| f.is_git && !f.saw_markers && f.hunks.is_empty())
+        {
+            apply_extended_header(file, line);
+        }
+    }
+    files.extend(current.and_then(PendingFile::into_file_patch));
+    files
+}
+
+fn apply_extended_header(file: &mut PendingFile, line: &str) {
+    if line.starts_with("new file mode ") {
+        file.old_path = None;
+        file.status = FileStatus::Added;
+    } else if line.starts_with("deleted file mode ") {
+        file.new_path = None;
+        file.status = FileStatus::Deleted;
+    } else if let Some(path) = line.strip_prefix("rename from ") {
+        file.old_path = Some(header_path(path));
+        file.status = FileStatus::Renamed;
+    } else if let Some(path) = line.strip_prefix("rename to ") {
+        file.new_path = Some(header_path(path));
+        file.status = FileStatus::Renamed;
+    } else if let Some(path) = line.strip_prefix("copy from ") {
+        file.old_path = Some(header_path(path));
+        file.status = FileStatus::Copied;
+    } else if let Some(path) = line.strip_prefix("copy to ") {
+        file.new_path = Some(header_path(path));
+        file.status = FileStatus::Copied;
+    } else if line.starts_with("Binary files ") || line == "GIT binary patch" {
+        file.is_binary = true;
+    }
+}
+
+/// Paths from `diff --git a/<old> b/<new>`. Unquoted paths may contain spaces,
+/// so when both sides name the same file the line is split down the middle.
+fn parse_git_header_paths(rest: &str) -> Option<(PathBuf, PathBuf)> {
+    if rest.starts_with('"') {
+        let (old, tail) = unquote(rest)?;
+        let tail = tail.trim_start();
+        let new = match unquote(tail) {
+            Some((new, _)) => new,
+            None => tail.to_string(),
+        };
+        return Some(strip_header_prefixes(&old, &new));
+    }
+    if let Some((old, _)) = rest.split_once(" \"") {
+        let (new, _) = unquote(&rest[old.len() + 1..])?;
+        return Some(strip_header_prefixes(old, &new));
+    }
+    if rest.len() % 2 == 1 && rest.is_char_boundary(rest.len() / 2) {
+        let (old, new) = (&rest[..rest.len() / 2], &rest[rest.len() / 2 + 1..]);
+        let (old_path, new_path) = strip_header_prefixes(old, new);
+        if old_path == new_path {
+            return Some((old_path, new_path));
+        }
+    }
+    let (old, new) = rest.split_once(" b/")?;
+    Some(strip_header_prefixes(old, &format!("b/{new}")))
+}
+
+/// Drop git's `a/` and `b/` prefixes when both sides carry them, so
+/// `--no-prefix` diffs keep their paths intact.
+fn strip_header_prefixes(old: &str, new: &str) -> (PathBuf, PathBuf) {
+    match (old.strip_prefix("a/"), new.strip_prefix("b/")) {
+        (Some(old), Some(new)) => (PathBuf::from(old), PathBuf::from(new)),
+        _ => (PathBuf::from(old), PathBuf::from(new)),
+    }
+}
+
+/// Path from a `---`/`+++` line: optionally quoted, optionally followed by a
+/// tab and a timestamp, with `/dev/null` meaning the side does not exist.
+fn marker_path(raw: &str, prefix: &str) -> Option<PathBuf> {
+    let path = match unquote(raw) {
+        Some((path, _)) => path,
+        None => raw.split('\t').next().unwrap_or(raw).trim_end().to_string(),
+    };
+    if path == "/dev/null" {
+        return None;
+    }
+    Some(PathBuf::from(path.strip_prefix(prefix).unwrap_or(&path)))
+}
+
+/// Path from a `rename from`/`copy to` style header, which git quotes the
+/// same way as the `diff --git` line but never prefixes.
+fn header_path(raw: &str) -> PathBuf {
+    match unquote(raw) {
+        Some((path, _)) => PathBuf::from(path),
+        None => PathBuf::from(raw),
+    }
+}
+
+/// Decode a C-style quoted string as git writes unusual paths, returning the
+/// text and whatever follows the closing quote.
+fn unquote(s: &str) -> Option<(String, &str)> {
+    let body = s.strip_prefix('"')?;
+    let mut bytes = Vec::new();
+    let mut iter = body.char_indices();
+    while let Some((idx, c)) = iter.next() {
+        match c {
+            '"' => {
+                let decoded = String::from_utf8_lossy(&bytes).into_owned();
+                return Some((decoded, &body[idx + 1..]));
+            }
+            '\\' => {
+                let (_, escaped) = iter.next()?;
+                let byte = match escaped {
+                    'n' => b'\n',
+                    't' => b'\t',
+                    'r' => b'\r',
+                    'a' => 0x07,
+                    'b' => 0x08,
+                    'f' => 0x0c,
+                    'v' => 0x0b,
+                    '0'..='7' => {
+                        let mut value = escaped.to_digit(8)?;
+                        for _ in 0..2 {
+                            let (_, digit) = iter.next()?;
+                            value = value * 8 + digit.to_digit(8)?;
+                        }
+                        u8::try_from(value).ok()?
+                    }
+                    other => {
+                        let mut buf = [0u8; 4];
+                        bytes.extend_from_slice(other.encode_utf8(&mut buf).as_bytes());
+                        continue;
+                    }
+                };
+                bytes.push(byte);
+            }
+            other => {
+                let mut buf = [0u8; 4];
+                bytes.extend_from_slice(other.encode_utf8(&mut buf).as_bytes());
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn paths(patch: &FilePatch) -> (Option<&str>, Option<&str>) {
+        (
+            patch.old_path.as_deref().and_then(Path::to_str),
+            patch.new_path.as_deref().and_then(Path::to_str),
+        )
+    }
+
+    #[test]
+    fn splits_git_diff_with_rename_add_delete_and_binary() {
+        let text = "\
+diff --git a/src/old name.rs b/src/new name.rs
+similarity index 90%
+rename from src/old name.rs
+rename to src/new name.rs
+index 111..222 100644
+--- a/src/old name.rs
++++ b/src/new name.rs
+@@ -1,2 +1,2 @@
+ keep
+-old
++new
+diff --git a/added.txt b/added.txt
+new file mode 100644
+--- /dev/null
++++ b/added.txt
+@@ -0,0 +1 @@
++hello
+diff --git a/gone.txt b/gone.txt
+deleted file mode 100644
+--- a/gone.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-bye
+diff --git a/logo.png b/logo.png
+index 333..444 100644
+GIT binary patch
+literal 4
+LcmZ?wbhEHbO#lD@
+";
+        let files = split_patch(text);
+
+        assert_eq!(files.len(), 4);
+        assert_eq!(
+            paths(&files[0]),
+            (Some("src/old name.rs"), Some("src/new name.rs"))
+        );
+        assert_eq!(files[0].status, FileStatus::Renamed);
+        assert_eq!(paths(&files[1]), (None, Some("added.txt")));
+        assert_eq!(files[1].status, FileStatus::Added);
+        assert_eq!(paths(&files[2]), (Some("gone.txt"), None));
+        assert_eq!(files[2].status, FileStatus::Deleted);
+        assert!(files[3].is_binary);
+        assert_eq!(paths(&files[3]), (Some("logo.png"), Some("logo.png")));
+
+        let parsed = parse_file_patches(files, &SyntaxHighlighter::default()).unwrap();
+        assert_eq!(parsed[0].hunks[0].lines.len(), 3);
+    }
+
+    #[test]
+    fn skips_mail_text_and_keeps_hunk_lines_that_look_like_headers() {
+        let text = "\
+From 1234 Mon Sep 17 00:00:00 2001
+From: Alice <alice@example.com>
+Subject: [PATCH] Tweak config
+
+Body text.
+---
+ a.txt | 3 ++-
+ 1 file changed, 2 insertions(+), 1 deletion(-)
+
+diff --git a/a.txt b/a.txt
+--- a/a.txt
++++ b/a.txt
+@@ -1,3 +1,4 @@
+ one
+
+--- old comment
++++ new comment
++added
+--
+2.43.0
+";
+        let files = split_patch(text);
+
+        assert_eq!(files.len(), 1);
+        let parsed = parse_file_patches(files, &SyntaxHighlighter::default()).unwrap();
+        let lines: Vec<_> = parsed[0].hunks[0]
+            .lines
+            .iter()
+            .map(|l| (l.origin, l.content.as_str()))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                (LineOrigin::Context, "one"),
+                (LineOrigin::Context, ""),
+                (LineOrigin::Deletion, "-- old comment"),
+                (LineOrigin::Addition, "++ new comment"),
+                (LineOrigin::Addition, "added"),
+            ]
+        );
+    }
+
+    #[test]
+    fn splits_plain_unified_diff_and_quoted_paths() {
+        let text = "\
+--- a/plain.txt\t2024-05-01 10:00:00.000000000 +0200
++++ b/plain.txt\t2024-05-01 10:05:00.000000000 +0200
+@@ -1 +1 @@
+-x
++y
+diff --git \"a/\\346\\227\\245.txt\" \"b/\\346\\227\\245.txt\"
+--- \"a/\\346\\227\\245.txt\"
++++ \"b/\\346\\227\\245.txt\"
+@@ -1 +1 @@
+-a
++b
+";
+        let files = split_patch(text);
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(paths(&files[0]), (Some("plain.txt"), Some("plain.txt")));
+        assert_eq!(paths(&files[1]), (Some("日.txt"), Some("日.txt")));
+    }
+
+    #[test]
+    fn rejects_text_without_any_file_sections() {
+        assert!(matches!(
+            PatchBackend::new("empty", "just words\n", PathBuf::from(".")),
+            Err(TuicrError::NoChanges)
+        ));
+    }
+
+    #[test]
+    fn keys_sessions_by_patch_content() {
+        let text = "--- a/x\n+++ b/x\n@@ -1 +1 @@\n-a\n+b\n";
+        let first = PatchBackend::new("one.patch", text, PathBuf::from(".")).unwrap();
+        let again = PatchBackend::new("two.patch", text, PathBuf::from(".")).unwrap();
+        let other =
+            PatchBackend::new("one.patch", &text.replace("+b", "+c"), PathBuf::from(".")).unwrap();
+
+        assert!(first.info().head_commit.starts_with(PATCH_SESSION_PREFIX));
+        assert_eq!(first.info().head_commit, again.info().head_commit);
+        assert_ne!(first.info().head_commit, other.info().head_commit);
+        assert_eq!(first.info().branch_name, again.info().branch_name);
+        assert_eq!(first.display_ref(), Some("one.patch"));
+    }
+
+    fn numbered(range: std::ops::RangeInclusive<u32>) -> String {
+        range.map(|n| format!("line {n}\n")).collect()
+    }
+
+    const TWO_HUNKS: &str = "\
+--- a/f.txt
++++ b/f.txt
+@@ -2,1 +2,2 @@
+ line 2
++inserted
+@@ -8,1 +9,1 @@
+-line 8
++changed
+";
+
+    #[test]
+    fn expands_context_from_a_checkout_that_matches_the_old_side() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::write(temp.path().join("f.txt"), numbered(1..=10)).unwrap();
+        let backend = PatchBackend::new("p", TWO_HUNKS, temp.path().to_path_buf()).unwrap();
+        backend
+            .get_working_tree_diff(&SyntaxHighlighter::default())
+            .unwrap();
+        let path = Path::new("f.txt");
+
+        // New lines 4..=8 sit between the hunks, one line past their old position.
+        let lines = backend
+            .fetch_context_lines(path, FileStatus::Modified, None, 4, 8)
+            .unwrap();
+        let contents: Vec<_> = lines.iter().map(|l| l.content.as_str()).collect();
+        assert_eq!(
+            contents,
+            vec!["line 3", "line 4", "line 5", "line 6", "line 7"]
+        );
+        assert_eq!(lines[0].old_lineno, Some(3));
+        assert_eq!(lines[0].new_lineno, Some(4));
+        assert_eq!(
+            backend
+                .file_line_count(path, FileStatus::Modified, None)
+                .unwrap(),
+            11
+        );
+    }
+
+    #[test]
+    fn expands_context_from_a_checkout_that_already_has_the_patch() {
+        let temp = tempfile::tempdir().unwrap();
+        let applied = numbered(1..=10)
+            .replace("line 2\n", "line 2\ninserted\n")
+            .replace("line 8\n", "changed\n");
+        fs::write(temp.path().join("f.txt"), applied).unwrap();
+        let backend = PatchBackend::new("p", TWO_HUNKS, temp.path().to_path_buf()).unwrap();
+        backend
+            .get_working_tree_diff(&SyntaxHighlighter::default())
+            .unwrap();
+
+        let lines = backend
+            .fetch_context_lines(Path::new("f.txt"), FileStatus::Modified, None, 10, 11)
+            .unwrap();
+        let contents: Vec<_> = lines.iter().map(|l| l.content.as_str()).collect();
+        assert_eq!(contents, vec!["line 9", "line 10"]);
+    }
+
+    #[test]
+    fn keys_a_series_file_touched_twice_by_patch_and_keeps_its_path() {
+        let mail = |sha: &str, n: u32, from: &str, to: &str| {
+            format!(
+                "From {sha} Mon Sep 17 00:00:00 2001\n\
+                 From: A <a@example.com>\n\
+                 Subject: [PATCH {n}/2] step {n}\n\n\
+                 ---\n\
+                 diff --git a/f.rs b/f.rs\n\
+                 --- a/f.rs\n\
+                 +++ b/f.rs\n\
+                 @@ -2 +2 @@\n\
+                 -{from}\n\
+                 +{to}\n\n"
+            )
+        };
+        let series = format!(
+            "{}{}",
+            mail(&"1".repeat(40), 1, "line 2", "first"),
+            mail(&"2".repeat(40), 2, "first", "second")
+        );
+        let temp = tempfile::tempdir().unwrap();
+        fs::write(
+            temp.path().join("f.rs"),
+            numbered(1..=4).replace("line 2", "first"),
+        )
+        .unwrap();
+        let backend = PatchBackend::new("s.mbox", &series, temp.path().to_path_buf()).unwrap();
+
+        let files = backend
+            .get_working_tree_diff(&SyntaxHighlighter::default())
+            .unwrap();
+
+        assert_eq!(files[0].display_path(), Path::new("f.rs"));
+        assert_eq!(files[1].display_path(), Path::new("f.rs (2222222)"));
+        assert_eq!(files[1].old_path.as_deref(), Some(Path::new("f.rs")));
+        assert_eq!(files[1].new_path.as_deref(), Some(Path::new("f.rs")));
+        let lines = backend
+            .fetch_context_lines(files[1].display_path(), FileStatus::Modified, None, 3, 4)
+            .unwrap();
+        let contents: Vec<_> = lines.iter().map(|l| l.content.as_str()).collect();
+        assert_eq!(contents, vec!["line 3", "line 4"]);
+    }
+
+    #[test]
+    fn refuses_context_when_the_checkout_does_not_match() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::write(temp.path().join("f.txt"), "unrelated\n").unwrap();
+        let backend = PatchBackend::new("p", TWO_HUNKS, temp.path().to_path_buf()).unwrap();
+        backend
+            .get_working_tree_diff(&SyntaxHighlighter::default())
+            .unwrap();
+
+        assert!(matches!(
+            backend.fetch_context_lines(Path::new("f.txt"), FileStatus::Modified, None, 4, 8),
+            Err(TuicrError::UnsupportedOperation(_))
+        ));
+    }
+}
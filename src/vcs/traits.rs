@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use std::borrow::Cow;
+use std::ops::RangeInclusive;
 use std::path::{Path, PathBuf};
 
 use crate::error::Result;
@@ -13,6 +14,8 @@ pub enum VcsType {
     Mercurial,
     Jujutsu,
     File,
+    /// A raw patch reviewed with `--patch`; see [`PatchBackend`](super::PatchBackend).
+    Patch,
 }
 
 impl std::fmt::Display for VcsType {
@@ -22,6 +25,7 @@ impl std::fmt::Display for VcsType {
             VcsType::Mercurial => write!(f, "hg"),
             VcsType::Jujutsu => write!(f, "jj"),
             VcsType::File => write!(f, "file"),
+            VcsType::Patch => write!(f, "patch"),
         }
     }
 }
@@ -156,11 +160,96 @@ pub enum ChangeKind {
     Unstaged,
 }
 
+/// Which local comparison a hunk-level change was taken from.
+///
+/// This decides what a synthesized patch is applied against: an unstaged hunk
+/// was diffed index → worktree, a staged hunk HEAD → index, and a combined
+/// hunk HEAD → worktree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HunkChangeBase {
+    Unstaged,
+    Staged,
+    StagedAndUnstaged,
+}
+
+impl HunkChangeBase {
+    /// Whether `operation` means anything for hunks from this comparison.
+    /// Staged hunks are already in the index, unstaged hunks have nothing to
+    /// unstage, and discarding a staged hunk would silently drop the worktree
+    /// copy too, so it has to be unstaged first.
+    pub fn supports(self, operation: HunkOperation) -> bool {
+        !matches!(
+            (self, operation),
+            (Self::Staged, HunkOperation::Stage | HunkOperation::Discard)
+                | (Self::Unstaged, HunkOperation::Unstage)
+        )
+    }
+}
+
+/// A hunk-level change to the index or working tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HunkOperation {
+    /// Copy the selected changes into the index.
+    Stage,
+    /// Revert the selected changes in the index, keeping the worktree.
+    Unstage,
+    /// Revert the selected changes in the working tree.
+    Discard,
+}
+
+/// One hunk of a file taking part in a hunk-level change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HunkSelection {
+    /// Index into `DiffFile::hunks`.
+    pub hunk_idx: usize,
+    /// Indices into the hunk's `lines` to change, or `None` for the whole
+    /// hunk. Unselected changed lines are left as they are.
+    pub lines: Option<RangeInclusive<usize>>,
+}
+
+/// Request for [`VcsBackend::apply_hunk_change`].
+#[derive(Debug, Clone, Copy)]
+pub struct HunkChange<'a> {
+    /// The file as currently displayed. Backends re-diff the path and refuse
+    /// to apply when the fresh hunks no longer match these.
+    pub file: &'a DiffFile,
+    pub base: HunkChangeBase,
+    pub operation: HunkOperation,
+    pub selections: &'a [HunkSelection],
+}
+
+/// Last change to one line of a file, as reported by [`VcsBackend::blame`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameLine {
+    /// Commit that last touched the line, or `None` when the line only
+    /// exists in the working copy.
+    pub commit_id: Option<String>,
+    pub author: String,
+    pub time: Option<DateTime<Utc>>,
+}
+
+impl BlameLine {
+    /// Blame entry for a line that has not been committed yet.
+    pub fn uncommitted() -> Self {
+        Self {
+            commit_id: None,
+            author: String::new(),
+            time: None,
+        }
+    }
+}
+
 /// Trait for VCS backend implementations
 pub trait VcsBackend: Send {
     /// Get repository information
     fn info(&self) -> &VcsInfo;
 
+    /// What the header shows in place of `info().branch_name`, for backends
+    /// whose branch is only a session key. `None` shows the branch.
+    fn display_ref(&self) -> Option<&str> {
+        None
+    }
+
     /// Non-fatal notices that should be shown after startup.
     fn startup_warnings(&self) -> Vec<String> {
         Vec::new()
@@ -274,18 +363,50 @@ pub trait VcsBackend: Send {
         ))
     }
 
+    /// Attribute every line of a file to the commit that last changed it.
+    /// When `ref_commit` is `Some`, blames the file as of that commit;
+    /// otherwise blames the working copy. Entry `i` describes line `i + 1`.
+    fn blame(&self, _file_path: &Path, _ref_commit: Option<&str>) -> Result<Vec<BlameLine>> {
+        Err(crate::error::TuicrError::UnsupportedOperation(
+            "Blame not supported for this VCS".into(),
+        ))
+    }
+
     /// Stage a file (add to index).
     fn stage_file(&self, _path: &Path) -> Result<()> {
         Err(crate::error::TuicrError::UnsupportedOperation(
             "Staging not supported for this VCS".into(),
         ))
     }
+
+    /// Stage, unstage, or discard individual hunks (or lines within them) of
+    /// one file by applying a synthesized patch to the index or worktree.
+    fn apply_hunk_change(&self, _change: &HunkChange<'_>) -> Result<()> {
+        Err(crate::error::TuicrError::UnsupportedOperation(
+            "Hunk staging not supported for this VCS".into(),
+        ))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn hunk_change_base_supports_only_meaningful_operations() {
+        assert!(HunkChangeBase::Unstaged.supports(HunkOperation::Stage));
+        assert!(HunkChangeBase::Unstaged.supports(HunkOperation::Discard));
+        assert!(!HunkChangeBase::Unstaged.supports(HunkOperation::Unstage));
+
+        assert!(HunkChangeBase::Staged.supports(HunkOperation::Unstage));
+        assert!(!HunkChangeBase::Staged.supports(HunkOperation::Stage));
+        assert!(!HunkChangeBase::Staged.supports(HunkOperation::Discard));
+
+        assert!(HunkChangeBase::StagedAndUnstaged.supports(HunkOperation::Stage));
+        assert!(HunkChangeBase::StagedAndUnstaged.supports(HunkOperation::Unstage));
+        assert!(HunkChangeBase::StagedAndUnstaged.supports(HunkOperation::Discard));
+    }
+
     #[test]
     fn vcs_type_display_git() {
         assert_eq!(format!("{}", VcsType::Git), "git");